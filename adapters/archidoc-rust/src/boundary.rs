@@ -0,0 +1,182 @@
+//! Container boundary checks — reaching into another container's internals.
+//!
+//! A container's components are its internals: the rest of the system should
+//! depend on the container itself, whose entry module is its facade. This
+//! pass reports declared relationships and `use crate::...` imports from an
+//! element inside one container to a component of a different container.
+//!
+//! Imports are read from the element's own source files (see
+//! [`crate::metrics`]) and resolved to the longest matching module path.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+use archidoc_types::{ElementKind, ModuleDoc};
+
+use crate::metrics;
+
+/// A dependency from an element to a component of another container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundaryViolation {
+    /// Module path of the depending element.
+    pub from: String,
+    /// Module path of the component depended on.
+    pub to: String,
+    /// Container the target component belongs to.
+    pub container: String,
+    /// How the dependency was found: `declared relationship` or the import.
+    pub via: String,
+}
+
+/// Find dependencies that bypass another container's facade.
+///
+/// `allow` lists exceptions, either `from -> to` for one dependency or a
+/// bare `to` for a component any container may use directly.
+pub fn find_violations(docs: &[ModuleDoc], allow: &[String]) -> Vec<BoundaryViolation> {
+    let entry_files: BTreeSet<PathBuf> = docs.iter().map(|d| PathBuf::from(&d.source_file)).collect();
+    let mut violations = Vec::new();
+
+    for doc in docs {
+        let Some(own_container) = container_of(doc) else {
+            continue;
+        };
+
+        let mut targets: Vec<(String, String)> = doc
+            .relationships
+            .iter()
+            .map(|rel| (rel.target.clone(), "declared relationship".to_string()))
+            .collect();
+        for path in imported_paths(doc, &entry_files) {
+            if let Some(target) = resolve_module(docs, &path) {
+                targets.push((target, format!("`use crate::{}`", path.join("::"))));
+            }
+        }
+
+        let mut seen = BTreeSet::new();
+        for (target, via) in targets {
+            let Some(target_doc) = docs.iter().find(|d| d.module_path == target) else {
+                continue;
+            };
+            let Some(container) = &target_doc.parent_container else {
+                continue;
+            };
+            if target_doc.c4_level != ElementKind::Component
+                || container == own_container
+                || is_allowed(allow, &doc.module_path, &target)
+                || !seen.insert(target.clone())
+            {
+                continue;
+            }
+            violations.push(BoundaryViolation {
+                from: doc.module_path.clone(),
+                to: target,
+                container: container.clone(),
+                via,
+            });
+        }
+    }
+
+    violations
+}
+
+/// The container an element belongs to: itself for a container, its parent
+/// for a component.
+fn container_of(doc: &ModuleDoc) -> Option<&str> {
+    if doc.c4_level.is_container() {
+        Some(&doc.module_path)
+    } else {
+        doc.parent_container.as_deref()
+    }
+}
+
+fn is_allowed(allow: &[String], from: &str, to: &str) -> bool {
+    allow.iter().any(|entry| match entry.split_once("->") {
+        Some((a, b)) => a.trim() == from && b.trim() == to,
+        None => entry.trim() == to,
+    })
+}
+
+/// Longest module path that prefixes an imported `crate::` path.
+fn resolve_module(docs: &[ModuleDoc], path: &[String]) -> Option<String> {
+    (1..=path.len())
+        .rev()
+        .map(|len| path[..len].join("."))
+        .find(|candidate| docs.iter().any(|d| &d.module_path == candidate))
+}
+
+/// `crate::`-rooted paths imported by the element's own source files, without
+/// the leading `crate`.
+fn imported_paths(doc: &ModuleDoc, entry_files: &BTreeSet<PathBuf>) -> Vec<Vec<String>> {
+    let mut paths = Vec::new();
+    for file in metrics::module_sources(doc, entry_files) {
+        let Some(parsed) = fs::read_to_string(&file).ok().and_then(|s| syn::parse_file(&s).ok()) else {
+            continue;
+        };
+        collect_use_paths(&parsed.items, &mut paths);
+    }
+    paths
+        .into_iter()
+        .filter(|p| p.first().is_some_and(|s| s == "crate"))
+        .map(|p| p[1..].to_vec())
+        .collect()
+}
+
+fn collect_use_paths(items: &[syn::Item], out: &mut Vec<Vec<String>>) {
+    fn walk(tree: &syn::UseTree, prefix: &mut Vec<String>, out: &mut Vec<Vec<String>>) {
+        match tree {
+            syn::UseTree::Path(p) => {
+                prefix.push(p.ident.to_string());
+                walk(&p.tree, prefix, out);
+                prefix.pop();
+            }
+            syn::UseTree::Name(n) => out.push([prefix.as_slice(), &[n.ident.to_string()]].concat()),
+            syn::UseTree::Rename(r) => out.push([prefix.as_slice(), &[r.ident.to_string()]].concat()),
+            syn::UseTree::Glob(_) => out.push(prefix.clone()),
+            syn::UseTree::Group(g) => g.items.iter().for_each(|t| walk(t, prefix, out)),
+        }
+    }
+
+    for item in items {
+        match item {
+            syn::Item::Use(u) => walk(&u.tree, &mut Vec::new(), out),
+            syn::Item::Mod(m) => {
+                if let Some((_, nested)) = &m.content {
+                    collect_use_paths(nested, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn use_paths_flatten_groups_and_renames() {
+        let file = syn::parse_file("use crate::bus::{calc::Sma, store as s};\nuse std::fmt;\n").unwrap();
+        let mut paths = Vec::new();
+
+        collect_use_paths(&file.items, &mut paths);
+
+        assert_eq!(
+            paths,
+            vec![
+                vec!["crate", "bus", "calc", "Sma"],
+                vec!["crate", "bus", "store"],
+                vec!["std", "fmt"],
+            ]
+        );
+    }
+
+    #[test]
+    fn allowlist_accepts_pairs_and_open_targets() {
+        let allow = vec!["api.handlers -> bus.calc".to_string(), "bus.events".to_string()];
+
+        assert!(is_allowed(&allow, "api.handlers", "bus.calc"));
+        assert!(!is_allowed(&allow, "api.auth", "bus.calc"));
+        assert!(is_allowed(&allow, "api.auth", "bus.events"));
+    }
+}
@@ -254,7 +254,7 @@ pub fn detect_orphans_cmd(root: &Path) -> Result<Vec<String>, String> {
         .lines()
         .map(|line| line.trim())
         .filter(|line| !line.is_empty())
-        .map(|line| crate_path_to_module(line))
+        .map(crate_path_to_module)
         .collect();
 
     Ok(orphans)
@@ -314,23 +314,22 @@ digraph {
 
     #[test]
     fn test_validate_relationships_no_import() {
-        use archidoc_types::{C4Level, PatternStatus, Relationship};
+        use archidoc_types::{ElementKind, Relationship};
 
         let docs = vec![ModuleDoc {
             module_path: "core".to_string(),
             content: "test".to_string(),
             source_file: "test.rs".to_string(),
-            c4_level: C4Level::Component,
+            c4_level: ElementKind::Component,
             pattern: "--".to_string(),
-            pattern_status: PatternStatus::Planned,
             description: "test".to_string(),
-            parent_container: None,
             relationships: vec![Relationship {
                 target: "utils".to_string(),
                 label: "test".to_string(),
                 protocol: "Rust".to_string(),
+                hidden: false,
             }],
-            files: vec![],
+            ..Default::default()
         }];
 
         let graph = ImportGraph::default(); // Empty graph
@@ -344,19 +343,16 @@ digraph {
 
     #[test]
     fn test_detect_orphans() {
-        use archidoc_types::{C4Level, PatternStatus};
+        use archidoc_types::ElementKind;
 
         let docs = vec![ModuleDoc {
             module_path: "core".to_string(),
             content: "test".to_string(),
             source_file: "test.rs".to_string(),
-            c4_level: C4Level::Component,
+            c4_level: ElementKind::Component,
             pattern: "--".to_string(),
-            pattern_status: PatternStatus::Planned,
             description: "test".to_string(),
-            parent_container: None,
-            relationships: vec![],
-            files: vec![],
+            ..Default::default()
         }];
 
         let mut graph = ImportGraph::default();
@@ -0,0 +1,238 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use archidoc_types::{HealthStatus, HeuristicConfig, ModuleDoc};
+
+use crate::pattern_heuristic;
+use crate::promote::VERIFIABLE_PATTERNS;
+
+/// A file-table row proposed for a `.rs` file its module does not catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogRow {
+    pub element: String,
+    /// The annotated file whose table gets the row.
+    pub source_file: PathBuf,
+    pub filename: String,
+    /// The first pattern whose structural heuristic matches the file, or `--`.
+    pub pattern: String,
+}
+
+impl CatalogRow {
+    /// The row as written in a file table: purpose to do, health `planned`.
+    pub fn to_markdown(&self) -> String {
+        format!("| `{}` | {} | [TODO] | planned |", self.filename, self.pattern)
+    }
+}
+
+/// Rows for the `.rs` files that orphan detection reports: files in a
+/// cataloged module's directory, other than `mod.rs`, `lib.rs`, and
+/// `main.rs`, that its table does not list. Modules annotated outside a
+/// Rust file (sidecars) are skipped, since there is no table to extend.
+pub fn uncataloged_files(docs: &[ModuleDoc], thresholds: &HeuristicConfig) -> Vec<CatalogRow> {
+    let mut rows = Vec::new();
+    for doc in docs {
+        let is_rust = Path::new(&doc.source_file).extension().is_some_and(|ext| ext == "rs");
+        if doc.files.is_empty() || !is_rust {
+            continue;
+        }
+        let Some(dir) = doc.catalog_dir() else {
+            continue;
+        };
+        let cataloged: HashSet<&str> = doc.files.iter().map(|f| f.name.as_str()).collect();
+        for (filename, source) in crate::walker::read_rs_sources(&dir) {
+            if matches!(filename.as_str(), "mod.rs" | "lib.rs" | "main.rs") || cataloged.contains(filename.as_str()) {
+                continue;
+            }
+            rows.push(CatalogRow {
+                element: doc.module_path.clone(),
+                source_file: PathBuf::from(&doc.source_file),
+                filename,
+                pattern: infer_pattern(&source, thresholds).unwrap_or("--").to_string(),
+            });
+        }
+    }
+    rows
+}
+
+/// The first pattern, in [`VERIFIABLE_PATTERNS`] order, with structural
+/// evidence in `source`.
+pub fn infer_pattern(source: &str, thresholds: &HeuristicConfig) -> Option<&'static str> {
+    VERIFIABLE_PATTERNS
+        .iter()
+        .copied()
+        .find(|pattern| pattern_heuristic::check_pattern_with(pattern, source, thresholds))
+}
+
+/// Find the uncataloged files of every annotated module under `root`; with
+/// `write`, append their rows to the file tables in place. Returns the rows.
+pub fn catalog_tree(root: &Path, thresholds: &HeuristicConfig, write: bool) -> Result<Vec<CatalogRow>, String> {
+    let docs = crate::walker::extract(root).docs;
+    let rows = uncataloged_files(&docs, thresholds);
+    if !write {
+        return Ok(rows);
+    }
+
+    let mut by_file: BTreeMap<&Path, Vec<&CatalogRow>> = BTreeMap::new();
+    for row in &rows {
+        by_file.entry(&row.source_file).or_default().push(row);
+    }
+    for (path, file_rows) in by_file {
+        let source = fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        let updated = add_rows(&source, &file_rows)
+            .ok_or_else(|| format!("no file table in the annotation of {}", path.display()))?;
+        fs::write(path, updated).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    }
+    Ok(rows)
+}
+
+/// `source` with `rows` appended to the file table in its leading `//!`
+/// block, or `None` when the block has no file table.
+pub fn add_rows(source: &str, rows: &[&CatalogRow]) -> Option<String> {
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let (header, table_end) = file_table(&lines)?;
+
+    let indent = &lines[header][..lines[header].len() - lines[header].trim_start().len()];
+    let mut out: String = lines[..table_end].concat();
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    for row in rows {
+        out.push_str(&format!("{}//! {}\n", indent, row.to_markdown()));
+    }
+    out.push_str(&lines[table_end..].concat());
+    Some(out)
+}
+
+/// `source` with the purpose and/or health of `file`'s row in the file
+/// table of its leading `//!` block replaced, or `None` when the table has
+/// no row for `file`. A replaced purpose drops the row's continuation rows;
+/// pipes outside code spans are escaped.
+pub fn update_row(source: &str, file: &str, purpose: Option<&str>, health: Option<HealthStatus>) -> Option<String> {
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let (header, table_end) = file_table(&lines)?;
+    let row = (header + 2..table_end).find(|&i| {
+        let cells = crate::fmt::split_raw(text(lines[i]).unwrap_or(""));
+        cells.first().is_some_and(|c| c.trim_matches('`') == file)
+    })?;
+
+    let mut cells = crate::fmt::split_raw(text(lines[row]).unwrap_or(""));
+    cells.resize(cells.len().max(4), String::new());
+    let mut row_end = row + 1;
+    if let Some(purpose) = purpose {
+        cells[2] = escape_pipes(purpose);
+        row_end += lines[row + 1..table_end]
+            .iter()
+            .take_while(|line| crate::fmt::split_raw(text(line).unwrap_or("")).first().is_none_or(|c| c.is_empty()))
+            .count();
+    }
+    if let Some(health) = health {
+        cells[3] = health.to_string();
+    }
+
+    let indent = &lines[row][..lines[row].len() - lines[row].trim_start().len()];
+    let mut out: String = lines[..row].concat();
+    out.push_str(&format!("{}//! | {} |\n", indent, cells.join(" | ")));
+    out.push_str(&lines[row_end..].concat());
+    Some(out)
+}
+
+/// The line range of the file table in the leading `//!` block of `lines`:
+/// its header row and the first line after its last row.
+fn file_table(lines: &[&str]) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|line| text(line).is_some())?;
+    let end = start + lines[start..].iter().take_while(|line| text(line).is_some()).count();
+
+    let header = (start..end).find(|&i| {
+        let cell = text(lines[i]).unwrap_or("").to_lowercase();
+        cell.starts_with('|') && cell.contains("file") && cell.contains("pattern")
+    })?;
+    let table_end = header + lines[header..end].iter().take_while(|line| text(line).is_some_and(|t| t.starts_with('|'))).count();
+    Some((header, table_end))
+}
+
+/// `text` with `|` escaped as `\|`, except inside code spans.
+fn escape_pipes(text: &str) -> String {
+    let mut out = String::new();
+    let mut in_code = false;
+    for c in text.chars() {
+        match c {
+            '`' => in_code = !in_code,
+            '|' if !in_code => out.push('\\'),
+            _ => {}
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// The text of a `//!` line, `None` for any other line.
+fn text(line: &str) -> Option<&str> {
+    line.trim().strip_prefix("//!").map(str::trim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANNOTATION: &str = "//! @c4 component\n//!\n//! # Bus\n//!\n//! Routes events.\n//!\n//! | File | Pattern | Purpose | Health |\n//! |------|---------|---------|--------|\n//! | `calc.rs` | -- | Math | stable |\n//!\n//! More prose.\n\npub mod calc;\n";
+
+    #[test]
+    fn new_files_get_a_planned_row_with_an_inferred_pattern() {
+        let tmp = tempfile::tempdir().unwrap();
+        let bus = tmp.path().join("bus");
+        fs::create_dir_all(&bus).unwrap();
+        fs::write(bus.join("mod.rs"), ANNOTATION).unwrap();
+        fs::write(bus.join("calc.rs"), "pub fn add() {}\n").unwrap();
+        fs::write(bus.join("lanes.rs"), "use std::sync::mpsc;\npub fn lanes() -> mpsc::Sender<u8> { todo!() }\n").unwrap();
+        fs::write(bus.join("util.rs"), "pub fn clamp() {}\n").unwrap();
+
+        let rows = catalog_tree(tmp.path(), &HeuristicConfig::default(), false).unwrap();
+
+        let summary: Vec<(&str, &str)> = rows.iter().map(|r| (r.filename.as_str(), r.pattern.as_str())).collect();
+        assert_eq!(summary, vec![("lanes.rs", "Observer"), ("util.rs", "--")]);
+        assert_eq!(fs::read_to_string(bus.join("mod.rs")).unwrap(), ANNOTATION);
+
+        catalog_tree(tmp.path(), &HeuristicConfig::default(), true).unwrap();
+        let updated = fs::read_to_string(bus.join("mod.rs")).unwrap();
+        assert!(updated.contains(
+            "//! | `calc.rs` | -- | Math | stable |\n//! | `lanes.rs` | Observer | [TODO] | planned |\n//! | `util.rs` | -- | [TODO] | planned |\n//!\n//! More prose.\n"
+        ));
+        assert!(catalog_tree(tmp.path(), &HeuristicConfig::default(), false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn edited_purpose_and_health_replace_the_row() {
+        let source = "//! | File | Pattern | Purpose | Health |\n//! |------|---------|---------|--------|\n//! | `calc.rs` | -- | Math | stable |\n//! | `lanes.rs` | Observer | Event lanes that are | planned |\n//! |  | | wrapped | |\n//!\n\npub mod calc;\n";
+
+        let updated = update_row(source, "lanes.rs", Some("Lanes for a | b `x|y`"), Some(HealthStatus::Active)).unwrap();
+        assert_eq!(
+            updated,
+            "//! | File | Pattern | Purpose | Health |\n//! |------|---------|---------|--------|\n//! | `calc.rs` | -- | Math | stable |\n//! | `lanes.rs` | Observer | Lanes for a \\| b `x|y` | active |\n//!\n\npub mod calc;\n"
+        );
+        assert_eq!(
+            crate::parser::extract_file_table(&updated.replace("//! ", "").replace("//!", ""))[1].purpose,
+            "Lanes for a | b `x|y`"
+        );
+        assert!(update_row(source, "calc.rs", None, Some(HealthStatus::Planned))
+            .unwrap()
+            .contains("//! | `calc.rs` | -- | Math | planned |\n//! | `lanes.rs` | Observer | Event lanes that are | planned |\n//! |  | | wrapped | |\n"));
+        assert_eq!(update_row(source, "util.rs", None, Some(HealthStatus::Planned)), None);
+    }
+
+    #[test]
+    fn annotations_without_a_file_table_are_left_alone() {
+        let row = CatalogRow {
+            element: "bus".to_string(),
+            source_file: PathBuf::from("bus/mod.rs"),
+            filename: "lanes.rs".to_string(),
+            pattern: "--".to_string(),
+        };
+        assert_eq!(add_rows("//! @c4 component\n//! # Bus\n", &[&row]), None);
+        assert_eq!(update_row("//! @c4 component\n//! # Bus\n", "lanes.rs", None, None), None);
+        assert_eq!(
+            add_rows("//! | File | Pattern | Purpose | Health |\n//! |---|---|---|---|", &[&row]).unwrap(),
+            "//! | File | Pattern | Purpose | Health |\n//! |---|---|---|---|\n//! | `lanes.rs` | -- | [TODO] | planned |\n"
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Feature-gate analysis — marks modules declared behind `#[cfg(...)]`.
+//!
+//! A module is optional when its `mod` declaration in the parent file
+//! carries a `cfg` attribute, e.g. `#[cfg(feature = "gpu")] mod gpu;`.
+//! Nested modules inherit the gate of their nearest gated ancestor.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use archidoc_types::ModuleDoc;
+use quote::ToTokens;
+
+/// Set [`ModuleDoc::condition`] on every module whose declaration is gated.
+///
+/// Modules that already carry a condition are left alone. Crate roots have
+/// no declaring file and are never gated.
+pub fn apply_cfg_gates(docs: &mut [ModuleDoc]) {
+    for doc in docs.iter_mut() {
+        if doc.condition.is_some() || doc.module_path.starts_with('_') {
+            continue;
+        }
+        doc.condition = declaration_condition(Path::new(&doc.source_file));
+    }
+
+    let gated: Vec<(String, String)> = docs
+        .iter()
+        .filter_map(|d| d.condition.clone().map(|c| (format!("{}.", d.module_path), c)))
+        .collect();
+
+    for doc in docs.iter_mut().filter(|d| d.condition.is_none()) {
+        doc.condition = gated
+            .iter()
+            .filter(|(prefix, _)| doc.module_path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, condition)| condition.clone());
+    }
+}
+
+/// Find the `cfg` condition on a module's `mod` declaration.
+///
+/// `source` is the module's entry file: `foo/mod.rs`, flat `foo.rs`, or a
+/// sidecar inside `foo/`.
+pub fn declaration_condition(source: &Path) -> Option<String> {
+    let (name, declaring_dir) = module_name_and_parent_dir(source)?;
+
+    declaring_files(&declaring_dir)
+        .into_iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .find_map(|text| cfg_on_mod(&text, &name))
+}
+
+/// Render a `cfg` predicate for display: `feature = "gpu"` becomes
+/// `feature: gpu`; anything else is shown as `cfg(...)`.
+pub fn describe_predicate(predicate: &str) -> String {
+    let compact = predicate
+        .replace(" (", "(")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(" ,", ",");
+
+    let feature = compact
+        .strip_prefix("feature")
+        .map(str::trim_start)
+        .and_then(|rest| rest.strip_prefix('='))
+        .map(|rest| rest.trim().trim_matches('"'))
+        .filter(|name| !name.contains(['"', '(', ',']));
+
+    match feature {
+        Some(name) => format!("feature: {}", name),
+        None => format!("cfg({})", compact),
+    }
+}
+
+fn module_name_and_parent_dir(source: &Path) -> Option<(String, PathBuf)> {
+    let filename = source.file_name()?.to_str()?;
+    let dir = source.parent()?;
+
+    if filename.ends_with(".rs") && filename != "mod.rs" {
+        let stem = source.file_stem()?.to_str()?;
+        return Some((stem.to_string(), dir.to_path_buf()));
+    }
+
+    let name = dir.file_name()?.to_str()?;
+    Some((name.to_string(), dir.parent()?.to_path_buf()))
+}
+
+/// Files that may declare modules living directly in `dir`.
+fn declaring_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = vec![
+        dir.join("mod.rs"),
+        dir.join("lib.rs"),
+        dir.join("main.rs"),
+    ];
+    if let (Some(parent), Some(name)) = (dir.parent(), dir.file_name()) {
+        files.push(parent.join(format!("{}.rs", name.to_string_lossy())));
+    }
+    files.into_iter().filter(|f| f.is_file()).collect()
+}
+
+fn cfg_on_mod(source: &str, name: &str) -> Option<String> {
+    let file = syn::parse_file(source).ok()?;
+
+    file.items.iter().find_map(|item| match item {
+        syn::Item::Mod(m) if m.ident == name => m
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("cfg"))
+            .find_map(|attr| match &attr.meta {
+                syn::Meta::List(list) => Some(list.tokens.to_token_stream().to_string()),
+                _ => None,
+            })
+            .filter(|predicate| predicate != "test")
+            .map(|predicate| describe_predicate(&predicate)),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn feature_predicates_are_shortened() {
+        assert_eq!(describe_predicate("feature = \"gpu\""), "feature: gpu");
+        assert_eq!(describe_predicate("unix"), "cfg(unix)");
+        assert_eq!(
+            describe_predicate("any (feature = \"a\" , feature = \"b\")"),
+            "cfg(any(feature = \"a\", feature = \"b\"))"
+        );
+    }
+
+    #[test]
+    fn cfg_on_mod_declaration_is_found() {
+        let source = "//! Root\n\npub mod bus;\n\n#[cfg(feature = \"gpu\")]\npub mod gpu;\n\n#[cfg(test)]\nmod tests;\n";
+
+        assert_eq!(cfg_on_mod(source, "gpu").as_deref(), Some("feature: gpu"));
+        assert_eq!(cfg_on_mod(source, "bus"), None);
+        assert_eq!(cfg_on_mod(source, "tests"), None);
+    }
+}
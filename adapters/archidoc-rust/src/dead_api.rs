@@ -0,0 +1,183 @@
+//! Dead public API detection — "facade creep".
+//!
+//! A component's public surface should be what the rest of the codebase
+//! actually uses. This pass collects each component's top-level `pub`
+//! items with syn, collects every identifier referenced by code outside
+//! the component, and reports the public items nothing outside mentions.
+//!
+//! Matching is by name: an item counts as used when any outside file
+//! refers to an identifier of the same name, or glob-imports the component.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use archidoc_types::{ElementKind, ModuleDoc};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use walkdir::WalkDir;
+
+/// A public item of a component that no code outside the component references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedItem {
+    pub component: String,
+    pub item: String,
+    pub file: PathBuf,
+}
+
+/// Find public items of each component that are unused outside it.
+///
+/// Items of a component are unused only if no file outside the component's
+/// directory (nested child modules count as inside) references them.
+pub fn find_unused_public_items(docs: &[ModuleDoc]) -> Vec<UnusedItem> {
+    let all_files = project_files(docs);
+    let mut unused = Vec::new();
+
+    for doc in docs.iter().filter(|d| d.c4_level == ElementKind::Component) {
+        let inside = module_files(doc);
+        if inside.is_empty() {
+            continue;
+        }
+
+        let leaf = doc.module_path.rsplit('.').next().unwrap_or(&doc.module_path);
+        let mut referenced = BTreeSet::new();
+        let mut glob_imported = false;
+        for file in all_files.iter().filter(|f| !inside.contains(*f)) {
+            if let Some(parsed) = parse(file) {
+                collect_idents(parsed.to_token_stream(), &mut referenced);
+                glob_imported |= glob_imports(&parsed.items, leaf);
+            }
+        }
+        if glob_imported {
+            continue;
+        }
+
+        for file in &inside {
+            let Some(parsed) = parse(file) else {
+                continue;
+            };
+            for item in public_item_names(&parsed.items) {
+                if !referenced.contains(&item) {
+                    unused.push(UnusedItem {
+                        component: doc.module_path.clone(),
+                        item,
+                        file: file.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    unused
+}
+
+/// Names of top-level `pub` items. Re-exports and module declarations are
+/// part of the module tree, not the component's own surface.
+pub fn public_item_names(items: &[syn::Item]) -> Vec<String> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let (vis, ident) = match item {
+                syn::Item::Fn(i) => (&i.vis, &i.sig.ident),
+                syn::Item::Struct(i) => (&i.vis, &i.ident),
+                syn::Item::Enum(i) => (&i.vis, &i.ident),
+                syn::Item::Trait(i) => (&i.vis, &i.ident),
+                syn::Item::Type(i) => (&i.vis, &i.ident),
+                syn::Item::Const(i) => (&i.vis, &i.ident),
+                syn::Item::Static(i) => (&i.vis, &i.ident),
+                syn::Item::Union(i) => (&i.vis, &i.ident),
+                _ => return None,
+            };
+            matches!(vis, syn::Visibility::Public(_)).then(|| ident.to_string())
+        })
+        .collect()
+}
+
+fn parse(path: &Path) -> Option<syn::File> {
+    let source = fs::read_to_string(path).ok()?;
+    syn::parse_file(&source).ok()
+}
+
+/// Every `.rs` file under any annotated module's directory.
+fn project_files(docs: &[ModuleDoc]) -> BTreeSet<PathBuf> {
+    docs.iter().flat_map(module_files).collect()
+}
+
+/// A module's entry file plus everything under its directory.
+fn module_files(doc: &ModuleDoc) -> BTreeSet<PathBuf> {
+    let source = PathBuf::from(&doc.source_file);
+    let mut files = BTreeSet::new();
+    if source.extension().is_some_and(|e| e == "rs") {
+        files.insert(source.clone());
+    }
+    if let Some(dir) = ModuleDoc::own_dir(&source) {
+        files.extend(rs_files_under(&dir));
+    }
+    files
+}
+
+fn rs_files_under(dir: &Path) -> Vec<PathBuf> {
+    WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .map(|e| e.into_path())
+        .filter(|p| p.extension().is_some_and(|e| e == "rs"))
+        .collect()
+}
+
+fn collect_idents(tokens: TokenStream, out: &mut BTreeSet<String>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                out.insert(ident.to_string());
+            }
+            TokenTree::Group(group) => collect_idents(group.stream(), out),
+            _ => {}
+        }
+    }
+}
+
+/// Whether any `use` in `items` glob-imports a module named `leaf`.
+fn glob_imports(items: &[syn::Item], leaf: &str) -> bool {
+    fn walk(tree: &syn::UseTree, last: Option<&syn::Ident>, leaf: &str) -> bool {
+        match tree {
+            syn::UseTree::Path(p) => walk(&p.tree, Some(&p.ident), leaf),
+            syn::UseTree::Group(g) => g.items.iter().any(|t| walk(t, last, leaf)),
+            syn::UseTree::Glob(_) => last.is_some_and(|ident| ident == leaf),
+            _ => false,
+        }
+    }
+
+    items.iter().any(|item| match item {
+        syn::Item::Use(u) => walk(&u.tree, None, leaf),
+        syn::Item::Mod(m) => m
+            .content
+            .as_ref()
+            .is_some_and(|(_, nested)| glob_imports(nested, leaf)),
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_items_exclude_reexports_and_private_items() {
+        let file = syn::parse_file(
+            "pub use inner::Thing;\npub mod inner;\npub struct Engine;\nfn helper() {}\npub(crate) fn internal() {}\npub fn run() {}\n",
+        )
+        .unwrap();
+
+        assert_eq!(public_item_names(&file.items), vec!["Engine", "run"]);
+    }
+
+    #[test]
+    fn glob_imports_match_the_module_name() {
+        let file = syn::parse_file("use crate::bus::{calc::*, store::Cache};\n").unwrap();
+
+        assert!(glob_imports(&file.items, "calc"));
+        assert!(!glob_imports(&file.items, "store"));
+    }
+}
@@ -1,8 +1,6 @@
-use std::path::Path;
+use archidoc_types::{FitnessSummary, HeuristicConfig, ModuleDoc, ProjectConfig};
 
-use archidoc_types::ModuleDoc;
-
-use crate::pattern_heuristic;
+use crate::{boundary, dead_api, path_resolver, pattern_heuristic};
 
 /// Result of running a fitness function across modules.
 #[derive(Debug)]
@@ -21,39 +19,223 @@ pub struct FitnessFailure {
 }
 
 /// H4: All modules with pattern "Strategy" must define at least one trait.
-pub fn all_strategy_modules_define_a_trait(docs: &[ModuleDoc]) -> FitnessResult {
-    check_modules_for_pattern(docs, "Strategy", "no trait definition found")
+pub fn all_strategy_modules_define_a_trait(docs: &[ModuleDoc], thresholds: &HeuristicConfig) -> FitnessResult {
+    check_modules_for_pattern(docs, thresholds, "Strategy", "no trait definition found")
 }
 
 /// H5: All modules with pattern "Facade" must re-export submodules.
-pub fn all_facade_modules_reexport_submodules(docs: &[ModuleDoc]) -> FitnessResult {
-    check_modules_for_pattern(docs, "Facade", "no pub use re-exports or pub mod declarations found")
+pub fn all_facade_modules_reexport_submodules(docs: &[ModuleDoc], thresholds: &HeuristicConfig) -> FitnessResult {
+    check_modules_for_pattern(docs, thresholds, "Facade", "no pub use re-exports or pub mod declarations found")
 }
 
 /// H6: All modules with pattern "Observer" must have channels or callbacks.
-pub fn all_observer_modules_have_channels_or_callbacks(docs: &[ModuleDoc]) -> FitnessResult {
-    check_modules_for_pattern(docs, "Observer", "no channel types or callback parameters found")
+pub fn all_observer_modules_have_channels_or_callbacks(docs: &[ModuleDoc], thresholds: &HeuristicConfig) -> FitnessResult {
+    check_modules_for_pattern(docs, thresholds, "Observer", "no channel types or callback parameters found")
+}
+
+/// All modules with pattern "Repository" must expose query and mutation
+/// operations over a store.
+pub fn all_repository_modules_expose_crud_operations(docs: &[ModuleDoc], thresholds: &HeuristicConfig) -> FitnessResult {
+    check_modules_for_pattern(
+        docs,
+        thresholds,
+        "Repository",
+        "no trait or storage-backed type with both query and mutation methods found",
+    )
+}
+
+/// All modules with pattern "State" must define state transitions.
+pub fn all_state_modules_define_transitions(docs: &[ModuleDoc], thresholds: &HeuristicConfig) -> FitnessResult {
+    check_modules_for_pattern(
+        docs,
+        thresholds,
+        "State",
+        "no match-driven enum transitions or swapped trait-object state found",
+    )
+}
+
+/// All modules with pattern "Template Method" must define a trait whose
+/// default methods call its required methods.
+pub fn all_template_method_modules_define_hook_calls(docs: &[ModuleDoc], thresholds: &HeuristicConfig) -> FitnessResult {
+    check_modules_for_pattern(
+        docs,
+        thresholds,
+        "Template Method",
+        "no trait default method calling a required method found",
+    )
+}
+
+/// H7: Every public item of a component must be used outside it.
+///
+/// Public items nothing else references widen the component's surface
+/// beyond what its declared Facade actually serves ("facade creep").
+pub fn all_component_public_items_are_used(docs: &[ModuleDoc]) -> FitnessResult {
+    let checked = docs
+        .iter()
+        .filter(|d| d.c4_level == archidoc_types::ElementKind::Component)
+        .count();
+
+    let failures: Vec<FitnessFailure> = dead_api::find_unused_public_items(docs)
+        .into_iter()
+        .map(|unused| FitnessFailure {
+            module_path: unused.component,
+            source_file: unused.file.to_string_lossy().to_string(),
+            reason: format!("pub item `{}` is not used outside the component", unused.item),
+        })
+        .collect();
+
+    FitnessResult {
+        passed: failures.is_empty(),
+        checked,
+        failures,
+    }
 }
 
-/// Run a named fitness function by name.
-pub fn run_fitness(name: &str, docs: &[ModuleDoc]) -> Option<FitnessResult> {
+/// Run a named fitness function by name, with settings from `archidoc.toml`
+/// (`[heuristics]` for pattern checks, `[fitness]` for the rest).
+pub fn run_fitness(name: &str, docs: &[ModuleDoc], config: &ProjectConfig) -> Option<FitnessResult> {
+    let thresholds = &config.heuristics;
     match name {
         "all_strategy_modules_define_a_trait" => {
-            Some(all_strategy_modules_define_a_trait(docs))
+            Some(all_strategy_modules_define_a_trait(docs, thresholds))
         }
         "all_facade_modules_reexport_submodules" => {
-            Some(all_facade_modules_reexport_submodules(docs))
+            Some(all_facade_modules_reexport_submodules(docs, thresholds))
         }
         "all_observer_modules_have_channels_or_callbacks" => {
-            Some(all_observer_modules_have_channels_or_callbacks(docs))
+            Some(all_observer_modules_have_channels_or_callbacks(docs, thresholds))
+        }
+        "all_repository_modules_expose_crud_operations" => {
+            Some(all_repository_modules_expose_crud_operations(docs, thresholds))
         }
+        "all_state_modules_define_transitions" => {
+            Some(all_state_modules_define_transitions(docs, thresholds))
+        }
+        "all_template_method_modules_define_hook_calls" => {
+            Some(all_template_method_modules_define_hook_calls(docs, thresholds))
+        }
+        "all_component_public_items_are_used" => {
+            Some(all_component_public_items_are_used(docs))
+        }
+        "every_container_is_connected_or_standalone" => {
+            Some(every_container_is_connected_or_standalone(docs))
+        }
+        "no_component_depends_on_another_containers_internals" => Some(
+            no_component_depends_on_another_containers_internals(docs, &config.fitness.allow_cross_container),
+        ),
         _ => None,
     }
 }
 
+/// Names of every built-in fitness function, in report order.
+pub const FITNESS_FUNCTIONS: &[&str] = &[
+    "all_strategy_modules_define_a_trait",
+    "all_facade_modules_reexport_submodules",
+    "all_observer_modules_have_channels_or_callbacks",
+    "all_repository_modules_expose_crud_operations",
+    "all_state_modules_define_transitions",
+    "all_template_method_modules_define_hook_calls",
+    "all_component_public_items_are_used",
+    "every_container_is_connected_or_standalone",
+    "no_component_depends_on_another_containers_internals",
+];
+
+/// Run every built-in fitness function and summarize each result.
+pub fn run_all(docs: &[ModuleDoc], config: &ProjectConfig) -> Vec<FitnessSummary> {
+    FITNESS_FUNCTIONS
+        .iter()
+        .filter_map(|name| run_fitness(name, docs, config).map(|result| (name, result)))
+        .map(|(name, result)| FitnessSummary {
+            name: name.to_string(),
+            checked: result.checked,
+            failed: result.failures.len(),
+        })
+        .collect()
+}
+
+/// Elements must depend on other containers, not on their components.
+///
+/// A dependency — a declared relationship or a `use crate::...` import —
+/// from inside one container to a component of another bypasses that
+/// container's facade. `allow` lists accepted exceptions (see
+/// [`boundary::find_violations`]).
+pub fn no_component_depends_on_another_containers_internals(
+    docs: &[ModuleDoc],
+    allow: &[String],
+) -> FitnessResult {
+    let checked = docs
+        .iter()
+        .filter(|d| d.c4_level != archidoc_types::ElementKind::Unknown)
+        .count();
+
+    let failures: Vec<FitnessFailure> = boundary::find_violations(docs, allow)
+        .into_iter()
+        .map(|violation| FitnessFailure {
+            source_file: docs
+                .iter()
+                .find(|d| d.module_path == violation.from)
+                .map(|d| d.source_file.clone())
+                .unwrap_or_default(),
+            reason: format!(
+                "depends on `{}` inside container `{}` ({}); depend on `{}` instead",
+                violation.to, violation.container, violation.via, violation.container
+            ),
+            module_path: violation.from,
+        })
+        .collect();
+
+    FitnessResult {
+        passed: failures.is_empty(),
+        checked,
+        failures,
+    }
+}
+
+/// Every top-level container must have a relationship in or out, or be
+/// marked `@standalone`.
+///
+/// Relationships of a container's components count as the container's own,
+/// except those between two elements inside it. Crate roots are the system
+/// itself and are not checked. A container with no connections usually had
+/// its annotations stubbed and never wired into the rest of the system.
+pub fn every_container_is_connected_or_standalone(docs: &[ModuleDoc]) -> FitnessResult {
+    let containers: Vec<&ModuleDoc> = docs
+        .iter()
+        .filter(|d| d.c4_level.is_container())
+        .filter(|d| d.parent_container.is_none() && !path_resolver::is_crate_root(&d.module_path))
+        .collect();
+
+    let failures: Vec<FitnessFailure> = containers
+        .iter()
+        .filter(|container| !container.standalone)
+        .filter(|container| {
+            let inside = |path: &str| {
+                path == container.module_path || path.starts_with(&format!("{}.", container.module_path))
+            };
+            !docs.iter().any(|doc| {
+                doc.relationships
+                    .iter()
+                    .any(|rel| inside(&doc.module_path) != inside(&rel.target))
+            })
+        })
+        .map(|container| FitnessFailure {
+            module_path: container.module_path.clone(),
+            source_file: container.source_file.clone(),
+            reason: "no relationships in or out; declare one with `@c4 uses` or mark it `@standalone`".to_string(),
+        })
+        .collect();
+
+    FitnessResult {
+        passed: failures.is_empty(),
+        checked: containers.len(),
+        failures,
+    }
+}
+
 /// Generic: check all modules with the given pattern against the corresponding heuristic.
 fn check_modules_for_pattern(
     docs: &[ModuleDoc],
+    thresholds: &HeuristicConfig,
     pattern: &str,
     failure_reason: &str,
 ) -> FitnessResult {
@@ -67,7 +249,7 @@ fn check_modules_for_pattern(
 
         checked += 1;
 
-        let source_dir = match Path::new(&doc.source_file).parent() {
+        let source_dir = match doc.catalog_dir() {
             Some(dir) => dir,
             None => {
                 failures.push(FitnessFailure {
@@ -79,7 +261,7 @@ fn check_modules_for_pattern(
             }
         };
 
-        if !pattern_heuristic::check_module_pattern(pattern, source_dir) {
+        if !pattern_heuristic::check_module_pattern(pattern, &source_dir, thresholds) {
             failures.push(FitnessFailure {
                 module_path: doc.module_path.clone(),
                 source_file: doc.source_file.clone(),
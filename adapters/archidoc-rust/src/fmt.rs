@@ -0,0 +1,333 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Purposes longer than this wrap onto continuation rows.
+pub const PURPOSE_WIDTH: usize = 60;
+
+/// Format the annotations of every annotated Rust file under `root`, the
+/// files the walker reads. Returns the files whose formatting changed;
+/// with `write`, they are rewritten in place.
+pub fn format_tree(root: &Path, write: bool) -> Result<Vec<PathBuf>, String> {
+    let mut sources: Vec<PathBuf> = crate::walker::extract(root)
+        .docs
+        .into_iter()
+        .map(|doc| PathBuf::from(doc.source_file))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    sources.sort();
+    sources.dedup();
+
+    let mut changed = Vec::new();
+    for path in sources {
+        let source = fs::read_to_string(&path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        let formatted = format_source(&source);
+        if formatted == source {
+            continue;
+        }
+        if write {
+            fs::write(&path, &formatted).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+        }
+        changed.push(path);
+    }
+    Ok(changed)
+}
+
+/// Format the leading `//!` block of a Rust source file; the rest of the
+/// file is left as is.
+pub fn format_source(source: &str) -> String {
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    let is_doc = |line: &str| line.trim_start().starts_with("//!");
+    let start = match lines.iter().position(|line| is_doc(line)) {
+        Some(start) => start,
+        None => return source.to_string(),
+    };
+    let end = start + lines[start..].iter().take_while(|line| is_doc(line)).count();
+
+    let indent_len = lines[start].len() - lines[start].trim_start().len();
+    let indent = &lines[start][..indent_len];
+    let text: Vec<String> = lines[start..end]
+        .iter()
+        .map(|line| {
+            let line = line.trim();
+            let rest = line.strip_prefix("//!").unwrap_or(line);
+            rest.strip_prefix(' ').unwrap_or(rest).to_string()
+        })
+        .collect();
+
+    let mut out: String = lines[..start].concat();
+    for line in format_annotation(&text) {
+        if line.is_empty() {
+            out.push_str(&format!("{}//!\n", indent));
+        } else {
+            out.push_str(&format!("{}//! {}\n", indent, line));
+        }
+    }
+    // Keep a final block without a trailing newline that way
+    if end == lines.len() && !source.ends_with('\n') {
+        out.pop();
+    }
+    out.push_str(&lines[end..].concat());
+    out
+}
+
+/// Format annotation text: trailing whitespace trimmed, `@c4` markers
+/// single-spaced, and tables aligned. File tables are also sorted by file
+/// name, with long purposes wrapped onto continuation rows. Fenced code
+/// blocks are left alone.
+pub fn format_annotation(lines: &[String]) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut in_code = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i].trim_end();
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+        }
+        if in_code || trimmed.starts_with("```") {
+            out.push(line.to_string());
+            i += 1;
+        } else if trimmed.starts_with('|') {
+            let end = i + lines[i..].iter().take_while(|l| l.trim_start().starts_with('|')).count();
+            out.extend(format_table(&lines[i..end]));
+            i = end;
+        } else if trimmed.starts_with("@c4") {
+            out.push(normalize_marker(trimmed));
+            i += 1;
+        } else {
+            out.push(line.to_string());
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Collapse whitespace between marker tokens, leaving quoted text alone.
+fn normalize_marker(line: &str) -> String {
+    let mut out = String::new();
+    let mut in_quote = false;
+    for c in line.trim().chars() {
+        if c == '"' {
+            in_quote = !in_quote;
+        }
+        if !in_quote && c.is_whitespace() {
+            if !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Align a markdown table's columns. Tables with a header row and a
+/// separator row that look like a file table (see
+/// [`crate::parser::extract_file_table`]) are sorted and wrapped too.
+fn format_table(rows: &[String]) -> Vec<String> {
+    let mut cells: Vec<Vec<String>> = rows.iter().map(|r| split_raw(r)).collect();
+    let has_separator = cells.len() > 1 && cells[1].iter().all(|c| is_separator_cell(c));
+    if !has_separator {
+        return rows.iter().map(|r| r.trim().to_string()).collect();
+    }
+
+    let header = cells.remove(0);
+    let separator = cells.remove(0);
+    let is_file_table = {
+        let joined = header.join(" ").to_lowercase();
+        joined.contains("file") && joined.contains("pattern") && header.len() >= 4
+    };
+    if is_file_table {
+        cells = sort_and_wrap(cells);
+    }
+
+    let columns = std::iter::once(&header)
+        .chain(&cells)
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0);
+    let mut widths = vec![3; columns];
+    for row in std::iter::once(&header).chain(&cells) {
+        for (c, cell) in row.iter().enumerate() {
+            widths[c] = widths[c].max(cell.chars().count());
+        }
+    }
+
+    let render = |row: &[String]| {
+        let padded: Vec<String> = (0..columns)
+            .map(|c| {
+                let cell = row.get(c).map_or("", String::as_str);
+                format!(" {}{} ", cell, " ".repeat(widths[c] - cell.chars().count()))
+            })
+            .collect();
+        format!("|{}|", padded.join("|"))
+    };
+
+    let rule: Vec<String> = (0..columns)
+        .map(|c| {
+            let cell = separator.get(c).map_or("", String::as_str);
+            let mut dashes = "-".repeat(widths[c] + 2);
+            if cell.starts_with(':') {
+                dashes.replace_range(..1, ":");
+            }
+            if cell.len() > 1 && cell.ends_with(':') {
+                dashes.replace_range(dashes.len() - 1.., ":");
+            }
+            dashes
+        })
+        .collect();
+
+    let mut out = vec![render(&header), format!("|{}|", rule.join("|"))];
+    out.extend(cells.iter().map(|row| render(row)));
+    out
+}
+
+/// File table rows sorted by file name, each purpose re-wrapped at
+/// [`PURPOSE_WIDTH`]. Continuation rows (empty file cell) are joined to
+/// their entry first.
+fn sort_and_wrap(rows: Vec<Vec<String>>) -> Vec<Vec<String>> {
+    let mut entries: Vec<Vec<String>> = Vec::new();
+    for row in rows {
+        let is_continuation = row.first().is_none_or(|c| c.is_empty());
+        match entries.last_mut() {
+            Some(last) if is_continuation => {
+                let extra = row.get(2).map_or("", String::as_str);
+                if !extra.is_empty() {
+                    if !last[2].is_empty() {
+                        last[2].push(' ');
+                    }
+                    last[2].push_str(extra);
+                }
+            }
+            _ => {
+                let mut row = row;
+                row.resize(row.len().max(4), String::new());
+                entries.push(row);
+            }
+        }
+    }
+    entries.sort_by_key(|row| row[0].trim_matches('`').to_string());
+
+    let mut out = Vec::new();
+    for mut entry in entries {
+        let mut lines = wrap(&entry[2], PURPOSE_WIDTH).into_iter();
+        entry[2] = lines.next().unwrap_or_default();
+        let width = entry.len();
+        out.push(entry);
+        for line in lines {
+            let mut row = vec![String::new(); width];
+            row[2] = line;
+            out.push(row);
+        }
+    }
+    out
+}
+
+/// Greedy word wrap; words longer than `width` get a line of their own.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+    lines
+}
+
+fn is_separator_cell(cell: &str) -> bool {
+    !cell.is_empty() && cell.contains('-') && cell.chars().all(|c| c == '-' || c == ':')
+}
+
+/// Split a table row into trimmed cells, keeping escaped pipes and code
+/// spans as written.
+pub(crate) fn split_raw(row: &str) -> Vec<String> {
+    let trimmed = row.trim();
+    let inner = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut in_code = false;
+    let mut chars = inner.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                current.push(c);
+                current.push(chars.next().unwrap());
+            }
+            '`' => {
+                in_code = !in_code;
+                current.push(c);
+            }
+            '|' if !in_code => {
+                cells.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        cells.push(current.trim().to_string());
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn file_tables_are_sorted_aligned_and_wrapped() {
+        let text = lines(
+            "@c4   component\n\
+             @c4 uses  bus   \"Routes  events\"\n\
+             \n\
+             | File | Pattern | Purpose | Health |\n\
+             |------|---------|---------|--------|\n\
+             | `walker.rs` | -- | Directory walking | stable |\n\
+             | `api.rs` | Facade (verified) | Public entry point that hides the walker, the parser, and the cache behind one call | active |\n\
+             | | | continued | |\n",
+        );
+
+        let formatted = format_annotation(&text);
+
+        assert_eq!(
+            formatted,
+            lines(
+                "@c4 component\n\
+                 @c4 uses bus \"Routes  events\"\n\
+                 \n\
+                 | File        | Pattern           | Purpose                                                   | Health |\n\
+                 |-------------|-------------------|-----------------------------------------------------------|--------|\n\
+                 | `api.rs`    | Facade (verified) | Public entry point that hides the walker, the parser, and | active |\n\
+                 |             |                   | the cache behind one call continued                       |        |\n\
+                 | `walker.rs` | --                | Directory walking                                         | stable |\n"
+            )
+        );
+        assert_eq!(format_annotation(&formatted), formatted);
+    }
+
+    #[test]
+    fn formatting_keeps_the_parsed_annotation() {
+        let source = "#![allow(dead_code)]\n//! @c4 container\n//!\n//! | File | Pattern | Purpose | Health |\n//! |---|---|---|---|\n//! | `b.rs` | -- | a \\| b | active |\n//! | `a.rs` | Observer (verified) | Events | stable |\n\npub mod a;\n";
+
+        let formatted = format_source(source);
+
+        assert!(formatted.starts_with("#![allow(dead_code)]\n//! @c4 container\n//!\n//! | File   |"), "{}", formatted);
+        assert!(formatted.ends_with("\n\npub mod a;\n"));
+        let before = crate::parser::extract_file_table(&crate::parser::archidoc_from_source(source).unwrap());
+        let after = crate::parser::extract_file_table(&crate::parser::archidoc_from_source(&formatted).unwrap());
+        let mut sorted = before.clone();
+        sorted.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(after, sorted);
+        assert_eq!(format_source("fn main() {}\n"), "fn main() {}\n");
+    }
+}
@@ -6,18 +6,36 @@
 //!
 //! | File | Pattern | Purpose | Health |
 //! |------|---------|---------|--------|
-//! | `walker.rs` | -- | Directory tree walker | planned |
-//! | `parser.rs` | -- | Annotation parser | planned |
-//! | `path_resolver.rs` | -- | File path to module path conversion | planned |
-//! | `pattern_heuristic.rs` | Strategy | Structural GoF pattern detection | planned |
-//! | `fitness.rs` | -- | Architectural fitness functions | planned |
-//! | `promote.rs` | -- | Auto-promote planned to verified | planned |
-//! | `cargo_modules.rs` | -- | cargo-modules integration (optional) | planned |
+//! | `walker.rs` | -- | Directory tree walker | stable |
+//! | `parser.rs` | -- | Annotation parser | stable |
+//! | `path_resolver.rs` | -- | File path to module path conversion | stable |
+//! | `pattern_heuristic.rs` | -- | Structural GoF pattern detection | stable |
+//! | `fitness.rs` | -- | Architectural fitness functions | active |
+//! | `dead_api.rs` | -- | Unused public API detection | active |
+//! | `boundary.rs` | -- | Cross-container dependency detection | active |
+//! | `promote.rs` | -- | Auto-promote planned to verified | active |
+//! | `cargo_modules.rs` | -- | cargo-modules integration (optional) | active |
+//! | `sidecar.rs` | -- | TOML/YAML sidecar annotation files | active |
+//! | `cfg_gate.rs` | -- | Feature-gated module detection | active |
+//! | `metrics.rs` | -- | Per-module complexity metrics | active |
+//! | `unsafe_code.rs` | -- | Unsafe block and function accounting | active |
+//! | `fmt.rs` | -- | Annotation formatter | active |
+//! | `snippets.rs` | -- | Editor snippets for annotations | active |
+//! | `catalog.rs` | -- | File-table row insertion and editing | active |
 
+pub mod boundary;
 pub mod cargo_modules;
+pub mod catalog;
+pub mod cfg_gate;
+pub mod dead_api;
 pub mod fitness;
+pub mod fmt;
+pub mod metrics;
 pub mod parser;
 pub mod path_resolver;
 pub mod pattern_heuristic;
 pub mod promote;
+pub mod sidecar;
+pub mod snippets;
+pub mod unsafe_code;
 pub mod walker;
@@ -0,0 +1,189 @@
+//! Complexity metrics — measures each module's own source files.
+//!
+//! Opt-in pass run after walking (`archidoc --metrics`). A module's sources
+//! are the `.rs` files in its directory plus a flat entry file, excluding
+//! files that are the entry point of another module.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+use archidoc_types::ModuleDoc;
+use syn::spanned::Spanned;
+
+/// Non-blank lines across the module's sources.
+pub const LOC: &str = "loc";
+/// Number of source files measured.
+pub const FILES: &str = "files";
+/// `pub` items, including `pub` methods of inherent impls.
+pub const PUBLIC_ITEMS: &str = "public_items";
+/// Average function length in lines, rounded to the nearest line.
+pub const AVG_FN_LINES: &str = "avg_fn_lines";
+
+/// Fill [`ModuleDoc::metrics`] for every module with readable sources.
+pub fn collect_metrics(docs: &mut [ModuleDoc]) {
+    let entry_files: BTreeSet<PathBuf> = docs.iter().map(|d| PathBuf::from(&d.source_file)).collect();
+
+    for doc in docs.iter_mut() {
+        let sources: Vec<String> = module_sources(doc, &entry_files)
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .collect();
+        if !sources.is_empty() {
+            doc.metrics = measure(&sources);
+        }
+    }
+}
+
+/// Measure a set of Rust sources. Files that fail to parse still count
+/// towards lines and files.
+pub fn measure(sources: &[String]) -> BTreeMap<String, u64> {
+    let mut loc = 0;
+    let mut public_items = 0;
+    let mut fn_lines = Vec::new();
+
+    for source in sources {
+        loc += source.lines().filter(|l| !l.trim().is_empty()).count() as u64;
+        if let Ok(file) = syn::parse_file(source) {
+            count_items(&file.items, &mut public_items, &mut fn_lines);
+        }
+    }
+
+    let avg_fn_lines = if fn_lines.is_empty() {
+        0
+    } else {
+        let total: u64 = fn_lines.iter().sum();
+        (total + fn_lines.len() as u64 / 2) / fn_lines.len() as u64
+    };
+
+    BTreeMap::from([
+        (LOC.to_string(), loc),
+        (FILES.to_string(), sources.len() as u64),
+        (PUBLIC_ITEMS.to_string(), public_items),
+        (AVG_FN_LINES.to_string(), avg_fn_lines),
+    ])
+}
+
+pub(crate) fn module_sources(doc: &ModuleDoc, entry_files: &BTreeSet<PathBuf>) -> Vec<PathBuf> {
+    let source = PathBuf::from(&doc.source_file);
+    let mut files = Vec::new();
+    if source.extension().is_some_and(|e| e == "rs") {
+        files.push(source.clone());
+    }
+
+    if let Some(dir) = ModuleDoc::own_dir(&source) {
+        let mut entries: Vec<PathBuf> = fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "rs"))
+            .filter(|p| *p != source && !entry_files.contains(p))
+            .collect();
+        entries.sort();
+        files.extend(entries);
+    }
+
+    files
+}
+
+fn count_items(items: &[syn::Item], public_items: &mut u64, fn_lines: &mut Vec<u64>) {
+    for item in items {
+        if is_public_item(item) {
+            *public_items += 1;
+        }
+        match item {
+            syn::Item::Fn(f) => fn_lines.push(span_lines(f.span())),
+            syn::Item::Impl(imp) => {
+                for impl_item in &imp.items {
+                    if let syn::ImplItem::Fn(f) = impl_item {
+                        fn_lines.push(span_lines(f.span()));
+                        if imp.trait_.is_none() && matches!(f.vis, syn::Visibility::Public(_)) {
+                            *public_items += 1;
+                        }
+                    }
+                }
+            }
+            syn::Item::Trait(t) => {
+                for trait_item in &t.items {
+                    if let syn::TraitItem::Fn(f) = trait_item {
+                        if f.default.is_some() {
+                            fn_lines.push(span_lines(f.span()));
+                        }
+                    }
+                }
+            }
+            syn::Item::Mod(m) => {
+                if let Some((_, nested)) = &m.content {
+                    count_items(nested, public_items, fn_lines);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Public API items. Module declarations and re-exports are not counted.
+fn is_public_item(item: &syn::Item) -> bool {
+    let vis = match item {
+        syn::Item::Fn(i) => &i.vis,
+        syn::Item::Struct(i) => &i.vis,
+        syn::Item::Enum(i) => &i.vis,
+        syn::Item::Trait(i) => &i.vis,
+        syn::Item::Type(i) => &i.vis,
+        syn::Item::Const(i) => &i.vis,
+        syn::Item::Static(i) => &i.vis,
+        syn::Item::Union(i) => &i.vis,
+        _ => return false,
+    };
+    matches!(vis, syn::Visibility::Public(_))
+}
+
+fn span_lines(span: proc_macro2::Span) -> u64 {
+    (span.end().line.saturating_sub(span.start().line) + 1) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_public_items_and_function_length() {
+        let source = r#"
+pub struct Bus;
+
+struct Internal;
+
+impl Bus {
+    pub fn publish(&self) {
+        let a = 1;
+        let _ = a;
+    }
+
+    fn route(&self) {}
+}
+
+pub fn helper() {
+    todo!()
+}
+"#;
+
+        let metrics = measure(&[source.to_string()]);
+
+        assert_eq!(metrics[LOC], 12);
+        assert_eq!(metrics[FILES], 1);
+        // Bus, Bus::publish, helper
+        assert_eq!(metrics[PUBLIC_ITEMS], 3);
+        // publish: 4 lines, route: 1, helper: 3
+        assert_eq!(metrics[AVG_FN_LINES], 3);
+    }
+
+    #[test]
+    fn unparsable_sources_still_count_lines() {
+        let metrics = measure(&["fn broken( {\n".to_string()]);
+
+        assert_eq!(metrics[LOC], 1);
+        assert_eq!(metrics[PUBLIC_ITEMS], 0);
+        assert_eq!(metrics[AVG_FN_LINES], 0);
+    }
+}
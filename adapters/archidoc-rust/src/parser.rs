@@ -2,16 +2,143 @@ use std::fs;
 use std::path::Path;
 
 use archidoc_types::{
-    C4Level, FileEntry, HealthStatus, PatternStatus, Relationship,
+    is_marker_line, ElementKind, FileEntry, Flow, HealthStatus, PatternStatus, Relationship, Review, Risk,
+    DocLink, RiskSeverity, Term,
 };
 
 /// Extract `//!` doc comments from a Rust source file.
 ///
-/// Returns the joined content of all leading `//!` lines, with prefixes stripped.
+/// Returns the joined content of the file's inner doc comments, with prefixes
+/// stripped. See [`archidoc_from_source`] for what counts as a doc comment.
 pub fn archidoc_from_file(path: &Path) -> Option<String> {
     let content = fs::read_to_string(path).ok()?;
+    archidoc_from_source(&content)
+}
+
+/// Extract inner documentation from Rust source text.
+///
+/// Scans the file header — inner attributes, comments, and blank lines —
+/// and collects `//!` comments, `/*! */` blocks, and `#![doc = "..."]`
+/// attributes in order. Other inner attributes such as `#![allow(...)]`
+/// may appear before or between doc lines.
+pub fn archidoc_from_source(content: &str) -> Option<String> {
+    let header = &content[..header_len(content)];
+
+    let doc_lines = match syn::parse_file(header) {
+        Ok(file) => inner_doc_lines(&file.attrs),
+        Err(_) => leading_doc_comment_lines(content),
+    };
+
+    if doc_lines.is_empty() {
+        None
+    } else {
+        Some(doc_lines.join("\n"))
+    }
+}
+
+/// Byte length of the file header: leading whitespace, comments, a shebang,
+/// and inner attributes (`#![...]`), stopping at the first item.
+fn header_len(content: &str) -> usize {
+    let bytes = content.as_bytes();
+    let mut pos = 0;
+
+    if content.starts_with("#!") && !content.starts_with("#![") {
+        pos = content.find('\n').map_or(content.len(), |i| i + 1);
+    }
+
+    loop {
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        let rest = &content[pos..];
+
+        if rest.starts_with("//") {
+            pos += rest.find('\n').map_or(rest.len(), |i| i + 1);
+        } else if rest.starts_with("/*") {
+            match rest.find("*/") {
+                Some(end) => pos += end + 2,
+                None => return pos,
+            }
+        } else if rest.starts_with("#![") {
+            match inner_attribute_len(rest) {
+                Some(len) => pos += len,
+                None => return pos,
+            }
+        } else {
+            return pos;
+        }
+    }
+}
+
+/// Length of the inner attribute at the start of `rest`, including the
+/// closing bracket. Brackets inside string literals are ignored.
+fn inner_attribute_len(rest: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in rest.char_indices().skip(2) {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
 
-    let doc_lines: Vec<&str> = content
+    None
+}
+
+/// Collect doc text from parsed inner attributes.
+///
+/// syn desugars `//! text` into `#![doc = " text"]`, so one leading space is
+/// stripped to match the comment form. Non-literal values such as
+/// `include_str!(...)` are skipped.
+fn inner_doc_lines(attrs: &[syn::Attribute]) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for attr in attrs {
+        if !attr.path().is_ident("doc") {
+            continue;
+        }
+        let syn::Meta::NameValue(meta) = &attr.meta else {
+            continue;
+        };
+        let syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(text),
+            ..
+        }) = &meta.value
+        else {
+            continue;
+        };
+
+        let value = text.value();
+        for line in value.split('\n') {
+            let line = line.trim_end();
+            lines.push(line.strip_prefix(' ').unwrap_or(line).to_string());
+        }
+    }
+
+    lines
+}
+
+/// Fallback for headers syn cannot parse: leading `//!` lines only.
+fn leading_doc_comment_lines(content: &str) -> Vec<String> {
+    content
         .lines()
         .take_while(|line| {
             let trimmed = line.trim();
@@ -28,32 +155,93 @@ pub fn archidoc_from_file(path: &Path) -> Option<String> {
                 trimmed.strip_prefix("//!").unwrap_or("")
             }
         })
-        .collect();
+        .map(str::to_string)
+        .collect()
+}
 
-    if doc_lines.is_empty() {
-        None
-    } else {
-        Some(doc_lines.join("\n"))
-    }
+/// Extract the element kind from the first `@c4 <kind>` marker in doc
+/// content: `container`, `component`, `context`, `person`,
+/// `external_system` (or `external`), `deployment_node`, or
+/// `custom:<name>`. Declarations of other elements (see
+/// [`extract_declarations`]) are not kind markers.
+pub fn extract_kind(content: &str) -> ElementKind {
+    content
+        .lines()
+        .filter(|line| !is_declaration(line.trim()))
+        .flat_map(|line| {
+            line.match_indices("@c4 ")
+                .map(move |(i, marker)| line[i + marker.len()..].split_whitespace().next().unwrap_or(""))
+        })
+        .map(ElementKind::parse)
+        .find(|kind| *kind != ElementKind::Unknown)
+        .unwrap_or(ElementKind::Unknown)
+}
+
+/// A person or external system declared by a `@c4 person` or
+/// `@c4 external` marker, without a module of its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declaration {
+    pub name: String,
+    pub kind: ElementKind,
+    pub description: String,
+    /// What the element uses, from a trailing `uses` clause.
+    pub relationship: Option<Relationship>,
 }
 
-/// Extract the C4 level marker from doc content.
+/// Extract the elements outside the system that doc content declares:
 ///
-/// Uses `@c4 container` / `@c4 component` syntax.
-pub fn extract_c4_level(content: &str) -> C4Level {
-    if content.contains("@c4 container") {
-        C4Level::Container
-    } else if content.contains("@c4 component") {
-        C4Level::Component
-    } else {
-        C4Level::Unknown
-    }
+/// ```text
+/// @c4 person customer "Places orders" [uses api "Browses the shop" ["HTTPS"]]
+/// @c4 external stripe "Card payments" [uses ...]
+/// ```
+///
+/// `external_system` may be written for `external`. A marker naming one of
+/// these kinds followed by more words is a declaration; a malformed one is
+/// returned as an error holding the marker.
+pub fn extract_declarations(content: &str) -> Vec<Result<Declaration, String>> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| is_declaration(line))
+        .map(|line| parse_declaration(line).ok_or_else(|| line.to_string()))
+        .collect()
+}
+
+/// Whether `line` is a `@c4 person` or `@c4 external` marker with
+/// something after the kind.
+fn is_declaration(line: &str) -> bool {
+    let mut words = match line.strip_prefix("@c4 ") {
+        Some(rest) => rest.split_whitespace(),
+        None => return false,
+    };
+    let declares = words
+        .next()
+        .map(ElementKind::parse)
+        .is_some_and(|kind| matches!(kind, ElementKind::Person | ElementKind::ExternalSystem));
+    declares && words.next().is_some()
+}
+
+fn parse_declaration(line: &str) -> Option<Declaration> {
+    let (keyword, rest) = line.strip_prefix("@c4 ")?.trim_start().split_once(char::is_whitespace)?;
+    let (name, rest) = rest.trim_start().split_once(char::is_whitespace)?;
+    let (description, rest) = rest.trim_start().strip_prefix('"')?.split_once('"')?;
+    let relationship = match rest.trim() {
+        "" => None,
+        uses => Some(parse_uses_marker(uses.strip_prefix("uses ")?)?),
+    };
+    Some(Declaration {
+        name: name.to_string(),
+        kind: ElementKind::parse(keyword),
+        description: description.to_string(),
+        relationship,
+    })
 }
 
 /// Extract the primary GoF pattern name from doc content.
 ///
-/// Looks for known pattern names in the content. Returns the first match
-/// or "--" if none found.
+/// A `GoF: Name` line naming a known pattern wins; otherwise looks for known
+/// pattern names anywhere in the content. Returns the first match or "--"
+/// if none found.
 pub fn extract_pattern(content: &str) -> String {
     let patterns = [
         "Mediator",
@@ -75,7 +263,20 @@ pub fn extract_pattern(content: &str) -> String {
         "Interpreter",
         "Flyweight",
         "Publisher",
+        "Template Method",
     ];
+    // Too common as a plain word to be picked out of prose; recognized only
+    // on an explicit `GoF:` line.
+    let gof_line_only = ["State"];
+
+    let declared = content
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("GoF:"))
+        .map(|v| v.split('(').next().unwrap_or(v).trim())
+        .find(|v| patterns.contains(v) || gof_line_only.contains(v));
+    if let Some(name) = declared {
+        return name.to_string();
+    }
 
     for name in patterns {
         if content.contains(name) {
@@ -105,7 +306,7 @@ pub fn extract_description(content: &str) -> String {
             let trimmed = l.trim();
             !trimmed.is_empty()
                 && !trimmed.starts_with('#')
-                && !trimmed.starts_with("@c4 ")
+                && !is_marker_line(trimmed)
                 && !trimmed.starts_with('|')
                 && !trimmed.starts_with("GoF:")
         })
@@ -114,11 +315,258 @@ pub fn extract_description(content: &str) -> String {
         .to_string()
 }
 
+/// Extract the `@files-dir path` override for the file table's directory.
+///
+/// Returns the first non-empty path; later markers are ignored.
+pub fn extract_files_dir(content: &str) -> Option<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("@files-dir "))
+        .map(str::trim)
+        .find(|dir| !dir.is_empty())
+        .map(str::to_string)
+}
+
+/// Whether the module is marked `@standalone` — intentionally connected to
+/// nothing else in the system.
+pub fn extract_standalone(content: &str) -> bool {
+    content.lines().any(|line| line.trim() == "@standalone")
+}
+
+/// Whether the module is marked `@crosscutting` — a concern such as
+/// logging or metrics that most of the system uses.
+pub fn extract_crosscutting(content: &str) -> bool {
+    content.lines().any(|line| line.trim() == "@crosscutting")
+}
+
+/// Extract the stable `@id identifier` used for diagram nodes and anchors.
+///
+/// Identifiers are ASCII letters, digits and `_`, so they are valid node
+/// names in every diagram format. The first well-formed marker wins.
+pub fn extract_id(content: &str) -> Option<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("@id "))
+        .map(str::trim)
+        .find(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        .map(str::to_string)
+}
+
+/// Extract the `@group kind:name` grouping, e.g. `domain:payments`.
+///
+/// The kind is optional (`@group payments`). The first marker with a
+/// single-word value wins.
+pub fn extract_group(content: &str) -> Option<String> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("@group "))
+        .map(str::trim)
+        .find(|group| !group.is_empty() && !group.contains(char::is_whitespace))
+        .map(str::to_string)
+}
+
+/// Extract the latest `@reviewed-by reviewer YYYY-MM-DD [hash]` sign-off.
+///
+/// When several reviews are recorded, the one with the latest date wins.
+/// Markers without a well-formed date are ignored.
+pub fn extract_review(content: &str) -> Option<Review> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("@reviewed-by "))
+        .filter_map(|rest| {
+            let mut parts = rest.split_whitespace();
+            let reviewer = parts.next()?;
+            let date = parts.next()?;
+            let content_hash = parts.next().map(str::to_string);
+            if parts.next().is_some() || !is_iso_date(date) {
+                return None;
+            }
+            Some(Review {
+                reviewer: reviewer.to_string(),
+                date: date.to_string(),
+                content_hash,
+            })
+        })
+        .max_by(|a, b| a.date.cmp(&b.date))
+}
+
+/// Extract `@risk "description" severity=high` annotations.
+///
+/// The description may be quoted or bare; `severity=` may appear anywhere
+/// after it and defaults to `medium`. Markers without a description are skipped.
+pub fn extract_risks(content: &str) -> Vec<Risk> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("@risk "))
+        .filter_map(parse_risk_marker)
+        .collect()
+}
+
+fn parse_risk_marker(body: &str) -> Option<Risk> {
+    let body = body.trim();
+    let (description, rest) = match body.strip_prefix('"') {
+        Some(after_open) => {
+            let close = after_open.find('"')?;
+            (after_open[..close].to_string(), &after_open[close + 1..])
+        }
+        None => (String::new(), body),
+    };
+
+    let mut severity = RiskSeverity::default();
+    let mut bare = Vec::new();
+    for token in rest.split_whitespace() {
+        match token.strip_prefix("severity=") {
+            Some(value) => severity = RiskSeverity::parse(value),
+            None => bare.push(token),
+        }
+    }
+
+    let description = if description.is_empty() {
+        bare.join(" ")
+    } else {
+        description
+    };
+    if description.trim().is_empty() {
+        return None;
+    }
+
+    Some(Risk {
+        description: description.trim().to_string(),
+        severity,
+    })
+}
+
+/// Extract `@term Name: definition` glossary annotations.
+///
+/// The name ends at the first colon. Markers without a colon, name, or
+/// definition are skipped.
+pub fn extract_terms(content: &str) -> Vec<Term> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("@term "))
+        .filter_map(|rest| {
+            let (name, definition) = rest.split_once(':')?;
+            let (name, definition) = (name.trim(), definition.trim());
+            if name.is_empty() || definition.is_empty() {
+                return None;
+            }
+            Some(Term {
+                name: name.to_string(),
+                definition: definition.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Extract `@flow name: a -> b -> c` runtime scenarios.
+///
+/// The name ends at the first colon and may hold letters, digits, `_`, and
+/// `-`. Markers with another name or fewer than two steps are skipped.
+pub fn extract_flows(content: &str) -> Vec<Flow> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("@flow "))
+        .filter_map(|rest| {
+            let (name, steps) = rest.split_once(':')?;
+            let name = name.trim();
+            let steps: Vec<String> = steps.split("->").map(|s| s.trim().to_string()).collect();
+            let valid_name = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+            if !valid_name || steps.len() < 2 || steps.iter().any(String::is_empty) {
+                return None;
+            }
+            Some(Flow {
+                name: name.to_string(),
+                steps,
+            })
+        })
+        .collect()
+}
+
+/// Extract the numbered `N. [text:] a -> b -> c` items under a `Data Flow`
+/// heading as flows named `data-flow-N`, steps as written.
+///
+/// Steps are single words, optionally in backticks; items with a step that
+/// is not, or with fewer than two steps, are prose and skipped. The
+/// section ends at the next heading.
+pub fn extract_data_flow(content: &str) -> Vec<Flow> {
+    let mut flows = Vec::new();
+    let mut in_section = false;
+    for line in content.lines().map(str::trim) {
+        if let Some(heading) = line.strip_prefix('#') {
+            in_section = heading.trim_start_matches('#').trim().eq_ignore_ascii_case("data flow");
+            continue;
+        }
+        if !in_section {
+            continue;
+        }
+        let Some((number, item)) = line.split_once(". ") else {
+            continue;
+        };
+        if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        let chain = match item.split_once("->").and_then(|(head, _)| head.rfind(':')) {
+            Some(colon) => &item[colon + 1..],
+            None => item,
+        };
+        let steps: Vec<String> = chain.split("->").map(|s| s.trim().trim_matches('`').to_string()).collect();
+        if steps.len() < 2 || steps.iter().any(|s| s.is_empty() || s.contains(char::is_whitespace)) {
+            continue;
+        }
+        flows.push(Flow {
+            name: format!("data-flow-{}", number),
+            steps,
+        });
+    }
+    flows
+}
+
+/// Extract `@doc <url> ["title"]` external documentation links.
+///
+/// The title is optional. URLs are kept as written; shape is checked by
+/// validation so malformed links are reported rather than silently dropped.
+pub fn extract_doc_links(content: &str) -> Vec<DocLink> {
+    content
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("@doc "))
+        .filter_map(|rest| {
+            let rest = rest.trim();
+            let (url, title) = match rest.split_once(char::is_whitespace) {
+                Some((url, title)) => (url, title.trim().trim_matches('"').trim()),
+                None => (rest, ""),
+            };
+            if url.is_empty() {
+                return None;
+            }
+            Some(DocLink {
+                url: url.to_string(),
+                title: title.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn is_iso_date(s: &str) -> bool {
+    s.len() == 10
+        && s.char_indices().all(|(i, c)| match i {
+            4 | 7 => c == '-',
+            _ => c.is_ascii_digit(),
+        })
+}
+
 /// Extract the parent container from a dot-notation module path.
 ///
 /// "bus.calc.indicators" -> Some("bus")
 /// "bus" -> None
+/// "_bin.server.routes" -> Some("_bin.server") (binary targets are containers)
+/// "_bin.server" -> None
 pub fn extract_parent_container(module_path: &str) -> Option<String> {
+    if let Some(rest) = module_path.strip_prefix("_bin.") {
+        return rest
+            .split_once('.')
+            .map(|(target, _)| format!("_bin.{}", target));
+    }
+
     if module_path.contains('.') {
         Some(
             module_path
@@ -140,8 +588,13 @@ pub fn extract_parent_container(module_path: &str) -> Option<String> {
 /// |------|---------|---------|--------|
 /// | `core.rs` | Facade | Entry point | stable |
 /// ```
+///
+/// Cells may contain escaped pipes (`\|`), pipes inside inline code spans,
+/// and links. A row with an empty File cell is a continuation row: its
+/// Purpose text is appended to the previous entry's purpose, so long
+/// descriptions can be wrapped across several rows.
 pub fn extract_file_table(content: &str) -> Vec<FileEntry> {
-    let mut entries = Vec::new();
+    let mut entries: Vec<FileEntry> = Vec::new();
     let mut in_table = false;
     let mut header_seen = false;
 
@@ -169,36 +622,95 @@ pub fn extract_file_table(content: &str) -> Vec<FileEntry> {
                 break; // End of table
             }
 
-            let cells: Vec<&str> = trimmed
-                .split('|')
-                .filter(|s| !s.trim().is_empty())
-                .map(|s| s.trim())
-                .collect();
-
-            if cells.len() >= 4 {
-                let filename = cells[0]
-                    .trim_matches('`')
-                    .trim()
-                    .to_string();
+            let cells = split_table_row(trimmed);
+            if cells.len() < 4 {
+                continue;
+            }
 
-                let (pattern, pattern_status) = parse_pattern_field(cells[1]);
-                let purpose = cells[2].trim().to_string();
-                let health = HealthStatus::parse(cells[3]);
+            let filename = cells[0].trim_matches('`').trim().to_string();
 
-                entries.push(FileEntry {
-                    name: filename,
-                    pattern,
-                    pattern_status,
-                    purpose,
-                    health,
-                });
+            // Continuation row: extends the previous entry's purpose
+            if filename.is_empty() {
+                if let Some(last) = entries.last_mut() {
+                    let extra = cells[2].trim();
+                    if !extra.is_empty() {
+                        if !last.purpose.is_empty() {
+                            last.purpose.push(' ');
+                        }
+                        last.purpose.push_str(extra);
+                    }
+                }
+                continue;
             }
+
+            let (pattern, pattern_status) = parse_pattern_field(&cells[1]);
+            let purpose = cells[2].trim().to_string();
+            let health = HealthStatus::parse(&cells[3]);
+
+            entries.push(FileEntry {
+                name: filename,
+                pattern,
+                pattern_status,
+                purpose,
+                health,
+                loc: None,
+            });
         }
     }
 
     entries
 }
 
+/// Split a markdown table row into trimmed cell strings.
+///
+/// Leading and trailing pipes are optional. A pipe is treated as cell
+/// content (not a separator) when escaped as `\|` or when it appears
+/// inside an inline code span. A backtick without a closing partner is
+/// literal text, so it never swallows the rest of the row. Empty cells
+/// are preserved so columns never shift.
+pub fn split_table_row(row: &str) -> Vec<String> {
+    let trimmed = row.trim();
+    let inner = trimmed.strip_prefix('|').unwrap_or(trimmed);
+
+    // With an odd number of backticks the last one opens no span
+    let unmatched = match inner.matches('`').count() % 2 {
+        1 => inner.rfind('`'),
+        _ => None,
+    };
+
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut in_code = false;
+    let mut chars = inner.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if chars.peek().map(|&(_, next)| next) == Some('|') => {
+                chars.next();
+                current.push('|');
+            }
+            '`' => {
+                if unmatched != Some(i) {
+                    in_code = !in_code;
+                }
+                current.push(c);
+            }
+            '|' if !in_code => {
+                cells.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+
+    // Text after the final pipe is only a cell if the row had no closing pipe
+    if !current.trim().is_empty() {
+        cells.push(current.trim().to_string());
+    }
+
+    cells
+}
+
 /// Parse a pattern field like "Strategy (verified)" into (pattern, status).
 fn parse_pattern_field(field: &str) -> (String, PatternStatus) {
     let trimmed = field.trim();
@@ -214,32 +726,439 @@ fn parse_pattern_field(field: &str) -> (String, PatternStatus) {
     }
 }
 
-/// Parse `@c4 uses target "label" "protocol"` markers from content.
+/// Parse `@c4 uses target "label" ["protocol"] [hidden]` markers from content.
+///
+/// The protocol is optional; a trailing `[hidden]` keeps the relationship
+/// out of diagrams. The legacy `<<uses: target "label" "protocol">>`
+/// form is accepted as an alias. Malformed markers (no target, missing or
+/// unterminated label, trailing garbage) are skipped.
 pub fn extract_relationships(content: &str) -> Vec<Relationship> {
-    let mut rels = Vec::new();
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if let Some(rest) = trimmed.strip_prefix("@c4 uses ") {
-            // Parse: target "label" "protocol"
-            // Split on first quote to get target, then extract quoted strings
-            if let Some(quote_start) = rest.find('"') {
-                let target = rest[..quote_start].trim().to_string();
-                let quoted_part = &rest[quote_start..];
-                let quotes: Vec<&str> = quoted_part
-                    .split('"')
-                    .filter(|s| !s.trim().is_empty())
-                    .collect();
-                if quotes.len() >= 2 {
-                    rels.push(Relationship {
-                        target,
-                        label: quotes[0].to_string(),
-                        protocol: quotes[1].to_string(),
-                    });
-                }
+    content
+        .lines()
+        .filter_map(|line| {
+            let trimmed = line.trim();
+            if let Some(rest) = trimmed.strip_prefix("@c4 uses ") {
+                parse_uses_marker(rest)
+            } else {
+                trimmed
+                    .strip_prefix("<<uses:")
+                    .and_then(|rest| rest.strip_suffix(">>"))
+                    .and_then(parse_uses_marker)
             }
-        }
+        })
+        .collect()
+}
+
+/// Parse the body of a uses marker: `target "label" ["protocol"] [hidden]`.
+fn parse_uses_marker(body: &str) -> Option<Relationship> {
+    let body = body.trim();
+    let quote_start = body.find('"')?;
+    let target = body[..quote_start].trim();
+    if target.is_empty() || target.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let mut quoted = Vec::new();
+    let mut rest = &body[quote_start..];
+    while let Some(after_open) = rest.strip_prefix('"') {
+        let close = after_open.find('"')?;
+        quoted.push(after_open[..close].to_string());
+        rest = after_open[close + 1..].trim_start();
+    }
+    let hidden = rest == "[hidden]";
+    if !rest.is_empty() && !hidden {
+        return None;
+    }
+
+    let mut fields = quoted.into_iter();
+    let label = fields.next().filter(|l| !l.trim().is_empty())?;
+    let protocol = fields.next().unwrap_or_default();
+    if fields.next().is_some() {
+        return None;
+    }
+
+    Some(Relationship {
+        target: target.to_string(),
+        label,
+        protocol,
+        hidden,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "| File | Pattern | Purpose | Health |\n|------|---------|---------|--------|\n";
+
+    #[test]
+    fn pattern_prefers_the_gof_line() {
+        assert_eq!(extract_pattern("# Session\n\nKeeps State per user.\n"), "--");
+        assert_eq!(extract_pattern("# Session\n\nGoF: State\n"), "State");
+        assert_eq!(extract_pattern("Routes Command messages.\n\nGoF: Template Method (verified)\n"), "Template Method");
+    }
+
+    #[test]
+    fn file_table_parses_basic_row() {
+        let content = format!("{}| `core.rs` | Facade | Entry point | stable |\n", HEADER);
+        let entries = extract_file_table(&content);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "core.rs");
+        assert_eq!(entries[0].pattern, "Facade");
+        assert_eq!(entries[0].purpose, "Entry point");
+        assert_eq!(entries[0].health, HealthStatus::Stable);
+    }
+
+    #[test]
+    fn file_table_keeps_escaped_pipes() {
+        let content = format!("{}| `io.rs` | -- | Reads a \\| b streams | active |\n", HEADER);
+        let entries = extract_file_table(&content);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].purpose, "Reads a | b streams");
+        assert_eq!(entries[0].health, HealthStatus::Active);
     }
 
-    rels
+    #[test]
+    fn file_table_keeps_pipes_and_commas_in_inline_code() {
+        let content = format!(
+            "{}| `ops.rs` | -- | Parses `a|b`, `c, d` tokens | stable |\n",
+            HEADER
+        );
+        let entries = extract_file_table(&content);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].purpose, "Parses `a|b`, `c, d` tokens");
+        assert_eq!(entries[0].health, HealthStatus::Stable);
+    }
+
+    #[test]
+    fn file_table_keeps_links() {
+        let content = format!(
+            "{}| `api.rs` | -- | See [spec](https://example.com/a?b=c) | planned |\n",
+            HEADER
+        );
+        let entries = extract_file_table(&content);
+
+        assert_eq!(entries[0].purpose, "See [spec](https://example.com/a?b=c)");
+    }
+
+    #[test]
+    fn file_table_joins_continuation_rows() {
+        let content = format!(
+            "{}| `bus.rs` | Mediator | Routes commands between | active |\n|  |  | agents and the engine |  |\n| `store.rs` | -- | Cache | stable |\n",
+            HEADER
+        );
+        let entries = extract_file_table(&content);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].purpose, "Routes commands between agents and the engine");
+        assert_eq!(entries[0].health, HealthStatus::Active);
+        assert_eq!(entries[1].name, "store.rs");
+    }
+
+    #[test]
+    fn file_table_preserves_empty_cells() {
+        let content = format!("{}| `x.rs` |  | Helper | stable |\n", HEADER);
+        let entries = extract_file_table(&content);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].pattern, "");
+        assert_eq!(entries[0].purpose, "Helper");
+        assert_eq!(entries[0].health, HealthStatus::Stable);
+    }
+
+    #[test]
+    fn split_table_row_without_outer_pipes() {
+        assert_eq!(split_table_row("a | b | c"), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn split_table_row_treats_an_unbalanced_backtick_as_literal() {
+        assert_eq!(
+            split_table_row("| `a.rs` | -- | Quotes with ` marks | active |"),
+            vec!["`a.rs`", "--", "Quotes with ` marks", "active"]
+        );
+    }
+
+    #[test]
+    fn uses_marker_with_protocol() {
+        let rels = extract_relationships("@c4 uses database \"Persists user data\" \"sqlx\"");
+
+        assert_eq!(rels.len(), 1);
+        assert_eq!(rels[0].target, "database");
+        assert_eq!(rels[0].label, "Persists user data");
+        assert_eq!(rels[0].protocol, "sqlx");
+    }
+
+    #[test]
+    fn uses_marker_protocol_is_optional() {
+        let rels = extract_relationships("@c4 uses events \"Publishes domain events\"");
+
+        assert_eq!(rels.len(), 1);
+        assert_eq!(rels[0].target, "events");
+        assert_eq!(rels[0].protocol, "");
+    }
+
+    #[test]
+    fn uses_marker_can_be_hidden() {
+        let rels = extract_relationships("@c4 uses metrics \"Emits counters\" \"statsd\" [hidden]\n@c4 uses log \"Writes\" [hidden]");
+
+        assert_eq!(rels.len(), 2);
+        assert!(rels[0].hidden);
+        assert_eq!(rels[0].protocol, "statsd");
+        assert!(rels[1].hidden);
+        assert_eq!(rels[1].protocol, "");
+        assert!(!extract_relationships("@c4 uses log \"Writes\"")[0].hidden);
+    }
+
+    #[test]
+    fn legacy_uses_marker_is_an_alias() {
+        let rels = extract_relationships("<<uses: bus.lanes \"Routes events\" \"crossbeam\">>");
+
+        assert_eq!(rels.len(), 1);
+        assert_eq!(rels[0].target, "bus.lanes");
+        assert_eq!(rels[0].label, "Routes events");
+        assert_eq!(rels[0].protocol, "crossbeam");
+    }
+
+    #[test]
+    fn malformed_uses_markers_are_skipped() {
+        let content = [
+            "@c4 uses \"No target\" \"http\"",
+            "@c4 uses database",
+            "@c4 uses database \"Unterminated label",
+            "@c4 uses database \"\" \"sqlx\"",
+            "@c4 uses two words \"Label\"",
+            "@c4 uses database \"Label\" \"sqlx\" trailing",
+            "@c4 uses database \"Label\" \"sqlx\" \"extra\"",
+            "@c4 uses database \"Label\" [shown]",
+            "<<uses: database \"Unclosed alias\"",
+        ]
+        .join("\n");
+
+        assert!(extract_relationships(&content).is_empty());
+    }
+
+    #[test]
+    fn doc_comments_are_extracted() {
+        let doc = archidoc_from_source("//! @c4 container\n//!\n//! # Bus\n\npub mod lanes;\n");
+
+        assert_eq!(doc.as_deref(), Some("@c4 container\n\n# Bus"));
+    }
+
+    #[test]
+    fn doc_comments_after_inner_attributes() {
+        let source = "#![allow(rustdoc::invalid_html_tags)]\n//! @c4 component\n//! # Types\n\npub mod annotation;\n";
+        let doc = archidoc_from_source(source);
+
+        assert_eq!(doc.as_deref(), Some("@c4 component\n# Types"));
+    }
+
+    #[test]
+    fn doc_attributes_are_extracted() {
+        let source = "#![doc = \"@c4 container\"]\n#![doc = \"\"]\n#![doc = \"Routes [requests] to \\\"handlers\\\"\"]\n\nfn main() {}\n";
+        let doc = archidoc_from_source(source);
+
+        assert_eq!(
+            doc.as_deref(),
+            Some("@c4 container\n\nRoutes [requests] to \"handlers\"")
+        );
+    }
+
+    #[test]
+    fn multi_line_attributes_and_comments_are_skipped() {
+        let source = "// Copyright header\n#![cfg_attr(\n    docsrs,\n    feature(doc_cfg)\n)]\n/* licence */\n//! @c4 container\n//! # Api\n";
+        let doc = archidoc_from_source(source);
+
+        assert_eq!(doc.as_deref(), Some("@c4 container\n# Api"));
+    }
+
+    #[test]
+    fn doc_comments_after_first_item_are_ignored() {
+        let source = "use std::fs;\n//! @c4 container\n";
+
+        assert_eq!(archidoc_from_source(source), None);
+    }
+
+    #[test]
+    fn include_str_doc_is_skipped() {
+        let source = "#![doc = include_str!(\"../README.md\")]\n//! @c4 container\n";
+
+        assert_eq!(archidoc_from_source(source).as_deref(), Some("@c4 container"));
+    }
+
+    #[test]
+    fn parent_container_of_bin_target_modules() {
+        assert_eq!(extract_parent_container("bus.calc"), Some("bus".to_string()));
+        assert_eq!(extract_parent_container("_bin.server"), None);
+        assert_eq!(
+            extract_parent_container("_bin.server.routes.auth"),
+            Some("_bin.server".to_string())
+        );
+    }
+
+    #[test]
+    fn files_dir_is_extracted() {
+        let content = "@c4 component\n@files-dir ../proto\n\nProto definitions";
+
+        assert_eq!(extract_files_dir(content).as_deref(), Some("../proto"));
+        assert_eq!(extract_description(content), "Proto definitions");
+        assert_eq!(extract_files_dir("@c4 component\n@files-dir \n"), None);
+    }
+
+    #[test]
+    fn standalone_marker_is_extracted() {
+        assert!(extract_standalone("@c4 container\n@standalone\n\nOffline tooling"));
+        assert!(!extract_standalone("@c4 container\n\nNot @standalone in prose"));
+    }
+
+    #[test]
+    fn crosscutting_marker_is_extracted() {
+        assert!(extract_crosscutting("@c4 container\n@crosscutting\n\nStructured logging"));
+        assert!(!extract_crosscutting("@c4 container\n\nNot @crosscutting in prose"));
+    }
+
+    #[test]
+    fn flows_are_extracted() {
+        let content = "@c4 container\n@flow checkout: api -> payments -> db\n@flow refund api -> db\n@flow lone: api\n@flow two words: api -> db";
+
+        assert_eq!(
+            extract_flows(content),
+            vec![Flow {
+                name: "checkout".to_string(),
+                steps: vec!["api".to_string(), "payments".to_string(), "db".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn numbered_data_flow_items_become_flows() {
+        let content = "# Shop\n\n## Data Flow\n\n1. Checkout: `api` -> payments -> store\n2. TODO: Primary flow (e.g., Frontend -> API)\n3. Step three\n4. store -> api\n\n## Deployment\n\n1. ci -> registry\n";
+
+        assert_eq!(
+            extract_data_flow(content),
+            vec![
+                Flow {
+                    name: "data-flow-1".to_string(),
+                    steps: vec!["api".to_string(), "payments".to_string(), "store".to_string()],
+                },
+                Flow {
+                    name: "data-flow-4".to_string(),
+                    steps: vec!["store".to_string(), "api".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn id_marker_is_extracted_when_well_formed() {
+        assert_eq!(extract_id("@c4 component\n@id order_book\n\nBook").as_deref(), Some("order_book"));
+        assert_eq!(extract_id("@id order-book\n@id engine_v2\n").as_deref(), Some("engine_v2"));
+        assert_eq!(extract_id("@c4 component\n\nNo id"), None);
+    }
+
+    #[test]
+    fn element_kind_comes_from_the_first_kind_marker() {
+        assert_eq!(extract_kind("@c4 person\n\n# Trader"), ElementKind::Person);
+        assert_eq!(extract_kind("@c4 external_system\n@c4 uses api \"Calls\""), ElementKind::ExternalSystem);
+        assert_eq!(extract_kind("@c4 uses api \"Calls\"\n@c4 custom:queue"), ElementKind::Custom("queue".to_string()));
+        assert_eq!(extract_kind("@c4 component\n\nSee `@c4 container` markers"), ElementKind::Component);
+        assert_eq!(extract_kind("@c4 custom:\n@c4 systems"), ElementKind::Unknown);
+        assert_eq!(extract_kind("@c4 external\n\n# Stripe"), ElementKind::ExternalSystem);
+        assert_eq!(extract_kind("@c4 person customer \"Buyer\"\n@c4 container"), ElementKind::Container);
+    }
+
+    #[test]
+    fn people_and_external_systems_are_declared_with_an_optional_uses_clause() {
+        let content = "@c4 container\n\n# Shop\n\n@c4 person customer \"Places orders\" uses api \"Browses\" \"HTTPS\"\n@c4 external_system stripe \"Card payments\"\n@c4 external bank \"Unquoted\n@c4 person\n";
+
+        let declarations = extract_declarations(content);
+
+        assert_eq!(
+            declarations,
+            [
+                Ok(Declaration {
+                    name: "customer".to_string(),
+                    kind: ElementKind::Person,
+                    description: "Places orders".to_string(),
+                    relationship: Some(Relationship {
+                        target: "api".to_string(),
+                        label: "Browses".to_string(),
+                        protocol: "HTTPS".to_string(),
+                        hidden: false,
+                    }),
+                }),
+                Ok(Declaration {
+                    name: "stripe".to_string(),
+                    kind: ElementKind::ExternalSystem,
+                    description: "Card payments".to_string(),
+                    relationship: None,
+                }),
+                Err("@c4 external bank \"Unquoted".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn group_marker_is_extracted() {
+        assert_eq!(extract_group("@c4 container\n@group domain:payments\n\nBilling").as_deref(), Some("domain:payments"));
+        assert_eq!(extract_group("@group two words\n@group payments\n").as_deref(), Some("payments"));
+        assert_eq!(extract_group("@c4 container\n\nNo group"), None);
+    }
+
+    #[test]
+    fn latest_review_is_extracted() {
+        let content = "@c4 container\n@reviewed-by alice 2024-06-01\n@reviewed-by bob 2024-09-12 00ff00ff00ff00ff\n\nBus";
+        let review = extract_review(content).unwrap();
+
+        assert_eq!(review.reviewer, "bob");
+        assert_eq!(review.date, "2024-09-12");
+        assert_eq!(review.content_hash.as_deref(), Some("00ff00ff00ff00ff"));
+        assert_eq!(extract_description(content), "Bus");
+    }
+
+    #[test]
+    fn malformed_reviews_are_ignored() {
+        assert_eq!(extract_review("@reviewed-by alice"), None);
+        assert_eq!(extract_review("@reviewed-by alice June 2024"), None);
+        assert_eq!(extract_review("@reviewed-by alice 2024-06-01 abc extra"), None);
+    }
+
+    #[test]
+    fn risks_are_extracted() {
+        let content = "@c4 container\n@risk \"Single point of failure\" severity=high\n@risk Unbounded queue growth\n@risk severity=low\n\nBus";
+        let risks = extract_risks(content);
+
+        assert_eq!(risks.len(), 2);
+        assert_eq!(risks[0].description, "Single point of failure");
+        assert_eq!(risks[0].severity, RiskSeverity::High);
+        assert_eq!(risks[1].description, "Unbounded queue growth");
+        assert_eq!(risks[1].severity, RiskSeverity::Medium);
+        assert_eq!(extract_description(content), "Bus");
+    }
+
+    #[test]
+    fn terms_are_extracted() {
+        let content = "@c4 container\n@term EventLane: A typed channel between producers and consumers\n@term Missing colon\n@term : no name\n\nBus";
+        let terms = extract_terms(content);
+
+        assert_eq!(terms.len(), 1);
+        assert_eq!(terms[0].name, "EventLane");
+        assert_eq!(terms[0].definition, "A typed channel between producers and consumers");
+        assert_eq!(extract_description(content), "Bus");
+    }
+
+    #[test]
+    fn doc_links_are_extracted() {
+        let content = "@c4 container\n@doc https://wiki.example.com/bus  \"Runbook\"\n@doc https://grafana.example.com/d/bus\n\nBus";
+        let links = extract_doc_links(content);
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].url, "https://wiki.example.com/bus");
+        assert_eq!(links[0].title, "Runbook");
+        assert_eq!(links[1].title, "");
+        assert_eq!(extract_description(content), "Bus");
+    }
 }
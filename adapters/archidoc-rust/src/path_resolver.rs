@@ -1,4 +1,6 @@
-use std::path::Path;
+use std::path::{Component, Path};
+
+use archidoc_types::ModuleDoc;
 
 /// Convert a file path to dot-notation module path.
 ///
@@ -8,6 +10,11 @@ use std::path::Path;
 /// - `root/lib.rs` -> `_lib`
 /// - `root/foo.rs` -> `foo` (flat module at root)
 /// - `root/foo/bar.rs` -> `foo.bar` (flat module nested)
+/// - `root/main.rs` -> `_main` (binary crate root)
+/// - `root/bin/cli.rs` or `root/bin/cli/main.rs` -> `_bin.cli` (extra binary target)
+/// - `root/bin/cli/args.rs` -> `_bin.cli.args` (module inside a binary target)
+/// - `root/Order Book/mod.rs` -> `order_book` (names outside the module path
+///   grammar are normalized, see [`renamed_names`])
 pub fn path_to_module_name(path: &Path, root: &Path, filename: &str) -> String {
     let relative = path.strip_prefix(root).unwrap_or(path);
     let parent = relative.parent().unwrap_or(Path::new(""));
@@ -16,10 +23,84 @@ pub fn path_to_module_name(path: &Path, root: &Path, filename: &str) -> String {
         return "_lib".to_string();
     }
 
+    if filename == "main.rs" && parent.as_os_str().is_empty() {
+        return "_main".to_string();
+    }
+
+    let dotted = dotted_path(parent, filename);
+    match dotted.strip_prefix("bin.") {
+        Some(rest) => {
+            let target = match rest.split_once('.') {
+                Some((name, "main")) => name,
+                _ => rest,
+            };
+            format!("_bin.{}", target)
+        }
+        None => dotted,
+    }
+}
+
+/// Check whether a module path names a crate root: `lib.rs`, `main.rs`,
+/// or a binary target under `bin/`.
+///
+/// Crate roots are entry points in their own right, so they are documented
+/// without requiring a C4 marker.
+pub fn is_crate_root(module_path: &str) -> bool {
+    match module_path.strip_prefix("_bin.") {
+        Some(name) => !name.contains('.'),
+        None => module_path == "_lib" || module_path == "_main",
+    }
+}
+
+/// Directory and file names along `path` that are not module path segments
+/// as written (spaces, dots, `-`, capitals, non-ASCII), each with the
+/// segment [`path_to_module_name`] uses instead.
+pub fn renamed_names(path: &Path, root: &Path, filename: &str) -> Vec<(String, String)> {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let parent = relative.parent().unwrap_or(Path::new(""));
+    let mut names: Vec<String> = names(parent).map(|name| name.to_string_lossy().to_string()).collect();
+    if filename != "mod.rs" && filename != "lib.rs" {
+        names.push(filename.strip_suffix(".rs").unwrap_or(filename).to_string());
+    }
+    names
+        .into_iter()
+        .map(|name| {
+            let segment = ModuleDoc::path_segment(&name);
+            (name, segment)
+        })
+        .filter(|(name, segment)| name != segment)
+        .collect()
+}
+
+/// The reserved name (see [`ModuleDoc::RESERVED_NAMES`]) `path` would take
+/// without being the crate root the name stands for, e.g. a `_lib/mod.rs`
+/// that would shadow `lib.rs`.
+pub fn reserved_name(path: &Path, root: &Path, filename: &str) -> Option<String> {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let parent = relative.parent().unwrap_or(Path::new(""));
+    if filename == "lib.rs" || (filename == "main.rs" && parent.as_os_str().is_empty()) {
+        return None;
+    }
+    let dotted = dotted_path(parent, filename);
+    let first = dotted.split('.').next().unwrap_or_default();
+    ModuleDoc::RESERVED_NAMES.contains(&first).then(|| first.to_string())
+}
+
+/// Directory names along `parent`.
+fn names(parent: &Path) -> impl Iterator<Item = &std::ffi::OsStr> {
+    parent.components().filter_map(|c| match c {
+        Component::Normal(name) => Some(name),
+        _ => None,
+    })
+}
+
+/// Join the parent directories and file stem into dot notation, each name
+/// brought into the module path grammar.
+fn dotted_path(parent: &Path, filename: &str) -> String {
+
     // Convert path components to dot notation
-    let parts: Vec<&str> = parent
-        .components()
-        .filter_map(|c| c.as_os_str().to_str())
+    let parts: Vec<String> = names(parent)
+        .map(|name| ModuleDoc::path_segment(&name.to_string_lossy()))
         .collect();
 
     if filename == "mod.rs" {
@@ -27,10 +108,10 @@ pub fn path_to_module_name(path: &Path, root: &Path, filename: &str) -> String {
         parts.join(".")
     } else {
         // Flat module structure: src/foo.rs -> foo, src/foo/bar.rs -> foo.bar
-        let module_name = filename.strip_suffix(".rs").unwrap_or(filename);
+        let module_name = ModuleDoc::path_segment(filename.strip_suffix(".rs").unwrap_or(filename));
         if parts.is_empty() {
             // Standalone file at root (e.g., router.rs)
-            module_name.to_string()
+            module_name
         } else {
             // Nested flat module: src/foo/bar.rs -> foo.bar
             let mut full_parts = parts;
@@ -86,6 +167,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_main_rs_at_root() {
+        let root = PathBuf::from("/src");
+        let path = PathBuf::from("/src/main.rs");
+        assert_eq!(path_to_module_name(&path, &root, "main.rs"), "_main");
+    }
+
+    #[test]
+    fn test_bin_targets() {
+        let root = PathBuf::from("/src");
+        let flat = PathBuf::from("/src/bin/migrate.rs");
+        let dir = PathBuf::from("/src/bin/server/main.rs");
+        let inner = PathBuf::from("/src/bin/server/routes/mod.rs");
+
+        assert_eq!(path_to_module_name(&flat, &root, "migrate.rs"), "_bin.migrate");
+        assert_eq!(path_to_module_name(&dir, &root, "main.rs"), "_bin.server");
+        assert_eq!(
+            path_to_module_name(&inner, &root, "mod.rs"),
+            "_bin.server.routes"
+        );
+    }
+
+    #[test]
+    fn test_nested_main_rs_is_flat_module() {
+        let root = PathBuf::from("/src");
+        let path = PathBuf::from("/src/tools/main.rs");
+        assert_eq!(path_to_module_name(&path, &root, "main.rs"), "tools.main");
+    }
+
+    #[test]
+    fn test_is_crate_root() {
+        assert!(is_crate_root("_lib"));
+        assert!(is_crate_root("_main"));
+        assert!(is_crate_root("_bin.server"));
+        assert!(!is_crate_root("_bin.server.routes"));
+        assert!(!is_crate_root("bus"));
+    }
+
+    #[test]
+    fn names_outside_the_grammar_are_normalized() {
+        let root = PathBuf::from("/src");
+        let path = PathBuf::from("/src/Order Book/v1.2/mod.rs");
+        assert_eq!(path_to_module_name(&path, &root, "mod.rs"), "order_book.v1_2");
+        assert_eq!(
+            renamed_names(&path, &root, "mod.rs"),
+            vec![
+                ("Order Book".to_string(), "order_book".to_string()),
+                ("v1.2".to_string(), "v1_2".to_string())
+            ]
+        );
+
+        let flat = PathBuf::from("/src/bus/Événements.rs");
+        assert_eq!(path_to_module_name(&flat, &root, "Événements.rs"), "bus._v_nements");
+        assert!(renamed_names(&PathBuf::from("/src/bus/calc/mod.rs"), &root, "mod.rs").is_empty());
+    }
+
+    #[test]
+    fn reserved_names_outside_crate_roots() {
+        let root = PathBuf::from("/src");
+        assert_eq!(reserved_name(&PathBuf::from("/src/_lib/mod.rs"), &root, "mod.rs").as_deref(), Some("_lib"));
+        assert_eq!(reserved_name(&PathBuf::from("/src/_main.rs"), &root, "_main.rs").as_deref(), Some("_main"));
+        assert_eq!(reserved_name(&PathBuf::from("/src/lib.rs"), &root, "lib.rs"), None);
+        assert_eq!(reserved_name(&PathBuf::from("/src/main.rs"), &root, "main.rs"), None);
+        assert_eq!(reserved_name(&PathBuf::from("/src/bin/cli.rs"), &root, "cli.rs"), None);
+        assert_eq!(reserved_name(&PathBuf::from("/src/bus/_lib/mod.rs"), &root, "mod.rs"), None);
+    }
+
     #[test]
     fn test_flat_module_deeply_nested() {
         let root = PathBuf::from("/src");
@@ -11,6 +11,8 @@
 
 use std::path::Path;
 
+use archidoc_types::{HeuristicConfig, PatternEvidence};
+use syn::spanned::Spanned;
 use syn::{Item, Visibility};
 
 use crate::walker;
@@ -97,6 +99,11 @@ pub fn check_strategy(source: &str) -> bool {
 /// Looks for `pub use` re-exports or `pub mod` declarations — a Facade
 /// provides a simplified entry point by re-exporting from submodules.
 pub fn check_facade(source: &str) -> bool {
+    check_facade_with(source, &HeuristicConfig::default())
+}
+
+/// [`check_facade`] with the `pub mod` count from `thresholds`.
+pub fn check_facade_with(source: &str, thresholds: &HeuristicConfig) -> bool {
     if let Ok(file) = syn::parse_file(source) {
         let mut pub_use_count = 0;
         let mut pub_mod_count = 0;
@@ -117,8 +124,8 @@ pub fn check_facade(source: &str) -> bool {
             }
         }
 
-        // A Facade must have at least one pub use or two pub mod declarations
-        pub_use_count >= 1 || pub_mod_count >= 2
+        // A Facade must have at least one pub use or enough pub mod declarations
+        pub_use_count >= 1 || pub_mod_count >= thresholds.facade_min_pub_mods
     } else {
         false
     }
@@ -128,6 +135,11 @@ pub fn check_facade(source: &str) -> bool {
 ///
 /// Looks for chained setter methods returning Self, or a `build()` method.
 pub fn check_builder(source: &str) -> bool {
+    check_builder_with(source, &HeuristicConfig::default())
+}
+
+/// [`check_builder`] with the setter count from `thresholds`.
+pub fn check_builder_with(source: &str, thresholds: &HeuristicConfig) -> bool {
     if let Ok(file) = syn::parse_file(source) {
         for item in &file.items {
             if let Item::Impl(impl_item) = item {
@@ -150,8 +162,8 @@ pub fn check_builder(source: &str) -> bool {
                     }
                 }
 
-                // Builder pattern: build() method, or 2+ chained setters returning Self
-                if has_build || has_self_return >= 2 {
+                // Builder pattern: build() method, or enough chained setters returning Self
+                if has_build || has_self_return >= thresholds.builder_min_setters {
                     return true;
                 }
             }
@@ -203,6 +215,11 @@ pub fn check_factory(source: &str) -> bool {
 ///
 /// Looks for a struct wrapping another type combined with a trait implementation.
 pub fn check_adapter(source: &str) -> bool {
+    check_adapter_with(source, &HeuristicConfig::default())
+}
+
+/// [`check_adapter`] with the wrapper field-count range from `thresholds`.
+pub fn check_adapter_with(source: &str, thresholds: &HeuristicConfig) -> bool {
     if let Ok(file) = syn::parse_file(source) {
         let mut has_wrapper_struct = false;
         let mut has_trait_impl = false;
@@ -210,17 +227,16 @@ pub fn check_adapter(source: &str) -> bool {
         for item in &file.items {
             match item {
                 Item::Struct(s) => {
-                    // A wrapper struct typically has 1-2 fields
+                    // A wrapper struct typically has few fields
                     if let syn::Fields::Named(fields) = &s.fields {
-                        if (1..=2).contains(&fields.named.len()) {
+                        let range = thresholds.adapter_min_fields..=thresholds.adapter_max_fields;
+                        if range.contains(&fields.named.len()) {
                             has_wrapper_struct = true;
                         }
                     }
                 }
-                Item::Impl(impl_item) => {
-                    if impl_item.trait_.is_some() {
-                        has_trait_impl = true;
-                    }
+                Item::Impl(impl_item) if impl_item.trait_.is_some() => {
+                    has_trait_impl = true;
                 }
                 _ => {}
             }
@@ -260,10 +276,8 @@ pub fn check_decorator(source: &str) -> bool {
                             }
                         }
                     }
-                    Item::Impl(impl_item) => {
-                        if impl_item.trait_.is_some() {
-                            has_trait_impl = true;
-                        }
+                    Item::Impl(impl_item) if impl_item.trait_.is_some() => {
+                        has_trait_impl = true;
                     }
                     _ => {}
                 }
@@ -318,18 +332,265 @@ pub fn check_command(source: &str) -> bool {
     false
 }
 
+/// Check if Rust source code structurally matches the Repository pattern.
+///
+/// Looks for a trait declaring both read-style (`find`, `get`, `load`, ...)
+/// and write-style (`save`, `insert`, `delete`, ...) methods, or a struct
+/// holding a connection/pool/client field whose inherent impl has both.
+pub fn check_repository(source: &str) -> bool {
+    let Ok(file) = syn::parse_file(source) else {
+        return false;
+    };
+
+    let mut storage_structs = Vec::new();
+    for item in &file.items {
+        match item {
+            Item::Trait(trait_item) => {
+                let names = trait_item.items.iter().filter_map(|method| match method {
+                    syn::TraitItem::Fn(m) => Some(m.sig.ident.to_string()),
+                    _ => None,
+                });
+                if has_read_and_write(names) {
+                    return true;
+                }
+            }
+            Item::Struct(s) if holds_storage_handle(&s.fields) => {
+                storage_structs.push(s.ident.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    file.items.iter().any(|item| match item {
+        Item::Impl(impl_item) if impl_item.trait_.is_none() => {
+            let self_ty = &impl_item.self_ty;
+            let self_name = quote::quote!(#self_ty).to_string();
+            let names = impl_item.items.iter().filter_map(|method| match method {
+                syn::ImplItem::Fn(m) => Some(m.sig.ident.to_string()),
+                _ => None,
+            });
+            storage_structs.contains(&self_name) && has_read_and_write(names)
+        }
+        _ => false,
+    })
+}
+
+/// Whether the method names include at least one query and one mutation.
+fn has_read_and_write(names: impl Iterator<Item = String>) -> bool {
+    const READS: &[&str] = &["find", "get", "load", "fetch", "list", "query", "all", "by_id", "exists", "count"];
+    const WRITES: &[&str] = &["save", "insert", "add", "create", "store", "put", "update", "upsert", "delete", "remove"];
+    let starts_with_any = |name: &str, words: &[&str]| {
+        words.iter().any(|w| name == *w || name.starts_with(&format!("{}_", w)))
+    };
+
+    let (mut read, mut write) = (false, false);
+    for name in names {
+        read |= starts_with_any(&name, READS);
+        write |= starts_with_any(&name, WRITES);
+    }
+    read && write
+}
+
+/// Whether a struct has a field whose type looks like a database or store handle.
+fn holds_storage_handle(fields: &syn::Fields) -> bool {
+    const HANDLES: &[&str] = &["Connection", "Conn", "Pool", "Client", "Database", "Db", "Session", "Transaction"];
+    fields.iter().any(|field| {
+        let ty = &field.ty;
+        let ty_str = quote::quote!(#ty).to_string();
+        ty_str
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|word| HANDLES.iter().any(|h| word.ends_with(h)))
+    })
+}
+
+/// Check if Rust source code structurally matches the Mediator pattern.
+///
+/// Looks for a trait with routing methods (`notify`, `route`, `dispatch`,
+/// ...), or a struct holding two or more collaborators of different types
+/// (shared references, channels, or trait objects) whose inherent impl has
+/// a routing method.
+pub fn check_mediator(source: &str) -> bool {
+    let Ok(file) = syn::parse_file(source) else {
+        return false;
+    };
+
+    let mut hubs = Vec::new();
+    for item in &file.items {
+        match item {
+            Item::Trait(trait_item) => {
+                let routes = trait_item.items.iter().any(|method| match method {
+                    syn::TraitItem::Fn(m) => is_routing_method(&m.sig.ident.to_string()),
+                    _ => false,
+                });
+                if routes {
+                    return true;
+                }
+            }
+            Item::Struct(s) if collaborator_types(&s.fields) >= 2 => {
+                hubs.push(s.ident.to_string());
+            }
+            _ => {}
+        }
+    }
+
+    file.items.iter().any(|item| match item {
+        Item::Impl(impl_item) if impl_item.trait_.is_none() => {
+            let self_ty = &impl_item.self_ty;
+            let routes = impl_item.items.iter().any(|method| match method {
+                syn::ImplItem::Fn(m) => is_routing_method(&m.sig.ident.to_string()),
+                _ => false,
+            });
+            routes && hubs.contains(&quote::quote!(#self_ty).to_string())
+        }
+        _ => false,
+    })
+}
+
+fn is_routing_method(name: &str) -> bool {
+    const ROUTES: &[&str] = &["notify", "route", "dispatch", "forward", "relay", "mediate", "broadcast", "handle"];
+    ROUTES.iter().any(|r| name == *r || name.starts_with(&format!("{}_", r))) || name.starts_with("on_")
+}
+
+/// Number of distinct field types that refer to a collaborator rather than
+/// owning plain data.
+fn collaborator_types(fields: &syn::Fields) -> usize {
+    const MARKERS: &[&str] = &["Arc <", "Rc <", "Weak <", "&", "Sender", "Box < dyn", "dyn "];
+    let mut types: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            quote::quote!(#ty).to_string()
+        })
+        .filter(|ty| MARKERS.iter().any(|m| ty.contains(m)))
+        .collect();
+    types.sort();
+    types.dedup();
+    types.len()
+}
+
+/// Check if Rust source code structurally matches the State pattern.
+///
+/// Looks for match-driven transitions — a function that matches and
+/// produces or assigns a variant of an enum defined in the file — or a
+/// context struct whose trait-object field is reassigned at runtime.
+pub fn check_state(source: &str) -> bool {
+    let Ok(file) = syn::parse_file(source) else {
+        return false;
+    };
+
+    let enums: Vec<String> = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Enum(e) => Some(e.ident.to_string()),
+            _ => None,
+        })
+        .collect();
+    let dyn_fields: Vec<(String, String)> = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Struct(s) => Some(s),
+            _ => None,
+        })
+        .flat_map(|s| {
+            s.fields.iter().filter_map(move |field| {
+                let ty = &field.ty;
+                let name = field.ident.as_ref()?;
+                quote::quote!(#ty)
+                    .to_string()
+                    .contains("dyn ")
+                    .then(|| (s.ident.to_string(), name.to_string()))
+            })
+        })
+        .collect();
+
+    let is_transition = |body: &str, self_name: Option<&str>| {
+        let produces = |ty: &str| body.contains(&format!("=> {} ::", ty)) || body.contains(&format!("= {} ::", ty));
+        body.contains("match ")
+            && (enums.iter().any(|e| produces(e))
+                || self_name.is_some_and(|name| enums.iter().any(|e| e == name) && produces("Self")))
+    };
+
+    file.items.iter().any(|item| match item {
+        Item::Fn(func) => {
+            let block = &func.block;
+            is_transition(&quote::quote!(#block).to_string(), None)
+        }
+        Item::Impl(impl_item) => {
+            let self_ty = &impl_item.self_ty;
+            let self_name = quote::quote!(#self_ty).to_string();
+            impl_item.items.iter().any(|method| {
+                let syn::ImplItem::Fn(m) = method else {
+                    return false;
+                };
+                let block = &m.block;
+                let body = quote::quote!(#block).to_string();
+                is_transition(&body, Some(&self_name))
+                    || dyn_fields
+                        .iter()
+                        .any(|(owner, field)| *owner == self_name && body.contains(&format!("self . {} =", field)))
+            })
+        }
+        _ => false,
+    })
+}
+
+/// Check if Rust source code structurally matches the Template Method pattern.
+///
+/// Looks for a trait with a default method that calls one of the trait's
+/// required methods — the fixed algorithm deferring steps to implementors.
+pub fn check_template_method(source: &str) -> bool {
+    let Ok(file) = syn::parse_file(source) else {
+        return false;
+    };
+
+    file.items.iter().any(|item| {
+        let Item::Trait(trait_item) = item else {
+            return false;
+        };
+        let methods: Vec<&syn::TraitItemFn> = trait_item
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                syn::TraitItem::Fn(m) => Some(m),
+                _ => None,
+            })
+            .collect();
+        let required: Vec<String> = methods
+            .iter()
+            .filter(|m| m.default.is_none())
+            .map(|m| m.sig.ident.to_string())
+            .collect();
+
+        methods.iter().filter_map(|m| m.default.as_ref()).any(|block| {
+            let body = quote::quote!(#block).to_string();
+            required.iter().any(|name| body.contains(&format!("self . {} (", name)))
+        })
+    })
+}
+
 /// Run the appropriate heuristic for a named GoF pattern.
 pub fn check_pattern(pattern: &str, source: &str) -> bool {
+    check_pattern_with(pattern, source, &HeuristicConfig::default())
+}
+
+/// [`check_pattern`] with tunable thresholds from `archidoc.toml`.
+pub fn check_pattern_with(pattern: &str, source: &str, thresholds: &HeuristicConfig) -> bool {
     match pattern {
         "Observer" => check_observer(source),
         "Strategy" => check_strategy(source),
-        "Facade" => check_facade(source),
-        "Builder" => check_builder(source),
+        "Facade" => check_facade_with(source, thresholds),
+        "Builder" => check_builder_with(source, thresholds),
         "Factory" => check_factory(source),
-        "Adapter" => check_adapter(source),
+        "Adapter" => check_adapter_with(source, thresholds),
         "Decorator" => check_decorator(source),
         "Singleton" => check_singleton(source),
         "Command" => check_command(source),
+        "Repository" => check_repository(source),
+        "Mediator" => check_mediator(source),
+        "State" => check_state(source),
+        "Template Method" => check_template_method(source),
         _ => false,
     }
 }
@@ -339,10 +600,103 @@ pub fn check_pattern(pattern: &str, source: &str) -> bool {
 /// Returns true if ANY file in the directory passes the pattern heuristic.
 /// File discovery is delegated to `walker::read_rs_sources` to keep this
 /// module focused on AST analysis.
-pub fn check_module_pattern(pattern: &str, source_dir: &Path) -> bool {
+pub fn check_module_pattern(pattern: &str, source_dir: &Path, thresholds: &HeuristicConfig) -> bool {
+    walker::read_rs_sources(source_dir)
+        .iter()
+        .any(|(_, source)| check_pattern_with(pattern, source, thresholds))
+}
+
+/// Locate the evidence for a pattern in a module's source directory.
+///
+/// The evidence is the first file, in name order, that passes the heuristic,
+/// together with the item in it that carries the pattern (see
+/// [`evidence_item`]). `None` when no file passes.
+pub fn find_module_evidence(
+    pattern: &str,
+    source_dir: &Path,
+    thresholds: &HeuristicConfig,
+) -> Option<PatternEvidence> {
     walker::read_rs_sources(source_dir)
+        .into_iter()
+        .find(|(_, source)| check_pattern_with(pattern, source, thresholds))
+        .map(|(file, source)| PatternEvidence {
+            item: evidence_item(pattern, &source, thresholds),
+            file,
+        })
+}
+
+/// Check one cataloged file for a pattern, with the same evidence as
+/// [`find_module_evidence`]. `None` when the file is missing or does not
+/// pass the heuristic.
+pub fn find_file_evidence(pattern: &str, file: &Path, thresholds: &HeuristicConfig) -> Option<PatternEvidence> {
+    let source = std::fs::read_to_string(file).ok()?;
+    if !check_pattern_with(pattern, &source, thresholds) {
+        return None;
+    }
+    Some(PatternEvidence {
+        file: file.file_name()?.to_string_lossy().to_string(),
+        item: evidence_item(pattern, &source, thresholds),
+    })
+}
+
+/// Name the item in a matching file that carries the pattern.
+///
+/// Prefers the first named item that passes the heuristic on its own source
+/// text. Heuristics that need several items together (a wrapper struct plus
+/// its trait impl) fall back to the type the file's impls are for, then to
+/// the first type declared. Empty when the file declares no types.
+fn evidence_item(pattern: &str, source: &str, thresholds: &HeuristicConfig) -> String {
+    let Ok(file) = syn::parse_file(source) else {
+        return String::new();
+    };
+    let lines: Vec<&str> = source.lines().collect();
+
+    for item in &file.items {
+        let Some(name) = item_name(item) else {
+            continue;
+        };
+        let span = item.span();
+        let start = span.start().line.saturating_sub(1);
+        let end = span.end().line.min(lines.len());
+        if start < end && check_pattern_with(pattern, &lines[start..end].join("\n"), thresholds) {
+            return name;
+        }
+    }
+
+    file.items
         .iter()
-        .any(|(_, source)| check_pattern(pattern, source))
+        .find_map(|item| match item {
+            Item::Impl(imp) => impl_type_name(imp),
+            _ => None,
+        })
+        .or_else(|| {
+            file.items.iter().find_map(|item| match item {
+                Item::Struct(_) | Item::Enum(_) | Item::Trait(_) => item_name(item),
+                _ => None,
+            })
+        })
+        .unwrap_or_default()
+}
+
+fn item_name(item: &Item) -> Option<String> {
+    match item {
+        Item::Struct(s) => Some(s.ident.to_string()),
+        Item::Enum(e) => Some(e.ident.to_string()),
+        Item::Trait(t) => Some(t.ident.to_string()),
+        Item::Fn(f) => Some(f.sig.ident.to_string()),
+        Item::Static(s) => Some(s.ident.to_string()),
+        Item::Mod(m) => Some(m.ident.to_string()),
+        Item::Impl(imp) => impl_type_name(imp),
+        _ => None,
+    }
+}
+
+/// `Foo` for `impl Foo` and `impl Trait for Foo<T>`.
+fn impl_type_name(imp: &syn::ItemImpl) -> Option<String> {
+    match imp.self_ty.as_ref() {
+        syn::Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -399,6 +753,23 @@ mod tests {
         assert!(!check_facade(source));
     }
 
+    #[test]
+    fn thresholds_tighten_facade_adapter_and_builder() {
+        let strict = HeuristicConfig {
+            facade_min_pub_mods: 3,
+            adapter_min_fields: 1,
+            adapter_max_fields: 1,
+            builder_min_setters: 3,
+        };
+        let mods = "pub mod calc;\npub mod store;\n";
+        let adapter = "pub struct Wrap { a: u8, b: u8 }\nimpl Tr for Wrap {}\n";
+        let builder = "impl B { fn a(self) -> Self { self } fn b(self) -> Self { self } }";
+
+        assert!(check_facade(mods) && !check_facade_with(mods, &strict));
+        assert!(check_adapter(adapter) && !check_adapter_with(adapter, &strict));
+        assert!(check_builder(builder) && !check_builder_with(builder, &strict));
+    }
+
     #[test]
     fn observer_detects_channel() {
         let source = r#"
@@ -437,6 +808,163 @@ mod tests {
         assert!(!check_observer(source));
     }
 
+    #[test]
+    fn repository_detects_crud_trait() {
+        let source = r#"
+            pub trait OrderRepository {
+                fn find_by_id(&self, id: u64) -> Option<Order>;
+                fn save(&mut self, order: Order);
+                fn delete(&mut self, id: u64);
+            }
+        "#;
+        assert!(check_repository(source));
+    }
+
+    #[test]
+    fn repository_detects_struct_wrapping_a_pool() {
+        let source = r#"
+            pub struct Orders {
+                pool: sqlx::PgPool,
+            }
+            impl Orders {
+                pub async fn get(&self, id: i64) -> Order { todo!() }
+                pub async fn insert(&self, order: &Order) { todo!() }
+            }
+        "#;
+        assert!(check_repository(source));
+    }
+
+    #[test]
+    fn repository_rejects_read_only_or_unbacked_types() {
+        let read_only = "pub trait Lookup { fn find(&self, key: &str) -> Option<String>; fn list(&self) -> Vec<String>; }";
+        assert!(!check_repository(read_only));
+
+        let in_memory = r#"
+            pub struct Cache { items: Vec<String> }
+            impl Cache {
+                pub fn get(&self, i: usize) -> &str { &self.items[i] }
+                pub fn insert(&mut self, item: String) { self.items.push(item) }
+            }
+        "#;
+        assert!(!check_repository(in_memory));
+    }
+
+    #[test]
+    fn mediator_detects_routing_trait() {
+        let source = "pub trait Mediator { fn notify(&self, sender: &str, event: Event); }";
+        assert!(check_mediator(source));
+    }
+
+    #[test]
+    fn mediator_detects_hub_routing_between_collaborators() {
+        let source = r#"
+            pub struct Bus {
+                feeds: Arc<FeedManager>,
+                orders: Sender<OrderCommand>,
+                risk: Box<dyn RiskCheck>,
+            }
+            impl Bus {
+                pub fn route(&self, msg: Message) {
+                    match msg {
+                        Message::Tick(t) => self.feeds.push(t),
+                        Message::Order(o) => self.orders.send(o).unwrap(),
+                    }
+                }
+            }
+        "#;
+        assert!(check_mediator(source));
+    }
+
+    #[test]
+    fn mediator_rejects_single_collaborator_or_no_routing() {
+        let one = r#"
+            pub struct Wrapper { inner: Arc<Feed> }
+            impl Wrapper { pub fn dispatch(&self) {} }
+        "#;
+        assert!(!check_mediator(one));
+
+        let no_routing = r#"
+            pub struct Hub { a: Arc<A>, b: Arc<B> }
+            impl Hub { pub fn new(a: Arc<A>, b: Arc<B>) -> Self { Self { a, b } } }
+        "#;
+        assert!(!check_mediator(no_routing));
+    }
+
+    #[test]
+    fn state_detects_match_driven_enum_transitions() {
+        let source = r#"
+            pub enum Connection { Idle, Connecting, Open }
+            pub struct Session { state: Connection }
+            impl Session {
+                pub fn on_event(&mut self, event: Event) {
+                    self.state = match (&self.state, event) {
+                        (Connection::Idle, Event::Dial) => Connection::Connecting,
+                        (_, Event::Drop) => Connection::Idle,
+                        _ => return,
+                    };
+                }
+            }
+        "#;
+        assert!(check_state(source));
+
+        let self_transition = r#"
+            enum Light { Red, Green }
+            impl Light {
+                fn next(self) -> Self {
+                    match self { Light::Red => Self::Green, Light::Green => Self::Red }
+                }
+            }
+        "#;
+        assert!(check_state(self_transition));
+    }
+
+    #[test]
+    fn state_detects_swapped_trait_object() {
+        let source = r#"
+            pub struct Context { state: Box<dyn State> }
+            impl Context {
+                pub fn transition(&mut self, next: Box<dyn State>) { self.state = next; }
+            }
+        "#;
+        assert!(check_state(source));
+    }
+
+    #[test]
+    fn state_rejects_enum_without_transitions() {
+        let source = r#"
+            pub enum Level { Low, High }
+            pub fn describe(level: &Level) -> &str {
+                match level { Level::Low => "low", Level::High => "high" }
+            }
+        "#;
+        assert!(!check_state(source));
+    }
+
+    #[test]
+    fn template_method_detects_default_calling_required() {
+        let source = r#"
+            pub trait Report {
+                fn header(&self) -> String;
+                fn body(&self) -> String;
+                fn render(&self) -> String {
+                    format!("{}\n{}", self.header(), self.body())
+                }
+            }
+        "#;
+        assert!(check_template_method(source));
+    }
+
+    #[test]
+    fn template_method_rejects_independent_defaults() {
+        let source = r#"
+            pub trait Named {
+                fn name(&self) -> String;
+                fn greeting(&self) -> String { "hello".to_string() }
+            }
+        "#;
+        assert!(!check_template_method(source));
+    }
+
     #[test]
     fn check_pattern_dispatches_correctly() {
         let strategy_src = "pub trait Algo { fn run(&self); }";
@@ -445,4 +973,38 @@ mod tests {
         assert!(!check_pattern("UnknownPattern", strategy_src));
     }
 
+    #[test]
+    fn evidence_names_the_matching_item() {
+        let source = r#"
+pub struct Request;
+
+pub struct RequestBuilder {
+    url: String,
+}
+
+impl RequestBuilder {
+    pub fn build(self) -> Request {
+        Request
+    }
+}
+"#;
+        assert_eq!(evidence_item("Builder", source, &HeuristicConfig::default()), "RequestBuilder");
+    }
+
+    #[test]
+    fn evidence_falls_back_to_the_impl_type() {
+        // Adapter needs the struct and the trait impl together
+        let source = r#"
+pub struct SyslogAdapter {
+    inner: Syslog,
+}
+
+impl Logger for SyslogAdapter {
+    fn log(&self, message: &str) {}
+}
+"#;
+        assert_eq!(evidence_item("Adapter", source, &HeuristicConfig::default()), "SyslogAdapter");
+        assert_eq!(evidence_item("Facade", "pub mod a;\npub mod b;\n", &HeuristicConfig::default()), "");
+    }
+
 }
@@ -1,13 +1,12 @@
-use std::path::Path;
-
-use archidoc_types::{ModuleDoc, PatternStatus};
+use archidoc_types::{HeuristicConfig, ModuleDoc, PatternStatus, StatusChange};
 
 use crate::pattern_heuristic;
 
 /// Recognized patterns that have structural heuristics.
-const VERIFIABLE_PATTERNS: &[&str] = &[
+pub(crate) const VERIFIABLE_PATTERNS: &[&str] = &[
     "Observer", "Strategy", "Facade", "Builder", "Factory",
-    "Adapter", "Decorator", "Singleton", "Command",
+    "Adapter", "Decorator", "Singleton", "Command", "Repository",
+    "Mediator", "State", "Template Method",
 ];
 
 /// H7: Auto-promote pattern labels from `planned` to `verified`
@@ -17,29 +16,56 @@ const VERIFIABLE_PATTERNS: &[&str] = &[
 /// - Skip if pattern_status is already Verified
 /// - Skip if pattern has no heuristic (not in VERIFIABLE_PATTERNS)
 /// - Scan the module's source directory for structural evidence
-/// - Promote to Verified if the heuristic passes
+/// - Promote to Verified if the heuristic passes, recording where the
+///   evidence was found in `pattern_evidence`
+///
+/// File-table rows claiming a pattern are checked the same way, each
+/// against its own file only, so one module's files are verified
+/// independently of each other and of the module.
+///
+/// `thresholds` tunes the heuristics (`[heuristics]` in `archidoc.toml`).
 ///
-/// Returns the number of modules promoted.
-pub fn auto_promote(docs: &mut [ModuleDoc]) -> usize {
-    let mut promoted = 0;
+/// Returns one status change per module or file promoted, for the audit
+/// log; files are logged as `module/file.rs`.
+pub fn auto_promote(docs: &mut [ModuleDoc], thresholds: &HeuristicConfig) -> Vec<StatusChange> {
+    let mut promoted = Vec::new();
 
     for doc in docs.iter_mut() {
-        if doc.pattern_status != PatternStatus::Planned {
-            continue;
-        }
-
-        if !VERIFIABLE_PATTERNS.contains(&doc.pattern.as_str()) {
-            continue;
-        }
-
-        let source_dir = match Path::new(&doc.source_file).parent() {
+        let source_dir = match doc.catalog_dir() {
             Some(dir) => dir,
             None => continue,
         };
 
-        if pattern_heuristic::check_module_pattern(&doc.pattern, source_dir) {
-            doc.pattern_status = PatternStatus::Verified;
-            promoted += 1;
+        if doc.pattern_status == PatternStatus::Planned && VERIFIABLE_PATTERNS.contains(&doc.pattern.as_str()) {
+            let evidence = pattern_heuristic::find_module_evidence(&doc.pattern, &source_dir, thresholds);
+            if let Some(evidence) = evidence {
+                promoted.push(StatusChange {
+                    element: doc.module_path.clone(),
+                    pattern: doc.pattern.clone(),
+                    from: doc.pattern_status,
+                    to: PatternStatus::Verified,
+                    evidence: Some(evidence.clone()),
+                });
+                doc.pattern_status = PatternStatus::Verified;
+                doc.pattern_evidence = Some(evidence);
+            }
+        }
+
+        for file in doc.files.iter_mut() {
+            if file.pattern_status != PatternStatus::Planned || !VERIFIABLE_PATTERNS.contains(&file.pattern.as_str()) {
+                continue;
+            }
+            let path = source_dir.join(&file.name);
+            if let Some(evidence) = pattern_heuristic::find_file_evidence(&file.pattern, &path, thresholds) {
+                promoted.push(StatusChange {
+                    element: format!("{}/{}", doc.module_path, file.name),
+                    pattern: file.pattern.clone(),
+                    from: file.pattern_status,
+                    to: PatternStatus::Verified,
+                    evidence: Some(evidence),
+                });
+                file.pattern_status = PatternStatus::Verified;
+            }
         }
     }
 
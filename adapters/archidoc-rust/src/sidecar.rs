@@ -0,0 +1,383 @@
+use std::fs;
+use std::path::Path;
+
+use archidoc_types::ElementKind;
+use serde::Deserialize;
+
+/// File names recognized as sidecar annotation files.
+pub const SIDECAR_FILENAMES: [&str; 3] = ["archidoc.toml", ".archidoc.yaml", ".archidoc.yml"];
+
+/// A module annotation stored next to the code instead of in doc comments.
+///
+/// Used for directories whose source headers cannot be edited (generated,
+/// vendored, or compliance-locked code). A file without a `c4` key is not a
+/// sidecar — this keeps a project-level `archidoc.toml` from being mistaken
+/// for a module annotation.
+///
+/// ```toml
+/// c4 = "component"
+/// name = "Generated Protos"
+/// description = "gRPC stubs generated from proto/."
+/// pattern = "Adapter"
+///
+/// [[uses]]
+/// target = "bus"
+/// label = "Publishes events"
+///
+/// [[files]]
+/// file = "client.rs"
+/// purpose = "Generated client"
+/// health = "stable"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Sidecar {
+    pub c4: Option<String>,
+    /// Renders `@id`.
+    pub id: Option<String>,
+    /// Renders `@group`, e.g. `"domain:payments"`.
+    pub group: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub pattern: Option<String>,
+    pub pattern_status: Option<String>,
+    pub files_dir: Option<String>,
+    /// Renders `@standalone`.
+    #[serde(default)]
+    pub standalone: bool,
+    /// Renders `@crosscutting`.
+    #[serde(default)]
+    pub crosscutting: bool,
+    #[serde(default)]
+    pub uses: Vec<SidecarUse>,
+    #[serde(default)]
+    pub files: Vec<SidecarFile>,
+    /// Extra annotation lines appended verbatim to the rendered doc content.
+    pub annotations: Option<String>,
+}
+
+/// A `@c4 uses` relationship declared in a sidecar.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SidecarUse {
+    pub target: String,
+    pub label: String,
+    #[serde(default)]
+    pub protocol: String,
+    /// Renders a trailing `[hidden]`.
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+/// A file table row declared in a sidecar.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SidecarFile {
+    pub file: String,
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub purpose: String,
+    pub health: Option<String>,
+}
+
+/// Check whether a file name is a sidecar annotation file.
+pub fn is_sidecar_filename(filename: &str) -> bool {
+    SIDECAR_FILENAMES.contains(&filename)
+}
+
+/// Read a sidecar file and render it as annotation text.
+///
+/// Returns `Ok(None)` when the file has no `c4` key, and `Err` when it
+/// cannot be read or parsed.
+pub fn archidoc_from_sidecar(path: &Path) -> Result<Option<String>, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+    let sidecar = parse_sidecar(&text, filename)
+        .map_err(|e| format!("invalid sidecar {}: {}", path.display(), e))?;
+
+    match sidecar {
+        Some(sidecar) => sidecar.to_annotation().map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Parse sidecar text, choosing TOML or YAML by file name.
+///
+/// Files without a `c4` key yield `Ok(None)` without being validated
+/// further, since they may hold unrelated configuration.
+pub fn parse_sidecar(text: &str, filename: &str) -> Result<Option<Sidecar>, String> {
+    let is_yaml = filename.ends_with(".yaml") || filename.ends_with(".yml");
+
+    let has_c4 = if is_yaml {
+        let value: serde_yaml::Value = serde_yaml::from_str(text).map_err(|e| e.to_string())?;
+        value.get("c4").is_some()
+    } else {
+        let value: toml::Table = toml::from_str(text).map_err(|e| e.to_string())?;
+        value.contains_key("c4")
+    };
+    if !has_c4 {
+        return Ok(None);
+    }
+
+    let sidecar = if is_yaml {
+        serde_yaml::from_str(text).map_err(|e| e.to_string())?
+    } else {
+        toml::from_str(text).map_err(|e| e.to_string())?
+    };
+
+    Ok(Some(sidecar))
+}
+
+impl Sidecar {
+    /// Render the sidecar as the equivalent `//!` annotation content,
+    /// without comment prefixes.
+    pub fn to_annotation(&self) -> Result<String, String> {
+        let level = match self.c4.as_deref().map(ElementKind::parse) {
+            Some(ElementKind::Unknown) => {
+                return Err(format!(
+                    "c4 must be an element kind (container, component, context, person, external_system, deployment_node, or custom:<name>), got \"{}\"",
+                    self.c4.as_deref().unwrap_or_default().trim()
+                ))
+            }
+            Some(kind) => kind,
+            None => return Err("missing c4 key".to_string()),
+        };
+
+        let mut lines = vec![format!("@c4 {}", level)];
+
+        if let Some(name) = &self.name {
+            lines.push(String::new());
+            lines.push(format!("# {}", name.trim()));
+        }
+
+        if let Some(description) = &self.description {
+            lines.push(String::new());
+            lines.extend(description.trim().lines().map(str::to_string));
+        }
+
+        if let Some(pattern) = &self.pattern {
+            lines.push(String::new());
+            match self.pattern_status.as_deref() {
+                Some(status) => lines.push(format!("GoF: {} ({})", pattern.trim(), status.trim())),
+                None => lines.push(format!("GoF: {}", pattern.trim())),
+            }
+        }
+
+        if let Some(dir) = &self.files_dir {
+            lines.push(String::new());
+            lines.push(format!("@files-dir {}", dir.trim()));
+        }
+
+        if self.standalone {
+            lines.push(String::new());
+            lines.push("@standalone".to_string());
+        }
+
+        if self.crosscutting {
+            lines.push(String::new());
+            lines.push("@crosscutting".to_string());
+        }
+
+        if let Some(id) = &self.id {
+            lines.push(String::new());
+            lines.push(format!("@id {}", id.trim()));
+        }
+
+        if let Some(group) = &self.group {
+            lines.push(String::new());
+            lines.push(format!("@group {}", group.trim()));
+        }
+
+        if !self.uses.is_empty() {
+            lines.push(String::new());
+            for rel in &self.uses {
+                let mut line = if rel.protocol.is_empty() {
+                    format!("@c4 uses {} \"{}\"", rel.target, rel.label)
+                } else {
+                    format!("@c4 uses {} \"{}\" \"{}\"", rel.target, rel.label, rel.protocol)
+                };
+                if rel.hidden {
+                    line.push_str(" [hidden]");
+                }
+                lines.push(line);
+            }
+        }
+
+        if !self.files.is_empty() {
+            lines.push(String::new());
+            lines.push("| File | Pattern | Purpose | Health |".to_string());
+            lines.push("|------|---------|---------|--------|".to_string());
+            for file in &self.files {
+                lines.push(format!(
+                    "| `{}` | {} | {} | {} |",
+                    file.file,
+                    escape_cell(file.pattern.as_deref().unwrap_or("--")),
+                    escape_cell(&file.purpose),
+                    escape_cell(file.health.as_deref().unwrap_or("planned")),
+                ));
+            }
+        }
+
+        if let Some(annotations) = &self.annotations {
+            lines.push(String::new());
+            lines.extend(annotations.trim().lines().map(str::to_string));
+        }
+
+        Ok(lines.join("\n"))
+    }
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+    use archidoc_types::{ElementKind, HealthStatus, PatternStatus};
+
+    #[test]
+    fn toml_sidecar_renders_annotation() {
+        let text = r#"
+c4 = "component"
+name = "Generated Protos"
+description = "gRPC stubs generated from proto/."
+pattern = "Adapter"
+pattern_status = "verified"
+
+[[uses]]
+target = "bus"
+label = "Publishes events"
+protocol = "crossbeam"
+
+[[files]]
+file = "client.rs"
+purpose = "Generated client | server stubs"
+health = "stable"
+"#;
+        let sidecar = parse_sidecar(text, "archidoc.toml").unwrap().unwrap();
+        let content = sidecar.to_annotation().unwrap();
+
+        assert_eq!(parser::extract_kind(&content), ElementKind::Component);
+        assert_eq!(parser::extract_pattern(&content), "Adapter");
+        assert_eq!(parser::extract_pattern_status(&content), PatternStatus::Verified);
+        assert_eq!(
+            parser::extract_description(&content),
+            "gRPC stubs generated from proto/."
+        );
+
+        let rels = parser::extract_relationships(&content);
+        assert_eq!(rels.len(), 1);
+        assert_eq!(rels[0].target, "bus");
+        assert_eq!(rels[0].protocol, "crossbeam");
+
+        let files = parser::extract_file_table(&content);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].name, "client.rs");
+        assert_eq!(files[0].purpose, "Generated client | server stubs");
+        assert_eq!(files[0].health, HealthStatus::Stable);
+    }
+
+    #[test]
+    fn documented_toml_example_parses() {
+        // Verbatim from "Sidecar Annotation Files" in docs/annotation-spec.md
+        let text = r#"c4 = "component"
+name = "Generated Protos"
+description = "gRPC stubs generated from proto/."
+pattern = "Adapter"
+pattern_status = "planned"
+files_dir = "../proto"
+annotations = """
+Any further annotation lines, copied verbatim.
+"""
+
+[[uses]]
+target = "bus"
+label = "Publishes events"
+protocol = "crossbeam"
+
+[[files]]
+file = "client.rs"
+pattern = "Proxy"
+purpose = "Generated client"
+health = "stable"
+"#;
+        let sidecar = parse_sidecar(text, "archidoc.toml").unwrap().unwrap();
+        let content = sidecar.to_annotation().unwrap();
+
+        assert!(content.contains("Any further annotation lines, copied verbatim."));
+        assert_eq!(parser::extract_files_dir(&content).as_deref(), Some("../proto"));
+        assert_eq!(parser::extract_relationships(&content)[0].target, "bus");
+        assert_eq!(parser::extract_file_table(&content)[0].pattern, "Proxy");
+    }
+
+    #[test]
+    fn yaml_sidecar_renders_annotation() {
+        let text = "c4: container\nname: Vendor\ndescription: Vendored SDK\nuses:\n  - target: net\n    label: Calls out\n";
+        let sidecar = parse_sidecar(text, ".archidoc.yaml").unwrap().unwrap();
+        let content = sidecar.to_annotation().unwrap();
+
+        assert_eq!(parser::extract_kind(&content), ElementKind::Container);
+        assert_eq!(parser::extract_description(&content), "Vendored SDK");
+        let rels = parser::extract_relationships(&content);
+        assert_eq!(rels[0].target, "net");
+        assert_eq!(rels[0].protocol, "");
+    }
+
+    #[test]
+    fn files_dir_is_rendered() {
+        let text = "c4 = \"component\"\nfiles_dir = \"../proto\"\n";
+        let sidecar = parse_sidecar(text, "archidoc.toml").unwrap().unwrap();
+        let content = sidecar.to_annotation().unwrap();
+
+        assert_eq!(parser::extract_files_dir(&content).as_deref(), Some("../proto"));
+    }
+
+    #[test]
+    fn standalone_is_rendered() {
+        let text = "c4 = \"container\"\nstandalone = true\n";
+        let sidecar = parse_sidecar(text, "archidoc.toml").unwrap().unwrap();
+
+        assert!(parser::extract_standalone(&sidecar.to_annotation().unwrap()));
+    }
+
+    #[test]
+    fn id_is_rendered() {
+        let text = "c4 = \"component\"\nid = \"order_book\"\n";
+        let sidecar = parse_sidecar(text, "archidoc.toml").unwrap().unwrap();
+
+        assert_eq!(parser::extract_id(&sidecar.to_annotation().unwrap()).as_deref(), Some("order_book"));
+    }
+
+    #[test]
+    fn group_is_rendered() {
+        let text = "c4 = \"container\"\ngroup = \"domain:payments\"\n";
+        let sidecar = parse_sidecar(text, "archidoc.toml").unwrap().unwrap();
+
+        assert_eq!(parser::extract_group(&sidecar.to_annotation().unwrap()).as_deref(), Some("domain:payments"));
+    }
+
+    #[test]
+    fn file_without_c4_key_is_not_a_sidecar() {
+        let text = "[drift]\nscope = \"all\"\n";
+
+        assert!(parse_sidecar(text, "archidoc.toml").unwrap().is_none());
+    }
+
+    #[test]
+    fn invalid_c4_level_is_rejected() {
+        let sidecar = parse_sidecar("c4 = \"system\"\n", "archidoc.toml")
+            .unwrap()
+            .unwrap();
+
+        assert!(sidecar.to_annotation().is_err());
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(parse_sidecar("c4 = \"container\"\ncolour = \"red\"\n", "archidoc.toml").is_err());
+    }
+}
@@ -0,0 +1,275 @@
+use archidoc_types::{ElementKind, HealthStatus};
+
+use crate::promote::VERIFIABLE_PATTERNS;
+
+/// An editor snippet for part of a `//!` annotation.
+///
+/// `lines` mark tab stops as `{name}`; `fields` gives each name, in tab
+/// order, its options: one option is a default to type over, several are a
+/// choice. The options come from the types the parser reads into (element
+/// kinds, health values, patterns with heuristics), so the snippets offer
+/// exactly what the parser accepts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub prefix: &'static str,
+    pub description: &'static str,
+    pub lines: Vec<&'static str>,
+    pub fields: Vec<(&'static str, Vec<String>)>,
+}
+
+/// The annotation block, `@c4 uses` line, and file-table row snippets.
+/// With `protocols` (`[protocols] allowed`), the protocol is a choice.
+pub fn snippets(protocols: &[String]) -> Vec<Snippet> {
+    let kinds = [
+        ElementKind::Container,
+        ElementKind::Component,
+        ElementKind::Context,
+        ElementKind::Person,
+        ElementKind::ExternalSystem,
+        ElementKind::DeploymentNode,
+    ];
+    let kinds: Vec<String> = kinds.iter().map(ToString::to_string).collect();
+    let patterns: Vec<String> = std::iter::once("--").chain(VERIFIABLE_PATTERNS.iter().copied()).map(String::from).collect();
+    let health: Vec<String> = [HealthStatus::Planned, HealthStatus::Active, HealthStatus::Stable]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let protocols = if protocols.is_empty() {
+        vec!["protocol".to_string()]
+    } else {
+        protocols.to_vec()
+    };
+    let one = |default: &str| vec![default.to_string()];
+
+    vec![
+        Snippet {
+            prefix: "c4",
+            description: "archidoc annotation block",
+            lines: vec![
+                "//! @c4 {kind}",
+                "//!",
+                "//! # {title}",
+                "//!",
+                "//! {description}",
+                "//!",
+                "//! | File | Pattern | Purpose | Health |",
+                "//! |------|---------|---------|--------|",
+                "//! | `{file}` | {pattern} | {purpose} | {health} |",
+            ],
+            fields: vec![
+                ("kind", kinds),
+                ("title", one("Title")),
+                ("description", one("What this module is responsible for")),
+                ("file", one("file.rs")),
+                ("pattern", patterns.clone()),
+                ("purpose", one("Purpose")),
+                ("health", health.clone()),
+            ],
+        },
+        Snippet {
+            prefix: "c4uses",
+            description: "archidoc @c4 uses relationship",
+            lines: vec!["//! @c4 uses {target} \"{label}\" \"{protocol}\""],
+            fields: vec![("target", one("target")), ("label", one("Uses")), ("protocol", protocols)],
+        },
+        Snippet {
+            prefix: "c4file",
+            description: "archidoc file-table row",
+            lines: vec!["//! | `{file}` | {pattern} | {purpose} | {health} |"],
+            fields: vec![
+                ("file", one("file.rs")),
+                ("pattern", patterns),
+                ("purpose", one("Purpose")),
+                ("health", health),
+            ],
+        },
+    ]
+}
+
+impl Snippet {
+    /// The snippet as inserted with every field left at its first option.
+    pub fn expand(&self) -> String {
+        self.render(|_, _, options| options[0].clone(), |text| text.to_string())
+    }
+
+    /// The body lines with each field written by `field(index, name,
+    /// options)`, indexes counting from 1, and the text around fields
+    /// passed through `text`.
+    fn render(
+        &self,
+        field: impl Fn(usize, &str, &[String]) -> String,
+        text: impl Fn(&str) -> String,
+    ) -> String {
+        let mut lines = Vec::new();
+        for line in &self.lines {
+            let mut out = String::new();
+            let mut rest = *line;
+            while let Some(start) = rest.find('{') {
+                let end = start + rest[start..].find('}').expect("fields are closed");
+                let name = &rest[start + 1..end];
+                let (index, (_, options)) = self
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .find(|(_, (n, _))| *n == name)
+                    .expect("every field is declared");
+                out.push_str(&text(&rest[..start]));
+                out.push_str(&field(index + 1, name, options));
+                rest = &rest[end + 1..];
+            }
+            out.push_str(&text(rest));
+            lines.push(out);
+        }
+        lines.join("\n")
+    }
+}
+
+/// VS Code snippets (a `.code-snippets` file) scoped to Rust.
+pub fn render_vscode(snippets: &[Snippet]) -> String {
+    let entries: Vec<String> = snippets
+        .iter()
+        .map(|snippet| {
+            let body = snippet.render(
+                |index, _, options| match options {
+                    [default] => format!("${{{}:{}}}", index, default.replace('}', "\\}")),
+                    _ => format!("${{{}|{}|}}", index, options.join(",")),
+                },
+                |text| text.replace('$', "\\$"),
+            );
+            let body: Vec<String> = body.lines().map(json_string).collect();
+            format!(
+                "  {}: {{\n    \"scope\": \"rust\",\n    \"prefix\": {},\n    \"body\": [\n      {}\n    ],\n    \"description\": {}\n  }}",
+                json_string(snippet.description),
+                json_string(snippet.prefix),
+                body.join(",\n      "),
+                json_string(snippet.description)
+            )
+        })
+        .collect();
+    format!("{{\n{}\n}}\n", entries.join(",\n"))
+}
+
+/// A JetBrains live template set, to import or copy into the IDE's
+/// `templates` directory.
+pub fn render_jetbrains(snippets: &[Snippet]) -> String {
+    let mut out = String::from("<templateSet group=\"archidoc\">\n");
+    for snippet in snippets {
+        let value = snippet.render(|_, name, _| format!("${}$", name.to_uppercase()), |text| text.replace('$', "$$"));
+        out.push_str(&format!(
+            "  <template name=\"{}\" value=\"{}\" description=\"{}\" toReformat=\"false\" toShortenFQNames=\"false\">\n",
+            snippet.prefix,
+            xml_escape(&value),
+            xml_escape(snippet.description)
+        ));
+        for (name, options) in &snippet.fields {
+            let quoted: Vec<String> = options.iter().map(|o| format!("\"{}\"", o)).collect();
+            let expression = match options.as_slice() {
+                [_] => String::new(),
+                _ => format!("enum({})", quoted.join(",")),
+            };
+            out.push_str(&format!(
+                "    <variable name=\"{}\" expression=\"{}\" defaultValue=\"{}\" alwaysStopAt=\"true\" />\n",
+                name.to_uppercase(),
+                xml_escape(&expression),
+                xml_escape(&quoted[0])
+            ));
+        }
+        out.push_str("    <context>\n      <option name=\"OTHER\" value=\"true\" />\n    </context>\n  </template>\n");
+    }
+    out.push_str("</templateSet>\n");
+    out
+}
+
+/// SnipMate-format snippets (`rust.snippets`), which LuaSnip, nvim-snippets,
+/// and snipMate load. The format has no choices, so a choice field offers
+/// its first option.
+pub fn render_snipmate(snippets: &[Snippet]) -> String {
+    let mut out = String::new();
+    for snippet in snippets {
+        let body = snippet.render(
+            |index, _, options| format!("${{{}:{}}}", index, options[0]),
+            |text| text.replace('$', "\\$"),
+        );
+        out.push_str(&format!("snippet {} {}\n", snippet.prefix, snippet.description));
+        for line in body.lines() {
+            out.push_str(&format!("\t{}\n", line));
+        }
+    }
+    out
+}
+
+fn json_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\n', "&#10;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn content(snippet: &Snippet) -> String {
+        parser::archidoc_from_source(&format!("{}\n", snippet.expand())).unwrap()
+    }
+
+    #[test]
+    fn expanded_snippets_parse_as_what_they_stand_for() {
+        let all = snippets(&[]);
+
+        let block = content(&all[0]);
+        assert_eq!(parser::extract_kind(&block), ElementKind::Container);
+        assert_eq!(parser::extract_description(&block), "What this module is responsible for");
+        let files = parser::extract_file_table(&block);
+        assert_eq!((files[0].name.as_str(), files[0].health), ("file.rs", HealthStatus::Planned));
+
+        let uses = parser::extract_relationships(&content(&all[1]));
+        assert_eq!((uses[0].target.as_str(), uses[0].label.as_str()), ("target", "Uses"));
+        assert_eq!(uses[0].protocol, "protocol");
+
+        // The row snippet in place of the block's own row
+        let block = all[0].expand();
+        let table = format!("{}\n{}\n", &block[..block.rfind('\n').unwrap()], all[2].expand());
+        assert_eq!(parser::extract_file_table(&parser::archidoc_from_source(&table).unwrap()).len(), 1);
+    }
+
+    #[test]
+    fn every_choice_is_accepted_by_the_parser() {
+        let all = snippets(&[]);
+        let choices = |name: &str| all[0].fields.iter().find(|(n, _)| *n == name).unwrap().1.clone();
+
+        for kind in choices("kind") {
+            assert_eq!(ElementKind::parse(&kind).to_string(), kind);
+            assert_ne!(ElementKind::parse(&kind), ElementKind::Unknown);
+        }
+        for health in choices("health") {
+            assert_eq!(HealthStatus::parse(&health).to_string(), health);
+        }
+        for pattern in choices("pattern").iter().skip(1) {
+            assert!(parser::extract_file_table(&format!(
+                "| File | Pattern | Purpose | Health |\n|---|---|---|---|\n| `a.rs` | {} | A | stable |\n",
+                pattern
+            ))[0]
+                .pattern
+                .starts_with(pattern.as_str()));
+        }
+    }
+
+    #[test]
+    fn editors_get_their_own_placeholder_syntax() {
+        let all = snippets(&["http".to_string(), "grpc".to_string()]);
+
+        let vscode = render_vscode(&all);
+        assert!(vscode.contains("\"//! @c4 uses ${1:target} \\\"${2:Uses}\\\" \\\"${3|http,grpc|}\\\"\""), "{}", vscode);
+        let jetbrains = render_jetbrains(&all);
+        assert!(jetbrains.contains("value=\"//! @c4 uses $TARGET$ &quot;$LABEL$&quot; &quot;$PROTOCOL$&quot;\""), "{}", jetbrains);
+        assert!(jetbrains.contains("expression=\"enum(&quot;http&quot;,&quot;grpc&quot;)\""));
+        assert!(render_snipmate(&all).contains("snippet c4uses archidoc @c4 uses relationship\n\t//! @c4 uses ${1:target} \"${2:Uses}\" \"${3:http}\"\n"));
+    }
+}
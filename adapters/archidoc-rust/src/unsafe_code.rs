@@ -0,0 +1,98 @@
+//! Unsafe-code accounting — counts `unsafe` sites per module.
+//!
+//! Runs during extraction over each module's own source files (see
+//! [`crate::metrics`] for which files those are). Counts `unsafe { }`
+//! blocks and `unsafe fn` declarations, including methods and trait
+//! functions.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+use archidoc_types::ModuleDoc;
+use syn::visit::{self, Visit};
+
+use crate::metrics;
+
+/// Set [`ModuleDoc::unsafe_count`] for every module.
+pub fn count_unsafe(docs: &mut [ModuleDoc]) {
+    let entry_files: BTreeSet<PathBuf> = docs.iter().map(|d| PathBuf::from(&d.source_file)).collect();
+
+    for doc in docs.iter_mut() {
+        doc.unsafe_count = metrics::module_sources(doc, &entry_files)
+            .iter()
+            .filter_map(|path| fs::read_to_string(path).ok())
+            .map(|source| count_in_source(&source))
+            .sum();
+    }
+}
+
+/// Count unsafe blocks and functions in one source file. Unparsable files
+/// count as zero.
+pub fn count_in_source(source: &str) -> usize {
+    let Ok(file) = syn::parse_file(source) else {
+        return 0;
+    };
+    let mut counter = UnsafeCounter::default();
+    counter.visit_file(&file);
+    counter.count
+}
+
+#[derive(Default)]
+struct UnsafeCounter {
+    count: usize,
+}
+
+impl<'ast> Visit<'ast> for UnsafeCounter {
+    fn visit_expr_unsafe(&mut self, node: &'ast syn::ExprUnsafe) {
+        self.count += 1;
+        visit::visit_expr_unsafe(self, node);
+    }
+
+    fn visit_signature(&mut self, node: &'ast syn::Signature) {
+        if node.unsafety.is_some() {
+            self.count += 1;
+        }
+        visit::visit_signature(self, node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_blocks_and_functions() {
+        let source = r#"
+pub unsafe fn raw(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+struct Buf;
+
+impl Buf {
+    unsafe fn get_unchecked(&self) {}
+
+    fn read(&self, ptr: *const u8) -> u8 {
+        unsafe { raw(ptr) }
+    }
+}
+
+trait Device {
+    unsafe fn map(&self);
+}
+
+fn safe() -> u8 {
+    1
+}
+"#;
+
+        assert_eq!(count_in_source(source), 4);
+    }
+
+    #[test]
+    fn safe_code_counts_zero() {
+        assert_eq!(count_in_source("fn main() { let x = 1; }"), 0);
+        assert_eq!(count_in_source("fn broken( {"), 0);
+    }
+}
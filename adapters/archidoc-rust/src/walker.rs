@@ -1,24 +1,80 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use archidoc_types::ModuleDoc;
+use archidoc_types::{ElementKind, Flow, ModuleDoc, SkippedFile};
 use walkdir::WalkDir;
 
+use crate::cfg_gate;
 use crate::parser;
 use crate::path_resolver;
+use crate::sidecar;
+use crate::unsafe_code;
+
+/// The docs [`extract`] finds under `root`, without its skipped files and
+/// warnings.
+#[deprecated(note = "use `extract`, which also returns the skipped files and warnings")]
+pub fn extract_all_docs(root: &Path) -> Vec<ModuleDoc> {
+    extract(root).docs
+}
+
+/// Result of walking a source tree.
+#[derive(Debug, Default)]
+pub struct Extraction {
+    pub docs: Vec<ModuleDoc>,
+    /// Annotated files that did not become modules, with the reason.
+    pub skipped: Vec<SkippedFile>,
+    pub warnings: Vec<String>,
+}
 
 /// Walk a source tree and extract ModuleDocs from all module entry files.
 ///
 /// Finds `lib.rs`, `mod.rs`, and flat `.rs` module files with archidoc annotations,
 /// extracts `//!` doc comments, and builds ModuleDoc structs from the parsed annotations.
 ///
-/// Flat module support: A `.rs` file that is not `mod.rs` or `lib.rs` is included
-/// if it contains archidoc annotations (C4 markers: `@c4 container` or `@c4 component`).
-pub fn extract_all_docs(root: &Path) -> Vec<ModuleDoc> {
+/// Flat module support: A `.rs` file that is not `mod.rs` or a crate root is included
+/// if it contains archidoc annotations (an element kind marker such as `@c4 container`).
+///
+/// Binary crates: `main.rs` and each `bin/` target are crate roots, documented
+/// like `lib.rs` (see [`path_resolver::path_to_module_name`]).
+///
+/// Feature gates: modules declared behind `#[cfg(...)]` are marked optional
+/// (see [`cfg_gate::apply_cfg_gates`]).
+///
+/// Unsafe code: `unsafe` blocks and functions are counted per module
+/// (see [`unsafe_code::count_unsafe`]).
+///
+/// Duplicate module paths: when several files resolve to the same module path
+/// (`foo.rs` beside `foo/mod.rs`, or two crates under one root), `mod.rs`
+/// wins over a flat file and otherwise the first path in sort order wins. The
+/// others are skipped with a warning naming both files.
+///
+/// Data Flow: the numbered `a -> b -> c` steps of the narrative's Data Flow
+/// section become flows, like `@flow` (see [`parser::extract_data_flow`]).
+///
+/// Sidecar support: A directory whose sources cannot carry doc comments may hold an
+/// `archidoc.toml` or `.archidoc.yaml` file with a `c4` key instead. Sidecars name the
+/// module after their directory and are only used when no `.rs` entry file claims
+/// the same module path.
+///
+/// Module paths: directory and file names become segments of lowercase ASCII
+/// letters, digits, and `_` (`Order Book/` -> `order_book`) with a warning, and
+/// a file whose path would start with a name reserved for crate roots (`_lib`,
+/// `_main`, `_bin`) is skipped with a warning.
+///
+/// Skipped files and warnings are returned with the docs, not printed.
+pub fn extract(root: &Path) -> Extraction {
     let mut docs = Vec::new();
+    let mut skipped = Vec::new();
+    let mut warnings = Vec::new();
     let mut seen_modules = std::collections::HashSet::new();
+    let mut candidates = Vec::new();
+    let mut sidecars = Vec::new();
 
-    for entry in WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
+    for entry in WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
         let path = entry.path();
 
         // Skip target directories
@@ -26,74 +82,326 @@ pub fn extract_all_docs(root: &Path) -> Vec<ModuleDoc> {
             continue;
         }
 
-        // Only process .rs files
         let filename = match path.file_name().and_then(|n| n.to_str()) {
-            Some(name) if name.ends_with(".rs") => name,
-            _ => continue,
+            Some(name) => name,
+            None => continue,
         };
 
+        if sidecar::is_sidecar_filename(filename) {
+            sidecars.push(path.to_path_buf());
+            continue;
+        }
+
+        // Only process .rs files
+        if !filename.ends_with(".rs") {
+            continue;
+        }
+
         // Extract archidoc content
         let content = match parser::archidoc_from_file(path) {
             Some(c) if !c.trim().is_empty() => c,
             _ => continue,
         };
 
+        let module_path = path_resolver::path_to_module_name(path, root, filename);
+
         // For non-standard entry files, require C4 markers
-        let is_standard_entry = filename == "lib.rs" || filename == "mod.rs";
-        if !is_standard_entry {
-            let has_c4_marker = content.contains("@c4 container")
-                || content.contains("@c4 component");
-            if !has_c4_marker {
+        let is_standard_entry =
+            filename == "mod.rs" || path_resolver::is_crate_root(&module_path);
+        if !is_standard_entry && parser::extract_kind(&content) == ElementKind::Unknown {
+            skipped.push(skip(path, "annotated flat module without a @c4 marker"));
+            continue;
+        }
+
+        if !check_module_path(path, root, filename, &module_path, &mut skipped, &mut warnings) {
+            continue;
+        }
+        candidates.push((module_path, content, path.to_path_buf()));
+    }
+
+    for (module_path, content, path) in resolve_duplicates(candidates, root, &mut skipped, &mut warnings) {
+        seen_modules.insert(module_path.clone());
+        docs.push(build_module_doc(module_path, content, &path));
+    }
+
+    // Already in walk order, which is sorted; keep it explicit since the
+    // first sidecar for a directory wins
+    sidecars.sort();
+    for path in sidecars {
+        let content = match sidecar::archidoc_from_sidecar(&path) {
+            Ok(Some(c)) => c,
+            Ok(None) => continue,
+            Err(e) => {
+                warnings.push(format!("skipping sidecar: {}", e));
+                skipped.push(skip(&path, &e));
                 continue;
             }
-        }
+        };
 
-        let module_path = path_resolver::path_to_module_name(path, root, filename);
+        let module_path = match path_resolver::path_to_module_name(&path, root, "mod.rs") {
+            p if p.is_empty() => "_lib".to_string(),
+            p => p,
+        };
+        if !check_module_path(&path, root, "mod.rs", &module_path, &mut skipped, &mut warnings) {
+            continue;
+        }
 
-        // Skip duplicate module paths (e.g., both src/foo/mod.rs and src/foo.rs exist)
-        // mod.rs takes priority
+        // Doc comments in source take priority over sidecars
         if !seen_modules.insert(module_path.clone()) {
+            skipped.push(skip(&path, &format!("module '{}' is documented in source", module_path)));
             continue;
         }
 
-        let c4_level = parser::extract_c4_level(&content);
-        let pattern = parser::extract_pattern(&content);
-        let pattern_status = parser::extract_pattern_status(&content);
-        let description = parser::extract_description(&content);
-        let parent_container = parser::extract_parent_container(&module_path);
-        let relationships = parser::extract_relationships(&content);
-        let files = parser::extract_file_table(&content);
+        docs.push(build_module_doc(module_path, content, &path));
+    }
+
+    cfg_gate::apply_cfg_gates(&mut docs);
+    docs.iter_mut().for_each(count_file_lines);
+    unsafe_code::count_unsafe(&mut docs);
+
+    add_declared_elements(&mut docs, root, &mut warnings);
+    docs.sort_by(|a, b| a.module_path.cmp(&b.module_path));
+    add_data_flows(&mut docs);
+    Extraction {
+        docs,
+        skipped,
+        warnings,
+    }
+}
 
-        docs.push(ModuleDoc {
+/// Report names [`path_resolver::path_to_module_name`] had to bring into
+/// the module path grammar, and skip files whose path would take a name
+/// reserved for crate roots. Returns whether the file is kept.
+fn check_module_path(
+    path: &Path,
+    root: &Path,
+    filename: &str,
+    module_path: &str,
+    skipped: &mut Vec<SkippedFile>,
+    warnings: &mut Vec<String>,
+) -> bool {
+    let display = path.strip_prefix(root).unwrap_or(path).display();
+    if let Some(name) = path_resolver::reserved_name(path, root, filename) {
+        let reason = format!("module path '{}' uses '{}', which is reserved for crate roots", module_path, name);
+        warnings.push(format!("{}: {}; skipping it", display, reason));
+        skipped.push(skip(path, &reason));
+        return false;
+    }
+    let renamed = path_resolver::renamed_names(path, root, filename);
+    if !renamed.is_empty() {
+        let names: Vec<String> = renamed
+            .iter()
+            .map(|(name, segment)| format!("'{}' as '{}'", name, segment))
+            .collect();
+        warnings.push(format!(
+            "{}: module path '{}' spells {} (segments are lowercase ASCII letters, digits, and `_`)",
+            display,
             module_path,
-            content,
-            source_file: path.to_string_lossy().to_string(),
-            c4_level,
-            pattern,
-            pattern_status,
-            description,
-            parent_container,
-            relationships,
-            files,
-        });
+            names.join(", ")
+        ));
     }
+    true
+}
 
-    docs.sort_by(|a, b| a.module_path.cmp(&b.module_path));
-    docs
+/// Keep one `(module_path, content, path)` per module path: `mod.rs` before
+/// a flat file, then the first path. Every other file is skipped with a
+/// warning naming the one that won.
+fn resolve_duplicates(
+    mut candidates: Vec<(String, String, PathBuf)>,
+    root: &Path,
+    skipped: &mut Vec<SkippedFile>,
+    warnings: &mut Vec<String>,
+) -> Vec<(String, String, PathBuf)> {
+    candidates.sort_by(|a, b| {
+        let rank = |path: &Path| path.file_name().is_none_or(|name| name != "mod.rs");
+        a.0.cmp(&b.0).then(rank(&a.2).cmp(&rank(&b.2))).then(a.2.cmp(&b.2))
+    });
+
+    let display = |path: &Path| path.strip_prefix(root).unwrap_or(path).display().to_string();
+    let mut kept: Vec<(String, String, PathBuf)> = Vec::new();
+    for candidate in candidates {
+        match kept.last() {
+            Some(winner) if winner.0 == candidate.0 => {
+                warnings.push(format!(
+                    "duplicate module path '{}': {} and {}; using {}",
+                    candidate.0,
+                    display(&winner.2),
+                    display(&candidate.2),
+                    display(&winner.2)
+                ));
+                skipped.push(skip(
+                    &candidate.2,
+                    &format!("duplicate module path '{}' (using {})", candidate.0, display(&winner.2)),
+                ));
+            }
+            _ => kept.push(candidate),
+        }
+    }
+    kept
+}
+
+fn skip(path: &Path, reason: &str) -> SkippedFile {
+    SkippedFile {
+        path: path.to_string_lossy().to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Build a ModuleDoc from annotation content found at `source`.
+fn build_module_doc(module_path: String, content: String, source: &Path) -> ModuleDoc {
+    let c4_level = parser::extract_kind(&content);
+    let pattern = parser::extract_pattern(&content);
+    let pattern_status = parser::extract_pattern_status(&content);
+    let description = parser::extract_description(&content);
+    let parent_container = parser::extract_parent_container(&module_path);
+    let relationships = parser::extract_relationships(&content);
+    let files = parser::extract_file_table(&content);
+    let files_dir = parser::extract_files_dir(&content);
+    let review = parser::extract_review(&content);
+    let risks = parser::extract_risks(&content);
+    let terms = parser::extract_terms(&content);
+    let links = parser::extract_doc_links(&content);
+    let standalone = parser::extract_standalone(&content);
+    let id = parser::extract_id(&content);
+    let group = parser::extract_group(&content);
+    let crosscutting = parser::extract_crosscutting(&content);
+    let flows = parser::extract_flows(&content);
+
+    ModuleDoc {
+        module_path,
+        content,
+        source_file: source.to_string_lossy().to_string(),
+        c4_level,
+        pattern,
+        pattern_status,
+        description,
+        parent_container,
+        relationships,
+        files,
+        files_dir,
+        review,
+        risks,
+        terms,
+        links,
+        standalone,
+        id,
+        group,
+        crosscutting,
+        flows,
+        ..Default::default()
+    }
+}
+
+/// Add the people and external systems declared in doc blocks (see
+/// [`parser::extract_declarations`]) as elements named by the declaration
+/// and sourced from the declaring file. Malformed declarations, names that
+/// are not a single module path segment, and names already taken are
+/// skipped with a warning.
+fn add_declared_elements(docs: &mut Vec<ModuleDoc>, root: &Path, warnings: &mut Vec<String>) {
+    let mut declared: Vec<ModuleDoc> = Vec::new();
+    for doc in docs.iter() {
+        let source = Path::new(&doc.source_file);
+        let display = source.strip_prefix(root).unwrap_or(source).display();
+        for declaration in parser::extract_declarations(&doc.content) {
+            let declaration = match declaration {
+                Ok(declaration) => declaration,
+                Err(marker) => {
+                    warnings.push(format!(
+                        "{}: malformed declaration `{}`; expected `@c4 person|external <name> \"description\" [uses <target> \"label\"]`",
+                        display, marker
+                    ));
+                    continue;
+                }
+            };
+            let name = &declaration.name;
+            if name.contains('.') || ModuleDoc::check_path(name).is_err() {
+                warnings.push(format!(
+                    "{}: declared {} '{}' is not named by lowercase ASCII letters, digits, and `_`; skipping it",
+                    display, declaration.kind, name
+                ));
+                continue;
+            }
+            if docs.iter().chain(&declared).any(|d| &d.module_path == name) {
+                warnings.push(format!(
+                    "{}: declared {} '{}' is already an element; skipping it",
+                    display, declaration.kind, name
+                ));
+                continue;
+            }
+            declared.push(ModuleDoc {
+                module_path: declaration.name,
+                source_file: doc.source_file.clone(),
+                c4_level: declaration.kind,
+                pattern: "--".to_string(),
+                description: declaration.description,
+                relationships: declaration.relationship.into_iter().collect(),
+                ..Default::default()
+            });
+        }
+    }
+    docs.extend(declared);
+}
+
+/// Give the crate root that provides the narrative the numbered steps of
+/// its Data Flow section as flows (see [`parser::extract_data_flow`]). A
+/// step naming an element by its last path segment, in any case, is
+/// resolved to the element's module path.
+fn add_data_flows(docs: &mut [ModuleDoc]) {
+    let Some(root) = ModuleDoc::narrative_root(docs) else {
+        return;
+    };
+    let resolve = |step: &str| {
+        docs.iter()
+            .find(|d| d.module_path == step)
+            .or_else(|| {
+                docs.iter().find(|d| {
+                    let name = d.module_path.rsplit('.').next().unwrap_or(&d.module_path);
+                    name.eq_ignore_ascii_case(step) && !path_resolver::is_crate_root(&d.module_path)
+                })
+            })
+            .map_or_else(|| step.to_string(), |d| d.module_path.clone())
+    };
+    let root_path = root.module_path.clone();
+    let flows: Vec<Flow> = parser::extract_data_flow(&root.content)
+        .into_iter()
+        .filter(|flow| !root.flows.iter().any(|f| f.name == flow.name))
+        .map(|flow| Flow {
+            steps: flow.steps.iter().map(|step| resolve(step)).collect(),
+            name: flow.name,
+        })
+        .collect();
+    if let Some(root) = docs.iter_mut().find(|d| d.module_path == root_path) {
+        root.flows.extend(flows);
+    }
+}
+
+/// Fill in [`FileEntry::loc`] for every cataloged file that exists on disk.
+///
+/// [`FileEntry::loc`]: archidoc_types::FileEntry::loc
+fn count_file_lines(doc: &mut ModuleDoc) {
+    let Some(dir) = doc.catalog_dir() else {
+        return;
+    };
+    for file in &mut doc.files {
+        if let Ok(source) = fs::read_to_string(dir.join(&file.name)) {
+            file.loc = Some(source.lines().filter(|l| !l.trim().is_empty()).count());
+        }
+    }
 }
 
 /// Read all `.rs` source files in a directory and return their contents.
 ///
-/// Returns a vec of `(filename, source_code)` pairs. Skips files that
-/// cannot be read. Used by pattern heuristics to scan a module directory
-/// for structural evidence without coupling AST analysis to filesystem I/O.
+/// Returns a vec of `(filename, source_code)` pairs in name order, whatever
+/// order the filesystem lists them in. Skips files that cannot be read.
+/// Used by pattern heuristics to scan a module directory for structural
+/// evidence without coupling AST analysis to filesystem I/O.
 pub fn read_rs_sources(dir: &Path) -> Vec<(String, String)> {
     let entries = match fs::read_dir(dir) {
         Ok(e) => e,
         Err(_) => return Vec::new(),
     };
 
-    entries
+    let mut sources: Vec<(String, String)> = entries
         .filter_map(|e| e.ok())
         .filter_map(|entry| {
             let path = entry.path();
@@ -105,5 +413,7 @@ pub fn read_rs_sources(dir: &Path) -> Vec<(String, String)> {
                 None
             }
         })
-        .collect()
+        .collect();
+    sources.sort();
+    sources
 }
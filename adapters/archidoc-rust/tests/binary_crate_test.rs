@@ -0,0 +1,69 @@
+//! Integration test for binary crate roots
+//!
+//! Verifies that `main.rs` and `bin/` targets become root elements with
+//! distinct module paths, and that bin-local modules nest under their target.
+
+use archidoc_rust::walker;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn main_rs_is_crate_root_without_marker() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::write(
+        root.join("main.rs"),
+        "#![allow(clippy::all)]\n//! # Tool\n//!\n//! Command-line tool\n\nfn main() {}\n",
+    )
+    .expect("failed to write main.rs");
+
+    let docs = walker::extract(root).docs;
+
+    assert_eq!(docs.len(), 1);
+    assert_eq!(docs[0].module_path, "_main");
+    assert_eq!(docs[0].parent_container, None);
+}
+
+#[test]
+fn lib_and_main_have_distinct_paths() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::write(root.join("lib.rs"), "//! @c4 container\n//!\n//! # Core\n")
+        .expect("failed to write lib.rs");
+    fs::write(root.join("main.rs"), "//! @c4 container\n//!\n//! # Cli\n")
+        .expect("failed to write main.rs");
+
+    let docs = walker::extract(root).docs;
+    let paths: Vec<&str> = docs.iter().map(|d| d.module_path.as_str()).collect();
+
+    assert_eq!(paths, vec!["_lib", "_main"]);
+}
+
+#[test]
+fn multi_binary_crate_containment() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::create_dir_all(root.join("bin/server/routes")).expect("failed to create bin dirs");
+    fs::write(root.join("bin/migrate.rs"), "//! # Migrate\n//!\n//! Schema migrations\n")
+        .expect("failed to write bin/migrate.rs");
+    fs::write(
+        root.join("bin/server/main.rs"),
+        "//! @c4 container\n//!\n//! # Server\n//!\n//! HTTP server\n",
+    )
+    .expect("failed to write bin/server/main.rs");
+    fs::write(
+        root.join("bin/server/routes/mod.rs"),
+        "//! @c4 component\n//!\n//! # Routes\n//!\n//! Route table\n",
+    )
+    .expect("failed to write routes/mod.rs");
+
+    let docs = walker::extract(root).docs;
+    let paths: Vec<&str> = docs.iter().map(|d| d.module_path.as_str()).collect();
+
+    assert_eq!(paths, vec!["_bin.migrate", "_bin.server", "_bin.server.routes"]);
+    assert_eq!(docs[1].parent_container, None);
+    assert_eq!(docs[2].parent_container, Some("_bin.server".to_string()));
+}
@@ -0,0 +1,35 @@
+//! Integration test for Data Flow sections
+//!
+//! Verifies that the numbered steps of the crate root's Data Flow section
+//! become flows whose steps name elements by module path.
+
+use archidoc_rust::walker;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn data_flow_steps_of_the_crate_root_become_flows() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::write(
+        root.join("lib.rs"),
+        "//! # Shop\n//!\n//! Online shop\n//!\n//! ## Data Flow\n//!\n//! 1. Orders: API -> engine -> postgres\n//! 2. Settings are read once at startup\n",
+    )
+    .expect("failed to write lib.rs");
+    for (dir, content) in [
+        ("api", "//! @c4 container\n//!\n//! REST gateway\n"),
+        ("engine", "//! @c4 container\n//!\n//! Order matching\n"),
+    ] {
+        fs::create_dir_all(root.join(dir)).expect("failed to create module dir");
+        fs::write(root.join(dir).join("mod.rs"), content).expect("failed to write mod.rs");
+    }
+
+    let docs = walker::extract(root).docs;
+
+    let lib = docs.iter().find(|d| d.module_path == "_lib").expect("crate root");
+    assert_eq!(lib.flows.len(), 1);
+    assert_eq!(lib.flows[0].name, "data-flow-1");
+    assert_eq!(lib.flows[0].steps, ["api", "engine", "postgres"]);
+    assert!(docs.iter().filter(|d| d.module_path != "_lib").all(|d| d.flows.is_empty()));
+}
@@ -0,0 +1,47 @@
+//! Integration test for declared people and external systems
+//!
+//! Verifies that `@c4 person` and `@c4 external` declarations become
+//! elements of their own, and that malformed or clashing ones are skipped
+//! with a warning.
+
+use archidoc_rust::walker;
+use archidoc_types::ElementKind;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn declarations_become_elements_outside_the_system() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::write(
+        root.join("lib.rs"),
+        "//! @c4 container\n//! # Shop\n//!\n//! Online shop\n//!\n//! @c4 person customer \"Places orders\" uses api \"Browses\" \"HTTPS\"\n//! @c4 external stripe \"Card payments\"\n//! @c4 external api \"Clashes with the module\"\n//! @c4 person Buyer \"Not a segment\"\n//! @c4 person nobody\n",
+    )
+    .expect("failed to write lib.rs");
+    fs::create_dir_all(root.join("api")).expect("failed to create module dir");
+    fs::write(root.join("api/mod.rs"), "//! @c4 container\n//!\n//! REST gateway\n//!\n//! @c4 uses stripe \"Charges cards\" \"HTTPS\"\n")
+        .expect("failed to write mod.rs");
+
+    let extraction = walker::extract(root);
+
+    let paths: Vec<&str> = extraction.docs.iter().map(|d| d.module_path.as_str()).collect();
+    assert_eq!(paths, ["_lib", "api", "customer", "stripe"]);
+    let lib = &extraction.docs[0];
+    assert_eq!(lib.c4_level, ElementKind::Container);
+    let customer = &extraction.docs[2];
+    assert_eq!(customer.c4_level, ElementKind::Person);
+    assert_eq!(customer.description, "Places orders");
+    assert_eq!(customer.source_file, lib.source_file);
+    assert_eq!(customer.relationships[0].target, "api");
+    assert_eq!(extraction.docs[3].c4_level, ElementKind::ExternalSystem);
+
+    assert_eq!(
+        extraction.warnings,
+        [
+            "lib.rs: declared external_system 'api' is already an element; skipping it",
+            "lib.rs: declared person 'Buyer' is not named by lowercase ASCII letters, digits, and `_`; skipping it",
+            "lib.rs: malformed declaration `@c4 person nobody`; expected `@c4 person|external <name> \"description\" [uses <target> \"label\"]`",
+        ]
+    );
+}
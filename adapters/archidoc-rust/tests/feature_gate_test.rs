@@ -0,0 +1,66 @@
+//! Integration test for feature-gated modules
+//!
+//! Verifies that modules declared behind `#[cfg(...)]` are marked with their
+//! build condition, and that nested modules inherit it.
+
+use archidoc_rust::walker;
+use std::fs;
+use tempfile::TempDir;
+
+fn condition_of<'a>(docs: &'a [archidoc_types::ModuleDoc], path: &str) -> Option<&'a str> {
+    docs.iter()
+        .find(|d| d.module_path == path)
+        .unwrap_or_else(|| panic!("module '{}' not found", path))
+        .condition
+        .as_deref()
+}
+
+#[test]
+fn feature_gated_container_and_its_components() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::create_dir_all(root.join("gpu/kernels")).expect("failed to create gpu dirs");
+    fs::create_dir_all(root.join("bus")).expect("failed to create bus dir");
+    fs::write(
+        root.join("lib.rs"),
+        "//! @c4 container\n//!\n//! # Core\n\npub mod bus;\n\n#[cfg(feature = \"gpu\")]\npub mod gpu;\n",
+    )
+    .expect("failed to write lib.rs");
+    fs::write(root.join("bus/mod.rs"), "//! @c4 container\n//!\n//! Messaging\n")
+        .expect("failed to write bus/mod.rs");
+    fs::write(
+        root.join("gpu/mod.rs"),
+        "//! @c4 container\n//!\n//! GPU acceleration\n\npub mod kernels;\n",
+    )
+    .expect("failed to write gpu/mod.rs");
+    fs::write(root.join("gpu/kernels/mod.rs"), "//! @c4 component\n//!\n//! Compute kernels\n")
+        .expect("failed to write gpu/kernels/mod.rs");
+
+    let docs = walker::extract(root).docs;
+
+    assert_eq!(condition_of(&docs, "gpu"), Some("feature: gpu"));
+    assert_eq!(condition_of(&docs, "gpu.kernels"), Some("feature: gpu"));
+    assert_eq!(condition_of(&docs, "bus"), None);
+    assert_eq!(condition_of(&docs, "_lib"), None);
+}
+
+#[test]
+fn flat_module_with_platform_cfg() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::create_dir_all(root.join("platform")).expect("failed to create platform dir");
+    fs::write(
+        root.join("platform.rs"),
+        "//! @c4 container\n//!\n//! Platform layer\n\n#[cfg(target_os = \"linux\")]\nmod epoll;\n",
+    )
+    .expect("failed to write platform.rs");
+    fs::write(root.join("platform/epoll.rs"), "//! @c4 component\n//!\n//! epoll reactor\n")
+        .expect("failed to write platform/epoll.rs");
+
+    let docs = walker::extract(root).docs;
+
+    assert_eq!(condition_of(&docs, "platform.epoll"), Some("cfg(target_os = \"linux\")"));
+    assert_eq!(condition_of(&docs, "platform"), None);
+}
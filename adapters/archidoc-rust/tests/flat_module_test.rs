@@ -19,11 +19,11 @@ fn flat_module_with_at_c4_syntax() {
     )
     .expect("failed to write router.rs");
 
-    let docs = walker::extract_all_docs(root);
+    let docs = walker::extract(root).docs;
 
     assert_eq!(docs.len(), 1);
     assert_eq!(docs[0].module_path, "router");
-    assert_eq!(docs[0].c4_level, archidoc_types::C4Level::Container);
+    assert_eq!(docs[0].c4_level, archidoc_types::ElementKind::Container);
 }
 
 #[test]
@@ -39,11 +39,11 @@ fn nested_flat_module() {
     )
     .expect("failed to write bus/events.rs");
 
-    let docs = walker::extract_all_docs(root);
+    let docs = walker::extract(root).docs;
 
     assert_eq!(docs.len(), 1);
     assert_eq!(docs[0].module_path, "bus.events");
-    assert_eq!(docs[0].c4_level, archidoc_types::C4Level::Component);
+    assert_eq!(docs[0].c4_level, archidoc_types::ElementKind::Component);
     assert_eq!(docs[0].parent_container, Some("bus".to_string()));
 }
 
@@ -66,12 +66,70 @@ fn mod_rs_takes_priority_over_flat_module() {
     )
     .expect("failed to write foo/mod.rs");
 
-    let docs = walker::extract_all_docs(root);
+    let docs = walker::extract(root).docs;
 
     assert_eq!(docs.len(), 1);
     assert_eq!(docs[0].module_path, "foo");
     assert!(docs[0].content.contains("Foo Mod"), "mod.rs content should be used");
     assert!(!docs[0].content.contains("Foo Flat"), "foo.rs should be ignored");
+
+    let extraction = walker::extract(root);
+    assert_eq!(
+        extraction.warnings,
+        vec!["duplicate module path 'foo': foo/mod.rs and foo.rs; using foo/mod.rs"]
+    );
+    assert_eq!(extraction.skipped.len(), 1);
+    assert!(extraction.skipped[0].path.ends_with("foo.rs"));
+}
+
+#[test]
+fn duplicate_crate_roots_warn_and_keep_the_first_path() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    for krate in ["beta", "alpha"] {
+        fs::create_dir_all(root.join(krate)).expect("failed to create crate dir");
+        fs::write(
+            root.join(krate).join("lib.rs"),
+            format!("//! @c4 container\n//!\n//! # {}\n", krate),
+        )
+        .expect("failed to write lib.rs");
+    }
+
+    let extraction = walker::extract(root);
+
+    assert_eq!(extraction.docs.len(), 1);
+    assert!(extraction.docs[0].content.contains("alpha"));
+    assert_eq!(
+        extraction.warnings,
+        vec!["duplicate module path '_lib': alpha/lib.rs and beta/lib.rs; using alpha/lib.rs"]
+    );
+}
+
+#[test]
+fn directory_names_are_normalized_and_reserved_names_skipped() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    for dir in ["Order Book", "_lib"] {
+        fs::create_dir_all(root.join(dir)).expect("failed to create module dir");
+        fs::write(root.join(dir).join("mod.rs"), "//! @c4 container\n//!\n//! Matching engine\n")
+            .expect("failed to write mod.rs");
+    }
+
+    let extraction = walker::extract(root);
+
+    assert_eq!(extraction.docs.len(), 1);
+    assert_eq!(extraction.docs[0].module_path, "order_book");
+    assert_eq!(
+        extraction.warnings,
+        vec![
+            "Order Book/mod.rs: module path 'order_book' spells 'Order Book' as 'order_book' (segments are lowercase ASCII letters, digits, and `_`)",
+            "_lib/mod.rs: module path '_lib' uses '_lib', which is reserved for crate roots; skipping it",
+        ]
+    );
+    assert_eq!(extraction.skipped.len(), 1);
+    assert!(extraction.skipped[0].path.ends_with("mod.rs"));
 }
 
 #[test]
@@ -86,7 +144,7 @@ fn flat_module_without_c4_marker_is_skipped() {
     )
     .expect("failed to write utils.rs");
 
-    let docs = walker::extract_all_docs(root);
+    let docs = walker::extract(root).docs;
 
     // Should be skipped because it has no C4 marker
     assert_eq!(docs.len(), 0);
@@ -126,7 +184,7 @@ fn mixed_traditional_and_flat_modules() {
     )
     .expect("failed to write bus/calc.rs");
 
-    let mut docs = walker::extract_all_docs(root);
+    let mut docs = walker::extract(root).docs;
     docs.sort_by(|a, b| a.module_path.cmp(&b.module_path));
 
     assert_eq!(docs.len(), 4);
@@ -135,3 +193,86 @@ fn mixed_traditional_and_flat_modules() {
     assert_eq!(docs[2].module_path, "bus.calc");
     assert_eq!(docs[3].module_path, "router");
 }
+
+#[test]
+fn mod_rs_less_tree_resolves_containment_and_catalog() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    // 2018-style layout: foo.rs declares submodules living in foo/
+    fs::write(
+        root.join("foo.rs"),
+        "//! @c4 container\n//!\n//! # Foo\n//!\n//! Parent module\n//!\n//! | File | Pattern | Purpose | Health |\n//! |------|---------|---------|--------|\n//! | `bar.rs` | -- | Child | active |\n",
+    )
+    .expect("failed to write foo.rs");
+    fs::create_dir_all(root.join("foo")).expect("failed to create foo dir");
+    fs::write(
+        root.join("foo/bar.rs"),
+        "//! @c4 component\n//!\n//! # Bar\n//!\n//! Child module\n",
+    )
+    .expect("failed to write foo/bar.rs");
+
+    let docs = walker::extract(root).docs;
+
+    assert_eq!(docs.len(), 2);
+    assert_eq!(docs[0].module_path, "foo");
+    assert_eq!(docs[1].module_path, "foo.bar");
+    assert_eq!(docs[1].parent_container, Some("foo".to_string()));
+
+    // foo's catalog lives in foo/, not next to foo.rs
+    assert_eq!(docs[0].catalog_dir(), Some(root.join("foo")));
+    assert!(docs[0].catalog_dir().unwrap().join(&docs[0].files[0].name).exists());
+}
+
+#[test]
+fn flat_module_without_directory_catalogs_siblings() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::write(
+        root.join("router.rs"),
+        "//! @c4 container\n//!\n//! # Router\n//!\n//! HTTP request router\n",
+    )
+    .expect("failed to write router.rs");
+
+    let docs = walker::extract(root).docs;
+
+    assert_eq!(docs[0].catalog_dir(), Some(root.to_path_buf()));
+}
+
+#[test]
+fn files_dir_overrides_catalog_directory() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::create_dir_all(root.join("api")).expect("failed to create api dir");
+    fs::create_dir_all(root.join("proto")).expect("failed to create proto dir");
+    fs::write(root.join("proto/user.proto"), "syntax = \"proto3\";\n")
+        .expect("failed to write user.proto");
+    fs::write(
+        root.join("api/mod.rs"),
+        "//! @c4 container\n//! @files-dir ../proto\n//!\n//! # Api\n//!\n//! Generated API surface\n//!\n//! | File | Pattern | Purpose | Health |\n//! |------|---------|---------|--------|\n//! | `user.proto` | -- | User schema | stable |\n",
+    )
+    .expect("failed to write api/mod.rs");
+
+    let docs = walker::extract(root).docs;
+
+    assert_eq!(docs[0].files_dir.as_deref(), Some("../proto"));
+    assert_eq!(docs[0].description, "Generated API surface");
+    let dir = docs[0].catalog_dir().expect("catalog dir");
+    assert!(dir.join("user.proto").exists());
+}
+
+#[test]
+fn module_sources_are_read_in_name_order() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    for name in ["zeta.rs", "alpha.rs", "mid.rs", "beta.rs"] {
+        fs::write(root.join(name), "pub struct S;\n").expect("failed to write source");
+    }
+
+    let names: Vec<String> = walker::read_rs_sources(root).into_iter().map(|(name, _)| name).collect();
+
+    assert_eq!(names, vec!["alpha.rs", "beta.rs", "mid.rs", "zeta.rs"]);
+}
@@ -0,0 +1,145 @@
+//! Accuracy harness for the structural pattern heuristics
+//!
+//! Runs every heuristic over the labeled corpus in `tests/heuristic_corpus/`
+//! (`<pattern>/positive/*.rs` should match, `<pattern>/negative/*.rs` should
+//! not) and fails when precision or recall drops below the floor recorded
+//! here. Raise a floor when a heuristic improves; lowering one needs a reason.
+//!
+//! Run with `--nocapture` to see the per-pattern table.
+
+use archidoc_rust::pattern_heuristic;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Corpus directory, pattern name, minimum precision, minimum recall.
+const THRESHOLDS: &[(&str, &str, f64, f64)] = &[
+    ("observer", "Observer", 0.75, 1.0),
+    ("strategy", "Strategy", 0.75, 1.0),
+    ("facade", "Facade", 1.0, 1.0),
+    ("builder", "Builder", 0.75, 1.0),
+    ("factory", "Factory", 0.75, 1.0),
+    ("adapter", "Adapter", 0.75, 1.0),
+    ("decorator", "Decorator", 0.65, 1.0),
+    ("singleton", "Singleton", 0.75, 1.0),
+    ("command", "Command", 1.0, 1.0),
+    ("repository", "Repository", 1.0, 1.0),
+    ("mediator", "Mediator", 1.0, 1.0),
+    ("state", "State", 1.0, 1.0),
+    ("template_method", "Template Method", 1.0, 1.0),
+];
+
+#[derive(Default)]
+struct Score {
+    true_positives: usize,
+    false_positives: usize,
+    false_negatives: usize,
+    misses: Vec<String>,
+}
+
+impl Score {
+    fn precision(&self) -> f64 {
+        ratio(self.true_positives, self.true_positives + self.false_positives)
+    }
+
+    fn recall(&self) -> f64 {
+        ratio(self.true_positives, self.true_positives + self.false_negatives)
+    }
+}
+
+/// A heuristic that never fires has nothing to be imprecise about.
+fn ratio(hits: usize, total: usize) -> f64 {
+    if total == 0 {
+        1.0
+    } else {
+        hits as f64 / total as f64
+    }
+}
+
+fn corpus_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/heuristic_corpus")
+}
+
+fn snippets(dir: &Path) -> Vec<(String, String)> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", dir.display(), e))
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "rs"))
+        .collect();
+    files.sort();
+    files
+        .into_iter()
+        .map(|p| {
+            let source = fs::read_to_string(&p).expect("failed to read snippet");
+            (p.file_name().unwrap().to_string_lossy().to_string(), source)
+        })
+        .collect()
+}
+
+fn score(dir: &str, pattern: &str) -> Score {
+    let base = corpus_dir().join(dir);
+    let mut score = Score::default();
+
+    for (name, source) in snippets(&base.join("positive")) {
+        if pattern_heuristic::check_pattern(pattern, &source) {
+            score.true_positives += 1;
+        } else {
+            score.false_negatives += 1;
+            score.misses.push(format!("missed positive/{}", name));
+        }
+    }
+    for (name, source) in snippets(&base.join("negative")) {
+        if pattern_heuristic::check_pattern(pattern, &source) {
+            score.false_positives += 1;
+            score.misses.push(format!("matched negative/{}", name));
+        }
+    }
+    score
+}
+
+#[test]
+fn every_corpus_directory_has_a_threshold() {
+    let mut dirs: Vec<String> = fs::read_dir(corpus_dir())
+        .expect("failed to read corpus")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    dirs.sort();
+    let mut known: Vec<String> = THRESHOLDS.iter().map(|(d, ..)| d.to_string()).collect();
+    known.sort();
+
+    assert_eq!(dirs, known);
+}
+
+#[test]
+fn heuristics_meet_accuracy_thresholds() {
+    let mut failures = Vec::new();
+
+    println!("{:<16} {:>3} {:>3} {:>3} {:>9} {:>7}", "pattern", "tp", "fp", "fn", "precision", "recall");
+    for (dir, pattern, min_precision, min_recall) in THRESHOLDS {
+        let score = score(dir, pattern);
+        println!(
+            "{:<16} {:>3} {:>3} {:>3} {:>9.2} {:>7.2}",
+            pattern,
+            score.true_positives,
+            score.false_positives,
+            score.false_negatives,
+            score.precision(),
+            score.recall()
+        );
+        for miss in &score.misses {
+            println!("    {}", miss);
+        }
+
+        assert!(score.true_positives + score.false_negatives > 0, "{} has no positive snippets", pattern);
+        if score.precision() < *min_precision {
+            failures.push(format!("{}: precision {:.2} < {:.2}", pattern, score.precision(), min_precision));
+        }
+        if score.recall() < *min_recall {
+            failures.push(format!("{}: recall {:.2} < {:.2}", pattern, score.recall(), min_recall));
+        }
+    }
+
+    assert!(failures.is_empty(), "heuristic accuracy regressed:\n{}", failures.join("\n"));
+}
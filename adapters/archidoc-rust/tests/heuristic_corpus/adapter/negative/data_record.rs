@@ -0,0 +1,12 @@
+pub struct Order {
+    pub id: u64,
+    pub symbol: String,
+    pub quantity: u32,
+    pub price: f64,
+}
+
+impl Order {
+    pub fn notional(&self) -> f64 {
+        self.quantity as f64 * self.price
+    }
+}
@@ -0,0 +1,10 @@
+pub struct RetrySettings {
+    pub attempts: u32,
+    pub delay_ms: u64,
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        Self { attempts: 3, delay_ms: 250 }
+    }
+}
@@ -0,0 +1,14 @@
+use std::fmt;
+
+pub struct Hex {
+    bytes: Vec<u8>,
+}
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in &self.bytes {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
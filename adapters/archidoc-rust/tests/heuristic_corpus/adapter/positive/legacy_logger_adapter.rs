@@ -0,0 +1,13 @@
+pub trait Logger {
+    fn log(&self, message: &str);
+}
+
+pub struct SyslogAdapter {
+    inner: legacy::Syslog,
+}
+
+impl Logger for SyslogAdapter {
+    fn log(&self, message: &str) {
+        self.inner.write_entry(6, message);
+    }
+}
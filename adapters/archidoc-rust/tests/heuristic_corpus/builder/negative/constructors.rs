@@ -0,0 +1,14 @@
+pub struct RingBuffer {
+    data: Vec<u8>,
+    head: usize,
+}
+
+impl RingBuffer {
+    pub fn new() -> Self {
+        Self::with_capacity(1024)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { data: vec![0; capacity], head: 0 }
+    }
+}
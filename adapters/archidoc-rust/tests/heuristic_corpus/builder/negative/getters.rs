@@ -0,0 +1,14 @@
+pub struct User {
+    name: String,
+    email: String,
+}
+
+impl User {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+}
@@ -0,0 +1,9 @@
+pub struct PoolConfig {
+    size: usize,
+}
+
+impl PoolConfig {
+    pub fn build(&self) -> Pool {
+        Pool::with_capacity(self.size)
+    }
+}
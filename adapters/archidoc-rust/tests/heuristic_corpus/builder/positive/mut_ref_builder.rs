@@ -0,0 +1,17 @@
+#[derive(Default)]
+pub struct CommandBuilder {
+    program: String,
+    args: Vec<String>,
+}
+
+impl CommandBuilder {
+    pub fn arg(&mut self, arg: &str) -> &mut Self {
+        self.args.push(arg.to_string());
+        self
+    }
+
+    pub fn args(&mut self, args: &[&str]) -> &mut Self {
+        self.args.extend(args.iter().map(|a| a.to_string()));
+        self
+    }
+}
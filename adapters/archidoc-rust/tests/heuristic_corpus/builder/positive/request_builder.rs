@@ -0,0 +1,21 @@
+pub struct RequestBuilder {
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout_ms: u64,
+}
+
+impl RequestBuilder {
+    pub fn header(mut self, key: &str, value: &str) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn timeout(mut self, ms: u64) -> Self {
+        self.timeout_ms = ms;
+        self
+    }
+
+    pub fn build(self) -> Request {
+        Request { url: self.url, headers: self.headers, timeout_ms: self.timeout_ms }
+    }
+}
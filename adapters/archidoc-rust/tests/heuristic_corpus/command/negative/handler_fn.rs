@@ -0,0 +1,6 @@
+pub fn handle(input: &str) -> String {
+    match input.trim() {
+        "ping" => "pong".to_string(),
+        other => format!("unknown: {}", other),
+    }
+}
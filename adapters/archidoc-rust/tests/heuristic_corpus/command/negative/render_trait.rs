@@ -0,0 +1,11 @@
+pub trait Render {
+    fn render(&self) -> String;
+}
+
+pub struct Badge(pub String);
+
+impl Render for Badge {
+    fn render(&self) -> String {
+        format!("[{}]", self.0)
+    }
+}
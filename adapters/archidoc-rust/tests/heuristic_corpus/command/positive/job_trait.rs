@@ -0,0 +1,7 @@
+pub trait Job: Send {
+    fn run(&self) -> Result<(), String>;
+}
+
+pub struct Queue {
+    jobs: Vec<Box<dyn Job>>,
+}
@@ -0,0 +1,19 @@
+pub trait EditCommand {
+    fn execute(&mut self, doc: &mut String);
+    fn undo(&mut self, doc: &mut String);
+}
+
+pub struct Insert {
+    pub at: usize,
+    pub text: String,
+}
+
+impl EditCommand for Insert {
+    fn execute(&mut self, doc: &mut String) {
+        doc.insert_str(self.at, &self.text);
+    }
+
+    fn undo(&mut self, doc: &mut String) {
+        doc.replace_range(self.at..self.at + self.text.len(), "");
+    }
+}
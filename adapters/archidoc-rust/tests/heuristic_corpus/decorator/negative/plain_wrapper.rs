@@ -0,0 +1,7 @@
+pub struct Meters(f64);
+
+impl Meters {
+    pub fn to_feet(&self) -> f64 {
+        self.0 * 3.28084
+    }
+}
@@ -0,0 +1,7 @@
+pub trait Plugin {
+    fn name(&self) -> &str;
+}
+
+pub fn load_plugins() -> Vec<Box<dyn Plugin>> {
+    Vec::new()
+}
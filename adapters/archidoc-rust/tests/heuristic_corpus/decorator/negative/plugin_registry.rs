@@ -0,0 +1,13 @@
+pub trait Plugin {
+    fn name(&self) -> &str;
+}
+
+pub struct Registry {
+    plugins: Vec<Box<dyn Plugin>>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self { plugins: Vec::new() }
+    }
+}
@@ -0,0 +1,15 @@
+pub trait Store {
+    fn get(&self, key: &str) -> Option<String>;
+}
+
+pub struct LoggingStore {
+    inner: Box<dyn Store>,
+}
+
+impl Store for LoggingStore {
+    fn get(&self, key: &str) -> Option<String> {
+        let value = self.inner.get(key);
+        eprintln!("get {} -> {:?}", key, value);
+        value
+    }
+}
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+pub trait HttpClient: Send + Sync {
+    fn fetch(&self, url: &str) -> Result<Vec<u8>, String>;
+}
+
+pub struct Retrying {
+    inner: Arc<dyn HttpClient>,
+    attempts: u32,
+}
+
+impl HttpClient for Retrying {
+    fn fetch(&self, url: &str) -> Result<Vec<u8>, String> {
+        let mut last = Err("no attempts".to_string());
+        for _ in 0..self.attempts {
+            last = self.inner.fetch(url);
+            if last.is_ok() {
+                break;
+            }
+        }
+        last
+    }
+}
@@ -0,0 +1,6 @@
+mod cache;
+mod fetch;
+
+pub fn get(url: &str) -> Vec<u8> {
+    cache::lookup(url).unwrap_or_else(|| fetch::download(url))
+}
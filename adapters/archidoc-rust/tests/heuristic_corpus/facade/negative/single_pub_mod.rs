@@ -0,0 +1,5 @@
+pub mod types;
+
+pub fn version() -> &'static str {
+    "1.0.0"
+}
@@ -0,0 +1,4 @@
+mod parser;
+mod lexer;
+
+pub use parser::{parse, Ast};
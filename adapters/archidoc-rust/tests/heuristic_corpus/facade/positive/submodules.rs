@@ -0,0 +1,4 @@
+//! Storage backends.
+pub mod memory;
+pub mod sqlite;
+pub mod s3;
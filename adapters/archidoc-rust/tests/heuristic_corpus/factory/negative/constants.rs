@@ -0,0 +1,6 @@
+pub const MAX_RETRIES: u32 = 5;
+pub const DEFAULT_PORT: u16 = 8080;
+
+pub fn is_retryable(status: u16) -> bool {
+    matches!(status, 429 | 502 | 503 | 504)
+}
@@ -0,0 +1,3 @@
+pub fn numbers_up_to(limit: u32) -> impl Iterator<Item = u32> {
+    (0..limit).filter(|n| n % 2 == 0)
+}
@@ -0,0 +1,4 @@
+pub fn parse_pair(input: &str) -> Option<(String, String)> {
+    let (key, value) = input.split_once('=')?;
+    Some((key.trim().to_string(), value.trim().to_string()))
+}
@@ -0,0 +1,11 @@
+pub trait Connector {
+    fn connect(&self, url: &str) -> Result<(), String>;
+}
+
+pub fn make_connector(scheme: &str) -> Result<Box<dyn Connector>, String> {
+    match scheme {
+        "postgres" => Ok(Box::new(postgres::PgConnector::default())),
+        "sqlite" => Ok(Box::new(sqlite::SqliteConnector::default())),
+        other => Err(format!("unsupported scheme '{}'", other)),
+    }
+}
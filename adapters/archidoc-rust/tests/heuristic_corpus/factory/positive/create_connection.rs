@@ -0,0 +1,7 @@
+pub struct Connection {
+    dsn: String,
+}
+
+pub fn create_connection(dsn: &str) -> Connection {
+    Connection { dsn: dsn.to_string() }
+}
@@ -0,0 +1,17 @@
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+struct Circle(f64);
+struct Square(f64);
+
+impl Shape for Circle { fn area(&self) -> f64 { 3.14159 * self.0 * self.0 } }
+impl Shape for Square { fn area(&self) -> f64 { self.0 * self.0 } }
+
+pub fn shape_for(kind: &str, size: f64) -> Option<Box<dyn Shape>> {
+    match kind {
+        "circle" => Some(Box::new(Circle(size))),
+        "square" => Some(Box::new(Square(size))),
+        _ => None,
+    }
+}
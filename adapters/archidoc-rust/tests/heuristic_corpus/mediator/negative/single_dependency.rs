@@ -0,0 +1,11 @@
+use std::sync::Arc;
+
+pub struct Greeter {
+    clock: Arc<Clock>,
+}
+
+impl Greeter {
+    pub fn greet(&self, name: &str) -> String {
+        format!("Good {}, {}", self.clock.part_of_day(), name)
+    }
+}
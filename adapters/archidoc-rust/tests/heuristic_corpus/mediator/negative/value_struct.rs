@@ -0,0 +1,10 @@
+pub struct Rect {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Rect {
+    pub fn area(&self) -> f64 {
+        self.width * self.height
+    }
+}
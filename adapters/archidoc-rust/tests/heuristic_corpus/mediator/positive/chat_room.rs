@@ -0,0 +1,3 @@
+pub trait ChatMediator {
+    fn broadcast(&self, from: &str, message: &str);
+}
@@ -0,0 +1,21 @@
+use std::sync::Arc;
+use std::sync::mpsc::Sender;
+
+pub struct TradingBus {
+    feeds: Arc<FeedManager>,
+    risk: Arc<RiskEngine>,
+    orders: Sender<OrderCommand>,
+}
+
+impl TradingBus {
+    pub fn dispatch(&self, message: BusMessage) {
+        match message {
+            BusMessage::Quote(q) => self.feeds.update(q),
+            BusMessage::Order(o) => {
+                if self.risk.approve(&o) {
+                    let _ = self.orders.send(o);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+
+pub struct Dialog {
+    checkbox: Rc<RefCell<Checkbox>>,
+    submit: Rc<RefCell<Button>>,
+}
+
+impl Dialog {
+    pub fn on_toggle(&self) {
+        let checked = self.checkbox.borrow().checked;
+        self.submit.borrow_mut().enabled = checked;
+    }
+}
@@ -0,0 +1,9 @@
+use std::collections::BTreeMap;
+
+pub fn group_by<T, K: Ord>(items: Vec<T>, key: impl Fn(&T) -> K) -> BTreeMap<K, Vec<T>> {
+    let mut groups = BTreeMap::new();
+    for item in items {
+        groups.entry(key(&item)).or_insert_with(Vec::new).push(item);
+    }
+    groups
+}
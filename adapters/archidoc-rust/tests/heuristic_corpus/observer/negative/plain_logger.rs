@@ -0,0 +1,13 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+
+pub struct FileLogger {
+    path: String,
+}
+
+impl FileLogger {
+    pub fn log(&self, line: &str) -> std::io::Result<()> {
+        let mut file = OpenOptions::new().append(true).create(true).open(&self.path)?;
+        writeln!(file, "{}", line)
+    }
+}
@@ -0,0 +1,9 @@
+pub struct Table {
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn sort_by_column(&mut self, column: usize) {
+        self.rows.sort_by(|a, b| a[column].cmp(&b[column]));
+    }
+}
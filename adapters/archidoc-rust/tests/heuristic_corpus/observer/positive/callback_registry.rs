@@ -0,0 +1,15 @@
+pub struct Button {
+    handlers: Vec<Box<dyn FnMut(u32, u32)>>,
+}
+
+impl Button {
+    pub fn on_click(&mut self, handler: impl FnMut(u32, u32) + 'static) {
+        self.handlers.push(Box::new(handler));
+    }
+
+    pub fn click(&mut self, x: u32, y: u32) {
+        for handler in &mut self.handlers {
+            handler(x, y);
+        }
+    }
+}
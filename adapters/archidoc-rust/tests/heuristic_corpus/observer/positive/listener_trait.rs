@@ -0,0 +1,15 @@
+pub trait ConfigListener {
+    fn on_change(&self, key: &str, value: &str);
+}
+
+pub struct ConfigStore {
+    listeners: Vec<Box<dyn ConfigListener>>,
+}
+
+impl ConfigStore {
+    pub fn set(&mut self, key: &str, value: &str) {
+        for listener in &self.listeners {
+            listener.on_change(key, value);
+        }
+    }
+}
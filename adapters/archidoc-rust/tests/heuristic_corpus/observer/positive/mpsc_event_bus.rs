@@ -0,0 +1,18 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+
+pub enum MarketEvent {
+    Tick { symbol: String, price: f64 },
+    Halt(String),
+}
+
+pub struct EventBus {
+    subscribers: Vec<Sender<MarketEvent>>,
+}
+
+impl EventBus {
+    pub fn subscribe(&mut self) -> Receiver<MarketEvent> {
+        let (tx, rx) = mpsc::channel();
+        self.subscribers.push(tx);
+        rx
+    }
+}
@@ -0,0 +1,17 @@
+use std::collections::HashMap;
+
+pub struct Lru {
+    map: HashMap<String, String>,
+    order: Vec<String>,
+}
+
+impl Lru {
+    pub fn get(&mut self, key: &str) -> Option<&String> {
+        self.map.get(key)
+    }
+
+    pub fn insert(&mut self, key: String, value: String) {
+        self.order.push(key.clone());
+        self.map.insert(key, value);
+    }
+}
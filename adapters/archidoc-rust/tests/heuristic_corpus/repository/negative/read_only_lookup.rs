@@ -0,0 +1,4 @@
+pub trait CountryLookup {
+    fn find_by_code(&self, code: &str) -> Option<Country>;
+    fn all(&self) -> Vec<Country>;
+}
@@ -0,0 +1,6 @@
+pub trait OrderRepository {
+    fn find_by_id(&self, id: u64) -> Option<Order>;
+    fn find_open(&self) -> Vec<Order>;
+    fn save(&mut self, order: Order);
+    fn delete(&mut self, id: u64) -> bool;
+}
@@ -0,0 +1,16 @@
+pub struct Notes {
+    conn: rusqlite::Connection,
+}
+
+impl Notes {
+    pub fn list(&self) -> rusqlite::Result<Vec<String>> {
+        let mut stmt = self.conn.prepare("SELECT body FROM notes")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        rows.collect()
+    }
+
+    pub fn add(&self, body: &str) -> rusqlite::Result<()> {
+        self.conn.execute("INSERT INTO notes (body) VALUES (?1)", [body])?;
+        Ok(())
+    }
+}
@@ -0,0 +1,16 @@
+use sqlx::PgPool;
+
+pub struct UserStore {
+    pool: PgPool,
+}
+
+impl UserStore {
+    pub async fn get(&self, id: i64) -> sqlx::Result<User> {
+        sqlx::query_as("SELECT * FROM users WHERE id = $1").bind(id).fetch_one(&self.pool).await
+    }
+
+    pub async fn insert(&self, user: &User) -> sqlx::Result<()> {
+        sqlx::query("INSERT INTO users (name) VALUES ($1)").bind(&user.name).execute(&self.pool).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub const PRIMES: [u32; 5] = [2, 3, 5, 7, 11];
+
+pub fn nth_prime(n: usize) -> Option<u32> {
+    PRIMES.get(n).copied()
+}
@@ -0,0 +1,12 @@
+use std::cell::OnceCell;
+
+pub struct Document {
+    text: String,
+    word_count: OnceCell<usize>,
+}
+
+impl Document {
+    pub fn word_count(&self) -> usize {
+        *self.word_count.get_or_init(|| self.text.split_whitespace().count())
+    }
+}
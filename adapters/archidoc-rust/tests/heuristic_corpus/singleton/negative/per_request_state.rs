@@ -0,0 +1,10 @@
+pub struct RequestContext {
+    pub request_id: String,
+    pub user: Option<String>,
+}
+
+impl RequestContext {
+    pub fn new(request_id: String) -> Self {
+        Self { request_id, user: None }
+    }
+}
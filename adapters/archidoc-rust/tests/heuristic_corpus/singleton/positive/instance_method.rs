@@ -0,0 +1,8 @@
+pub struct Clock;
+
+impl Clock {
+    pub fn instance() -> &'static Clock {
+        static CLOCK: Clock = Clock;
+        &CLOCK
+    }
+}
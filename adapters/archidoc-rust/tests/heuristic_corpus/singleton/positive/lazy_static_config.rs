@@ -0,0 +1,7 @@
+lazy_static::lazy_static! {
+    static ref SETTINGS: Settings = Settings::from_env();
+}
+
+pub fn settings() -> &'static Settings {
+    &SETTINGS
+}
@@ -0,0 +1,8 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+static REGISTRY: OnceLock<Mutex<HashMap<String, u32>>> = OnceLock::new();
+
+pub fn registry() -> &'static Mutex<HashMap<String, u32>> {
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
@@ -0,0 +1,11 @@
+pub enum Color {
+    Red,
+    Green,
+}
+
+pub fn name(color: &Color) -> &'static str {
+    match color {
+        Color::Red => "red",
+        Color::Green => "green",
+    }
+}
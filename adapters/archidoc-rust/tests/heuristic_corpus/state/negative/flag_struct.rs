@@ -0,0 +1,9 @@
+pub struct Toggle {
+    on: bool,
+}
+
+impl Toggle {
+    pub fn flip(&mut self) {
+        self.on = !self.on;
+    }
+}
@@ -0,0 +1,13 @@
+pub trait PostState {
+    fn approve(self: Box<Self>) -> Box<dyn PostState>;
+}
+
+pub struct Post {
+    state: Box<dyn PostState>,
+}
+
+impl Post {
+    pub fn set_state(&mut self, next: Box<dyn PostState>) {
+        self.state = next;
+    }
+}
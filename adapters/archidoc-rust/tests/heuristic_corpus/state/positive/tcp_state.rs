@@ -0,0 +1,20 @@
+pub enum TcpState {
+    Closed,
+    Listen,
+    Established,
+}
+
+pub struct Socket {
+    state: TcpState,
+}
+
+impl Socket {
+    pub fn handle(&mut self, event: Event) {
+        self.state = match (&self.state, event) {
+            (TcpState::Closed, Event::Open) => TcpState::Listen,
+            (TcpState::Listen, Event::Accept) => TcpState::Established,
+            (_, Event::Close) => TcpState::Closed,
+            _ => return,
+        };
+    }
+}
@@ -0,0 +1,16 @@
+#[derive(Clone, Copy)]
+pub enum Light {
+    Red,
+    Yellow,
+    Green,
+}
+
+impl Light {
+    pub fn next(self) -> Self {
+        match self {
+            Light::Red => Self::Green,
+            Light::Green => Self::Yellow,
+            Light::Yellow => Self::Red,
+        }
+    }
+}
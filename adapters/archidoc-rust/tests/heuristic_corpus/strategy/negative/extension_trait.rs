@@ -0,0 +1,9 @@
+pub trait StrExt {
+    fn is_blank(&self) -> bool;
+}
+
+impl StrExt for str {
+    fn is_blank(&self) -> bool {
+        self.trim().is_empty()
+    }
+}
@@ -0,0 +1,8 @@
+pub fn price(base: f64, quantity: u32) -> f64 {
+    let total = base * quantity as f64;
+    if quantity >= 100 {
+        total * 0.9
+    } else {
+        total
+    }
+}
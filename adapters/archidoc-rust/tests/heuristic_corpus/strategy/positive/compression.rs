@@ -0,0 +1,16 @@
+pub trait Compressor: Send + Sync {
+    fn compress(&self, input: &[u8]) -> Vec<u8>;
+    fn name(&self) -> &'static str;
+}
+
+pub struct Identity;
+
+impl Compressor for Identity {
+    fn compress(&self, input: &[u8]) -> Vec<u8> {
+        input.to_vec()
+    }
+
+    fn name(&self) -> &'static str {
+        "identity"
+    }
+}
@@ -0,0 +1,15 @@
+pub trait PricingStrategy {
+    fn price(&self, base: f64, quantity: u32) -> f64;
+}
+
+pub struct BulkDiscount {
+    pub threshold: u32,
+    pub discount: f64,
+}
+
+impl PricingStrategy for BulkDiscount {
+    fn price(&self, base: f64, quantity: u32) -> f64 {
+        let total = base * quantity as f64;
+        if quantity >= self.threshold { total * (1.0 - self.discount) } else { total }
+    }
+}
@@ -0,0 +1,16 @@
+use std::time::Duration;
+
+pub trait Backoff {
+    fn delay(&self, attempt: u32) -> Option<Duration>;
+}
+
+pub struct Exponential {
+    pub base: Duration,
+    pub max_attempts: u32,
+}
+
+impl Backoff for Exponential {
+    fn delay(&self, attempt: u32) -> Option<Duration> {
+        (attempt < self.max_attempts).then(|| self.base * 2u32.pow(attempt))
+    }
+}
@@ -0,0 +1,7 @@
+pub trait Describe {
+    fn id(&self) -> u64;
+
+    fn kind(&self) -> &'static str {
+        "item"
+    }
+}
@@ -0,0 +1,4 @@
+pub trait Codec {
+    fn encode(&self, value: &str) -> Vec<u8>;
+    fn decode(&self, bytes: &[u8]) -> String;
+}
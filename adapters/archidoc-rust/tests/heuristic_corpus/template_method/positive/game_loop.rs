@@ -0,0 +1,11 @@
+pub trait Game {
+    fn init(&mut self);
+    fn update(&mut self) -> bool;
+    fn finish(&mut self) {}
+
+    fn play(&mut self) {
+        self.init();
+        while self.update() {}
+        self.finish();
+    }
+}
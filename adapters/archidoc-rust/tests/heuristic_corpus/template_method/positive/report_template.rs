@@ -0,0 +1,12 @@
+pub trait Report {
+    fn title(&self) -> String;
+    fn rows(&self) -> Vec<String>;
+
+    fn render(&self) -> String {
+        let mut out = format!("# {}\n", self.title());
+        for row in self.rows() {
+            out.push_str(&format!("- {}\n", row));
+        }
+        out
+    }
+}
@@ -0,0 +1,115 @@
+//! Integration test for sidecar annotation files
+//!
+//! Verifies that directories annotated via `archidoc.toml` / `.archidoc.yaml`
+//! produce the same ModuleDocs as doc-comment annotations.
+
+use archidoc_rust::walker;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn toml_sidecar_produces_module_doc() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::create_dir_all(root.join("protos")).expect("failed to create protos dir");
+    fs::write(root.join("protos/generated.rs"), "pub struct Request;\n")
+        .expect("failed to write generated.rs");
+    fs::write(
+        root.join("protos/archidoc.toml"),
+        "c4 = \"container\"\nname = \"Protos\"\ndescription = \"Generated gRPC stubs\"\n",
+    )
+    .expect("failed to write archidoc.toml");
+
+    let docs = walker::extract(root).docs;
+
+    assert_eq!(docs.len(), 1);
+    assert_eq!(docs[0].module_path, "protos");
+    assert_eq!(docs[0].c4_level, archidoc_types::ElementKind::Container);
+    assert_eq!(docs[0].description, "Generated gRPC stubs");
+    assert!(docs[0].source_file.ends_with("archidoc.toml"));
+}
+
+#[test]
+fn yaml_sidecar_nested_component() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::create_dir_all(root.join("vendor/sdk")).expect("failed to create vendor dir");
+    fs::write(
+        root.join("vendor/sdk/.archidoc.yaml"),
+        "c4: component\nname: SDK\ndescription: Vendored payment SDK\n",
+    )
+    .expect("failed to write .archidoc.yaml");
+
+    let docs = walker::extract(root).docs;
+
+    assert_eq!(docs.len(), 1);
+    assert_eq!(docs[0].module_path, "vendor.sdk");
+    assert_eq!(docs[0].parent_container, Some("vendor".to_string()));
+}
+
+#[test]
+fn mod_rs_takes_priority_over_sidecar() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::create_dir_all(root.join("foo")).expect("failed to create foo dir");
+    fs::write(
+        root.join("foo/archidoc.toml"),
+        "c4 = \"container\"\ndescription = \"From sidecar\"\n",
+    )
+    .expect("failed to write archidoc.toml");
+    fs::write(
+        root.join("foo/mod.rs"),
+        "//! @c4 container\n//!\n//! # Foo\n//!\n//! From doc comments\n",
+    )
+    .expect("failed to write foo/mod.rs");
+
+    let docs = walker::extract(root).docs;
+
+    assert_eq!(docs.len(), 1);
+    assert_eq!(docs[0].description, "From doc comments");
+}
+
+#[test]
+fn project_config_without_c4_is_ignored() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::write(root.join("archidoc.toml"), "[output]\ndir = \"docs\"\n")
+        .expect("failed to write archidoc.toml");
+
+    let docs = walker::extract(root).docs;
+
+    assert!(docs.is_empty());
+}
+
+#[test]
+fn extract_reports_shadowed_and_invalid_sidecars() {
+    let temp = TempDir::new().expect("failed to create temp dir");
+    let root = temp.path();
+
+    fs::create_dir_all(root.join("foo")).expect("failed to create foo dir");
+    fs::create_dir_all(root.join("bar")).expect("failed to create bar dir");
+    fs::write(root.join("foo/archidoc.toml"), "c4 = \"container\"\n")
+        .expect("failed to write foo/archidoc.toml");
+    fs::write(root.join("foo/mod.rs"), "//! @c4 container\n//!\n//! # Foo\n")
+        .expect("failed to write foo/mod.rs");
+    fs::write(root.join("bar/archidoc.toml"), "c4 = \"system\"\n")
+        .expect("failed to write bar/archidoc.toml");
+
+    let extraction = walker::extract(root);
+
+    assert_eq!(extraction.docs.len(), 1);
+    assert_eq!(extraction.warnings.len(), 1);
+    let reasons: Vec<(&str, &str)> = extraction
+        .skipped
+        .iter()
+        .map(|s| (s.path.as_str(), s.reason.as_str()))
+        .collect();
+    assert_eq!(reasons.len(), 2);
+    assert!(reasons[0].0.ends_with("bar/archidoc.toml"));
+    assert!(reasons[1].0.ends_with("foo/archidoc.toml"));
+    assert_eq!(reasons[1].1, "module 'foo' is documented in source");
+}
@@ -59,14 +59,21 @@ export function extractJsDoc(content: string): string | null {
   return result || null;
 }
 
+const ELEMENT_KINDS = ["context", "container", "component", "person", "external_system", "deployment_node"];
+
 /**
- * Extract the C4 level from JSDoc content.
+ * Extract the element kind from JSDoc content.
  *
- * Looks for `@c4 container` or `@c4 component` tags.
+ * The first `@c4 <kind>` tag naming a kind wins: `container`, `component`,
+ * `context`, `person`, `external_system`, `deployment_node`, or `custom:<name>`.
  */
 export function extractC4Level(content: string): C4Level {
-  if (/@c4\s+container\b/.test(content)) return "container";
-  if (/@c4\s+component\b/.test(content)) return "component";
+  for (const match of content.matchAll(/@c4\s+(\S+)/g)) {
+    const kind = match[1].toLowerCase();
+    if (ELEMENT_KINDS.includes(kind) || /^custom:[a-z0-9_-]+$/.test(kind)) {
+      return kind as C4Level;
+    }
+  }
   return "unknown";
 }
 
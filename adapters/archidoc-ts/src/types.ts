@@ -8,7 +8,15 @@
  * | `types.ts` | -- | IR type definitions | stable |
  */
 
-export type C4Level = "container" | "component" | "unknown";
+export type C4Level =
+  | "context"
+  | "container"
+  | "component"
+  | "person"
+  | "external_system"
+  | "deployment_node"
+  | `custom:${string}`
+  | "unknown";
 export type PatternStatus = "planned" | "verified";
 export type HealthStatus = "planned" | "active" | "stable";
 
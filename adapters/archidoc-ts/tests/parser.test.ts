@@ -41,6 +41,11 @@ describe("extractC4Level", () => {
     expect(extractC4Level("@c4 component\n\nSome desc")).toBe("component");
   });
 
+  it("detects other element kinds", () => {
+    expect(extractC4Level("@c4 uses api \"Calls\"\n@c4 person")).toBe("person");
+    expect(extractC4Level("/** @c4 custom:queue */")).toBe("custom:queue");
+  });
+
   it("returns unknown when no marker", () => {
     expect(extractC4Level("Just some text")).toBe("unknown");
   });
@@ -0,0 +1,124 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use archidoc_engine::confluence::ConfluenceApi;
+
+/// [`ConfluenceApi`] over the REST API (`/rest/api/content`), through
+/// `curl`. The credentials reach curl on stdin, not its command line.
+pub struct CurlApi {
+    /// Site address, e.g. `https://example.atlassian.net/wiki`.
+    pub base_url: String,
+    /// Space key.
+    pub space: String,
+    /// Account for basic authentication with `token` as its API token
+    /// (Confluence Cloud); without one, `token` is sent as a bearer
+    /// personal access token (Data Center).
+    pub user: Option<String>,
+    pub token: String,
+}
+
+impl CurlApi {
+    fn request(
+        &self,
+        method: &str,
+        path: &str,
+        query: &[(&str, &str)],
+        body: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value, String> {
+        let url = format!("{}/rest/api/content{}", self.base_url.trim_end_matches('/'), path);
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let auth = match &self.user {
+            Some(user) => format!("user = {}\n", quote(&format!("{}:{}", user, self.token))),
+            None => format!("header = {}\n", quote(&format!("Authorization: Bearer {}", self.token))),
+        };
+
+        let mut command = Command::new("curl");
+        command.args(["-sS", "-K", "-", "-w", "\n%{http_code}", "-H", "Accept: application/json"]);
+        let body_file = match body {
+            Some(body) => {
+                let mut file = tempfile::NamedTempFile::new().map_err(|e| e.to_string())?;
+                file.write_all(body.to_string().as_bytes()).map_err(|e| e.to_string())?;
+                Some(file)
+            }
+            None => None,
+        };
+        match &body_file {
+            Some(file) => {
+                command.args(["-X", method, "-H", "Content-Type: application/json", "--data-binary"]);
+                command.arg(format!("@{}", file.path().display()));
+            }
+            None => {
+                command.arg("-G");
+                for (name, value) in query {
+                    command.arg("--data-urlencode").arg(format!("{}={}", name, value));
+                }
+            }
+        }
+        let mut child = command
+            .arg(&url)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("failed to run curl: {}", e))?;
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(auth.as_bytes())
+            .map_err(|e| format!("failed to run curl: {}", e))?;
+        let output = child.wait_with_output().map_err(|e| format!("failed to run curl: {}", e))?;
+        if !output.status.success() {
+            return Err(format!("{} {} failed: {}", method, url, String::from_utf8_lossy(&output.stderr).trim()));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (response, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+        if !status.starts_with('2') {
+            return Err(format!("{} {} returned HTTP {}: {}", method, url, status, response.trim()));
+        }
+        serde_json::from_str(response).map_err(|e| format!("{} {} returned invalid JSON: {}", method, url, e))
+    }
+
+    fn content(&self, title: &str, parent_id: Option<&str>, body: &str) -> serde_json::Value {
+        let ancestors: Vec<serde_json::Value> = parent_id.map(|id| serde_json::json!({ "id": id })).into_iter().collect();
+        serde_json::json!({
+            "type": "page",
+            "title": title,
+            "space": { "key": self.space },
+            "ancestors": ancestors,
+            "body": { "storage": { "value": body, "representation": "storage" } },
+        })
+    }
+}
+
+impl ConfluenceApi for CurlApi {
+    fn find(&self, title: &str) -> Result<Option<(String, u64)>, String> {
+        let found = self.request(
+            "GET",
+            "",
+            &[("spaceKey", &self.space), ("title", title), ("expand", "version")],
+            None,
+        )?;
+        Ok(found["results"].get(0).and_then(|page| {
+            let id = page["id"].as_str()?;
+            Some((id.to_string(), page["version"]["number"].as_u64()?))
+        }))
+    }
+
+    fn create(&self, title: &str, parent_id: Option<&str>, body: &str) -> Result<String, String> {
+        let created = self.request("POST", "", &[], Some(self.content(title, parent_id, body)))?;
+        created["id"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| format!("Confluence returned no id for '{}'", title))
+    }
+
+    fn update(&self, id: &str, version: u64, title: &str, parent_id: Option<&str>, body: &str) -> Result<(), String> {
+        let mut content = self.content(title, parent_id, body);
+        content["id"] = serde_json::json!(id);
+        content["version"] = serde_json::json!({ "number": version });
+        self.request("PUT", &format!("/{}", id), &[], Some(content))?;
+        Ok(())
+    }
+}
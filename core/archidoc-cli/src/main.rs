@@ -1,8 +1,12 @@
 use std::fs;
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use clap::{Args, Parser, Subcommand};
+use archidoc_engine::compile::timed;
+use archidoc_types::CompileReport;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+mod confluence;
 
 #[derive(Parser)]
 #[command(name = "archidoc")]
@@ -33,7 +37,7 @@ struct GlobalOpts {
     #[arg(short, long)]
     verbose: bool,
 
-    /// Output machine-readable JSON (for --health, --validate, --check)
+    /// Output machine-readable JSON (for --health, --validate, --validate-ir, --check, --risks, --lint, gap)
     #[arg(long)]
     json: bool,
 
@@ -41,18 +45,78 @@ struct GlobalOpts {
     #[arg(long)]
     check: bool,
 
+    /// With --check, show a unified diff of each drifted file
+    #[arg(long)]
+    diff: bool,
+
+    /// With --check or --validate, skip the run when nothing under the root changed relative to --base;
+    /// --validate also narrows to the elements owning the changed files
+    #[arg(long)]
+    changed: bool,
+
+    /// Git ref that --changed compares against
+    #[arg(long, default_value = "main", requires = "changed")]
+    base: String,
+
+    /// Check the architecture against the approved snapshot (exit 1 on structural divergence)
+    #[arg(long)]
+    check_approved: bool,
+
     /// Print architecture health report
     #[arg(long)]
     health: bool,
 
+    /// With --health, also write the report as a self-contained HTML dashboard
+    #[arg(long, value_name = "FILE", requires = "health")]
+    html: Option<PathBuf>,
+
     /// Validate file tables against filesystem
     #[arg(long)]
     validate: bool,
 
+    /// With --validate, fail on warnings (orphans by default) as well as errors
+    #[arg(long, requires = "validate")]
+    warnings_as_errors: bool,
+
+    /// Print the risk register collected from @risk annotations
+    #[arg(long)]
+    risks: bool,
+
+    /// Lint annotation prose (placeholder, TODO, short, and duplicate descriptions)
+    #[arg(long)]
+    lint: bool,
+
+    /// Collect per-module complexity metrics (LOC, files, public items, function length)
+    #[arg(long)]
+    metrics: bool,
+
+    /// Promote `planned` patterns to `verified` when structural heuristics match,
+    /// logging each promotion to .archidoc/promotions.log
+    #[arg(long)]
+    verify_patterns: bool,
+
+    /// LCOV or `cargo llvm-cov --json` report to map onto modules
+    #[arg(long, value_name = "FILE")]
+    coverage: Option<PathBuf>,
+
+    /// Write a JSON run report (modules, skipped files, warnings, phase timings, outputs)
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+
     /// Output JSON IR to stdout
     #[arg(long)]
     emit_ir: bool,
 
+    /// With --emit-ir, leave raw annotation text out of the IR (structured
+    /// fields are kept)
+    #[arg(long, requires = "emit_ir")]
+    no_content: bool,
+
+    /// Byte-for-byte reproducible output: no timestamps, source paths
+    /// relative to the project root, no timings in --report
+    #[arg(long)]
+    reproducible: bool,
+
     /// Also generate PlantUML diagram files
     #[arg(long)]
     plantuml: bool,
@@ -61,6 +125,34 @@ struct GlobalOpts {
     #[arg(long)]
     drawio: bool,
 
+    /// Also generate D2 diagram files
+    #[arg(long)]
+    d2: bool,
+
+    /// Also generate a Structurizr DSL workspace
+    #[arg(long)]
+    structurizr: bool,
+
+    /// Also write ARCHITECTURE.md as a single HTML page
+    #[arg(long)]
+    html_page: bool,
+
+    /// Also write Confluence storage-format pages
+    #[arg(long)]
+    confluence: bool,
+
+    /// Also write multi-page documentation site projects under the `[site]` directory
+    #[arg(long, value_name = "KIND", value_delimiter = ',')]
+    site: Vec<SiteKind>,
+
+    /// Also render the Mermaid and PlantUML diagram files to images (needs `mmdc` / PlantUML; see `[render]`)
+    #[arg(long, value_name = "FORMAT", value_delimiter = ',')]
+    render: Vec<RenderKind>,
+
+    /// Run the fitness functions and add their results to ARCHITECTURE.md
+    #[arg(long)]
+    fitness: bool,
+
     /// Do not generate ARCHITECTURE.ai.md
     #[arg(long)]
     no_ai: bool,
@@ -80,6 +172,25 @@ struct GlobalOpts {
     /// Merge multiple IR files (use with multiple --from-json-file; requires --merge-ir)
     #[arg(long)]
     merge_ir: bool,
+
+    /// Value for a `${NAME}` placeholder in annotation prose, overriding the
+    /// environment and `[variables]` (repeatable)
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    vars: Vec<String>,
+
+    /// Hand-authored IR whose elements are laid over the ones extracted from
+    /// source, overlay winning (repeatable; later files win)
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["from_json", "from_json_file"])]
+    overlay: Vec<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum IrCommand {
+    /// Rewrite IR files from older schema versions as the current version, in place
+    Upgrade {
+        #[arg(required = true, value_name = "FILE")]
+        files: Vec<PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -95,12 +206,192 @@ enum Commands {
         /// Path to directory to generate annotation for
         path: PathBuf,
     },
+    /// Create a new module directory with an annotated mod.rs
+    Stub {
+        /// Dot-separated module path, relative to the source root (e.g. bus.event_lanes)
+        module_path: String,
+        /// Element kind (container, component, person, system, custom:<name>);
+        /// container at the top level and component below it when omitted
+        #[arg(long)]
+        level: Option<String>,
+        /// Parent module path to create the module under
+        #[arg(long)]
+        parent: Option<String>,
+        /// Also declare the module in its parent's mod.rs (or lib.rs/main.rs)
+        #[arg(long)]
+        register: bool,
+    },
     /// Generate root-level lib.rs/index.ts template with architectural sections
     Init {
         /// Language for comment syntax (auto-detected from Cargo.toml/package.json if omitted)
         #[arg(long)]
         lang: Option<String>,
     },
+    /// Record the current architecture as approved in .archidoc/approved.json
+    Freeze {
+        /// Name of the reviewer approving the architecture
+        #[arg(long)]
+        reviewer: String,
+
+        /// Approval date as YYYY-MM-DD (defaults to today, UTC)
+        #[arg(long)]
+        date: Option<String>,
+    },
+    /// Write a generated README.md into each annotated module directory
+    ScaffoldReadmes,
+    /// Archive the current architecture IR in .archidoc/history/
+    Snapshot {
+        /// Commit or tag to record the snapshot under (defaults to the checked-out tag or commit)
+        #[arg(long = "ref")]
+        git_ref: Option<String>,
+
+        /// Snapshot date as YYYY-MM-DD (defaults to today, UTC)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// After archiving, keep only the N most recent snapshots
+        #[arg(long)]
+        keep_last: Option<usize>,
+
+        /// After archiving, delete snapshots older than N days
+        #[arg(long)]
+        keep_days: Option<u64>,
+
+        /// List archived snapshots instead of taking a new one
+        #[arg(long)]
+        list: bool,
+    },
+    /// Drop removed elements from ARCHITECTURE.md's Archived Elements appendix ([architecture] archive)
+    PurgeArchived {
+        /// Module paths to purge (all archived elements when omitted)
+        #[arg(value_name = "MODULE")]
+        modules: Vec<String>,
+    },
+    /// Summarize runs recorded in .archidoc/stats.jsonl
+    Stats {
+        /// Print the recorded runs as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compare two architecture IR snapshots
+    Diff {
+        /// Base architecture: an IR file, or a ref or YYYY-MM-DD date from the snapshot archive
+        before: String,
+
+        /// Changed architecture, same forms as BEFORE (defaults to parsing the project path)
+        #[arg(long)]
+        after: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+    },
+    /// Compare a planned architecture IR against the code: missing elements and relationships, unplanned dependencies
+    Gap {
+        /// Planned architecture: an IR file, or a ref or YYYY-MM-DD date from the snapshot archive
+        planned: String,
+    },
+    /// List language adapters and whether the project uses them
+    Adapters {
+        /// Also show which annotation features each adapter honors, and the gaps
+        #[arg(long)]
+        doctor: bool,
+    },
+    /// (Experimental) Write purposes and health values edited in ARCHITECTURE.md's file tables back to the annotations
+    PushBack {
+        /// Edited document to read, relative to the project root (defaults to --output)
+        #[arg(long)]
+        doc: Option<PathBuf>,
+        /// List the edits without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Publish the pages written by --confluence to the `[confluence]` space through the REST API; the
+    /// token is read from CONFLUENCE_TOKEN (with CONFLUENCE_USER for Confluence Cloud)
+    PublishConfluence {
+        /// List the pages without publishing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Generate editor snippets for annotation blocks, `@c4 uses` lines, and file-table rows
+    Snippets {
+        /// Editor to generate snippets for
+        #[arg(long, value_enum)]
+        editor: Editor,
+        /// Write the snippets to this file instead of stdout
+        #[arg(long, value_name = "FILE")]
+        out: Option<PathBuf>,
+    },
+    /// Propose file-table rows (inferred pattern, planned health) for .rs files missing from their catalog
+    Catalog {
+        /// Write the rows into the file tables (default with `[catalog] auto_apply = true`)
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Normalize annotation blocks in place: aligned tables, single-spaced @c4 markers, sorted file tables
+    Fmt {
+        /// List files that need formatting and exit non-zero instead of rewriting them
+        #[arg(long)]
+        check: bool,
+    },
+    /// Run the full pipeline against archidoc's own crates (developer check; PATH defaults to this repository)
+    #[command(hide = true)]
+    VerifySelf,
+    /// Work with JSON IR files
+    Ir {
+        #[command(subcommand)]
+        command: IrCommand,
+    },
+    /// Roll many repositories up into a portfolio report: health scores, pattern adoption, drift, and a context diagram
+    OrgReport {
+        /// Per-repository JSON files as FILE or NAME=FILE: IR (--emit-ir), health (--health --json), or drift
+        /// (--check --json); files with the same NAME describe one repository
+        #[arg(required = true, value_name = "INPUT")]
+        inputs: Vec<String>,
+        /// Also write the report as a self-contained HTML page
+        #[arg(long, value_name = "FILE")]
+        html: Option<PathBuf>,
+    },
+    /// Explain a finding code from --validate, --lint, or --fitness (e.g. AD101): its cause, impact, and fix;
+    /// without a code, list every code
+    Explain {
+        /// Finding code, or the rule or fitness function name it stands for
+        code: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Editor {
+    /// VS Code `.code-snippets` JSON
+    Vscode,
+    /// JetBrains live template set (XML)
+    Jetbrains,
+    /// SnipMate-format snippets, loaded by LuaSnip and nvim-snippets
+    Nvim,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SiteKind {
+    /// `mkdocs.yml` and a `docs/` tree for `mkdocs build`
+    Mkdocs,
+    /// An `architecture/` doc category with front matter for a Docusaurus `docs/` folder
+    Docusaurus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum RenderKind {
+    Svg,
+    Png,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DiffFormat {
+    /// Plain text, one change per line
+    Text,
+    /// The IrDiff as JSON
+    Json,
+    /// Markdown body for a pull-request comment
+    PrComment,
 }
 
 fn main() {
@@ -117,10 +408,83 @@ fn main() {
                 run_suggest(&path);
                 return;
             }
+            Commands::Stub { module_path, level, parent, register } => {
+                run_stub(&cli.path, &module_path, level.as_deref(), parent.as_deref(), register);
+                return;
+            }
             Commands::Init { lang } => {
                 run_init(&cli.path, lang.as_deref());
                 return;
             }
+            Commands::Freeze { reviewer, date } => {
+                run_freeze(&cli.path, &reviewer, date.as_deref());
+                return;
+            }
+            Commands::ScaffoldReadmes => {
+                run_scaffold_readmes(&cli.path);
+                return;
+            }
+            Commands::Snapshot { git_ref, date, keep_last, keep_days, list } => {
+                let policy = archidoc_engine::history::PrunePolicy { keep_last, keep_days };
+                run_snapshot(&cli.path, git_ref.as_deref(), date.as_deref(), policy, list);
+                return;
+            }
+            Commands::PurgeArchived { modules } => {
+                run_purge_archived(&cli.path, &modules);
+                return;
+            }
+            Commands::Stats { json } => {
+                run_stats(&cli.path, json);
+                return;
+            }
+            Commands::Diff { before, after, format } => {
+                run_diff(&cli.path, &before, after.as_deref(), format);
+                return;
+            }
+            Commands::Gap { planned } => {
+                run_gap(&cli.path, &planned, cli.global.json);
+                return;
+            }
+            Commands::Adapters { doctor } => {
+                run_adapters(&cli.path, doctor, cli.global.json);
+                return;
+            }
+            Commands::PushBack { doc, dry_run } => {
+                run_push_back(&cli.path, &cli.global, doc.as_deref(), dry_run);
+                return;
+            }
+            Commands::PublishConfluence { dry_run } => {
+                run_publish_confluence(&cli.path, &cli.global, dry_run);
+                return;
+            }
+            Commands::Snippets { editor, out } => {
+                run_snippets(&cli.path, &cli.global, editor, out.as_deref());
+                return;
+            }
+            Commands::Catalog { apply } => {
+                run_catalog(&cli.path, &cli.global, apply);
+                return;
+            }
+            Commands::Fmt { check } => {
+                run_fmt(&cli.path, check);
+                return;
+            }
+            Commands::VerifySelf => {
+                run_verify_self(&cli.path, &cli.global);
+                return;
+            }
+            Commands::Ir { command: IrCommand::Upgrade { files } } => {
+                run_ir_upgrade(&files);
+                return;
+            }
+            Commands::OrgReport { inputs, html } => {
+                run_org_report(&inputs, html.as_deref(), &cli.global);
+                return;
+            }
+            Commands::Explain { code } => {
+                run_explain(code.as_deref());
+                return;
+            }
         }
     }
 
@@ -137,10 +501,16 @@ fn main() {
         }
     } else if cli.global.check {
         Mode::Check
+    } else if cli.global.check_approved {
+        Mode::CheckApproved
     } else if cli.global.health {
         Mode::Health
     } else if cli.global.validate {
         Mode::Validate
+    } else if cli.global.risks {
+        Mode::Risks
+    } else if cli.global.lint {
+        Mode::Lint
     } else if cli.global.emit_ir {
         Mode::EmitIr
     } else {
@@ -158,39 +528,60 @@ fn main() {
     // Execute mode
     match mode {
         Mode::FromJsonStdin => {
-            let docs = read_ir_from_stdin();
+            let mut report = CompileReport::default();
+            let mut docs = timed(&mut report, "read_ir", read_ir_from_stdin);
             let root = cli
                 .path
                 .unwrap_or_else(|| std::env::current_dir().expect("failed to get current directory"));
-            run_generate(&root, &docs, &cli.global, verbosity);
+            let config = project_config(&root, &cli.global);
+            prepare_docs(&config, &cli.global, &mut docs, &mut report);
+            report.outputs = timed(&mut report, "generate", || {
+                run_generate(&root, &config, &docs, &[], &cli.global, verbosity)
+            });
+            finish_run(&root, &config, &cli.global, "generate", &report);
         }
         Mode::FromJsonFile => {
+            let mut report = CompileReport::default();
             let path = &cli.global.from_json_file[0];
-            let docs = read_ir_from_file(path);
+            let mut docs = timed(&mut report, "read_ir", || read_ir_from_file(path));
             let root = cli
                 .path
                 .unwrap_or_else(|| std::env::current_dir().expect("failed to get current directory"));
-            run_generate(&root, &docs, &cli.global, verbosity);
+            let config = project_config(&root, &cli.global);
+            prepare_docs(&config, &cli.global, &mut docs, &mut report);
+            report.outputs = timed(&mut report, "generate", || {
+                run_generate(&root, &config, &docs, &[], &cli.global, verbosity)
+            });
+            finish_run(&root, &config, &cli.global, "generate", &report);
         }
         Mode::MergeIr => {
             if cli.global.from_json_file.len() < 2 {
                 eprintln!("error: --merge-ir requires at least 2 --from-json-file arguments");
                 std::process::exit(1);
             }
-            let ir_sets: Vec<Vec<archidoc_types::ModuleDoc>> = cli
-                .global
-                .from_json_file
-                .iter()
-                .map(|p| read_ir_from_file(p))
-                .collect();
-            let docs = archidoc_engine::merge::merge_ir(ir_sets).unwrap_or_else(|e| {
-                eprintln!("error: {}", e);
-                std::process::exit(1);
+            let mut report = CompileReport::default();
+            let ir_sets: Vec<Vec<archidoc_types::ModuleDoc>> = timed(&mut report, "read_ir", || {
+                cli.global.from_json_file.iter().map(read_ir_from_file).collect()
             });
+            let outcome = timed(&mut report, "merge", || archidoc_engine::merge::merge_ir(ir_sets))
+                .unwrap_or_else(|e| {
+                    eprintln!("error: {}", e);
+                    std::process::exit(1);
+                });
+            for warning in &outcome.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            report.warnings.extend(outcome.warnings);
+            let mut docs = outcome.docs;
             let root = cli
                 .path
                 .unwrap_or_else(|| std::env::current_dir().expect("failed to get current directory"));
-            run_generate(&root, &docs, &cli.global, verbosity);
+            let config = project_config(&root, &cli.global);
+            prepare_docs(&config, &cli.global, &mut docs, &mut report);
+            report.outputs = timed(&mut report, "generate", || {
+                run_generate(&root, &config, &docs, &[], &cli.global, verbosity)
+            });
+            finish_run(&root, &config, &cli.global, "merge_ir", &report);
         }
         Mode::ValidateIr => {
             let json = if !cli.global.from_json_file.is_empty() {
@@ -206,7 +597,7 @@ fn main() {
                     .expect("failed to read from stdin");
                 buf
             };
-            run_validate_ir(&json);
+            run_validate_ir(&json, cli.global.json);
         }
         _ => {
             // Modes that parse from source need a root directory
@@ -219,15 +610,141 @@ fn main() {
                 std::process::exit(1);
             }
 
-            let docs = archidoc_rust::walker::extract_all_docs(&root);
+            let changed = if cli.global.changed {
+                if !matches!(mode, Mode::Check | Mode::Validate) {
+                    eprintln!("error: --changed only applies to --check and --validate");
+                    std::process::exit(1);
+                }
+                let files = changed_files_under(&root, &cli.global.base);
+                if files.is_empty() {
+                    println!(
+                        "No files under {} changed since {}.",
+                        root.display(),
+                        cli.global.base
+                    );
+                    return;
+                }
+                Some(files)
+            } else {
+                None
+            };
+
+            let config = project_config(&root, &cli.global);
+            let mut report = CompileReport::default();
+            let extraction = timed(&mut report, "extract", || archidoc_rust::walker::extract(&root));
+            for warning in &extraction.warnings {
+                eprintln!("warning: {}", warning);
+            }
+            let mut docs = extraction.docs;
+            report.modules_found = docs.len();
+            report.skipped_files = extraction.skipped;
+            report.warnings = extraction.warnings;
+            if !cli.global.overlay.is_empty() {
+                docs = timed(&mut report, "overlay", || {
+                    cli.global
+                        .overlay
+                        .iter()
+                        .fold(docs, |docs, path| archidoc_engine::merge::apply_overlay(docs, read_ir_from_file(path)))
+                });
+                report.modules_found = docs.len();
+            }
+            prepare_docs(&config, &cli.global, &mut docs, &mut report);
+
+            if cli.global.metrics {
+                timed(&mut report, "metrics", || archidoc_rust::metrics::collect_metrics(&mut docs));
+            }
+            if cli.global.verify_patterns {
+                let changes = timed(&mut report, "verify_patterns", || {
+                    archidoc_rust::promote::auto_promote(&mut docs, &config.heuristics)
+                });
+                archidoc_engine::promotions::record(
+                    &root,
+                    &changes,
+                    &archidoc_engine::date::now_timestamp(),
+                    env!("CARGO_PKG_VERSION"),
+                )
+                .unwrap_or_else(|e| {
+                    eprintln!("error: {}", e);
+                    std::process::exit(1);
+                });
+            }
+            if let Some(path) = &cli.global.coverage {
+                let coverage = timed(&mut report, "coverage", || archidoc_engine::coverage::load(path))
+                    .unwrap_or_else(|e| {
+                        eprintln!("error: {}", e);
+                        std::process::exit(1);
+                    });
+                archidoc_engine::coverage::apply_coverage(&mut docs, &coverage);
+            }
+
+            // --check always compares every artifact: any change under the
+            // root can stale one. --validate narrows to the changed elements
+            // unless a change is deleted or unowned.
+            if let Some(files) = &changed {
+                let touched = archidoc_engine::ownership::changed_modules(&docs, files);
+                if verbosity == Verbosity::Verbose {
+                    match &touched {
+                        Some(touched) => {
+                            let names: Vec<&str> = touched.iter().map(String::as_str).collect();
+                            eprintln!("changed elements: {}", names.join(", "));
+                        }
+                        None => eprintln!("changed files outside annotated elements; considering all elements"),
+                    }
+                }
+                if let (Mode::Validate, Some(touched)) = (mode, &touched) {
+                    docs.retain(|d| touched.contains(&d.module_path));
+                }
+            }
+
+            let fitness = if matches!(mode, Mode::Generate | Mode::Check) && config.generators.fitness {
+                timed(&mut report, "fitness", || archidoc_rust::fitness::run_all(&docs, &config))
+            } else {
+                Vec::new()
+            };
 
-            match mode {
-                Mode::Generate => run_generate(&root, &docs, &cli.global, verbosity),
-                Mode::Check => run_check(&root, &docs, &cli.global.output, cli.global.json),
-                Mode::Health => run_health(&docs, cli.global.json),
-                Mode::Validate => run_validate(&docs, cli.global.json),
-                Mode::EmitIr => run_emit_ir(&docs),
+            let phase = match mode {
+                Mode::Generate => "generate",
+                Mode::Check => "check",
+                Mode::CheckApproved => "check_approved",
+                Mode::Health => "health",
+                Mode::Validate => "validate",
+                Mode::Risks => "risks",
+                Mode::Lint => "lint",
+                _ => "emit_ir",
+            };
+            let mut outputs = Vec::new();
+            let passed = timed(&mut report, phase, || match mode {
+                Mode::Generate => {
+                    outputs = run_generate(&root, &config, &docs, &fitness, &cli.global, verbosity);
+                    true
+                }
+                Mode::Check => run_check(&root, &config, &docs, &fitness, &cli.global),
+                Mode::CheckApproved => run_check_approved(&root, &docs, cli.global.json),
+                Mode::Health => {
+                    run_health(&root, &docs, &cli.global, config.generators.language);
+                    true
+                }
+                Mode::Validate => run_validate(&config, &docs, &cli.global),
+                Mode::Risks => {
+                    run_risks(&docs, cli.global.json);
+                    true
+                }
+                Mode::Lint => run_lint(&config, &docs, &cli.global),
+                Mode::EmitIr => {
+                    let mut docs = output_inputs(&root, &docs, &root, &cli.global).0;
+                    if cli.global.no_content {
+                        archidoc_engine::ir::strip_content(&mut docs);
+                    }
+                    run_emit_ir(&docs);
+                    true
+                }
                 _ => unreachable!(),
+            });
+
+            report.outputs = outputs;
+            finish_run(&root, &config, &cli.global, phase, &report);
+            if !passed {
+                std::process::exit(1);
             }
         }
     }
@@ -237,8 +754,11 @@ fn main() {
 enum Mode {
     Generate,
     Check,
+    CheckApproved,
     Health,
     Validate,
+    Risks,
+    Lint,
     EmitIr,
     FromJsonStdin,
     FromJsonFile,
@@ -253,12 +773,15 @@ enum Verbosity {
     Verbose,
 }
 
+/// Write the generated documentation and return the files written.
 fn run_generate(
-    root: &PathBuf,
+    root: &Path,
+    config: &archidoc_types::ProjectConfig,
     docs: &[archidoc_types::ModuleDoc],
+    fitness: &[archidoc_types::FitnessSummary],
     opts: &GlobalOpts,
     verbosity: Verbosity,
-) {
+) -> Vec<archidoc_types::OutputFile> {
     if verbosity != Verbosity::Quiet {
         println!("archidoc: {} modules", docs.len());
     }
@@ -272,175 +795,586 @@ fn run_generate(
             println!("  2. Run `archidoc suggest <dir>` to generate a template for a directory");
             println!("  3. See https://github.com/archidoc/archidoc#getting-started");
         }
-        return;
+        return Vec::new();
     }
 
-    // Generate single ARCHITECTURE.md
     let output_path = if opts.output.is_absolute() {
         opts.output.clone()
     } else {
         root.join(&opts.output)
     };
-    let link_base = output_path.parent().unwrap_or(root.as_path());
-    let content = archidoc_engine::architecture::generate(docs, link_base);
-
-    if let Some(parent) = output_path.parent() {
-        if !parent.as_os_str().is_empty() {
-            fs::create_dir_all(parent).expect("failed to create output directory");
-        }
-    }
-    fs::write(&output_path, &content).unwrap_or_else(|e| {
-        eprintln!("error: failed to write {}: {}", output_path.display(), e);
-        std::process::exit(1);
-    });
-
-    if verbosity != Verbosity::Quiet {
-        println!("wrote {}", output_path.display());
-    }
-
-    // AI context (default on, --no-ai to skip)
-    if !opts.no_ai {
-        let stem = output_path
-            .file_stem()
-            .unwrap()
-            .to_string_lossy();
-        let ai_path = output_path.with_file_name(format!("{}.ai.md", stem));
-        let ai_content = archidoc_engine::ai_context::generate(docs);
-        fs::write(&ai_path, &ai_content).unwrap_or_else(|e| {
-            eprintln!("error: failed to write {}: {}", ai_path.display(), e);
+    let out_dir = output_path.parent().unwrap_or(root);
+    // Validation reads the catalogs before paths are made reproducible
+    let snapshot = snapshot(root, docs, config);
+    let (docs, link_base) = output_inputs(root, docs, out_dir, opts);
+
+    // ARCHITECTURE.md plus the enabled generators (AI context is on by
+    // default; --no-ai skips it)
+    let ids = archidoc_engine::config::enabled_generators(config);
+    let mut generators = archidoc_engine::generator::builtins(&ids, &output_settings(&output_path, &link_base, fitness, snapshot, config));
+    generators.extend(archidoc_engine::plugin::declared(config, root));
+    let generators = archidoc_engine::hooks::wrap(generators, &config.hooks, root, false);
+    let generators = with_header(generators, config);
+    let mut outputs = archidoc_engine::compile::write_outputs(&docs, out_dir, &generators)
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
             std::process::exit(1);
         });
-        if verbosity != Verbosity::Quiet {
-            println!("wrote {}", ai_path.display());
-        }
+    let rendered = archidoc_engine::render::render(&outputs, out_dir, &config.render.formats, &config.render, root)
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        });
+    for warning in rendered.warnings.iter().chain(&archidoc_engine::mermaid::edge_style_warnings(&config.diagrams)) {
+        eprintln!("warning: {}", warning);
     }
+    outputs.extend(rendered.files);
 
-    // Optional sidecar outputs
-    if opts.plantuml || opts.drawio {
-        let sidecar_dir = output_path.parent().unwrap_or(root);
-        let c4_dir = sidecar_dir.join("c4");
-        fs::create_dir_all(&c4_dir).expect("failed to create c4 dir");
-
-        if opts.plantuml {
-            archidoc_engine::plantuml::generate_container(&c4_dir, docs);
-            archidoc_engine::plantuml::generate_component(&c4_dir, docs);
-            if verbosity == Verbosity::Verbose {
-                println!("wrote PlantUML files to {}", c4_dir.display());
-            }
+    if verbosity != Verbosity::Quiet {
+        for output in &outputs {
+            println!("wrote {}", output.path);
         }
+    }
+    outputs
+}
 
-        if opts.drawio {
-            let drawio_dir = sidecar_dir.join("drawio");
-            fs::create_dir_all(&drawio_dir).expect("failed to create drawio dir");
-            archidoc_engine::drawio::generate_container_csv(&drawio_dir, docs);
-            archidoc_engine::drawio::generate_component_csv(&drawio_dir, docs);
-            if verbosity == Verbosity::Verbose {
-                println!("wrote draw.io CSV files to {}", drawio_dir.display());
-            }
+/// `archidoc.toml` from `root`, with generator flags from the command line
+/// applied on top.
+fn project_config(root: &Path, opts: &GlobalOpts) -> archidoc_types::ProjectConfig {
+    let mut config = archidoc_engine::config::load(root).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+    config.generators.ai_context &= !opts.no_ai;
+    config.generators.plantuml |= opts.plantuml;
+    config.generators.drawio |= opts.drawio;
+    config.generators.d2 |= opts.d2;
+    config.generators.structurizr |= opts.structurizr;
+    config.generators.html |= opts.html_page;
+    config.generators.confluence |= opts.confluence;
+    config.generators.mkdocs |= opts.site.contains(&SiteKind::Mkdocs);
+    config.generators.docusaurus |= opts.site.contains(&SiteKind::Docusaurus);
+    config.generators.fitness |= opts.fitness;
+    for kind in &opts.render {
+        let format = match kind {
+            RenderKind::Svg => archidoc_types::RenderFormat::Svg,
+            RenderKind::Png => archidoc_types::RenderFormat::Png,
+        };
+        if !config.render.formats.contains(&format) {
+            config.render.formats.push(format);
         }
     }
+    if opts.reproducible {
+        config.header.timestamp = false;
+        config.front_matter.timestamp = false;
+    }
+    config
 }
 
-fn run_check(root: &PathBuf, docs: &[archidoc_types::ModuleDoc], output_path: &PathBuf, json: bool) {
-    let arch_file = if output_path.is_absolute() {
-        output_path.clone()
-    } else {
-        root.join(output_path)
-    };
-    let link_base = arch_file.parent().unwrap_or(root.as_path());
-    let report = archidoc_engine::check::check_drift(docs, &arch_file, link_base);
+/// Bring `docs` into the shape every generator expects: path rules,
+/// protocol spellings, and placeholders applied, relationship targets
+/// checked, and diagram ids made unique.
+fn prepare_docs(
+    config: &archidoc_types::ProjectConfig,
+    opts: &GlobalOpts,
+    docs: &mut Vec<archidoc_types::ModuleDoc>,
+    report: &mut CompileReport,
+) {
+    rewrite_paths(config, docs, report);
+    normalize_protocols(config, docs, report);
+    substitute_variables(config, opts, docs, report);
+    check_relationship_targets(config, opts, docs, report);
+    assign_ids(docs, report);
+    report.modules_found = docs.len();
+}
 
-    if json {
-        let json_output = serde_json::to_string_pretty(&report).expect("failed to serialize report");
-        println!("{}", json_output);
-    } else {
-        let text = archidoc_engine::check::format_drift_report(&report);
-        print!("{}", text);
+/// Apply the `[rewrite]` path rules, warning about rules that match nothing.
+fn rewrite_paths(
+    config: &archidoc_types::ProjectConfig,
+    docs: &mut Vec<archidoc_types::ModuleDoc>,
+    report: &mut CompileReport,
+) {
+    for warning in archidoc_engine::rewrite::rewrite_paths(docs, &config.rewrite) {
+        eprintln!("warning: {}", warning);
+        report.warnings.push(warning);
     }
+}
 
-    if report.has_drift() {
+/// Extract the project's elements with the `[rewrite]` path rules applied
+/// and diagram ids made unique, as the compile paths see them.
+fn extract_docs(root: &Path) -> Vec<archidoc_types::ModuleDoc> {
+    let config = archidoc_engine::config::load(root).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
         std::process::exit(1);
+    });
+    let extraction = archidoc_rust::walker::extract(root);
+    for warning in &extraction.warnings {
+        eprintln!("warning: {}", warning);
     }
+    let mut docs = extraction.docs;
+    for warning in archidoc_engine::rewrite::rewrite_paths(&mut docs, &config.rewrite) {
+        eprintln!("warning: {}", warning);
+    }
+    assign_ids(&mut docs, &mut CompileReport::default());
+    docs
 }
 
-fn run_health(docs: &[archidoc_types::ModuleDoc], json: bool) {
-    let report = archidoc_engine::health::aggregate_health(docs);
-
-    if json {
-        let json_output = serde_json::to_string_pretty(&report).expect("failed to serialize report");
-        println!("{}", json_output);
-    } else {
-        let text = archidoc_engine::health::format_health_report(&report);
-        print!("{}", text);
+/// Rewrite relationship protocols to their `[protocols]` catalog spelling,
+/// warning about unknown ones.
+fn normalize_protocols(
+    config: &archidoc_types::ProjectConfig,
+    docs: &mut [archidoc_types::ModuleDoc],
+    report: &mut CompileReport,
+) {
+    for warning in archidoc_engine::protocols::normalize_protocols(docs, &config.protocols) {
+        eprintln!("warning: {}", warning);
+        report.warnings.push(warning);
     }
 }
 
-fn run_validate(docs: &[archidoc_types::ModuleDoc], json: bool) {
-    let report = archidoc_engine::validate::validate_file_tables(docs);
-
-    if json {
-        let json_output = serde_json::to_string_pretty(&report).expect("failed to serialize report");
-        println!("{}", json_output);
-    } else {
-        let text = archidoc_engine::validate::format_validation_report(&report);
-        print!("{}", text);
+/// Resolve `${NAME}` placeholders from `--var`, the environment, and
+/// `[variables]`, in that order, warning about unresolved ones.
+fn substitute_variables(
+    config: &archidoc_types::ProjectConfig,
+    opts: &GlobalOpts,
+    docs: &mut [archidoc_types::ModuleDoc],
+    report: &mut CompileReport,
+) {
+    let mut vars = std::collections::BTreeMap::new();
+    for var in &opts.vars {
+        match var.split_once('=') {
+            Some((name, value)) if archidoc_engine::variables::is_variable_name(name) => {
+                vars.insert(name.to_string(), value.to_string());
+            }
+            _ => {
+                eprintln!("error: invalid --var '{}' (expected NAME=VALUE)", var);
+                std::process::exit(1);
+            }
+        }
+    }
+    let lookup = |name: &str| {
+        vars.get(name)
+            .cloned()
+            .or_else(|| std::env::var(name).ok())
+            .or_else(|| config.variables.get(name).cloned())
+    };
+    for warning in archidoc_engine::variables::substitute_variables(docs, lookup) {
+        eprintln!("warning: {}", warning);
+        report.warnings.push(warning);
     }
+}
 
-    if !report.is_clean() {
-        std::process::exit(1);
+/// Resolve diagram id collisions, warning about each reassigned id, and
+/// record every element's id in the run report.
+fn assign_ids(docs: &mut [archidoc_types::ModuleDoc], report: &mut CompileReport) {
+    for warning in archidoc_engine::ids::assign_ids(docs) {
+        eprintln!("warning: {}", warning);
+        report.warnings.push(warning);
     }
+    report.element_ids = archidoc_engine::ids::id_map(docs);
 }
 
-fn run_emit_ir(docs: &[archidoc_types::ModuleDoc]) {
-    let json = archidoc_engine::ir::serialize(docs);
-    println!("{}", json);
+/// Warn about relationships whose target is neither an element nor one of
+/// `[lint] external_systems`; `--lint` reports the same as findings.
+fn check_relationship_targets(
+    config: &archidoc_types::ProjectConfig,
+    opts: &GlobalOpts,
+    docs: &[archidoc_types::ModuleDoc],
+    report: &mut CompileReport,
+) {
+    if opts.lint {
+        return;
+    }
+    for finding in archidoc_engine::lint::unknown_targets(docs, &config.lint.external_systems) {
+        let warning = format!("{}: {}", finding.element, finding.message);
+        eprintln!("warning: {}", warning);
+        report.warnings.push(warning);
+    }
 }
 
-fn read_ir_from_stdin() -> Vec<archidoc_types::ModuleDoc> {
-    let mut json = String::new();
-    std::io::stdin()
-        .read_to_string(&mut json)
-        .expect("failed to read JSON IR from stdin");
-    archidoc_engine::ir::deserialize(&json).unwrap_or_else(|e| {
-        eprintln!("error: {}", e);
-        std::process::exit(1);
-    })
+/// `docs` as the generators see them, and the base for source links in
+/// ARCHITECTURE.md. With `--reproducible`, both are relative to `root` so
+/// the output does not depend on where the project is checked out.
+fn output_inputs(
+    root: &Path,
+    docs: &[archidoc_types::ModuleDoc],
+    out_dir: &Path,
+    opts: &GlobalOpts,
+) -> (Vec<archidoc_types::ModuleDoc>, PathBuf) {
+    let mut docs = docs.to_vec();
+    if !opts.reproducible {
+        return (docs, out_dir.to_path_buf());
+    }
+    archidoc_engine::reproducible::normalize_docs(&mut docs, root);
+    let link_base = out_dir.strip_prefix(root).unwrap_or(out_dir).to_path_buf();
+    (docs, link_base)
 }
 
-fn read_ir_from_file(path: &PathBuf) -> Vec<archidoc_types::ModuleDoc> {
-    let json = fs::read_to_string(path).unwrap_or_else(|e| {
-        eprintln!("error: failed to read {}: {}", path.display(), e);
-        std::process::exit(1);
-    });
-    archidoc_engine::ir::deserialize(&json).unwrap_or_else(|e| {
-        eprintln!("error: {}", e);
-        std::process::exit(1);
-    })
+/// Settings for the built-in generators writing to `architecture_file`.
+fn output_settings(
+    architecture_file: &Path,
+    link_base: &Path,
+    fitness: &[archidoc_types::FitnessSummary],
+    snapshot: archidoc_engine::architecture::Snapshot,
+    config: &archidoc_types::ProjectConfig,
+) -> archidoc_engine::generator::OutputSettings {
+    let mut settings = archidoc_engine::generator::OutputSettings::new(architecture_file, link_base);
+    settings.fitness = fitness.to_vec();
+    settings.snapshot = snapshot;
+    settings.language = config.generators.language;
+    settings.layout = config.architecture.clone();
+    settings.front_matter = front_matter_settings(config);
+    settings.diagrams = config.diagrams.clone();
+    settings.html = config.html.clone();
+    settings.confluence = config.confluence.clone();
+    settings.site = config.site.clone();
+    settings
 }
 
-fn run_validate_ir(json: &str) {
-    match archidoc_engine::ir::validate(json) {
-        Ok(()) => println!("IR is valid."),
-        Err(e) => {
-            eprintln!("{}", e);
+/// Project health, validation findings, and archived elements for
+/// ARCHITECTURE.md, as far as `[architecture]` asks for them.
+fn snapshot(
+    root: &Path,
+    docs: &[archidoc_types::ModuleDoc],
+    config: &archidoc_types::ProjectConfig,
+) -> archidoc_engine::architecture::Snapshot {
+    let validation = config.architecture.validation.then(|| {
+        let mut report = archidoc_engine::validate::validate_file_tables(docs);
+        report.severities = config.validate;
+        report
+    });
+    let archived = config.architecture.archive.then(|| {
+        archidoc_engine::history::archived_elements(root, docs).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
             std::process::exit(1);
-        }
+        })
+    });
+    archidoc_engine::architecture::Snapshot {
+        health: config.architecture.health.then(|| archidoc_engine::health::aggregate_health(docs)),
+        validation,
+        archived,
     }
 }
 
-fn run_suggest(path: &PathBuf) {
-    if !path.exists() {
-        eprintln!("error: path does not exist: {}", path.display());
-        std::process::exit(1);
+/// `generators` with a header comment, when `[header]` enables it.
+fn with_header(
+    generators: Vec<Box<dyn archidoc_engine::generator::Generator>>,
+    config: &archidoc_types::ProjectConfig,
+) -> Vec<Box<dyn archidoc_engine::generator::Generator>> {
+    if !config.header.enabled {
+        return generators;
     }
-    if !path.is_dir() {
-        eprintln!("error: path is not a directory: {}", path.display());
+    let command = std::iter::once("archidoc".to_string())
+        .chain(std::env::args().skip(1))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let settings = archidoc_engine::header::HeaderSettings {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        generated_at: config.header.timestamp.then(archidoc_engine::date::now_timestamp),
+        command,
+    };
+    archidoc_engine::header::wrap(generators, &settings)
+}
+
+/// Front matter for this run's pages, when `[front_matter]` enables it.
+fn front_matter_settings(
+    config: &archidoc_types::ProjectConfig,
+) -> Option<archidoc_engine::front_matter::FrontMatterSettings> {
+    config.front_matter.enabled.then(|| archidoc_engine::front_matter::FrontMatterSettings {
+        weight: config.front_matter.weight,
+        generated_at: config.front_matter.timestamp.then(archidoc_engine::date::now_timestamp),
+    })
+}
+
+/// Returns false when the documentation has drifted.
+fn run_check(
+    root: &Path,
+    config: &archidoc_types::ProjectConfig,
+    docs: &[archidoc_types::ModuleDoc],
+    fitness: &[archidoc_types::FitnessSummary],
+    opts: &GlobalOpts,
+) -> bool {
+    let arch_file = if opts.output.is_absolute() {
+        opts.output.clone()
+    } else {
+        root.join(&opts.output)
+    };
+    let out_dir = arch_file.parent().unwrap_or(root);
+    let snapshot = snapshot(root, docs, config);
+    let (docs, link_base) = output_inputs(root, docs, out_dir, opts);
+    let ids = archidoc_engine::config::checked_generators(config);
+    let mut generators = archidoc_engine::generator::builtins(&ids, &output_settings(&arch_file, &link_base, fitness, snapshot, config));
+    generators.extend(
+        archidoc_engine::plugin::declared(config, root)
+            .into_iter()
+            .filter(|g| !config.check.skip.iter().any(|s| s == g.id())),
+    );
+    let generators = archidoc_engine::hooks::wrap(generators, &config.hooks, root, true);
+    let generators = with_header(generators, config);
+    let report = archidoc_engine::check::check_artifacts(&docs, out_dir, &generators).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+
+    if opts.json {
+        let json_output = serde_json::to_string_pretty(&report).expect("failed to serialize report");
+        println!("{}", json_output);
+    } else {
+        let text = archidoc_engine::check::format_drift_report(&report, opts.diff);
+        print!("{}", text);
+    }
+
+    !report.has_drift()
+}
+
+/// Write the `--report` file, if requested, and append to the usage
+/// statistics when enabled in archidoc.toml.
+fn finish_run(
+    root: &Path,
+    config: &archidoc_types::ProjectConfig,
+    opts: &GlobalOpts,
+    mode: &str,
+    report: &CompileReport,
+) {
+    if let Some(path) = &opts.report {
+        let mut report = report.clone();
+        if opts.reproducible {
+            archidoc_engine::reproducible::normalize_report(&mut report, root);
+        }
+        let json = serde_json::to_string_pretty(&report).expect("failed to serialize report");
+        fs::write(path, json + "\n").unwrap_or_else(|e| {
+            eprintln!("error: failed to write {}: {}", path.display(), e);
+            std::process::exit(1);
+        });
+    }
+
+    if config.stats.enabled {
+        let run = archidoc_engine::stats::from_report(report, mode, &archidoc_engine::date::today());
+        if let Err(e) = archidoc_engine::stats::record(root, &run) {
+            eprintln!("warning: {}", e);
+        }
+    }
+}
+
+/// Files under `root` changed relative to `base`, from git.
+fn changed_files_under(root: &Path, base: &str) -> Vec<PathBuf> {
+    let abs_root = fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let files = archidoc_engine::git::changed_files(&abs_root, base).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+    files.into_iter().filter(|f| f.starts_with(&abs_root)).collect()
+}
+
+/// Returns false when the architecture diverges from the approved snapshot.
+fn run_check_approved(root: &Path, docs: &[archidoc_types::ModuleDoc], json: bool) -> bool {
+    let approved = archidoc_engine::freeze::read_approved(root).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+    let report = archidoc_engine::freeze::check_approved(&approved, docs);
+
+    if json {
+        let json_output = serde_json::to_string_pretty(&report).expect("failed to serialize report");
+        println!("{}", json_output);
+    } else {
+        let text = archidoc_engine::freeze::format_approval_report(&report);
+        print!("{}", text);
+    }
+
+    report.is_approved()
+}
+
+fn run_health(root: &Path, docs: &[archidoc_types::ModuleDoc], opts: &GlobalOpts, language: archidoc_types::Language) {
+    let report = archidoc_engine::health::aggregate_health(docs);
+
+    if let Some(path) = &opts.html {
+        let title = root
+            .canonicalize()
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| "Project".to_string());
+        let html = archidoc_engine::health_html::render_health_html(&report, &title);
+        if let Err(e) = fs::write(path, html) {
+            eprintln!("error: failed to write {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+        if !opts.quiet {
+            eprintln!("wrote {}", path.display());
+        }
+    }
+
+    if opts.json {
+        let json_output = serde_json::to_string_pretty(&report).expect("failed to serialize report");
+        println!("{}", json_output);
+    } else {
+        let text = archidoc_engine::health::format_health_report_localized(&report, language);
+        print!("{}", text);
+    }
+}
+
+fn run_lint(
+    config: &archidoc_types::ProjectConfig,
+    docs: &[archidoc_types::ModuleDoc],
+    opts: &GlobalOpts,
+) -> bool {
+    let report = archidoc_engine::lint::lint(docs, &config.lint);
+
+    if opts.json {
+        let json_output = serde_json::to_string_pretty(&report).expect("failed to serialize report");
+        println!("{}", json_output);
+    } else {
+        print!("{}", archidoc_engine::lint::format_lint_report(&report));
+    }
+
+    report.is_clean()
+}
+
+fn run_risks(docs: &[archidoc_types::ModuleDoc], json: bool) {
+    let register = archidoc_engine::risk::collect_risks(docs);
+
+    if json {
+        let json_output = serde_json::to_string_pretty(&register).expect("failed to serialize report");
+        println!("{}", json_output);
+    } else {
+        let text = archidoc_engine::risk::format_risk_register(&register);
+        print!("{}", text);
+    }
+}
+
+/// Returns false when file tables have ghosts, orphans, or invalid links.
+fn run_validate(
+    config: &archidoc_types::ProjectConfig,
+    docs: &[archidoc_types::ModuleDoc],
+    opts: &GlobalOpts,
+) -> bool {
+    let mut report = archidoc_engine::validate::validate_file_tables(docs);
+    report.severities = config.validate;
+
+    if opts.json {
+        let json_output = serde_json::to_string_pretty(&report).expect("failed to serialize report");
+        println!("{}", json_output);
+    } else {
+        let text = archidoc_engine::validate::format_validation_report(&report);
+        print!("{}", text);
+    }
+
+    report.passes(opts.warnings_as_errors)
+}
+
+fn run_emit_ir(docs: &[archidoc_types::ModuleDoc]) {
+    let json = archidoc_engine::ir::serialize(docs);
+    println!("{}", json);
+}
+
+fn read_ir_from_stdin() -> Vec<archidoc_types::ModuleDoc> {
+    let mut json = String::new();
+    std::io::stdin()
+        .read_to_string(&mut json)
+        .expect("failed to read JSON IR from stdin");
+    archidoc_engine::ir::deserialize(&json).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    })
+}
+
+fn read_ir_from_file(path: &PathBuf) -> Vec<archidoc_types::ModuleDoc> {
+    let json = fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("error: failed to read {}: {}", path.display(), e);
+        std::process::exit(1);
+    });
+    archidoc_engine::ir::deserialize(&json).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    })
+}
+
+fn run_ir_upgrade(files: &[PathBuf]) {
+    for path in files {
+        let json = fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("error: failed to read {}: {}", path.display(), e);
+            std::process::exit(1);
+        });
+        let (from, upgraded) = archidoc_engine::ir::upgrade_json(&json).unwrap_or_else(|e| {
+            eprintln!("error: {}: {}", path.display(), e);
+            std::process::exit(1);
+        });
+        if from == archidoc_engine::ir::VERSION {
+            println!("{}: already IR version {}", path.display(), from);
+            continue;
+        }
+        fs::write(path, upgraded + "\n").unwrap_or_else(|e| {
+            eprintln!("error: failed to write {}: {}", path.display(), e);
+            std::process::exit(1);
+        });
+        println!("{}: upgraded from IR version {} to {}", path.display(), from, archidoc_engine::ir::VERSION);
+    }
+}
+
+fn run_validate_ir(json: &str, as_json: bool) {
+    let violations = archidoc_engine::ir::violations(json);
+    let valid = violations.is_empty();
+    if as_json {
+        let report = archidoc_types::IrValidationReport { valid, violations };
+        let json_output = serde_json::to_string_pretty(&report).expect("failed to serialize report");
+        println!("{}", json_output);
+    } else if valid {
+        println!("IR is valid.");
+    } else {
+        eprintln!("{}", archidoc_engine::ir::format_violations(&violations));
+    }
+    if !valid {
+        std::process::exit(1);
+    }
+}
+
+fn run_suggest(path: &Path) {
+    if !path.exists() {
+        eprintln!("error: path does not exist: {}", path.display());
+        std::process::exit(1);
+    }
+    if !path.is_dir() {
+        eprintln!("error: path is not a directory: {}", path.display());
         std::process::exit(1);
     }
     let annotation = archidoc_engine::suggest::suggest_annotation(path);
     print!("{}", annotation);
 }
 
+fn run_stub(path: &Option<PathBuf>, module_path: &str, level: Option<&str>, parent: Option<&str>, register: bool) {
+    use archidoc_engine::stub::{create_stub, default_kind};
+    use archidoc_types::ElementKind;
+
+    let root = resolve_root(path);
+    let module_path = match parent {
+        Some(parent) if !module_path.starts_with(&format!("{}.", parent)) => format!("{}.{}", parent, module_path),
+        _ => module_path.to_string(),
+    };
+    let kind = match level {
+        Some(level) => match ElementKind::parse(level) {
+            ElementKind::Unknown => {
+                eprintln!("error: unknown element kind '{}'", level);
+                std::process::exit(1);
+            }
+            kind => kind,
+        },
+        None => default_kind(&module_path),
+    };
+
+    match create_stub(&root, &module_path, &kind, register) {
+        Ok(outcome) => {
+            println!("created {}", outcome.entry_file.display());
+            if let Some(parent_file) = outcome.registered_in {
+                println!("registered {} in {}", module_path, parent_file.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
 fn run_init(path: &Option<PathBuf>, lang: Option<&str>) {
     use archidoc_engine::init::{CommentStyle, wrap_jsdoc};
 
@@ -469,6 +1403,558 @@ fn run_init(path: &Option<PathBuf>, lang: Option<&str>) {
     }
 }
 
+fn run_freeze(path: &Option<PathBuf>, reviewer: &str, date: Option<&str>) {
+    let root = path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().expect("failed to get current directory"));
+
+    if !root.exists() {
+        eprintln!("error: path does not exist: {}", root.display());
+        std::process::exit(1);
+    }
+
+    if reviewer.trim().is_empty() {
+        eprintln!("error: --reviewer must not be empty");
+        std::process::exit(1);
+    }
+
+    let approved_at = match date {
+        Some(d) if archidoc_engine::date::is_valid(d) => d.to_string(),
+        Some(d) => {
+            eprintln!("error: invalid date '{}' (expected YYYY-MM-DD)", d);
+            std::process::exit(1);
+        }
+        None => archidoc_engine::date::today(),
+    };
+
+    let docs = extract_docs(&root);
+    let snapshot = archidoc_engine::freeze::freeze(&docs, &root, reviewer.trim(), &approved_at);
+    let written = archidoc_engine::freeze::write_approved(&root, &snapshot).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+
+    println!(
+        "approved {} modules ({} on {})",
+        docs.len(),
+        snapshot.reviewer,
+        snapshot.approved_at
+    );
+    println!("wrote {}", written.display());
+}
+
+fn run_scaffold_readmes(path: &Option<PathBuf>) {
+    let root = path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().expect("failed to get current directory"));
+
+    if !root.exists() {
+        eprintln!("error: path does not exist: {}", root.display());
+        std::process::exit(1);
+    }
+
+    let config = archidoc_engine::config::load(&root).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+    let extraction = archidoc_rust::walker::extract(&root);
+    for warning in &extraction.warnings {
+        eprintln!("warning: {}", warning);
+    }
+    let docs = extraction.docs;
+    let front_matter = front_matter_settings(&config);
+    let outcome = archidoc_engine::readme::scaffold_readmes(&docs, front_matter.as_ref()).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+
+    for path in &outcome.written {
+        println!("wrote {}", path.display());
+    }
+    for path in &outcome.skipped {
+        println!("skipped {} (hand-written)", path.display());
+    }
+    println!(
+        "{} written, {} unchanged, {} skipped",
+        outcome.written.len(),
+        outcome.unchanged.len(),
+        outcome.skipped.len()
+    );
+}
+
+fn run_adapters(path: &Option<PathBuf>, doctor: bool, json: bool) {
+    let root = resolve_root(path);
+    // The Rust adapter is compiled into this binary
+    let statuses = archidoc_engine::adapters::statuses(&root, env!("CARGO_PKG_VERSION"));
+    if json {
+        println!("{}", serde_json::to_string_pretty(&statuses).expect("failed to serialize adapters"));
+    } else if doctor {
+        print!("{}", archidoc_engine::adapters::format_doctor(&statuses));
+    } else {
+        print!("{}", archidoc_engine::adapters::format_list(&statuses));
+    }
+}
+
+fn run_fmt(path: &Option<PathBuf>, check: bool) {
+    let root = resolve_root(path);
+    let changed = archidoc_rust::fmt::format_tree(&root, !check).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+
+    for file in &changed {
+        println!("{}", file.display());
+    }
+    if check && !changed.is_empty() {
+        eprintln!("{} file(s) need formatting; run `archidoc fmt`", changed.len());
+        std::process::exit(1);
+    }
+}
+
+fn run_push_back(path: &Option<PathBuf>, opts: &GlobalOpts, doc: Option<&Path>, dry_run: bool) {
+    let root = resolve_root(path);
+    let doc = doc.unwrap_or(&opts.output);
+    let doc = if doc.is_absolute() { doc.to_path_buf() } else { root.join(doc) };
+    let markdown = fs::read_to_string(&doc).unwrap_or_else(|e| {
+        eprintln!("error: cannot read {}: {}", doc.display(), e);
+        std::process::exit(1);
+    });
+
+    // Compare against the prose as generated, so resolved ${NAME}
+    // placeholders do not read as edits
+    let mut docs = extract_docs(&root);
+    substitute_variables(&project_config(&root, opts), opts, &mut docs, &mut CompileReport::default());
+    let edits = archidoc_engine::pushback::read_edits(&docs, &markdown).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+
+    print!("{}", archidoc_engine::pushback::format_edits(&edits));
+    if dry_run || edits.is_empty() {
+        return;
+    }
+    for edit in &edits {
+        let source_file = Path::new(&edit.source_file);
+        let source = fs::read_to_string(source_file).unwrap_or_else(|e| {
+            eprintln!("error: cannot read {}: {}", source_file.display(), e);
+            std::process::exit(1);
+        });
+        let Some(updated) = archidoc_rust::catalog::update_row(&source, &edit.file, edit.purpose.as_deref(), edit.health)
+        else {
+            eprintln!("error: no file-table row for `{}` in {}", edit.file, source_file.display());
+            std::process::exit(1);
+        };
+        if let Err(e) = fs::write(source_file, updated) {
+            eprintln!("error: cannot write {}: {}", source_file.display(), e);
+            std::process::exit(1);
+        }
+    }
+    println!("Pushed {} edit(s) back to the annotations; regenerate to refresh the docs.", edits.len());
+}
+
+fn run_publish_confluence(path: &Option<PathBuf>, opts: &GlobalOpts, dry_run: bool) {
+    let root = resolve_root(path);
+    let config = project_config(&root, opts).confluence;
+    let output_path = if opts.output.is_absolute() {
+        opts.output.clone()
+    } else {
+        root.join(&opts.output)
+    };
+    let out_dir = output_path.parent().unwrap_or(&root);
+
+    if dry_run {
+        let pages = archidoc_engine::confluence::read_manifest(out_dir).unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        });
+        for page in pages {
+            match page.parent {
+                Some(parent) => println!("would publish {} under {}", page.title, parent),
+                None => println!("would publish {}", page.title),
+            }
+        }
+        return;
+    }
+
+    if config.base_url.is_empty() || config.space.is_empty() {
+        eprintln!("error: set base_url and space in [confluence] to publish");
+        std::process::exit(1);
+    }
+    let Ok(token) = std::env::var("CONFLUENCE_TOKEN") else {
+        eprintln!("error: CONFLUENCE_TOKEN is not set");
+        std::process::exit(1);
+    };
+    let api = confluence::CurlApi {
+        base_url: config.base_url.clone(),
+        space: config.space.clone(),
+        user: std::env::var("CONFLUENCE_USER").ok(),
+        token,
+    };
+    match archidoc_engine::confluence::publish(&api, out_dir, config.parent_id.as_deref()) {
+        Ok(lines) => {
+            for line in lines {
+                println!("{}", line);
+            }
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_snippets(path: &Option<PathBuf>, opts: &GlobalOpts, editor: Editor, out: Option<&Path>) {
+    use archidoc_rust::snippets;
+
+    let root = resolve_root(path);
+    let config = project_config(&root, opts);
+    let all = snippets::snippets(&config.protocols.allowed);
+    let content = match editor {
+        Editor::Vscode => snippets::render_vscode(&all),
+        Editor::Jetbrains => snippets::render_jetbrains(&all),
+        Editor::Nvim => snippets::render_snipmate(&all),
+    };
+
+    match out {
+        Some(file) => {
+            if let Err(e) = fs::write(file, content) {
+                eprintln!("error: cannot write {}: {}", file.display(), e);
+                std::process::exit(1);
+            }
+            println!("wrote {}", file.display());
+        }
+        None => print!("{}", content),
+    }
+}
+
+fn run_catalog(path: &Option<PathBuf>, opts: &GlobalOpts, apply: bool) {
+    let root = resolve_root(path);
+    let config = project_config(&root, opts);
+    let apply = apply || config.catalog.auto_apply;
+    let rows = archidoc_rust::catalog::catalog_tree(&root, &config.heuristics, apply).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+
+    if rows.is_empty() {
+        println!("All files are cataloged.");
+        return;
+    }
+    for row in &rows {
+        println!("{} ({}): {}", row.element, row.source_file.display(), row.to_markdown());
+    }
+    if apply {
+        println!("Added {} file-table row(s).", rows.len());
+    } else {
+        println!("Run `archidoc catalog --apply` to add {} row(s).", rows.len());
+    }
+}
+
+/// Annotated source trees of this repository, one per crate so that their
+/// root elements do not collide.
+const SELF_CRATES: &[&str] = &[
+    "core/archidoc-types/src",
+    "core/archidoc-engine/src",
+    "adapters/archidoc-rust/src",
+    "core/tests/src",
+];
+
+fn run_verify_self(path: &Option<PathBuf>, opts: &GlobalOpts) {
+    let repo = match path {
+        Some(_) => resolve_root(path),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("../.."),
+    };
+
+    let mut failed = false;
+    for dir in SELF_CRATES {
+        match verify_tree(&repo.join(dir), opts) {
+            Ok(1) => println!("ok    {} (1 element)", dir),
+            Ok(elements) => println!("ok    {} ({} elements)", dir, elements),
+            Err(problems) => {
+                failed = true;
+                println!("FAIL  {}", dir);
+                for problem in problems {
+                    println!("      {}", problem);
+                }
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// Extract, verify patterns, run the fitness functions, validate, and
+/// check freshly generated output for drift, without writing anything
+/// under `root`. Returns the element count, or every problem found.
+fn verify_tree(root: &Path, opts: &GlobalOpts) -> Result<usize, Vec<String>> {
+    let config = archidoc_engine::config::load(root).map_err(|e| vec![e])?;
+
+    let extraction = archidoc_rust::walker::extract(root);
+    let mut docs = extraction.docs;
+    if docs.is_empty() {
+        return Err(vec!["no annotated elements".to_string()]);
+    }
+    let mut report = CompileReport::default();
+    prepare_docs(&config, opts, &mut docs, &mut report);
+    let mut problems = extraction.warnings;
+    problems.extend(report.warnings);
+
+    // Promotions only show that verification runs; they are not recorded
+    archidoc_rust::promote::auto_promote(&mut docs, &config.heuristics);
+
+    // Each crate is compiled alone, so uses of its public items from the
+    // other crates are invisible and the unused-API rule does not apply
+    let fitness: Vec<_> = archidoc_rust::fitness::run_all(&docs, &config)
+        .into_iter()
+        .filter(|s| s.name != "all_component_public_items_are_used")
+        .collect();
+    for summary in fitness.iter().filter(|s| !s.passed()) {
+        let code = archidoc_engine::explain::code_of(&summary.name).unwrap_or("--");
+        problems.push(format!("fitness '{}' failed {} time(s) [{}]", summary.name, summary.failed, code));
+    }
+
+    let mut validation = archidoc_engine::validate::validate_file_tables(&docs);
+    validation.severities = config.validate;
+    if !validation.passes(true) {
+        problems.extend(archidoc_engine::validate::format_validation_report(&validation).lines().map(str::to_string));
+    }
+    for doc in &docs {
+        let Some(dir) = doc.catalog_dir() else { continue };
+        for file in &doc.files {
+            if file.health == archidoc_types::HealthStatus::Planned && dir.join(&file.name).exists() {
+                problems.push(format!("{}: `{}` exists but its health is still planned", doc.module_path, file.name));
+            }
+        }
+    }
+
+    if archidoc_engine::ir::deserialize(&archidoc_engine::ir::serialize(&docs)).as_ref() != Ok(&docs) {
+        problems.push("IR does not survive a serialize/deserialize round trip".to_string());
+    }
+
+    let out_dir = std::env::temp_dir().join(format!("archidoc-verify-self-{}", std::process::id()));
+    let mut settings = archidoc_engine::generator::OutputSettings::new(&out_dir.join("ARCHITECTURE.md"), &out_dir);
+    settings.fitness = fitness;
+    let generators = archidoc_engine::generator::builtins(archidoc_engine::generator::BUILTIN, &settings);
+    let drift = archidoc_engine::compile::write_outputs(&docs, &out_dir, &generators)
+        .and_then(|_| archidoc_engine::check::check_artifacts(&docs, &out_dir, &generators));
+    let _ = fs::remove_dir_all(&out_dir);
+    match drift {
+        Ok(report) if report.has_drift() => problems.push("regenerated output drifts from the first run".to_string()),
+        Ok(_) => {}
+        Err(e) => problems.push(e),
+    }
+
+    if problems.is_empty() {
+        Ok(docs.len())
+    } else {
+        Err(problems)
+    }
+}
+
+fn run_stats(path: &Option<PathBuf>, json: bool) {
+    let root = resolve_root(path);
+    let runs = archidoc_engine::stats::load(&root).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+
+    if json {
+        let json_output = serde_json::to_string_pretty(&runs).expect("failed to serialize stats");
+        println!("{}", json_output);
+    } else {
+        print!("{}", archidoc_engine::stats::format_stats(&runs));
+    }
+}
+
+fn run_snapshot(
+    path: &Option<PathBuf>,
+    git_ref: Option<&str>,
+    date: Option<&str>,
+    policy: archidoc_engine::history::PrunePolicy,
+    list: bool,
+) {
+    let root = resolve_root(path);
+
+    if list {
+        let snapshots = archidoc_engine::history::list_snapshots(&root);
+        if snapshots.is_empty() {
+            println!("No snapshots in {}.", root.join(archidoc_engine::history::HISTORY_DIR).display());
+        }
+        for snapshot in snapshots {
+            println!("{}  {}", snapshot.date, snapshot.git_ref);
+        }
+        return;
+    }
+
+    let date = match date {
+        Some(d) if archidoc_engine::date::is_valid(d) => d.to_string(),
+        Some(d) => {
+            eprintln!("error: invalid date '{}' (expected YYYY-MM-DD)", d);
+            std::process::exit(1);
+        }
+        None => archidoc_engine::date::today(),
+    };
+    let git_ref = git_ref
+        .map(str::to_string)
+        .or_else(|| archidoc_engine::history::head_ref(&root))
+        .unwrap_or_else(|| "working".to_string());
+
+    let docs = extract_docs(&root);
+    let written = archidoc_engine::history::write_snapshot(&root, &docs, &date, &git_ref)
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        });
+    println!("Archived {} elements at {} ({}).", docs.len(), git_ref, written.display());
+
+    if policy.keep_last.is_some() || policy.keep_days.is_some() {
+        let removed = archidoc_engine::history::prune(&root, policy, archidoc_engine::date::today_days())
+            .unwrap_or_else(|e| {
+                eprintln!("error: {}", e);
+                std::process::exit(1);
+            });
+        if !removed.is_empty() {
+            println!("Pruned {} old snapshot(s).", removed.len());
+        }
+    }
+}
+
+fn run_purge_archived(path: &Option<PathBuf>, modules: &[String]) {
+    let root = resolve_root(path);
+    let docs = extract_docs(&root);
+    let purged = archidoc_engine::history::purge_archived(&root, &docs, modules).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    });
+    for element in &purged {
+        println!("purged {} (last seen {})", element.module_path, element.last_seen);
+    }
+    println!("Purged {} archived element(s); regenerate to refresh the docs.", purged.len());
+}
+
+fn resolve_root(path: &Option<PathBuf>) -> PathBuf {
+    let root = path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().expect("failed to get current directory"));
+    if !root.exists() {
+        eprintln!("error: path does not exist: {}", root.display());
+        std::process::exit(1);
+    }
+    root
+}
+
+/// Load IR from a file, or from the snapshot archive when `spec` is not a file.
+fn read_ir_spec(root: &std::path::Path, spec: &str) -> Vec<archidoc_types::ModuleDoc> {
+    let file = PathBuf::from(spec);
+    if file.is_file() {
+        return read_ir_from_file(&file);
+    }
+
+    archidoc_engine::history::resolve(root, spec)
+        .and_then(|entry| archidoc_engine::history::read_snapshot(&entry))
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        })
+}
+
+fn run_diff(path: &Option<PathBuf>, before: &str, after: Option<&str>, format: DiffFormat) {
+    let root = resolve_root(path);
+    let before_docs = read_ir_spec(&root, before);
+    let after_docs = match after {
+        Some(spec) => read_ir_spec(&root, spec),
+        None => extract_docs(&root),
+    };
+
+    let diff = archidoc_engine::diff::diff_ir(&before_docs, &after_docs);
+
+    match format {
+        DiffFormat::Text => print!("{}", archidoc_engine::diff::format_ir_diff(&diff)),
+        DiffFormat::Json => {
+            let json_output = serde_json::to_string_pretty(&diff).expect("failed to serialize diff");
+            println!("{}", json_output);
+        }
+        DiffFormat::PrComment => print!("{}", archidoc_engine::diff::format_pr_comment(&diff)),
+    }
+}
+
+fn run_gap(path: &Option<PathBuf>, planned: &str, json: bool) {
+    let root = resolve_root(path);
+    let planned_docs = read_ir_spec(&root, planned);
+    let current_docs = extract_docs(&root);
+
+    let report = archidoc_engine::gap::gap_analysis(&planned_docs, &current_docs);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report).expect("failed to serialize gap report"));
+    } else {
+        print!("{}", archidoc_engine::gap::format_gap_report(&report));
+    }
+}
+
+fn run_explain(code: Option<&str>) {
+    let Some(code) = code else {
+        print!("{}", archidoc_engine::explain::format_index());
+        return;
+    };
+    match archidoc_engine::explain::lookup(code) {
+        Some(explanation) => print!("{}", archidoc_engine::explain::format_explanation(explanation)),
+        None => {
+            eprintln!("error: unknown finding code '{}' (run `archidoc explain` to list them)", code);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_org_report(inputs: &[String], html: Option<&Path>, opts: &GlobalOpts) {
+    let mut repos: Vec<archidoc_engine::org::RepoInput> = Vec::new();
+    for input in inputs {
+        let (name, file) = match input.split_once('=') {
+            Some((name, file)) => (name.to_string(), PathBuf::from(file)),
+            None => {
+                let file = PathBuf::from(input);
+                let stem = file.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+                (stem, file)
+            }
+        };
+        let json = fs::read_to_string(&file).unwrap_or_else(|e| {
+            eprintln!("error: cannot read {}: {}", file.display(), e);
+            std::process::exit(1);
+        });
+        let index = match repos.iter().position(|r| r.name == name) {
+            Some(index) => index,
+            None => {
+                repos.push(archidoc_engine::org::RepoInput::new(&name));
+                repos.len() - 1
+            }
+        };
+        if let Err(e) = repos[index].add_json(&json) {
+            eprintln!("error: {}: {}", file.display(), e);
+            std::process::exit(1);
+        }
+    }
+
+    let report = archidoc_engine::org::org_report(&repos);
+
+    if let Some(path) = html {
+        let html = archidoc_engine::org::render_org_html(&report, "Organization");
+        if let Err(e) = fs::write(path, html) {
+            eprintln!("error: failed to write {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+        if !opts.quiet {
+            eprintln!("wrote {}", path.display());
+        }
+    }
+
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&report).expect("failed to serialize org report"));
+    } else {
+        print!("{}", archidoc_engine::org::format_org_markdown(&report));
+    }
+}
+
 fn run_init_adapter(lang: &str) {
     println!("Creating adapter scaffold for '{}'...", lang);
 
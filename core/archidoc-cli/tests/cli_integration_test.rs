@@ -4,11 +4,562 @@
 /// - Output directory configuration (--output)
 /// - Verbosity levels (--quiet, --verbose)
 /// - JSON output mode (--json)
-/// - Subcommands (init-adapter)
+/// - Subcommands (init-adapter, verify-self, org-report, push-back)
+/// - Reproducible output (--reproducible)
+/// - Template variables (--var, [variables])
+/// - Post-generation hooks ([hooks.<id>])
+/// - Project health and validation findings in ARCHITECTURE.md ([architecture])
+/// - Confluence pages and publishing (--confluence, publish-confluence)
+/// - Documentation site projects (--site)
+/// - Diagram images from external renderers (--render, [render])
+/// - Diagram id collisions and the id mapping in the run report (--report)
+/// - System Context diagrams from declared people and external systems, through the IR (--emit-ir, --from-json-file)
+/// - Finding codes and their explanations (explain)
+/// - Archived elements from the snapshot history (purge-archived)
+/// - IR schema validation (--validate-ir) and version upgrades (ir upgrade)
 
 #[test]
 fn test_cli_builds_successfully() {
     // This test simply verifies that the CLI binary compiles.
     // The actual CLI functionality is tested through the BDD test suite.
-    assert!(true);
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    assert!(std::path::Path::new(bin).exists());
+}
+
+/// Write a small annotated project with front matter and headers enabled.
+fn write_project(root: &std::path::Path) {
+    let module = |dir: &str, content: &str| {
+        let dir = root.join("src").join(dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("mod.rs"), content).unwrap();
+    };
+    module("api", "//! @c4 container\n//!\n//! # Api\n//!\n//! REST gateway\n//!\n//! @c4 uses bus \"Publishes requests\" \"crossbeam\"\n");
+    module("bus", "//! @c4 container\n//!\n//! # Bus\n//!\n//! Event bus\n");
+    std::fs::write(
+        root.join("archidoc.toml"),
+        "[front_matter]\nenabled = true\n\n[header]\nenabled = true\n",
+    )
+    .unwrap();
+}
+
+/// Every file under `root`, relative path to content.
+fn read_tree(root: &std::path::Path) -> std::collections::BTreeMap<String, Vec<u8>> {
+    let mut files = std::collections::BTreeMap::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else {
+                let rel = path.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/");
+                files.insert(rel, std::fs::read(&path).unwrap());
+            }
+        }
+    }
+    files
+}
+
+#[test]
+fn reproducible_runs_are_byte_identical_across_checkouts() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let run = || {
+        let tmp = tempfile::tempdir().unwrap();
+        write_project(tmp.path());
+        let status = std::process::Command::new(bin)
+            .current_dir(tmp.path())
+            .args(["--reproducible", "--quiet", "--plantuml", "--drawio", "--report", "report.json", "."])
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let ir = std::process::Command::new(bin)
+            .current_dir(tmp.path())
+            .args(["--reproducible", "--emit-ir", "."])
+            .output()
+            .unwrap();
+        assert!(ir.status.success());
+        (read_tree(tmp.path()), ir.stdout)
+    };
+
+    let (first, first_ir) = run();
+    let (second, second_ir) = run();
+
+    assert!(first.contains_key("ARCHITECTURE.md"));
+    assert!(first.contains_key("report.json"));
+    assert_eq!(first.keys().collect::<Vec<_>>(), second.keys().collect::<Vec<_>>());
+    for (path, content) in &first {
+        assert!(second[path] == *content, "{} differs between runs", path);
+    }
+    assert_eq!(first_ir, second_ir);
+}
+
+#[test]
+fn archidoc_verifies_its_own_crates() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_archidoc"))
+        .arg("verify-self")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "verify-self failed:\n{}", stdout);
+    assert!(stdout.contains("ok    adapters/archidoc-rust/src"));
+}
+
+#[test]
+fn org_report_rolls_up_inputs_by_repository_name() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    let ir = std::process::Command::new(bin).args(["--emit-ir"]).arg(tmp.path()).output().unwrap();
+    assert!(ir.status.success());
+    std::fs::write(tmp.path().join("ir.json"), &ir.stdout).unwrap();
+    std::fs::write(tmp.path().join("drift.json"), "{\"drifted_files\": [], \"missing_files\": [], \"extra_files\": []}").unwrap();
+
+    let output = std::process::Command::new(bin)
+        .current_dir(tmp.path())
+        .args(["org-report", "shop=ir.json", "shop=drift.json", "--html", "org.html"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(stdout.contains("| shop | 2 | 2 | 0 | 0 | - | 0 / 0 | clean |"), "{}", stdout);
+    assert!(stdout.contains("System(shop, \"shop\", \"2 elements\")"));
+    assert!(tmp.path().join("org.html").exists());
+}
+
+#[test]
+fn variables_resolve_from_var_flag_then_config() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path().join("src").join("api");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("mod.rs"),
+        "//! @c4 container\n//!\n//! # Api\n//!\n//! Serves ${ARCHIDOC_TEST_ENV} traffic at ${ARCHIDOC_TEST_HOST}\n",
+    )
+    .unwrap();
+    std::fs::write(tmp.path().join("archidoc.toml"), "[variables]\nARCHIDOC_TEST_ENV = \"staging\"\nARCHIDOC_TEST_HOST = \"stg.example.com\"\n").unwrap();
+
+    let status = std::process::Command::new(bin)
+        .current_dir(tmp.path())
+        .args(["--quiet", "--var", "ARCHIDOC_TEST_ENV=production", "."])
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+    let architecture = std::fs::read_to_string(tmp.path().join("ARCHITECTURE.md")).unwrap();
+    assert!(architecture.contains("Serves production traffic at stg.example.com"), "{}", architecture);
+}
+
+#[test]
+fn push_back_writes_edited_purposes_and_health_to_the_annotation() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path().join("src").join("api");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("mod.rs"),
+        "//! @c4 container\n//!\n//! # Api\n//!\n//! REST gateway\n//!\n//! | File | Pattern | Purpose | Health |\n//! |------|---------|---------|--------|\n//! | `routes.rs` | -- | Routes | active |\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("routes.rs"), "pub fn routes() {}\n").unwrap();
+    let archidoc = |args: &[&str]| {
+        std::process::Command::new(bin)
+            .current_dir(tmp.path())
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    assert!(archidoc(&["--quiet", "."]).status.success());
+    let architecture = tmp.path().join("ARCHITECTURE.md");
+    let generated = std::fs::read_to_string(&architecture).unwrap();
+    assert!(generated.contains("| `routes.rs` | -- | Routes | active |"), "{}", generated);
+    std::fs::write(&architecture, generated.replace("| Routes | active |", "| HTTP routes | stable |")).unwrap();
+
+    // Run from a subdirectory: the document resolves against the root, not the CWD
+    let output = std::process::Command::new(bin)
+        .current_dir(&dir)
+        .args([tmp.path().to_str().unwrap(), "push-back"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("src.api `routes.rs` purpose: HTTP routes"));
+    let annotation = std::fs::read_to_string(dir.join("mod.rs")).unwrap();
+    assert!(annotation.ends_with("//! | `routes.rs` | -- | HTTP routes | stable |\n"), "{}", annotation);
+    let again = archidoc(&[".", "push-back"]);
+    assert_eq!(String::from_utf8_lossy(&again.stdout), "No edits to push back.\n");
+}
+
+#[test]
+fn hooks_rewrite_artifacts_and_check_compares_the_hooked_output() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    let mut config = std::fs::read_to_string(tmp.path().join("archidoc.toml")).unwrap();
+    config.push_str("\n[hooks.ai_context]\ncommand = \"tr\"\nargs = [\"a-z\", \"A-Z\"]\n");
+    std::fs::write(tmp.path().join("archidoc.toml"), config).unwrap();
+    let archidoc = |args: &[&str]| {
+        std::process::Command::new(bin)
+            .current_dir(tmp.path())
+            .args(args)
+            .status()
+            .unwrap()
+    };
+
+    assert!(archidoc(&["--quiet", "."]).success());
+
+    let ai_context = std::fs::read_to_string(tmp.path().join("ARCHITECTURE.ai.md")).unwrap();
+    assert!(ai_context.contains("REST GATEWAY"), "{}", ai_context);
+    let architecture = std::fs::read_to_string(tmp.path().join("ARCHITECTURE.md")).unwrap();
+    assert!(architecture.contains("REST gateway"));
+    assert!(archidoc(&["--quiet", "--check", "."]).success());
+}
+
+#[test]
+fn architecture_embeds_health_and_validation_findings_when_configured() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    let store = tmp.path().join("src/store");
+    std::fs::create_dir_all(&store).unwrap();
+    std::fs::write(
+        store.join("mod.rs"),
+        "//! @c4 container\n//!\n//! # Store\n//!\n//! Persistence\n//!\n//! | File | Pattern | Purpose | Health |\n//! |------|---------|---------|--------|\n//! | `cache.rs` | -- | Cache | stable |\n//! | `gone.rs` | -- | Removed | planned |\n",
+    )
+    .unwrap();
+    std::fs::write(store.join("cache.rs"), "").unwrap();
+    std::fs::write(store.join("extra.rs"), "").unwrap();
+    let mut config = std::fs::read_to_string(tmp.path().join("archidoc.toml")).unwrap();
+    config.push_str("\n[architecture]\nhealth = true\nvalidation = true\n");
+    std::fs::write(tmp.path().join("archidoc.toml"), config).unwrap();
+    let archidoc = |args: &[&str]| {
+        std::process::Command::new(bin)
+            .current_dir(tmp.path())
+            .args(args)
+            .status()
+            .unwrap()
+    };
+
+    assert!(archidoc(&["--quiet", "--reproducible", "."]).success());
+
+    let architecture = std::fs::read_to_string(tmp.path().join("ARCHITECTURE.md")).unwrap();
+    assert!(architecture.contains("## Project Health"), "{}", architecture);
+    assert!(architecture.contains("| planned | 1 | 50.0% |\n| active | 0 | 0.0% |\n| stable | 1 | 50.0% |\n"));
+    assert!(architecture.contains("| ghost | src.store | `gone.rs` | error |\n| orphan | src.store | `extra.rs` | warning |\n"));
+    assert!(archidoc(&["--quiet", "--check", "--reproducible", "."]).success());
+}
+
+#[test]
+fn removed_elements_are_archived_until_purged() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    let mut config = std::fs::read_to_string(tmp.path().join("archidoc.toml")).unwrap();
+    config.push_str("\n[architecture]\narchive = true\n");
+    std::fs::write(tmp.path().join("archidoc.toml"), config).unwrap();
+    let archidoc = |args: &[&str]| {
+        std::process::Command::new(bin)
+            .current_dir(tmp.path())
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    let architecture = || std::fs::read_to_string(tmp.path().join("ARCHITECTURE.md")).unwrap();
+
+    assert!(archidoc(&[".", "snapshot", "--ref", "v1", "--date", "2026-01-10"]).status.success());
+    std::fs::remove_dir_all(tmp.path().join("src/bus")).unwrap();
+    assert!(archidoc(&["--quiet", "."]).status.success());
+    assert!(architecture().contains("| src.bus | container | Event bus | 2026-01-10 | -- |\n"), "{}", architecture());
+
+    assert!(archidoc(&[".", "snapshot", "--ref", "v2", "--date", "2026-02-01"]).status.success());
+    assert!(archidoc(&["--quiet", "."]).status.success());
+    assert!(architecture().contains("| src.bus | container | Event bus | 2026-01-10 | 2026-02-01 |\n"));
+    assert!(archidoc(&["--quiet", "--check", "."]).status.success());
+
+    assert!(!archidoc(&[".", "purge-archived", "src.api"]).status.success());
+    let purged = archidoc(&[".", "purge-archived", "src.bus"]);
+    assert!(String::from_utf8_lossy(&purged.stdout).starts_with("purged src.bus (last seen 2026-01-10)\n"));
+    assert!(!archidoc(&["--quiet", "--check", "."]).status.success(), "the purge is drift until regenerated");
+    assert!(archidoc(&["--quiet", "."]).status.success());
+    assert!(!architecture().contains("## Archived Elements"));
+}
+
+#[test]
+fn confluence_pages_are_generated_and_listed_for_publishing() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    let archidoc = |args: &[&str]| {
+        std::process::Command::new(bin)
+            .current_dir(tmp.path())
+            .env_remove("CONFLUENCE_TOKEN")
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    assert!(archidoc(&["--quiet", "--confluence", "."]).status.success());
+
+    let api = std::fs::read_to_string(tmp.path().join("confluence/containers/src_api.xml")).unwrap();
+    assert!(api.starts_with("<p>REST gateway</p>\n"), "{}", api);
+    assert!(api.contains("<ac:structured-macro ac:name=\"mermaid-cloud\">\n<ac:plain-text-body><![CDATA[C4Component\n"));
+    let listed = archidoc(&[".", "publish-confluence", "--dry-run"]);
+    assert!(listed.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&listed.stdout),
+        "would publish Architecture Context\nwould publish src.api under Architecture Context\nwould publish src.bus under Architecture Context\n"
+    );
+
+    let published = archidoc(&[".", "publish-confluence"]);
+    assert!(!published.status.success());
+    assert!(String::from_utf8_lossy(&published.stderr).contains("set base_url and space in [confluence]"));
+    let mut config = std::fs::read_to_string(tmp.path().join("archidoc.toml")).unwrap();
+    config.push_str("\n[confluence]\nbase_url = \"https://example.atlassian.net/wiki\"\nspace = \"ARCH\"\n");
+    std::fs::write(tmp.path().join("archidoc.toml"), config).unwrap();
+    let published = archidoc(&[".", "publish-confluence"]);
+    assert!(String::from_utf8_lossy(&published.stderr).contains("CONFLUENCE_TOKEN is not set"));
+}
+
+#[test]
+fn mkdocs_site_has_a_page_per_container() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+
+    let status = std::process::Command::new(bin)
+        .current_dir(tmp.path())
+        .args(["--quiet", "--site", "mkdocs", "."])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let config = std::fs::read_to_string(tmp.path().join("site/mkdocs.yml")).unwrap();
+    assert!(config.contains("      - \"src.api\": containers/src_api.md\n      - \"src.bus\": containers/src_bus.md\n"), "{}", config);
+    let index = std::fs::read_to_string(tmp.path().join("site/docs/index.md")).unwrap();
+    assert!(index.contains("- [src.api](containers/src_api.md) — REST gateway\n"), "{}", index);
+    let api = std::fs::read_to_string(tmp.path().join("site/docs/containers/src_api.md")).unwrap();
+    assert!(api.contains("# src.api\n\nREST gateway\n"), "{}", api);
+    assert!(api.contains("```mermaid\nC4Component\n"));
+}
+
+#[test]
+fn colliding_diagram_ids_are_disambiguated_and_reported() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    for dir in ["bus/calc", "bus_calc"] {
+        let dir = tmp.path().join("src").join(dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("mod.rs"), "//! @c4 component\n//!\n//! Price calculations\n").unwrap();
+    }
+
+    let output = std::process::Command::new(bin)
+        .current_dir(tmp.path())
+        .args(["--quiet", "--report", "report.json", "."])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("warning: diagram id 'src_bus_calc' of 'src.bus_calc' is taken by 'src.bus.calc'; using 'src_bus_calc_2'"),
+        "{}",
+        stderr
+    );
+    let report: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(tmp.path().join("report.json")).unwrap()).unwrap();
+    assert_eq!(report["element_ids"]["src.bus.calc"], "src_bus_calc");
+    assert_eq!(report["element_ids"]["src.bus_calc"], "src_bus_calc_2");
+    let architecture = std::fs::read_to_string(tmp.path().join("ARCHITECTURE.md")).unwrap();
+    assert!(architecture.contains("<a id=\"src_bus_calc_2\"></a>"), "{}", architecture);
+}
+
+#[test]
+fn declared_people_and_external_systems_draw_a_system_context_through_the_ir() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    std::fs::write(
+        tmp.path().join("src/lib.rs"),
+        "//! @c4 container\n//! # Shop\n//!\n//! Online shop\n//!\n//! @c4 person customer \"Places orders\" uses src.api \"Browses\" \"HTTPS\"\n//! @c4 external stripe \"Card payments\" uses src.bus \"Sends webhooks\"\n",
+    )
+    .unwrap();
+
+    let ir = std::process::Command::new(bin).args(["--emit-ir"]).arg(tmp.path()).output().unwrap();
+    assert!(ir.status.success(), "{}", String::from_utf8_lossy(&ir.stderr));
+    let ir_json: serde_json::Value = serde_json::from_slice(&ir.stdout).unwrap();
+    let customer = ir_json["modules"].as_array().unwrap().iter().find(|e| e["module_path"] == "customer").unwrap();
+    assert_eq!(customer["c4_level"], "person");
+    std::fs::write(tmp.path().join("ir.json"), &ir.stdout).unwrap();
+
+    let out = tempfile::tempdir().unwrap();
+    let output = std::process::Command::new(bin)
+        .arg("--quiet")
+        .arg("--plantuml")
+        .arg("--from-json-file")
+        .arg(tmp.path().join("ir.json"))
+        .arg(out.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let architecture = std::fs::read_to_string(out.path().join("ARCHITECTURE.md")).unwrap();
+    assert!(architecture.contains("## System Context\n\n```mermaid\nC4Context\n"), "{}", architecture);
+    assert!(architecture.contains("    System(sys, \"System\", \"Online shop\")\n"));
+    assert!(architecture.contains("    Rel(customer, sys, \"Browses\", \"HTTPS\")\n    Rel(stripe, sys, \"Sends webhooks\", \"\")\n"));
+    let context = std::fs::read_to_string(out.path().join("c4/c4-context.puml")).unwrap();
+    assert!(context.contains("Person(customer, \"Customer\", \"Places orders\")\nSystem_Ext(stripe, \"Stripe\", \"Card payments\")\n"), "{}", context);
+}
+
+#[test]
+fn rendered_images_sit_beside_their_diagrams() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    let mut config = std::fs::read_to_string(tmp.path().join("archidoc.toml")).unwrap();
+    config.push_str(
+        r#"
+[generators]
+enable = ["mermaid"]
+
+[render.mermaid]
+command = "sh"
+args = ["-c", "cp \"$2\" \"$4\"", "sh"]
+
+[render.plantuml]
+command = "archidoc-no-such-renderer"
+"#,
+    );
+    std::fs::write(tmp.path().join("archidoc.toml"), config).unwrap();
+
+    let output = std::process::Command::new(bin)
+        .current_dir(tmp.path())
+        .args(["--quiet", "--plantuml", "--render", "svg,png", "."])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let svg = std::fs::read_to_string(tmp.path().join("mermaid/c4-container.svg")).unwrap();
+    assert!(svg.starts_with("C4Container\n"), "{}", svg);
+    assert!(tmp.path().join("mermaid/c4-component.png").exists());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("warning: PlantUML (archidoc-no-such-renderer) is not installed; diagrams were not rendered"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn docusaurus_pages_carry_front_matter_before_the_header() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+
+    let status = std::process::Command::new(bin)
+        .current_dir(tmp.path())
+        .args(["--quiet", "--site", "mkdocs,docusaurus", "."])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    assert!(tmp.path().join("site/mkdocs.yml").exists());
+    let api = std::fs::read_to_string(tmp.path().join("site/architecture/src_api.md")).unwrap();
+    assert!(
+        api.starts_with("---\ntitle: \"src.api\"\ndescription: \"REST gateway\"\nsidebar_position: 2\ntags: [\"container\"]\n---\n\n<!-- Generated by archidoc "),
+        "{}",
+        api
+    );
+    let index = std::fs::read_to_string(tmp.path().join("site/architecture/index.md")).unwrap();
+    assert!(index.contains("- [src.bus](src_bus.md) — "), "{}", index);
+}
+
+#[test]
+fn every_finding_has_an_explained_code() {
+    for name in archidoc_rust::fitness::FITNESS_FUNCTIONS {
+        assert!(archidoc_engine::explain::code_of(name).is_some(), "fitness function {} has no code", name);
+    }
+
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    write_project(tmp.path());
+    let archidoc = |args: &[&str]| std::process::Command::new(bin).current_dir(tmp.path()).args(args).output().unwrap();
+
+    let lint = archidoc(&["--lint", "."]);
+    let lint = String::from_utf8_lossy(&lint.stdout);
+    assert!(lint.contains("[AD205 unknown_target]"), "{}", lint);
+
+    let explained = archidoc(&["explain", "AD205"]);
+    assert!(explained.status.success());
+    assert!(String::from_utf8_lossy(&explained.stdout).starts_with("AD205: unknown relationship target\nReported by --lint as `unknown_target`.\n"));
+    let listed = archidoc(&["explain"]);
+    assert!(String::from_utf8_lossy(&listed.stdout).contains("AD309  --fitness   dependency on another container's internals\n"));
+    let unknown = archidoc(&["explain", "AD999"]);
+    assert!(!unknown.status.success());
+    assert!(String::from_utf8_lossy(&unknown.stderr).contains("unknown finding code 'AD999'"));
+}
+
+#[test]
+fn validate_ir_reports_every_violation_with_its_pointer() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    let ir = tmp.path().join("ir.json");
+    std::fs::write(
+        &ir,
+        r#"[{"module_path": "bus", "source_file": "src/bus/mod.rs", "c4_level": "container", "pattern": "--",
+            "pattern_status": "planned", "description": "Event bus", "parent_container": null, "relationships": [],
+            "files": [{"name": "lanes.rs", "pattern": "--", "pattern_status": "planned", "purpose": "Lanes", "health": "deprecated"}],
+            "owner": "ops"}]"#,
+    )
+    .unwrap();
+    let validate = |json: bool| {
+        let mut command = std::process::Command::new(bin);
+        command.arg("--from-json-file").arg(&ir).arg("--validate-ir");
+        if json {
+            command.arg("--json");
+        }
+        command.output().unwrap()
+    };
+
+    let text = validate(false);
+    assert!(!text.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&text.stderr),
+        "IR validation failed with 2 violations:\n  /0/files/0/health: unknown value 'deprecated', expected one of planned, active, stable\n  /0/owner: unknown field\n"
+    );
+
+    let json = validate(true);
+    assert!(!json.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    assert_eq!(report["valid"], false);
+    assert_eq!(report["violations"][0]["pointer"], "/0/files/0/health");
+    assert_eq!(report["violations"][1]["message"], "unknown field");
+}
+
+#[test]
+fn ir_upgrade_rewrites_version_1_arrays_once() {
+    let bin = env!("CARGO_BIN_EXE_archidoc");
+    let tmp = tempfile::tempdir().unwrap();
+    let ir = tmp.path().join("ir.json");
+    std::fs::write(
+        &ir,
+        r#"[{"module_path": "bus", "source_file": "src/bus/mod.rs", "c4_level": "container", "pattern": "--",
+            "pattern_status": "planned", "description": "Event bus", "parent_container": null, "relationships": [], "files": []}]"#,
+    )
+    .unwrap();
+    let upgrade = || std::process::Command::new(bin).args(["ir", "upgrade"]).arg(&ir).output().unwrap();
+
+    let first = upgrade();
+    assert!(first.status.success(), "{}", String::from_utf8_lossy(&first.stderr));
+    assert!(String::from_utf8_lossy(&first.stdout).ends_with("upgraded from IR version 1 to 2\n"));
+    let upgraded: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&ir).unwrap()).unwrap();
+    assert_eq!(upgraded["ir_version"], 2);
+    assert_eq!(upgraded["modules"][0]["module_path"], "bus");
+
+    let second = upgrade();
+    assert!(String::from_utf8_lossy(&second.stdout).ends_with("already IR version 2\n"));
+    let valid = std::process::Command::new(bin).arg("--from-json-file").arg(&ir).arg("--validate-ir").output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&valid.stdout), "IR is valid.\n");
 }
@@ -0,0 +1,191 @@
+use std::fs;
+use std::path::Path;
+
+use archidoc_types::AdapterStatus;
+
+/// Annotation features an adapter may honor: `(id, description)`.
+pub const FEATURES: &[(&str, &str)] = &[
+    ("relationships", "`@c4 uses` dependencies"),
+    ("relationships_without_protocol", "`@c4 uses` without a protocol"),
+    ("import_relationships", "dependencies discovered from imports"),
+    ("file_tables", "file tables"),
+    ("files_dir", "`@files-dir`"),
+    ("flat_modules", "flat module files (`foo.rs` beside `foo/`)"),
+    ("sidecars", "sidecar annotation files"),
+    ("conditions", "feature-gated elements"),
+    ("review", "`@reviewed-by`"),
+    ("risks", "`@risk`"),
+    ("terms", "`@term`"),
+    ("doc_links", "`@doc`"),
+    ("stable_ids", "`@id`"),
+    ("standalone", "`@standalone`"),
+];
+
+/// A known adapter and the features it honors.
+pub struct AdapterProfile {
+    pub name: &'static str,
+    pub language: &'static str,
+    /// File in the project root that marks the language as in use.
+    pub manifest: &'static str,
+    pub features: &'static [&'static str],
+}
+
+/// Adapters archidoc knows about, in display order.
+pub const ADAPTERS: &[AdapterProfile] = &[
+    AdapterProfile {
+        name: "rust",
+        language: "Rust",
+        manifest: "Cargo.toml",
+        features: &[
+            "relationships",
+            "relationships_without_protocol",
+            "file_tables",
+            "files_dir",
+            "flat_modules",
+            "sidecars",
+            "conditions",
+            "review",
+            "risks",
+            "terms",
+            "doc_links",
+            "stable_ids",
+            "standalone",
+        ],
+    },
+    AdapterProfile {
+        name: "typescript",
+        language: "TypeScript",
+        manifest: "package.json",
+        features: &["relationships", "import_relationships", "file_tables"],
+    },
+];
+
+/// Status of every known adapter for the project at `root`. The Rust
+/// adapter is built into the CLI, so it reports `rust_version`; the
+/// TypeScript adapter's version comes from `node_modules`.
+pub fn statuses(root: &Path, rust_version: &str) -> Vec<AdapterStatus> {
+    ADAPTERS
+        .iter()
+        .map(|adapter| {
+            let version = match adapter.name {
+                "rust" => Some(rust_version.to_string()),
+                _ => npm_version(root, "archidoc-ts"),
+            };
+            AdapterStatus {
+                name: adapter.name.to_string(),
+                language: adapter.language.to_string(),
+                version,
+                detected: root.join(adapter.manifest).exists(),
+                supported: adapter.features.iter().map(|f| f.to_string()).collect(),
+                gaps: FEATURES
+                    .iter()
+                    .map(|(id, _)| *id)
+                    .filter(|id| !adapter.features.contains(id))
+                    .map(str::to_string)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Version of an npm package installed under `root/node_modules`.
+fn npm_version(root: &Path, package: &str) -> Option<String> {
+    let manifest = fs::read_to_string(root.join("node_modules").join(package).join("package.json")).ok()?;
+    let manifest: serde_json::Value = serde_json::from_str(&manifest).ok()?;
+    manifest.get("version")?.as_str().map(str::to_string)
+}
+
+/// One line per adapter: name, language, version, and whether the project
+/// uses it.
+pub fn format_list(statuses: &[AdapterStatus]) -> String {
+    let mut out = String::new();
+    for status in statuses {
+        out.push_str(&format!(
+            "{:<12} {:<12} {:<14} {}\n",
+            status.name,
+            status.language,
+            status.version.as_deref().unwrap_or("not installed"),
+            if status.detected { "detected" } else { "not detected" }
+        ));
+    }
+    out
+}
+
+/// [`format_list`], followed by a feature matrix and the features each
+/// detected adapter ignores (every adapter's, when none is detected).
+pub fn format_doctor(statuses: &[AdapterStatus]) -> String {
+    let mut out = String::from("Adapters:\n");
+    for line in format_list(statuses).lines() {
+        out.push_str(&format!("  {}\n", line));
+    }
+
+    out.push_str("\nFeatures:\n");
+    let width = FEATURES.iter().map(|(_, d)| d.chars().count()).max().unwrap_or(0);
+    out.push_str(&format!("  {:<width$}", "", width = width));
+    for status in statuses {
+        out.push_str(&format!("  {}", status.name));
+    }
+    out.push('\n');
+    for (id, description) in FEATURES {
+        out.push_str(&format!("  {:<width$}", description, width = width));
+        for status in statuses {
+            let mark = if status.supported.iter().any(|f| f == id) { "yes" } else { "no" };
+            out.push_str(&format!("  {:<w$}", mark, w = status.name.len()));
+        }
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+    }
+
+    let detected: Vec<&AdapterStatus> = statuses.iter().filter(|s| s.detected).collect();
+    let shown = if detected.is_empty() { statuses.iter().collect() } else { detected };
+    out.push_str("\nGaps:\n");
+    for status in shown {
+        if status.gaps.is_empty() {
+            out.push_str(&format!("  {}: none\n", status.name));
+            continue;
+        }
+        let gaps: Vec<&str> = status
+            .gaps
+            .iter()
+            .filter_map(|gap| FEATURES.iter().find(|(id, _)| id == gap).map(|(_, d)| *d))
+            .collect();
+        out.push_str(&format!("  {} ignores {}\n", status.name, gaps.join(", ")));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detection_follows_manifests_and_installed_packages() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join("package.json"), "{}").unwrap();
+        let ts = tmp.path().join("node_modules/archidoc-ts");
+        fs::create_dir_all(&ts).unwrap();
+        fs::write(ts.join("package.json"), r#"{"name": "archidoc-ts", "version": "0.3.1"}"#).unwrap();
+
+        let statuses = statuses(tmp.path(), "0.3.0");
+
+        assert_eq!(statuses[0].version.as_deref(), Some("0.3.0"));
+        assert!(!statuses[0].detected);
+        assert_eq!(statuses[1].version.as_deref(), Some("0.3.1"));
+        assert!(statuses[1].detected);
+        assert_eq!(statuses[0].gaps, vec!["import_relationships"]);
+        assert!(statuses[1].gaps.contains(&"risks".to_string()));
+    }
+
+    #[test]
+    fn doctor_lists_gaps_of_detected_adapters() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join("package.json"), "{}").unwrap();
+
+        let text = format_doctor(&statuses(tmp.path(), "0.3.0"));
+
+        assert!(text.contains("  typescript   TypeScript   not installed  detected\n"), "{}", text);
+        assert!(text.contains("  `@c4 uses` without a protocol"), "{}", text);
+        assert!(text.contains("\nGaps:\n  typescript ignores `@c4 uses` without a protocol, `@files-dir`,"), "{}", text);
+        assert!(!text.contains("rust ignores"), "{}", text);
+    }
+}
@@ -2,6 +2,30 @@ use std::collections::HashSet;
 
 use archidoc_types::ModuleDoc;
 
+use crate::generator::{Artifact, Generator};
+
+/// `<stem>.ai.md`, named after the ARCHITECTURE.md file it accompanies.
+pub struct AiContextGenerator {
+    pub architecture_file: String,
+}
+
+impl Generator for AiContextGenerator {
+    fn id(&self) -> &str {
+        "ai_context"
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        let stem = self
+            .architecture_file
+            .rsplit_once('.')
+            .map_or(self.architecture_file.as_str(), |(stem, _)| stem);
+        Ok(vec![Artifact {
+            path: format!("{}.ai.md", stem),
+            content: generate(docs),
+        }])
+    }
+}
+
 /// Generate token-optimized AI context from module documentation.
 ///
 /// Produces a compressed tree format: no Mermaid, no ASCII art, no tables.
@@ -31,12 +55,21 @@ pub fn generate(docs: &[ModuleDoc]) -> String {
     out
 }
 
-/// Extract prose from _lib content, skipping code blocks, tables, and markers.
+/// Extract prose from the crate root's content, skipping code blocks, tables, and markers.
+///
+/// IR emitted without content has only the root's description to go on.
 fn narrative(docs: &[ModuleDoc]) -> String {
-    let lib = match docs.iter().find(|d| d.module_path == "_lib") {
+    let lib = match ModuleDoc::narrative_root(docs) {
         Some(doc) => doc,
         None => return String::new(),
     };
+    if lib.content.trim().is_empty() {
+        return if lib.description.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n", lib.description)
+        };
+    }
 
     let mut lines: Vec<&str> = Vec::new();
     let mut in_code_block = false;
@@ -54,7 +87,7 @@ fn narrative(docs: &[ModuleDoc]) -> String {
             continue;
         }
 
-        if t.starts_with("@c4 ") {
+        if archidoc_types::is_marker_line(t) {
             continue;
         }
 
@@ -107,9 +140,10 @@ fn narrative(docs: &[ModuleDoc]) -> String {
 
 /// Build indented module tree with pattern and description.
 fn module_tree(docs: &[ModuleDoc]) -> String {
+    let root_path = ModuleDoc::narrative_root(docs).map(|d| d.module_path.as_str());
     let mut modules: Vec<&ModuleDoc> = docs
         .iter()
-        .filter(|d| d.module_path != "_lib")
+        .filter(|d| Some(d.module_path.as_str()) != root_path)
         .collect();
     modules.sort_by(|a, b| a.module_path.cmp(&b.module_path));
 
@@ -171,9 +205,10 @@ fn module_tree(docs: &[ModuleDoc]) -> String {
 
 /// Flat relationship list with short module names.
 fn relationships(docs: &[ModuleDoc]) -> String {
+    let root_path = ModuleDoc::narrative_root(docs).map(|d| d.module_path.as_str());
     let modules: Vec<&ModuleDoc> = docs
         .iter()
-        .filter(|d| d.module_path != "_lib")
+        .filter(|d| Some(d.module_path.as_str()) != root_path)
         .collect();
     let prefix = common_prefix(&modules);
 
@@ -198,10 +233,14 @@ fn relationships(docs: &[ModuleDoc]) -> String {
 
     let mut out = String::new();
     for (src, tgt, label, proto) in &rels {
-        out.push_str(&format!(
-            "{} -> {}: \"{}\" ({})\n",
-            src, tgt, label, proto
-        ));
+        if proto.is_empty() {
+            out.push_str(&format!("{} -> {}: \"{}\"\n", src, tgt, label));
+        } else {
+            out.push_str(&format!(
+                "{} -> {}: \"{}\" ({})\n",
+                src, tgt, label, proto
+            ));
+        }
     }
     out
 }
@@ -246,20 +285,16 @@ fn common_prefix(modules: &[&ModuleDoc]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use archidoc_types::{C4Level, PatternStatus};
+    use archidoc_types::ElementKind;
 
-    fn doc(path: &str, pattern: &str, desc: &str, level: C4Level) -> ModuleDoc {
+    fn doc(path: &str, pattern: &str, desc: &str, level: ElementKind) -> ModuleDoc {
         ModuleDoc {
             module_path: path.to_string(),
-            content: String::new(),
             source_file: format!("src/{}/mod.rs", path.replace('.', "/")),
             c4_level: level,
             pattern: pattern.to_string(),
-            pattern_status: PatternStatus::Planned,
             description: desc.to_string(),
-            parent_container: None,
-            relationships: vec![],
-            files: vec![],
+            ..Default::default()
         }
     }
 
@@ -268,13 +303,9 @@ mod tests {
             module_path: "_lib".to_string(),
             content: content.to_string(),
             source_file: "src/lib.rs".to_string(),
-            c4_level: C4Level::Container,
+            c4_level: ElementKind::Container,
             pattern: "--".to_string(),
-            pattern_status: PatternStatus::Planned,
-            description: String::new(),
-            parent_container: None,
-            relationships: vec![],
-            files: vec![],
+            ..Default::default()
         }
     }
 
@@ -286,7 +317,7 @@ mod tests {
 
     #[test]
     fn single_module() {
-        let docs = vec![doc("api", "Facade", "REST gateway", C4Level::Container)];
+        let docs = vec![doc("api", "Facade", "REST gateway", ElementKind::Container)];
         let out = generate(&docs);
         assert!(out.contains("api/ Facade — REST gateway"));
     }
@@ -294,8 +325,8 @@ mod tests {
     #[test]
     fn strips_common_prefix() {
         let docs = vec![
-            doc("a.b.foo", "Facade", "Foo", C4Level::Container),
-            doc("a.b.bar", "--", "Bar", C4Level::Container),
+            doc("a.b.foo", "Facade", "Foo", ElementKind::Container),
+            doc("a.b.bar", "--", "Bar", ElementKind::Container),
         ];
         let out = generate(&docs);
         assert!(out.contains("bar/ — Bar"));
@@ -305,9 +336,9 @@ mod tests {
     #[test]
     fn nested_indentation() {
         let docs = vec![
-            doc("a.b.bus", "Mediator", "Bus", C4Level::Container),
-            doc("a.b.bus.calc", "Strategy", "Calc", C4Level::Component),
-            doc("a.b.bus.calc.ind", "--", "Indicators", C4Level::Component),
+            doc("a.b.bus", "Mediator", "Bus", ElementKind::Container),
+            doc("a.b.bus.calc", "Strategy", "Calc", ElementKind::Component),
+            doc("a.b.bus.calc.ind", "--", "Indicators", ElementKind::Component),
         ];
         let out = generate(&docs);
         assert!(out.contains("bus/ Mediator — Bus\n"));
@@ -342,9 +373,23 @@ mod tests {
         assert!(!out.contains("GoF:"));
     }
 
+    #[test]
+    fn narrative_falls_back_to_description_without_content() {
+        let mut root = lib("# Eng\n\nLong narrative.");
+        root.description = "Engine".to_string();
+        let mut docs = vec![root];
+        crate::ir::strip_content(&mut docs);
+
+        let json = crate::ir::serialize(&docs);
+        assert!(!json.contains("\"content\""));
+        let out = generate(&crate::ir::deserialize(&json).unwrap());
+        assert!(out.contains("Engine\n"));
+        assert!(!out.contains("Long narrative."));
+    }
+
     #[test]
     fn dash_dash_pattern_hidden() {
-        let docs = vec![doc("types", "--", "Core types", C4Level::Container)];
+        let docs = vec![doc("types", "--", "Core types", ElementKind::Container)];
         let out = generate(&docs);
         assert!(out.contains("types/ — Core types"));
         assert!(!out.contains("--"));
@@ -370,13 +415,14 @@ mod tests {
 
     #[test]
     fn relationships_included() {
-        let mut api = doc("x.api", "Facade", "API", C4Level::Container);
+        let mut api = doc("x.api", "Facade", "API", ElementKind::Container);
         api.relationships = vec![archidoc_types::Relationship {
             target: "x.db".to_string(),
             label: "Persists".to_string(),
             protocol: "sqlx".to_string(),
+            hidden: false,
         }];
-        let docs = vec![api, doc("x.db", "Repository", "DB", C4Level::Container)];
+        let docs = vec![api, doc("x.db", "Repository", "DB", ElementKind::Container)];
         let out = generate(&docs);
         assert!(out.contains("api -> db: \"Persists\" (sqlx)"));
     }
@@ -1,6 +1,92 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use archidoc_types::{C4Level, ModuleDoc};
+use archidoc_types::{
+    ArchitectureConfig, DiagramConfig, ElementKind, FitnessSummary, HealthReport, HealthStatus, IndexOrder, Language, ModuleDoc,
+    PatternStatus, ValidationReport,
+};
+
+use crate::front_matter::FrontMatterSettings;
+use crate::history::ArchivedElement;
+use crate::generator::{Artifact, Generator};
+use crate::i18n::{fill, Strings};
+
+/// ARCHITECTURE.md, written as `file_name` in the output directory.
+pub struct ArchitectureGenerator {
+    pub file_name: String,
+    pub link_base: PathBuf,
+    pub fitness: Vec<FitnessSummary>,
+    pub snapshot: Snapshot,
+    pub language: Language,
+    /// Sections left out and Component Index order.
+    pub layout: ArchitectureConfig,
+    /// Prepend YAML front matter when set.
+    pub front_matter: Option<FrontMatterSettings>,
+    /// Options for the inline diagrams.
+    pub diagrams: DiagramConfig,
+}
+
+impl Generator for ArchitectureGenerator {
+    fn id(&self) -> &str {
+        "architecture"
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        let mut content = generate_document(
+            docs,
+            &self.link_base,
+            &self.fitness,
+            &self.snapshot,
+            self.language,
+            &self.layout,
+            &self.diagrams,
+        );
+        if let Some(settings) = &self.front_matter {
+            let title = crate::i18n::strings(self.language).architecture_title;
+            let header = crate::front_matter::render(title, &crate::front_matter::ir_hash(docs), settings.weight, settings);
+            content.insert_str(0, &header);
+        }
+        Ok(vec![Artifact {
+            path: self.file_name.clone(),
+            content,
+        }])
+    }
+}
+
+/// Project state at generation time, for the Project Health and
+/// Validation Findings sections. Each section is present only when its
+/// report is.
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub health: Option<HealthReport>,
+    pub validation: Option<ValidationReport>,
+    /// Elements removed from the project, for the Archived Elements
+    /// appendix.
+    pub archived: Option<Vec<ArchivedElement>>,
+}
+
+/// Ids of the ARCHITECTURE.md sections that `[architecture] skip` can
+/// leave out, in document order.
+pub const SECTIONS: &[&str] = &[
+    "contents",
+    "narrative",
+    "system_context",
+    "system_diagram",
+    "component_diagram",
+    "component_index",
+    "elements",
+    "relationship_map",
+    "scenarios",
+    "crosscutting",
+    "links",
+    "risk_register",
+    "fitness",
+    "health",
+    "validation",
+    "glossary",
+    "patterns_in_use",
+    "metrics",
+    "archived",
+];
 
 /// Generate the full ARCHITECTURE.md content as a string.
 ///
@@ -8,36 +94,183 @@ use archidoc_types::{C4Level, ModuleDoc};
 /// file links in the Component Index table.
 ///
 /// Sections:
-/// 1. Header + narrative (from _lib doc content)
-/// 2. System Diagram (inline Mermaid C4 container)
-/// 3. Component Diagram (inline Mermaid C4 component, if any)
-/// 4. Component Index (table with source file links)
-/// 5. Relationship Map
-/// 6. Footer
+/// 1. Header, table of contents, and narrative (from the crate root: _lib,
+///    else _main)
+/// 2. System Diagram (inline Mermaid C4 container, followed by a text
+///    description)
+/// 3. Component Diagram (inline Mermaid C4 component, if any, followed by a
+///    text description)
+/// 4. Component Index (table linking to each element's section)
+/// 5. Elements (one section per element with its source link and
+///    dependencies)
+/// 6. Relationship Map, then Scenarios (if any `@flow` annotations or Data Flow steps)
+/// 7. Links (if any `@doc` annotations)
+/// 8. Risk Register (if any `@risk` annotations)
+/// 9. Architectural Fitness (if fitness results are passed in, see
+///    [`generate_with_fitness`])
+/// 10. Project Health and Validation Findings (if a [`Snapshot`] is passed
+///     in, see [`generate_document`])
+/// 11. Glossary (if any `@term` annotations)
+/// 12. Patterns in Use appendix (if any module or file claims a pattern)
+/// 13. Metrics appendix (if complexity metrics were collected)
+/// 14. Archived Elements appendix (if the [`Snapshot`] lists removed
+///     elements)
+/// 15. Footer
+///
+/// Every section is preceded by a `section-<id>` anchor (the id from
+/// [`SECTIONS`] with `-` for `_`) and every element
+/// section by its element id (see [`ModuleDoc::element_id`]), so links
+/// survive reordering and localized headings.
 pub fn generate(docs: &[ModuleDoc], root: &Path) -> String {
+    generate_with_fitness(docs, root, &[])
+}
+
+/// [`generate`], including an Architectural Fitness section with the given
+/// fitness function results.
+pub fn generate_with_fitness(docs: &[ModuleDoc], root: &Path, fitness: &[FitnessSummary]) -> String {
+    generate_localized(docs, root, fitness, Language::En)
+}
+
+/// [`generate_with_fitness`], with headings and table labels in `language`.
+/// Module content is written as annotated.
+pub fn generate_localized(
+    docs: &[ModuleDoc],
+    root: &Path,
+    fitness: &[FitnessSummary],
+    language: Language,
+) -> String {
+    generate_document(
+        docs,
+        root,
+        fitness,
+        &Snapshot::default(),
+        language,
+        &ArchitectureConfig::default(),
+        &DiagramConfig::default(),
+    )
+}
+
+/// [`generate_localized`], leaving out the sections whose ids (see
+/// [`SECTIONS`]) are in `layout.skip` and ordering the Component Index by
+/// `layout.index_order`. Without `elements`, Component Index rows link to
+/// source files instead. `snapshot` adds the Project Health and
+/// Validation Findings sections; `diagrams` shapes the inline System
+/// Diagram.
+pub fn generate_document(
+    docs: &[ModuleDoc],
+    root: &Path,
+    fitness: &[FitnessSummary],
+    snapshot: &Snapshot,
+    language: Language,
+    layout: &ArchitectureConfig,
+    diagrams: &DiagramConfig,
+) -> String {
+    let t = crate::i18n::strings(language);
+    let show = |id: &str| !layout.skip.iter().any(|s| s == id);
     let mut output = String::new();
 
-    output.push_str("# Architecture Context\n\n");
-    output.push_str("> Auto-generated by archidoc. Do not edit manually.\n\n");
+    output.push_str(&format!("# {}\n\n", t.architecture_title));
+    output.push_str(&format!("> {}\n\n", t.auto_generated));
 
-    output.push_str(&section_narrative(docs));
-    output.push_str(&section_container_diagram(docs));
-    output.push_str(&section_component_diagram(docs));
-    output.push_str(&section_component_index(docs, root));
-    output.push_str(&section_relationship_map(docs));
+    // (id, title, content); skipped and empty sections are left out
+    let sections = [
+        ("system_context", t.system_context, section_context_diagram(docs, diagrams, t)),
+        ("system_diagram", t.system_diagram, section_container_diagram(docs, diagrams, t)),
+        ("component_diagram", t.component_diagram, section_component_diagram(docs, diagrams, t)),
+        ("component_index", t.component_index, section_component_index(docs, root, t, layout.index_order, show("elements"))),
+        ("elements", t.elements, section_elements(docs, root, t)),
+        ("relationship_map", t.relationship_map, section_relationship_map(docs, t)),
+        ("scenarios", t.scenarios, section_scenarios(docs, t)),
+        ("crosscutting", t.crosscutting, section_crosscutting(docs, t)),
+        ("links", t.links, section_links(docs, t)),
+        ("risk_register", t.risk_register, section_risk_register(docs, t)),
+        ("fitness", t.fitness, section_fitness(fitness, t)),
+        ("health", t.project_health, section_health(snapshot.health.as_ref(), t)),
+        ("validation", t.validation_findings, section_validation(snapshot.validation.as_ref(), t)),
+        ("glossary", t.glossary, section_glossary(docs, root, t)),
+        ("patterns_in_use", t.patterns_in_use, section_patterns_in_use(docs, root, t)),
+        ("metrics", t.metrics, section_metrics(docs, t)),
+        ("archived", t.archived_elements, section_archived(snapshot.archived.as_deref(), t)),
+    ];
+    let present: Vec<&(&str, &str, String)> = sections
+        .iter()
+        .filter(|(id, _, content)| show(id) && !content.is_empty())
+        .collect();
+    let anchor = |id: &str| format!("section-{}", id.replace('_', "-"));
+
+    if show("contents") && !present.is_empty() {
+        output.push_str(&format!("## {}\n\n", t.contents));
+        for (id, title, _) in &present {
+            output.push_str(&format!("- [{}](#{})\n", title, anchor(id)));
+        }
+        output.push('\n');
+    }
+
+    if show("narrative") {
+        // A generated System Context replaces the hand-written one
+        let generated_context = show("system_context") && crate::mermaid::has_context(docs);
+        output.push_str(&section_narrative(docs, generated_context));
+    }
+    for (id, _, content) in present {
+        output.push_str(&anchor_line(&anchor(id)));
+        output.push_str(content);
+    }
 
-    output.push_str("---\n\n*Auto-generated by archidoc. Do not edit manually.*\n");
+    output.push_str(&format!("---\n\n*{}*\n", t.auto_generated));
 
     output
 }
 
-/// Extract narrative prose from the _lib module's content.
+/// Insert `section` into a document generated without section `id`, where
+/// that section would have been: before the next section present, or else
+/// before the closing note.
+pub(crate) fn insert_section(markdown: &mut String, id: &str, section: &str) {
+    let at = SECTIONS
+        .iter()
+        .skip_while(|s| **s != id)
+        .skip(1)
+        .find_map(|s| markdown.find(&format!("<a id=\"section-{}\"></a>", s.replace('_', "-"))))
+        .or_else(|| markdown.rfind("---\n\n*"))
+        .unwrap_or(markdown.len());
+    markdown.insert_str(at, section);
+}
+
+/// One element's section, as `document` writes it under Elements,
+/// followed by a diagram of the element and its direct dependencies both
+/// ways. Lets watch mode and servers refresh a single element without
+/// regenerating the whole document; `None` when no element has
+/// `module_path`.
+pub fn generate_element(docs: &[ModuleDoc], module_path: &str, document: &ArchitectureGenerator) -> Option<String> {
+    let doc = docs.iter().find(|d| d.module_path == module_path)?;
+    let t = crate::i18n::strings(document.language);
+    let mut output = element_section(doc, docs, &indexed_modules(docs), &document.link_base, t);
+    output.push_str(&crate::mermaid::element_diagram(docs, doc, &document.diagrams));
+    output.push_str("\n\n");
+    Some(output)
+}
+
+/// An HTML anchor on its own line. The blank line after it ends the HTML
+/// block, so a following heading still renders as one.
+pub(crate) fn anchor_line(id: &str) -> String {
+    format!("<a id=\"{}\"></a>\n\n", id)
+}
+
+/// A markdown table header row and its separator, each dash run as wide as
+/// the padded header cell.
+fn table_header(columns: &[&str]) -> String {
+    let header: Vec<String> = columns.iter().map(|c| format!(" {} ", c)).collect();
+    let separator: Vec<String> = header.iter().map(|c| "-".repeat(c.chars().count())).collect();
+    format!("|{}|\n|{}|\n", header.join("|"), separator.join("|"))
+}
+
+/// Extract narrative prose from the crate root module's content.
 ///
-/// Filters out @c4 markers, file table rows, and GoF lines.
-fn section_narrative(docs: &[ModuleDoc]) -> String {
-    let lib_doc = docs.iter().find(|d| d.module_path == "_lib");
-    let content = match lib_doc {
-        Some(doc) => &doc.content,
+/// Filters out annotation markers, file table rows, and GoF lines; with
+/// `drop_context`, also hand-written `C4Context` diagrams.
+fn section_narrative(docs: &[ModuleDoc], drop_context: bool) -> String {
+    let content = match ModuleDoc::narrative_root(docs) {
+        Some(doc) if drop_context => without_context_diagrams(&doc.content),
+        Some(doc) => doc.content.clone(),
         None => return String::new(),
     };
 
@@ -47,8 +280,8 @@ fn section_narrative(docs: &[ModuleDoc]) -> String {
     for line in content.lines() {
         let trimmed = line.trim();
 
-        // Skip @c4 marker lines
-        if trimmed.starts_with("@c4 ") {
+        // Skip @c4 and other annotation marker lines
+        if archidoc_types::is_marker_line(trimmed) {
             continue;
         }
 
@@ -80,68 +313,356 @@ fn section_narrative(docs: &[ModuleDoc]) -> String {
     }
 }
 
+/// `content` without its ```` ```mermaid ```` blocks drawing a `C4Context`
+/// diagram, and without a heading such a block leaves with nothing under
+/// it.
+fn without_context_diagrams(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut kept: Vec<&str> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let is_context = lines[i].trim() == "```mermaid" && lines.get(i + 1).is_some_and(|l| l.trim() == "C4Context");
+        if !is_context {
+            kept.push(lines[i]);
+            i += 1;
+            continue;
+        }
+        i = match lines[i + 1..].iter().position(|l| l.trim() == "```") {
+            Some(n) => i + n + 2,
+            None => lines.len(),
+        };
+        let next = lines[i..].iter().find(|l| !l.trim().is_empty());
+        let trim_blank = |kept: &mut Vec<&str>| {
+            while kept.last().is_some_and(|l| l.trim().is_empty()) {
+                kept.pop();
+            }
+        };
+        trim_blank(&mut kept);
+        if next.is_none_or(|l| l.trim_start().starts_with('#')) && kept.last().is_some_and(|l| l.trim_start().starts_with('#')) {
+            kept.pop();
+            trim_blank(&mut kept);
+        }
+    }
+    kept.join("\n")
+}
+
+/// Inline Mermaid C4 system context diagram (skipped without people or
+/// external systems).
+fn section_context_diagram(docs: &[ModuleDoc], diagrams: &DiagramConfig, t: &Strings) -> String {
+    if !crate::mermaid::has_context(docs) {
+        return String::new();
+    }
+
+    format!(
+        "## {}\n\n{}\n\n{}",
+        t.system_context,
+        crate::mermaid::context_diagram(docs, diagrams),
+        crate::describe::context_description(docs, t)
+    )
+}
+
 /// Inline Mermaid C4 container diagram.
-fn section_container_diagram(docs: &[ModuleDoc]) -> String {
-    let has_containers = docs.iter().any(|d| d.c4_level == C4Level::Container);
+fn section_container_diagram(docs: &[ModuleDoc], diagrams: &DiagramConfig, t: &Strings) -> String {
+    let has_containers = docs.iter().any(|d| d.c4_level.is_container());
     if !has_containers {
         return String::new();
     }
 
-    let diagram = crate::mermaid::container_diagram(docs);
-    format!("## System Diagram\n\n{}\n\n", diagram)
+    let diagram = crate::mermaid::container_diagram(docs, diagrams);
+    format!(
+        "## {}\n\n{}\n\n{}",
+        t.system_diagram,
+        diagram,
+        crate::describe::container_description(docs, t)
+    )
 }
 
 /// Inline Mermaid C4 component diagram (skipped if no components).
-fn section_component_diagram(docs: &[ModuleDoc]) -> String {
-    let has_components = docs.iter().any(|d| d.c4_level == C4Level::Component);
+fn section_component_diagram(docs: &[ModuleDoc], diagrams: &DiagramConfig, t: &Strings) -> String {
+    let has_components = docs.iter().any(|d| d.c4_level == ElementKind::Component);
     if !has_components {
         return String::new();
     }
 
-    let diagram = crate::mermaid::component_diagram(docs);
-    format!("## Component Diagram\n\n{}\n\n", diagram)
+    let diagram = crate::mermaid::component_diagram(docs, diagrams);
+    format!(
+        "## {}\n\n{}\n\n{}",
+        t.component_diagram,
+        diagram,
+        crate::describe::component_description(docs, t)
+    )
 }
 
-/// Component index table with links to source files.
-///
-/// Links are relative to `root` so they work when the generated markdown
-/// is opened from any location within the project tree.
-fn section_component_index(docs: &[ModuleDoc], root: &Path) -> String {
+/// Every element except the narrative root, sorted by module path.
+fn indexed_modules(docs: &[ModuleDoc]) -> Vec<&ModuleDoc> {
+    let root_path = ModuleDoc::narrative_root(docs).map(|d| d.module_path.as_str());
     let mut modules: Vec<&ModuleDoc> = docs
         .iter()
-        .filter(|d| d.module_path != "_lib")
+        .filter(|d| Some(d.module_path.as_str()) != root_path)
         .collect();
     modules.sort_by(|a, b| a.module_path.cmp(&b.module_path));
+    modules
+}
 
+/// [`indexed_modules`] in Component Index order. Sorts are stable, so ties
+/// stay in module path order.
+pub(crate) fn index_modules(docs: &[ModuleDoc], order: IndexOrder) -> Vec<&ModuleDoc> {
+    let mut modules = indexed_modules(docs);
+    match order {
+        IndexOrder::Alphabetical => {}
+        IndexOrder::Container => modules.sort_by_key(|d| {
+            let rank = if d.c4_level.is_container() {
+                0
+            } else if d.c4_level == ElementKind::Component {
+                1
+            } else {
+                2
+            };
+            (container_of(d), rank)
+        }),
+        IndexOrder::Health => modules.sort_by_key(|d| {
+            let score = maturity(d);
+            (score.is_none(), score)
+        }),
+        IndexOrder::Owner => modules.sort_by_key(|d| {
+            let owner = owner(docs, d);
+            (owner.is_none(), owner)
+        }),
+    }
+    modules
+}
+
+/// The container an element sits in: its parent for components, else the
+/// element itself.
+pub(crate) fn container_of(doc: &ModuleDoc) -> &str {
+    match (&doc.c4_level, &doc.parent_container) {
+        (ElementKind::Component, Some(parent)) => parent,
+        _ => &doc.module_path,
+    }
+}
+
+/// File table maturity in per mille, planned counting 0, active half, and
+/// stable full; `None` without a file table.
+fn maturity(doc: &ModuleDoc) -> Option<usize> {
+    if doc.files.is_empty() {
+        return None;
+    }
+    let points: usize = doc
+        .files
+        .iter()
+        .map(|f| match f.health {
+            HealthStatus::Planned => 0,
+            HealthStatus::Active => 1,
+            HealthStatus::Stable => 2,
+        })
+        .sum();
+    Some(points * 1000 / (2 * doc.files.len()))
+}
+
+/// An element's `@group`, or its container's for a component without one.
+fn owner<'a>(docs: &'a [ModuleDoc], doc: &'a ModuleDoc) -> Option<&'a str> {
+    doc.group.as_deref().or_else(|| {
+        docs.iter()
+            .find(|d| d.module_path == container_of(doc) && d.module_path != doc.module_path)
+            .and_then(|d| d.group.as_deref())
+    })
+}
+
+/// Component index table with links to each element's section, or to the
+/// element's source file when there are no element sections.
+fn section_component_index(
+    docs: &[ModuleDoc],
+    root: &Path,
+    t: &Strings,
+    order: IndexOrder,
+    link_elements: bool,
+) -> String {
+    let modules = index_modules(docs, order);
     if modules.is_empty() {
         return String::new();
     }
 
+    // Optional columns only appear once some element has data for them.
+    let show_unsafe = docs.iter().any(|d| d.unsafe_count > 0);
+    let show_coverage = docs.iter().any(|d| crate::coverage::module_coverage(d).is_some());
+
+    let mut columns = vec![t.col_module, t.col_level, t.col_pattern, t.col_description];
+    if show_unsafe {
+        columns.push(t.col_unsafe);
+    }
+    if show_coverage {
+        columns.push(t.col_coverage);
+    }
+
     let mut output = String::new();
-    output.push_str("## Component Index\n\n");
-    output.push_str("| Module | Level | Pattern | Description |\n");
-    output.push_str("|--------|-------|---------|-------------|\n");
+    output.push_str(&format!("## {}\n\n", t.component_index));
+    output.push_str(&table_header(&columns));
 
     for doc in &modules {
-        let source = Path::new(&doc.source_file);
-        let rel = pathdiff::diff_paths(source, root)
-            .unwrap_or_else(|| source.to_path_buf());
         output.push_str(&format!(
-            "| [{}]({}) | {} | {} | {} |\n",
+            "| [{}]({}) | {} | {} | {} |",
             doc.module_path,
-            rel.display().to_string().replace('\\', "/"),
-            doc.c4_level,
-            doc.pattern,
-            doc.description,
+            if link_elements {
+                format!("#{}", doc.element_id())
+            } else {
+                source_link(doc, root)
+            },
+            match &doc.condition {
+                Some(condition) => format!("{} ({})", doc.c4_level, condition),
+                None => doc.c4_level.to_string(),
+            },
+            escape_table_cell(&doc.pattern),
+            escape_table_cell(&doc.description),
         ));
+        if show_unsafe {
+            output.push_str(&format!(" {} |", doc.unsafe_count));
+        }
+        if show_coverage {
+            match crate::coverage::module_coverage(doc) {
+                Some(pct) => output.push_str(&format!(" {:.1}% |", pct)),
+                None => output.push_str(" -- |"),
+            }
+        }
+        output.push('\n');
     }
 
     output.push('\n');
     output
 }
 
+/// One section per indexed element, anchored by its element id: level,
+/// pattern, a link to its source file, its dependencies both ways, and its
+/// file table. `archidoc push-back` reads edited purposes and health values
+/// back from the file tables (see [`crate::pushback`]).
+///
+/// Source links are relative to `root` so they work when the generated
+/// markdown is opened from any location within the project tree.
+fn section_elements(docs: &[ModuleDoc], root: &Path, t: &Strings) -> String {
+    let modules = indexed_modules(docs);
+    if modules.is_empty() {
+        return String::new();
+    }
+
+    let mut output = String::new();
+    output.push_str(&format!("## {}\n\n", t.elements));
+    for doc in &modules {
+        output.push_str(&element_section(doc, docs, &modules, root, t));
+    }
+    output
+}
+
+/// The section for one element, as it appears under Elements.
+fn element_section(doc: &ModuleDoc, docs: &[ModuleDoc], modules: &[&ModuleDoc], root: &Path, t: &Strings) -> String {
+    let mut output = String::new();
+    output.push_str(&anchor_line(&doc.element_id()));
+    output.push_str(&format!("### {}\n\n", doc.module_path));
+    output.push_str(&element_body(doc, docs, modules, root, t));
+    output
+}
+
+/// Every element's section without its anchor and heading, in Elements
+/// order, for renderers that lay elements out themselves.
+pub(crate) fn element_bodies<'a>(docs: &'a [ModuleDoc], root: &Path, language: Language) -> Vec<(&'a ModuleDoc, String)> {
+    let t = crate::i18n::strings(language);
+    let modules = indexed_modules(docs);
+    modules
+        .iter()
+        .map(|doc| (*doc, element_body(doc, docs, &modules, root, t)))
+        .collect()
+}
+
+/// An element's description, facts, and file table. Links to other
+/// elements point at their sections when `modules` has one.
+fn element_body(doc: &ModuleDoc, docs: &[ModuleDoc], modules: &[&ModuleDoc], root: &Path, t: &Strings) -> String {
+    let element_link = |path: &str| {
+        if modules.iter().any(|d| d.module_path == path) {
+            format!("[{}](#{})", path, ModuleDoc::id_of(docs, path))
+        } else {
+            path.to_string()
+        }
+    };
+
+    let mut output = String::new();
+    if !doc.description.is_empty() {
+        output.push_str(&format!("{}\n\n", doc.description));
+    }
+
+    let level = match &doc.condition {
+        Some(condition) => format!("{} ({})", doc.c4_level, condition),
+        None => doc.c4_level.to_string(),
+    };
+    output.push_str(&format!("- **{}:** {}\n", t.col_level, level));
+    if is_claimed(&doc.pattern) {
+        output.push_str(&format!("- **{}:** {} ({})\n", t.col_pattern, doc.pattern, doc.pattern_status));
+    }
+    let source = source_link(doc, root);
+    output.push_str(&format!("- **{}:** [{}]({})\n", t.element_source, source, source));
+
+    let uses: Vec<String> = doc
+        .relationships
+        .iter()
+        .map(|rel| {
+            let link = element_link(&rel.target);
+            if rel.protocol.is_empty() {
+                format!("{} — {}", link, rel.label)
+            } else {
+                format!("{} — {} ({})", link, rel.label, rel.protocol)
+            }
+        })
+        .collect();
+    if !uses.is_empty() {
+        output.push_str(&format!("- **{}:** {}\n", t.element_uses, uses.join("; ")));
+    }
+
+    let used_by: Vec<String> = docs
+        .iter()
+        .filter(|d| d.relationships.iter().any(|r| r.target == doc.module_path))
+        .map(|d| element_link(&d.module_path))
+        .collect();
+    if !used_by.is_empty() {
+        output.push_str(&format!("- **{}:** {}\n", t.element_used_by, used_by.join(", ")));
+    }
+    output.push('\n');
+
+    if !doc.files.is_empty() {
+        output.push_str(&table_header(&[t.col_file, t.col_pattern, t.col_purpose, t.col_health]));
+        for file in &doc.files {
+            output.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                file.name,
+                escape_table_cell(&file.pattern),
+                escape_table_cell(&file.purpose),
+                file.health
+            ));
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Link target for a module's source file, relative to `root` and using
+/// forward slashes.
+fn source_link(doc: &ModuleDoc, root: &Path) -> String {
+    let source = Path::new(&doc.source_file);
+    let rel = pathdiff::diff_paths(source, root).unwrap_or_else(|| source.to_path_buf());
+    rel.display().to_string().replace('\\', "/")
+}
+
+/// Escape text for use inside a markdown table cell.
+///
+/// Pipes are escaped as `\|` and line breaks collapse to spaces, so the
+/// rendered row parses back to the original cell text.
+fn escape_table_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Flat relationship map across all modules.
-fn section_relationship_map(docs: &[ModuleDoc]) -> String {
+fn section_relationship_map(docs: &[ModuleDoc], t: &Strings) -> String {
     let all_rels: Vec<(&str, &archidoc_types::Relationship)> = docs
         .iter()
         .flat_map(|doc| {
@@ -156,12 +677,133 @@ fn section_relationship_map(docs: &[ModuleDoc]) -> String {
     }
 
     let mut output = String::new();
-    output.push_str("## Relationship Map\n\n");
+    output.push_str(&format!("## {}\n\n", t.relationship_map));
 
     for (source, rel) in &all_rels {
+        if rel.protocol.is_empty() {
+            output.push_str(&format!(
+                "- {} -> {}: \"{}\"\n",
+                source, rel.target, rel.label
+            ));
+        } else {
+            output.push_str(&format!(
+                "- {} -> {}: \"{}\" ({})\n",
+                source, rel.target, rel.label, rel.protocol
+            ));
+        }
+    }
+
+    output.push('\n');
+    output
+}
+
+/// Each flow as a numbered list of its steps and a sequence diagram.
+fn section_scenarios(docs: &[ModuleDoc], t: &Strings) -> String {
+    let flows = crate::flows::all_flows(docs);
+    if flows.is_empty() {
+        return String::new();
+    }
+
+    let mut output = String::new();
+    output.push_str(&format!("## {}\n\n", t.scenarios));
+    for flow in flows {
+        output.push_str(&format!("### {}\n\n", flow.name));
+        for step in crate::flows::flow_steps(docs, flow) {
+            output.push_str(&format!("{}. {} -> {}", step.index, step.from, step.to));
+            if !step.label.is_empty() {
+                output.push_str(&format!(": \"{}\"", step.label));
+            }
+            if !step.protocol.is_empty() {
+                output.push_str(&format!(" ({})", step.protocol));
+            }
+            output.push('\n');
+        }
+        output.push('\n');
+        output.push_str(&crate::mermaid::sequence_diagram(docs, flow));
+        output.push_str("\n\n");
+    }
+    output
+}
+
+/// `@crosscutting` elements with every element that uses them, in place of
+/// the arrows the diagrams leave out.
+fn section_crosscutting(docs: &[ModuleDoc], t: &Strings) -> String {
+    let concerns: Vec<&ModuleDoc> = docs.iter().filter(|d| d.crosscutting).collect();
+    if concerns.is_empty() {
+        return String::new();
+    }
+
+    let mut output = String::new();
+    output.push_str(&format!("## {}
+
+", t.crosscutting));
+    output.push_str(&table_header(&[t.col_concern, t.col_description, t.element_used_by]));
+
+    for concern in concerns {
+        let users: Vec<&str> = docs
+            .iter()
+            .filter(|d| d.relationships.iter().any(|r| r.target == concern.module_path))
+            .map(|d| d.module_path.as_str())
+            .collect();
+        output.push_str(&format!(
+            "| {} | {} | {} |\n",
+            concern.module_path,
+            escape_table_cell(&concern.description),
+            if users.is_empty() { "-".to_string() } else { users.join(", ") }
+        ));
+    }
+
+    output.push('\n');
+    output
+}
+
+/// External documentation links per element, from `@doc`.
+///
+/// Links that fail the URL shape check are omitted; `--validate` reports them.
+fn section_links(docs: &[ModuleDoc], t: &Strings) -> String {
+    let mut modules: Vec<&ModuleDoc> = docs
+        .iter()
+        .filter(|d| d.links.iter().any(|l| l.has_valid_url()))
+        .collect();
+    if modules.is_empty() {
+        return String::new();
+    }
+    modules.sort_by(|a, b| a.module_path.cmp(&b.module_path));
+
+    let mut output = String::new();
+    output.push_str(&format!("## {}\n\n", t.links));
+
+    for doc in modules {
+        let links: Vec<String> = doc
+            .links
+            .iter()
+            .filter(|l| l.has_valid_url())
+            .map(|l| format!("[{}]({})", l.text(), l.url))
+            .collect();
+        output.push_str(&format!("- **{}**: {}\n", doc.module_path, links.join(", ")));
+    }
+
+    output.push('\n');
+    output
+}
+
+/// Consolidated risk register, most severe first.
+fn section_risk_register(docs: &[ModuleDoc], t: &Strings) -> String {
+    let register = crate::risk::collect_risks(docs);
+    if register.entries.is_empty() {
+        return String::new();
+    }
+
+    let mut output = String::new();
+    output.push_str(&format!("## {}\n\n", t.risk_register));
+    output.push_str(&table_header(&[t.col_severity, t.col_element, t.col_risk]));
+
+    for entry in &register.entries {
         output.push_str(&format!(
-            "- {} -> {}: \"{}\" ({})\n",
-            source, rel.target, rel.label, rel.protocol
+            "| {} | {} | {} |\n",
+            entry.severity,
+            entry.element,
+            escape_table_cell(&entry.description)
         ));
     }
 
@@ -169,28 +811,388 @@ fn section_relationship_map(docs: &[ModuleDoc]) -> String {
     output
 }
 
+/// Pass/fail per fitness function, failing rules first.
+fn section_fitness(fitness: &[FitnessSummary], t: &Strings) -> String {
+    if fitness.is_empty() {
+        return String::new();
+    }
+
+    let failing = fitness.iter().filter(|f| !f.passed()).count();
+    let mut output = String::new();
+    output.push_str(&format!("## {}\n\n", t.fitness));
+    let (passed, total) = ((fitness.len() - failing).to_string(), fitness.len().to_string());
+    output.push_str(&fill(t.rules_pass, &[("passed", &passed), ("total", &total)]));
+    output.push_str("\n\n");
+    output.push_str(&table_header(&[t.col_rule, t.col_result, t.col_checked, t.col_failures]));
+
+    let mut rules: Vec<&FitnessSummary> = fitness.iter().collect();
+    rules.sort_by_key(|f| f.passed());
+    for rule in rules {
+        let result = if !rule.passed() {
+            t.result_fail
+        } else if rule.checked == 0 {
+            t.result_not_applicable
+        } else {
+            t.result_pass
+        };
+        output.push_str(&format!("| `{}` | {} | {} | {} |\n", rule.name, result, rule.checked, rule.failed));
+    }
+
+    output.push('\n');
+    output
+}
+
+/// File maturity, pattern confidence, and review state across the
+/// project, as shares of the whole.
+fn section_health(report: Option<&HealthReport>, t: &Strings) -> String {
+    let Some(report) = report else {
+        return String::new();
+    };
+    let count = |n: usize| n.to_string();
+    let share = |part: usize, total: usize| {
+        if total == 0 {
+            "--".to_string()
+        } else {
+            format!("{:.1}%", part as f64 / total as f64 * 100.0)
+        }
+    };
+
+    let mut output = String::new();
+    output.push_str(&format!("## {}\n\n", t.project_health));
+    output.push_str(&format!(
+        "{}: {}\n\n",
+        t.health_elements,
+        fill(
+            t.health_elements_summary,
+            &[
+                ("total", &count(report.total_elements)),
+                ("containers", &count(report.container_count)),
+                ("components", &count(report.component_count)),
+            ],
+        )
+    ));
+    output.push_str(&table_header(&[t.col_status, t.col_files, t.col_share]));
+    for (status, files) in [
+        (HealthStatus::Planned, report.files_planned),
+        (HealthStatus::Active, report.files_active),
+        (HealthStatus::Stable, report.files_stable),
+    ] {
+        output.push_str(&format!("| {} | {} | {} |\n", status, files, share(files, report.total_files)));
+    }
+    output.push('\n');
+
+    output.push_str(&format!(
+        "{}: {}",
+        t.health_patterns,
+        fill(t.health_patterns_assigned, &[("n", &count(report.patterns_total))])
+    ));
+    if report.patterns_total > 0 {
+        output.push_str(&format!(
+            " — {} {} ({}), {} {} ({})",
+            PatternStatus::Verified,
+            report.patterns_verified,
+            share(report.patterns_verified, report.patterns_total),
+            PatternStatus::Planned,
+            report.patterns_planned,
+            share(report.patterns_planned, report.patterns_total)
+        ));
+    }
+    output.push_str("\n\n");
+
+    output.push_str(&format!(
+        "{}: {}\n\n",
+        t.health_reviews,
+        fill(
+            t.health_reviews_summary,
+            &[
+                ("reviewed", &count(report.reviews_current)),
+                ("unreviewed", &count(report.reviews_unreviewed)),
+                ("stale", &count(report.reviews_stale)),
+                ("unverifiable", &count(report.reviews_unverifiable)),
+            ],
+        )
+    ));
+    output
+}
+
+/// Ghost and orphan files and invalid doc links, with their severity
+/// under `[validate]`. A clean report says so, so readers can tell a
+/// checked project from an unchecked one.
+fn section_validation(report: Option<&ValidationReport>, t: &Strings) -> String {
+    let Some(report) = report else {
+        return String::new();
+    };
+
+    let mut output = String::new();
+    output.push_str(&format!("## {}\n\n", t.validation_findings));
+    if report.is_clean() {
+        output.push_str(t.validation_clean);
+        output.push_str("\n\n");
+        return output;
+    }
+
+    output.push_str(&table_header(&[t.col_finding, t.col_element, t.col_file, t.col_severity]));
+    for ghost in &report.ghosts {
+        output.push_str(&format!(
+            "| {} | {} | `{}` | {} |\n",
+            t.finding_ghost,
+            ghost.element,
+            ghost.filename,
+            report.severities.ghost.as_str()
+        ));
+    }
+    for orphan in &report.orphans {
+        output.push_str(&format!(
+            "| {} | {} | `{}` | {} |\n",
+            t.finding_orphan,
+            orphan.element,
+            orphan.filename,
+            report.severities.orphan.as_str()
+        ));
+    }
+    for link in &report.invalid_links {
+        output.push_str(&format!(
+            "| {} | {} | `{}` | {} |\n",
+            t.finding_invalid_link,
+            link.element,
+            link.url,
+            report.severities.invalid_link.as_str()
+        ));
+    }
+
+    output.push('\n');
+    output
+}
+
+/// Measured complexity per element, next to the file maturity the element
+/// declares. Only present when the adapter collected metrics.
+/// Removed elements with the snapshot dates they were last seen and found
+/// missing; `--` when no snapshot has been taken since the removal.
+fn section_archived(archived: Option<&[ArchivedElement]>, t: &Strings) -> String {
+    let Some(archived) = archived.filter(|a| !a.is_empty()) else {
+        return String::new();
+    };
+
+    let mut output = String::new();
+    output.push_str(&format!("## {}\n\n{}\n\n", t.archived_elements, t.archived_intro));
+    output.push_str(&table_header(&[t.col_element, t.col_level, t.col_description, t.col_last_seen, t.col_removed]));
+    for element in archived {
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            element.module_path,
+            element.c4_level,
+            escape_table_cell(&element.description),
+            element.last_seen,
+            element.removed.as_deref().unwrap_or("--")
+        ));
+    }
+    output.push('\n');
+    output
+}
+
+fn section_metrics(docs: &[ModuleDoc], t: &Strings) -> String {
+    let measured: Vec<&ModuleDoc> = docs.iter().filter(|d| d.metrics.contains_key("loc")).collect();
+    if measured.is_empty() {
+        return String::new();
+    }
+
+    let mut output = String::new();
+    output.push_str(&format!("## {}\n\n", t.metrics));
+    output.push_str(&table_header(&[
+        t.col_element,
+        t.col_loc,
+        t.col_files,
+        t.col_public_items,
+        t.col_avg_fn_lines,
+        t.col_declared_health,
+    ]));
+
+    for doc in measured {
+        let metric = |key: &str| {
+            doc.metrics
+                .get(key)
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "-".to_string())
+        };
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            doc.module_path,
+            metric("loc"),
+            metric("files"),
+            metric("public_items"),
+            metric("avg_fn_lines"),
+            declared_health(doc)
+        ));
+    }
+
+    output.push('\n');
+    output
+}
+
+/// Summarize a module's file table by maturity, e.g. `2 stable, 1 planned`.
+fn declared_health(doc: &ModuleDoc) -> String {
+    let counts: Vec<String> = [HealthStatus::Stable, HealthStatus::Active, HealthStatus::Planned]
+        .into_iter()
+        .map(|status| (status, doc.files.iter().filter(|f| f.health == status).count()))
+        .filter(|(_, n)| *n > 0)
+        .map(|(status, n)| format!("{} {}", n, status))
+        .collect();
+
+    if counts.is_empty() {
+        "--".to_string()
+    } else {
+        counts.join(", ")
+    }
+}
+
+/// Alphabetized glossary of `@term` definitions with links back to the
+/// defining modules.
+///
+/// Terms sort case-insensitively. A term defined identically in several
+/// modules is listed once with a link to each; differing definitions are
+/// listed separately.
+fn section_glossary(docs: &[ModuleDoc], root: &Path, t: &Strings) -> String {
+    let mut entries: Vec<(&str, &str, Vec<&ModuleDoc>)> = Vec::new();
+    for doc in docs {
+        for term in &doc.terms {
+            match entries
+                .iter_mut()
+                .find(|(name, definition, _)| *name == term.name && *definition == term.definition)
+            {
+                Some((_, _, defined_in)) => defined_in.push(doc),
+                None => entries.push((&term.name, &term.definition, vec![doc])),
+            }
+        }
+    }
+
+    if entries.is_empty() {
+        return String::new();
+    }
+
+    entries.sort_by(|a, b| {
+        a.0.to_lowercase()
+            .cmp(&b.0.to_lowercase())
+            .then_with(|| a.0.cmp(b.0))
+            .then_with(|| a.1.cmp(b.1))
+    });
+
+    let mut output = String::new();
+    output.push_str(&format!("## {}\n\n", t.glossary));
+
+    for (name, definition, defined_in) in &entries {
+        let links: Vec<String> = defined_in
+            .iter()
+            .map(|doc| format!("[{}]({})", doc.module_path, source_link(doc, root)))
+            .collect();
+        output.push_str(&format!(
+            "- **{}** — {} ({})\n",
+            name,
+            definition,
+            links.join(", ")
+        ));
+    }
+
+    output.push('\n');
+    output
+}
+
+/// Appendix cross-referencing each design pattern with the modules and
+/// files that claim it, verified claims first.
+fn section_patterns_in_use(docs: &[ModuleDoc], root: &Path, t: &Strings) -> String {
+    // (pattern, status, link label, link target)
+    let mut claims: Vec<(&str, PatternStatus, String, String)> = Vec::new();
+
+    for doc in docs {
+        if is_claimed(&doc.pattern) {
+            claims.push((
+                &doc.pattern,
+                doc.pattern_status,
+                doc.module_path.clone(),
+                source_link(doc, root),
+            ));
+        }
+
+        let dir = doc.catalog_dir();
+        for file in doc.files.iter().filter(|f| is_claimed(&f.pattern)) {
+            let path = dir
+                .as_ref()
+                .map(|d| d.join(&file.name))
+                .unwrap_or_else(|| PathBuf::from(&file.name));
+            let rel = pathdiff::diff_paths(&path, root).unwrap_or(path);
+            claims.push((
+                &file.pattern,
+                file.pattern_status,
+                format!("{}/{}", doc.module_path, file.name),
+                rel.display().to_string().replace('\\', "/"),
+            ));
+        }
+    }
+
+    if claims.is_empty() {
+        return String::new();
+    }
+
+    claims.sort_by(|a, b| {
+        a.0.cmp(b.0)
+            .then_with(|| (a.1 != PatternStatus::Verified).cmp(&(b.1 != PatternStatus::Verified)))
+            .then_with(|| a.2.cmp(&b.2))
+    });
+
+    let mut output = String::new();
+    output.push_str(&format!("## {}\n\n", t.patterns_in_use));
+    output.push_str(&table_header(&[t.col_pattern, t.col_claimed_by, t.col_status]));
+
+    for (pattern, status, label, link) in &claims {
+        output.push_str(&format!(
+            "| {} | [{}]({}) | {} |\n",
+            escape_table_cell(pattern),
+            label,
+            link,
+            status
+        ));
+    }
+
+    output.push('\n');
+    output
+}
+
+/// Whether a pattern cell names a pattern rather than the `--` placeholder.
+fn is_claimed(pattern: &str) -> bool {
+    let pattern = pattern.trim();
+    !pattern.is_empty() && pattern != "--"
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::Path;
-    use archidoc_types::{C4Level, PatternStatus, Relationship};
+    use archidoc_types::{ElementKind, PatternStatus, Relationship};
 
     fn test_root() -> &'static Path {
         Path::new("")
     }
 
+    fn document() -> ArchitectureGenerator {
+        ArchitectureGenerator {
+            file_name: "ARCHITECTURE.md".to_string(),
+            link_base: test_root().to_path_buf(),
+            fitness: vec![],
+            snapshot: Snapshot::default(),
+            language: Language::En,
+            layout: ArchitectureConfig::default(),
+            front_matter: None,
+            diagrams: DiagramConfig::default(),
+        }
+    }
+
     fn make_container(name: &str, desc: &str) -> ModuleDoc {
         ModuleDoc {
             module_path: name.to_string(),
-            content: String::new(),
             source_file: format!("src/{}/mod.rs", name),
-            c4_level: C4Level::Container,
+            c4_level: ElementKind::Container,
             pattern: "--".to_string(),
-            pattern_status: PatternStatus::Planned,
             description: desc.to_string(),
-            parent_container: None,
-            relationships: vec![],
-            files: vec![],
+            ..Default::default()
         }
     }
 
@@ -198,15 +1200,12 @@ mod tests {
         let full_path = format!("{}.{}", parent, name);
         ModuleDoc {
             module_path: full_path.clone(),
-            content: String::new(),
             source_file: format!("src/{}/{}/mod.rs", parent, name),
-            c4_level: C4Level::Component,
+            c4_level: ElementKind::Component,
             pattern: "--".to_string(),
-            pattern_status: PatternStatus::Planned,
             description: desc.to_string(),
             parent_container: Some(parent.to_string()),
-            relationships: vec![],
-            files: vec![],
+            ..Default::default()
         }
     }
 
@@ -215,13 +1214,10 @@ mod tests {
             module_path: "_lib".to_string(),
             content: content.to_string(),
             source_file: "src/lib.rs".to_string(),
-            c4_level: C4Level::Container,
+            c4_level: ElementKind::Container,
             pattern: "--".to_string(),
-            pattern_status: PatternStatus::Planned,
             description: "Root module".to_string(),
-            parent_container: None,
-            relationships: vec![],
-            files: vec![],
+            ..Default::default()
         }
     }
 
@@ -243,7 +1239,7 @@ mod tests {
         assert!(output.contains("```mermaid"));
         assert!(output.contains("Container(api"));
         assert!(output.contains("## Component Index"));
-        assert!(output.contains("| [api](src/api/mod.rs) | container | -- | REST API gateway |"));
+        assert!(output.contains("| [api](#api) | container | -- | REST API gateway |"));
     }
 
     #[test]
@@ -274,6 +1270,7 @@ mod tests {
             target: "database".to_string(),
             label: "Persists data".to_string(),
             protocol: "sqlx".to_string(),
+            hidden: false,
         }];
 
         let docs = vec![api, make_container("database", "Data store")];
@@ -309,12 +1306,12 @@ mod tests {
     }
 
     #[test]
-    fn index_links_use_forward_slashes() {
+    fn source_links_use_forward_slashes() {
         let mut doc = make_container("api", "REST API");
         doc.source_file = "src\\api\\mod.rs".to_string();
         let output = generate(&[doc], test_root());
 
-        assert!(output.contains("[api](src/api/mod.rs)"));
+        assert!(output.contains("[src/api/mod.rs](src/api/mod.rs)"));
     }
 
     #[test]
@@ -324,8 +1321,483 @@ mod tests {
         let output = generate(&[lib, api], test_root());
 
         // _lib should NOT appear in component index
-        assert!(!output.contains("| [_lib]"));
+        assert!(!output.contains("[_lib](#"));
         // api should appear
-        assert!(output.contains("| [api]"));
+        assert!(output.contains("[api](#api)"));
+    }
+
+    #[test]
+    fn binary_crate_uses_main_as_root() {
+        let mut main = make_lib("# Tool\n\nCommand-line tool.");
+        main.module_path = "_main".to_string();
+        let api = make_container("api", "REST API");
+        let output = generate(&[main, api], test_root());
+
+        assert!(output.contains("Command-line tool."));
+        assert!(!output.contains("[_main](#"));
+    }
+
+    #[test]
+    fn main_is_indexed_when_lib_is_root() {
+        let lib = make_lib("# Root\n\nLibrary narrative.");
+        let mut main = make_lib("# Cli\n\nCli narrative.");
+        main.module_path = "_main".to_string();
+        main.description = "Binary entry".to_string();
+        let output = generate(&[lib, main], test_root());
+
+        assert!(output.contains("Library narrative."));
+        assert!(!output.contains("Cli narrative."));
+        assert!(output.contains("[_main](#_main)"));
+    }
+
+    #[test]
+    fn explicit_id_names_anchor_and_diagram_node() {
+        let mut api = make_container("api", "REST API");
+        api.id = Some("gateway".to_string());
+        let output = generate(&[api], test_root());
+
+        assert!(output.contains("| [api](#gateway) |"));
+        assert!(output.contains("<a id=\"gateway\"></a>\n\n### api\n"));
+        assert!(output.contains("Container(gateway, \"Api\""));
+    }
+
+    #[test]
+    fn contents_link_to_anchored_sections_that_are_present() {
+        let output = generate(&[make_container("api", "REST API")], test_root());
+
+        let contents = &output[output.find("## Contents").unwrap()..output.find("<a id=").unwrap()];
+        assert_eq!(
+            contents,
+            "## Contents\n\n\
+             - [System Diagram](#section-system-diagram)\n\
+             - [Component Index](#section-component-index)\n\
+             - [Elements](#section-elements)\n\n"
+        );
+        assert!(output.contains("<a id=\"section-component-index\"></a>\n\n## Component Index\n"));
+        assert!(!output.contains("section-glossary"));
+        assert!(!generate(&[], test_root()).contains("## Contents"));
+    }
+
+    #[test]
+    fn skipped_sections_leave_contents_and_index_links_consistent() {
+        let docs = vec![make_lib("# Root\n\nNarrative."), make_container("api", "REST API")];
+        let layout = ArchitectureConfig {
+            skip: ["narrative", "system_diagram", "elements"].iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+
+        let output = generate_document(&docs, test_root(), &[], &Snapshot::default(), Language::En, &layout, &DiagramConfig::default());
+
+        assert!(!output.contains("Narrative."));
+        assert!(!output.contains("## System Diagram") && !output.contains("section-system-diagram"));
+        assert!(!output.contains("## Elements"));
+        assert!(output.contains("- [Component Index](#section-component-index)\n\n"));
+        assert!(output.contains("| [api](src/api/mod.rs) | container |"));
+    }
+
+    #[test]
+    fn element_sections_link_dependencies_both_ways() {
+        let mut api = make_container("api", "REST API gateway");
+        api.relationships = vec![
+            Relationship {
+                target: "bus.calc".to_string(),
+                label: "Requests indicators".to_string(),
+                protocol: "channel".to_string(),
+                hidden: false,
+            },
+            Relationship {
+                target: "postgres".to_string(),
+                label: "Persists".to_string(),
+                protocol: String::new(),
+                hidden: false,
+            },
+        ];
+        let docs = vec![api, make_container("bus", "Bus"), make_component("bus", "calc", "Indicators")];
+
+        let output = generate(&docs, test_root());
+
+        assert!(output.contains(
+            "<a id=\"api\"></a>\n\n### api\n\nREST API gateway\n\n\
+             - **Level:** container\n\
+             - **Source:** [src/api/mod.rs](src/api/mod.rs)\n\
+             - **Uses:** [bus.calc](#bus_calc) — Requests indicators (channel); postgres — Persists\n\n"
+        ));
+        assert!(output.contains("### bus.calc\n\nIndicators\n\n- **Level:** component\n"));
+        assert!(output.contains("- **Used by:** [api](#api)\n"));
+    }
+
+    #[test]
+    fn single_element_matches_its_section_in_the_document() {
+        let mut api = make_container("api", "REST API gateway");
+        api.relationships = vec![
+            Relationship {
+                target: "bus.calc".to_string(),
+                label: "Requests indicators".to_string(),
+                protocol: "channel".to_string(),
+                hidden: false,
+            },
+            Relationship {
+                target: "postgres".to_string(),
+                label: "Persists".to_string(),
+                protocol: String::new(),
+                hidden: false,
+            },
+        ];
+        let docs = vec![api, make_container("bus", "Bus"), make_component("bus", "calc", "Indicators")];
+
+        let element = generate_element(&docs, "bus.calc", &document()).unwrap();
+        let (section, diagram) = element.split_once("```mermaid\n").unwrap();
+
+        assert!(generate(&docs, test_root()).contains(section));
+        assert!(diagram.starts_with("C4Component\n    title bus.calc\n\n"));
+        assert!(diagram.contains("    Component(bus_calc, \"bus.calc\", \"--\", \"Indicators\")\n"));
+        assert!(diagram.contains("    Container(api, \"api\", \"--\", \"REST API gateway\")\n"));
+        assert!(diagram.contains("    Rel(api, bus_calc, \"Requests indicators\", \"channel\")\n"));
+        assert!(!diagram.contains("postgres"), "only direct neighbours are drawn: {}", diagram);
+
+        let api = generate_element(&docs, "api", &document()).unwrap();
+        assert!(api.contains("    System_Ext(postgres, \"postgres\")\n"));
+        assert!(generate_element(&docs, "missing", &document()).is_none());
+    }
+
+    #[test]
+    fn element_sections_follow_the_document_language_and_diagram_settings() {
+        let mut api = make_container("api", "REST API gateway");
+        api.relationships.push(Relationship {
+            target: "bus".to_string(),
+            label: "Publishes".to_string(),
+            protocol: "channel".to_string(),
+            hidden: false,
+        });
+        let docs = vec![api, make_container("bus", "Bus")];
+        let mut document = document();
+        document.language = Language::De;
+        document.diagrams.edge_styles.insert(
+            "channel".to_string(),
+            archidoc_types::EdgeStyle { color: Some("green".to_string()), line: archidoc_types::LineStyle::Solid },
+        );
+
+        let element = generate_element(&docs, "bus", &document).unwrap();
+
+        assert!(element.contains("Genutzt von"), "{}", element);
+        assert!(element.contains("    UpdateRelStyle(api, bus, $textColor=\"green\", $lineColor=\"green\")\n"), "{}", element);
+    }
+
+    #[test]
+    fn index_escapes_pipes_in_cells() {
+        let doc = make_container("api", "Routes a | b traffic");
+        let output = generate(&[doc], test_root());
+
+        assert!(output.contains("| Routes a \\| b traffic |"));
+    }
+
+    #[test]
+    fn index_order_groups_by_container_health_or_owner() {
+        let file = |health| archidoc_types::FileEntry {
+            name: "lib.rs".to_string(),
+            pattern: "--".to_string(),
+            pattern_status: PatternStatus::Planned,
+            purpose: "Entry".to_string(),
+            health,
+            loc: None,
+        };
+        let mut api = make_container("api", "API");
+        api.files.push(file(archidoc_types::HealthStatus::Stable));
+        api.group = Some("team:web".to_string());
+        let mut bus = make_container("bus", "Bus");
+        bus.files.push(file(archidoc_types::HealthStatus::Planned));
+        bus.group = Some("team:core".to_string());
+        let lanes = make_component("bus", "lanes", "Lanes");
+        let docs = vec![api, make_component("api", "routes", "Routes"), bus, lanes];
+        let order = |order| -> Vec<&str> {
+            index_modules(&docs, order).iter().map(|d| d.module_path.as_str()).collect()
+        };
+
+        assert_eq!(order(IndexOrder::Alphabetical), ["api", "api.routes", "bus", "bus.lanes"]);
+        assert_eq!(order(IndexOrder::Container), ["api", "api.routes", "bus", "bus.lanes"]);
+        assert_eq!(order(IndexOrder::Health), ["bus", "api", "api.routes", "bus.lanes"]);
+        assert_eq!(order(IndexOrder::Owner), ["bus", "bus.lanes", "api", "api.routes"]);
+    }
+
+    #[test]
+    fn risks_produce_register_section() {
+        let mut bus = make_container("bus", "Messaging");
+        bus.risks.push(archidoc_types::Risk {
+            description: "Single point of failure".to_string(),
+            severity: archidoc_types::RiskSeverity::High,
+        });
+
+        let output = generate(&[bus], test_root());
+        assert!(output.contains("## Risk Register"));
+        assert!(output.contains("| high | bus | Single point of failure |"));
+        assert!(!generate(&[make_container("api", "API")], test_root()).contains("## Risk Register"));
+    }
+
+    #[test]
+    fn fitness_section_lists_failing_rules_first() {
+        let rule = |name: &str, checked, failed| FitnessSummary { name: name.to_string(), checked, failed };
+        let fitness = [rule("all_strategy_modules_define_a_trait", 2, 0), rule("no_cross_container", 5, 2)];
+
+        let output = generate_with_fitness(&[make_container("api", "API")], test_root(), &fitness);
+
+        let section = &output[output.find("## Architectural Fitness").unwrap()..];
+        assert!(section.starts_with("## Architectural Fitness\n\n1 of 2 rules pass.\n"));
+        let failing = section.find("| `no_cross_container` | FAIL | 5 | 2 |").unwrap();
+        let passing = section.find("| `all_strategy_modules_define_a_trait` | pass | 2 | 0 |").unwrap();
+        assert!(failing < passing);
+        assert!(!generate(&[make_container("api", "API")], test_root()).contains("## Architectural Fitness"));
+    }
+
+    #[test]
+    fn snapshot_sections_show_maturity_shares_and_findings() {
+        let docs = [make_container("api", "API")];
+        let health = HealthReport {
+            total_elements: 1,
+            container_count: 1,
+            total_files: 4,
+            files_planned: 1,
+            files_stable: 3,
+            patterns_total: 2,
+            patterns_verified: 1,
+            patterns_planned: 1,
+            reviews_unreviewed: 1,
+            ..Default::default()
+        };
+        let mut validation = ValidationReport::default();
+        validation.ghosts.push(archidoc_types::GhostEntry {
+            element: "api".to_string(),
+            filename: "gone.rs".to_string(),
+            source_dir: "/abs/src/api".to_string(),
+        });
+        validation.orphans.push(archidoc_types::OrphanEntry {
+            element: "api".to_string(),
+            filename: "extra.rs".to_string(),
+            source_dir: "/abs/src/api".to_string(),
+        });
+        let snapshot = Snapshot { health: Some(health), validation: Some(validation), archived: None };
+
+        let output = generate_document(
+            &docs,
+            test_root(),
+            &[],
+            &snapshot,
+            Language::En,
+            &ArchitectureConfig::default(),
+            &DiagramConfig::default(),
+        );
+
+        assert!(output.contains("- [Project Health](#section-health)\n- [Validation Findings](#section-validation)\n"));
+        let health = &output[output.find("## Project Health").unwrap()..output.find("## Validation Findings").unwrap()];
+        assert!(health.contains("| planned | 1 | 25.0% |\n| active | 0 | 0.0% |\n| stable | 3 | 75.0% |\n"));
+        assert!(health.contains("Patterns: 2 assigned — verified 1 (50.0%), planned 1 (50.0%)\n"));
+        let findings = &output[output.find("## Validation Findings").unwrap()..];
+        assert!(findings.contains("| ghost | api | `gone.rs` | error |\n| orphan | api | `extra.rs` | warning |\n"));
+        assert!(!findings.contains("/abs/src/api"));
+        assert!(!generate(&docs, test_root()).contains("## Project Health"));
+    }
+
+    #[test]
+    fn a_clean_validation_is_reported_as_such() {
+        let snapshot = Snapshot { health: None, validation: Some(ValidationReport::default()), archived: None };
+
+        let output = generate_document(
+            &[make_container("api", "API")],
+            test_root(),
+            &[],
+            &snapshot,
+            Language::En,
+            &ArchitectureConfig::default(),
+            &DiagramConfig::default(),
+        );
+
+        assert!(output.contains("## Validation Findings\n\nNo ghost or orphan files and no invalid doc links.\n"));
+        assert!(!output.contains("## Project Health"));
+    }
+
+    #[test]
+    fn archived_elements_form_the_last_appendix() {
+        let archived = |path: &str, removed: Option<&str>| ArchivedElement {
+            module_path: path.to_string(),
+            c4_level: ElementKind::Container,
+            description: "Old | gone".to_string(),
+            last_seen: "2026-01-10".to_string(),
+            removed: removed.map(str::to_string),
+        };
+        let snapshot = Snapshot {
+            archived: Some(vec![archived("bus", Some("2026-02-01")), archived("cache", None)]),
+            ..Default::default()
+        };
+        let document = |snapshot: &Snapshot| {
+            generate_document(
+                &[make_container("api", "API")],
+                test_root(),
+                &[],
+                snapshot,
+                Language::En,
+                &ArchitectureConfig::default(),
+                &DiagramConfig::default(),
+            )
+        };
+
+        let output = document(&snapshot);
+        let appendix = &output[output.find("<a id=\"section-archived\"></a>\n\n## Archived Elements\n\n").unwrap()..];
+        assert!(appendix.contains("| bus | container | Old \\| gone | 2026-01-10 | 2026-02-01 |\n| cache | container | Old \\| gone | 2026-01-10 | -- |\n\n---\n"));
+        assert!(!document(&Snapshot { archived: Some(vec![]), ..Default::default() }).contains("## Archived Elements"));
+    }
+
+    #[test]
+    fn terms_produce_alphabetized_glossary() {
+        let term = |name: &str, definition: &str| archidoc_types::Term {
+            name: name.to_string(),
+            definition: definition.to_string(),
+        };
+        let mut bus = make_container("bus", "Messaging");
+        bus.terms.push(term("lane", "A typed channel"));
+        let mut api = make_container("api", "API");
+        api.terms.push(term("Gateway", "Public entry point"));
+        api.terms.push(term("lane", "A typed channel"));
+
+        let output = generate(&[bus, api], test_root());
+        let glossary = &output[output.find("## Glossary").unwrap()..];
+        assert!(glossary.find("**Gateway**").unwrap() < glossary.find("**lane**").unwrap());
+        assert!(glossary.contains("- **lane** — A typed channel ([bus](src/bus/mod.rs), [api](src/api/mod.rs))"));
+    }
+
+    #[test]
+    fn patterns_appendix_lists_modules_and_files() {
+        let mut bus = make_container("bus", "Messaging");
+        bus.pattern = "Observer".to_string();
+        bus.files.push(archidoc_types::FileEntry {
+            name: "lanes.rs".to_string(),
+            pattern: "Observer".to_string(),
+            pattern_status: PatternStatus::Verified,
+            purpose: "Event routing".to_string(),
+            health: archidoc_types::HealthStatus::Active,
+            loc: None,
+        });
+        bus.files.push(archidoc_types::FileEntry {
+            name: "util.rs".to_string(),
+            pattern: "--".to_string(),
+            pattern_status: PatternStatus::Planned,
+            purpose: "Helpers".to_string(),
+            health: archidoc_types::HealthStatus::Active,
+            loc: None,
+        });
+
+        let output = generate(&[bus, make_container("api", "API")], test_root());
+        let appendix = &output[output.find("## Patterns in Use").unwrap()..];
+        assert!(appendix.contains("| Observer | [bus/lanes.rs](src/bus/lanes.rs) | verified |\n| Observer | [bus](src/bus/mod.rs) | planned |"));
+        assert!(!appendix.contains("util.rs"));
+        assert!(!appendix.contains("[api]"));
+    }
+
+    #[test]
+    fn doc_links_produce_links_section() {
+        let link = |url: &str, title: &str| archidoc_types::DocLink {
+            url: url.to_string(),
+            title: title.to_string(),
+        };
+        let mut bus = make_container("bus", "Messaging");
+        bus.links.push(link("https://wiki.example.com/bus", "Runbook"));
+        bus.links.push(link("https://grafana.example.com/d/bus", ""));
+        bus.links.push(link("wiki/bus", "Broken"));
+
+        let output = generate(&[bus], test_root());
+        assert!(output.contains("- **bus**: [Runbook](https://wiki.example.com/bus), [https://grafana.example.com/d/bus](https://grafana.example.com/d/bus)\n"));
+        assert!(!output.contains("Broken"));
+    }
+
+    #[test]
+    fn optional_elements_are_marked() {
+        let mut gpu = make_container("gpu", "GPU kernels");
+        gpu.condition = Some("feature: gpu".to_string());
+
+        let output = generate(&[gpu, make_container("bus", "Messaging")], test_root());
+        assert!(output.contains("[gpu](#gpu) | container (feature: gpu) |"));
+        assert!(output.contains("Container(gpu, \"Gpu [feature: gpu]\""));
+        assert!(output.contains("UpdateElementStyle(gpu, "));
+        assert!(!output.contains("UpdateElementStyle(bus, "));
+    }
+
+    #[test]
+    fn edge_styles_color_arrows_by_protocol() {
+        let mut api = make_container("api", "Gateway");
+        api.relationships.push(Relationship {
+            target: "bus".to_string(),
+            label: "Publishes".to_string(),
+            protocol: "Channel".to_string(),
+            hidden: false,
+        });
+        let docs = [api, make_container("bus", "Messaging")];
+        let mut diagrams = DiagramConfig::default();
+        diagrams.edge_styles.insert(
+            "channel".to_string(),
+            archidoc_types::EdgeStyle {
+                color: Some("green".to_string()),
+                line: archidoc_types::LineStyle::Dashed,
+            },
+        );
+
+        let output = generate_document(&docs, test_root(), &[], &Snapshot::default(), Language::En, &ArchitectureConfig::default(), &diagrams);
+        assert!(output.contains(
+            "    Rel(api, bus, \"Publishes\", \"Channel\")\n    UpdateRelStyle(api, bus, $textColor=\"green\", $lineColor=\"green\")\n"
+        ));
+        assert_eq!(
+            crate::mermaid::edge_style_warnings(&diagrams),
+            vec!["[diagrams.edge_styles] channel: Mermaid C4 diagrams cannot draw dashed lines and use only the color; PlantUML, D2, and draw.io draw them"]
+        );
+    }
+
+    #[test]
+    fn scenarios_carry_a_sequence_diagram() {
+        let mut api = make_container("api", "Gateway");
+        api.relationships.push(Relationship {
+            target: "payments".to_string(),
+            label: "Charges card".to_string(),
+            protocol: "HTTPS".to_string(),
+            hidden: false,
+        });
+        let mut customer = make_container("customer", "Shopper");
+        customer.c4_level = ElementKind::Person;
+        customer.flows.push(archidoc_types::Flow {
+            name: "checkout".to_string(),
+            steps: vec!["customer".to_string(), "api".to_string(), "payments".to_string()],
+        });
+        let docs = [api, customer, make_container("payments", "Card payments")];
+
+        let output = generate_document(&docs, test_root(), &[], &Snapshot::default(), Language::En, &ArchitectureConfig::default(), &DiagramConfig::default());
+        assert!(output.contains(
+            "2. api -> payments: \"Charges card\" (HTTPS)\n\n```mermaid\nsequenceDiagram\n    title checkout\n    actor customer as customer\n    participant api as api\n    participant payments as payments\n    customer->>api: 1.\n    api->>payments: 2. Charges card (HTTPS)\n```\n"
+        ), "{}", output);
+    }
+
+    #[test]
+    fn generated_system_context_replaces_the_hand_written_one() {
+        let lib = make_lib("@c4 container\n\n# Shop\n\nSells things.\n\n## C4 Context\n\n```mermaid\nC4Context\n    Person(user, \"User\")\n```\n\n## Data Flow\n\nOrders go in.\n");
+        let mut customer = make_container("customer", "Shopper");
+        customer.c4_level = ElementKind::Person;
+        customer.relationships.push(Relationship {
+            target: "api".to_string(),
+            label: "Browses".to_string(),
+            protocol: "HTTPS".to_string(),
+            hidden: false,
+        });
+        let docs = [lib, make_container("api", "Gateway"), customer];
+
+        let output = generate_document(&docs, test_root(), &[], &Snapshot::default(), Language::En, &ArchitectureConfig::default(), &DiagramConfig::default());
+        assert!(output.contains("# Shop\n\nSells things.\n\n## Data Flow\n\nOrders go in.\n"), "{}", output);
+        assert!(output.contains(
+            "<a id=\"section-system-context\"></a>\n\n## System Context\n\n```mermaid\nC4Context\n    title System Context Diagram\n\n    Person(customer, \"Customer\", \"Shopper\")\n    System(sys, \"System\", \"Root module\")\n\n    Rel(customer, sys, \"Browses\", \"HTTPS\")\n\n    UpdateLayoutConfig("
+        ), "{}", output);
+        assert!(output.contains("- [System Context](#section-system-context)\n"));
+        assert!(!output.contains("Person(user"));
+
+        let layout = ArchitectureConfig {
+            skip: vec!["system_context".to_string()],
+            ..Default::default()
+        };
+        let output = generate_document(&docs, test_root(), &[], &Snapshot::default(), Language::En, &layout, &DiagramConfig::default());
+        assert!(output.contains("## C4 Context\n\n```mermaid\nC4Context\n    Person(user, \"User\")\n```\n"), "{}", output);
+        assert!(!output.contains("## System Context"));
     }
 }
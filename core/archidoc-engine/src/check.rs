@@ -3,37 +3,75 @@ use std::path::Path;
 
 use archidoc_types::{DriftReport, DriftedFile, ModuleDoc};
 
+use crate::generator::{Generator, OutputSettings};
+
 /// Check for documentation drift against a single ARCHITECTURE.md file.
 ///
 /// Generates the expected content in memory and compares it to the
 /// existing file on disk. Returns a report of differences.
 pub fn check_drift(docs: &[ModuleDoc], architecture_file: &Path, root: &Path) -> DriftReport {
-    let expected = crate::architecture::generate(docs, root);
+    let generators = crate::generator::builtins(&["architecture"], &OutputSettings::new(architecture_file, root));
+    check_artifacts(docs, architecture_file.parent().unwrap_or(root), &generators)
+        .expect("the ARCHITECTURE.md generator cannot fail")
+}
 
+/// Check for drift in the artifacts of each of the given generators.
+///
+/// Artifacts are expected where [`crate::compile::write_outputs`] writes
+/// them, under `out_dir`; paths in the report are relative to it. Fails
+/// when a generator does.
+pub fn check_artifacts(
+    docs: &[ModuleDoc],
+    out_dir: &Path,
+    generators: &[Box<dyn Generator>],
+) -> Result<DriftReport, String> {
     let mut report = DriftReport::default();
 
-    if !architecture_file.exists() {
-        report
-            .missing_files
-            .push("ARCHITECTURE.md".to_string());
-        return report;
+    for generator in generators {
+        for artifact in generator.generate(docs)? {
+            let path = out_dir.join(&artifact.path);
+            compare(&mut report, artifact.path, &path, &artifact.content);
+        }
     }
 
-    let actual = fs::read_to_string(architecture_file).unwrap_or_default();
+    Ok(report)
+}
 
-    if expected != actual {
+fn compare(report: &mut DriftReport, name: String, path: &Path, expected: &str) {
+    if !path.exists() {
+        report.missing_files.push(name);
+        return;
+    }
+
+    let actual = fs::read_to_string(path).unwrap_or_default();
+
+    // Front matter and headers record when and how a page was generated;
+    // that alone is not drift
+    let same = expected == actual || normalize(expected) == normalize(&actual);
+    if !same {
+        let diff = crate::line_diff::unified_diff(
+            &actual,
+            expected,
+            &name,
+            &format!("{} (generated)", name),
+            crate::line_diff::CONTEXT_LINES,
+        );
         report.drifted_files.push(DriftedFile {
-            path: "ARCHITECTURE.md".to_string(),
+            path: name,
             expected_lines: expected.lines().count(),
             actual_lines: actual.lines().count(),
+            diff,
         });
     }
+}
 
-    report
+fn normalize(text: &str) -> String {
+    crate::front_matter::without_timestamp(&crate::header::strip(text))
 }
 
-/// Format a drift report as human-readable text.
-pub fn format_drift_report(report: &DriftReport) -> String {
+/// Format a drift report as human-readable text. With `show_diff`, each
+/// changed file is followed by its unified diff.
+pub fn format_drift_report(report: &DriftReport, show_diff: bool) -> String {
     let mut out = String::new();
 
     if !report.has_drift() {
@@ -52,6 +90,15 @@ pub fn format_drift_report(report: &DriftReport) -> String {
             out.push_str(&format!("  {} (expected {} lines, got {})\n",
                 file.path, file.expected_lines, file.actual_lines));
         }
+        if show_diff {
+            for file in report.drifted_files.iter().filter(|f| !f.diff.is_empty()) {
+                out.push('\n');
+                out.push_str(&file.diff);
+            }
+            if !report.missing_files.is_empty() || !report.extra_files.is_empty() {
+                out.push('\n');
+            }
+        }
     }
 
     if !report.missing_files.is_empty() {
@@ -77,3 +124,127 @@ pub fn format_drift_report(report: &DriftReport) -> String {
     out.push_str("\nRun `archidoc` to regenerate.\n");
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::ElementKind;
+
+    fn doc() -> ModuleDoc {
+        ModuleDoc {
+            module_path: "bus".to_string(),
+            content: "Event bus".to_string(),
+            source_file: "src/bus/mod.rs".to_string(),
+            c4_level: ElementKind::Container,
+            pattern: "Mediator".to_string(),
+            description: "Event bus".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn generators(ids: &[&str], arch: &Path) -> Vec<Box<dyn Generator>> {
+        crate::generator::builtins(ids, &OutputSettings::new(arch, arch.parent().unwrap()))
+    }
+
+    #[test]
+    fn only_requested_artifacts_are_checked() {
+        let tmp = tempfile::tempdir().unwrap();
+        let docs = vec![doc()];
+        let arch = tmp.path().join("ARCHITECTURE.md");
+        fs::write(&arch, crate::architecture::generate(&docs, tmp.path())).unwrap();
+
+        let report = check_artifacts(&docs, tmp.path(), &generators(&["architecture"], &arch)).unwrap();
+        assert!(!report.has_drift());
+
+        let report = check_artifacts(&docs, tmp.path(), &generators(crate::generator::BUILTIN, &arch)).unwrap();
+        assert_eq!(
+            report.missing_files,
+            vec![
+                "ARCHITECTURE.ai.md",
+                "mermaid/c4-container.md",
+                "mermaid/c4-component.md",
+                "c4/c4-container.puml",
+                "c4/c4-component.puml",
+                "drawio/c4-container.csv",
+                "drawio/c4-component.csv",
+                "d2/c4-container.d2",
+                "d2/c4-component.d2",
+                "structurizr/workspace.dsl",
+                "ARCHITECTURE.html",
+                "confluence/index.xml",
+                "confluence/containers/bus.xml",
+                "confluence/pages.json",
+                "site/mkdocs.yml",
+                "site/docs/index.md",
+                "site/docs/containers/bus.md",
+                "site/architecture/_category_.json",
+                "site/architecture/index.md",
+                "site/architecture/bus.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn stale_sidecar_output_is_drift() {
+        let tmp = tempfile::tempdir().unwrap();
+        let docs = vec![doc()];
+        let arch = tmp.path().join("ARCHITECTURE.md");
+        fs::create_dir_all(tmp.path().join("c4")).unwrap();
+        fs::write(tmp.path().join("c4").join(crate::plantuml::CONTAINER_FILE), "@startuml\n@enduml\n").unwrap();
+        crate::plantuml::generate_component(&tmp.path().join("c4"), &docs);
+
+        let report = check_artifacts(&docs, tmp.path(), &generators(&["plantuml"], &arch)).unwrap();
+
+        assert!(report.missing_files.is_empty());
+        assert_eq!(report.drifted_files.len(), 1);
+        assert_eq!(report.drifted_files[0].path, "c4/c4-container.puml");
+        assert!(report.drifted_files[0].diff.starts_with("--- c4/c4-container.puml\n+++ c4/c4-container.puml (generated)\n"));
+
+        let text = format_drift_report(&report, true);
+        assert!(text.contains("+@startuml c4-container"));
+        assert!(!format_drift_report(&report, false).contains("@@"));
+    }
+
+    #[test]
+    fn front_matter_timestamps_are_not_drift() {
+        let tmp = tempfile::tempdir().unwrap();
+        let docs = vec![doc()];
+        let arch = tmp.path().join("ARCHITECTURE.md");
+        let with_front_matter = |at: &str| {
+            let mut settings = OutputSettings::new(&arch, tmp.path());
+            settings.front_matter = Some(crate::front_matter::FrontMatterSettings {
+                weight: 1,
+                generated_at: Some(at.to_string()),
+            });
+            crate::generator::builtins(&["architecture"], &settings)
+        };
+        let written = with_front_matter("2026-10-01T09:30:00Z")[0].generate(&docs).unwrap();
+        fs::write(&arch, &written[0].content).unwrap();
+
+        let report = check_artifacts(&docs, tmp.path(), &with_front_matter("2026-10-02T11:00:00Z")).unwrap();
+        assert!(!report.has_drift());
+        assert!(written[0].content.starts_with("---\ntitle: \"Architecture Context\"\n"), "{}", written[0].content);
+
+        let report = check_artifacts(&docs, tmp.path(), &generators(&["architecture"], &arch)).unwrap();
+        assert_eq!(report.drifted_files.len(), 1);
+    }
+
+    #[test]
+    fn header_comments_are_not_drift() {
+        let tmp = tempfile::tempdir().unwrap();
+        let docs = vec![doc()];
+        let arch = tmp.path().join("ARCHITECTURE.md");
+        let with_header = |version: &str| {
+            let settings = crate::header::HeaderSettings {
+                version: version.to_string(),
+                generated_at: None,
+                command: "archidoc".to_string(),
+            };
+            crate::header::wrap(generators(&["architecture", "plantuml"], &arch), &settings)
+        };
+        crate::compile::write_outputs(&docs, tmp.path(), &with_header("0.4.0")).unwrap();
+
+        assert!(!check_artifacts(&docs, tmp.path(), &with_header("0.5.0")).unwrap().has_drift());
+        assert!(!check_artifacts(&docs, tmp.path(), &generators(&["architecture"], &arch)).unwrap().has_drift());
+    }
+}
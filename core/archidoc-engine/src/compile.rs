@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use archidoc_types::{CompileReport, ModuleDoc, OutputFile, PhaseTiming};
+
+use crate::generator::Generator;
+
+/// Run `f`, recording its wall-clock time in `report` under `phase`.
+pub fn timed<T>(report: &mut CompileReport, phase: &str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let value = f();
+    report.phases.push(PhaseTiming {
+        phase: phase.to_string(),
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+    });
+    value
+}
+
+/// Run `generators` and write their artifacts under `out_dir`, the
+/// directory holding ARCHITECTURE.md. Returns every file written, in
+/// generator order.
+pub fn write_outputs(
+    docs: &[ModuleDoc],
+    out_dir: &Path,
+    generators: &[Box<dyn Generator>],
+) -> Result<Vec<OutputFile>, String> {
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+    for generator in generators {
+        for artifact in generator.generate(docs)? {
+            files.push((out_dir.join(&artifact.path), artifact.content));
+        }
+    }
+
+    files
+        .into_iter()
+        .map(|(path, content)| {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("failed to create {}: {}", parent.display(), e))?;
+            }
+            fs::write(&path, &content).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+            Ok(OutputFile {
+                path: path.to_string_lossy().to_string(),
+                bytes: content.len(),
+                hash: crate::hash::bytes_hash(content.as_bytes()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_enabled_outputs_with_hashes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let arch = tmp.path().join("docs").join("ARCHITECTURE.md");
+        let generators = crate::generator::builtins(
+            &["architecture", "ai_context", "plantuml"],
+            &crate::generator::OutputSettings::new(&arch, tmp.path()),
+        );
+
+        let outputs = write_outputs(&[], &tmp.path().join("docs"), &generators).unwrap();
+
+        let names: Vec<String> = outputs
+            .iter()
+            .map(|o| Path::new(&o.path).strip_prefix(tmp.path()).unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "docs/ARCHITECTURE.md",
+                "docs/ARCHITECTURE.ai.md",
+                "docs/c4/c4-container.puml",
+                "docs/c4/c4-component.puml",
+            ]
+        );
+        let written = fs::read(&arch).unwrap();
+        assert_eq!(outputs[0].bytes, written.len());
+        assert_eq!(outputs[0].hash, crate::hash::bytes_hash(&written));
+    }
+}
@@ -0,0 +1,326 @@
+use std::fs;
+use std::path::Path;
+
+use archidoc_types::ProjectConfig;
+
+use crate::{architecture, generator, rewrite, variables};
+
+/// Project configuration file, read from the project root.
+pub const CONFIG_FILE: &str = "archidoc.toml";
+
+/// Load `archidoc.toml` from `root`. A missing file yields the defaults.
+pub fn load(root: &Path) -> Result<ProjectConfig, String> {
+    let path = root.join(CONFIG_FILE);
+    if !path.exists() {
+        return Ok(ProjectConfig::default());
+    }
+
+    let text = fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    parse(&text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Parse and validate configuration text.
+pub fn parse(text: &str) -> Result<ProjectConfig, String> {
+    let config: ProjectConfig = toml::from_str(text).map_err(|e| e.message().to_string())?;
+
+    for (name, plugin) in &config.generator {
+        if generator::BUILTIN.contains(&name.as_str()) {
+            return Err(format!("[generator.{}] clashes with the built-in generator of that name", name));
+        }
+        if plugin.command.trim().is_empty() {
+            return Err(format!("[generator.{}] command is empty", name));
+        }
+    }
+
+    for (name, hook) in &config.hooks {
+        if !generator::BUILTIN.contains(&name.as_str()) && !config.generator.contains_key(name) {
+            return Err(format!(
+                "[hooks.{}] names no generator (expected one of: {}, or a [generator.<name>])",
+                name,
+                generator::BUILTIN.join(", ")
+            ));
+        }
+        if hook.command.trim().is_empty() {
+            return Err(format!("[hooks.{}] command is empty", name));
+        }
+    }
+
+    // External generators always run, but can be left out of --check
+    for (section, names, plugins_allowed) in [
+        ("[generators] enable", &config.generators.enable, false),
+        ("[check] skip", &config.check.skip, true),
+    ] {
+        let known = |name: &str| {
+            generator::BUILTIN.contains(&name) || (plugins_allowed && config.generator.contains_key(name))
+        };
+        if let Some(name) = names.iter().find(|n| !known(n)) {
+            return Err(format!(
+                "unknown generator '{}' in {} (expected one of: {})",
+                name,
+                section,
+                generator::BUILTIN.join(", ")
+            ));
+        }
+    }
+
+    if let Some(id) = config.architecture.skip.iter().find(|id| !architecture::SECTIONS.contains(&id.as_str())) {
+        return Err(format!(
+            "unknown section '{}' in [architecture] skip (expected one of: {})",
+            id,
+            architecture::SECTIONS.join(", ")
+        ));
+    }
+
+    let heuristics = &config.heuristics;
+    if heuristics.facade_min_pub_mods == 0 || heuristics.builder_min_setters == 0 {
+        return Err("[heuristics] facade_min_pub_mods and builder_min_setters must be at least 1".to_string());
+    }
+    if heuristics.adapter_min_fields > heuristics.adapter_max_fields {
+        return Err(format!(
+            "[heuristics] adapter_min_fields ({}) is greater than adapter_max_fields ({})",
+            heuristics.adapter_min_fields, heuristics.adapter_max_fields
+        ));
+    }
+
+    for entry in &config.fitness.allow_cross_container {
+        let valid = match entry.split_once("->") {
+            Some((from, to)) => is_module_path(from) && is_module_path(to),
+            None => is_module_path(entry),
+        };
+        if !valid {
+            return Err(format!(
+                "invalid [fitness] allow_cross_container entry '{}' (expected \"from -> to\" or a module path)",
+                entry
+            ));
+        }
+    }
+
+    let protocols = &config.protocols;
+    if !protocols.allowed.is_empty() {
+        let known = |name: &str| protocols.allowed.iter().any(|p| p.eq_ignore_ascii_case(name));
+        if let Some((alias, target)) = protocols.aliases.iter().find(|(_, target)| !known(target)) {
+            return Err(format!(
+                "[protocols] alias '{}' points to '{}', which is not in allowed",
+                alias, target
+            ));
+        }
+    }
+
+    let rewrite = &config.rewrite;
+    for (kind, entries) in [("rename", &rewrite.rename), ("collapse", &rewrite.collapse)] {
+        if let Some(entry) = entries.iter().find(|e| rewrite::parse_rule(e).is_none()) {
+            return Err(format!(
+                "invalid [rewrite] {} rule '{}' (expected \"from -> to\")",
+                kind, entry
+            ));
+        }
+    }
+
+    if let Some(name) = config.variables.keys().find(|name| !variables::is_variable_name(name)) {
+        return Err(format!(
+            "invalid [variables] name '{}' (expected letters, digits, and _)",
+            name
+        ));
+    }
+
+    Ok(config)
+}
+
+fn is_module_path(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty() && !text.contains(char::is_whitespace)
+}
+
+/// Ids of the generators a run writes: ARCHITECTURE.md, each generator
+/// switched on in `[generators]`, and those listed in `enable`, in
+/// [`generator::BUILTIN`] order.
+pub fn enabled_generators(config: &ProjectConfig) -> Vec<&'static str> {
+    let generators = &config.generators;
+    generator::BUILTIN
+        .iter()
+        .copied()
+        .filter(|id| match *id {
+            "architecture" => true,
+            "ai_context" if generators.ai_context => true,
+            "plantuml" if generators.plantuml => true,
+            "drawio" if generators.drawio => true,
+            "d2" if generators.d2 => true,
+            "structurizr" if generators.structurizr => true,
+            "html" if generators.html => true,
+            "confluence" if generators.confluence => true,
+            "mkdocs" if generators.mkdocs => true,
+            "docusaurus" if generators.docusaurus => true,
+            _ => generators.enable.iter().any(|e| e == id),
+        })
+        .collect()
+}
+
+/// Generators `--check` compares: every enabled generator minus anything
+/// listed in `[check] skip`.
+pub fn checked_generators(config: &ProjectConfig) -> Vec<&'static str> {
+    enabled_generators(config)
+        .into_iter()
+        .filter(|id| !config.check.skip.iter().any(|s| s == id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{IndexOrder, Language, LineStyle, RenderFormat};
+
+    #[test]
+    fn defaults_check_architecture_and_ai_context() {
+        let config = parse("").unwrap();
+
+        assert_eq!(checked_generators(&config), vec!["architecture", "ai_context"]);
+    }
+
+    #[test]
+    fn enabled_generators_are_checked_unless_skipped() {
+        let config = parse(
+            "[generators]\nplantuml = true\ndrawio = true\n\n[check]\nskip = [\"drawio\", \"ai_context\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(checked_generators(&config), vec!["architecture", "plantuml"]);
+    }
+
+    #[test]
+    fn generators_can_be_enabled_by_id() {
+        let config = parse("[generators]\nenable = [\"drawio\", \"mermaid\"]\nai_context = false\n").unwrap();
+
+        assert_eq!(enabled_generators(&config), vec!["architecture", "mermaid", "drawio"]);
+    }
+
+    #[test]
+    fn external_generators_are_declared_by_name() {
+        let config = parse(
+            "[generator.backstage]\ncommand = \"./gen-backstage\"\nargs = [\"--owner\", \"platform\"]\n\n[check]\nskip = [\"backstage\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.generator["backstage"].args, vec!["--owner", "platform"]);
+        assert!(parse("[generator.mermaid]\ncommand = \"gen\"\n").unwrap_err().contains("clashes"));
+        assert!(parse("[generator.x]\ncommand = \" \"\n").unwrap_err().contains("empty"));
+        assert!(parse("[generator.x]\ncmd = \"gen\"\n").is_err());
+        assert!(parse("[hooks.architecture]\ncommand = \"./translate\"\n\n[hooks.backstage]\ncommand = \"cat\"\n\n[generator.backstage]\ncommand = \"gen\"\n").is_ok());
+        assert!(parse("[hooks.svg]\ncommand = \"cat\"\n").unwrap_err().contains("names no generator"));
+        assert!(parse("[hooks.mermaid]\ncommand = \"\"\n").unwrap_err().contains("empty"));
+    }
+
+    #[test]
+    fn rejects_unknown_names() {
+        assert!(parse("[check]\nskip = [\"svg\"]\n").unwrap_err().contains("unknown generator"));
+        assert!(parse("[generators]\nenable = [\"svg\"]\n").unwrap_err().contains("[generators] enable"));
+        assert!(parse("[generators]\nsvg = true\n").is_err());
+        assert!(parse("[c4]\nlevel = \"container\"\n").is_ok());
+    }
+
+    #[test]
+    fn edge_styles_are_keyed_by_protocol() {
+        let config = parse("[diagrams.edge_styles]\nhttp = { color = \"blue\" }\nchannel = { color = \"green\", line = \"dashed\" }\n").unwrap();
+
+        let style = config.diagrams.edge_style("HTTP").unwrap();
+        assert_eq!(style.color.as_deref(), Some("blue"));
+        assert_eq!(style.line, LineStyle::Solid);
+        assert_eq!(config.diagrams.edge_style("channel").unwrap().line, LineStyle::Dashed);
+        assert!(config.diagrams.edge_style("").is_none());
+        assert!(parse("[diagrams.edge_styles]\nhttp = { line = \"wavy\" }\n").is_err());
+    }
+
+    #[test]
+    fn render_formats_and_renderer_commands() {
+        let config = parse("[render]\nformats = [\"svg\", \"png\"]\nplantuml = { command = \"java\", args = [\"-jar\", \"plantuml.jar\"] }\n").unwrap();
+
+        assert_eq!(config.render.formats, [RenderFormat::Svg, RenderFormat::Png]);
+        assert_eq!(config.render.mermaid.command, "mmdc");
+        assert_eq!(config.render.plantuml.args, ["-jar", "plantuml.jar"]);
+        assert!(parse("[render]\nformats = [\"pdf\"]\n").is_err());
+    }
+
+    #[test]
+    fn heuristic_thresholds_default_and_validate() {
+        let config = parse("[heuristics]\nfacade_min_pub_mods = 3\n").unwrap();
+
+        assert_eq!(config.heuristics.facade_min_pub_mods, 3);
+        assert_eq!(config.heuristics.adapter_max_fields, 2);
+        assert!(parse("[heuristics]\nadapter_min_fields = 3\n").unwrap_err().contains("adapter_max_fields"));
+        assert!(parse("[heuristics]\nbuilder_min_setters = 0\n").is_err());
+        assert!(parse("[heuristics]\nfacade_pub_uses = 1\n").is_err());
+    }
+
+    #[test]
+    fn architecture_sections_are_validated() {
+        let config = parse("[architecture]\nskip = [\"elements\", \"metrics\"]\n").unwrap();
+
+        assert_eq!(config.architecture.skip, vec!["elements", "metrics"]);
+        let err = parse("[architecture]\nskip = [\"timeline\"]\n").unwrap_err();
+        assert!(err.starts_with("unknown section 'timeline' in [architecture] skip"), "{}", err);
+    }
+
+    #[test]
+    fn index_order_defaults_to_alphabetical() {
+        assert_eq!(parse("").unwrap().architecture.index_order, IndexOrder::Alphabetical);
+        let config = parse("[architecture]\nindex_order = \"health\"\n").unwrap();
+        assert_eq!(config.architecture.index_order, IndexOrder::Health);
+        assert!(parse("[architecture]\nindex_order = \"size\"\n").is_err());
+    }
+
+    #[test]
+    fn output_language_defaults_to_english() {
+        assert_eq!(parse("").unwrap().generators.language, Language::En);
+        assert_eq!(parse("[generators]\nlanguage = \"ja\"\n").unwrap().generators.language, Language::Ja);
+        assert!(parse("[generators]\nlanguage = \"fr\"\n").is_err());
+    }
+
+    #[test]
+    fn validation_severities_default_and_override() {
+        use archidoc_types::Severity;
+
+        assert_eq!(parse("").unwrap().validate.orphan, Severity::Warning);
+        let config = parse("[validate]\nghost = \"warning\"\norphan = \"error\"\n").unwrap();
+        assert_eq!(config.validate.ghost, Severity::Warning);
+        assert_eq!(config.validate.orphan, Severity::Error);
+        assert_eq!(config.validate.invalid_link, Severity::Error);
+        assert!(parse("[validate]\norphan = \"info\"\n").is_err());
+        assert!(parse("[catalog]\nauto_apply = true\n").unwrap().catalog.auto_apply);
+    }
+
+    #[test]
+    fn protocol_aliases_must_target_allowed_protocols() {
+        let config = parse("[protocols]\nallowed = [\"http\", \"grpc\"]\naliases = { https = \"HTTP\" }\n").unwrap();
+
+        assert_eq!(config.protocols.aliases["https"], "HTTP");
+        let err = parse("[protocols]\nallowed = [\"http\"]\naliases = { rpc = \"grpc\" }\n").unwrap_err();
+        assert!(err.contains("alias 'rpc' points to 'grpc'"), "{}", err);
+        assert!(parse("[protocols]\naliases = { rpc = \"grpc\" }\n").is_ok());
+    }
+
+    #[test]
+    fn variable_names_are_identifiers() {
+        let config = parse("[variables]\nENV = \"staging\"\n").unwrap();
+        assert_eq!(config.variables["ENV"], "staging");
+        let err = parse("[variables]\n\"base-url\" = \"x\"\n").unwrap_err();
+        assert_eq!(err, "invalid [variables] name 'base-url' (expected letters, digits, and _)");
+    }
+
+    #[test]
+    fn rewrite_rules_need_an_arrow() {
+        let config = parse("[rewrite]\nrename = [\"legacy_core -> core\"]\ncollapse = [\"utils.* -> utils\"]\n").unwrap();
+
+        assert_eq!(config.rewrite.collapse, vec!["utils.* -> utils"]);
+        let err = parse("[rewrite]\ncollapse = [\"utils.*\"]\n").unwrap_err();
+        assert_eq!(err, "invalid [rewrite] collapse rule 'utils.*' (expected \"from -> to\")");
+    }
+
+    #[test]
+    fn cross_container_allowlist_entries_are_validated() {
+        let config = parse("[fitness]\nallow_cross_container = [\"api.auth -> bus.calc\", \"bus.events\"]\n").unwrap();
+
+        assert_eq!(config.fitness.allow_cross_container.len(), 2);
+        assert!(parse("[fitness]\nallow_cross_container = [\"api.auth ->\"]\n").is_err());
+        assert!(parse("[fitness]\nallow_cross_container = [\"bus calc\"]\n").is_err());
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use archidoc_types::ModuleDoc;
+use serde::{Deserialize, Serialize};
+
+use crate::architecture::ArchitectureGenerator;
+use crate::generator::{Artifact, Generator};
+use crate::health_html::escape;
+use crate::site;
+
+/// The page list the generator writes next to the pages, relative to the
+/// output directory.
+pub const MANIFEST: &str = "confluence/pages.json";
+
+/// One page in [`MANIFEST`]. Pages are listed parents first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page {
+    /// Storage-format file, relative to the manifest's directory.
+    pub file: String,
+    pub title: String,
+    /// Title of the parent page; `None` for the index page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+}
+
+/// Confluence storage-format XHTML under `confluence/`: an index page with
+/// the ARCHITECTURE.md sections, and a child page per container holding
+/// the container, a diagram of its dependencies, and its components.
+/// Elements outside any container stay on the index page. Diagrams are
+/// drawn by the `diagram_macro` macro; links between elements become page
+/// links.
+pub struct ConfluenceGenerator {
+    /// The document to split into pages; its front matter is ignored.
+    pub document: ArchitectureGenerator,
+    pub title_prefix: String,
+    pub diagram_macro: String,
+}
+
+impl Generator for ConfluenceGenerator {
+    fn id(&self) -> &str {
+        "confluence"
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        let pages = self.pages(docs);
+        let manifest: Vec<&Page> = pages.iter().map(|(page, _)| page).collect();
+        let mut artifacts: Vec<Artifact> = pages
+            .iter()
+            .map(|(page, body)| Artifact {
+                path: format!("confluence/{}", page.file),
+                content: body.clone(),
+            })
+            .collect();
+        artifacts.push(Artifact {
+            path: MANIFEST.to_string(),
+            content: serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())? + "\n",
+        });
+        Ok(artifacts)
+    }
+}
+
+impl ConfluenceGenerator {
+    /// Every page with its body, the index page first.
+    fn pages(&self, docs: &[ModuleDoc]) -> Vec<(Page, String)> {
+        let document = &self.document;
+        let t = crate::i18n::strings(document.language);
+        let skipped = |id: &str| document.layout.skip.iter().any(|s| s == id);
+        let index_title = format!("{}{}", self.title_prefix, t.architecture_title);
+        let title = |doc: &ModuleDoc| format!("{}{}", self.title_prefix, doc.module_path);
+
+        let site = site::split(docs, document);
+        let titles: BTreeMap<String, String> = site
+            .homes
+            .iter()
+            .map(|(id, home)| (id.clone(), home.map_or_else(|| index_title.clone(), title)))
+            .collect();
+
+        let mut index = String::new();
+        if !skipped("contents") {
+            index.push_str("<ac:structured-macro ac:name=\"toc\" />\n");
+        }
+        if !site.pages.is_empty() && !skipped("component_index") {
+            index.push_str(&format!(
+                "<h2>{}</h2>\n<ac:structured-macro ac:name=\"children\" />\n",
+                escape(t.component_index)
+            ));
+        }
+        index.push_str(&self.storage(&site.index, &titles));
+
+        let mut pages = vec![(
+            Page {
+                file: "index.xml".to_string(),
+                title: index_title.clone(),
+                parent: None,
+            },
+            index,
+        )];
+        for (container, markdown) in &site.pages {
+            pages.push((
+                Page {
+                    file: format!("containers/{}.xml", container.element_id()),
+                    title: title(container),
+                    parent: Some(index_title.clone()),
+                },
+                self.storage(markdown, &titles),
+            ));
+        }
+        pages
+    }
+
+    /// Markdown as storage-format XHTML. `titles` maps element ids to the
+    /// title of the page holding the element.
+    fn storage(&self, markdown: &str, titles: &BTreeMap<String, String>) -> String {
+        let fence = |lang: &str, code: &str| {
+            if lang == "mermaid" {
+                format!(
+                    "<ac:structured-macro ac:name=\"{}\">\n<ac:plain-text-body>{}</ac:plain-text-body>\n</ac:structured-macro>\n",
+                    escape(&self.diagram_macro),
+                    cdata(code)
+                )
+            } else {
+                let language = if lang.is_empty() {
+                    String::new()
+                } else {
+                    format!("<ac:parameter ac:name=\"language\">{}</ac:parameter>\n", escape(lang))
+                };
+                format!(
+                    "<ac:structured-macro ac:name=\"code\">\n{}<ac:plain-text-body>{}</ac:plain-text-body>\n</ac:structured-macro>\n",
+                    language,
+                    cdata(code)
+                )
+            }
+        };
+        let (html, _) = crate::html::convert(markdown, &fence);
+        // The page title stands in for the page heading
+        let html = match html.split_once('\n') {
+            Some((first, rest)) if first.starts_with("<h1>") => rest,
+            _ => html.as_str(),
+        };
+
+        let mut out = String::new();
+        for line in html.lines() {
+            // Confluence anchors headings itself
+            if line.starts_with("<a id=\"") && line.ends_with("\"></a>") {
+                continue;
+            }
+            if line.starts_with("<details") {
+                out.push_str("<ac:structured-macro ac:name=\"expand\">\n");
+            } else if let Some(summary) = line.strip_prefix("<summary>").and_then(|s| s.strip_suffix("</summary>")) {
+                out.push_str(&format!(
+                    "<ac:parameter ac:name=\"title\">{}</ac:parameter>\n<ac:rich-text-body>\n",
+                    summary
+                ));
+            } else if line == "</details>" {
+                out.push_str("</ac:rich-text-body>\n</ac:structured-macro>\n");
+            } else if line == "<hr>" {
+                out.push_str("<hr />\n");
+            } else {
+                out.push_str(&page_links(line, titles));
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+/// `text` as CDATA, split where it contains the CDATA terminator.
+fn cdata(text: &str) -> String {
+    format!("<![CDATA[{}]]>", text.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+/// Rewrite the links in converted Markdown: element anchors become links
+/// to the element's page, absolute URLs stay, and relative links (source
+/// files) keep only their text, since they mean nothing on the site.
+fn page_links(html: &str, titles: &BTreeMap<String, String>) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<a href=\"") {
+        let Some((href, tail)) = rest[start + 9..].split_once("\">") else { break };
+        let Some((label, tail)) = tail.split_once("</a>") else { break };
+        out.push_str(&rest[..start]);
+        match href.strip_prefix('#') {
+            Some(id) => match titles.get(id) {
+                Some(title) => out.push_str(&format!(
+                    "<ac:link><ri:page ri:content-title=\"{}\" /><ac:link-body>{}</ac:link-body></ac:link>",
+                    escape(title),
+                    label
+                )),
+                None => out.push_str(label),
+            },
+            None if href.contains("://") || href.starts_with("mailto:") => {
+                out.push_str(&format!("<a href=\"{}\">{}</a>", href, label))
+            }
+            None => out.push_str(label),
+        }
+        rest = tail;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The Confluence REST API calls [`publish`] makes.
+pub trait ConfluenceApi {
+    /// Id and version number of the space's page titled `title`, if any.
+    fn find(&self, title: &str) -> Result<Option<(String, u64)>, String>;
+
+    /// Create a page under `parent_id`, returning its id.
+    fn create(&self, title: &str, parent_id: Option<&str>, body: &str) -> Result<String, String>;
+
+    /// Replace a page's title, parent, and body as version `version`.
+    fn update(&self, id: &str, version: u64, title: &str, parent_id: Option<&str>, body: &str) -> Result<(), String>;
+}
+
+/// The pages listed in `out_dir`'s [`MANIFEST`].
+pub fn read_manifest(out_dir: &Path) -> Result<Vec<Page>, String> {
+    let path = out_dir.join(MANIFEST);
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("cannot read {} (generate with --confluence first): {}", path.display(), e))?;
+    serde_json::from_str(&json).map_err(|e| format!("invalid {}: {}", path.display(), e))
+}
+
+/// Publish the pages in `out_dir`'s [`MANIFEST`], in order: pages missing
+/// from the space are created, the others updated to a new version. The
+/// index page goes under `parent_id`, or at the space's top level. Returns
+/// one line per page.
+pub fn publish(api: &dyn ConfluenceApi, out_dir: &Path, parent_id: Option<&str>) -> Result<Vec<String>, String> {
+    let mut ids: BTreeMap<String, String> = BTreeMap::new();
+    let mut lines = Vec::new();
+    for page in read_manifest(out_dir)? {
+        let path = out_dir.join("confluence").join(&page.file);
+        let body = std::fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        let parent = match &page.parent {
+            Some(title) => Some(
+                ids.get(title)
+                    .ok_or_else(|| format!("page '{}' is listed before its parent '{}'", page.title, title))?
+                    .as_str(),
+            ),
+            None => parent_id,
+        };
+        let id = match api.find(&page.title)? {
+            Some((id, version)) => {
+                api.update(&id, version + 1, &page.title, parent, &body)?;
+                lines.push(format!("updated {} (version {})", page.title, version + 1));
+                id
+            }
+            None => {
+                let id = api.create(&page.title, parent, &body)?;
+                lines.push(format!("created {}", page.title));
+                id
+            }
+        };
+        ids.insert(page.title, id);
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::path::PathBuf;
+    use archidoc_types::{ArchitectureConfig, DiagramConfig, ElementKind, Language, Relationship};
+
+    fn doc(path: &str, kind: ElementKind, parent: Option<&str>, uses: &[&str]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: format!("src/{}/mod.rs", path.replace('.', "/")),
+            c4_level: kind,
+            pattern: "--".to_string(),
+            description: format!("The {} element", path),
+            parent_container: parent.map(str::to_string),
+            relationships: uses
+                .iter()
+                .map(|target| Relationship {
+                    target: target.to_string(),
+                    label: "Uses".to_string(),
+                    protocol: String::new(),
+                    hidden: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn generator() -> ConfluenceGenerator {
+        ConfluenceGenerator {
+            document: ArchitectureGenerator {
+                file_name: "ARCHITECTURE.md".to_string(),
+                link_base: PathBuf::new(),
+                fitness: vec![],
+                snapshot: Default::default(),
+                language: Language::En,
+                layout: ArchitectureConfig::default(),
+                front_matter: None,
+                diagrams: DiagramConfig::default(),
+            },
+            title_prefix: "Shop: ".to_string(),
+            diagram_macro: "mermaid-cloud".to_string(),
+        }
+    }
+
+    fn docs() -> Vec<ModuleDoc> {
+        vec![
+            doc("api", ElementKind::Container, None, &["bus.lanes"]),
+            doc("bus", ElementKind::Container, None, &[]),
+            doc("bus.lanes", ElementKind::Component, Some("bus"), &[]),
+            doc("customer", ElementKind::Person, None, &["api"]),
+        ]
+    }
+
+    #[test]
+    fn index_page_and_one_page_per_container() {
+        let artifacts = generator().generate(&docs()).unwrap();
+        let paths: Vec<&str> = artifacts.iter().map(|a| a.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["confluence/index.xml", "confluence/containers/api.xml", "confluence/containers/bus.xml", MANIFEST]
+        );
+
+        let manifest: Vec<Page> = serde_json::from_str(&artifacts[3].content).unwrap();
+        assert_eq!(manifest[0].title, "Shop: Architecture Context");
+        assert_eq!(manifest[2].title, "Shop: bus");
+        assert_eq!(manifest[2].parent.as_deref(), Some("Shop: Architecture Context"));
+
+        let index = &artifacts[0].content;
+        assert!(index.starts_with("<ac:structured-macro ac:name=\"toc\" />\n<h2>Component Index</h2>\n<ac:structured-macro ac:name=\"children\" />\n"));
+        assert!(!index.contains("<h1>") && !index.contains("<a id="));
+        assert!(index.contains("<ac:structured-macro ac:name=\"mermaid-cloud\">\n<ac:plain-text-body><![CDATA[C4Container\n"));
+        assert!(index.contains("<h3>customer</h3>"), "elements outside containers stay on the index page");
+        assert!(index.contains("<hr />"));
+        assert!(index.contains("<ac:structured-macro ac:name=\"expand\">\n<ac:parameter ac:name=\"title\">Text description of the system diagram</ac:parameter>\n<ac:rich-text-body>\n"));
+
+        let bus = &artifacts[2].content;
+        assert!(bus.starts_with("<p>The bus element</p>\n"));
+        assert!(bus.contains("<![CDATA[C4Component\n    title bus\n"));
+        assert!(bus.contains("<h2>bus.lanes</h2>"));
+        assert!(bus.contains("<li><strong>Used by:</strong> <ac:link><ri:page ri:content-title=\"Shop: api\" /><ac:link-body>api</ac:link-body></ac:link></li>"));
+        assert!(bus.contains("<li><strong>Source:</strong> src/bus/mod.rs</li>"));
+    }
+
+    #[test]
+    fn cdata_survives_its_terminator() {
+        assert_eq!(cdata("a]]>b"), "<![CDATA[a]]]]><![CDATA[>b]]>");
+    }
+
+    struct FakePage {
+        id: String,
+        title: String,
+        version: u64,
+        parent: Option<String>,
+    }
+
+    #[derive(Default)]
+    struct FakeApi {
+        pages: RefCell<Vec<FakePage>>,
+    }
+
+    impl ConfluenceApi for FakeApi {
+        fn find(&self, title: &str) -> Result<Option<(String, u64)>, String> {
+            Ok(self.pages.borrow().iter().find(|p| p.title == title).map(|p| (p.id.clone(), p.version)))
+        }
+
+        fn create(&self, title: &str, parent_id: Option<&str>, _body: &str) -> Result<String, String> {
+            let id = format!("{}", 100 + self.pages.borrow().len());
+            self.pages.borrow_mut().push(FakePage {
+                id: id.clone(),
+                title: title.to_string(),
+                version: 1,
+                parent: parent_id.map(str::to_string),
+            });
+            Ok(id)
+        }
+
+        fn update(&self, id: &str, version: u64, _title: &str, parent_id: Option<&str>, _body: &str) -> Result<(), String> {
+            let mut pages = self.pages.borrow_mut();
+            let page = pages.iter_mut().find(|p| p.id == id).unwrap();
+            page.version = version;
+            page.parent = parent_id.map(str::to_string);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn publish_creates_then_updates_pages_under_their_parents() {
+        let tmp = tempfile::tempdir().unwrap();
+        let generators: Vec<Box<dyn Generator>> = vec![Box::new(generator())];
+        crate::compile::write_outputs(&docs(), tmp.path(), &generators).unwrap();
+        let api = FakeApi::default();
+
+        let lines = publish(&api, tmp.path(), Some("42")).unwrap();
+        assert_eq!(lines, ["created Shop: Architecture Context", "created Shop: api", "created Shop: bus"]);
+        assert_eq!(api.pages.borrow()[0].parent.as_deref(), Some("42"));
+        assert_eq!(api.pages.borrow()[2].parent.as_deref(), Some("100"));
+
+        let lines = publish(&api, tmp.path(), None).unwrap();
+        assert_eq!(lines[1], "updated Shop: api (version 2)");
+        assert_eq!(api.pages.borrow()[0].parent, None);
+        assert!(publish(&api, &tmp.path().join("missing"), None).unwrap_err().contains("--confluence"));
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use archidoc_types::ModuleDoc;
+
+use crate::ownership::FileOwnership;
+
+/// Metric key for the number of instrumented lines in a module.
+pub const COVERABLE_LINES: &str = "coverable_lines";
+/// Metric key for the number of instrumented lines executed by tests.
+pub const COVERED_LINES: &str = "covered_lines";
+
+/// Line coverage for one source file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileCoverage {
+    pub coverable: u64,
+    pub covered: u64,
+}
+
+/// Load a coverage report, detecting the format from its content.
+///
+/// Accepts LCOV tracefiles (`cargo llvm-cov --lcov`, grcov, ...) and
+/// `cargo llvm-cov --json` exports.
+pub fn load(path: &Path) -> Result<BTreeMap<PathBuf, FileCoverage>, String> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+
+    if text.trim_start().starts_with('{') {
+        parse_llvm_cov_json(&text)
+    } else {
+        parse_lcov(&text)
+    }
+}
+
+/// Parse an LCOV tracefile.
+///
+/// Uses the `LF`/`LH` summary of each record, falling back to counting
+/// `DA` lines when a record has no summary.
+pub fn parse_lcov(text: &str) -> Result<BTreeMap<PathBuf, FileCoverage>, String> {
+    let mut files = BTreeMap::new();
+    let mut current: Option<PathBuf> = None;
+    let mut summary: Option<FileCoverage> = None;
+    let mut counted = FileCoverage::default();
+
+    for line in text.lines().map(str::trim) {
+        if let Some(file) = line.strip_prefix("SF:") {
+            current = Some(PathBuf::from(file));
+            summary = None;
+            counted = FileCoverage::default();
+        } else if let Some(found) = line.strip_prefix("LF:") {
+            summary.get_or_insert_with(FileCoverage::default).coverable = parse_count(found)?;
+        } else if let Some(hit) = line.strip_prefix("LH:") {
+            summary.get_or_insert_with(FileCoverage::default).covered = parse_count(hit)?;
+        } else if let Some(data) = line.strip_prefix("DA:") {
+            counted.coverable += 1;
+            let hits = data.split(',').nth(1).unwrap_or("0");
+            if hits.trim() != "0" {
+                counted.covered += 1;
+            }
+        } else if line == "end_of_record" {
+            let file = current.take().ok_or("LCOV record without SF line")?;
+            add(&mut files, file, summary.take().unwrap_or(counted));
+        }
+    }
+
+    Ok(files)
+}
+
+/// Parse a `cargo llvm-cov --json` (llvm-cov export) report.
+pub fn parse_llvm_cov_json(text: &str) -> Result<BTreeMap<PathBuf, FileCoverage>, String> {
+    let json: serde_json::Value =
+        serde_json::from_str(text).map_err(|e| format!("invalid coverage JSON: {}", e))?;
+    let data = json
+        .get("data")
+        .and_then(|d| d.as_array())
+        .ok_or("coverage JSON has no \"data\" array (expected llvm-cov export format)")?;
+
+    let mut files = BTreeMap::new();
+    for export in data {
+        for file in export.get("files").and_then(|f| f.as_array()).into_iter().flatten() {
+            let Some(name) = file.get("filename").and_then(|f| f.as_str()) else {
+                continue;
+            };
+            let lines = file.pointer("/summary/lines");
+            let coverage = FileCoverage {
+                coverable: lines.and_then(|l| l.get("count")).and_then(|c| c.as_u64()).unwrap_or(0),
+                covered: lines.and_then(|l| l.get("covered")).and_then(|c| c.as_u64()).unwrap_or(0),
+            };
+            add(&mut files, PathBuf::from(name), coverage);
+        }
+    }
+
+    Ok(files)
+}
+
+/// Attribute file coverage to modules and record it in each module's
+/// metrics under [`COVERABLE_LINES`] and [`COVERED_LINES`].
+///
+/// Files are attributed as described in [`FileOwnership`].
+pub fn apply_coverage(docs: &mut [ModuleDoc], coverage: &BTreeMap<PathBuf, FileCoverage>) {
+    let ownership = FileOwnership::new(docs);
+    let mut totals: Vec<Option<FileCoverage>> = vec![None; docs.len()];
+
+    for (file, cov) in coverage {
+        if let Some(i) = ownership.owner(file) {
+            let total = totals[i].get_or_insert_with(FileCoverage::default);
+            total.coverable += cov.coverable;
+            total.covered += cov.covered;
+        }
+    }
+
+    for (doc, total) in docs.iter_mut().zip(totals) {
+        if let Some(total) = total {
+            doc.metrics.insert(COVERABLE_LINES.to_string(), total.coverable);
+            doc.metrics.insert(COVERED_LINES.to_string(), total.covered);
+        }
+    }
+}
+
+/// Line coverage percentage recorded on a module, if any.
+pub fn module_coverage(doc: &ModuleDoc) -> Option<f64> {
+    let coverable = *doc.metrics.get(COVERABLE_LINES)?;
+    let covered = *doc.metrics.get(COVERED_LINES)?;
+    if coverable == 0 {
+        return None;
+    }
+    Some(covered as f64 / coverable as f64 * 100.0)
+}
+
+fn parse_count(text: &str) -> Result<u64, String> {
+    text.trim()
+        .parse()
+        .map_err(|_| format!("invalid LCOV line count '{}'", text))
+}
+
+fn add(files: &mut BTreeMap<PathBuf, FileCoverage>, file: PathBuf, cov: FileCoverage) {
+    let entry = files.entry(file).or_default();
+    entry.coverable += cov.coverable;
+    entry.covered += cov.covered;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::ElementKind;
+
+    fn doc(source: &Path, path: &str) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: source.to_string_lossy().to_string(),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_lcov_summaries_and_line_data() {
+        let lcov = "SF:/src/bus/mod.rs\nDA:1,3\nDA:2,0\nLF:2\nLH:1\nend_of_record\nSF:/src/bus/calc.rs\nDA:1,0\nDA:2,5\nDA:3,1\nend_of_record\n";
+
+        let files = parse_lcov(lcov).unwrap();
+
+        assert_eq!(files[Path::new("/src/bus/mod.rs")], FileCoverage { coverable: 2, covered: 1 });
+        assert_eq!(files[Path::new("/src/bus/calc.rs")], FileCoverage { coverable: 3, covered: 2 });
+    }
+
+    #[test]
+    fn parses_llvm_cov_json_export() {
+        let json = r#"{"type":"llvm.coverage.json.export","data":[{"files":[
+            {"filename":"/src/api/mod.rs","summary":{"lines":{"count":40,"covered":30,"percent":75.0}}}
+        ]}]}"#;
+
+        let files = parse_llvm_cov_json(json).unwrap();
+
+        assert_eq!(files[Path::new("/src/api/mod.rs")], FileCoverage { coverable: 40, covered: 30 });
+        assert!(parse_llvm_cov_json("{}").is_err());
+    }
+
+    #[test]
+    fn nested_modules_claim_their_own_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path();
+        fs::create_dir_all(src.join("bus/calc")).unwrap();
+        let mut docs = vec![doc(&src.join("bus/mod.rs"), "bus"), doc(&src.join("bus/calc/mod.rs"), "bus.calc")];
+        let coverage = BTreeMap::from([
+            (src.join("bus/queue.rs"), FileCoverage { coverable: 10, covered: 9 }),
+            (src.join("bus/calc/mod.rs"), FileCoverage { coverable: 4, covered: 1 }),
+            (src.join("bus/calc/math.rs"), FileCoverage { coverable: 6, covered: 1 }),
+        ]);
+
+        apply_coverage(&mut docs, &coverage);
+
+        assert_eq!(module_coverage(&docs[0]), Some(90.0));
+        assert_eq!(module_coverage(&docs[1]), Some(20.0));
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+
+use archidoc_types::{DiagramConfig, ElementKind, LineStyle, ModuleDoc};
+
+use crate::generator::{Artifact, Generator};
+use crate::mermaid::{components_by_container, container_edges, containers_by_group, drawn_relationships, group_boundary};
+
+/// File name of the D2 container diagram.
+pub const CONTAINER_FILE: &str = "c4-container.d2";
+/// File name of the D2 component diagram.
+pub const COMPONENT_FILE: &str = "c4-component.d2";
+
+/// D2 container and component diagrams under `d2/`.
+pub struct D2Generator {
+    pub diagrams: DiagramConfig,
+}
+
+impl Generator for D2Generator {
+    fn id(&self) -> &str {
+        "d2"
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        Ok(vec![
+            Artifact {
+                path: format!("d2/{}", CONTAINER_FILE),
+                content: container_diagram(docs, &self.diagrams),
+            },
+            Artifact {
+                path: format!("d2/{}", COMPONENT_FILE),
+                content: component_diagram(docs, &self.diagrams),
+            },
+        ])
+    }
+}
+
+/// Render the D2 container diagram: people and systems outside the
+/// boundary at the top level, containers inside a `System` box, with a
+/// nested box per `@group`.
+pub fn container_diagram(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
+    let elements: Vec<&ModuleDoc> = docs.iter().filter(|d| d.c4_level.in_container_view()).collect();
+    let (external, containers): (Vec<&ModuleDoc>, Vec<&ModuleDoc>) =
+        elements.iter().partition(|d| d.c4_level.is_external());
+
+    // Connections name nodes by their full key, e.g. `sys.group_data.store`
+    let mut keys: BTreeMap<&str, String> = BTreeMap::new();
+    let mut out = String::from("direction: right\n\n");
+    for doc in &external {
+        out.push_str(&node_def(doc, &to_title_case(&doc.module_path), ""));
+        keys.insert(&doc.module_path, doc.element_id());
+    }
+
+    let (groups, ungrouped) = containers_by_group(&containers);
+    out.push_str("sys: System {\n");
+    for doc in ungrouped {
+        out.push_str(&node_def(doc, &to_title_case(&doc.module_path), "  "));
+        keys.insert(&doc.module_path, format!("sys.{}", doc.element_id()));
+    }
+    for (group, members) in &groups {
+        let (id, label, _) = group_boundary(group);
+        out.push_str(&format!("  {}: {} {{\n", id, quote(&label)));
+        for doc in members {
+            out.push_str(&node_def(doc, &to_title_case(&doc.module_path), "    "));
+            keys.insert(&doc.module_path, format!("sys.{}.{}", id, doc.element_id()));
+        }
+        out.push_str("  }\n");
+    }
+    out.push_str("}\n");
+
+    let edges = container_edges(docs, &elements, diagrams);
+    if !edges.is_empty() {
+        out.push('\n');
+    }
+    for (from, rel) in edges {
+        out.push_str(&edge(docs, &keys, from, &rel.target, &rel.label, &rel.protocol, diagrams));
+    }
+    out
+}
+
+/// Render the D2 component diagram: one box per container holding its
+/// components.
+pub fn component_diagram(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
+    let by_container = components_by_container(docs);
+
+    let mut keys: BTreeMap<&str, String> = BTreeMap::new();
+    let mut out = String::from("direction: right\n");
+    for (container, components) in &by_container {
+        let container_id = ModuleDoc::id_of(docs, container);
+        out.push_str(&format!("\n{}: {} {{\n", container_id, quote(&to_title_case(container))));
+        for doc in components {
+            let name = doc.module_path.split('.').next_back().unwrap_or(&doc.module_path);
+            out.push_str(&node_def(doc, name, "  "));
+            keys.insert(&doc.module_path, format!("{}.{}", container_id, doc.element_id()));
+        }
+        out.push_str("}\n");
+    }
+
+    let mut edges = String::new();
+    for doc in docs.iter().filter(|d| d.c4_level == ElementKind::Component) {
+        for rel in drawn_relationships(docs, doc) {
+            edges.push_str(&edge(docs, &keys, &doc.module_path, &rel.target, &rel.label, &rel.protocol, diagrams));
+        }
+    }
+    if !edges.is_empty() {
+        out.push('\n');
+        out.push_str(&edges);
+    }
+    out
+}
+
+/// A node with its name, pattern, and description as the label. People get
+/// the person shape and systems outside the boundary a grey fill.
+fn node_def(doc: &ModuleDoc, name: &str, indent: &str) -> String {
+    let label = if doc.c4_level.is_external() {
+        format!("{}\n{}", name, doc.description)
+    } else {
+        format!("{}\n[{}]\n{}", name, doc.pattern, doc.description)
+    };
+    let mut out = format!("{}{}: {}", indent, doc.element_id(), quote(label.trim_end()));
+    let mut attrs = Vec::new();
+    if doc.c4_level == ElementKind::Person {
+        attrs.push("shape: person");
+    }
+    if doc.c4_level.is_external() && doc.c4_level != ElementKind::Person {
+        attrs.push("style.fill: \"#999999\"");
+    }
+    if attrs.is_empty() {
+        out.push('\n');
+    } else {
+        out.push_str(" {\n");
+        for attr in attrs {
+            out.push_str(&format!("{}  {}\n", indent, attr));
+        }
+        out.push_str(&format!("{}}}\n", indent));
+    }
+    out
+}
+
+/// A connection between the nodes at `from` and `to`, styled per
+/// `[diagrams.edge_styles]`. A target the diagram does not draw is named by
+/// its id, so D2 adds it as a plain node.
+fn edge(
+    docs: &[ModuleDoc],
+    keys: &BTreeMap<&str, String>,
+    from: &str,
+    to: &str,
+    label: &str,
+    protocol: &str,
+    diagrams: &DiagramConfig,
+) -> String {
+    let key = |path: &str| keys.get(path).cloned().unwrap_or_else(|| ModuleDoc::id_of(docs, path));
+    let label = if protocol.is_empty() { label.to_string() } else { format!("{}\n[{}]", label, protocol) };
+    let mut out = format!("{} -> {}: {}", key(from), key(to), quote(&label));
+    let mut attrs = Vec::new();
+    if let Some(style) = diagrams.edge_style(protocol) {
+        if let Some(color) = &style.color {
+            attrs.push(format!("style.stroke: {}", quote(color)));
+            attrs.push(format!("style.font-color: {}", quote(color)));
+        }
+        match style.line {
+            LineStyle::Solid => {}
+            LineStyle::Dashed => attrs.push("style.stroke-dash: 5".to_string()),
+            LineStyle::Dotted => attrs.push("style.stroke-dash: 2".to_string()),
+            LineStyle::Bold => attrs.push("style.stroke-width: 4".to_string()),
+        }
+    }
+    if attrs.is_empty() {
+        out.push('\n');
+    } else {
+        out.push_str(" {\n");
+        for attr in attrs {
+            out.push_str(&format!("  {}\n", attr));
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+/// `text` as a double-quoted D2 string.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+fn to_title_case(s: &str) -> String {
+    s.split('.')
+        .next_back()
+        .unwrap_or(s)
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(c) => c.to_uppercase().to_string() + chars.as_str(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::Relationship;
+
+    fn doc(path: &str, kind: ElementKind, uses: &[&str]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            c4_level: kind,
+            pattern: "Mediator".to_string(),
+            description: format!("The {} element", path),
+            relationships: uses
+                .iter()
+                .map(|target| Relationship {
+                    target: target.to_string(),
+                    label: "Uses".to_string(),
+                    protocol: "http".to_string(),
+                    hidden: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn docs() -> Vec<ModuleDoc> {
+        let mut store = doc("store", ElementKind::Container, &[]);
+        store.group = Some("domain:data".to_string());
+        vec![
+            doc("customer", ElementKind::Person, &["api"]),
+            doc("api", ElementKind::Container, &["store", "stripe"]),
+            doc("api.routes", ElementKind::Component, &["store.repo", "legacy"]),
+            store,
+            doc("store.repo", ElementKind::Component, &[]),
+            doc("stripe", ElementKind::ExternalSystem, &[]),
+        ]
+    }
+
+    #[test]
+    fn containers_nest_in_the_system_and_connections_use_full_keys() {
+        let diagram = container_diagram(&docs(), &DiagramConfig::default());
+
+        assert_eq!(
+            diagram,
+            r##"direction: right
+
+customer: "Customer\nThe customer element" {
+  shape: person
+}
+stripe: "Stripe\nThe stripe element" {
+  style.fill: "#999999"
+}
+sys: System {
+  api: "Api\n[Mediator]\nThe api element"
+  group_domain_data: "Data" {
+    store: "Store\n[Mediator]\nThe store element"
+  }
+}
+
+customer -> sys.api: "Uses\n[http]"
+sys.api -> sys.group_domain_data.store: "Uses\n[http]"
+sys.api -> stripe: "Uses\n[http]"
+"##
+        );
+    }
+
+    #[test]
+    fn edge_styles_apply_to_connections_of_their_protocol() {
+        let mut diagrams = DiagramConfig::default();
+        diagrams.edge_styles.insert(
+            "HTTP".to_string(),
+            archidoc_types::EdgeStyle {
+                color: Some("#1f77b4".to_string()),
+                line: LineStyle::Dotted,
+            },
+        );
+
+        let diagram = container_diagram(&docs(), &diagrams);
+
+        assert!(diagram.contains(
+            "customer -> sys.api: \"Uses\\n[http]\" {\n  style.stroke: \"#1f77b4\"\n  style.font-color: \"#1f77b4\"\n  style.stroke-dash: 2\n}\n"
+        ), "{}", diagram);
+    }
+
+    #[test]
+    fn components_sit_in_their_container_box() {
+        let diagram = component_diagram(&docs(), &DiagramConfig::default());
+
+        assert!(diagram.contains("api: \"Api\" {\n  api_routes: \"routes\\n[Mediator]\\nThe api.routes element\"\n}\n"));
+        assert!(diagram.contains("api.api_routes -> store.store_repo: \"Uses\\n[http]\"\n"));
+        assert!(diagram.ends_with("api.api_routes -> legacy: \"Uses\\n[http]\"\n"));
+    }
+}
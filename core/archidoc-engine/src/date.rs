@@ -0,0 +1,109 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Today's date in UTC as `YYYY-MM-DD`.
+pub fn today() -> String {
+    format_days(today_days())
+}
+
+/// Days since 1970-01-01 for today in UTC.
+pub fn today_days() -> u64 {
+    now_secs() / 86_400
+}
+
+/// The current time in UTC as `YYYY-MM-DDTHH:MM:SSZ`.
+pub fn now_timestamp() -> String {
+    format_timestamp(now_secs())
+}
+
+/// Format seconds since the Unix epoch as `YYYY-MM-DDTHH:MM:SSZ`.
+pub fn format_timestamp(secs: u64) -> String {
+    let time = secs % 86_400;
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        format_days(secs / 86_400),
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Format a count of days since 1970-01-01 as `YYYY-MM-DD`.
+///
+/// Civil-from-days conversion for the proleptic Gregorian calendar.
+pub fn format_days(days: u64) -> String {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Check that a string is a `YYYY-MM-DD` date that exists in the
+/// Gregorian calendar.
+pub fn is_valid(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+    if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2 {
+        return false;
+    }
+    if !parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit())) {
+        return false;
+    }
+    let year: u32 = parts[0].parse().unwrap_or(0);
+    let month: u32 = parts[1].parse().unwrap_or(0);
+    let day: u32 = parts[2].parse().unwrap_or(0);
+    (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day)
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn epoch_and_leap_days() {
+        assert_eq!(format_days(0), "1970-01-01");
+        assert_eq!(format_days(11_016), "2000-02-29");
+        assert_eq!(format_days(20_498), "2026-02-14");
+    }
+
+    #[test]
+    fn timestamps_include_time_of_day() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp(20_498 * 86_400 + 13 * 3_600 + 5 * 60 + 9), "2026-02-14T13:05:09Z");
+    }
+
+    #[test]
+    fn validates_dates() {
+        assert!(is_valid("2026-02-14"));
+        assert!(!is_valid("2026-13-01"));
+        assert!(!is_valid("2026-02-31"));
+        assert!(!is_valid("2026-04-31"));
+        assert!(!is_valid("2026-02-29"));
+        assert!(!is_valid("1900-02-29"));
+        assert!(is_valid("2000-02-29"));
+        assert!(is_valid("2028-02-29"));
+        assert!(!is_valid("26-02-14"));
+        assert!(!is_valid("2026-02-1x"));
+    }
+}
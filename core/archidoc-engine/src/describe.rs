@@ -0,0 +1,148 @@
+use archidoc_types::ModuleDoc;
+
+use crate::i18n::{fill, Strings};
+
+/// Prose alternative to the container diagram: every element it draws with
+/// its description and the elements it uses, in a collapsed `<details>`
+/// block.
+pub fn container_description(docs: &[ModuleDoc], t: &Strings) -> String {
+    let containers: Vec<&ModuleDoc> = docs.iter().filter(|d| d.c4_level.in_container_view()).collect();
+
+    let mut body = fill(t.text_containers, &[("n", &containers.len().to_string())]);
+    body.push_str("\n\n");
+    for doc in containers {
+        body.push_str(&format!("- {}\n", element_line(doc, t)));
+    }
+    details(t.text_system_diagram, &body)
+}
+
+/// Prose alternative to the system context diagram: the people and
+/// systems around the system, with what they use.
+pub fn context_description(docs: &[ModuleDoc], t: &Strings) -> String {
+    let mut body = String::new();
+    for doc in docs.iter().filter(|d| d.c4_level.is_external()) {
+        body.push_str(&format!("- {}\n", element_line(doc, t)));
+    }
+    details(t.text_system_context, &body)
+}
+
+/// Prose alternative to the component diagram: components listed under
+/// their container, nested the way the diagram's boundaries nest them.
+pub fn component_description(docs: &[ModuleDoc], t: &Strings) -> String {
+    let mut body = String::new();
+    for (container, components) in crate::mermaid::components_by_container(docs) {
+        let name = format!("**{}**", container);
+        body.push_str(&format!("- {}\n", fill(t.text_contains, &[("container", &name)])));
+        for root in components.iter().filter(|c| parent_in(c, &components).is_none()) {
+            push_subtree(&mut body, root, &components, 1, t);
+        }
+    }
+    details(t.text_component_diagram, &body)
+}
+
+fn details(summary: &str, body: &str) -> String {
+    format!("<details>\n<summary>{}</summary>\n\n{}\n</details>\n\n", summary, body)
+}
+
+fn push_subtree(out: &mut String, doc: &ModuleDoc, group: &[&ModuleDoc], depth: usize, t: &Strings) {
+    out.push_str(&format!("{}- {}\n", "  ".repeat(depth), element_line(doc, t)));
+    for child in group.iter().filter(|c| parent_in(c, group) == Some(doc.module_path.as_str())) {
+        push_subtree(out, child, group, depth + 1, t);
+    }
+}
+
+/// The closest component in `group` whose path prefixes `doc`'s.
+fn parent_in<'a>(doc: &ModuleDoc, group: &[&'a ModuleDoc]) -> Option<&'a str> {
+    group
+        .iter()
+        .map(|c| c.module_path.as_str())
+        .filter(|p| doc.module_path.starts_with(&format!("{}.", p)))
+        .max_by_key(|p| p.len())
+}
+
+/// `**bus.calc** (feature: gpu): Indicator calculations — uses **bus.lanes** (Results, channel)`
+fn element_line(doc: &ModuleDoc, t: &Strings) -> String {
+    let mut line = format!("**{}**", doc.module_path);
+    if let Some(condition) = &doc.condition {
+        line.push_str(&format!(" ({})", condition));
+    }
+    if !doc.description.is_empty() {
+        line.push_str(&format!(": {}", doc.description));
+    }
+
+    let uses: Vec<String> = doc
+        .relationships
+        .iter()
+        .map(|rel| {
+            let details: Vec<&str> = [rel.label.as_str(), rel.protocol.as_str()]
+                .into_iter()
+                .filter(|s| !s.is_empty())
+                .collect();
+            if details.is_empty() {
+                format!("**{}**", rel.target)
+            } else {
+                format!("**{}** ({})", rel.target, details.join(", "))
+            }
+        })
+        .collect();
+    if !uses.is_empty() {
+        line.push_str(&format!(" — {} {}", t.text_uses, uses.join(", ")));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{ElementKind, Language, Relationship};
+
+    fn doc(path: &str, level: ElementKind, desc: &str) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: format!("src/{}/mod.rs", path.replace('.', "/")),
+            c4_level: level,
+            pattern: "--".to_string(),
+            description: desc.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn containers_are_listed_with_their_dependencies() {
+        let mut api = doc("api", ElementKind::Container, "REST gateway");
+        api.relationships.push(Relationship {
+            target: "bus".to_string(),
+            label: "Routes commands".to_string(),
+            protocol: "crossbeam".to_string(),
+            hidden: false,
+        });
+        let docs = vec![api, doc("bus", ElementKind::Container, "Messaging")];
+
+        let text = container_description(&docs, crate::i18n::strings(Language::En));
+
+        assert_eq!(
+            text,
+            "<details>\n<summary>Text description of the system diagram</summary>\n\n\
+             Containers (2):\n\n\
+             - **api**: REST gateway — uses **bus** (Routes commands, crossbeam)\n\
+             - **bus**: Messaging\n\
+             \n</details>\n\n"
+        );
+    }
+
+    #[test]
+    fn components_nest_under_their_container_and_parent() {
+        let docs = vec![
+            doc("bus", ElementKind::Container, "Messaging"),
+            doc("bus.calc", ElementKind::Component, "Calculations"),
+            doc("bus.calc.rsi", ElementKind::Component, "RSI"),
+            doc("bus.lanes", ElementKind::Component, "Lanes"),
+        ];
+
+        let text = component_description(&docs, crate::i18n::strings(Language::En));
+
+        assert!(text.contains(
+            "- **bus** contains:\n  - **bus.calc**: Calculations\n    - **bus.calc.rsi**: RSI\n  - **bus.lanes**: Lanes\n"
+        ));
+    }
+}
@@ -0,0 +1,536 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use archidoc_types::{ElementChange, ElementRename, FieldChange, IrDiff, ModuleDoc, RelationshipChange};
+
+use crate::hash::annotation_hash;
+
+/// Compute the structural difference between two IR snapshots.
+///
+/// Elements are matched by module path. An element that disappears while
+/// another appears is reported as a rename when exactly one of each shares
+/// an `@id`, an annotation content hash, or a file table (tried in that
+/// order). Attribute changes cover the fields that shape generated
+/// documentation (level, pattern, confidence, description, parent).
+/// Relationships are matched by `(from, to)` after applying renames; a label
+/// or protocol change is reported as a removal plus an addition.
+pub fn diff_ir(before: &[ModuleDoc], after: &[ModuleDoc]) -> IrDiff {
+    let before_map: BTreeMap<&str, &ModuleDoc> =
+        before.iter().map(|d| (d.module_path.as_str(), d)).collect();
+    let after_map: BTreeMap<&str, &ModuleDoc> =
+        after.iter().map(|d| (d.module_path.as_str(), d)).collect();
+
+    let mut diff = IrDiff::default();
+
+    let mut added: Vec<&ModuleDoc> = Vec::new();
+    for (path, new) in &after_map {
+        match before_map.get(path) {
+            None => added.push(new),
+            Some(old) => {
+                let fields = changed_fields(old, new);
+                if !fields.is_empty() {
+                    diff.changed_elements.push(ElementChange {
+                        element: path.to_string(),
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+
+    let mut removed: Vec<&ModuleDoc> = before_map
+        .iter()
+        .filter(|(path, _)| !after_map.contains_key(*path))
+        .map(|(_, doc)| *doc)
+        .collect();
+
+    diff.renamed_elements = match_renames(&mut removed, &mut added);
+    for rename in &diff.renamed_elements {
+        let fields = changed_fields(before_map[rename.from.as_str()], after_map[rename.to.as_str()]);
+        if !fields.is_empty() {
+            diff.changed_elements.push(ElementChange {
+                element: rename.to.clone(),
+                fields,
+            });
+        }
+    }
+    diff.changed_elements.sort_by(|a, b| a.element.cmp(&b.element));
+    diff.added_elements = added.iter().map(|d| d.module_path.clone()).collect();
+    diff.removed_elements = removed.iter().map(|d| d.module_path.clone()).collect();
+
+    let renamed: BTreeMap<&str, &str> = diff
+        .renamed_elements
+        .iter()
+        .map(|r| (r.from.as_str(), r.to.as_str()))
+        .collect();
+    let before_rels = relationship_set(before, &renamed);
+    let after_rels = relationship_set(after, &BTreeMap::new());
+    diff.added_relationships = after_rels.difference(&before_rels).cloned().map(to_change).collect();
+    diff.removed_relationships = before_rels.difference(&after_rels).cloned().map(to_change).collect();
+
+    diff
+}
+
+/// Format an IR diff as human-readable text.
+pub fn format_ir_diff(diff: &IrDiff) -> String {
+    let mut out = String::new();
+
+    if diff.is_empty() {
+        out.push_str("No architectural changes.\n");
+        return out;
+    }
+
+    for element in &diff.added_elements {
+        out.push_str(&format!("+ {}\n", element));
+    }
+    for element in &diff.removed_elements {
+        out.push_str(&format!("- {}\n", element));
+    }
+    for rename in &diff.renamed_elements {
+        out.push_str(&format!("> {} -> {} ({})\n", rename.from, rename.to, match_reason(rename)));
+    }
+    for change in &diff.changed_elements {
+        out.push_str(&format!("~ {}\n", change.element));
+        for field in &change.fields {
+            out.push_str(&format!(
+                "    {}: '{}' -> '{}'\n",
+                field.field, field.before, field.after
+            ));
+        }
+    }
+    for rel in &diff.added_relationships {
+        out.push_str(&format!("+ {} -> {} ({})\n", rel.from, rel.to, rel.label));
+    }
+    for rel in &diff.removed_relationships {
+        out.push_str(&format!("- {} -> {} ({})\n", rel.from, rel.to, rel.label));
+    }
+
+    out
+}
+
+/// Hidden marker at the top of every PR comment, so CI bots can find and
+/// update their previous comment instead of posting a new one.
+pub const PR_COMMENT_MARKER: &str = "<!-- archidoc:architecture-diff -->";
+
+/// Format an IR diff as a markdown body for a pull-request comment.
+///
+/// Opens with an emoji summary line whose counts link to the detail
+/// sections below. Each section is collapsed in a `<details>` block, and
+/// every element carries an anchor so reviewers can link to it.
+pub fn format_pr_comment(diff: &IrDiff) -> String {
+    let mut out = String::new();
+    out.push_str(PR_COMMENT_MARKER);
+    out.push_str("\n## 🏗️ Architecture changes\n\n");
+
+    if diff.is_empty() {
+        out.push_str("✅ No architectural changes.\n");
+        return out;
+    }
+
+    let counts = [
+        ("➕", diff.added_elements.len(), "added", "archidoc-added"),
+        ("➖", diff.removed_elements.len(), "removed", "archidoc-removed"),
+        ("🔀", diff.renamed_elements.len(), "renamed", "archidoc-renamed"),
+        ("✏️", diff.changed_elements.len(), "changed", "archidoc-changed"),
+        (
+            "🔗",
+            diff.added_relationships.len() + diff.removed_relationships.len(),
+            "relationship changes",
+            "archidoc-relationships",
+        ),
+    ];
+    let summary: Vec<String> = counts
+        .iter()
+        .filter(|(_, n, _, _)| *n > 0)
+        .map(|(emoji, n, label, anchor)| format!("{} [{} {}](#{})", emoji, n, label, anchor))
+        .collect();
+    out.push_str(&summary.join(" · "));
+    out.push_str("\n\n");
+
+    if !diff.added_elements.is_empty() {
+        open_section(&mut out, "archidoc-added", "➕ Added elements", diff.added_elements.len());
+        for element in &diff.added_elements {
+            out.push_str(&format!("- {}`{}`\n", element_anchor(element), element));
+        }
+        close_section(&mut out);
+    }
+
+    if !diff.removed_elements.is_empty() {
+        open_section(&mut out, "archidoc-removed", "➖ Removed elements", diff.removed_elements.len());
+        for element in &diff.removed_elements {
+            out.push_str(&format!("- {}`{}`\n", element_anchor(element), element));
+        }
+        close_section(&mut out);
+    }
+
+    if !diff.renamed_elements.is_empty() {
+        open_section(&mut out, "archidoc-renamed", "🔀 Renamed elements", diff.renamed_elements.len());
+        for rename in &diff.renamed_elements {
+            out.push_str(&format!(
+                "- {}`{}` → `{}` ({})\n",
+                element_anchor(&rename.to),
+                rename.from,
+                rename.to,
+                match_reason(rename)
+            ));
+        }
+        close_section(&mut out);
+    }
+
+    if !diff.changed_elements.is_empty() {
+        open_section(&mut out, "archidoc-changed", "✏️ Changed elements", diff.changed_elements.len());
+        for change in &diff.changed_elements {
+            out.push_str(&format!("- {}`{}`\n", element_anchor(&change.element), change.element));
+            for field in &change.fields {
+                out.push_str(&format!(
+                    "  - {}: {} → {}\n",
+                    field.field,
+                    inline_code(&field.before),
+                    inline_code(&field.after)
+                ));
+            }
+        }
+        close_section(&mut out);
+    }
+
+    let rel_count = diff.added_relationships.len() + diff.removed_relationships.len();
+    if rel_count > 0 {
+        open_section(&mut out, "archidoc-relationships", "🔗 Relationships", rel_count);
+        for rel in &diff.added_relationships {
+            out.push_str(&format!("- ➕ `{}` → `{}`: {}\n", rel.from, rel.to, rel.label));
+        }
+        for rel in &diff.removed_relationships {
+            out.push_str(&format!("- ➖ `{}` → `{}`: {}\n", rel.from, rel.to, rel.label));
+        }
+        close_section(&mut out);
+    }
+
+    out
+}
+
+fn open_section(out: &mut String, anchor: &str, title: &str, count: usize) {
+    out.push_str(&format!(
+        "<a id=\"{}\"></a>\n<details>\n<summary>{} ({})</summary>\n\n",
+        anchor, title, count
+    ));
+}
+
+fn close_section(out: &mut String) {
+    out.push_str("\n</details>\n\n");
+}
+
+/// Wrap a value in a code span, widening the fence when it contains backticks.
+fn inline_code(value: &str) -> String {
+    if value.contains('`') {
+        format!("`` {} ``", value)
+    } else {
+        format!("`{}`", value)
+    }
+}
+
+/// Inline anchor for an element, e.g. `bus.calc` -> `archidoc-bus-calc`.
+fn element_anchor(element: &str) -> String {
+    let slug: String = element
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    format!("<a id=\"archidoc-{}\"></a>", slug)
+}
+
+/// Pair removed with added elements that are probably the same element
+/// under a new path, removing the pairs from both lists.
+///
+/// Each criterion only pairs elements whose key is unique on both sides,
+/// so two modules sharing a file table are never guessed between.
+fn match_renames(removed: &mut Vec<&ModuleDoc>, added: &mut Vec<&ModuleDoc>) -> Vec<ElementRename> {
+    let mut renames = Vec::new();
+    for matched_by in ["id", "content", "files"] {
+        let unique = |docs: &[&ModuleDoc]| {
+            let mut keyed: BTreeMap<String, Option<usize>> = BTreeMap::new();
+            for (i, doc) in docs.iter().enumerate() {
+                if let Some(k) = rename_key(matched_by, doc) {
+                    keyed
+                        .entry(k)
+                        .and_modify(|slot| *slot = None)
+                        .or_insert(Some(i));
+                }
+            }
+            keyed
+        };
+        let old_keys = unique(removed);
+        let new_keys = unique(added);
+
+        let mut pairs: Vec<(usize, usize)> = old_keys
+            .iter()
+            .filter_map(|(k, old)| Some(((*old)?, new_keys.get(k).copied().flatten()?)))
+            .collect();
+        pairs.sort_unstable();
+        for &(old, new) in &pairs {
+            renames.push(ElementRename {
+                from: removed[old].module_path.clone(),
+                to: added[new].module_path.clone(),
+                matched_by: matched_by.to_string(),
+            });
+        }
+        let (old_idx, new_idx): (BTreeSet<usize>, BTreeSet<usize>) = pairs.into_iter().unzip();
+        drop_indices(removed, &old_idx);
+        drop_indices(added, &new_idx);
+    }
+
+    renames.sort_by(|a, b| a.from.cmp(&b.from));
+    renames
+}
+
+/// The value two elements must share to be paired by `matched_by`.
+fn rename_key(matched_by: &str, doc: &ModuleDoc) -> Option<String> {
+    match matched_by {
+        "id" => doc.id.clone(),
+        "content" => annotation_hash(doc),
+        _ => {
+            let mut names: Vec<&str> = doc.files.iter().map(|f| f.name.as_str()).collect();
+            names.sort_unstable();
+            (!names.is_empty()).then(|| names.join("\n"))
+        }
+    }
+}
+
+fn drop_indices(docs: &mut Vec<&ModuleDoc>, indices: &BTreeSet<usize>) {
+    *docs = docs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !indices.contains(i))
+        .map(|(_, doc)| *doc)
+        .collect();
+}
+
+fn match_reason(rename: &ElementRename) -> &'static str {
+    match rename.matched_by.as_str() {
+        "id" => "same @id",
+        "content" => "same content",
+        _ => "same files",
+    }
+}
+
+fn changed_fields(old: &ModuleDoc, new: &ModuleDoc) -> Vec<FieldChange> {
+    let pairs = [
+        ("c4_level", old.c4_level.to_string(), new.c4_level.to_string()),
+        ("pattern", old.pattern.clone(), new.pattern.clone()),
+        (
+            "pattern_status",
+            old.pattern_status.to_string(),
+            new.pattern_status.to_string(),
+        ),
+        ("description", old.description.clone(), new.description.clone()),
+        (
+            "parent_container",
+            old.parent_container.clone().unwrap_or_default(),
+            new.parent_container.clone().unwrap_or_default(),
+        ),
+    ];
+
+    pairs
+        .into_iter()
+        .filter(|(_, before, after)| before != after)
+        .map(|(field, before, after)| FieldChange {
+            field: field.to_string(),
+            before,
+            after,
+        })
+        .collect()
+}
+
+type RelKey = (String, String, String, String);
+
+/// Relationship keys with renamed endpoints replaced by their new paths.
+fn relationship_set(docs: &[ModuleDoc], renamed: &BTreeMap<&str, &str>) -> BTreeSet<RelKey> {
+    let current = |path: &str| renamed.get(path).copied().unwrap_or(path).to_string();
+    docs.iter()
+        .flat_map(|doc| {
+            doc.relationships.iter().map(move |rel| {
+                (
+                    current(&doc.module_path),
+                    current(&rel.target),
+                    rel.label.clone(),
+                    rel.protocol.clone(),
+                )
+            })
+        })
+        .collect()
+}
+
+fn to_change((from, to, label, protocol): RelKey) -> RelationshipChange {
+    RelationshipChange {
+        from,
+        to,
+        label,
+        protocol,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{ElementKind, FileEntry, HealthStatus, PatternStatus, Relationship};
+
+    fn doc(path: &str, desc: &str) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: format!("src/{}/mod.rs", path.replace('.', "/")),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            description: desc.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn uses(mut doc: ModuleDoc, target: &str) -> ModuleDoc {
+        doc.relationships.push(Relationship {
+            target: target.to_string(),
+            label: "Calls".to_string(),
+            protocol: String::new(),
+            hidden: false,
+        });
+        doc
+    }
+
+    #[test]
+    fn identical_snapshots_have_empty_diff() {
+        let docs = vec![doc("api", "API"), doc("bus", "Bus")];
+
+        assert!(diff_ir(&docs, &docs).is_empty());
+    }
+
+    #[test]
+    fn added_removed_and_changed_elements() {
+        let before = vec![doc("api", "API"), doc("legacy", "Old")];
+        let after = vec![doc("api", "REST API"), doc("bus", "Bus")];
+
+        let diff = diff_ir(&before, &after);
+
+        assert_eq!(diff.added_elements, vec!["bus"]);
+        assert_eq!(diff.removed_elements, vec!["legacy"]);
+        assert_eq!(diff.changed_elements.len(), 1);
+        assert_eq!(diff.changed_elements[0].fields[0].field, "description");
+        assert_eq!(diff.changed_elements[0].fields[0].after, "REST API");
+    }
+
+    #[test]
+    fn relationship_changes() {
+        let before = vec![uses(doc("api", "API"), "db"), doc("bus", "Bus")];
+        let after = vec![doc("api", "API"), uses(doc("bus", "Bus"), "api")];
+
+        let diff = diff_ir(&before, &after);
+
+        assert_eq!(diff.added_relationships.len(), 1);
+        assert_eq!(diff.added_relationships[0].from, "bus");
+        assert_eq!(diff.removed_relationships[0].to, "db");
+    }
+
+    fn with_files(mut doc: ModuleDoc, names: &[&str]) -> ModuleDoc {
+        doc.files = names
+            .iter()
+            .map(|name| FileEntry {
+                name: name.to_string(),
+                pattern: "--".to_string(),
+                pattern_status: PatternStatus::Planned,
+                purpose: String::new(),
+                health: HealthStatus::Planned,
+                loc: None,
+            })
+            .collect();
+        doc
+    }
+
+    #[test]
+    fn moved_element_is_a_rename_not_a_removal_and_addition() {
+        let before = vec![
+            uses(doc("api", "API"), "engine.calc"),
+            with_files(doc("engine.calc", "Calc"), &["indicator.rs", "scorer.rs"]),
+        ];
+        let after = vec![
+            uses(doc("api", "API"), "bus.calc"),
+            with_files(doc("bus.calc", "Calculations"), &["scorer.rs", "indicator.rs"]),
+        ];
+
+        let diff = diff_ir(&before, &after);
+
+        assert_eq!(
+            diff.renamed_elements,
+            vec![ElementRename {
+                from: "engine.calc".to_string(),
+                to: "bus.calc".to_string(),
+                matched_by: "files".to_string(),
+            }]
+        );
+        assert!(diff.added_elements.is_empty() && diff.removed_elements.is_empty());
+        assert!(diff.added_relationships.is_empty() && diff.removed_relationships.is_empty());
+        assert_eq!(diff.changed_elements[0].element, "bus.calc");
+        assert!(format_ir_diff(&diff).contains("> engine.calc -> bus.calc (same files)"));
+    }
+
+    #[test]
+    fn renames_match_by_id_then_content_and_skip_ambiguous_pairs() {
+        let mut gateway = doc("api", "API");
+        gateway.id = Some("gateway".to_string());
+        let mut moved_gateway = doc("edge.api", "Edge API");
+        moved_gateway.id = Some("gateway".to_string());
+        let mut cache = doc("cache", "Cache");
+        cache.content = "# Cache\n\nRead-through cache".to_string();
+        let mut moved_cache = doc("store.cache", "Cache");
+        moved_cache.content = cache.content.clone();
+        let before = vec![
+            gateway,
+            cache,
+            with_files(doc("a", "A"), &["mod.rs"]),
+            with_files(doc("b", "B"), &["mod.rs"]),
+        ];
+        let after = vec![moved_gateway, moved_cache, with_files(doc("c", "C"), &["mod.rs"])];
+
+        let diff = diff_ir(&before, &after);
+
+        let pairs: Vec<(&str, &str, &str)> = diff
+            .renamed_elements
+            .iter()
+            .map(|r| (r.from.as_str(), r.to.as_str(), r.matched_by.as_str()))
+            .collect();
+        assert_eq!(pairs, vec![("api", "edge.api", "id"), ("cache", "store.cache", "content")]);
+        assert_eq!(diff.removed_elements, vec!["a", "b"]);
+        assert_eq!(diff.added_elements, vec!["c"]);
+    }
+
+    #[test]
+    fn renames_match_stripped_content_by_its_hash() {
+        let mut cache = doc("cache", "Cache");
+        cache.content = "# Cache\n\nRead-through cache".to_string();
+        let mut moved_cache = doc("store.cache", "Cache");
+        moved_cache.content = cache.content.clone();
+        let mut before = vec![cache];
+        crate::ir::strip_content(&mut before);
+
+        let diff = diff_ir(&before, &[moved_cache]);
+
+        assert_eq!(diff.renamed_elements.len(), 1);
+        assert_eq!(diff.renamed_elements[0].matched_by, "content");
+    }
+
+    #[test]
+    fn pr_comment_has_summary_and_collapsed_sections() {
+        let before = vec![doc("api", "API"), doc("legacy", "Old")];
+        let after = vec![uses(doc("api", "REST API"), "bus.calc"), doc("bus.calc", "Calc")];
+
+        let comment = format_pr_comment(&diff_ir(&before, &after));
+
+        assert!(comment.starts_with(PR_COMMENT_MARKER));
+        assert!(comment.contains("➕ [1 added](#archidoc-added) · ➖ [1 removed](#archidoc-removed)"));
+        assert!(comment.contains("<summary>✏️ Changed elements (1)</summary>"));
+        assert!(comment.contains("- <a id=\"archidoc-bus-calc\"></a>`bus.calc`"));
+        assert!(comment.contains("  - description: `API` → `REST API`"));
+        assert!(comment.contains("- ➕ `api` → `bus.calc`: Calls"));
+    }
+
+    #[test]
+    fn pr_comment_for_unchanged_architecture() {
+        let docs = vec![doc("api", "API")];
+
+        assert!(format_pr_comment(&diff_ir(&docs, &docs)).contains("✅ No architectural changes."));
+    }
+}
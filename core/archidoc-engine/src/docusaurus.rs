@@ -0,0 +1,182 @@
+use archidoc_types::ModuleDoc;
+
+use crate::architecture::ArchitectureGenerator;
+use crate::generator::{Artifact, Generator};
+use crate::site;
+
+/// A Docusaurus doc category under `<dir>/architecture/`, to copy or point
+/// into a site's `docs/` folder: an `index.md` with the ARCHITECTURE.md
+/// sections, which Docusaurus makes the category page, and a page per
+/// container holding the container, a diagram of its dependencies, and its
+/// components. Each page has front matter with its title, sidebar
+/// position, and tags; links between elements point at the page holding
+/// the target. Diagrams need `@docusaurus/theme-mermaid`.
+pub struct DocusaurusGenerator {
+    /// The document to split into pages; its front matter is ignored.
+    pub document: ArchitectureGenerator,
+    /// Directory holding the category, relative to the output directory.
+    pub dir: String,
+}
+
+impl Generator for DocusaurusGenerator {
+    fn id(&self) -> &str {
+        "docusaurus"
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        let t = crate::i18n::strings(self.document.language);
+        let site = site::split(docs, &self.document);
+        // Every page sits in the same folder
+        let path = |to: Option<&str>| format!("{}.md", to.unwrap_or("index"));
+
+        let mut index = site::rewrite_links(&site.index, &|target| site.retarget(target, None, &path));
+        site.insert_container_index(&mut index, &self.document, &|to| path(Some(to)));
+        let mut artifacts = vec![
+            Artifact {
+                path: format!("{}/architecture/_category_.json", self.dir),
+                content: serde_json::to_string_pretty(&serde_json::json!({ "label": t.architecture_title }))
+                    .map_err(|e| e.to_string())?
+                    + "\n",
+            },
+            Artifact {
+                path: format!("{}/architecture/index.md", self.dir),
+                content: format!("{}{}", front_matter(t.architecture_title, None, 1, &[]), without_heading(&index)),
+            },
+        ];
+        for (position, (container, page)) in site.pages.iter().enumerate() {
+            let id = container.element_id();
+            let page = site::rewrite_links(page, &|target| site.retarget(target, Some(&id), &path));
+            artifacts.push(Artifact {
+                path: format!("{}/architecture/{}.md", self.dir, id),
+                content: format!(
+                    "{}{}",
+                    front_matter(&container.module_path, Some(&container.description), position + 2, &tags(container)),
+                    without_heading(&page)
+                ),
+            });
+        }
+        Ok(artifacts)
+    }
+}
+
+/// YAML front matter as Docusaurus reads it. Strings are written as JSON,
+/// which YAML reads as-is.
+fn front_matter(title: &str, description: Option<&str>, position: usize, tags: &[String]) -> String {
+    let quote = |s: &str| serde_json::to_string(s).unwrap_or_default();
+    let mut out = format!("---\ntitle: {}\n", quote(title));
+    if let Some(description) = description.filter(|d| !d.is_empty()) {
+        out.push_str(&format!("description: {}\n", quote(description)));
+    }
+    out.push_str(&format!("sidebar_position: {}\n", position));
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags.iter().map(|tag| quote(tag)).collect();
+        out.push_str(&format!("tags: [{}]\n", tags.join(", ")));
+    }
+    out.push_str("---\n\n");
+    out
+}
+
+/// Tags of a container page: its C4 level, its pattern when it has one,
+/// and its `@group`.
+fn tags(doc: &ModuleDoc) -> Vec<String> {
+    let mut tags = vec![doc.c4_level.to_string()];
+    if doc.pattern != "--" && !doc.pattern.is_empty() {
+        tags.push(doc.pattern.to_lowercase());
+    }
+    if let Some(group) = &doc.group {
+        tags.push(group.rsplit(':').next().unwrap_or(group).trim().to_lowercase());
+    }
+    tags
+}
+
+/// `markdown` without its leading `#` heading, since Docusaurus shows the
+/// front matter title instead.
+fn without_heading(markdown: &str) -> &str {
+    match markdown.split_once('\n') {
+        Some((first, rest)) if first.starts_with("# ") => rest.trim_start_matches('\n'),
+        _ => markdown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use archidoc_types::{ArchitectureConfig, DiagramConfig, ElementKind, Language, Relationship};
+
+    fn doc(path: &str, kind: ElementKind, parent: Option<&str>, uses: &[&str]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: format!("src/{}/mod.rs", path.replace('.', "/")),
+            c4_level: kind,
+            pattern: "--".to_string(),
+            description: format!("The {} element", path),
+            parent_container: parent.map(str::to_string),
+            relationships: uses
+                .iter()
+                .map(|target| Relationship {
+                    target: target.to_string(),
+                    label: "Uses".to_string(),
+                    protocol: String::new(),
+                    hidden: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn generator() -> DocusaurusGenerator {
+        DocusaurusGenerator {
+            document: ArchitectureGenerator {
+                file_name: "ARCHITECTURE.md".to_string(),
+                link_base: PathBuf::new(),
+                fitness: vec![],
+                snapshot: Default::default(),
+                language: Language::En,
+                layout: ArchitectureConfig::default(),
+                front_matter: None,
+                diagrams: DiagramConfig::default(),
+            },
+            dir: "site".to_string(),
+        }
+    }
+
+    #[test]
+    fn category_with_an_index_and_one_page_per_container() {
+        let mut bus = doc("bus", ElementKind::Container, None, &[]);
+        bus.pattern = "Observer".to_string();
+        bus.group = Some("domain:Messaging".to_string());
+        let docs = vec![
+            doc("api", ElementKind::Container, None, &["bus.lanes"]),
+            bus,
+            doc("bus.lanes", ElementKind::Component, Some("bus"), &[]),
+            doc("customer", ElementKind::Person, None, &["api"]),
+        ];
+
+        let artifacts = generator().generate(&docs).unwrap();
+        let paths: Vec<&str> = artifacts.iter().map(|a| a.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "site/architecture/_category_.json",
+                "site/architecture/index.md",
+                "site/architecture/api.md",
+                "site/architecture/bus.md"
+            ]
+        );
+        assert_eq!(artifacts[0].content, "{\n  \"label\": \"Architecture Context\"\n}\n");
+
+        let index = &artifacts[1].content;
+        assert!(index.starts_with("---\ntitle: \"Architecture Context\"\nsidebar_position: 1\n---\n\n> "), "{}", index);
+        assert!(index.contains("- [api](api.md) — The api element\n"));
+        assert!(index.contains("### customer\n"));
+
+        let bus = &artifacts[3].content;
+        assert!(bus.starts_with(
+            "---\ntitle: \"bus\"\ndescription: \"The bus element\"\nsidebar_position: 3\ntags: [\"container\", \"observer\", \"messaging\"]\n---\n\nThe bus element\n"
+        ), "{}", bus);
+        let api = &artifacts[2].content;
+        assert!(api.contains("[bus.lanes](bus.md#bus_lanes)"));
+        assert!(api.contains("[customer](index.md#customer)"));
+    }
+}
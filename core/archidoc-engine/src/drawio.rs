@@ -2,50 +2,91 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
-use archidoc_types::{C4Level, ModuleDoc};
+use archidoc_types::{DiagramConfig, EdgeStyle, ElementKind, LineStyle, ModuleDoc, Relationship};
+
+use crate::generator::{Artifact, Generator};
+use crate::mermaid::{container_edges, drawn_relationships};
+
+/// File name of the draw.io container CSV.
+pub const CONTAINER_FILE: &str = "c4-container.csv";
+/// File name of the draw.io component CSV.
+pub const COMPONENT_FILE: &str = "c4-component.csv";
+
+/// draw.io container and component CSV files under `drawio/`.
+pub struct DrawIoGenerator {
+    pub diagrams: DiagramConfig,
+}
+
+impl Generator for DrawIoGenerator {
+    fn id(&self) -> &str {
+        "drawio"
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        Ok(vec![
+            Artifact {
+                path: format!("drawio/{}", CONTAINER_FILE),
+                content: container_csv(docs, &self.diagrams),
+            },
+            Artifact {
+                path: format!("drawio/{}", COMPONENT_FILE),
+                content: component_csv(docs, &self.diagrams),
+            },
+        ])
+    }
+}
 
 /// Generate draw.io container CSV.
 pub fn generate_container_csv(output_dir: &Path, docs: &[ModuleDoc]) {
-    let filepath = output_dir.join("c4-container.csv");
+    let filepath = output_dir.join(CONTAINER_FILE);
+    fs::write(&filepath, container_csv(docs, &DiagramConfig::default())).expect("Failed to write container CSV");
+}
 
-    let containers: Vec<&ModuleDoc> = docs
-        .iter()
-        .filter(|d| d.c4_level == C4Level::Container)
-        .collect();
+/// Render the draw.io container CSV. People and systems outside the
+/// boundary get their own `type`, and so their own style.
+pub fn container_csv(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
+    let containers: Vec<&ModuleDoc> = docs.iter().filter(|d| d.c4_level.in_container_view()).collect();
 
+    let edges = container_edges(docs, &containers, diagrams);
     let mut rows = Vec::new();
 
     // Build a map of module_path -> list of relationship targets
     for doc in &containers {
-        let refs: Vec<String> = doc.relationships.iter().map(|r| r.target.clone()).collect();
-        let refs_str = refs.join(",");
+        let rels = edges.iter().filter(|(from, _)| *from == doc.module_path).map(|(_, r)| r);
+        let (refs, styled) = ref_columns(docs, rels, diagrams);
 
+        let style = if doc.c4_level.is_external() { doc.c4_level.to_string() } else { "container".to_string() };
         rows.push(format!(
-            "{},{},container,{},{},{}",
-            doc.module_path,
+            "{},{},{},{},{}{},{}",
+            doc.element_id(),
             to_title_case(&doc.module_path),
+            style,
             doc.pattern,
             doc.description,
-            refs_str,
+            styled,
+            refs.join(","),
         ));
     }
 
-    let content = format!(
-        "{}\nid,name,type,pattern,description,refs\n{}",
-        csv_header(),
+    format!(
+        "{}\nid,name,type,pattern,description{},refs\n{}",
+        csv_header(diagrams),
+        styled_headers(diagrams),
         rows.join("\n")
-    );
-
-    fs::write(&filepath, content).expect("Failed to write container CSV");
+    )
 }
 
 /// Generate draw.io component CSV.
 pub fn generate_component_csv(output_dir: &Path, docs: &[ModuleDoc]) {
-    let filepath = output_dir.join("c4-component.csv");
+    let filepath = output_dir.join(COMPONENT_FILE);
+    fs::write(&filepath, component_csv(docs, &DiagramConfig::default())).expect("Failed to write component CSV");
+}
 
+/// Render the draw.io component CSV.
+pub fn component_csv(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
     let components: Vec<&ModuleDoc> = docs
         .iter()
-        .filter(|d| d.c4_level == C4Level::Component)
+        .filter(|d| d.c4_level == ElementKind::Component)
         .collect();
 
     // Group by parent
@@ -63,9 +104,10 @@ pub fn generate_component_csv(output_dir: &Path, docs: &[ModuleDoc]) {
     // Add container stubs for grouping
     for parent in grouped.keys() {
         rows.push(format!(
-            "{},{},container,,,",
-            parent,
+            "{},{},container,,{},",
+            ModuleDoc::id_of(docs, parent),
             to_title_case(parent),
+            ",".repeat(diagrams.edge_styles.len()),
         ));
     }
 
@@ -74,57 +116,121 @@ pub fn generate_component_csv(output_dir: &Path, docs: &[ModuleDoc]) {
         let name = doc
             .module_path
             .split('.')
-            .last()
+            .next_back()
             .unwrap_or(&doc.module_path);
         let parent = doc
             .parent_container
             .as_deref()
-            .unwrap_or("");
-        let refs: Vec<String> = doc.relationships.iter().map(|r| r.target.clone()).collect();
+            .map(|p| ModuleDoc::id_of(docs, p))
+            .unwrap_or_default();
+        let has_refs = drawn_relationships(docs, doc).next().is_some();
+        let (refs, styled) = ref_columns(docs, drawn_relationships(docs, doc), diagrams);
 
         rows.push(format!(
-            "{},{},component,{},{},{}",
-            doc.module_path,
+            "{},{},component,{},{}{},{}",
+            doc.element_id(),
             name,
             doc.pattern,
             doc.description,
-            if refs.is_empty() {
-                parent.to_string()
-            } else {
+            styled,
+            if has_refs {
                 refs.join(",")
+            } else {
+                parent
             },
         ));
     }
 
-    let content = format!(
-        "{}\nid,name,type,pattern,description,refs\n{}",
-        csv_header(),
+    format!(
+        "{}\nid,name,type,pattern,description{},refs\n{}",
+        csv_header(diagrams),
+        styled_headers(diagrams),
         rows.join("\n")
-    );
+    )
+}
 
-    fs::write(&filepath, content).expect("Failed to write component CSV");
+/// The targets of `rels` drawn with the default arrow, and the cells of the
+/// `refs_<n>` columns, one per `[diagrams.edge_styles]` entry, each a
+/// leading comma and the quoted targets of the arrows in that style.
+fn ref_columns<'a>(
+    docs: &[ModuleDoc],
+    rels: impl Iterator<Item = &'a Relationship>,
+    diagrams: &DiagramConfig,
+) -> (Vec<String>, String) {
+    let mut refs = Vec::new();
+    let mut styled = vec![Vec::new(); diagrams.edge_styles.len()];
+    for rel in rels {
+        let target = ModuleDoc::id_of(docs, &rel.target);
+        let column = diagrams
+            .edge_styles
+            .keys()
+            .position(|name| !rel.protocol.is_empty() && name.eq_ignore_ascii_case(&rel.protocol));
+        match column {
+            Some(n) => styled[n].push(target),
+            None => refs.push(target),
+        }
+    }
+    let cells = styled.iter().map(|targets| format!(",\"{}\"", targets.join(","))).collect();
+    (refs, cells)
+}
+
+/// Header names of the `refs_<n>` columns, each with a leading comma.
+fn styled_headers(diagrams: &DiagramConfig) -> String {
+    (1..=diagrams.edge_styles.len()).map(|n| format!(",refs_{}", n)).collect()
+}
+
+/// draw.io edge style for an `[diagrams.edge_styles]` entry.
+fn edge_style(style: &EdgeStyle) -> String {
+    let mut out = String::new();
+    if let Some(color) = &style.color {
+        out.push_str(&format!("strokeColor={};fontColor={};", color, color));
+    }
+    out.push_str(match style.line {
+        LineStyle::Solid => "",
+        LineStyle::Dashed => "dashed=1;",
+        LineStyle::Dotted => "dashed=1;dashPattern=1 4;",
+        LineStyle::Bold => "strokeWidth=3;",
+    });
+    out
+}
+
+/// The CSV import header, with a `connect` rule per `[diagrams.edge_styles]`
+/// entry drawing its `refs_<n>` column in that style.
+fn csv_header(diagrams: &DiagramConfig) -> String {
+    let mut connects = String::new();
+    let mut ignored = String::new();
+    for (n, style) in diagrams.edge_styles.values().enumerate() {
+        connects.push_str(&format!(
+            "\n# connect: {{\"from\": \"refs_{}\", \"to\": \"id\", \"invert\": false, \"style\": \"curved=1;exitX=0.5;exitY=1;entryX=0.5;entryY=0;{}\"}}",
+            n + 1,
+            edge_style(style)
+        ));
+        ignored.push_str(&format!(",refs_{}", n + 1));
+    }
+    CSV_HEADER.replace("{connects}", &connects).replace("{ignored}", &ignored)
 }
 
-fn csv_header() -> &'static str {
-    r#"## C4 Diagram
+const CSV_HEADER: &str = r#"## C4 Diagram
 ## Import: Arrange > Insert > Advanced > CSV
 #
 # label: <b>%name%</b><br><font style="font-size:11px;">%description%</font>
 # stylename: type
 # styles: {"container": "rounded=1;whiteSpace=wrap;fillColor=#438DD5;fontColor=#ffffff;", \
-#          "component": "rounded=1;whiteSpace=wrap;fillColor=#85BBF0;fontColor=#000000;"}
-# connect: {"from": "refs", "to": "id", "invert": false, "style": "curved=1;exitX=0.5;exitY=1;entryX=0.5;entryY=0;"}
+#          "component": "rounded=1;whiteSpace=wrap;fillColor=#85BBF0;fontColor=#000000;", \
+#          "context": "rounded=1;whiteSpace=wrap;fillColor=#1168BD;fontColor=#ffffff;", \
+#          "person": "shape=actor;whiteSpace=wrap;fillColor=#08427B;fontColor=#ffffff;", \
+#          "external_system": "rounded=1;whiteSpace=wrap;fillColor=#999999;fontColor=#ffffff;"}
+# connect: {"from": "refs", "to": "id", "invert": false, "style": "curved=1;exitX=0.5;exitY=1;entryX=0.5;entryY=0;"}{connects}
 # width: 200
 # height: 100
 # padding: 30
-# ignore: id,refs,type,pattern
+# ignore: id,refs{ignored},type,pattern
 # identity: id
-# namespace: c4"#
-}
+# namespace: c4"#;
 
 fn to_title_case(s: &str) -> String {
     s.split('.')
-        .last()
+        .next_back()
         .unwrap_or(s)
         .split('_')
         .map(|word| {
@@ -137,3 +243,51 @@ fn to_title_case(s: &str) -> String {
         .collect::<Vec<_>>()
         .join(" ")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{EdgeStyle, Relationship};
+
+    fn doc(path: &str, kind: ElementKind, parent: Option<&str>, uses: &[(&str, &str)]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            c4_level: kind,
+            parent_container: parent.map(str::to_string),
+            relationships: uses
+                .iter()
+                .map(|(target, protocol)| Relationship {
+                    target: target.to_string(),
+                    label: "Uses".to_string(),
+                    protocol: protocol.to_string(),
+                    hidden: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn edge_styles_get_their_own_connect_columns() {
+        let docs = vec![
+            doc("api", ElementKind::Container, None, &[("bus", "Channel"), ("db", "SQL"), ("auth", "")]),
+            doc("bus", ElementKind::Container, None, &[]),
+            doc("db", ElementKind::Container, None, &[]),
+            doc("auth", ElementKind::Container, None, &[]),
+        ];
+        let mut diagrams = DiagramConfig::default();
+        diagrams.edge_styles.insert(
+            "channel".to_string(),
+            EdgeStyle { color: Some("green".to_string()), line: LineStyle::Dashed },
+        );
+        diagrams.edge_styles.insert("sql".to_string(), EdgeStyle { color: None, line: LineStyle::Bold });
+
+        let csv = container_csv(&docs, &diagrams);
+
+        assert!(csv.contains("\n# connect: {\"from\": \"refs_1\", \"to\": \"id\", \"invert\": false, \"style\": \"curved=1;exitX=0.5;exitY=1;entryX=0.5;entryY=0;strokeColor=green;fontColor=green;dashed=1;\"}"));
+        assert!(csv.contains("strokeWidth=3;\"}\n# width"));
+        assert!(csv.contains("# ignore: id,refs,refs_1,refs_2,type,pattern\n"));
+        assert!(csv.contains("\nid,name,type,pattern,description,refs_1,refs_2,refs\napi,Api,container,,,\"bus\",\"db\",auth\n"), "{}", csv);
+        assert!(!container_csv(&docs, &DiagramConfig::default()).contains("refs_1"));
+    }
+}
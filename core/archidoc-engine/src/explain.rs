@@ -0,0 +1,266 @@
+/// A finding code and what `archidoc explain` prints for it. Codes are
+/// stable: a retired check keeps its code, and new checks take the next
+/// free one in their family (AD1xx validation, AD2xx lint, AD3xx fitness).
+pub struct Explanation {
+    pub code: &'static str,
+    /// Rule id or fitness function name the code stands for.
+    pub rule: &'static str,
+    /// The flag whose run reports the finding.
+    pub check: &'static str,
+    pub title: &'static str,
+    pub cause: &'static str,
+    pub impact: &'static str,
+    pub fix: &'static str,
+}
+
+/// Every finding code, in code order.
+pub const CODES: &[Explanation] = &[
+    Explanation {
+        code: "AD101",
+        rule: "ghost",
+        check: "--validate",
+        title: "ghost entry",
+        cause: "A file table lists a file that does not exist in the module's directory. The file was usually renamed or deleted without updating the annotation.",
+        impact: "ARCHITECTURE.md describes code that is not there, and health and pattern figures count a file nobody can open. An error by default.",
+        fix: "Remove the row, or rename it to the file's new name. Mark files not written yet with `planned` health until they exist. `[validate] ghost` sets the severity.",
+    },
+    Explanation {
+        code: "AD102",
+        rule: "orphan",
+        check: "--validate",
+        title: "orphan file",
+        cause: "A source file in a cataloged module's directory has no row in the module's file table.",
+        impact: "The file is invisible in ARCHITECTURE.md and in health reports. A warning by default; `--warnings-as-errors` fails the run on it.",
+        fix: "Add a row for the file: `archidoc catalog` lists the missing rows and `archidoc catalog --apply` writes them. `[validate] orphan` sets the severity.",
+    },
+    Explanation {
+        code: "AD103",
+        rule: "invalid_link",
+        check: "--validate",
+        title: "invalid doc link",
+        cause: "An `@doc` annotation's URL is not a well-formed http or https URL.",
+        impact: "The link is left out of the Links section of ARCHITECTURE.md. An error by default.",
+        fix: "Write the full URL, scheme included, e.g. `@doc https://wiki.example.com/bus \"Runbook\"`. `[validate] invalid_link` sets the severity.",
+    },
+    Explanation {
+        code: "AD201",
+        rule: "description_placeholder",
+        check: "--lint",
+        title: "placeholder description",
+        cause: "The element's description is empty or a placeholder such as `*No description*`, usually left by a scaffolding command.",
+        impact: "Readers of ARCHITECTURE.md and the AI context learn nothing about the element.",
+        fix: "Replace the placeholder with a sentence on what the element is responsible for.",
+    },
+    Explanation {
+        code: "AD202",
+        rule: "description_todo",
+        check: "--lint",
+        title: "TODO marker in description",
+        cause: "The element's description contains TODO, FIXME, XXX, or TBD.",
+        impact: "Unfinished notes are published as the element's documentation.",
+        fix: "Finish the description, and move open work to an issue tracker or a `@risk` annotation.",
+    },
+    Explanation {
+        code: "AD203",
+        rule: "description_too_short",
+        check: "--lint",
+        title: "description too short",
+        cause: "The element's description is shorter than `[lint] min_description_length` (10 characters by default).",
+        impact: "A one-word description rarely says more than the element's name.",
+        fix: "Say what the element does and for whom, or lower `[lint] min_description_length`.",
+    },
+    Explanation {
+        code: "AD204",
+        rule: "description_duplicate",
+        check: "--lint",
+        title: "duplicate description",
+        cause: "Two or more elements share the same description, ignoring case; usually a copied annotation block.",
+        impact: "Readers cannot tell the elements apart from their descriptions.",
+        fix: "Describe what sets each element apart.",
+    },
+    Explanation {
+        code: "AD205",
+        rule: "unknown_target",
+        check: "--lint",
+        title: "unknown relationship target",
+        cause: "A `@c4 uses` target or `@flow` step names neither an element nor an entry of `[lint] external_systems`. The finding suggests the closest known name when one is near.",
+        impact: "Diagrams draw a dangling arrow to an element nobody declared.",
+        fix: "Correct the module path, annotate the missing element, or add the name to `[lint] external_systems` for databases and third-party services.",
+    },
+    Explanation {
+        code: "AD206",
+        rule: "spelling",
+        check: "--lint",
+        title: "misspelling",
+        cause: "A narrative or file purpose contains a word from the built-in list of common misspellings. Runs with `[lint] spelling = true`.",
+        impact: "Misspellings end up in the published documentation and make it harder to search.",
+        fix: "Use the suggested correction, or add the word to `[lint] ignore_words` when it is spelled as intended.",
+    },
+    Explanation {
+        code: "AD301",
+        rule: "all_strategy_modules_define_a_trait",
+        check: "--fitness",
+        title: "Strategy without a trait",
+        cause: "A module declared with the Strategy pattern defines no trait in its directory.",
+        impact: "The pattern label promises interchangeable implementations the code does not offer.",
+        fix: "Define the strategy trait, or change the pattern to the one the module actually follows.",
+    },
+    Explanation {
+        code: "AD302",
+        rule: "all_facade_modules_reexport_submodules",
+        check: "--fitness",
+        title: "Facade without re-exports",
+        cause: "A module declared with the Facade pattern has no `pub use` re-exports or `pub mod` declarations.",
+        impact: "Callers have no single entry point and reach into the module's internals.",
+        fix: "Re-export the module's public surface from its root, or change the pattern. `[heuristics] facade_min_pub_mods` sets how many `pub mod` declarations count without re-exports.",
+    },
+    Explanation {
+        code: "AD303",
+        rule: "all_observer_modules_have_channels_or_callbacks",
+        check: "--fitness",
+        title: "Observer without channels or callbacks",
+        cause: "A module declared with the Observer pattern has no channel types or callback parameters.",
+        impact: "The pattern label promises event notification the code does not implement.",
+        fix: "Notify subscribers through a channel or callback, or change the pattern.",
+    },
+    Explanation {
+        code: "AD304",
+        rule: "all_repository_modules_expose_crud_operations",
+        check: "--fitness",
+        title: "Repository without query and mutation operations",
+        cause: "A module declared with the Repository pattern has no trait or storage-backed type offering both query and mutation methods.",
+        impact: "Storage access is likely spread over callers instead of going through the repository.",
+        fix: "Put the queries and mutations on one repository type or trait, or change the pattern.",
+    },
+    Explanation {
+        code: "AD305",
+        rule: "all_state_modules_define_transitions",
+        check: "--fitness",
+        title: "State without transitions",
+        cause: "A module declared with the State pattern has neither match-driven enum transitions nor a swapped trait-object state.",
+        impact: "The pattern label promises a state machine the code does not have.",
+        fix: "Model the states and their transitions explicitly, or change the pattern.",
+    },
+    Explanation {
+        code: "AD306",
+        rule: "all_template_method_modules_define_hook_calls",
+        check: "--fitness",
+        title: "Template Method without hook calls",
+        cause: "A module declared with the Template Method pattern has no trait whose default methods call its required methods.",
+        impact: "The pattern label promises a fixed algorithm with overridable steps the code does not have.",
+        fix: "Have a default trait method drive the algorithm through required hook methods, or change the pattern.",
+    },
+    Explanation {
+        code: "AD307",
+        rule: "all_component_public_items_are_used",
+        check: "--fitness",
+        title: "unused public item",
+        cause: "A component has a public item nothing outside the component references.",
+        impact: "The component's surface grows beyond what its callers need (facade creep), and dead code hides behind `pub`.",
+        fix: "Make the item private or `pub(crate)`, or remove it.",
+    },
+    Explanation {
+        code: "AD308",
+        rule: "every_container_is_connected_or_standalone",
+        check: "--fitness",
+        title: "unconnected container",
+        cause: "A top-level container has no relationship in or out, counting those of its components.",
+        impact: "The container floats unconnected in the container diagram; usually its annotations were stubbed and never wired into the system.",
+        fix: "Declare its dependencies with `@c4 uses`, or mark it `@standalone` when it is unconnected on purpose.",
+    },
+    Explanation {
+        code: "AD309",
+        rule: "no_component_depends_on_another_containers_internals",
+        check: "--fitness",
+        title: "dependency on another container's internals",
+        cause: "An element depends, through a relationship or a `use crate::...` import, on a component inside another container.",
+        impact: "The other container's facade is bypassed, so its internals cannot change without breaking the caller.",
+        fix: "Depend on the container itself. List accepted exceptions in `[fitness] allow_cross_container` as `\"from -> to\"`.",
+    },
+];
+
+/// The explanation for `query`: a code (any case) or the rule it stands for.
+pub fn lookup(query: &str) -> Option<&'static Explanation> {
+    let query = query.trim();
+    CODES.iter().find(|e| e.code.eq_ignore_ascii_case(query) || e.rule == query)
+}
+
+/// Code of a lint rule, validation finding kind, or fitness function.
+pub fn code_of(rule: &str) -> Option<&'static str> {
+    CODES.iter().find(|e| e.rule == rule).map(|e| e.code)
+}
+
+/// `rule` in brackets, led by its code when it has one, e.g.
+/// `[AD205 unknown_target]`.
+pub fn tag(rule: &str) -> String {
+    match code_of(rule) {
+        Some(code) => format!("[{} {}]", code, rule),
+        None => format!("[{}]", rule),
+    }
+}
+
+/// The text `archidoc explain CODE` prints.
+pub fn format_explanation(explanation: &Explanation) -> String {
+    format!(
+        "{}: {}\nReported by {} as `{}`.\n\nCause:\n  {}\n\nImpact:\n  {}\n\nFix:\n  {}\n",
+        explanation.code,
+        explanation.title,
+        explanation.check,
+        explanation.rule,
+        explanation.cause,
+        explanation.impact,
+        explanation.fix
+    )
+}
+
+/// One line per code, for `archidoc explain` without a code.
+pub fn format_index() -> String {
+    CODES
+        .iter()
+        .map(|e| format!("{}  {:<10}  {}\n", e.code, e.check, e.title))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_unique_and_ordered() {
+        let codes: Vec<&str> = CODES.iter().map(|e| e.code).collect();
+        let mut sorted = codes.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(codes, sorted);
+        assert!(CODES.iter().all(|e| e.code.len() == 5 && e.code.starts_with("AD")));
+    }
+
+    #[test]
+    fn every_lint_rule_and_validation_finding_has_a_code() {
+        for rule in [
+            "ghost",
+            "orphan",
+            "invalid_link",
+            "description_placeholder",
+            "description_todo",
+            "description_too_short",
+            "description_duplicate",
+            "unknown_target",
+            "spelling",
+        ] {
+            assert!(code_of(rule).is_some(), "{} has no code", rule);
+        }
+    }
+
+    #[test]
+    fn lookup_accepts_codes_in_any_case_and_rule_names() {
+        assert_eq!(lookup("ad101").map(|e| e.rule), Some("ghost"));
+        assert_eq!(lookup("unknown_target").map(|e| e.code), Some("AD205"));
+        assert!(lookup("AD999").is_none());
+        assert_eq!(tag("spelling"), "[AD206 spelling]");
+        assert_eq!(tag("custom"), "[custom]");
+
+        let text = format_explanation(lookup("AD101").unwrap());
+        assert!(text.starts_with("AD101: ghost entry\nReported by --validate as `ghost`.\n\nCause:\n  A file table lists"));
+    }
+}
@@ -0,0 +1,67 @@
+use archidoc_types::{Flow, ModuleDoc};
+
+/// One numbered arrow of a flow. The label and protocol come from the
+/// relationship `from` declares to `to`, and are empty when there is none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowStep<'a> {
+    /// 1-based position in the flow.
+    pub index: usize,
+    pub from: &'a str,
+    pub to: &'a str,
+    pub label: &'a str,
+    pub protocol: &'a str,
+}
+
+impl FlowStep<'_> {
+    /// Arrow text in sequence diagrams: the step number, then the label and
+    /// protocol when known, e.g. `1. Charges card (HTTPS)`.
+    pub fn message(&self) -> String {
+        let mut message = format!("{}.", self.index);
+        if !self.label.is_empty() {
+            message.push_str(&format!(" {}", self.label));
+        }
+        if !self.protocol.is_empty() {
+            message.push_str(&format!(" ({})", self.protocol));
+        }
+        message
+    }
+}
+
+/// Every `@flow` in the project, in element order.
+pub fn all_flows(docs: &[ModuleDoc]) -> Vec<&Flow> {
+    docs.iter().flat_map(|d| &d.flows).collect()
+}
+
+/// The arrows between consecutive steps of `flow`, numbered from 1.
+pub fn flow_steps<'a>(docs: &'a [ModuleDoc], flow: &'a Flow) -> Vec<FlowStep<'a>> {
+    flow.steps
+        .windows(2)
+        .enumerate()
+        .map(|(i, pair)| {
+            let (from, to) = (pair[0].as_str(), pair[1].as_str());
+            let rel = docs
+                .iter()
+                .filter(|d| d.module_path == from)
+                .flat_map(|d| &d.relationships)
+                .find(|r| r.target == to);
+            FlowStep {
+                index: i + 1,
+                from,
+                to,
+                label: rel.map_or("", |r| r.label.as_str()),
+                protocol: rel.map_or("", |r| r.protocol.as_str()),
+            }
+        })
+        .collect()
+}
+
+/// The distinct steps of `flow`, in order of first appearance.
+pub fn participants(flow: &Flow) -> Vec<&str> {
+    let mut seen: Vec<&str> = Vec::new();
+    for step in &flow.steps {
+        if !seen.contains(&step.as_str()) {
+            seen.push(step);
+        }
+    }
+    seen
+}
@@ -0,0 +1,231 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use archidoc_types::{ApprovalReport, ApprovedSnapshot, FileEntry, ModuleDoc};
+
+/// Location of the approved snapshot, relative to the project root.
+pub const APPROVED_PATH: &str = ".archidoc/approved.json";
+
+/// Build an approved snapshot of the current architecture.
+///
+/// Elements keep only what [`check_approved`] compares — path, kind,
+/// parent, relationships, and the `@id`, content hash, and file names that
+/// pair renames — with source files relative to `root`, so the committed
+/// snapshot is the same on every machine.
+pub fn freeze(docs: &[ModuleDoc], root: &Path, reviewer: &str, approved_at: &str) -> ApprovedSnapshot {
+    let mut elements: Vec<ModuleDoc> = docs.iter().map(|doc| structural(doc, root)).collect();
+    elements.sort_by(|a, b| a.module_path.cmp(&b.module_path));
+
+    ApprovedSnapshot {
+        reviewer: reviewer.to_string(),
+        approved_at: approved_at.to_string(),
+        elements,
+    }
+}
+
+/// The fields of `doc` an approval compares.
+fn structural(doc: &ModuleDoc, root: &Path) -> ModuleDoc {
+    ModuleDoc {
+        module_path: doc.module_path.clone(),
+        content_hash: crate::hash::annotation_hash(doc),
+        source_file: crate::reproducible::relative(&doc.source_file, root),
+        c4_level: doc.c4_level.clone(),
+        parent_container: doc.parent_container.clone(),
+        relationships: doc.relationships.clone(),
+        files: doc
+            .files
+            .iter()
+            .map(|file| FileEntry {
+                name: file.name.clone(),
+                pattern: String::new(),
+                pattern_status: Default::default(),
+                purpose: String::new(),
+                health: Default::default(),
+                loc: None,
+            })
+            .collect(),
+        id: doc.id.clone(),
+        ..Default::default()
+    }
+}
+
+/// Write an approved snapshot to `.archidoc/approved.json` under `root`.
+///
+/// Returns the path written.
+pub fn write_approved(root: &Path, snapshot: &ApprovedSnapshot) -> Result<PathBuf, String> {
+    let path = root.join(APPROVED_PATH);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("failed to create {}: {}", parent.display(), e))?;
+    }
+
+    let json = serde_json::to_string_pretty(snapshot)
+        .map_err(|e| format!("failed to serialize snapshot: {}", e))?;
+    fs::write(&path, json + "\n").map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+
+    Ok(path)
+}
+
+/// Read the approved snapshot from `.archidoc/approved.json` under `root`.
+pub fn read_approved(root: &Path) -> Result<ApprovedSnapshot, String> {
+    let path = root.join(APPROVED_PATH);
+    let json = fs::read_to_string(&path).map_err(|e| {
+        format!(
+            "no approved snapshot at {} ({}); run `archidoc freeze` first",
+            path.display(),
+            e
+        )
+    })?;
+
+    serde_json::from_str(&json).map_err(|e| format!("invalid snapshot {}: {}", path.display(), e))
+}
+
+/// Check the current architecture against an approved snapshot.
+///
+/// Structural divergence is any added, removed, or renamed element, or any
+/// new dependency that crosses a container boundary. Renames are reported
+/// once rather than as a removal plus an addition, and dependencies that
+/// only follow a renamed element are not new. Descriptions, patterns,
+/// file tables, and dependencies within one container may change freely.
+pub fn check_approved(approved: &ApprovedSnapshot, current: &[ModuleDoc]) -> ApprovalReport {
+    let diff = crate::diff::diff_ir(&approved.elements, current);
+
+    // Approved edges under current paths, so renames do not look like new edges
+    let current_path = |path: &str| {
+        diff.renamed_elements
+            .iter()
+            .find(|r| r.from == path)
+            .map_or_else(|| path.to_string(), |r| r.to.clone())
+    };
+    let approved_edges: BTreeSet<(String, String)> = approved
+        .elements
+        .iter()
+        .flat_map(|d| {
+            d.relationships
+                .iter()
+                .map(|r| (current_path(&d.module_path), current_path(&r.target)))
+        })
+        .collect();
+
+    let mut seen = BTreeSet::new();
+    let new_dependencies = diff
+        .added_relationships
+        .into_iter()
+        .filter(|rel| !approved_edges.contains(&(rel.from.clone(), rel.to.clone())))
+        .filter(|rel| container_of(&rel.from, current) != container_of(&rel.to, current))
+        .filter(|rel| seen.insert((rel.from.clone(), rel.to.clone())))
+        .collect();
+
+    ApprovalReport {
+        reviewer: approved.reviewer.clone(),
+        approved_at: approved.approved_at.clone(),
+        new_elements: diff.added_elements,
+        removed_elements: diff.removed_elements,
+        renamed_elements: diff.renamed_elements,
+        new_dependencies,
+    }
+}
+
+/// Format an approval report as human-readable text.
+pub fn format_approval_report(report: &ApprovalReport) -> String {
+    let mut out = String::new();
+
+    if report.is_approved() {
+        out.push_str(&format!(
+            "Architecture matches snapshot approved by {} on {}.\n",
+            report.reviewer, report.approved_at
+        ));
+        return out;
+    }
+
+    out.push_str(&format!(
+        "Architecture diverges from snapshot approved by {} on {}.\n\n",
+        report.reviewer, report.approved_at
+    ));
+
+    if !report.new_elements.is_empty() {
+        out.push_str(&format!("New elements ({}):\n", report.new_elements.len()));
+        for element in &report.new_elements {
+            out.push_str(&format!("  {}\n", element));
+        }
+    }
+
+    if !report.removed_elements.is_empty() {
+        out.push_str(&format!(
+            "Removed elements ({}):\n",
+            report.removed_elements.len()
+        ));
+        for element in &report.removed_elements {
+            out.push_str(&format!("  {}\n", element));
+        }
+    }
+
+    if !report.renamed_elements.is_empty() {
+        out.push_str(&format!(
+            "Renamed elements ({}):\n",
+            report.renamed_elements.len()
+        ));
+        for rename in &report.renamed_elements {
+            out.push_str(&format!("  {} -> {}\n", rename.from, rename.to));
+        }
+    }
+
+    if !report.new_dependencies.is_empty() {
+        out.push_str(&format!(
+            "New cross-container dependencies ({}):\n",
+            report.new_dependencies.len()
+        ));
+        for rel in &report.new_dependencies {
+            out.push_str(&format!("  {} -> {} ({})\n", rel.from, rel.to, rel.label));
+        }
+    }
+
+    out.push_str("\nRun `archidoc freeze --reviewer <name>` to approve.\n");
+    out
+}
+
+/// The container an element belongs to: its parent container, or itself
+/// for top-level elements. Unknown targets are treated as top-level.
+fn container_of<'a>(path: &'a str, docs: &'a [ModuleDoc]) -> &'a str {
+    docs.iter()
+        .find(|d| d.module_path == path)
+        .and_then(|d| d.parent_container.as_deref())
+        .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{ElementKind, HealthStatus};
+
+    #[test]
+    fn snapshots_keep_structure_with_relative_paths() {
+        let doc = ModuleDoc {
+            module_path: "api".to_string(),
+            content: "@c4 container\n\nREST gateway".to_string(),
+            source_file: "/home/ana/shop/src/api/mod.rs".to_string(),
+            c4_level: ElementKind::Container,
+            description: "REST gateway".to_string(),
+            files: vec![FileEntry {
+                name: "routes.rs".to_string(),
+                pattern: "Facade".to_string(),
+                pattern_status: Default::default(),
+                purpose: "HTTP routes".to_string(),
+                health: HealthStatus::Stable,
+                loc: Some(120),
+            }],
+            ..Default::default()
+        };
+
+        let snapshot = freeze(std::slice::from_ref(&doc), Path::new("/home/ana/shop"), "ana", "2026-02-14");
+        let element = &snapshot.elements[0];
+
+        assert_eq!(element.source_file, "src/api/mod.rs");
+        assert!(element.content.is_empty() && element.description.is_empty());
+        assert_eq!(element.content_hash, crate::hash::annotation_hash(&doc));
+        assert_eq!(element.files[0].name, "routes.rs");
+        assert!(element.files[0].purpose.is_empty());
+        assert!(check_approved(&snapshot, &[doc]).is_approved());
+    }
+}
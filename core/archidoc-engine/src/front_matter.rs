@@ -0,0 +1,101 @@
+use archidoc_types::ModuleDoc;
+
+/// Front matter settings for a run, from `[front_matter]` in
+/// `archidoc.toml` plus the run's timestamp.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontMatterSettings {
+    /// Navigation weight of ARCHITECTURE.md; module READMEs follow it.
+    pub weight: i64,
+    /// `YYYY-MM-DDTHH:MM:SSZ`, see [`crate::date::now_timestamp`]; `None`
+    /// for reproducible output.
+    pub generated_at: Option<String>,
+}
+
+/// Render a YAML front matter block for a generated page, as read by Hugo,
+/// Docusaurus, and MkDocs. `ir_hash` identifies the IR the page was
+/// generated from (see [`ir_hash`]).
+pub fn render(title: &str, ir_hash: &str, weight: i64, settings: &FrontMatterSettings) -> String {
+    let generated_at = settings
+        .generated_at
+        .as_ref()
+        .map(|at| format!("generated_at: {}\n", at))
+        .unwrap_or_default();
+    format!(
+        "---\ntitle: \"{}\"\n{}ir_hash: {}\nweight: {}\n---\n\n",
+        title.replace('\\', "\\\\").replace('"', "\\\""),
+        generated_at,
+        ir_hash,
+        weight
+    )
+}
+
+/// Hash of the JSON IR (see [`crate::hash::bytes_hash`]), so a page can be
+/// traced back to the architecture it describes.
+pub fn ir_hash(docs: &[ModuleDoc]) -> String {
+    crate::hash::bytes_hash(crate::ir::serialize(docs).as_bytes())
+}
+
+/// `text` without its front matter block, if it has one.
+pub fn strip(text: &str) -> &str {
+    text.strip_prefix("---\n")
+        .and_then(|rest| rest.split_once("\n---\n"))
+        .map_or(text, |(_, body)| body.strip_prefix('\n').unwrap_or(body))
+}
+
+/// `text` without the front matter's `generated_at` line, so pages that
+/// differ only in when (or whether) they record generation time compare
+/// equal.
+pub fn without_timestamp(text: &str) -> String {
+    let body = strip(text);
+    if body.len() == text.len() {
+        return text.to_string();
+    }
+    let header = &text[..text.len() - body.len()];
+    let header: Vec<&str> = header.lines().filter(|line| !line.starts_with("generated_at:")).collect();
+    format!("{}\n{}", header.join("\n"), body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(generated_at: &str) -> FrontMatterSettings {
+        FrontMatterSettings {
+            weight: 10,
+            generated_at: Some(generated_at.to_string()),
+        }
+    }
+
+    #[test]
+    fn renders_yaml_block() {
+        let block = render("Say \"hi\"", "00ff", 10, &settings("2026-10-01T09:30:00Z"));
+
+        assert_eq!(
+            block,
+            "---\ntitle: \"Say \\\"hi\\\"\"\ngenerated_at: 2026-10-01T09:30:00Z\nir_hash: 00ff\nweight: 10\n---\n\n"
+        );
+        assert_eq!(strip(&format!("{}# Body\n", block)), "# Body\n");
+        assert_eq!(strip("# No front matter\n"), "# No front matter\n");
+
+        let reproducible = FrontMatterSettings {
+            weight: 10,
+            generated_at: None,
+        };
+        assert_eq!(render("A", "00ff", 10, &reproducible), "---\ntitle: \"A\"\nir_hash: 00ff\nweight: 10\n---\n\n");
+    }
+
+    #[test]
+    fn timestamps_are_ignored_when_comparing() {
+        let page = |at: &str| format!("{}# Body\n", render("A", "00ff", 1, &settings(at)));
+
+        assert_eq!(
+            without_timestamp(&page("2026-10-01T09:30:00Z")),
+            without_timestamp(&page("2026-10-02T11:00:00Z"))
+        );
+        assert_ne!(
+            without_timestamp(&page("2026-10-01T09:30:00Z")),
+            without_timestamp(&format!("{}# Body\n", render("B", "00ff", 1, &settings("x"))))
+        );
+        assert_eq!(without_timestamp("generated_at: now\n"), "generated_at: now\n");
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use archidoc_types::{GapReport, ModuleDoc, Relationship, RelationshipChange};
+
+/// Compare a planned (to-be) architecture against the extracted (as-is) one.
+///
+/// Elements are matched by module path and relationships by `(from, to)`,
+/// so a planned relationship whose label or protocol differs in the code
+/// still counts as present. Only dependencies of planned elements can be
+/// unplanned: the plan usually covers the containers, and the components
+/// below them are not expected to appear in it.
+pub fn gap_analysis(planned: &[ModuleDoc], current: &[ModuleDoc]) -> GapReport {
+    let current_paths: BTreeSet<&str> = current.iter().map(|d| d.module_path.as_str()).collect();
+    let planned_paths: BTreeSet<&str> = planned.iter().map(|d| d.module_path.as_str()).collect();
+    let planned_rels = relationship_map(planned);
+    let current_rels = relationship_map(current);
+
+    let mut report = GapReport {
+        missing_elements: planned_paths
+            .iter()
+            .filter(|path| !current_paths.contains(*path))
+            .map(|path| path.to_string())
+            .collect(),
+        ..Default::default()
+    };
+    for (key, (from, rel)) in &planned_rels {
+        if !current_rels.contains_key(key) {
+            report.missing_relationships.push(to_change(from, rel));
+        }
+    }
+    for (key, (from, rel)) in &current_rels {
+        if planned_paths.contains(from) && !planned_rels.contains_key(key) {
+            report.unplanned_relationships.push(to_change(from, rel));
+        }
+    }
+    report
+}
+
+/// Format a gap report as human-readable text.
+pub fn format_gap_report(report: &GapReport) -> String {
+    if report.is_empty() {
+        return "No gaps: the code matches the planned architecture.\n".to_string();
+    }
+
+    let mut out = String::new();
+    if !report.missing_elements.is_empty() {
+        out.push_str(&format!("Missing elements ({}):\n", report.missing_elements.len()));
+        for element in &report.missing_elements {
+            out.push_str(&format!("  {}\n", element));
+        }
+    }
+    for (title, rels) in [
+        ("Missing relationships", &report.missing_relationships),
+        ("Unplanned dependencies", &report.unplanned_relationships),
+    ] {
+        if rels.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("{} ({}):\n", title, rels.len()));
+        for rel in rels {
+            out.push_str(&format!("  {} -> {} ({})\n", rel.from, rel.to, rel.label));
+        }
+    }
+    out
+}
+
+/// Relationships keyed by `(from, to)`; the first declaration of a pair wins.
+fn relationship_map(docs: &[ModuleDoc]) -> BTreeMap<(&str, &str), (&str, &Relationship)> {
+    let mut map = BTreeMap::new();
+    for doc in docs {
+        for rel in &doc.relationships {
+            map.entry((doc.module_path.as_str(), rel.target.as_str()))
+                .or_insert((doc.module_path.as_str(), rel));
+        }
+    }
+    map
+}
+
+fn to_change(from: &str, rel: &Relationship) -> RelationshipChange {
+    RelationshipChange {
+        from: from.to_string(),
+        to: rel.target.clone(),
+        label: rel.label.clone(),
+        protocol: rel.protocol.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::ElementKind;
+
+    fn doc(path: &str, uses: &[(&str, &str)]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            relationships: uses
+                .iter()
+                .map(|(target, label)| Relationship {
+                    target: target.to_string(),
+                    label: label.to_string(),
+                    protocol: String::new(),
+                    hidden: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reports_missing_elements_relationships_and_unplanned_dependencies() {
+        let planned = vec![
+            doc("api", &[("bus", "Publishes"), ("db", "Reads")]),
+            doc("bus", &[]),
+            doc("db", &[]),
+            doc("search", &[]),
+        ];
+        let current = vec![
+            doc("api", &[("db", "Queries"), ("legacy", "Calls")]),
+            doc("api.handlers", &[("legacy", "Calls")]),
+            doc("bus", &[]),
+            doc("db", &[]),
+            doc("legacy", &[("db", "Writes")]),
+        ];
+
+        let report = gap_analysis(&planned, &current);
+
+        assert_eq!(report.missing_elements, vec!["search"]);
+        let pairs = |rels: &[RelationshipChange]| -> Vec<(String, String)> {
+            rels.iter().map(|r| (r.from.clone(), r.to.clone())).collect()
+        };
+        assert_eq!(pairs(&report.missing_relationships), vec![("api".into(), "bus".into())]);
+        assert_eq!(pairs(&report.unplanned_relationships), vec![("api".into(), "legacy".into())]);
+        assert_eq!(
+            format_gap_report(&report),
+            "Missing elements (1):\n  search\nMissing relationships (1):\n  api -> bus (Publishes)\nUnplanned dependencies (1):\n  api -> legacy (Calls)\n"
+        );
+    }
+
+    #[test]
+    fn matching_architecture_has_no_gaps() {
+        let docs = vec![doc("api", &[("db", "Reads")]), doc("db", &[])];
+
+        assert!(gap_analysis(&docs, &docs).is_empty());
+        assert!(format_gap_report(&GapReport::default()).starts_with("No gaps"));
+    }
+}
@@ -0,0 +1,206 @@
+use std::path::{Path, PathBuf};
+
+use archidoc_types::{ArchitectureConfig, ConfluenceConfig, DiagramConfig, FitnessSummary, HtmlConfig, Language, ModuleDoc, SiteConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::ai_context::AiContextGenerator;
+use crate::architecture::{ArchitectureGenerator, Snapshot};
+use crate::confluence::ConfluenceGenerator;
+use crate::d2::D2Generator;
+use crate::docusaurus::DocusaurusGenerator;
+use crate::drawio::DrawIoGenerator;
+use crate::front_matter::FrontMatterSettings;
+use crate::html::HtmlGenerator;
+use crate::mkdocs::MkDocsGenerator;
+use crate::mermaid::MermaidGenerator;
+use crate::plantuml::PlantUmlGenerator;
+use crate::structurizr::StructurizrGenerator;
+
+/// A file produced by a generator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Artifact {
+    /// Path relative to the output directory (the one holding
+    /// ARCHITECTURE.md), with `/` separators.
+    pub path: String,
+    pub content: String,
+}
+
+/// An output format: turns the compiled modules into one or more files.
+///
+/// Generators do not write files themselves: [`crate::compile::write_outputs`]
+/// writes their artifacts and [`crate::check::check_artifacts`] compares
+/// them to disk. External generators are in [`crate::plugin`].
+pub trait Generator {
+    /// Name used in `archidoc.toml` (`[generators] enable`, `[check] skip`);
+    /// for an external generator, its `[generator.<name>]` name.
+    fn id(&self) -> &str;
+
+    /// Fails when the artifacts cannot be produced, e.g. an external
+    /// generator exits non-zero.
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String>;
+}
+
+/// Ids of the built-in generators, in output order.
+pub const BUILTIN: &[&str] =
+    &["architecture", "ai_context", "mermaid", "plantuml", "drawio", "d2", "structurizr", "html", "confluence", "mkdocs", "docusaurus"];
+
+/// Settings shared by the built-in generators.
+#[derive(Debug, Clone)]
+pub struct OutputSettings {
+    /// ARCHITECTURE.md file name; the AI context is named after its stem.
+    pub architecture_file: String,
+    /// Base directory for source file links in ARCHITECTURE.md.
+    pub link_base: PathBuf,
+    /// Fitness results embedded in ARCHITECTURE.md, if any.
+    pub fitness: Vec<FitnessSummary>,
+    /// Project health and validation findings embedded in ARCHITECTURE.md.
+    pub snapshot: Snapshot,
+    /// Language of ARCHITECTURE.md headings and table labels.
+    pub language: Language,
+    /// ARCHITECTURE.md sections to leave out and Component Index order.
+    pub layout: ArchitectureConfig,
+    /// YAML front matter for ARCHITECTURE.md, if enabled.
+    pub front_matter: Option<FrontMatterSettings>,
+    /// Diagram rendering options.
+    pub diagrams: DiagramConfig,
+    /// Options for the HTML page.
+    pub html: HtmlConfig,
+    /// Options for the Confluence pages.
+    pub confluence: ConfluenceConfig,
+    /// Options for the documentation site projects.
+    pub site: SiteConfig,
+}
+
+impl OutputSettings {
+    /// Settings for writing every section of `architecture_file` in
+    /// English, without fitness results or front matter.
+    pub fn new(architecture_file: &Path, link_base: &Path) -> Self {
+        Self {
+            architecture_file: architecture_file
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "ARCHITECTURE.md".to_string()),
+            link_base: link_base.to_path_buf(),
+            fitness: Vec::new(),
+            snapshot: Snapshot::default(),
+            language: Language::En,
+            layout: ArchitectureConfig::default(),
+            front_matter: None,
+            diagrams: DiagramConfig::default(),
+            html: HtmlConfig::default(),
+            confluence: ConfluenceConfig::default(),
+            site: SiteConfig::default(),
+        }
+    }
+}
+
+/// Look up a built-in generator by id.
+pub fn builtin(id: &str, settings: &OutputSettings) -> Option<Box<dyn Generator>> {
+    let generator: Box<dyn Generator> = match id {
+        "architecture" => Box::new(architecture_generator(settings)),
+        "ai_context" => Box::new(AiContextGenerator {
+            architecture_file: settings.architecture_file.clone(),
+        }),
+        "mermaid" => Box::new(MermaidGenerator {
+            diagrams: settings.diagrams.clone(),
+        }),
+        "plantuml" => Box::new(PlantUmlGenerator {
+            diagrams: settings.diagrams.clone(),
+        }),
+        "drawio" => Box::new(DrawIoGenerator {
+            diagrams: settings.diagrams.clone(),
+        }),
+        "d2" => Box::new(D2Generator {
+            diagrams: settings.diagrams.clone(),
+        }),
+        "structurizr" => Box::new(StructurizrGenerator),
+        "html" => Box::new(HtmlGenerator {
+            document: architecture_generator(settings),
+            mermaid_url: settings.html.mermaid_url.clone(),
+        }),
+        "confluence" => Box::new(ConfluenceGenerator {
+            document: architecture_generator(settings),
+            title_prefix: settings.confluence.title_prefix.clone(),
+            diagram_macro: settings.confluence.diagram_macro.clone(),
+        }),
+        "mkdocs" => Box::new(MkDocsGenerator {
+            document: architecture_generator(settings),
+            dir: settings.site.dir.clone(),
+        }),
+        "docusaurus" => Box::new(DocusaurusGenerator {
+            document: architecture_generator(settings),
+            dir: settings.site.dir.clone(),
+        }),
+        _ => return None,
+    };
+    Some(generator)
+}
+
+fn architecture_generator(settings: &OutputSettings) -> ArchitectureGenerator {
+    ArchitectureGenerator {
+        file_name: settings.architecture_file.clone(),
+        link_base: settings.link_base.clone(),
+        fitness: settings.fitness.clone(),
+        snapshot: settings.snapshot.clone(),
+        language: settings.language,
+        layout: settings.layout.clone(),
+        front_matter: settings.front_matter.clone(),
+        diagrams: settings.diagrams.clone(),
+    }
+}
+
+/// Built-in generators for `ids`, in the given order. Unknown ids are
+/// skipped; `archidoc.toml` is validated against [`BUILTIN`] on load.
+pub fn builtins(ids: &[&str], settings: &OutputSettings) -> Vec<Box<dyn Generator>> {
+    ids.iter().filter_map(|id| builtin(id, settings)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_builtin_id_resolves_to_its_generator() {
+        let settings = OutputSettings::new(Path::new("docs/ARCH.md"), Path::new("."));
+
+        for id in BUILTIN {
+            assert_eq!(builtin(id, &settings).map(|g| g.id().to_string()), Some(id.to_string()));
+        }
+        assert!(builtin("svg", &settings).is_none());
+    }
+
+    #[test]
+    fn artifact_paths_are_relative_to_the_output_directory() {
+        let settings = OutputSettings::new(Path::new("docs/ARCH.md"), Path::new("."));
+
+        let paths: Vec<String> = builtins(BUILTIN, &settings)
+            .iter()
+            .flat_map(|g| g.generate(&[]).unwrap())
+            .map(|a| a.path)
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "ARCH.md",
+                "ARCH.ai.md",
+                "mermaid/c4-container.md",
+                "mermaid/c4-component.md",
+                "c4/c4-container.puml",
+                "c4/c4-component.puml",
+                "drawio/c4-container.csv",
+                "drawio/c4-component.csv",
+                "d2/c4-container.d2",
+                "d2/c4-component.d2",
+                "structurizr/workspace.dsl",
+                "ARCH.html",
+                "confluence/index.xml",
+                "confluence/pages.json",
+                "site/mkdocs.yml",
+                "site/docs/index.md",
+                "site/architecture/_category_.json",
+                "site/architecture/index.md",
+            ]
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Run a git command in `dir` and return its trimmed stdout.
+///
+/// Fails with git's error output when git is missing or exits non-zero.
+pub fn run(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| format!("failed to run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Files changed relative to `base`: committed, staged, and unstaged edits,
+/// plus untracked files. Paths are absolute.
+pub fn changed_files(dir: &Path, base: &str) -> Result<Vec<PathBuf>, String> {
+    let toplevel = PathBuf::from(run(dir, &["rev-parse", "--show-toplevel"])?);
+    let diff = run(dir, &["diff", "--name-only", base, "--"])?;
+    let untracked = run(dir, &["ls-files", "--others", "--exclude-standard", "--full-name"])?;
+
+    let mut files: Vec<PathBuf> = diff
+        .lines()
+        .chain(untracked.lines())
+        .filter(|line| !line.is_empty())
+        .map(|line| toplevel.join(line))
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
@@ -0,0 +1,59 @@
+use archidoc_types::ModuleDoc;
+
+/// Content hash used to detect edits since a review.
+///
+/// 64-bit FNV-1a over the annotation content, rendered as 16 hex digits.
+/// `@reviewed-by` lines, blank lines, and trailing whitespace are excluded,
+/// so recording a review does not itself change the hash.
+pub fn content_hash(content: &str) -> String {
+    let bytes = content
+        .lines()
+        .map(str::trim_end)
+        .filter(|l| !l.is_empty() && !l.trim_start().starts_with("@reviewed-by"))
+        .flat_map(|line| line.bytes().chain(std::iter::once(b'\n')));
+
+    format!("{:016x}", fnv1a(bytes))
+}
+
+/// The [`content_hash`] of a module's raw annotation: the recorded
+/// `content_hash` if there is one, else the hash of `content`. `None` when
+/// the IR carries neither, so there is nothing to compare against.
+pub fn annotation_hash(doc: &ModuleDoc) -> Option<String> {
+    match &doc.content_hash {
+        Some(hash) => Some(hash.clone()),
+        None => (!doc.content.trim().is_empty()).then(|| content_hash(&doc.content)),
+    }
+}
+
+/// Hash of exact file content (64-bit FNV-1a, 16 hex digits), used to
+/// fingerprint generated outputs.
+pub fn bytes_hash(content: &[u8]) -> String {
+    format!("{:016x}", fnv1a(content.iter().copied()))
+}
+
+fn fnv1a(bytes: impl Iterator<Item = u8>) -> u64 {
+    bytes.fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn review_lines_do_not_affect_hash() {
+        let plain = "@c4 container\n\n# Bus\n\nMessaging";
+        let reviewed = "@c4 container\n@reviewed-by alice 2024-06-01 0123\n\n# Bus\n\nMessaging  \n\n";
+
+        assert_eq!(content_hash(plain), content_hash(reviewed));
+        assert_ne!(content_hash(plain), content_hash("@c4 container\n\n# Bus\n\nChanged"));
+        assert_eq!(content_hash(plain).len(), 16);
+    }
+
+    #[test]
+    fn bytes_hash_covers_every_byte() {
+        assert_eq!(bytes_hash(b""), "cbf29ce484222325");
+        assert_ne!(bytes_hash(b"a\n"), bytes_hash(b"a\n\n"));
+    }
+}
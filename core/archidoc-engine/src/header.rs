@@ -0,0 +1,142 @@
+use archidoc_types::ModuleDoc;
+
+use crate::generator::{Artifact, Generator};
+
+/// Header settings for a run, from `[header]` in `archidoc.toml` plus what
+/// the CLI knows about itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderSettings {
+    /// archidoc version that produced the file.
+    pub version: String,
+    /// `YYYY-MM-DDTHH:MM:SSZ`; `None` for reproducible output.
+    pub generated_at: Option<String>,
+    /// Command line of the run, e.g. `archidoc --fitness`.
+    pub command: String,
+}
+
+/// Start of every header, after the format's comment syntax.
+const PREFIX: &str = "Generated by archidoc ";
+
+/// `Generated by archidoc 0.4.0 at 2026-10-01T09:30:00Z: archidoc --fitness`
+pub fn render(settings: &HeaderSettings) -> String {
+    let mut line = format!("{}{}", PREFIX, settings.version);
+    if let Some(at) = &settings.generated_at {
+        line.push_str(&format!(" at {}", at));
+    }
+    line.push_str(&format!(": {}", settings.command));
+    line
+}
+
+/// `content` of the artifact at `path` with the header comment added.
+/// Markdown gets an HTML comment and PlantUML a `'` comment, placed after
+/// any front matter; other formats have no safe comment syntax and are
+/// returned unchanged.
+pub fn apply(path: &str, content: &str, settings: &HeaderSettings) -> String {
+    let comment = match extension(path) {
+        "md" => format!("<!-- {} -->\n\n", render(settings)),
+        "puml" => format!("' {}\n", render(settings)),
+        _ => return content.to_string(),
+    };
+    let body = crate::front_matter::strip(content);
+    let front_matter = &content[..content.len() - body.len()];
+    format!("{}{}{}", front_matter, comment, body)
+}
+
+/// `text` without its header comment, if it has one.
+pub fn strip(text: &str) -> String {
+    let body = crate::front_matter::strip(text);
+    let front_matter = &text[..text.len() - body.len()];
+    let rest = if let Some(rest) = body.strip_prefix(&format!("<!-- {}", PREFIX)) {
+        rest.split_once(" -->\n").map(|(_, rest)| rest.strip_prefix('\n').unwrap_or(rest))
+    } else if let Some(rest) = body.strip_prefix(&format!("' {}", PREFIX)) {
+        rest.split_once('\n').map(|(_, rest)| rest)
+    } else {
+        None
+    };
+    match rest {
+        Some(rest) => format!("{}{}", front_matter, rest),
+        None => text.to_string(),
+    }
+}
+
+fn extension(path: &str) -> &str {
+    path.rsplit_once('.').map_or("", |(_, ext)| ext)
+}
+
+/// A generator whose artifacts carry a header comment.
+struct HeaderGenerator {
+    inner: Box<dyn Generator>,
+    settings: HeaderSettings,
+}
+
+impl Generator for HeaderGenerator {
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        Ok(self
+            .inner
+            .generate(docs)?
+            .into_iter()
+            .map(|a| Artifact {
+                content: apply(&a.path, &a.content, &self.settings),
+                path: a.path,
+            })
+            .collect())
+    }
+}
+
+/// `generators` with a header comment added to each artifact.
+pub fn wrap(generators: Vec<Box<dyn Generator>>, settings: &HeaderSettings) -> Vec<Box<dyn Generator>> {
+    generators
+        .into_iter()
+        .map(|inner| {
+            Box::new(HeaderGenerator {
+                inner,
+                settings: settings.clone(),
+            }) as Box<dyn Generator>
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(generated_at: Option<&str>) -> HeaderSettings {
+        HeaderSettings {
+            version: "0.4.0".to_string(),
+            generated_at: generated_at.map(str::to_string),
+            command: "archidoc --fitness".to_string(),
+        }
+    }
+
+    #[test]
+    fn header_uses_the_format_comment_syntax() {
+        let at = settings(Some("2026-10-01T09:30:00Z"));
+
+        assert_eq!(
+            apply("ARCHITECTURE.md", "# Title\n", &at),
+            "<!-- Generated by archidoc 0.4.0 at 2026-10-01T09:30:00Z: archidoc --fitness -->\n\n# Title\n"
+        );
+        assert_eq!(
+            apply("c4/c4-container.puml", "@startuml\n", &settings(None)),
+            "' Generated by archidoc 0.4.0: archidoc --fitness\n@startuml\n"
+        );
+        assert_eq!(apply("drawio/c4-container.csv", "a,b\n", &at), "a,b\n");
+        assert_eq!(
+            apply("ARCHITECTURE.md", "---\ntitle: \"A\"\n---\n\n# Title\n", &settings(None)),
+            "---\ntitle: \"A\"\n---\n\n<!-- Generated by archidoc 0.4.0: archidoc --fitness -->\n\n# Title\n"
+        );
+    }
+
+    #[test]
+    fn strip_removes_only_the_header() {
+        for path in ["ARCHITECTURE.md", "c4/c4-container.puml"] {
+            let content = "---\ntitle: \"A\"\n---\n\n# Title\n";
+            assert_eq!(strip(&apply(path, content, &settings(Some("2026-10-01T09:30:00Z")))), content);
+        }
+        assert_eq!(strip("<!-- hand-written -->\n\n# Title\n"), "<!-- hand-written -->\n\n# Title\n");
+    }
+}
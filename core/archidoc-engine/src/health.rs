@@ -1,17 +1,32 @@
 use archidoc_types::{
-    C4Level, ElementHealth, HealthReport, HealthStatus, ModuleDoc, PatternStatus,
+    ElementKind, ElementHealth, HealthReport, HealthStatus, Language, ModuleDoc, PatternStatus, StaleReview,
 };
 
+use crate::coverage::{self, COVERABLE_LINES, COVERED_LINES};
+use crate::hash::annotation_hash;
+use crate::i18n::fill;
+
+/// Elements claiming stable files below this line coverage are called out.
+pub const LOW_COVERAGE_PERCENT: f64 = 50.0;
+
 /// Aggregate health across all architectural elements.
 ///
 /// Counts files by maturity (planned/active/stable) and patterns by
 /// confidence (planned/verified), both project-wide and per-element.
+/// Patterns claimed by file-table rows are counted separately.
+/// Files with a measured line count are also summed by maturity, so the
+/// report can weight health by size as well as by file count.
+/// Reviews are counted as reviewed, unreviewed, or stale — stale when the
+/// `@reviewed-by` hash no longer matches the module's content. A review
+/// without a hash is unverifiable rather than reviewed, since nothing
+/// would ever mark it stale.
 pub fn aggregate_health(docs: &[ModuleDoc]) -> HealthReport {
-    let mut report = HealthReport::default();
-
-    report.total_elements = docs.len();
-    report.container_count = docs.iter().filter(|d| d.c4_level == C4Level::Container).count();
-    report.component_count = docs.iter().filter(|d| d.c4_level == C4Level::Component).count();
+    let mut report = HealthReport {
+        total_elements: docs.len(),
+        container_count: docs.iter().filter(|d| d.c4_level.is_container()).count(),
+        component_count: docs.iter().filter(|d| d.c4_level == ElementKind::Component).count(),
+        ..Default::default()
+    };
 
     for doc in docs {
         let mut elem = ElementHealth {
@@ -23,21 +38,41 @@ pub fn aggregate_health(docs: &[ModuleDoc]) -> HealthReport {
             files_stable: 0,
             pattern: doc.pattern.clone(),
             pattern_confidence: doc.pattern_status.to_string(),
+            review_status: String::new(),
+            content_hash: annotation_hash(doc).unwrap_or_default(),
+            loc: 0,
+            unsafe_count: doc.unsafe_count,
+            coverage: coverage::module_coverage(doc),
         };
+        report.unsafe_total += doc.unsafe_count;
 
         for file in &doc.files {
+            let loc = file.loc.unwrap_or(0);
             match file.health {
                 HealthStatus::Planned => {
                     report.files_planned += 1;
                     elem.files_planned += 1;
+                    report.loc_planned += loc;
                 }
                 HealthStatus::Active => {
                     report.files_active += 1;
                     elem.files_active += 1;
+                    report.loc_active += loc;
                 }
                 HealthStatus::Stable => {
                     report.files_stable += 1;
                     elem.files_stable += 1;
+                    report.loc_stable += loc;
+                }
+            }
+            report.loc_total += loc;
+            elem.loc += loc;
+
+            if file.pattern != "--" && !file.pattern.is_empty() {
+                report.file_patterns_total += 1;
+                match file.pattern_status {
+                    PatternStatus::Planned => report.file_patterns_planned += 1,
+                    PatternStatus::Verified => report.file_patterns_verified += 1,
                 }
             }
         }
@@ -52,43 +87,120 @@ pub fn aggregate_health(docs: &[ModuleDoc]) -> HealthReport {
             }
         }
 
+        elem.review_status = match &doc.review {
+            None => {
+                report.reviews_unreviewed += 1;
+                "unreviewed"
+            }
+            Some(review) => {
+                let current = elem.content_hash.clone();
+                match &review.content_hash {
+                    // IR without content or a recorded hash has nothing to compare
+                    _ if current.is_empty() => {
+                        report.reviews_unverifiable += 1;
+                        "unverifiable"
+                    }
+                    None => {
+                        report.reviews_unverifiable += 1;
+                        "unverifiable"
+                    }
+                    Some(reviewed) if *reviewed != current => {
+                        report.reviews_stale += 1;
+                        report.stale_reviews.push(StaleReview {
+                            element: doc.module_path.clone(),
+                            reviewer: review.reviewer.clone(),
+                            reviewed_on: review.date.clone(),
+                            reviewed_hash: reviewed.clone(),
+                            current_hash: current,
+                        });
+                        "stale"
+                    }
+                    Some(_) => {
+                        report.reviews_current += 1;
+                        "reviewed"
+                    }
+                }
+            }
+        }
+        .to_string();
+
         report.per_element.push(elem);
     }
 
+    let coverable: u64 = docs.iter().filter_map(|d| d.metrics.get(COVERABLE_LINES)).sum();
+    let covered: u64 = docs.iter().filter_map(|d| d.metrics.get(COVERED_LINES)).sum();
+    if coverable > 0 {
+        report.coverage = Some(covered as f64 / coverable as f64 * 100.0);
+    }
+
     report
 }
 
 /// Format a health report as human-readable text.
 pub fn format_health_report(report: &HealthReport) -> String {
+    format_health_report_localized(report, Language::En)
+}
+
+/// [`format_health_report`] with labels in `language`. Status keywords
+/// (`planned`, `stable`, ...) stay as annotated.
+pub fn format_health_report_localized(report: &HealthReport, language: Language) -> String {
+    let t = crate::i18n::strings(language);
     let mut out = String::new();
+    let line = |out: &mut String, label: &str, value: String| {
+        out.push_str(&format!("{:<13}{}\n", format!("{}:", label), value));
+    };
+    let count = |n: usize| n.to_string();
 
-    out.push_str("Architecture Health Report\n");
-    out.push_str("==========================\n");
-    out.push_str(&format!(
-        "Elements:    {} total ({} containers, {} components)\n",
-        report.total_elements, report.container_count, report.component_count
-    ));
-    out.push_str(&format!("Files:       {} total\n", report.total_files));
+    out.push_str(&format!("{}\n", t.health_title));
+    out.push_str(&format!("{}\n", "=".repeat(t.health_title.chars().count())));
+    line(
+        &mut out,
+        t.health_elements,
+        fill(
+            t.health_elements_summary,
+            &[
+                ("total", &count(report.total_elements)),
+                ("containers", &count(report.container_count)),
+                ("components", &count(report.component_count)),
+            ],
+        ),
+    );
+    line(&mut out, t.health_files, fill(t.health_total, &[("n", &count(report.total_files))]));
 
     if report.total_files > 0 {
-        out.push_str(&format!(
-            "  planned:   {} ({:.1}%)\n",
-            report.files_planned,
-            percent(report.files_planned, report.total_files)
-        ));
-        out.push_str(&format!(
-            "  active:    {} ({:.1}%)\n",
-            report.files_active,
-            percent(report.files_active, report.total_files)
-        ));
-        out.push_str(&format!(
-            "  stable:    {} ({:.1}%)\n",
-            report.files_stable,
-            percent(report.files_stable, report.total_files)
-        ));
+        let rows = [
+            ("planned:", report.files_planned, report.loc_planned),
+            ("active:", report.files_active, report.loc_active),
+            ("stable:", report.files_stable, report.loc_stable),
+        ];
+        for (label, files, loc) in rows {
+            out.push_str(&format!(
+                "  {:<10} {} ({:.1}%)",
+                label,
+                files,
+                percent(files, report.total_files)
+            ));
+            if report.loc_total > 0 {
+                let share = format!("{:.1}", percent(loc, report.loc_total));
+                out.push_str(" — ");
+                out.push_str(&fill(t.health_loc_share, &[("loc", &count(loc)), ("percent", &share)]));
+            }
+            out.push('\n');
+        }
+        if report.loc_total > 0 {
+            out.push_str(&format!(
+                "  {:<10} {}\n",
+                format!("{}:", t.health_lines),
+                fill(t.health_lines_measured, &[("n", &count(report.loc_total))])
+            ));
+        }
     }
 
-    out.push_str(&format!("Patterns:    {} assigned\n", report.patterns_total));
+    line(
+        &mut out,
+        t.health_patterns,
+        fill(t.health_patterns_assigned, &[("n", &count(report.patterns_total))]),
+    );
     if report.patterns_total > 0 {
         out.push_str(&format!(
             "  planned:   {} ({:.1}%)\n",
@@ -101,6 +213,87 @@ pub fn format_health_report(report: &HealthReport) -> String {
             percent(report.patterns_verified, report.patterns_total)
         ));
     }
+    if report.file_patterns_total > 0 {
+        out.push_str("  ");
+        out.push_str(&fill(
+            t.health_file_patterns,
+            &[
+                ("verified", &count(report.file_patterns_verified)),
+                ("total", &count(report.file_patterns_total)),
+            ],
+        ));
+        out.push('\n');
+    }
+
+    line(
+        &mut out,
+        t.health_reviews,
+        fill(
+            t.health_reviews_summary,
+            &[
+                ("reviewed", &count(report.reviews_current)),
+                ("unreviewed", &count(report.reviews_unreviewed)),
+                ("stale", &count(report.reviews_stale)),
+                ("unverifiable", &count(report.reviews_unverifiable)),
+            ],
+        ),
+    );
+
+    if report.unsafe_total > 0 {
+        let sites: Vec<String> = report
+            .per_element
+            .iter()
+            .filter(|e| e.unsafe_count > 0)
+            .map(|e| format!("{} ({})", e.name, e.unsafe_count))
+            .collect();
+        line(
+            &mut out,
+            t.health_unsafe,
+            fill(t.health_unsafe_sites, &[("n", &count(report.unsafe_total)), ("sites", &sites.join(", "))]),
+        );
+    }
+
+    if let Some(total) = report.coverage {
+        line(
+            &mut out,
+            t.health_coverage,
+            fill(t.health_coverage_lines, &[("percent", &format!("{:.1}", total))]),
+        );
+        let weak: Vec<String> = report
+            .per_element
+            .iter()
+            .filter(|e| e.files_stable > 0)
+            .filter_map(|e| e.coverage.filter(|c| *c < LOW_COVERAGE_PERCENT).map(|c| (e, c)))
+            .map(|(e, c)| format!("{} ({:.1}%)", e.name, c))
+            .collect();
+        if !weak.is_empty() {
+            out.push_str("  ");
+            out.push_str(&fill(
+                t.health_low_coverage,
+                &[("threshold", &format!("{:.0}", LOW_COVERAGE_PERCENT)), ("elements", &weak.join(", "))],
+            ));
+            out.push('\n');
+        }
+    }
+
+    if !report.stale_reviews.is_empty() {
+        out.push('\n');
+        out.push_str(&fill(t.health_stale_reviews, &[("n", &count(report.stale_reviews.len()))]));
+        out.push('\n');
+        for stale in &report.stale_reviews {
+            out.push_str("  ");
+            out.push_str(&fill(
+                t.health_stale_entry,
+                &[
+                    ("element", &stale.element),
+                    ("reviewer", &stale.reviewer),
+                    ("date", &stale.reviewed_on),
+                    ("hash", &stale.current_hash),
+                ],
+            ));
+            out.push('\n');
+        }
+    }
 
     out
 }
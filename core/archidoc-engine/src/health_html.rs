@@ -0,0 +1,232 @@
+use archidoc_types::HealthReport;
+
+/// Segment colors for the donut charts: planned, active/verified, stable.
+const PLANNED_COLOR: &str = "#e0a030";
+const ACTIVE_COLOR: &str = "#4a90d9";
+const STABLE_COLOR: &str = "#3fa66b";
+
+pub(crate) const STYLE: &str = "\
+body{font-family:system-ui,-apple-system,sans-serif;margin:2rem auto;max-width:72rem;padding:0 1rem;color:#222}
+h1{margin-bottom:.25rem}
+.summary{display:flex;flex-wrap:wrap;gap:1rem;margin:1.5rem 0}
+.card{border:1px solid #ddd;border-radius:6px;padding:.75rem 1rem;min-width:9rem}
+.card b{display:block;font-size:1.6rem}
+.charts{display:flex;flex-wrap:wrap;gap:3rem;margin:1.5rem 0}
+.chart h2{font-size:1.1rem}
+.legend{list-style:none;padding:0}
+.legend span{display:inline-block;width:.8rem;height:.8rem;margin-right:.4rem;border-radius:2px}
+table{border-collapse:collapse;width:100%}
+th,td{border-bottom:1px solid #eee;padding:.4rem .6rem;text-align:left}
+th{cursor:pointer;user-select:none;background:#f6f6f6}
+th[aria-sort=ascending]::after{content:\" \\25B2\"}
+th[aria-sort=descending]::after{content:\" \\25BC\"}
+td.num{text-align:right}";
+
+pub(crate) const SCRIPT: &str = "\
+document.querySelectorAll('th').forEach(function(th,col){
+  th.addEventListener('click',function(){
+    var table=th.closest('table'),body=table.tBodies[0];
+    var asc=th.getAttribute('aria-sort')!=='ascending';
+    table.querySelectorAll('th').forEach(function(h){h.removeAttribute('aria-sort')});
+    th.setAttribute('aria-sort',asc?'ascending':'descending');
+    var rows=Array.prototype.slice.call(body.rows);
+    rows.sort(function(a,b){
+      var x=a.cells[col].dataset.value||a.cells[col].textContent,y=b.cells[col].dataset.value||b.cells[col].textContent;
+      var n=parseFloat(x)-parseFloat(y);
+      var c=isNaN(n)?x.localeCompare(y):n;
+      return asc?c:-c;
+    });
+    rows.forEach(function(r){body.appendChild(r)});
+  });
+});";
+
+/// Render a health report as a self-contained HTML page: summary cards,
+/// donut charts of file maturity and pattern confidence, and a sortable
+/// per-element table. CSS, script, and charts are inline, so the single
+/// file can be published as a build artifact.
+pub fn render_health_html(report: &HealthReport, title: &str) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{} — Architecture Health</title>\n", escape(title)));
+    out.push_str(&format!("<style>\n{}\n</style>\n</head>\n<body>\n", STYLE));
+    out.push_str(&format!("<h1>{}</h1>\n<p>Architecture health</p>\n", escape(title)));
+
+    out.push_str("<div class=\"summary\">\n");
+    let mut cards = vec![
+        ("Elements", report.total_elements.to_string()),
+        ("Containers", report.container_count.to_string()),
+        ("Components", report.component_count.to_string()),
+        ("Files", report.total_files.to_string()),
+        ("Patterns", report.patterns_total.to_string()),
+        ("Reviewed", format!("{} / {}", report.reviews_current, report.total_elements)),
+    ];
+    if report.loc_total > 0 {
+        cards.push(("Lines", report.loc_total.to_string()));
+    }
+    if let Some(coverage) = report.coverage {
+        cards.push(("Coverage", format!("{:.1}%", coverage)));
+    }
+    for (label, value) in cards {
+        out.push_str(&format!("<div class=\"card\"><b>{}</b>{}</div>\n", value, label));
+    }
+    out.push_str("</div>\n");
+
+    out.push_str("<div class=\"charts\">\n");
+    out.push_str(&donut(
+        "File maturity",
+        &[
+            ("planned", report.files_planned, PLANNED_COLOR),
+            ("active", report.files_active, ACTIVE_COLOR),
+            ("stable", report.files_stable, STABLE_COLOR),
+        ],
+    ));
+    out.push_str(&donut(
+        "Pattern confidence",
+        &[
+            ("planned", report.patterns_planned, PLANNED_COLOR),
+            ("verified", report.patterns_verified, STABLE_COLOR),
+        ],
+    ));
+    out.push_str("</div>\n");
+
+    let has_coverage = report.per_element.iter().any(|e| e.coverage.is_some());
+    out.push_str("<table>\n<thead><tr><th>Element</th><th>Level</th><th>Files</th><th>Planned</th><th>Active</th><th>Stable</th><th>Lines</th><th>Pattern</th><th>Confidence</th><th>Review</th>");
+    if has_coverage {
+        out.push_str("<th>Coverage</th>");
+    }
+    out.push_str("</tr></thead>\n<tbody>\n");
+    for element in &report.per_element {
+        out.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td>{}{}{}{}{}<td>{}</td><td>{}</td><td>{}</td>",
+            escape(&element.name),
+            escape(&element.c4_level),
+            number(element.file_count),
+            number(element.files_planned),
+            number(element.files_active),
+            number(element.files_stable),
+            number(element.loc),
+            escape(&element.pattern),
+            escape(&element.pattern_confidence),
+            escape(&element.review_status),
+        ));
+        if has_coverage {
+            match element.coverage {
+                Some(c) => out.push_str(&format!("<td class=\"num\" data-value=\"{:.1}\">{:.1}%</td>", c, c)),
+                None => out.push_str("<td class=\"num\" data-value=\"-1\">—</td>"),
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n");
+
+    out.push_str(&format!("<script>\n{}\n</script>\n</body>\n</html>\n", SCRIPT));
+    out
+}
+
+pub(crate) fn number(n: usize) -> String {
+    format!("<td class=\"num\">{}</td>", n)
+}
+
+/// An SVG donut with one arc per non-empty segment, and its legend.
+fn donut(title: &str, segments: &[(&str, usize, &str)]) -> String {
+    const RADIUS: f64 = 15.915_494_309_189_533; // circumference of 100
+    let total: usize = segments.iter().map(|(_, n, _)| n).sum();
+
+    let mut out = format!("<div class=\"chart\">\n<h2>{}</h2>\n", escape(title));
+    out.push_str("<svg width=\"160\" height=\"160\" viewBox=\"0 0 42 42\" role=\"img\">\n");
+    out.push_str(&format!(
+        "<circle cx=\"21\" cy=\"21\" r=\"{}\" fill=\"none\" stroke=\"#eee\" stroke-width=\"6\"/>\n",
+        RADIUS
+    ));
+    let mut offset = 25.0;
+    for (_, n, color) in segments.iter().filter(|(_, n, _)| *n > 0) {
+        let share = *n as f64 / total as f64 * 100.0;
+        out.push_str(&format!(
+            "<circle cx=\"21\" cy=\"21\" r=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"6\" stroke-dasharray=\"{:.2} {:.2}\" stroke-dashoffset=\"{:.2}\"/>\n",
+            RADIUS,
+            color,
+            share,
+            100.0 - share,
+            offset
+        ));
+        offset -= share;
+    }
+    out.push_str(&format!(
+        "<text x=\"21\" y=\"23\" text-anchor=\"middle\" font-size=\"6\">{}</text>\n</svg>\n<ul class=\"legend\">\n",
+        total
+    ));
+    for (label, n, color) in segments {
+        let share = if total == 0 { 0.0 } else { *n as f64 / total as f64 * 100.0 };
+        out.push_str(&format!(
+            "<li><span style=\"background:{}\"></span>{}: {} ({:.1}%)</li>\n",
+            color, label, n, share
+        ));
+    }
+    out.push_str("</ul>\n</div>\n");
+    out
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::ElementHealth;
+
+    fn element(name: &str) -> ElementHealth {
+        ElementHealth {
+            name: name.to_string(),
+            c4_level: "container".to_string(),
+            file_count: 3,
+            files_planned: 1,
+            files_active: 1,
+            files_stable: 1,
+            pattern: "Observer".to_string(),
+            pattern_confidence: "verified".to_string(),
+            review_status: "unreviewed".to_string(),
+            content_hash: String::new(),
+            loc: 120,
+            unsafe_count: 0,
+            coverage: None,
+        }
+    }
+
+    #[test]
+    fn page_is_self_contained() {
+        let report = HealthReport {
+            total_elements: 1,
+            total_files: 3,
+            files_planned: 1,
+            files_active: 1,
+            files_stable: 1,
+            patterns_total: 1,
+            patterns_verified: 1,
+            per_element: vec![element("bus<core>")],
+            ..Default::default()
+        };
+
+        let html = render_health_html(&report, "Demo & Co");
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>Demo &amp; Co</h1>"));
+        assert!(html.contains("<td>bus&lt;core&gt;</td>"));
+        assert!(html.contains("planned: 1 (33.3%)"));
+        assert!(html.contains("verified: 1 (100.0%)"));
+        assert!(html.contains("<script>"));
+        assert!(!html.contains("src=\"http"), "no external resources");
+        assert!(!html.contains("<th>Coverage</th>"));
+    }
+
+    #[test]
+    fn empty_report_renders_empty_charts() {
+        let html = render_health_html(&HealthReport::default(), "Empty");
+
+        assert!(html.contains("planned: 0 (0.0%)"));
+        assert!(!html.contains("stroke-dasharray"));
+    }
+}
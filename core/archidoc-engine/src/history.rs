@@ -0,0 +1,406 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use archidoc_types::{ElementKind, ModuleDoc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+/// Location of the snapshot archive, relative to the project root.
+pub const HISTORY_DIR: &str = ".archidoc/history";
+
+/// Archived elements purged from the Archived Elements appendix, relative
+/// to the project root: module path to the last snapshot date it was seen
+/// in when purged.
+pub const PURGED_FILE: &str = ".archidoc/purged.json";
+
+/// One archived IR snapshot.
+///
+/// Snapshots are stored as gzipped IR named `<YYYY-MM-DD>_<ref>.json.gz`,
+/// so the archive can be listed and resolved without opening any file.
+/// Characters other than ASCII letters, digits, `.`, `-`, and `_` are
+/// percent-encoded in the file name, so distinct refs never share a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotEntry {
+    /// Snapshot date as `YYYY-MM-DD`.
+    pub date: String,
+    /// Commit or tag the snapshot was taken at.
+    pub git_ref: String,
+    pub path: PathBuf,
+}
+
+/// Which snapshots `prune` keeps. A snapshot is removed when it falls
+/// outside either limit; `None` disables that limit.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrunePolicy {
+    /// Keep only the N most recent snapshots.
+    pub keep_last: Option<usize>,
+    /// Keep only snapshots taken within the last N days.
+    pub keep_days: Option<u64>,
+}
+
+/// Archive an IR snapshot under `.archidoc/history/` in `root`.
+///
+/// The IR is stored without annotation content (its hash is kept) and with
+/// source files relative to `root`, so snapshots stay small and the same
+/// wherever the project is checked out. An existing snapshot for the same
+/// date and ref is replaced. Returns the path written.
+pub fn write_snapshot(
+    root: &Path,
+    docs: &[ModuleDoc],
+    date: &str,
+    git_ref: &str,
+) -> Result<PathBuf, String> {
+    let dir = root.join(HISTORY_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+
+    let mut elements = docs.to_vec();
+    crate::reproducible::normalize_docs(&mut elements, root);
+    crate::ir::strip_content(&mut elements);
+
+    let path = dir.join(format!("{}_{}.json.gz", date, encode_ref(git_ref)));
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(crate::ir::serialize(&elements).as_bytes())
+        .and_then(|_| encoder.finish())
+        .and_then(|bytes| fs::write(&path, bytes))
+        .map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+
+    Ok(path)
+}
+
+/// List archived snapshots, oldest first.
+///
+/// Returns an empty list when the archive does not exist. Files that do not
+/// follow the snapshot naming scheme are ignored.
+pub fn list_snapshots(root: &Path) -> Vec<SnapshotEntry> {
+    let Ok(entries) = fs::read_dir(root.join(HISTORY_DIR)) else {
+        return Vec::new();
+    };
+
+    let mut snapshots: Vec<SnapshotEntry> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| parse_entry(&e.path()))
+        .collect();
+    snapshots.sort_by(|a, b| (&a.date, &a.path).cmp(&(&b.date, &b.path)));
+    snapshots
+}
+
+/// Find a snapshot by ref or date.
+///
+/// A `YYYY-MM-DD` spec selects the latest snapshot taken on or before that
+/// date. Anything else matches a ref exactly, or as a prefix of a commit
+/// hash; the latest match wins.
+pub fn resolve(root: &Path, spec: &str) -> Result<SnapshotEntry, String> {
+    let snapshots = list_snapshots(root);
+
+    let found = if crate::date::is_valid(spec) {
+        snapshots.into_iter().rev().find(|s| s.date.as_str() <= spec)
+    } else {
+        snapshots
+            .into_iter()
+            .rev()
+            .find(|s| s.git_ref == spec || same_commit(&s.git_ref, spec))
+    };
+
+    found.ok_or_else(|| {
+        format!(
+            "no snapshot matching '{}' in {}; run `archidoc snapshot` first",
+            spec,
+            root.join(HISTORY_DIR).display()
+        )
+    })
+}
+
+/// Read the IR stored in an archived snapshot.
+pub fn read_snapshot(entry: &SnapshotEntry) -> Result<Vec<ModuleDoc>, String> {
+    let mut json = String::new();
+    fs::File::open(&entry.path)
+        .and_then(|file| GzDecoder::new(file).read_to_string(&mut json))
+        .map_err(|e| format!("failed to read {}: {}", entry.path.display(), e))?;
+    crate::ir::deserialize(&json)
+}
+
+/// Delete snapshots outside the retention policy.
+///
+/// `today_days` is the current date as days since 1970-01-01. Returns the
+/// paths removed.
+pub fn prune(root: &Path, policy: PrunePolicy, today_days: u64) -> Result<Vec<PathBuf>, String> {
+    let snapshots = list_snapshots(root);
+    let cutoff = policy
+        .keep_days
+        .map(|days| crate::date::format_days(today_days.saturating_sub(days)));
+    let keep_from = policy
+        .keep_last
+        .map(|n| snapshots.len().saturating_sub(n))
+        .unwrap_or(0);
+
+    let mut removed = Vec::new();
+    for (i, snapshot) in snapshots.into_iter().enumerate() {
+        let too_old = cutoff.as_ref().is_some_and(|c| snapshot.date < *c);
+        if i < keep_from || too_old {
+            fs::remove_file(&snapshot.path)
+                .map_err(|e| format!("failed to remove {}: {}", snapshot.path.display(), e))?;
+            removed.push(snapshot.path);
+        }
+    }
+
+    Ok(removed)
+}
+
+/// An element found in the snapshot archive but no longer in the project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedElement {
+    pub module_path: String,
+    pub c4_level: ElementKind,
+    pub description: String,
+    /// Date of the last snapshot holding the element.
+    pub last_seen: String,
+    /// Date of the first later snapshot without it; `None` when the latest
+    /// snapshot still has it.
+    pub removed: Option<String>,
+}
+
+/// Elements archived in `root`'s snapshots that are missing from
+/// `current`, by module path, leaving out those purged since they were
+/// last seen. An element removed and added back is archived again only
+/// when it disappears again.
+pub fn archived_elements(root: &Path, current: &[ModuleDoc]) -> Result<Vec<ArchivedElement>, String> {
+    let purged = read_purged(root)?;
+    let mut seen: BTreeMap<String, ArchivedElement> = BTreeMap::new();
+    for entry in list_snapshots(root) {
+        let docs = read_snapshot(&entry)?;
+        for element in seen.values_mut().filter(|e| e.removed.is_none()) {
+            if !docs.iter().any(|d| d.module_path == element.module_path) {
+                element.removed = Some(entry.date.clone());
+            }
+        }
+        for doc in docs {
+            let element = ArchivedElement {
+                module_path: doc.module_path.clone(),
+                c4_level: doc.c4_level,
+                description: doc.description,
+                last_seen: entry.date.clone(),
+                removed: None,
+            };
+            seen.insert(doc.module_path, element);
+        }
+    }
+
+    Ok(seen
+        .into_values()
+        .filter(|e| !current.iter().any(|d| d.module_path == e.module_path))
+        .filter(|e| purged.get(&e.module_path).is_none_or(|date| *date < e.last_seen))
+        .collect())
+}
+
+/// Purge archived elements from the appendix: those at `module_paths`, or
+/// all of them when empty. Returns the elements purged; a path that is not
+/// archived is an error.
+pub fn purge_archived(root: &Path, current: &[ModuleDoc], module_paths: &[String]) -> Result<Vec<ArchivedElement>, String> {
+    let archived = archived_elements(root, current)?;
+    if let Some(missing) = module_paths.iter().find(|p| !archived.iter().any(|e| e.module_path == **p)) {
+        return Err(format!("'{}' is not an archived element", missing));
+    }
+    let purging: Vec<ArchivedElement> = archived
+        .into_iter()
+        .filter(|e| module_paths.is_empty() || module_paths.contains(&e.module_path))
+        .collect();
+
+    let mut purged = read_purged(root)?;
+    for element in &purging {
+        purged.insert(element.module_path.clone(), element.last_seen.clone());
+    }
+    let path = root.join(PURGED_FILE);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(&purged).map_err(|e| format!("failed to serialize purges: {}", e))?;
+    fs::write(&path, json + "\n").map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    Ok(purging)
+}
+
+fn read_purged(root: &Path) -> Result<BTreeMap<String, String>, String> {
+    let path = root.join(PURGED_FILE);
+    match fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| format!("invalid {}: {}", path.display(), e)),
+        Err(_) => Ok(BTreeMap::new()),
+    }
+}
+
+/// Name of the git ref checked out in `root`: the tag at HEAD if there is
+/// one, otherwise the short commit hash. `None` outside a git repository.
+pub fn head_ref(root: &Path) -> Option<String> {
+    crate::git::run(root, &["describe", "--tags", "--exact-match", "HEAD"])
+        .or_else(|_| crate::git::run(root, &["rev-parse", "--short", "HEAD"]))
+        .ok()
+        .filter(|r| !r.is_empty())
+}
+
+/// Two commit hashes of possibly different lengths name the same commit.
+fn same_commit(a: &str, b: &str) -> bool {
+    let is_hash = |s: &str| s.len() >= 7 && s.chars().all(|c| c.is_ascii_hexdigit());
+    is_hash(a) && is_hash(b) && (a.starts_with(b) || b.starts_with(a))
+}
+
+/// Make a ref safe for use in a file name without losing it:
+/// `release/1.2` -> `release%2F1.2`, while `release-1.2` stays as it is.
+fn encode_ref(git_ref: &str) -> String {
+    let mut out = String::new();
+    for byte in git_ref.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'.' | b'-' | b'_') {
+            out.push(char::from(byte));
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+/// The ref [`encode_ref`] encoded, or `None` for a malformed name.
+fn decode_ref(name: &str) -> Option<String> {
+    let mut bytes = Vec::new();
+    let mut rest = name.as_bytes();
+    while let Some((&byte, after)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(after.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &after[2..];
+        } else {
+            bytes.push(byte);
+            rest = after;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn parse_entry(path: &Path) -> Option<SnapshotEntry> {
+    let name = path.file_name()?.to_str()?;
+    let (date, git_ref) = name.strip_suffix(".json.gz")?.split_once('_')?;
+    let git_ref = decode_ref(git_ref)?;
+    if !crate::date::is_valid(date) || git_ref.is_empty() {
+        return None;
+    }
+
+    Some(SnapshotEntry {
+        date: date.to_string(),
+        git_ref,
+        path: path.to_path_buf(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::ElementKind;
+
+    fn doc(path: &str) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: format!("src/{}/mod.rs", path),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            description: format!("{} module", path),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn snapshots_resolve_by_ref_and_date() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_snapshot(tmp.path(), &[doc("api")], "2026-01-10", "v1.0").unwrap();
+        write_snapshot(tmp.path(), &[doc("api"), doc("bus")], "2026-02-01", "a1b2c3d").unwrap();
+
+        assert_eq!(resolve(tmp.path(), "v1.0").unwrap().date, "2026-01-10");
+        assert_eq!(resolve(tmp.path(), "a1b2c3d4e5f6").unwrap().git_ref, "a1b2c3d");
+        assert_eq!(resolve(tmp.path(), "2026-01-31").unwrap().git_ref, "v1.0");
+        assert!(resolve(tmp.path(), "2025-12-31").is_err());
+
+        let latest = resolve(tmp.path(), "2026-02-01").unwrap();
+        assert_eq!(read_snapshot(&latest).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn snapshots_are_compressed_and_portable() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut api = doc("api");
+        api.source_file = tmp.path().join("src/api/mod.rs").display().to_string();
+        api.content = "@c4 container\n\nLong narrative".to_string();
+
+        let path = write_snapshot(tmp.path(), std::slice::from_ref(&api), "2026-01-10", "v1.0").unwrap();
+
+        assert!(path.to_string_lossy().ends_with("2026-01-10_v1.0.json.gz"));
+        assert_eq!(fs::read(&path).unwrap()[..2], [0x1f, 0x8b]);
+        let stored = read_snapshot(&list_snapshots(tmp.path())[0]).unwrap();
+        assert_eq!(stored[0].source_file, "src/api/mod.rs");
+        assert!(stored[0].content.is_empty());
+        assert_eq!(stored[0].content_hash, crate::hash::annotation_hash(&api));
+    }
+
+    #[test]
+    fn refs_that_differ_only_in_punctuation_keep_separate_snapshots() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_snapshot(tmp.path(), &[doc("api")], "2026-01-10", "release/1.2").unwrap();
+        write_snapshot(tmp.path(), &[doc("api"), doc("bus")], "2026-01-10", "release-1.2").unwrap();
+
+        let refs: Vec<String> = list_snapshots(tmp.path()).into_iter().map(|s| s.git_ref).collect();
+        assert_eq!(refs.len(), 2);
+        assert!(refs.contains(&"release/1.2".to_string()));
+        assert_eq!(read_snapshot(&resolve(tmp.path(), "release/1.2").unwrap()).unwrap().len(), 1);
+        assert_eq!(read_snapshot(&resolve(tmp.path(), "release-1.2").unwrap()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn prune_applies_both_limits() {
+        let tmp = tempfile::tempdir().unwrap();
+        for (date, git_ref) in [("2026-01-01", "a"), ("2026-01-20", "b"), ("2026-02-01", "c"), ("2026-02-10", "d")] {
+            write_snapshot(tmp.path(), &[doc("api")], date, git_ref).unwrap();
+        }
+        fs::write(tmp.path().join(HISTORY_DIR).join("notes.txt"), "keep me").unwrap();
+
+        // 2026-02-14 is day 20498; keep 30 days (from 2026-01-15) and at most 3 snapshots.
+        let policy = PrunePolicy { keep_last: Some(3), keep_days: Some(30) };
+        let removed = prune(tmp.path(), policy, 20_498).unwrap();
+
+        assert_eq!(removed.len(), 1);
+        let refs: Vec<String> = list_snapshots(tmp.path()).into_iter().map(|s| s.git_ref).collect();
+        assert_eq!(refs, vec!["b", "c", "d"]);
+        assert!(tmp.path().join(HISTORY_DIR).join("notes.txt").exists());
+
+        let policy = PrunePolicy { keep_last: Some(1), keep_days: None };
+        prune(tmp.path(), policy, 20_498).unwrap();
+        assert_eq!(list_snapshots(tmp.path()).len(), 1);
+    }
+
+    #[test]
+    fn removed_elements_stay_archived_until_purged() {
+        let tmp = tempfile::tempdir().unwrap();
+        write_snapshot(tmp.path(), &[doc("api"), doc("bus"), doc("cache")], "2026-01-10", "a").unwrap();
+        write_snapshot(tmp.path(), &[doc("api"), doc("cache")], "2026-02-01", "b").unwrap();
+        let current = [doc("api")];
+
+        let archived = archived_elements(tmp.path(), &current).unwrap();
+        let summary: Vec<(&str, &str, Option<&str>)> = archived
+            .iter()
+            .map(|e| (e.module_path.as_str(), e.last_seen.as_str(), e.removed.as_deref()))
+            .collect();
+        assert_eq!(summary, [("bus", "2026-01-10", Some("2026-02-01")), ("cache", "2026-02-01", None)]);
+        assert_eq!(archived[0].description, "bus module");
+
+        assert!(purge_archived(tmp.path(), &current, &["api".to_string()]).is_err());
+        let purged = purge_archived(tmp.path(), &current, &["bus".to_string()]).unwrap();
+        assert_eq!(purged.len(), 1);
+        let left: Vec<String> = archived_elements(tmp.path(), &current).unwrap().into_iter().map(|e| e.module_path).collect();
+        assert_eq!(left, ["cache"]);
+
+        // Back and gone again: archived anew
+        write_snapshot(tmp.path(), &[doc("api"), doc("bus")], "2026-03-01", "c").unwrap();
+        write_snapshot(tmp.path(), &[doc("api")], "2026-04-01", "d").unwrap();
+        let left: Vec<String> = archived_elements(tmp.path(), &current).unwrap().into_iter().map(|e| e.module_path).collect();
+        assert_eq!(left, ["bus", "cache"]);
+        purge_archived(tmp.path(), &current, &[]).unwrap();
+        assert!(archived_elements(tmp.path(), &current).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use archidoc_types::{ModuleDoc, PluginConfig};
+
+use crate::generator::{Artifact, Generator};
+
+/// A generator whose artifacts are piped through a `[hooks.<id>]` command.
+///
+/// The command runs in the project root once per artifact, with the content
+/// on stdin and `ARCHIDOC_ARTIFACT` (the path relative to the output
+/// directory), `ARCHIDOC_GENERATOR`, and `ARCHIDOC_MODE` (`generate` or
+/// `check`) set. What it prints is the artifact's content, so a translator
+/// or formatter is written and checked like any other output; an uploader
+/// echoes its input and can skip the upload when checking. Hooks see the
+/// content before any `[header]` comment is added.
+struct HookedGenerator {
+    inner: Box<dyn Generator>,
+    command: PathBuf,
+    args: Vec<String>,
+    root: PathBuf,
+    mode: &'static str,
+}
+
+impl HookedGenerator {
+    fn run(&self, artifact: &Artifact) -> Result<String, String> {
+        let id = self.inner.id();
+        let mut child = Command::new(&self.command)
+            .args(&self.args)
+            .current_dir(&self.root)
+            .env("ARCHIDOC_ARTIFACT", &artifact.path)
+            .env("ARCHIDOC_GENERATOR", id)
+            .env("ARCHIDOC_MODE", self.mode)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("failed to run hook for '{}' ({}): {}", id, self.command.display(), e))?;
+
+        // Same as external generators: feed stdin from a thread so a hook
+        // that writes before it finishes reading cannot deadlock
+        let content = artifact.content.clone();
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let writer = std::thread::spawn(move || stdin.write_all(content.as_bytes()));
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("failed to run hook for '{}': {}", id, e))?;
+        let _ = writer.join();
+
+        if !output.status.success() {
+            return Err(format!(
+                "hook for '{}' failed on {} ({}): {}",
+                id,
+                artifact.path,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        String::from_utf8(output.stdout).map_err(|_| format!("hook for '{}' printed non-UTF-8 output for {}", id, artifact.path))
+    }
+}
+
+impl Generator for HookedGenerator {
+    fn id(&self) -> &str {
+        self.inner.id()
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        self.inner
+            .generate(docs)?
+            .into_iter()
+            .map(|artifact| {
+                Ok(Artifact {
+                    content: self.run(&artifact)?,
+                    path: artifact.path,
+                })
+            })
+            .collect()
+    }
+}
+
+/// `generators` with the `hooks` declared for their ids applied, for a run
+/// that writes the artifacts or, with `check`, compares them.
+pub fn wrap(
+    generators: Vec<Box<dyn Generator>>,
+    hooks: &BTreeMap<String, PluginConfig>,
+    root: &Path,
+    check: bool,
+) -> Vec<Box<dyn Generator>> {
+    generators
+        .into_iter()
+        .map(|inner| match hooks.get(inner.id()) {
+            Some(hook) => Box::new(HookedGenerator {
+                command: crate::plugin::resolve_command(&hook.command, root),
+                args: hook.args.clone(),
+                root: root.to_path_buf(),
+                mode: if check { "check" } else { "generate" },
+                inner,
+            }) as Box<dyn Generator>,
+            None => inner,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::OutputSettings;
+
+    fn hooked(script: &str, check: bool) -> Vec<Box<dyn Generator>> {
+        let settings = OutputSettings::new(Path::new("ARCHITECTURE.md"), Path::new("."));
+        let hooks = BTreeMap::from([(
+            "ai_context".to_string(),
+            PluginConfig {
+                command: "sh".to_string(),
+                args: vec!["-c".to_string(), script.to_string()],
+            },
+        )]);
+        wrap(crate::generator::builtins(&["architecture", "ai_context"], &settings), &hooks, &std::env::temp_dir(), check)
+    }
+
+    #[test]
+    fn only_the_hooked_generator_output_is_replaced() {
+        let generators = hooked(r#"printf '%s %s %s' "$ARCHIDOC_ARTIFACT" "$ARCHIDOC_GENERATOR" "$ARCHIDOC_MODE""#, true);
+
+        let architecture = generators[0].generate(&[]).unwrap();
+        let ai_context = generators[1].generate(&[]).unwrap();
+
+        assert!(architecture[0].content.starts_with("# Architecture"));
+        assert_eq!(ai_context[0].content, "ARCHITECTURE.ai.md ai_context check");
+        assert_eq!(ai_context[0].path, "ARCHITECTURE.ai.md");
+    }
+
+    #[test]
+    fn failing_hooks_fail_the_generator() {
+        let err = hooked("cat >/dev/null; echo 'translator offline' >&2; exit 2", false)[1]
+            .generate(&[])
+            .unwrap_err();
+
+        assert!(err.starts_with("hook for 'ai_context' failed on ARCHITECTURE.ai.md"), "{}", err);
+        assert!(err.ends_with("translator offline"), "{}", err);
+    }
+}
@@ -0,0 +1,560 @@
+use archidoc_types::{IndexOrder, Language, ModuleDoc};
+
+use crate::architecture::{self, ArchitectureGenerator};
+use crate::generator::{Artifact, Generator};
+use crate::health_html::escape;
+
+const STYLE: &str = "\
+body{font-family:system-ui,-apple-system,sans-serif;margin:0;color:#222;display:flex}
+nav{position:sticky;top:0;height:100vh;overflow-y:auto;box-sizing:border-box;width:18rem;flex-shrink:0;padding:1rem;background:#f6f6f6;border-right:1px solid #ddd;font-size:.9rem}
+nav h2{font-size:1rem;margin:1rem 0 .4rem}
+nav ul{list-style:none;padding:0;margin:0}
+nav li{margin:.2rem 0}
+nav li.component{padding-left:.5rem}
+nav details{border:none;padding:0;margin:0}
+nav details>summary{font-weight:normal}
+nav .kind{color:#777;font-size:.8rem}
+main{flex:1;min-width:0;max-width:72rem;padding:1rem 2rem}
+table{border-collapse:collapse;margin:1rem 0}
+th,td{border-bottom:1px solid #eee;padding:.4rem .6rem;text-align:left;vertical-align:top}
+th{background:#f6f6f6}
+pre{background:#f6f6f6;padding:.75rem;overflow-x:auto}
+pre.mermaid{background:none}
+details{border:1px solid #ddd;border-radius:6px;padding:.5rem 1rem;margin:.5rem 0}
+details>summary{cursor:pointer;font-weight:600}
+blockquote{color:#555;border-left:3px solid #ddd;margin:1rem 0;padding-left:1rem}";
+
+/// Opens the element section a link or the address points at.
+const SCRIPT: &str = "\
+function openTarget(){
+  var el=document.getElementById(decodeURIComponent(location.hash.slice(1)));
+  if(el&&el.tagName==='DETAILS'){el.open=true;}
+}
+window.addEventListener('hashchange',openTarget);
+openTarget();";
+
+/// `<stem>.html`: the ARCHITECTURE.md content as one page, named after the
+/// ARCHITECTURE.md file it accompanies. Elements are collapsible sections
+/// and the Component Index becomes a sidebar. Styles and script are inline;
+/// only the Mermaid library is loaded, from `mermaid_url`.
+pub struct HtmlGenerator {
+    /// The document to render; its front matter is ignored.
+    pub document: ArchitectureGenerator,
+    pub mermaid_url: String,
+}
+
+impl Generator for HtmlGenerator {
+    fn id(&self) -> &str {
+        "html"
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        let file_name = &self.document.file_name;
+        let stem = file_name.rsplit_once('.').map_or(file_name.as_str(), |(stem, _)| stem);
+        Ok(vec![Artifact {
+            path: format!("{}.html", stem),
+            content: page(docs, &self.document, &self.mermaid_url),
+        }])
+    }
+}
+
+/// Render the page: ARCHITECTURE.md without its contents list and
+/// Component Index, with each element in a `<details>` section, and a
+/// sidebar linking to the sections and, grouped by container, the
+/// elements.
+pub fn page(docs: &[ModuleDoc], document: &ArchitectureGenerator, mermaid_url: &str) -> String {
+    let t = crate::i18n::strings(document.language);
+    let skipped = |id: &str| document.layout.skip.iter().any(|s| s == id);
+    let mut layout = document.layout.clone();
+    layout.skip.extend(["contents", "component_index", "elements"].map(String::from));
+    let mut markdown = architecture::generate_document(
+        docs,
+        &document.link_base,
+        &document.fitness,
+        &document.snapshot,
+        document.language,
+        &layout,
+        &document.diagrams,
+    );
+    // Only the element sections below may carry raw HTML
+    markdown.retain(|c| c != RAW);
+
+    let elements = if skipped("elements") {
+        Vec::new()
+    } else {
+        architecture::element_bodies(docs, &document.link_base, document.language)
+    };
+    if !elements.is_empty() {
+        let mut section = format!("<a id=\"section-elements\"></a>\n\n## {}\n\n", t.elements);
+        for (doc, body) in &elements {
+            section.push_str(&format!(
+                "{RAW}<details id=\"{}\">\n{RAW}<summary>{} <span class=\"kind\">{}</span></summary>\n\n{}{RAW}</details>\n\n",
+                escape(&doc.element_id()),
+                escape(&doc.module_path),
+                doc.c4_level,
+                body.replace(RAW, "")
+            ));
+        }
+        architecture::insert_section(&mut markdown, "elements", &section);
+    }
+
+    let (body, sections) = convert_marked(&markdown, &fence);
+
+    let mut out = String::new();
+    out.push_str(&format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n",
+        language_code(document.language)
+    ));
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape(t.architecture_title)));
+    out.push_str(&format!("<style>\n{}\n</style>\n</head>\n<body>\n<nav>\n", STYLE));
+    if !sections.is_empty() {
+        out.push_str(&format!("<h2>{}</h2>\n<ul>\n", escape(t.contents)));
+        for (id, title) in &sections {
+            out.push_str(&format!("<li><a href=\"#{}\">{}</a></li>\n", id, title));
+        }
+        out.push_str("</ul>\n");
+    }
+    if !elements.is_empty() {
+        out.push_str(&format!("<h2>{}</h2>\n", escape(t.component_index)));
+        out.push_str(&component_index(docs, document.layout.index_order));
+    }
+    out.push_str("</nav>\n<main>\n");
+    out.push_str(&body);
+    out.push_str("</main>\n");
+    out.push_str(&format!(
+        "<script type=\"module\">\nimport mermaid from \"{}\";\nmermaid.initialize({{ startOnLoad: true }});\n</script>\n",
+        escape(mermaid_url)
+    ));
+    out.push_str(&format!("<script>\n{}\n</script>\n</body>\n</html>\n", SCRIPT));
+    out
+}
+
+/// The sidebar Component Index: one collapsible group per container, in
+/// `order`, holding the container's components.
+fn component_index(docs: &[ModuleDoc], order: IndexOrder) -> String {
+    let mut groups: Vec<(&str, Vec<&ModuleDoc>)> = Vec::new();
+    for doc in architecture::index_modules(docs, order) {
+        let container = architecture::container_of(doc);
+        match groups.iter_mut().find(|(c, _)| *c == container) {
+            Some((_, members)) => members.push(doc),
+            None => groups.push((container, vec![doc])),
+        }
+    }
+
+    let link = |doc: &ModuleDoc| {
+        format!(
+            "<a href=\"#{}\">{}</a> <span class=\"kind\">{}</span>",
+            doc.element_id(),
+            escape(&doc.module_path),
+            doc.c4_level
+        )
+    };
+    let mut out = String::from("<ul>\n");
+    for (container, members) in groups {
+        let (head, items): (Vec<&ModuleDoc>, Vec<&ModuleDoc>) =
+            members.into_iter().partition(|d| d.module_path == container);
+        let summary = match head.first() {
+            Some(doc) => link(doc),
+            None => escape(container),
+        };
+        if items.is_empty() {
+            out.push_str(&format!("<li>{}</li>\n", summary));
+            continue;
+        }
+        out.push_str(&format!("<li><details open>\n<summary>{}</summary>\n<ul>\n", summary));
+        for doc in items {
+            out.push_str(&format!("<li class=\"component\">{}</li>\n", link(doc)));
+        }
+        out.push_str("</ul>\n</details></li>\n");
+    }
+    out.push_str("</ul>\n");
+    out
+}
+
+fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::En => "en",
+        Language::De => "de",
+        Language::Ja => "ja",
+    }
+}
+
+/// Convert the Markdown archidoc writes to HTML, returning it with the
+/// anchor id and title of every `##` section.
+///
+/// Covers headings, paragraphs, lists, tables, block quotes, fenced code
+/// (Mermaid fences become `<pre class="mermaid">`), rules, inline code,
+/// emphasis, and links. The anchor and `<details>` lines archidoc emits
+/// pass through unchanged and `<summary>` lines keep their tags; any other
+/// HTML is escaped.
+pub fn to_html(markdown: &str) -> (String, Vec<(String, String)>) {
+    convert(markdown, &fence)
+}
+
+/// Fenced code as HTML, Mermaid fences as `<pre class="mermaid">`.
+fn fence(lang: &str, code: &str) -> String {
+    if lang == "mermaid" {
+        format!("<pre class=\"mermaid\">\n{}</pre>\n", escape(code))
+    } else {
+        format!("<pre><code>{}</code></pre>\n", escape(code))
+    }
+}
+
+/// [`to_html`] with fenced code rendered by `fence`, given the fence's
+/// language and its unescaped code.
+pub(crate) fn convert(markdown: &str, fence: &dyn Fn(&str, &str) -> String) -> (String, Vec<(String, String)>) {
+    convert_marked(&markdown.replace(RAW, ""), fence)
+}
+
+/// [`convert`], passing lines marked [`RAW`] through.
+fn convert_marked(markdown: &str, fence: &dyn Fn(&str, &str) -> String) -> (String, Vec<(String, String)>) {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut out = String::new();
+    let mut sections = Vec::new();
+    let mut anchor: Option<String> = None;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            i += 1;
+            continue;
+        }
+
+        if let Some(lang) = trimmed.strip_prefix("```") {
+            let mut code = String::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with("```") {
+                code.push_str(lines[i]);
+                code.push('\n');
+                i += 1;
+            }
+            i += 1;
+            out.push_str(&fence(lang.trim(), &code));
+            continue;
+        }
+
+        if is_passthrough(trimmed) {
+            if let Some(id) = anchor_id(trimmed) {
+                anchor = Some(id.to_string());
+            }
+            match trimmed.strip_prefix("<summary>").and_then(|s| s.strip_suffix("</summary>")) {
+                Some(summary) => out.push_str(&format!("<summary>{}</summary>", inline(summary))),
+                None => out.push_str(trimmed.trim_start_matches(RAW)),
+            }
+            out.push('\n');
+            i += 1;
+            continue;
+        }
+
+        let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            let title = inline(trimmed[hashes..].trim());
+            if hashes == 2 {
+                if let Some(id) = anchor.take() {
+                    sections.push((id, title.clone()));
+                }
+            }
+            out.push_str(&format!("<h{}>{}</h{}>\n", hashes, title, hashes));
+            i += 1;
+            continue;
+        }
+
+        if trimmed == "---" {
+            out.push_str("<hr>\n");
+            i += 1;
+            continue;
+        }
+
+        if trimmed.starts_with('|') && lines.get(i + 1).is_some_and(|next| is_table_separator(next)) {
+            out.push_str("<table>\n<thead><tr>");
+            for cell in table_cells(trimmed) {
+                out.push_str(&format!("<th>{}</th>", inline(&cell)));
+            }
+            out.push_str("</tr></thead>\n<tbody>\n");
+            i += 2;
+            while i < lines.len() && lines[i].trim().starts_with('|') {
+                out.push_str("<tr>");
+                for cell in table_cells(lines[i].trim()) {
+                    out.push_str(&format!("<td>{}</td>", inline(&cell)));
+                }
+                out.push_str("</tr>\n");
+                i += 1;
+            }
+            out.push_str("</tbody>\n</table>\n");
+            continue;
+        }
+
+        if trimmed.starts_with("> ") || trimmed == ">" {
+            let mut quote = Vec::new();
+            while i < lines.len() && lines[i].trim().starts_with('>') {
+                quote.push(lines[i].trim().trim_start_matches('>').trim());
+                i += 1;
+            }
+            out.push_str(&format!("<blockquote><p>{}</p></blockquote>\n", inline(&quote.join(" "))));
+            continue;
+        }
+
+        if let Some(ordered) = list_item(trimmed).map(|(ordered, _)| ordered) {
+            out.push_str(if ordered { "<ol>\n" } else { "<ul>\n" });
+            while i < lines.len() {
+                match list_item(lines[i].trim()) {
+                    Some((o, item)) if o == ordered => out.push_str(&format!("<li>{}</li>\n", inline(item))),
+                    _ => break,
+                }
+                i += 1;
+            }
+            out.push_str(if ordered { "</ol>\n" } else { "</ul>\n" });
+            continue;
+        }
+
+        let mut paragraph = Vec::new();
+        while i < lines.len() {
+            let next = lines[i].trim();
+            if next.is_empty() || next.starts_with("```") || next.starts_with('#') || is_passthrough(next) {
+                break;
+            }
+            if !paragraph.is_empty() && (next.starts_with('|') || next.starts_with('>') || list_item(next).is_some()) {
+                break;
+            }
+            paragraph.push(next);
+            i += 1;
+        }
+        out.push_str(&format!("<p>{}</p>\n", inline(&paragraph.join("\n"))));
+    }
+
+    (out, sections)
+}
+
+/// Marks a line of raw HTML [`page`] inserts; stripped from any other
+/// Markdown, so no prose can carry it.
+const RAW: char = '\0';
+
+/// The HTML lines archidoc itself writes into Markdown: those marked
+/// [`RAW`], and the exact anchor, `<details>`, and `<summary>` lines of the
+/// document, which carry nothing a reader could inject.
+fn is_passthrough(line: &str) -> bool {
+    line.starts_with(RAW)
+        || anchor_id(line).is_some()
+        || matches!(line, "<details>" | "<details open>" | "</details>")
+        || (line.starts_with("<summary>") && line.ends_with("</summary>"))
+}
+
+/// The id of an `<a id="..."></a>` anchor line, if it is a plain slug.
+fn anchor_id(line: &str) -> Option<&str> {
+    let id = line.strip_prefix("<a id=\"")?.strip_suffix("\"></a>")?;
+    let slug = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    slug.then_some(id)
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with('|') && line.contains('-') && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+/// The cells of a table row, with `\|` unescaped.
+fn table_cells(row: &str) -> Vec<String> {
+    let inner = row.trim().trim_start_matches('|');
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    let mut cells = vec![String::new()];
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cells.last_mut().unwrap().push('|');
+                chars.next();
+            }
+            '|' => cells.push(String::new()),
+            _ => cells.last_mut().unwrap().push(c),
+        }
+    }
+    cells.iter().map(|c| c.trim().to_string()).collect()
+}
+
+/// A list item's text and whether the list is ordered.
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some((false, item));
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        return line[digits..].strip_prefix(". ").map(|item| (true, item));
+    }
+    None
+}
+
+/// Inline Markdown: `code`, **strong**, *emphasis*, and [links](url).
+/// Everything else is escaped.
+fn inline(text: &str) -> String {
+    let mut out = String::new();
+    for (n, part) in text.split('`').enumerate() {
+        if n % 2 == 1 {
+            out.push_str(&format!("<code>{}</code>", escape(part)));
+        } else {
+            out.push_str(&emphasis(&links(part)));
+        }
+    }
+    out
+}
+
+/// Escape `text`, turning `[label](url)` into links. Links to anything but
+/// http(s), mailto, and relative URLs keep only their label.
+fn links(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        let Some((label, after)) = rest[open + 1..].split_once("](") else { break };
+        let Some((url, tail)) = after.split_once(')') else { break };
+        out.push_str(&escape(&rest[..open]));
+        if safe_url(url) {
+            out.push_str(&format!("<a href=\"{}\">{}</a>", escape(url), escape(label)));
+        } else {
+            out.push_str(&escape(label));
+        }
+        rest = tail;
+    }
+    out.push_str(&escape(rest));
+    out
+}
+
+/// Whether `url` is http(s), mailto, or relative: any scheme, if present,
+/// comes before the first `/`, `?`, or `#`.
+fn safe_url(url: &str) -> bool {
+    let url = url.trim();
+    let path_start = url.find(['/', '?', '#']).unwrap_or(url.len());
+    match url[..path_start].split_once(':') {
+        None => true,
+        Some((scheme, _)) => ["http", "https", "mailto"].iter().any(|s| scheme.eq_ignore_ascii_case(s)),
+    }
+}
+
+/// `**strong**` and `*emphasis*` pairs in already escaped text.
+fn emphasis(text: &str) -> String {
+    let pairs = |text: &str, marker: &str, tag: &str| {
+        let parts: Vec<&str> = text.split(marker).collect();
+        // An odd number of parts means every marker has a partner
+        if parts.len() < 3 || parts.len().is_multiple_of(2) {
+            return text.to_string();
+        }
+        let mut out = String::new();
+        for (n, part) in parts.iter().enumerate() {
+            if n > 0 {
+                out.push_str(&format!("<{}{}>", if n % 2 == 0 { "/" } else { "" }, tag));
+            }
+            out.push_str(part);
+        }
+        out
+    };
+    pairs(&pairs(text, "**", "strong"), "*", "em")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use archidoc_types::{ArchitectureConfig, DiagramConfig, ElementKind, Relationship};
+
+    fn doc(path: &str, kind: ElementKind, parent: Option<&str>, uses: &[&str]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: format!("src/{}/mod.rs", path.replace('.', "/")),
+            c4_level: kind,
+            pattern: "--".to_string(),
+            description: format!("The <{}> element", path),
+            parent_container: parent.map(str::to_string),
+            relationships: uses
+                .iter()
+                .map(|target| Relationship {
+                    target: target.to_string(),
+                    label: "Uses".to_string(),
+                    protocol: String::new(),
+                    hidden: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn document(skip: &[&str]) -> ArchitectureGenerator {
+        ArchitectureGenerator {
+            file_name: "ARCHITECTURE.md".to_string(),
+            link_base: PathBuf::new(),
+            fitness: vec![],
+            snapshot: Default::default(),
+            language: Language::En,
+            layout: ArchitectureConfig {
+                skip: skip.iter().map(|s| s.to_string()).collect(),
+                ..Default::default()
+            },
+            front_matter: None,
+            diagrams: DiagramConfig::default(),
+        }
+    }
+
+    fn docs() -> Vec<ModuleDoc> {
+        vec![
+            doc("api", ElementKind::Container, None, &["bus"]),
+            doc("bus", ElementKind::Container, None, &[]),
+            doc("bus.lanes", ElementKind::Component, Some("bus"), &[]),
+        ]
+    }
+
+    #[test]
+    fn elements_are_collapsible_and_indexed_in_the_sidebar() {
+        let html = page(&docs(), &document(&[]), "/js/mermaid.mjs");
+
+        assert!(html.starts_with("<!DOCTYPE html>\n<html lang=\"en\">"));
+        assert!(html.contains("<details id=\"api\">\n<summary>api <span class=\"kind\">container</span></summary>\n<p>The &lt;api&gt; element</p>\n"));
+        assert!(html.contains("<li><strong>Uses:</strong> <a href=\"#bus\">bus</a> — Uses</li>"));
+        assert!(html.contains("<li><a href=\"#api\">api</a> <span class=\"kind\">container</span></li>\n<li><details open>\n<summary><a href=\"#bus\">bus</a> <span class=\"kind\">container</span></summary>\n<ul>\n"));
+        assert!(html.contains("<li class=\"component\"><a href=\"#bus_lanes\">bus.lanes</a> <span class=\"kind\">component</span></li>"));
+        assert!(html.contains("<li><a href=\"#section-elements\">Elements</a></li>"));
+        assert!(html.contains("<pre class=\"mermaid\">\nC4Container\n"));
+        assert!(html.contains("import mermaid from \"/js/mermaid.mjs\";"));
+        assert!(!html.contains("## "), "all markdown is converted");
+        let sections = |id: &str| html.find(&format!("<a id=\"section-{}\"></a>", id)).unwrap();
+        assert!(sections("component-diagram") < sections("elements"));
+        assert!(sections("elements") < sections("relationship-map"));
+    }
+
+    #[test]
+    fn skipped_elements_leave_no_sections_or_index() {
+        let html = page(&docs(), &document(&["elements", "relationship_map"]), "m.mjs");
+
+        assert!(!html.contains("<details id=") && !html.contains("section-elements"));
+        assert!(!html.contains("Component Index"));
+    }
+
+    #[test]
+    fn markdown_blocks_and_inlines_convert() {
+        let (html, sections) = to_html(
+            "<a id=\"section-x\"></a>\n\n## X *one*\n\n| A | B |\n|---|---|\n| `a\\|b` | [l](u?a=1&b=2) |\n\n1. first\n2. **second**\n\n> quoted\n\n<script>x</script>\n",
+        );
+
+        assert_eq!(sections, vec![("section-x".to_string(), "X <em>one</em>".to_string())]);
+        assert!(html.contains("<tr><td><code>a|b</code></td><td><a href=\"u?a=1&amp;b=2\">l</a></td></tr>"));
+        assert!(html.contains("<ol>\n<li>first</li>\n<li><strong>second</strong></li>\n</ol>\n"));
+        assert!(html.contains("<blockquote><p>quoted</p></blockquote>"));
+        assert!(html.contains("<p>&lt;script&gt;x&lt;/script&gt;</p>"));
+    }
+
+    #[test]
+    fn prose_cannot_pass_html_or_script_links_through() {
+        let (html, _) = to_html(
+            "<details ontoggle=\"alert(1)\">\n\n<summary><img src=x onerror=alert(1)></summary>\n\n\
+             <a id=\"x\" onclick=\"alert(1)\"></a>\n\n\0<b>raw</b>\n\n\
+             [a](javascript:void) [b](JavaScript:x) [c](https://e.com) [d](mailto:a@b.c) [e](docs/a.md#x) [f](#api)\n",
+        );
+
+        assert!(!html.contains("<details ontoggle") && !html.contains("<img") && !html.contains("<a id=\"x\" onclick"));
+        assert!(html.contains("<p>&lt;b&gt;raw&lt;/b&gt;</p>"));
+        assert!(html.contains("<summary>&lt;img src=x onerror=alert(1)&gt;</summary>"));
+        assert!(!html.contains("javascript:") && !html.contains("JavaScript:"));
+        assert!(html.contains(
+            "<p>a b <a href=\"https://e.com\">c</a> <a href=\"mailto:a@b.c\">d</a> <a href=\"docs/a.md#x\">e</a> <a href=\"#api\">f</a></p>"
+        ), "{}", html);
+    }
+}
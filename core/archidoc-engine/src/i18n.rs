@@ -0,0 +1,437 @@
+use archidoc_types::Language;
+
+/// Fixed text of generated documents and reports in one language.
+///
+/// Templates mark their values as `{name}`; fill them with [`fill`].
+pub struct Strings {
+    // ARCHITECTURE.md
+    pub architecture_title: &'static str,
+    pub auto_generated: &'static str,
+    pub contents: &'static str,
+    pub system_context: &'static str,
+    pub system_diagram: &'static str,
+    pub component_diagram: &'static str,
+    pub component_index: &'static str,
+    pub elements: &'static str,
+    pub relationship_map: &'static str,
+    pub scenarios: &'static str,
+    pub crosscutting: &'static str,
+    pub links: &'static str,
+    pub risk_register: &'static str,
+    pub fitness: &'static str,
+    /// `{passed}`, `{total}`
+    pub rules_pass: &'static str,
+    pub project_health: &'static str,
+    pub validation_findings: &'static str,
+    pub validation_clean: &'static str,
+    pub finding_ghost: &'static str,
+    pub finding_orphan: &'static str,
+    pub finding_invalid_link: &'static str,
+    pub glossary: &'static str,
+    pub patterns_in_use: &'static str,
+    pub metrics: &'static str,
+    pub archived_elements: &'static str,
+    pub archived_intro: &'static str,
+    pub col_module: &'static str,
+    pub col_level: &'static str,
+    pub col_pattern: &'static str,
+    pub col_description: &'static str,
+    pub col_unsafe: &'static str,
+    pub col_coverage: &'static str,
+    pub col_severity: &'static str,
+    pub col_element: &'static str,
+    pub col_risk: &'static str,
+    pub col_concern: &'static str,
+    pub col_rule: &'static str,
+    pub col_result: &'static str,
+    pub col_checked: &'static str,
+    pub col_failures: &'static str,
+    pub col_claimed_by: &'static str,
+    pub col_status: &'static str,
+    pub col_loc: &'static str,
+    pub col_files: &'static str,
+    pub col_public_items: &'static str,
+    pub col_avg_fn_lines: &'static str,
+    pub col_declared_health: &'static str,
+    pub col_file: &'static str,
+    pub col_purpose: &'static str,
+    pub col_health: &'static str,
+    pub col_share: &'static str,
+    pub col_finding: &'static str,
+    pub col_last_seen: &'static str,
+    pub col_removed: &'static str,
+    pub result_pass: &'static str,
+    pub result_fail: &'static str,
+    pub result_not_applicable: &'static str,
+    pub element_source: &'static str,
+    pub element_uses: &'static str,
+    pub element_used_by: &'static str,
+
+    // Diagram text alternatives
+    pub text_system_context: &'static str,
+    pub text_system_diagram: &'static str,
+    pub text_component_diagram: &'static str,
+    /// `{n}`
+    pub text_containers: &'static str,
+    /// `{container}`
+    pub text_contains: &'static str,
+    pub text_uses: &'static str,
+
+    // Health report
+    pub health_title: &'static str,
+    pub health_elements: &'static str,
+    /// `{total}`, `{containers}`, `{components}`
+    pub health_elements_summary: &'static str,
+    pub health_files: &'static str,
+    /// `{n}`
+    pub health_total: &'static str,
+    /// `{loc}`, `{percent}`
+    pub health_loc_share: &'static str,
+    pub health_lines: &'static str,
+    /// `{n}`
+    pub health_lines_measured: &'static str,
+    pub health_patterns: &'static str,
+    /// `{n}`
+    pub health_patterns_assigned: &'static str,
+    /// `{verified}`, `{total}`
+    pub health_file_patterns: &'static str,
+    pub health_reviews: &'static str,
+    /// `{reviewed}`, `{unreviewed}`, `{stale}`
+    pub health_reviews_summary: &'static str,
+    pub health_unsafe: &'static str,
+    /// `{n}`, `{sites}`
+    pub health_unsafe_sites: &'static str,
+    pub health_coverage: &'static str,
+    /// `{percent}`
+    pub health_coverage_lines: &'static str,
+    /// `{threshold}`, `{elements}`
+    pub health_low_coverage: &'static str,
+    /// `{n}`
+    pub health_stale_reviews: &'static str,
+    /// `{element}`, `{reviewer}`, `{date}`, `{hash}`
+    pub health_stale_entry: &'static str,
+}
+
+/// The strings for `language`.
+pub fn strings(language: Language) -> &'static Strings {
+    match language {
+        Language::En => &EN,
+        Language::De => &DE,
+        Language::Ja => &JA,
+    }
+}
+
+/// Replace each `{name}` in `template` with its value.
+pub fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    values.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+static EN: Strings = Strings {
+    architecture_title: "Architecture Context",
+    auto_generated: "Auto-generated by archidoc. Do not edit manually.",
+    contents: "Contents",
+    system_context: "System Context",
+    system_diagram: "System Diagram",
+    component_diagram: "Component Diagram",
+    component_index: "Component Index",
+    elements: "Elements",
+    relationship_map: "Relationship Map",
+    scenarios: "Scenarios",
+    crosscutting: "Cross-cutting Concerns",
+    links: "Links",
+    risk_register: "Risk Register",
+    fitness: "Architectural Fitness",
+    rules_pass: "{passed} of {total} rules pass.",
+    project_health: "Project Health",
+    validation_findings: "Validation Findings",
+    validation_clean: "No ghost or orphan files and no invalid doc links.",
+    finding_ghost: "ghost",
+    finding_orphan: "orphan",
+    finding_invalid_link: "invalid link",
+    glossary: "Glossary",
+    patterns_in_use: "Patterns in Use",
+    metrics: "Metrics",
+    archived_elements: "Archived Elements",
+    archived_intro: "Elements no longer in the code, from the snapshot history. `archidoc purge-archived` drops them.",
+    col_module: "Module",
+    col_level: "Level",
+    col_pattern: "Pattern",
+    col_description: "Description",
+    col_unsafe: "Unsafe",
+    col_coverage: "Coverage",
+    col_severity: "Severity",
+    col_element: "Element",
+    col_risk: "Risk",
+    col_concern: "Concern",
+    col_rule: "Rule",
+    col_result: "Result",
+    col_checked: "Checked",
+    col_failures: "Failures",
+    col_claimed_by: "Claimed By",
+    col_status: "Status",
+    col_loc: "LOC",
+    col_files: "Files",
+    col_public_items: "Public Items",
+    col_avg_fn_lines: "Avg Fn Lines",
+    col_declared_health: "Declared Health",
+    col_file: "File",
+    col_purpose: "Purpose",
+    col_health: "Health",
+    col_share: "Share",
+    col_finding: "Finding",
+    col_last_seen: "Last Seen",
+    col_removed: "Removed",
+    result_pass: "pass",
+    result_fail: "FAIL",
+    result_not_applicable: "n/a",
+    element_source: "Source",
+    element_uses: "Uses",
+    element_used_by: "Used by",
+
+    text_system_context: "Text description of the system context diagram",
+    text_system_diagram: "Text description of the system diagram",
+    text_component_diagram: "Text description of the component diagram",
+    text_containers: "Containers ({n}):",
+    text_contains: "{container} contains:",
+    text_uses: "uses",
+
+    health_title: "Architecture Health Report",
+    health_elements: "Elements",
+    health_elements_summary: "{total} total ({containers} containers, {components} components)",
+    health_files: "Files",
+    health_total: "{n} total",
+    health_loc_share: "{loc} lines ({percent}% by LOC)",
+    health_lines: "lines",
+    health_lines_measured: "{n} measured",
+    health_patterns: "Patterns",
+    health_patterns_assigned: "{n} assigned",
+    health_file_patterns: "in files: {verified} of {total} verified",
+    health_reviews: "Reviews",
+    health_reviews_summary: "{reviewed} reviewed, {unreviewed} unreviewed, {stale} stale, {unverifiable} without a hash",
+    health_unsafe: "Unsafe",
+    health_unsafe_sites: "{n} site(s) in {sites}",
+    health_coverage: "Coverage",
+    health_coverage_lines: "{percent}% of lines",
+    health_low_coverage: "stable claims under {threshold}% coverage: {elements}",
+    health_stale_reviews: "Stale reviews ({n}):",
+    health_stale_entry: "{element} — reviewed by {reviewer} on {date}; content changed (hash now {hash})",
+};
+
+static DE: Strings = Strings {
+    architecture_title: "Architekturkontext",
+    auto_generated: "Automatisch von archidoc erzeugt. Nicht manuell bearbeiten.",
+    contents: "Inhalt",
+    system_context: "Systemkontext",
+    system_diagram: "Systemdiagramm",
+    component_diagram: "Komponentendiagramm",
+    component_index: "Komponentenverzeichnis",
+    elements: "Elemente",
+    relationship_map: "Beziehungen",
+    scenarios: "Szenarien",
+    crosscutting: "Querschnittsthemen",
+    links: "Links",
+    risk_register: "Risikoregister",
+    fitness: "Architektur-Fitness",
+    rules_pass: "{passed} von {total} Regeln erfüllt.",
+    project_health: "Projektzustand",
+    validation_findings: "Validierungsbefunde",
+    validation_clean: "Keine Geister- oder verwaisten Dateien und keine ungültigen Doku-Links.",
+    finding_ghost: "Geistereintrag",
+    finding_orphan: "verwaiste Datei",
+    finding_invalid_link: "ungültiger Link",
+    glossary: "Glossar",
+    patterns_in_use: "Verwendete Muster",
+    metrics: "Metriken",
+    archived_elements: "Archivierte Elemente",
+    archived_intro: "Elemente, die es im Code nicht mehr gibt, laut Snapshot-Verlauf. `archidoc purge-archived` entfernt sie.",
+    col_module: "Modul",
+    col_level: "Ebene",
+    col_pattern: "Muster",
+    col_description: "Beschreibung",
+    col_unsafe: "Unsafe",
+    col_coverage: "Abdeckung",
+    col_severity: "Schweregrad",
+    col_element: "Element",
+    col_risk: "Risiko",
+    col_concern: "Thema",
+    col_rule: "Regel",
+    col_result: "Ergebnis",
+    col_checked: "Geprüft",
+    col_failures: "Verstöße",
+    col_claimed_by: "Beansprucht von",
+    col_status: "Status",
+    col_loc: "LOC",
+    col_files: "Dateien",
+    col_public_items: "Öffentliche Elemente",
+    col_avg_fn_lines: "Ø Fn-Zeilen",
+    col_declared_health: "Deklarierte Reife",
+    col_file: "Datei",
+    col_purpose: "Zweck",
+    col_health: "Reife",
+    col_share: "Anteil",
+    col_finding: "Befund",
+    col_last_seen: "Zuletzt gesehen",
+    col_removed: "Entfernt",
+    result_pass: "ok",
+    result_fail: "FEHLER",
+    result_not_applicable: "n. a.",
+    element_source: "Quelle",
+    element_uses: "Nutzt",
+    element_used_by: "Genutzt von",
+
+    text_system_context: "Textbeschreibung des Systemkontextdiagramms",
+    text_system_diagram: "Textbeschreibung des Systemdiagramms",
+    text_component_diagram: "Textbeschreibung des Komponentendiagramms",
+    text_containers: "Container ({n}):",
+    text_contains: "{container} enthält:",
+    text_uses: "nutzt",
+
+    health_title: "Bericht zur Architekturgesundheit",
+    health_elements: "Elemente",
+    health_elements_summary: "{total} gesamt ({containers} Container, {components} Komponenten)",
+    health_files: "Dateien",
+    health_total: "{n} gesamt",
+    health_loc_share: "{loc} Zeilen ({percent}% nach LOC)",
+    health_lines: "Zeilen",
+    health_lines_measured: "{n} gemessen",
+    health_patterns: "Muster",
+    health_patterns_assigned: "{n} zugewiesen",
+    health_file_patterns: "in Dateien: {verified} von {total} verifiziert",
+    health_reviews: "Reviews",
+    health_reviews_summary: "{reviewed} geprüft, {unreviewed} ungeprüft, {stale} veraltet, {unverifiable} ohne Hash",
+    health_unsafe: "Unsafe",
+    health_unsafe_sites: "{n} Stelle(n) in {sites}",
+    health_coverage: "Abdeckung",
+    health_coverage_lines: "{percent}% der Zeilen",
+    health_low_coverage: "stabile Angaben unter {threshold}% Abdeckung: {elements}",
+    health_stale_reviews: "Veraltete Reviews ({n}):",
+    health_stale_entry: "{element} — geprüft von {reviewer} am {date}; Inhalt geändert (Hash jetzt {hash})",
+};
+
+static JA: Strings = Strings {
+    architecture_title: "アーキテクチャ概要",
+    auto_generated: "archidoc により自動生成されています。手動で編集しないでください。",
+    contents: "目次",
+    system_context: "システムコンテキスト",
+    system_diagram: "システム図",
+    component_diagram: "コンポーネント図",
+    component_index: "コンポーネント一覧",
+    elements: "要素",
+    relationship_map: "依存関係",
+    scenarios: "シナリオ",
+    crosscutting: "横断的関心事",
+    links: "リンク",
+    risk_register: "リスク一覧",
+    fitness: "アーキテクチャ適合性",
+    rules_pass: "{total} 件中 {passed} 件のルールに適合しています。",
+    project_health: "プロジェクトの健全性",
+    validation_findings: "検証結果",
+    validation_clean: "存在しないファイル、未登録のファイル、無効なドキュメントリンクはありません。",
+    finding_ghost: "存在しないファイル",
+    finding_orphan: "未登録のファイル",
+    finding_invalid_link: "無効なリンク",
+    glossary: "用語集",
+    patterns_in_use: "使用中のパターン",
+    metrics: "メトリクス",
+    archived_elements: "アーカイブされた要素",
+    archived_intro: "スナップショット履歴にあり、コードから削除された要素です。`archidoc purge-archived` で消去できます。",
+    col_module: "モジュール",
+    col_level: "レベル",
+    col_pattern: "パターン",
+    col_description: "説明",
+    col_unsafe: "Unsafe",
+    col_coverage: "カバレッジ",
+    col_severity: "重大度",
+    col_element: "要素",
+    col_risk: "リスク",
+    col_concern: "関心事",
+    col_rule: "ルール",
+    col_result: "結果",
+    col_checked: "検査数",
+    col_failures: "違反数",
+    col_claimed_by: "宣言元",
+    col_status: "状態",
+    col_loc: "LOC",
+    col_files: "ファイル",
+    col_public_items: "公開項目",
+    col_avg_fn_lines: "平均関数行数",
+    col_declared_health: "宣言された成熟度",
+    col_file: "ファイル",
+    col_purpose: "目的",
+    col_health: "成熟度",
+    col_share: "割合",
+    col_finding: "検出項目",
+    col_last_seen: "最終確認",
+    col_removed: "削除",
+    result_pass: "合格",
+    result_fail: "不合格",
+    result_not_applicable: "対象外",
+    element_source: "ソース",
+    element_uses: "依存先",
+    element_used_by: "依存元",
+
+    text_system_context: "システムコンテキスト図のテキスト説明",
+    text_system_diagram: "システム図のテキスト説明",
+    text_component_diagram: "コンポーネント図のテキスト説明",
+    text_containers: "コンテナ（{n}）:",
+    text_contains: "{container} の構成要素:",
+    text_uses: "依存先",
+
+    health_title: "アーキテクチャ健全性レポート",
+    health_elements: "要素",
+    health_elements_summary: "計 {total}（コンテナ {containers}、コンポーネント {components}）",
+    health_files: "ファイル",
+    health_total: "計 {n}",
+    health_loc_share: "{loc} 行（LOC 比 {percent}%）",
+    health_lines: "行数",
+    health_lines_measured: "{n} 行を計測",
+    health_patterns: "パターン",
+    health_patterns_assigned: "{n} 件割り当て",
+    health_file_patterns: "ファイル単位: {total} 件中 {verified} 件を検証済み",
+    health_reviews: "レビュー",
+    health_reviews_summary: "レビュー済み {reviewed}、未レビュー {unreviewed}、期限切れ {stale}、ハッシュなし {unverifiable}",
+    health_unsafe: "Unsafe",
+    health_unsafe_sites: "{n} 箇所（{sites}）",
+    health_coverage: "カバレッジ",
+    health_coverage_lines: "行の {percent}%",
+    health_low_coverage: "カバレッジ {threshold}% 未満の stable 宣言: {elements}",
+    health_stale_reviews: "期限切れのレビュー（{n}）:",
+    health_stale_entry: "{element} — {date} に {reviewer} がレビュー。以降に内容が変更されています（現在のハッシュ {hash}）",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_replaces_every_placeholder() {
+        let text = fill(strings(Language::De).rules_pass, &[("passed", "3"), ("total", "4")]);
+
+        assert_eq!(text, "3 von 4 Regeln erfüllt.");
+    }
+
+    #[test]
+    fn templates_keep_their_placeholders_in_every_language() {
+        for language in [Language::De, Language::Ja] {
+            let (en, other) = (strings(Language::En), strings(language));
+            for (a, b) in [
+                (en.rules_pass, other.rules_pass),
+                (en.health_elements_summary, other.health_elements_summary),
+                (en.health_loc_share, other.health_loc_share),
+                (en.health_file_patterns, other.health_file_patterns),
+                (en.health_reviews_summary, other.health_reviews_summary),
+                (en.health_unsafe_sites, other.health_unsafe_sites),
+                (en.health_low_coverage, other.health_low_coverage),
+                (en.health_stale_entry, other.health_stale_entry),
+            ] {
+                let mut expected: Vec<&str> = a.split('{').skip(1).filter_map(|s| s.split_once('}')).map(|(n, _)| n).collect();
+                let mut found: Vec<&str> = b.split('{').skip(1).filter_map(|s| s.split_once('}')).map(|(n, _)| n).collect();
+                expected.sort_unstable();
+                found.sort_unstable();
+                assert_eq!(expected, found, "{:?}: {}", language, b);
+            }
+        }
+    }
+}
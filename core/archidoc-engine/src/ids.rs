@@ -0,0 +1,105 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use archidoc_types::ModuleDoc;
+
+/// Give every element a diagram id no other element shares, warning about
+/// each one that had to change.
+///
+/// Ids default to the module path with dots replaced by underscores, so
+/// `bus.calc` and `bus_calc` would both be `bus_calc`, and two elements may
+/// declare the same `@id`. Elements claim their id in module path order,
+/// declared `@id`s before path-derived ones; a later claimant gets the
+/// first free `_2`, `_3`, ... suffix, recorded as its `id` so every
+/// generator and relationship resolves to it.
+pub fn assign_ids(docs: &mut [ModuleDoc]) -> Vec<String> {
+    let mut order: Vec<usize> = (0..docs.len()).collect();
+    order.sort_by(|&a, &b| {
+        docs[a]
+            .id
+            .is_none()
+            .cmp(&docs[b].id.is_none())
+            .then_with(|| docs[a].module_path.cmp(&docs[b].module_path))
+    });
+
+    let mut owners: BTreeMap<String, String> = BTreeMap::new();
+    let wanted: BTreeSet<String> = docs.iter().map(ModuleDoc::element_id).collect();
+    let mut warnings = Vec::new();
+    for i in order {
+        let doc = &mut docs[i];
+        let id = doc.element_id();
+        let Some(owner) = owners.get(&id) else {
+            owners.insert(id, doc.module_path.clone());
+            continue;
+        };
+        let free = (2..)
+            .map(|n| format!("{}_{}", id, n))
+            .find(|candidate| !owners.contains_key(candidate) && !wanted.contains(candidate))
+            .expect("an unused suffix exists");
+        warnings.push(format!(
+            "diagram id '{}' of '{}' is taken by '{}'; using '{}'",
+            id, doc.module_path, owner, free
+        ));
+        owners.insert(free.clone(), doc.module_path.clone());
+        doc.id = Some(free);
+    }
+    warnings
+}
+
+/// Module path to diagram id of every element, as the generators use them.
+pub fn id_map(docs: &[ModuleDoc]) -> BTreeMap<String, String> {
+    docs.iter().map(|doc| (doc.module_path.clone(), doc.element_id())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::ElementKind;
+
+    fn doc(path: &str, id: Option<&str>) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: format!("src/{}/mod.rs", path.replace('.', "/")),
+            c4_level: ElementKind::Component,
+            pattern: "--".to_string(),
+            description: format!("The {} element", path),
+            id: id.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn colliding_ids_get_the_first_free_suffix_in_path_order() {
+        let mut docs = vec![
+            doc("bus_calc", None),
+            doc("bus.calc", None),
+            doc("bus.calc_2", None),
+            doc("api", None),
+        ];
+
+        let warnings = assign_ids(&mut docs);
+
+        assert_eq!(warnings, ["diagram id 'bus_calc' of 'bus_calc' is taken by 'bus.calc'; using 'bus_calc_3'"]);
+        let ids = id_map(&docs);
+        assert_eq!(ids["bus.calc"], "bus_calc");
+        assert_eq!(ids["bus.calc_2"], "bus_calc_2");
+        assert_eq!(ids["bus_calc"], "bus_calc_3");
+        assert_eq!(ids["api"], "api");
+        assert_eq!(docs[1].id, None, "the first claimant keeps its derived id");
+    }
+
+    #[test]
+    fn declared_ids_win_over_derived_ones() {
+        let mut docs = vec![doc("engine", None), doc("matching", Some("engine")), doc("orders", Some("engine"))];
+
+        let warnings = assign_ids(&mut docs);
+
+        assert_eq!(
+            warnings,
+            [
+                "diagram id 'engine' of 'orders' is taken by 'matching'; using 'engine_2'",
+                "diagram id 'engine' of 'engine' is taken by 'matching'; using 'engine_3'",
+            ]
+        );
+        assert!(assign_ids(&mut docs).is_empty(), "assigned ids are stable");
+    }
+}
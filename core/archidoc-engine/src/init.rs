@@ -34,31 +34,44 @@ impl CommentStyle {
 ///
 /// Outputs a doc comment block with recommended architectural sections,
 /// each with TODO placeholders. Designed to be pasted into `lib.rs`, `index.ts`, etc.
+/// Rust templates declare the people and external systems the System
+/// Context diagram is generated from; TypeScript ones hold the diagram.
 pub fn generate_template(style: CommentStyle) -> String {
-    let sections = vec![
+    let mut sections = vec![
         Section::heading("@c4 container"),
         Section::heading("[Project Name]").h1(),
         Section::blank(),
         Section::line("[TODO: One-line description — what this system does and why it exists.]"),
         Section::blank(),
-        Section::heading("C4 Context").h2(),
-        Section::blank(),
-        Section::code_block(
-            "mermaid",
-            &[
-                "C4Context",
-                "    title System Context Diagram",
-                "",
-                "    Person(user, \"TODO: User\", \"TODO: Primary user/actor\")",
-                "    System(system, \"TODO: System Name\", \"TODO: System purpose\")",
-                "    System_Ext(ext1, \"TODO: External System\", \"TODO: External dependency\")",
-                "",
-                "    Rel(user, system, \"Uses\")",
-                "    Rel(system, ext1, \"TODO: relationship\", \"TODO: protocol\")",
-                "",
-                "    UpdateLayoutConfig($c4ShapeInRow=\"3\", $c4BoundaryInRow=\"1\")",
-            ],
-        ),
+    ];
+    match style {
+        CommentStyle::Rust => sections.extend([
+            Section::line("@c4 person user \"TODO: Primary user/actor\" uses _lib \"Uses\""),
+            Section::line("@c4 external ext1 \"TODO: External dependency\""),
+            Section::line("@c4 uses ext1 \"TODO: relationship\" \"TODO: protocol\""),
+        ]),
+        CommentStyle::TypeScript => sections.extend([
+            Section::heading("C4 Context").h2(),
+            Section::blank(),
+            Section::code_block(
+                "mermaid",
+                &[
+                    "C4Context",
+                    "    title System Context Diagram",
+                    "",
+                    "    Person(user, \"TODO: User\", \"TODO: Primary user/actor\")",
+                    "    System(system, \"TODO: System Name\", \"TODO: System purpose\")",
+                    "    System_Ext(ext1, \"TODO: External System\", \"TODO: External dependency\")",
+                    "",
+                    "    Rel(user, system, \"Uses\")",
+                    "    Rel(system, ext1, \"TODO: relationship\", \"TODO: protocol\")",
+                    "",
+                    "    UpdateLayoutConfig($c4ShapeInRow=\"3\", $c4BoundaryInRow=\"1\")",
+                ],
+            ),
+        ]),
+    }
+    sections.extend([
         Section::blank(),
         Section::heading("Data Flow").h2(),
         Section::blank(),
@@ -80,7 +93,7 @@ pub fn generate_template(style: CommentStyle) -> String {
         Section::blank(),
         Section::line("- TODO: Third-party APIs and services"),
         Section::line("- TODO: Databases and storage systems"),
-    ];
+    ]);
 
     render(style, &sections)
 }
@@ -209,7 +222,6 @@ mod tests {
     fn rust_template_has_all_sections() {
         let out = generate_template(CommentStyle::Rust);
         assert!(out.contains("# [Project Name]"));
-        assert!(out.contains("## C4 Context"));
         assert!(out.contains("## Data Flow"));
         assert!(out.contains("## Concurrency & Data Patterns"));
         assert!(out.contains("## Deployment"));
@@ -217,11 +229,19 @@ mod tests {
     }
 
     #[test]
-    fn rust_template_has_mermaid_block() {
+    fn rust_template_declares_the_system_context() {
         let out = generate_template(CommentStyle::Rust);
+        assert!(out.contains("//! @c4 person user \"TODO: Primary user/actor\" uses _lib \"Uses\"\n"));
+        assert!(out.contains("//! @c4 external ext1 \"TODO: External dependency\"\n"));
+        assert!(!out.contains("C4Context"));
+    }
+
+    #[test]
+    fn typescript_template_has_mermaid_block() {
+        let out = generate_template(CommentStyle::TypeScript);
+        assert!(out.contains(" * ## C4 Context"));
         assert!(out.contains("```mermaid"));
         assert!(out.contains("C4Context"));
-        assert!(out.contains("```"));
     }
 
     #[test]
@@ -1,27 +1,642 @@
-use archidoc_types::ModuleDoc;
+use archidoc_types::{ElementKind, IrViolation, ModuleDoc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// IR schema version this archidoc writes.
+///
+/// Version 1 is a bare `ModuleDoc[]` array; from version 2 on, the modules
+/// sit in an object next to `ir_version`. Older versions are upgraded on
+/// read (see [`upgrade`]), so a pipeline keeps consuming IR from
+/// repositories whose adapters have not caught up yet.
+pub const VERSION: u64 = 2;
+
+/// Steps from each older version to the next; `UPGRADES[n - 1]` turns
+/// version `n` into version `n + 1`.
+const UPGRADES: &[fn(Value) -> Value] = &[v1_to_v2];
+
+/// The envelope of the current IR version.
+#[derive(Serialize)]
+struct Document<'a> {
+    ir_version: u64,
+    modules: &'a [ModuleDoc],
+}
+
+#[derive(Deserialize)]
+struct OwnedDocument {
+    modules: Vec<ModuleDoc>,
+}
 
 /// Serialize a slice of ModuleDocs to JSON IR.
 ///
 /// This produces the portable intermediate representation that bridges
 /// language adapters and the core generator.
 pub fn serialize(docs: &[ModuleDoc]) -> String {
-    serde_json::to_string_pretty(docs).expect("failed to serialize ModuleDoc to JSON")
+    let document = Document {
+        ir_version: VERSION,
+        modules: docs,
+    };
+    serde_json::to_string_pretty(&document).expect("failed to serialize ModuleDoc to JSON")
 }
 
-/// Deserialize JSON IR into ModuleDocs.
+/// Drop the raw annotation text from `docs`, keeping the structured fields.
+///
+/// For projects with long narratives most of the IR is content that only
+/// the narrative sections read; without it those sections fall back to the
+/// root element's description. The content's hash is kept in
+/// `content_hash`, so reviews and renames are still checked against it.
+pub fn strip_content(docs: &mut [ModuleDoc]) {
+    for doc in docs {
+        doc.content_hash = crate::hash::annotation_hash(doc);
+        doc.content = String::new();
+    }
+}
+
+/// Deserialize JSON IR of any supported version into ModuleDocs.
 ///
 /// Returns an error message if the JSON is malformed or does not
 /// conform to the ModuleDoc[] schema.
 pub fn deserialize(json: &str) -> Result<Vec<ModuleDoc>, String> {
-    serde_json::from_str(json).map_err(|e| format!("invalid IR: {}", e))
+    let value: Value = serde_json::from_str(json).map_err(|e| format!("invalid IR: {}", e))?;
+    let (_, value) = upgrade(value)?;
+    let document: OwnedDocument = serde_json::from_value(value).map_err(|e| format!("invalid IR: {}", e))?;
+    Ok(document.modules)
+}
+
+/// Bring an IR document of any supported version up to [`VERSION`].
+/// Returns the version it was written in and the upgraded document.
+pub fn upgrade(mut value: Value) -> Result<(u64, Value), String> {
+    let from = version(&value).map_err(|(_, message)| format!("invalid IR: {}", message))?;
+    for step in &UPGRADES[from as usize - 1..] {
+        value = step(value);
+    }
+    Ok((from, value))
+}
+
+/// `json` rewritten as IR of the current version, with the version it was
+/// written in. Fields the schema does not define are dropped.
+pub fn upgrade_json(json: &str) -> Result<(u64, String), String> {
+    let value: Value = serde_json::from_str(json).map_err(|e| format!("invalid IR: {}", e))?;
+    let from = version(&value).map_err(|(_, message)| format!("invalid IR: {}", message))?;
+    Ok((from, serialize(&deserialize(json)?)))
+}
+
+/// The IR version of a document, or the pointer and message of what makes
+/// it unreadable.
+fn version(value: &Value) -> Result<u64, (&'static str, String)> {
+    if value.is_array() {
+        return Ok(1);
+    }
+    let Some(object) = value.as_object() else {
+        return Err(("", "expected an IR object with ir_version and modules, or a version 1 array".to_string()));
+    };
+    match object.get("ir_version").map(Value::as_u64) {
+        None => Err(("", "missing required field 'ir_version'".to_string())),
+        Some(Some(version)) if (2..=VERSION).contains(&version) => Ok(version),
+        Some(Some(version)) if version > VERSION => Err((
+            "/ir_version",
+            format!("IR version {} is newer than this archidoc reads (up to {}); upgrade archidoc", version, VERSION),
+        )),
+        Some(_) => Err(("/ir_version", format!("expected an IR version from 2 to {}", VERSION))),
+    }
+}
+
+/// Version 1 was the bare module array.
+fn v1_to_v2(modules: Value) -> Value {
+    serde_json::json!({ "ir_version": 2, "modules": modules })
 }
 
 /// Validate JSON IR without deserializing into a full result.
 ///
 /// Returns Ok(()) if the JSON conforms to the ModuleDoc[] schema,
-/// or Err with a description of what's wrong.
+/// or Err listing every violation, one per line.
 pub fn validate(json: &str) -> Result<(), String> {
-    let _: Vec<ModuleDoc> = serde_json::from_str(json)
-        .map_err(|e| format!("IR validation failed: {}", e))?;
-    Ok(())
+    let violations = violations(json);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(format_violations(&violations))
+    }
+}
+
+/// Check JSON IR against `core/spec/archidoc-ir-schema.json` and return
+/// every violation, each with a JSON pointer to the offending value.
+///
+/// Unlike deserializing, this does not stop at the first problem, and it
+/// rejects fields the schema does not define. Older versions are upgraded
+/// first; pointers still point into the document as written.
+pub fn violations(json: &str) -> Vec<IrViolation> {
+    let value: Value = match serde_json::from_str(json) {
+        Ok(value) => value,
+        Err(e) => return vec![violation("", format!("invalid JSON: {}", e))],
+    };
+    if let Err((pointer, message)) = version(&value) {
+        return vec![violation(pointer, message)];
+    }
+    let modules_at = if value.is_array() { "" } else { "/modules" };
+    let (_, value) = upgrade(value).expect("the version was read");
+
+    let mut checker = Checker::default();
+    checker.document(modules_at, &value);
+    // The checks mirror the schema; deserializing catches anything they miss
+    if checker.violations.is_empty() {
+        if let Err(e) = serde_json::from_value::<OwnedDocument>(value) {
+            checker.report("", e.to_string());
+        }
+    }
+    checker.violations
+}
+
+/// Format violations as human-readable text, one `pointer: message` line
+/// each.
+pub fn format_violations(violations: &[IrViolation]) -> String {
+    let mut out = format!(
+        "IR validation failed with {} violation{}:",
+        violations.len(),
+        if violations.len() == 1 { "" } else { "s" }
+    );
+    for v in violations {
+        if v.pointer.is_empty() {
+            out.push_str(&format!("\n  {}", v.message));
+        } else {
+            out.push_str(&format!("\n  {}: {}", v.pointer, v.message));
+        }
+    }
+    out
+}
+
+fn violation(pointer: &str, message: String) -> IrViolation {
+    IrViolation {
+        pointer: pointer.to_string(),
+        message,
+    }
+}
+
+/// Walks parsed IR, collecting violations. Each method checks the value at
+/// `at` (a JSON pointer) against one definition of the schema.
+#[derive(Default)]
+struct Checker {
+    violations: Vec<IrViolation>,
+}
+
+impl Checker {
+    fn report(&mut self, at: &str, message: String) {
+        self.violations.push(violation(at, message));
+    }
+
+    fn wrong_type(&mut self, at: &str, expected: &str, value: &Value) {
+        let found = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "a boolean",
+            Value::Number(_) => "a number",
+            Value::String(_) => "a string",
+            Value::Array(_) => "an array",
+            Value::Object(_) => "an object",
+        };
+        self.report(at, format!("expected {}, found {}", expected, found));
+    }
+
+    /// The object at `at`, once its `required` fields are checked for.
+    fn object<'v>(&mut self, at: &str, value: &'v Value, required: &[&str]) -> Option<&'v Map<String, Value>> {
+        let Some(object) = value.as_object() else {
+            self.wrong_type(at, "an object", value);
+            return None;
+        };
+        for field in required {
+            if !object.contains_key(*field) {
+                self.report(at, format!("missing required field '{}'", field));
+            }
+        }
+        Some(object)
+    }
+
+    /// Each field of `object` as `(pointer, name, value)`.
+    fn fields<'v>(at: &str, object: &'v Map<String, Value>) -> Vec<(String, &'v str, &'v Value)> {
+        object
+            .iter()
+            .map(|(name, value)| (format!("{}/{}", at, name.replace('~', "~0").replace('/', "~1")), name.as_str(), value))
+            .collect()
+    }
+
+    fn unknown_field(&mut self, at: &str) {
+        self.report(at, "unknown field".to_string());
+    }
+
+    fn string<'v>(&mut self, at: &str, value: &'v Value) -> Option<&'v str> {
+        let text = value.as_str();
+        if text.is_none() {
+            self.wrong_type(at, "a string", value);
+        }
+        text
+    }
+
+    fn boolean(&mut self, at: &str, value: &Value) {
+        if !value.is_boolean() {
+            self.wrong_type(at, "a boolean", value);
+        }
+    }
+
+    fn count(&mut self, at: &str, value: &Value) {
+        if !value.is_u64() {
+            self.wrong_type(at, "a non-negative integer", value);
+        }
+    }
+
+    fn one_of(&mut self, at: &str, value: &Value, allowed: &[&str]) {
+        if let Some(text) = self.string(at, value) {
+            if !allowed.contains(&text) {
+                self.report(at, format!("unknown value '{}', expected one of {}", text, allowed.join(", ")));
+            }
+        }
+    }
+
+    /// A string whose characters all satisfy `valid`, described by `rule`.
+    fn token(&mut self, at: &str, value: &Value, valid: fn(char) -> bool, rule: &str) {
+        if let Some(text) = self.string(at, value) {
+            if text.is_empty() || !text.chars().all(valid) {
+                self.report(at, format!("'{}' must be {}", text, rule));
+            }
+        }
+    }
+
+    fn array(&mut self, at: &str, value: &Value, item: fn(&mut Self, &str, &Value)) {
+        match value.as_array() {
+            Some(items) => {
+                for (i, value) in items.iter().enumerate() {
+                    item(self, &format!("{}/{}", at, i), value);
+                }
+            }
+            None => self.wrong_type(at, "an array", value),
+        }
+    }
+
+    /// The envelope, with its modules reported under `modules_at`.
+    fn document(&mut self, modules_at: &str, value: &Value) {
+        let Some(document) = self.object("", value, &["ir_version", "modules"]) else {
+            return;
+        };
+        for (at, name, value) in Self::fields("", document) {
+            match name {
+                "ir_version" => {}
+                "modules" => self.array(modules_at, value, Self::module_doc),
+                _ => self.unknown_field(&at),
+            }
+        }
+    }
+
+    fn module_doc(&mut self, at: &str, value: &Value) {
+        let required = [
+            "module_path",
+            "source_file",
+            "c4_level",
+            "pattern",
+            "pattern_status",
+            "description",
+            "parent_container",
+            "relationships",
+            "files",
+        ];
+        let Some(doc) = self.object(at, value, &required) else {
+            return;
+        };
+        for (at, name, value) in Self::fields(at, doc) {
+            let at = at.as_str();
+            match name {
+                "module_path" | "content" | "source_file" | "pattern" | "description" | "files_dir" | "condition" => {
+                    self.string(at, value);
+                }
+                "c4_level" => self.kind(at, value),
+                "pattern_status" => self.one_of(at, value, &["planned", "verified"]),
+                "parent_container" if value.is_null() => {}
+                "parent_container" => {
+                    self.string(at, value);
+                }
+                "relationships" => self.array(at, value, Self::relationship),
+                "files" => self.array(at, value, Self::file_entry),
+                "review" => self.review(at, value),
+                "risks" => self.array(at, value, Self::risk),
+                "terms" => self.array(at, value, Self::term),
+                "links" => self.array(at, value, Self::link),
+                "metrics" => match value.as_object() {
+                    Some(metrics) => {
+                        for (at, _, value) in Self::fields(at, metrics) {
+                            self.count(&at, value);
+                        }
+                    }
+                    None => self.wrong_type(at, "an object", value),
+                },
+                "unsafe_count" => self.count(at, value),
+                "pattern_evidence" => self.pattern_evidence(at, value),
+                "standalone" | "crosscutting" => self.boolean(at, value),
+                "content_hash" => self.token(at, value, |c| c.is_ascii_hexdigit(), "hex digits"),
+                "flows" => self.array(at, value, Self::flow),
+                "id" => self.token(at, value, |c| c.is_ascii_alphanumeric() || c == '_', "letters, digits, and underscores"),
+                "group" => self.token(at, value, |c| !c.is_whitespace(), "non-empty with no whitespace"),
+                _ => self.unknown_field(at),
+            }
+        }
+    }
+
+    fn kind(&mut self, at: &str, value: &Value) {
+        if let Some(text) = self.string(at, value) {
+            let kind = ElementKind::parse(text);
+            if kind.to_string() != text || (kind == ElementKind::Unknown && text != "unknown") {
+                self.report(
+                    at,
+                    format!(
+                        "unknown element kind '{}', expected one of context, container, component, person, external_system, deployment_node, unknown, or custom:<name>",
+                        text
+                    ),
+                );
+            }
+        }
+    }
+
+    fn relationship(&mut self, at: &str, value: &Value) {
+        let Some(relationship) = self.object(at, value, &["target", "label", "protocol"]) else {
+            return;
+        };
+        for (at, name, value) in Self::fields(at, relationship) {
+            match name {
+                "target" | "label" | "protocol" => {
+                    self.string(&at, value);
+                }
+                "hidden" => self.boolean(&at, value),
+                _ => self.unknown_field(&at),
+            }
+        }
+    }
+
+    fn file_entry(&mut self, at: &str, value: &Value) {
+        let Some(file) = self.object(at, value, &["name", "pattern", "pattern_status", "purpose", "health"]) else {
+            return;
+        };
+        for (at, name, value) in Self::fields(at, file) {
+            match name {
+                "name" | "pattern" | "purpose" => {
+                    self.string(&at, value);
+                }
+                "pattern_status" => self.one_of(&at, value, &["planned", "verified"]),
+                "health" => self.one_of(&at, value, &["planned", "active", "stable"]),
+                "loc" => self.count(&at, value),
+                _ => self.unknown_field(&at),
+            }
+        }
+    }
+
+    fn review(&mut self, at: &str, value: &Value) {
+        let Some(review) = self.object(at, value, &["reviewer", "date"]) else {
+            return;
+        };
+        for (at, name, value) in Self::fields(at, review) {
+            match name {
+                "reviewer" | "date" | "content_hash" => {
+                    self.string(&at, value);
+                }
+                _ => self.unknown_field(&at),
+            }
+        }
+    }
+
+    fn risk(&mut self, at: &str, value: &Value) {
+        let Some(risk) = self.object(at, value, &["description", "severity"]) else {
+            return;
+        };
+        for (at, name, value) in Self::fields(at, risk) {
+            match name {
+                "description" => {
+                    self.string(&at, value);
+                }
+                "severity" => self.one_of(&at, value, &["low", "medium", "high", "critical"]),
+                _ => self.unknown_field(&at),
+            }
+        }
+    }
+
+    fn term(&mut self, at: &str, value: &Value) {
+        let Some(term) = self.object(at, value, &["name", "definition"]) else {
+            return;
+        };
+        for (at, name, value) in Self::fields(at, term) {
+            match name {
+                "name" | "definition" => {
+                    self.string(&at, value);
+                }
+                _ => self.unknown_field(&at),
+            }
+        }
+    }
+
+    fn link(&mut self, at: &str, value: &Value) {
+        let Some(link) = self.object(at, value, &["url"]) else {
+            return;
+        };
+        for (at, name, value) in Self::fields(at, link) {
+            match name {
+                "url" | "title" => {
+                    self.string(&at, value);
+                }
+                _ => self.unknown_field(&at),
+            }
+        }
+    }
+
+    fn pattern_evidence(&mut self, at: &str, value: &Value) {
+        let Some(evidence) = self.object(at, value, &["file"]) else {
+            return;
+        };
+        for (at, name, value) in Self::fields(at, evidence) {
+            match name {
+                "file" | "item" => {
+                    self.string(&at, value);
+                }
+                _ => self.unknown_field(&at),
+            }
+        }
+    }
+
+    fn flow(&mut self, at: &str, value: &Value) {
+        let Some(flow) = self.object(at, value, &["name", "steps"]) else {
+            return;
+        };
+        for (at, name, value) in Self::fields(at, flow) {
+            match name {
+                "name" => self.token(&at, value, |c| c.is_ascii_alphanumeric() || c == '_' || c == '-', "letters, digits, '_', and '-'"),
+                "steps" => {
+                    self.array(&at, value, |checker, at, step| {
+                        checker.string(at, step);
+                    });
+                    if value.as_array().is_some_and(|steps| steps.len() < 2) {
+                        self.report(&at, "a flow needs at least 2 steps".to_string());
+                    }
+                }
+                _ => self.unknown_field(&at),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{DocLink, FileEntry, Flow, HealthStatus, PatternEvidence, PatternStatus, Relationship, Review, Risk, RiskSeverity, Term};
+
+    fn full_doc() -> ModuleDoc {
+        ModuleDoc {
+            module_path: "bus".to_string(),
+            content: "@c4 container".to_string(),
+            content_hash: Some("9f2c4e61a0b3d857".to_string()),
+            source_file: "src/bus/mod.rs".to_string(),
+            c4_level: ElementKind::Custom("queue".to_string()),
+            pattern: "Mediator".to_string(),
+            pattern_status: PatternStatus::Verified,
+            description: "Routes events".to_string(),
+            parent_container: Some("app".to_string()),
+            relationships: vec![Relationship {
+                target: "store".to_string(),
+                label: "Persists".to_string(),
+                protocol: "sql".to_string(),
+                hidden: true,
+            }],
+            files: vec![FileEntry {
+                name: "lanes.rs".to_string(),
+                pattern: "--".to_string(),
+                pattern_status: PatternStatus::Planned,
+                purpose: "Event lanes".to_string(),
+                health: HealthStatus::Active,
+                loc: Some(42),
+            }],
+            files_dir: Some("impl".to_string()),
+            review: Some(Review {
+                reviewer: "ana".to_string(),
+                date: "2024-05-01".to_string(),
+                content_hash: Some("abc".to_string()),
+            }),
+            risks: vec![Risk {
+                description: "Single writer".to_string(),
+                severity: RiskSeverity::High,
+            }],
+            terms: vec![Term {
+                name: "Lane".to_string(),
+                definition: "An ordered queue".to_string(),
+            }],
+            links: vec![DocLink {
+                url: "https://example.com".to_string(),
+                title: "Design".to_string(),
+            }],
+            condition: Some("feature: bus".to_string()),
+            metrics: [("loc".to_string(), 42)].into_iter().collect(),
+            unsafe_count: 1,
+            pattern_evidence: Some(PatternEvidence {
+                file: "mediator.rs".to_string(),
+                item: "Hub".to_string(),
+            }),
+            standalone: true,
+            id: Some("event_bus".to_string()),
+            group: Some("domain:events".to_string()),
+            crosscutting: true,
+            flows: vec![Flow {
+                name: "publish".to_string(),
+                steps: vec!["bus".to_string(), "store".to_string()],
+            }],
+        }
+    }
+
+    #[test]
+    fn serialized_docs_with_every_field_are_valid() {
+        assert_eq!(violations(&serialize(&[full_doc()])), vec![]);
+        assert_eq!(validate("[]"), Ok(()));
+    }
+
+    #[test]
+    fn every_violation_is_reported_with_its_pointer() {
+        let mut value = serde_json::to_value([full_doc(), full_doc()]).unwrap();
+        value[0]["c4_level"] = "service".into();
+        value[0].as_object_mut().unwrap().remove("files");
+        value[1]["files"][0]["health"] = "deprecated".into();
+        value[1]["files"][0]["loc"] = (-1).into();
+        value[1]["metrics"]["a/b"] = "many".into();
+        value[1]["flows"][0]["steps"] = serde_json::json!(["bus"]);
+        value[1]["owner"] = "ops".into();
+
+        let found: Vec<String> = violations(&value.to_string()).iter().map(|v| format!("{}: {}", v.pointer, v.message)).collect();
+
+        assert_eq!(
+            found,
+            vec![
+                "/0: missing required field 'files'",
+                "/0/c4_level: unknown element kind 'service', expected one of context, container, component, person, external_system, deployment_node, unknown, or custom:<name>",
+                "/1/files/0/health: unknown value 'deprecated', expected one of planned, active, stable",
+                "/1/files/0/loc: expected a non-negative integer, found a number",
+                "/1/flows/0/steps: a flow needs at least 2 steps",
+                "/1/metrics/a~1b: expected a non-negative integer, found a string",
+                "/1/owner: unknown field",
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_documents_are_one_violation() {
+        let err = validate("[{").unwrap_err();
+        assert!(err.starts_with("IR validation failed with 1 violation:\n  invalid JSON: "), "{}", err);
+        assert_eq!(violations("{}"), vec![violation("", "missing required field 'ir_version'".to_string())]);
+        assert_eq!(
+            violations(r#"{"ir_version": 2, "modules": {}, "tool": "x"}"#),
+            vec![
+                violation("/modules", "expected an array, found an object".to_string()),
+                violation("/tool", "unknown field".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn version_1_arrays_are_upgraded_on_read() {
+        let v1 = serde_json::to_string(&[full_doc()]).unwrap();
+
+        assert_eq!(deserialize(&v1).unwrap(), vec![full_doc()]);
+        assert_eq!(deserialize(&serialize(&[full_doc()])).unwrap(), vec![full_doc()]);
+        let (from, upgraded) = upgrade_json(&v1).unwrap();
+        assert_eq!((from, upgraded), (1, serialize(&[full_doc()])));
+        assert_eq!(upgrade_json(&serialize(&[])).unwrap().0, VERSION);
+        assert_eq!(UPGRADES.len() as u64, VERSION - 1);
+
+        let mut value = serde_json::to_value([full_doc()]).unwrap();
+        value[0]["pattern_status"] = "maybe".into();
+        let wrapped = serde_json::json!({ "ir_version": 2, "modules": value.clone() });
+        assert_eq!(violations(&value.to_string())[0].pointer, "/0/pattern_status");
+        assert_eq!(violations(&wrapped.to_string())[0].pointer, "/modules/0/pattern_status");
+    }
+
+    #[test]
+    fn stripped_content_keeps_its_hash_for_reviews() {
+        let mut doc = full_doc();
+        doc.content_hash = None;
+        doc.review = Some(Review {
+            reviewer: "ana".to_string(),
+            date: "2024-05-01".to_string(),
+            content_hash: Some(crate::hash::content_hash(&doc.content)),
+        });
+        let mut docs = vec![doc];
+        strip_content(&mut docs);
+
+        let docs = deserialize(&serialize(&docs)).unwrap();
+        assert!(docs[0].content.is_empty());
+        let report = crate::health::aggregate_health(&docs);
+        assert_eq!((report.reviews_current, report.reviews_stale), (1, 0));
+
+        let mut legacy = docs[0].clone();
+        legacy.content_hash = None;
+        assert_eq!(crate::health::aggregate_health(&[legacy]).reviews_unverifiable, 1);
+    }
+
+    #[test]
+    fn newer_versions_are_refused() {
+        let json = r#"{"ir_version": 3, "modules": []}"#;
+
+        assert_eq!(
+            deserialize(json),
+            Err("invalid IR: IR version 3 is newer than this archidoc reads (up to 2); upgrade archidoc".to_string())
+        );
+        assert_eq!(violations(json)[0].pointer, "/ir_version");
+        assert!(deserialize(r#"{"ir_version": 1, "modules": []}"#).is_err());
+    }
 }
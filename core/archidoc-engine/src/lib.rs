@@ -7,27 +7,117 @@
 //! | File | Pattern | Purpose | Health |
 //! |------|---------|---------|--------|
 //! | `architecture.rs` | -- | Single ARCHITECTURE.md generator | stable |
+//! | `adapters.rs` | -- | Adapter detection and feature parity | active |
 //! | `ai_context.rs` | -- | Token-optimized AI context generator | active |
 //! | `mermaid.rs` | -- | Mermaid C4 diagram generation | stable |
 //! | `drawio.rs` | -- | draw.io CSV generation | stable |
+//! | `d2.rs` | -- | D2 diagram generation | active |
+//! | `structurizr.rs` | -- | Structurizr DSL workspace generation | active |
 //! | `plantuml.rs` | -- | PlantUML C4 diagram generation | stable |
+//! | `describe.rs` | -- | Text alternatives for diagrams | active |
+//! | `generator.rs` | Strategy | Pluggable output generators | active |
+//! | `plugin.rs` | Adapter | External generators run as subprocesses | active |
 //! | `ir.rs` | -- | JSON IR serialization and validation | stable |
 //! | `check.rs` | -- | Documentation drift detection | stable |
+//! | `confluence.rs` | -- | Confluence storage-format pages and publishing | active |
+//! | `compile.rs` | -- | Output writing and run reports | active |
+//! | `line_diff.rs` | -- | Unified line diffs for drift reports | active |
+//! | `config.rs` | -- | archidoc.toml loading | active |
 //! | `health.rs` | -- | Health report aggregation | stable |
+//! | `health_html.rs` | -- | Single-file HTML health dashboard | active |
+//! | `html.rs` | -- | Single-page HTML export of ARCHITECTURE.md | active |
+//! | `mkdocs.rs` | -- | MkDocs site export | active |
+//! | `site.rs` | -- | Multi-page splits of ARCHITECTURE.md | active |
 //! | `validate.rs` | -- | Ghost and orphan detection | stable |
+//! | `lint.rs` | -- | Annotation prose lint | active |
 //! | `init.rs` | -- | Root-level project template generator | active |
 //! | `suggest.rs` | -- | Annotation scaffolding templates | active |
 //! | `merge.rs` | -- | Polyglot IR merging | active |
+//! | `diff.rs` | -- | Structural IR diff | active |
+//! | `docusaurus.rs` | -- | Docusaurus doc category export | active |
+//! | `explain.rs` | -- | Finding codes and their explanations | active |
+//! | `freeze.rs` | -- | Approved snapshot governance | active |
+//! | `gap.rs` | -- | Planned vs extracted architecture gaps | active |
+//! | `org.rs` | -- | Multi-repository portfolio roll-up | active |
+//! | `flows.rs` | -- | Numbered runtime scenarios from `@flow` | active |
+//! | `front_matter.rs` | -- | YAML front matter for static site generators | active |
+//! | `header.rs` | Decorator | Generated-by header comments | active |
+//! | `date.rs` | -- | UTC calendar dates | active |
+//! | `hash.rs` | -- | Annotation content hashing | active |
+//! | `i18n.rs` | -- | Localized headings and report labels | active |
+//! | `ids.rs` | -- | Collision-free diagram id assignment | active |
+//! | `readme.rs` | -- | Per-module README scaffolding | active |
+//! | `render.rs` | -- | SVG/PNG rendering of diagram files via external renderers | active |
+//! | `reproducible.rs` | -- | Checkout-independent IR and run reports | active |
+//! | `rewrite.rs` | -- | Config-driven module path rewrites | active |
+//! | `variables.rs` | -- | `$${NAME}` placeholder substitution | active |
+//! | `risk.rs` | -- | Risk register aggregation | active |
+//! | `spelling.rs` | -- | Misspelling check for annotation prose | active |
+//! | `stats.rs` | -- | Local usage statistics | active |
+//! | `stub.rs` | -- | New module scaffolding | active |
+//! | `promotions.rs` | -- | Pattern status audit log | active |
+//! | `protocols.rs` | -- | Relationship protocol catalog | active |
+//! | `pushback.rs` | -- | Round-trip edits read from ARCHITECTURE.md | active |
+//! | `history.rs` | -- | Archived IR snapshots | active |
+//! | `hooks.rs` | Decorator | Post-generation commands per generator | active |
+//! | `coverage.rs` | -- | Test coverage import and module mapping | active |
+//! | `ownership.rs` | -- | Source file to module mapping | active |
+//! | `git.rs` | -- | Git command helpers | active |
 
+pub mod adapters;
 pub mod ai_context;
 pub mod architecture;
 pub mod check;
+pub mod compile;
+pub mod config;
+pub mod confluence;
+pub mod coverage;
+pub mod d2;
+pub mod date;
+pub mod describe;
+pub mod diff;
+pub mod docusaurus;
 pub mod drawio;
+pub mod explain;
+pub mod flows;
+pub mod freeze;
+pub mod front_matter;
+pub mod gap;
+pub mod generator;
+pub mod git;
+pub mod hash;
+pub mod header;
 pub mod health;
+pub mod health_html;
+pub mod html;
+pub mod history;
+pub mod hooks;
+pub mod i18n;
+pub mod ids;
 pub mod init;
 pub mod ir;
+pub mod line_diff;
+pub mod lint;
 pub mod merge;
 pub mod mermaid;
+pub mod mkdocs;
+pub mod org;
+pub mod ownership;
 pub mod plantuml;
+pub mod plugin;
+pub mod promotions;
+pub mod protocols;
+pub mod pushback;
+pub mod readme;
+pub mod render;
+pub mod reproducible;
+pub mod rewrite;
+pub mod risk;
+pub mod site;
+pub mod spelling;
+pub mod stats;
+pub mod structurizr;
+pub mod stub;
 pub mod suggest;
 pub mod validate;
+pub mod variables;
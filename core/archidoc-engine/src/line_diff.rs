@@ -0,0 +1,190 @@
+/// Lines of unchanged context shown around each change.
+pub const CONTEXT_LINES: usize = 3;
+
+/// Above this many cells the LCS table is skipped and the changed middle
+/// of the file is reported as a single replacement.
+const MAX_TABLE_CELLS: usize = 16_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Keep,
+    Delete,
+    Insert,
+}
+
+/// Unified diff turning `old` into `new`, with `context` lines around each
+/// hunk. Returns an empty string when the texts have the same lines.
+pub fn unified_diff(old: &str, new: &str, old_name: &str, new_name: &str, context: usize) -> String {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let ops = diff_ops(&a, &b);
+    if ops.iter().all(|op| *op == Op::Keep) {
+        return String::new();
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for (start, end) in hunk_ranges(&ops, context) {
+        let (mut old_line, mut new_line) = positions_before(&ops, start);
+        let old_start = old_line;
+        let new_start = new_line;
+        let mut body = String::new();
+        for op in &ops[start..end] {
+            match op {
+                Op::Keep => {
+                    body.push_str(&format!(" {}\n", a[old_line]));
+                    old_line += 1;
+                    new_line += 1;
+                }
+                Op::Delete => {
+                    body.push_str(&format!("-{}\n", a[old_line]));
+                    old_line += 1;
+                }
+                Op::Insert => {
+                    body.push_str(&format!("+{}\n", b[new_line]));
+                    new_line += 1;
+                }
+            }
+        }
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_line - old_start),
+            range(new_start, new_line - new_start)
+        ));
+        out.push_str(&body);
+    }
+    out
+}
+
+/// Edit script from `a` to `b`: common prefix and suffix are kept as-is and
+/// the middle is aligned by longest common subsequence.
+fn diff_ops(a: &[&str], b: &[&str]) -> Vec<Op> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let mid_a = &a[prefix..a.len() - suffix];
+    let mid_b = &b[prefix..b.len() - suffix];
+
+    let mut ops = vec![Op::Keep; prefix];
+    if mid_a.len().saturating_mul(mid_b.len()) > MAX_TABLE_CELLS {
+        ops.extend(std::iter::repeat_n(Op::Delete, mid_a.len()));
+        ops.extend(std::iter::repeat_n(Op::Insert, mid_b.len()));
+    } else {
+        ops.extend(lcs_ops(mid_a, mid_b));
+    }
+    ops.extend(std::iter::repeat_n(Op::Keep, suffix));
+    ops
+}
+
+fn lcs_ops(a: &[&str], b: &[&str]) -> Vec<Op> {
+    let width = b.len() + 1;
+    // table[i * width + j] = LCS length of a[i..] and b[j..]
+    let mut table = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i * width + j] = if a[i] == b[j] {
+                table[(i + 1) * width + j + 1] + 1
+            } else {
+                table[(i + 1) * width + j].max(table[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            ops.push(Op::Keep);
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * width + j] >= table[i * width + j + 1] {
+            ops.push(Op::Delete);
+            i += 1;
+        } else {
+            ops.push(Op::Insert);
+            j += 1;
+        }
+    }
+    ops.extend(std::iter::repeat_n(Op::Delete, a.len() - i));
+    ops.extend(std::iter::repeat_n(Op::Insert, b.len() - j));
+    ops
+}
+
+/// Index ranges into `ops` for each hunk, merging changes whose context
+/// windows touch.
+fn hunk_ranges(ops: &[Op], context: usize) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (i, op) in ops.iter().enumerate() {
+        if *op == Op::Keep {
+            continue;
+        }
+        let start = i.saturating_sub(context);
+        let end = (i + 1 + context).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+fn positions_before(ops: &[Op], index: usize) -> (usize, usize) {
+    ops[..index].iter().fold((0, 0), |(old, new), op| match op {
+        Op::Keep => (old + 1, new + 1),
+        Op::Delete => (old + 1, new),
+        Op::Insert => (old, new + 1),
+    })
+}
+
+/// Hunk range in unified diff notation (1-based; empty ranges name the
+/// line before them).
+fn range(start: usize, len: usize) -> String {
+    match len {
+        0 => format!("{},0", start),
+        1 => format!("{}", start + 1),
+        _ => format!("{},{}", start + 1, len),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_texts_have_no_diff() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "old", "new", 3), "");
+    }
+
+    #[test]
+    fn changed_line_gets_context_and_hunk_header() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n";
+        let new = "1\n2\n3\n4\nfive\n6\n7\n8\n";
+
+        let diff = unified_diff(old, new, "ARCHITECTURE.md", "ARCHITECTURE.md (generated)", 2);
+
+        assert_eq!(
+            diff,
+            "--- ARCHITECTURE.md\n+++ ARCHITECTURE.md (generated)\n@@ -3,5 +3,5 @@\n 3\n 4\n-5\n+five\n 6\n 7\n"
+        );
+    }
+
+    #[test]
+    fn distant_changes_form_separate_hunks() {
+        let mut lines: Vec<String> = (1..=20).map(|i| i.to_string()).collect();
+        let old = lines.join("\n");
+        lines[1] = "two".to_string();
+        lines.remove(17);
+        lines.push("21".to_string());
+        let new = lines.join("\n");
+
+        let diff = unified_diff(&old, &new, "a", "b", 1);
+
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("@@ -1,3 +1,3 @@\n 1\n-2\n+two\n 3\n"));
+        assert!(diff.contains("-18\n"));
+        assert!(diff.contains("+21\n"));
+    }
+}
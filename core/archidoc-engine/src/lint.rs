@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use archidoc_types::{LintConfig, LintFinding, LintReport, ModuleDoc};
+
+use crate::spelling;
+
+/// Descriptions that stand in for a real one, compared ignoring case and
+/// surrounding `*` / `_` emphasis. `*No description*` is what the Rust
+/// adapter produces for an annotation without prose.
+pub const PLACEHOLDERS: &[&str] = &["no description", "description", "todo", "tbd", "n/a", "..."];
+
+/// Words marking unfinished prose.
+const TODO_MARKERS: &[&str] = &["TODO", "FIXME", "XXX", "TBD"];
+
+/// Lint every element's description:
+///
+/// - `description_placeholder`: empty or a placeholder such as `*No description*`
+/// - `description_todo`: contains a leftover TODO, FIXME, XXX, or TBD
+/// - `description_too_short`: shorter than `min_description_length`
+/// - `description_duplicate`: the same text as another element's
+/// - `unknown_target`: a relationship to something that is neither an
+///   element nor a declared external system (see [`unknown_targets`])
+/// - `spelling`: a common misspelling in the annotation prose, when
+///   `spelling` is on (see [`spelling::check_spelling`])
+pub fn lint(docs: &[ModuleDoc], config: &LintConfig) -> LintReport {
+    let mut report = LintReport::default();
+    let mut by_text: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+
+    for doc in docs {
+        let description = doc.description.trim();
+        let finding = |rule: &str, message: String| LintFinding {
+            element: doc.module_path.clone(),
+            rule: rule.to_string(),
+            message,
+            file: None,
+            line: None,
+        };
+
+        if is_placeholder(description) {
+            report.findings.push(finding(
+                "description_placeholder",
+                format!("description is a placeholder ('{}')", description),
+            ));
+            continue;
+        }
+        if let Some(marker) = TODO_MARKERS.iter().find(|m| has_word(description, m)) {
+            report.findings.push(finding("description_todo", format!("description contains {}", marker)));
+        }
+        let length = description.chars().count();
+        if length < config.min_description_length {
+            report.findings.push(finding(
+                "description_too_short",
+                format!(
+                    "description is {} characters, shorter than {}",
+                    length, config.min_description_length
+                ),
+            ));
+        }
+        by_text.entry(description.to_lowercase()).or_default().push(&doc.module_path);
+    }
+
+    for elements in by_text.values().filter(|e| e.len() > 1) {
+        for element in elements {
+            let others: Vec<&str> = elements.iter().filter(|e| *e != element).copied().collect();
+            report.findings.push(LintFinding {
+                element: element.to_string(),
+                rule: "description_duplicate".to_string(),
+                message: format!("description duplicates {}", others.join(", ")),
+                file: None,
+                line: None,
+            });
+        }
+    }
+
+    report.findings.extend(unknown_targets(docs, &config.external_systems));
+    if config.spelling {
+        report.findings.extend(spelling::check_spelling(docs, &config.ignore_words));
+    }
+
+    report.findings.sort_by(|a, b| a.element.cmp(&b.element));
+    report
+}
+
+/// Relationships whose target is not the module path of an element (overlay
+/// elements included) or one of `external_systems`, and `@flow` steps that
+/// name neither. Such a target is usually a typo and would show up as a
+/// dangling arrow, so the finding suggests the closest known name when one
+/// is near.
+pub fn unknown_targets(docs: &[ModuleDoc], external_systems: &[String]) -> Vec<LintFinding> {
+    let known: BTreeSet<&str> = docs
+        .iter()
+        .map(|d| d.module_path.as_str())
+        .chain(external_systems.iter().map(String::as_str))
+        .collect();
+
+    let mut findings = Vec::new();
+    for doc in docs {
+        let mut unknown = |target: &str, what: String| {
+            let mut message = format!("{} '{}' is not an element or external system", what, target);
+            if let Some(suggestion) = closest(target, &known) {
+                message.push_str(&format!(" (did you mean '{}'?)", suggestion));
+            }
+            findings.push(LintFinding {
+                element: doc.module_path.clone(),
+                rule: "unknown_target".to_string(),
+                message,
+                file: (!doc.source_file.is_empty()).then(|| doc.source_file.clone()),
+                line: None,
+            });
+        };
+        for rel in doc.relationships.iter().filter(|r| !known.contains(r.target.as_str())) {
+            unknown(&rel.target, "relationship target".to_string());
+        }
+        for flow in &doc.flows {
+            for step in flow.steps.iter().filter(|s| !known.contains(s.as_str())) {
+                unknown(step, format!("flow '{}' step", flow.name));
+            }
+        }
+    }
+    findings
+}
+
+/// The known name nearest to `name`, within two edits.
+fn closest<'a>(name: &str, known: &BTreeSet<&'a str>) -> Option<&'a str> {
+    known
+        .iter()
+        .map(|k| (edit_distance(name, k), *k))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, k)| k)
+}
+
+/// Levenshtein distance between `a` and `b`, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+    row[b.len()]
+}
+
+fn is_placeholder(description: &str) -> bool {
+    let bare = description.trim_matches(|c: char| c == '*' || c == '_' || c.is_whitespace());
+    bare.is_empty() || PLACEHOLDERS.iter().any(|p| bare.eq_ignore_ascii_case(p))
+}
+
+/// Whether `word` occurs in `text` as a whole word.
+fn has_word(text: &str, word: &str) -> bool {
+    text.split(|c: char| !c.is_alphanumeric()).any(|w| w == word)
+}
+
+/// Format a lint report as human-readable text.
+pub fn format_lint_report(report: &LintReport) -> String {
+    if report.is_clean() {
+        return "Lint: all clear\n".to_string();
+    }
+
+    let mut out = format!("Lint findings ({} found):\n", report.findings.len());
+    for finding in &report.findings {
+        out.push_str(&format!("  {} — {} {}", finding.element, finding.message, crate::explain::tag(&finding.rule)));
+        match (&finding.file, finding.line) {
+            (Some(file), Some(line)) => out.push_str(&format!(" ({}:{})", file, line)),
+            (Some(file), None) => out.push_str(&format!(" ({})", file)),
+            _ => {}
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{ElementKind, Relationship};
+
+    fn doc(path: &str, desc: &str) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            description: desc.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn rules(report: &LintReport) -> Vec<(&str, &str)> {
+        report.findings.iter().map(|f| (f.element.as_str(), f.rule.as_str())).collect()
+    }
+
+    #[test]
+    fn flags_placeholders_todos_short_and_duplicate_descriptions() {
+        let docs = vec![
+            doc("api", "*No description*"),
+            doc("bus", "Routes events TODO: document lanes"),
+            doc("cache", "Cache"),
+            doc("db", "Persists orders and invoices"),
+            doc("ledger", "persists orders and invoices "),
+            doc("todos", "Tracks TODOs for the planner"),
+        ];
+
+        let report = lint(&docs, &LintConfig::default());
+
+        assert_eq!(
+            rules(&report),
+            vec![
+                ("api", "description_placeholder"),
+                ("bus", "description_todo"),
+                ("cache", "description_too_short"),
+                ("db", "description_duplicate"),
+                ("ledger", "description_duplicate"),
+            ]
+        );
+        assert_eq!(report.findings[3].message, "description duplicates ledger");
+    }
+
+    #[test]
+    #[cfg(feature = "spellcheck")]
+    fn spelling_runs_only_when_enabled() {
+        let docs = vec![doc("bus", "Recieve and route events")];
+        let config = LintConfig {
+            spelling: true,
+            ..LintConfig::default()
+        };
+
+        assert!(lint(&docs, &LintConfig::default()).is_clean());
+        assert_eq!(rules(&lint(&docs, &config)), vec![("bus", "spelling")]);
+    }
+
+    #[test]
+    fn relationship_targets_must_be_elements_or_external_systems() {
+        let mut api = doc("api", "Public HTTP endpoints");
+        for target in ["databse", "stripe", "bus", "ledger"] {
+            api.relationships.push(Relationship {
+                target: target.to_string(),
+                label: "Calls".to_string(),
+                protocol: String::new(),
+                hidden: false,
+            });
+        }
+        api.flows.push(archidoc_types::Flow {
+            name: "checkout".to_string(),
+            steps: vec!["api".to_string(), "stripe".to_string(), "bsu".to_string()],
+        });
+        let docs = vec![api, doc("bus", "Routes domain events"), doc("database", "Persists orders")];
+
+        let findings = unknown_targets(&docs, &["stripe".to_string()]);
+
+        let messages: Vec<&str> = findings.iter().map(|f| f.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "relationship target 'databse' is not an element or external system (did you mean 'database'?)",
+                "relationship target 'ledger' is not an element or external system",
+                "flow 'checkout' step 'bsu' is not an element or external system (did you mean 'bus'?)",
+            ]
+        );
+        assert_eq!(findings[0].element, "api");
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn minimum_length_is_configurable() {
+        let docs = vec![doc("cache", "Cache")];
+        let config = LintConfig {
+            min_description_length: 0,
+            ..LintConfig::default()
+        };
+        assert!(lint(&docs, &config).is_clean());
+        assert!(format_lint_report(&lint(&docs, &LintConfig::default()))
+            .contains("cache — description is 5 characters, shorter than 10 [AD203 description_too_short]"));
+    }
+}
@@ -15,17 +15,32 @@ impl fmt::Display for MergeError {
     }
 }
 
+/// Result of a successful merge.
+#[derive(Debug, Default)]
+pub struct MergeOutcome {
+    pub docs: Vec<ModuleDoc>,
+    /// One line per module a later source overwrote, for the caller to
+    /// report or ignore.
+    pub warnings: Vec<String>,
+}
+
 /// Merge multiple IR sets into a single unified ModuleDoc list.
 ///
 /// Rules:
 /// - Modules with unique paths are included as-is
-/// - Duplicate module_paths with the SAME c4_level: last writer wins (later source overrides earlier)
+/// - Duplicate module_paths with the SAME c4_level: last writer wins (later source overrides earlier), with a warning
 /// - Duplicate module_paths with DIFFERENT c4_levels: returns MergeError
+/// - Module paths outside the grammar (see [`ModuleDoc::check_path`]) are
+///   normalized with a warning, along with the parent containers,
+///   relationship targets, and flow steps of the same set that name them;
+///   a path with nothing left to normalize returns MergeError
 /// - Output is sorted by module_path
-pub fn merge_ir(sources: Vec<Vec<ModuleDoc>>) -> Result<Vec<ModuleDoc>, MergeError> {
+pub fn merge_ir(sources: Vec<Vec<ModuleDoc>>) -> Result<MergeOutcome, MergeError> {
     let mut merged: HashMap<String, ModuleDoc> = HashMap::new();
+    let mut warnings = Vec::new();
 
-    for source_set in sources {
+    for mut source_set in sources {
+        normalize_paths(&mut source_set, &mut warnings)?;
         for doc in source_set {
             let module_path = doc.module_path.clone();
 
@@ -41,57 +56,117 @@ pub fn merge_ir(sources: Vec<Vec<ModuleDoc>>) -> Result<Vec<ModuleDoc>, MergeErr
                     });
                 }
 
-                eprintln!(
-                    "warning: duplicate module '{}' at C4 level '{}', overwriting with later source",
-                    module_path,
-                    doc.c4_level
-                );
+                warnings.push(format!(
+                    "duplicate module '{}' at C4 level '{}', overwriting with later source",
+                    module_path, doc.c4_level
+                ));
             }
 
             merged.insert(module_path, doc);
         }
     }
 
+    let mut docs: Vec<ModuleDoc> = merged.into_values().collect();
+    docs.sort_by(|a, b| a.module_path.cmp(&b.module_path));
+
+    Ok(MergeOutcome { docs, warnings })
+}
+
+/// Bring the module paths of one IR set into the grammar, renaming every
+/// reference to a renamed path within the set.
+fn normalize_paths(docs: &mut [ModuleDoc], warnings: &mut Vec<String>) -> Result<(), MergeError> {
+    let mut renamed: HashMap<String, String> = HashMap::new();
+    for doc in docs.iter() {
+        let Err(problem) = ModuleDoc::check_path(&doc.module_path) else {
+            continue;
+        };
+        let normalized = ModuleDoc::normalize_path(&doc.module_path);
+        if normalized.is_empty() {
+            return Err(MergeError {
+                module_path: doc.module_path.clone(),
+                message: format!("invalid module path in {}: {}", doc.source_file, problem),
+            });
+        }
+        warnings.push(format!("{}; using '{}'", problem, normalized));
+        renamed.insert(doc.module_path.clone(), normalized);
+    }
+    if renamed.is_empty() {
+        return Ok(());
+    }
+
+    let rename = |path: &mut String| {
+        if let Some(normalized) = renamed.get(path.as_str()) {
+            *path = normalized.clone();
+        }
+    };
+    for doc in docs.iter_mut() {
+        rename(&mut doc.module_path);
+        doc.parent_container.iter_mut().for_each(rename);
+        doc.relationships.iter_mut().for_each(|rel| rename(&mut rel.target));
+        doc.flows.iter_mut().flat_map(|flow| flow.steps.iter_mut()).for_each(rename);
+    }
+    Ok(())
+}
+
+/// Lay hand-authored overlay elements on top of source-extracted `docs`.
+///
+/// Rules:
+/// - Overlay modules with new paths are added (planned containers, external systems)
+/// - An overlay module with an existing path replaces the extracted one, C4 level
+///   included, but keeps the extracted relationships to targets the overlay does
+///   not mention
+/// - Output is sorted by module_path
+pub fn apply_overlay(docs: Vec<ModuleDoc>, overlay: Vec<ModuleDoc>) -> Vec<ModuleDoc> {
+    let mut merged: HashMap<String, ModuleDoc> = docs.into_iter().map(|d| (d.module_path.clone(), d)).collect();
+
+    for mut doc in overlay {
+        if let Some(extracted) = merged.remove(&doc.module_path) {
+            let kept: Vec<_> = extracted
+                .relationships
+                .into_iter()
+                .filter(|rel| !doc.relationships.iter().any(|r| r.target == rel.target))
+                .collect();
+            doc.relationships.extend(kept);
+        }
+        merged.insert(doc.module_path.clone(), doc);
+    }
+
     let mut result: Vec<ModuleDoc> = merged.into_values().collect();
     result.sort_by(|a, b| a.module_path.cmp(&b.module_path));
-
-    Ok(result)
+    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use archidoc_types::{C4Level, PatternStatus, Relationship};
+    use archidoc_types::{ElementKind, Relationship};
 
-    fn make_doc(path: &str, level: C4Level) -> ModuleDoc {
+    fn make_doc(path: &str, level: ElementKind) -> ModuleDoc {
         ModuleDoc {
             module_path: path.to_string(),
-            content: String::new(),
             source_file: format!("src/{}/mod.rs", path),
             c4_level: level,
             pattern: "--".to_string(),
-            pattern_status: PatternStatus::Planned,
             description: format!("Module {}", path),
-            parent_container: None,
-            relationships: vec![],
-            files: vec![],
+            ..Default::default()
         }
     }
 
     #[test]
     fn merge_combines_disjoint_sets() {
         let set1 = vec![
-            make_doc("api", C4Level::Container),
-            make_doc("core", C4Level::Container),
+            make_doc("api", ElementKind::Container),
+            make_doc("core", ElementKind::Container),
         ];
         let set2 = vec![
-            make_doc("database", C4Level::Component),
-            make_doc("ui", C4Level::Component),
+            make_doc("database", ElementKind::Component),
+            make_doc("ui", ElementKind::Component),
         ];
 
-        let result = merge_ir(vec![set1, set2]).unwrap();
+        let result = merge_ir(vec![set1, set2]).unwrap().docs;
 
         assert_eq!(result.len(), 4);
+        assert!(merge_ir(vec![vec![make_doc("api", ElementKind::Container)]]).unwrap().warnings.is_empty());
         assert_eq!(result[0].module_path, "api");
         assert_eq!(result[1].module_path, "core");
         assert_eq!(result[2].module_path, "database");
@@ -101,27 +176,60 @@ mod tests {
     #[test]
     fn merge_deduplicates_same_level() {
         let set1 = vec![
-            make_doc("api", C4Level::Container),
+            make_doc("api", ElementKind::Container),
         ];
         let mut set2 = vec![
-            make_doc("api", C4Level::Container),
+            make_doc("api", ElementKind::Container),
         ];
         set2[0].description = "Updated API module".to_string();
 
-        let result = merge_ir(vec![set1, set2]).unwrap();
+        let outcome = merge_ir(vec![set1, set2]).unwrap();
 
+        let result = &outcome.docs;
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].module_path, "api");
         assert_eq!(result[0].description, "Updated API module");
+        assert_eq!(
+            outcome.warnings,
+            vec!["duplicate module 'api' at C4 level 'container', overwriting with later source"]
+        );
+    }
+
+    #[test]
+    fn merge_normalizes_module_paths_outside_the_grammar() {
+        let mut profile = make_doc("Dashboard.user-profile", ElementKind::Component);
+        profile.parent_container = Some("Dashboard".to_string());
+        let mut settings = make_doc("settings", ElementKind::Component);
+        settings.relationships.push(Relationship {
+            target: "Dashboard.user-profile".to_string(),
+            label: "Reads".to_string(),
+            protocol: "--".to_string(),
+            hidden: false,
+        });
+        let set = vec![make_doc("Dashboard", ElementKind::Container), profile, settings];
+
+        let outcome = merge_ir(vec![set]).unwrap();
+
+        let paths: Vec<&str> = outcome.docs.iter().map(|d| d.module_path.as_str()).collect();
+        assert_eq!(paths, ["dashboard", "dashboard.user_profile", "settings"]);
+        assert_eq!(outcome.docs[1].parent_container.as_deref(), Some("dashboard"));
+        assert_eq!(outcome.docs[2].relationships[0].target, "dashboard.user_profile");
+        assert_eq!(
+            outcome.warnings[1],
+            "'D' in module path 'Dashboard.user-profile' is not a lowercase ASCII letter, digit, or `_`; using 'dashboard.user_profile'"
+        );
+
+        let err = merge_ir(vec![vec![make_doc("..", ElementKind::Container)]]).unwrap_err();
+        assert_eq!(err.message, "invalid module path in src/../mod.rs: empty segment in module path '..'");
     }
 
     #[test]
     fn merge_rejects_conflicting_c4_levels() {
         let set1 = vec![
-            make_doc("api", C4Level::Container),
+            make_doc("api", ElementKind::Container),
         ];
         let set2 = vec![
-            make_doc("api", C4Level::Component),
+            make_doc("api", ElementKind::Component),
         ];
 
         let result = merge_ir(vec![set1, set2]);
@@ -137,14 +245,14 @@ mod tests {
     #[test]
     fn merge_sorts_by_module_path() {
         let set1 = vec![
-            make_doc("zebra", C4Level::Container),
-            make_doc("alpha", C4Level::Container),
+            make_doc("zebra", ElementKind::Container),
+            make_doc("alpha", ElementKind::Container),
         ];
         let set2 = vec![
-            make_doc("middle", C4Level::Component),
+            make_doc("middle", ElementKind::Component),
         ];
 
-        let result = merge_ir(vec![set1, set2]).unwrap();
+        let result = merge_ir(vec![set1, set2]).unwrap().docs;
 
         assert_eq!(result.len(), 3);
         assert_eq!(result[0].module_path, "alpha");
@@ -154,34 +262,36 @@ mod tests {
 
     #[test]
     fn merge_empty_inputs_returns_empty() {
-        let result1 = merge_ir(vec![]).unwrap();
+        let result1 = merge_ir(vec![]).unwrap().docs;
         assert_eq!(result1.len(), 0);
 
-        let result2 = merge_ir(vec![vec![], vec![]]).unwrap();
+        let result2 = merge_ir(vec![vec![], vec![]]).unwrap().docs;
         assert_eq!(result2.len(), 0);
     }
 
     #[test]
     fn merge_preserves_relationships() {
-        let mut doc1 = make_doc("api", C4Level::Container);
+        let mut doc1 = make_doc("api", ElementKind::Container);
         doc1.relationships = vec![
             Relationship {
                 target: "database".to_string(),
                 label: "Persists data".to_string(),
                 protocol: "sqlx".to_string(),
+                hidden: false,
             },
         ];
 
-        let mut doc2 = make_doc("database", C4Level::Component);
+        let mut doc2 = make_doc("database", ElementKind::Component);
         doc2.relationships = vec![
             Relationship {
                 target: "storage".to_string(),
                 label: "Writes files".to_string(),
                 protocol: "fs".to_string(),
+                hidden: false,
             },
         ];
 
-        let result = merge_ir(vec![vec![doc1], vec![doc2]]).unwrap();
+        let result = merge_ir(vec![vec![doc1], vec![doc2]]).unwrap().docs;
 
         assert_eq!(result.len(), 2);
 
@@ -193,4 +303,47 @@ mod tests {
         assert_eq!(db_doc.relationships.len(), 1);
         assert_eq!(db_doc.relationships[0].target, "storage");
     }
+
+    #[test]
+    fn overlay_adds_elements_and_wins_over_extracted_ones() {
+        let mut api = make_doc("api", ElementKind::Component);
+        api.relationships = vec![
+            Relationship {
+                target: "database".to_string(),
+                label: "Persists data".to_string(),
+                protocol: "sqlx".to_string(),
+                hidden: false,
+            },
+            Relationship {
+                target: "cache".to_string(),
+                label: "Reads".to_string(),
+                protocol: "redis".to_string(),
+                hidden: false,
+            },
+        ];
+        let mut api_overlay = make_doc("api", ElementKind::Container);
+        api_overlay.description = "Public API".to_string();
+        api_overlay.relationships = vec![Relationship {
+            target: "database".to_string(),
+            label: "Stores orders".to_string(),
+            protocol: "sqlx".to_string(),
+            hidden: false,
+        }];
+
+        let result = apply_overlay(
+            vec![make_doc("core", ElementKind::Container), api],
+            vec![make_doc("billing", ElementKind::Container), api_overlay],
+        );
+
+        let paths: Vec<&str> = result.iter().map(|d| d.module_path.as_str()).collect();
+        assert_eq!(paths, vec!["api", "billing", "core"]);
+        assert_eq!(result[0].c4_level, ElementKind::Container);
+        assert_eq!(result[0].description, "Public API");
+        let rels: Vec<(&str, &str)> = result[0]
+            .relationships
+            .iter()
+            .map(|r| (r.target.as_str(), r.label.as_str()))
+            .collect();
+        assert_eq!(rels, vec![("database", "Stores orders"), ("cache", "Reads")]);
+    }
 }
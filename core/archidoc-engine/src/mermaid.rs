@@ -2,87 +2,369 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
-use archidoc_types::{C4Level, ModuleDoc};
+use archidoc_types::{DiagramConfig, ElementKind, Flow, LineStyle, ModuleDoc, Relationship};
 
-/// Return the Mermaid C4 container diagram as a markdown code block string.
-pub fn container_diagram(docs: &[ModuleDoc]) -> String {
-    let containers: Vec<&ModuleDoc> = docs
+use crate::flows::{all_flows, flow_steps, participants};
+use crate::generator::{Artifact, Generator};
+
+/// File name of the Mermaid system context diagram page.
+pub const CONTEXT_FILE: &str = "c4-context.md";
+/// File name of the Mermaid container diagram page.
+pub const CONTAINER_FILE: &str = "c4-container.md";
+/// File name of the Mermaid component diagram page.
+pub const COMPONENT_FILE: &str = "c4-component.md";
+
+/// Standalone Mermaid diagram pages under `mermaid/`: container and
+/// component pages, a system context page when there are people or
+/// external systems, and one sequence diagram page per flow named
+/// `sequence-<flow>.md`. ARCHITECTURE.md embeds the same diagrams inline.
+pub struct MermaidGenerator {
+    pub diagrams: DiagramConfig,
+}
+
+impl Generator for MermaidGenerator {
+    fn id(&self) -> &str {
+        "mermaid"
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        let mut artifacts = vec![
+            Artifact {
+                path: format!("mermaid/{}", CONTAINER_FILE),
+                content: container_page(docs, &self.diagrams),
+            },
+            Artifact {
+                path: format!("mermaid/{}", COMPONENT_FILE),
+                content: component_page(docs, &self.diagrams),
+            },
+        ];
+        if has_context(docs) {
+            artifacts.push(Artifact {
+                path: format!("mermaid/{}", CONTEXT_FILE),
+                content: context_page(docs, &self.diagrams),
+            });
+        }
+        for flow in all_flows(docs) {
+            artifacts.push(Artifact {
+                path: format!("mermaid/sequence-{}.md", flow.name),
+                content: format!(
+                    "# Sequence Diagram: {}\n\n> Auto-generated by archidoc\n\n{}\n",
+                    flow.name,
+                    sequence_diagram(docs, flow)
+                ),
+            });
+        }
+        Ok(artifacts)
+    }
+}
+
+/// Whether any element sits outside the system boundary, so that a system
+/// context diagram has something to show.
+pub fn has_context(docs: &[ModuleDoc]) -> bool {
+    docs.iter().any(|d| d.c4_level.is_external())
+}
+
+/// Return the Mermaid C4 system context diagram as a markdown code block
+/// string.
+///
+/// The system is one box, described by the narrative root, among the
+/// people and systems outside it; arrows are the relationships crossing
+/// its boundary (see [`context_edges`]).
+pub fn context_diagram(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
+    let mut defs = String::new();
+    for doc in docs.iter().filter(|d| d.c4_level.is_external()) {
+        defs.push_str(&node_def(doc, &with_condition(&to_title_case(&doc.module_path), doc), "    "));
+    }
+    defs.push_str(&format!("    System({}, \"System\", \"{}\")\n", SYSTEM_ID, system_description(docs)));
+
+    let mut rel_defs = String::new();
+    for (from, to, rel) in context_edges(docs) {
+        rel_defs.push_str(&rel_def(&from, &to, &rel, diagrams));
+    }
+
+    format!(
+        "```mermaid\nC4Context\n    title System Context Diagram\n\n{}\n{}\n    UpdateLayoutConfig($c4ShapeInRow=\"3\", $c4BoundaryInRow=\"1\")\n```",
+        defs, rel_defs,
+    )
+}
+
+/// Diagram id of the box standing for the whole system.
+pub(crate) const SYSTEM_ID: &str = "sys";
+
+/// Description of the system box: the narrative root's.
+pub(crate) fn system_description(docs: &[ModuleDoc]) -> &str {
+    ModuleDoc::narrative_root(docs).map_or("", |root| root.description.as_str())
+}
+
+/// Arrows of the system context diagram, as (source id, target id,
+/// relationship): the drawn relationships with an element outside the
+/// system boundary on either end, every element inside it drawn as the
+/// system box ([`SYSTEM_ID`]). The first relationship between two boxes
+/// is kept; arrows within the system, and to names that are no element,
+/// are left out.
+pub(crate) fn context_edges(docs: &[ModuleDoc]) -> Vec<(String, String, Relationship)> {
+    let box_of = |path: &str| {
+        let doc = docs.iter().find(|d| d.module_path == path)?;
+        Some(if doc.c4_level.is_external() { doc.element_id() } else { SYSTEM_ID.to_string() })
+    };
+    let mut edges: Vec<(String, String, Relationship)> = Vec::new();
+    for doc in docs {
+        for rel in drawn_relationships(docs, doc) {
+            let (Some(from), Some(to)) = (box_of(&doc.module_path), box_of(&rel.target)) else {
+                continue;
+            };
+            let crosses = from != SYSTEM_ID || to != SYSTEM_ID;
+            if crosses && !edges.iter().any(|(f, t, _)| *f == from && *t == to) {
+                edges.push((from, to, rel.clone()));
+            }
+        }
+    }
+    edges
+}
+
+/// Render the system context diagram page: the diagram plus a table of
+/// the people and systems around the system.
+pub fn context_page(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
+    let table_rows: Vec<String> = docs
         .iter()
-        .filter(|d| d.c4_level == C4Level::Container)
+        .filter(|d| d.c4_level.is_external())
+        .map(|d| format!("| {} | {} | {} |", d.module_path, d.c4_level, d.description))
         .collect();
 
+    format!(
+        "# C4 System Context Diagram\n\n> Auto-generated by archidoc\n\n{}\n\n## People and External Systems\n\n| Element | Kind | Description |\n|---------|------|-------------|\n{}\n",
+        context_diagram(docs, diagrams),
+        table_rows.join("\n")
+    )
+}
+
+/// Return the Mermaid C4 container diagram as a markdown code block string.
+///
+/// Containers are drawn inside the system boundary; people, external
+/// systems, and other systems outside it.
+pub fn container_diagram(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
+    let elements: Vec<&ModuleDoc> = docs.iter().filter(|d| d.c4_level.in_container_view()).collect();
+    let (external, containers): (Vec<&ModuleDoc>, Vec<&ModuleDoc>) =
+        elements.iter().partition(|d| d.c4_level.is_external());
+
+    let container_def = |doc: &ModuleDoc, indent: &str| {
+        node_def(doc, &with_condition(&to_title_case(&doc.module_path), doc), indent)
+    };
+
+    let mut external_defs = String::new();
+    for doc in &external {
+        external_defs.push_str(&container_def(doc, "    "));
+    }
+    if !external_defs.is_empty() {
+        external_defs.push('\n');
+    }
+
+    let (groups, ungrouped) = containers_by_group(&containers);
     let mut container_defs = String::new();
-    for doc in &containers {
-        let id = doc.module_path.replace('.', "_");
-        let name = to_title_case(&doc.module_path);
-        container_defs.push_str(&format!(
-            "        Container({}, \"{}\", \"{}\", \"{}\")\n",
-            id, name, doc.pattern, doc.description
-        ));
+    for doc in ungrouped {
+        container_defs.push_str(&container_def(doc, "        "));
+    }
+    for (group, members) in &groups {
+        let (id, label, kind) = group_boundary(group);
+        match kind {
+            Some(kind) => container_defs.push_str(&format!("        Boundary({}, \"{}\", \"{}\") {{\n", id, label, kind)),
+            None => container_defs.push_str(&format!("        Boundary({}, \"{}\") {{\n", id, label)),
+        }
+        for doc in members {
+            container_defs.push_str(&container_def(doc, "            "));
+        }
+        container_defs.push_str("        }\n");
     }
 
     let mut rel_defs = String::new();
-    for doc in &containers {
-        let from_id = doc.module_path.replace('.', "_");
-        for rel in &doc.relationships {
-            let to_id = rel.target.replace('.', "_");
-            rel_defs.push_str(&format!(
-                "    Rel({}, {}, \"{}\", \"{}\")\n",
-                from_id, to_id, rel.label, rel.protocol
-            ));
-        }
+    for (from, rel) in container_edges(docs, &elements, diagrams) {
+        rel_defs.push_str(&rel_def(&ModuleDoc::id_of(docs, from), &ModuleDoc::id_of(docs, &rel.target), &rel, diagrams));
     }
 
+    rel_defs.push_str(&optional_styles(&elements));
+
     format!(
-        "```mermaid\nC4Container\n    title Container Diagram\n\n    System_Boundary(sys, \"System\") {{\n{}    }}\n\n{}\n    UpdateLayoutConfig($c4ShapeInRow=\"3\", $c4BoundaryInRow=\"1\")\n```",
+        "```mermaid\nC4Container\n    title Container Diagram\n\n{}    System_Boundary(sys, \"System\") {{\n{}    }}\n\n{}\n    UpdateLayoutConfig($c4ShapeInRow=\"3\", $c4BoundaryInRow=\"1\")\n```",
+        external_defs,
         container_defs,
         rel_defs,
     )
 }
 
-/// Generate Mermaid C4 container diagram file from `@c4 container` modules.
-pub fn generate_container(output_dir: &Path, docs: &[ModuleDoc]) {
-    let filepath = output_dir.join("c4-container.md");
-
-    let containers: Vec<&ModuleDoc> = docs
+/// One warning per `[diagrams.edge_styles]` entry with a line style other
+/// than solid: Mermaid C4 has no dashed, dotted, or bold arrows, so its
+/// diagrams draw those protocols with the color alone.
+pub fn edge_style_warnings(diagrams: &DiagramConfig) -> Vec<String> {
+    diagrams
+        .edge_styles
         .iter()
-        .filter(|d| d.c4_level == C4Level::Container)
-        .collect();
+        .filter(|(_, style)| style.line != LineStyle::Solid)
+        .map(|(protocol, style)| {
+            format!(
+                "[diagrams.edge_styles] {}: Mermaid C4 diagrams cannot draw {} lines and use only the color; PlantUML, D2, and draw.io draw them",
+                protocol,
+                style.line.as_str()
+            )
+        })
+        .collect()
+}
 
-    let table_rows: Vec<String> = containers
-        .iter()
-        .map(|d| format!("| {} | {} | {} |", d.module_path, d.pattern, d.description))
-        .collect();
+/// A `Rel` arrow, followed by an `UpdateRelStyle` when
+/// `[diagrams.edge_styles]` gives its protocol a color.
+fn rel_def(from_id: &str, to_id: &str, rel: &Relationship, diagrams: &DiagramConfig) -> String {
+    let mut out = format!("    Rel({}, {}, \"{}\", \"{}\")\n", from_id, to_id, rel.label, rel.protocol);
+    if let Some(color) = diagrams.edge_style(&rel.protocol).and_then(|style| style.color.as_deref()) {
+        out.push_str(&format!(
+            "    UpdateRelStyle({}, {}, $textColor=\"{}\", $lineColor=\"{}\")\n",
+            from_id, to_id, color, color
+        ));
+    }
+    out
+}
 
-    let content = format!(
-        "# C4 Container Diagram\n\n> Auto-generated by archidoc\n\n{}\n\n## Containers\n\n| Container | Pattern | Description |\n|-----------|---------|-------------|\n{}\n",
-        container_diagram(docs),
-        table_rows.join("\n")
-    );
+/// The C4 macro call drawing `doc` under `label`. People and systems take
+/// no technology argument; the others show the pattern there.
+pub(crate) fn node_def(doc: &ModuleDoc, label: &str, indent: &str) -> String {
+    let kind = doc.c4_level.c4_macro();
+    if doc.c4_level.is_external() {
+        format!("{}{}({}, \"{}\", \"{}\")\n", indent, kind, doc.element_id(), label, doc.description)
+    } else {
+        format!(
+            "{}{}({}, \"{}\", \"{}\", \"{}\")\n",
+            indent,
+            kind,
+            doc.element_id(),
+            label,
+            doc.pattern,
+            doc.description
+        )
+    }
+}
 
-    fs::write(&filepath, content).expect("Failed to write c4-container.md");
+/// Relationships of `doc` that diagrams draw as arrows: all but `[hidden]`
+/// ones and those into `@crosscutting` elements.
+pub(crate) fn drawn_relationships<'a>(docs: &'a [ModuleDoc], doc: &'a ModuleDoc) -> impl Iterator<Item = &'a Relationship> {
+    doc.relationships.iter().filter(move |rel| {
+        !rel.hidden && !docs.iter().any(|d| d.crosscutting && d.module_path == rel.target)
+    })
 }
 
-/// Return the Mermaid C4 component diagram as a markdown code block string.
+/// Arrows of the container diagram, as (source path, relationship): the
+/// drawn relationships of its `elements` and, with
+/// `aggregate_relationships`, one arrow per container pair for the
+/// relationships of the components inside.
 ///
-/// Components are grouped by their nearest container (longest matching prefix),
-/// then arranged as a tree within each container using nested `Container_Boundary`
-/// blocks. Parent-child containment arrows are emitted automatically.
-pub fn component_diagram(docs: &[ModuleDoc]) -> String {
-    let components: Vec<&ModuleDoc> = docs
+/// An aggregated arrow keeps the label of a single relationship and
+/// otherwise reads `N dependencies`, with every distinct protocol. Pairs
+/// the containers already connect directly, and relationships inside one
+/// container, add no arrow.
+pub(crate) fn container_edges<'a>(
+    docs: &'a [ModuleDoc],
+    elements: &[&'a ModuleDoc],
+    diagrams: &DiagramConfig,
+) -> Vec<(&'a str, Relationship)> {
+    let mut edges: Vec<(&str, Relationship)> = Vec::new();
+    for doc in elements {
+        edges.extend(drawn_relationships(docs, doc).map(|rel| (doc.module_path.as_str(), rel.clone())));
+    }
+    if !diagrams.aggregate_relationships {
+        return edges;
+    }
+
+    let by_container = components_by_container(docs);
+    let container_of: BTreeMap<&str, &str> = by_container
         .iter()
-        .filter(|d| d.c4_level == C4Level::Component)
+        .flat_map(|(container, components)| components.iter().map(move |c| (c.module_path.as_str(), container.as_str())))
         .collect();
+    let element_of = |path: &str| enclosing_element(elements, container_of.get(path).copied().unwrap_or(path));
 
-    let containers: Vec<&ModuleDoc> = docs
+    let mut lifted: BTreeMap<(&str, &str), Vec<&Relationship>> = BTreeMap::new();
+    for component in docs.iter().filter(|d| d.c4_level == ElementKind::Component) {
+        let Some(from) = element_of(&component.module_path) else {
+            continue;
+        };
+        for rel in drawn_relationships(docs, component) {
+            let Some(to) = element_of(&rel.target) else {
+                continue;
+            };
+            let direct = edges.iter().any(|(f, r)| *f == from && r.target == to);
+            if to != from && !direct {
+                lifted.entry((from, to)).or_default().push(rel);
+            }
+        }
+    }
+
+    for ((from, to), rels) in lifted {
+        let mut protocols: Vec<&str> = Vec::new();
+        for rel in &rels {
+            if !rel.protocol.is_empty() && !protocols.contains(&rel.protocol.as_str()) {
+                protocols.push(&rel.protocol);
+            }
+        }
+        let label = match rels.as_slice() {
+            [rel] => rel.label.clone(),
+            _ => format!("{} dependencies", rels.len()),
+        };
+        edges.push((
+            from,
+            Relationship {
+                target: to.to_string(),
+                label,
+                protocol: protocols.join(", "),
+                hidden: false,
+            },
+        ));
+    }
+    edges
+}
+
+/// The element of `elements` that is `path` or, failing that, the nearest
+/// one containing it.
+fn enclosing_element<'a>(elements: &[&'a ModuleDoc], path: &str) -> Option<&'a str> {
+    elements
         .iter()
-        .filter(|d| d.c4_level == C4Level::Container)
+        .filter(|e| e.module_path == path || path.starts_with(&format!("{}.", e.module_path)))
+        .max_by_key(|e| e.module_path.len())
+        .map(|e| e.module_path.as_str())
+}
+
+/// Containers split by `@group`: the members of each group, by group name,
+/// and the containers outside any group. Both keep the input order.
+pub(crate) fn containers_by_group<'a>(
+    containers: &[&'a ModuleDoc],
+) -> (BTreeMap<&'a str, Vec<&'a ModuleDoc>>, Vec<&'a ModuleDoc>) {
+    let mut groups: BTreeMap<&str, Vec<&ModuleDoc>> = BTreeMap::new();
+    let mut ungrouped = Vec::new();
+    for doc in containers {
+        match &doc.group {
+            Some(group) => groups.entry(group.as_str()).or_default().push(doc),
+            None => ungrouped.push(*doc),
+        }
+    }
+    (groups, ungrouped)
+}
+
+/// Diagram id, title-cased label, and kind of a `kind:name` group:
+/// `domain:payments` -> (`group_domain_payments`, `Payments`, `domain`).
+pub(crate) fn group_boundary(group: &str) -> (String, String, Option<&str>) {
+    let id: String = group
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
         .collect();
+    let (kind, name) = match group.split_once(':') {
+        Some((kind, name)) => (Some(kind), name),
+        None => (None, group),
+    };
+    (format!("group_{}", id), to_title_case(name), kind)
+}
+
+/// Components grouped by their nearest container (longest prefix match),
+/// falling back to the `parent_container` field, then `other`.
+pub(crate) fn components_by_container(docs: &[ModuleDoc]) -> BTreeMap<String, Vec<&ModuleDoc>> {
+    let containers: Vec<&ModuleDoc> = docs.iter().filter(|d| d.c4_level.is_container()).collect();
 
-    // Group components by their nearest container (longest prefix match).
-    // Falls back to the parent_container field if no container prefix matches.
     let mut by_container: BTreeMap<String, Vec<&ModuleDoc>> = BTreeMap::new();
-    for comp in &components {
+    for comp in docs.iter().filter(|d| d.c4_level == ElementKind::Component) {
         let container = containers
             .iter()
             .filter(|c| comp.module_path.starts_with(&format!("{}.", c.module_path)))
@@ -95,12 +377,48 @@ pub fn component_diagram(docs: &[ModuleDoc]) -> String {
             });
         by_container.entry(container).or_default().push(comp);
     }
+    by_container
+}
+
+/// Generate Mermaid C4 container diagram file from `@c4 container` modules.
+pub fn generate_container(output_dir: &Path, docs: &[ModuleDoc]) {
+    let filepath = output_dir.join(CONTAINER_FILE);
+    fs::write(&filepath, container_page(docs, &DiagramConfig::default())).expect("Failed to write c4-container.md");
+}
+
+/// Render the container diagram page: the diagram plus a container table.
+pub fn container_page(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
+    let containers: Vec<&ModuleDoc> = docs.iter().filter(|d| d.c4_level.is_container()).collect();
+
+    let table_rows: Vec<String> = containers
+        .iter()
+        .map(|d| format!("| {} | {} | {} |", d.module_path, d.pattern, d.description))
+        .collect();
+
+    format!(
+        "# C4 Container Diagram\n\n> Auto-generated by archidoc\n\n{}\n\n## Containers\n\n| Container | Pattern | Description |\n|-----------|---------|-------------|\n{}\n",
+        container_diagram(docs, diagrams),
+        table_rows.join("\n")
+    )
+}
+
+/// Return the Mermaid C4 component diagram as a markdown code block string.
+///
+/// Components are grouped by their nearest container (longest matching prefix),
+/// then arranged as a tree within each container using nested `Container_Boundary`
+/// blocks. Parent-child containment arrows are emitted automatically.
+pub fn component_diagram(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
+    let components: Vec<&ModuleDoc> = docs
+        .iter()
+        .filter(|d| d.c4_level == ElementKind::Component)
+        .collect();
+    let by_container = components_by_container(docs);
 
     let mut boundary_defs = String::new();
     let mut containment_rels: Vec<(String, String)> = Vec::new();
 
     for (container_path, comps) in &by_container {
-        let container_id = container_path.replace('.', "_");
+        let container_id = ModuleDoc::id_of(docs, container_path);
         let container_name = to_title_case(container_path);
 
         // Build immediate-parent map within this container group.
@@ -147,8 +465,8 @@ pub fn component_diagram(docs: &[ModuleDoc]) -> String {
     // Containment arrows (parent -> child)
     let mut rel_defs = String::new();
     for (from, to) in &containment_rels {
-        let from_id = from.replace('.', "_");
-        let to_id = to.replace('.', "_");
+        let from_id = ModuleDoc::id_of(docs, from);
+        let to_id = ModuleDoc::id_of(docs, to);
         rel_defs.push_str(&format!(
             "    Rel({}, {}, \"contains\")\n",
             from_id, to_id
@@ -157,16 +475,14 @@ pub fn component_diagram(docs: &[ModuleDoc]) -> String {
 
     // User-defined @c4 uses relationships
     for doc in &components {
-        let from_id = doc.module_path.replace('.', "_");
-        for rel in &doc.relationships {
-            let to_id = rel.target.replace('.', "_");
-            rel_defs.push_str(&format!(
-                "    Rel({}, {}, \"{}\", \"{}\")\n",
-                from_id, to_id, rel.label, rel.protocol
-            ));
+        let from_id = doc.element_id();
+        for rel in drawn_relationships(docs, doc) {
+            rel_defs.push_str(&rel_def(&from_id, &ModuleDoc::id_of(docs, &rel.target), rel, diagrams));
         }
     }
 
+    rel_defs.push_str(&optional_styles(&components));
+
     format!(
         "```mermaid\nC4Component\n    title Component Diagram (GoF Patterns)\n\n{}{}```",
         boundary_defs, rel_defs
@@ -182,12 +498,14 @@ fn emit_node(
     depth: usize,
 ) {
     let indent = "    ".repeat(depth);
-    let id = doc.module_path.replace('.', "_");
-    let name = doc
-        .module_path
-        .split('.')
-        .last()
-        .unwrap_or(&doc.module_path);
+    let id = doc.element_id();
+    let name = with_condition(
+        doc.module_path
+            .split('.')
+            .next_back()
+            .unwrap_or(&doc.module_path),
+        doc,
+    );
 
     if let Some(kids) = children_of.get(doc.module_path.as_str()) {
         // Parent node: emit a sub-boundary containing itself + children
@@ -212,21 +530,123 @@ fn emit_node(
     }
 }
 
+/// Mermaid diagram of `doc` and the elements it uses or is used by. Elements
+/// keep their C4 level; relationship targets without an element are drawn
+/// as external systems.
+pub fn element_diagram(docs: &[ModuleDoc], doc: &ModuleDoc, diagrams: &DiagramConfig) -> String {
+    let dependents: Vec<&ModuleDoc> = docs
+        .iter()
+        .filter(|d| d.module_path != doc.module_path)
+        .filter(|d| drawn_relationships(docs, d).any(|r| r.target == doc.module_path))
+        .collect();
+    let mut neighbours: Vec<&str> = drawn_relationships(docs, doc)
+        .map(|r| r.target.as_str())
+        .chain(dependents.iter().map(|d| d.module_path.as_str()))
+        .filter(|p| *p != doc.module_path)
+        .collect();
+    neighbours.sort();
+    neighbours.dedup();
+
+    let mut nodes = vec![doc];
+    let mut defs = element_node(doc);
+    for path in neighbours {
+        match docs.iter().find(|d| d.module_path == path) {
+            Some(neighbour) => {
+                defs.push_str(&element_node(neighbour));
+                nodes.push(neighbour);
+            }
+            None => defs.push_str(&format!("    System_Ext({}, \"{}\")\n", ModuleDoc::path_slug(path), path)),
+        }
+    }
+
+    let mut rel_defs = String::new();
+    let from_id = doc.element_id();
+    for rel in drawn_relationships(docs, doc) {
+        rel_defs.push_str(&rel_def(&from_id, &ModuleDoc::id_of(docs, &rel.target), rel, diagrams));
+    }
+    for dependent in &dependents {
+        for rel in drawn_relationships(docs, dependent).filter(|r| r.target == doc.module_path) {
+            rel_defs.push_str(&rel_def(&dependent.element_id(), &from_id, rel, diagrams));
+        }
+    }
+    rel_defs.push_str(&optional_styles(&nodes));
+
+    format!(
+        "```mermaid\nC4Component\n    title {}\n\n{}\n{}```",
+        doc.module_path, defs, rel_defs
+    )
+}
+
+/// The node for `doc` in its kind's C4 macro, named by its full path.
+fn element_node(doc: &ModuleDoc) -> String {
+    node_def(doc, &with_condition(&doc.module_path, doc), "    ")
+}
+
 /// Generate Mermaid C4 component diagram file from `@c4 component` modules.
 pub fn generate_component(output_dir: &Path, docs: &[ModuleDoc]) {
-    let filepath = output_dir.join("c4-component.md");
+    let filepath = output_dir.join(COMPONENT_FILE);
+    fs::write(&filepath, component_page(docs, &DiagramConfig::default())).expect("Failed to write c4-component.md");
+}
 
-    let content = format!(
+/// Render the component diagram page.
+pub fn component_page(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
+    format!(
         "# C4 Component Diagram\n\n> Auto-generated by archidoc\n\n{}\n",
-        component_diagram(docs)
-    );
+        component_diagram(docs, diagrams)
+    )
+}
 
-    fs::write(&filepath, content).expect("Failed to write c4-component.md");
+/// Return the Mermaid sequence diagram of `flow` as a markdown code block
+/// string: a participant per step, people as actors, and one numbered
+/// message per step.
+pub fn sequence_diagram(docs: &[ModuleDoc], flow: &Flow) -> String {
+    let mut lines = vec!["```mermaid".to_string(), "sequenceDiagram".to_string(), format!("    title {}", flow.name)];
+    for step in participants(flow) {
+        let is_person = docs.iter().any(|d| d.module_path == step && d.c4_level == ElementKind::Person);
+        lines.push(format!(
+            "    {} {} as {}",
+            if is_person { "actor" } else { "participant" },
+            ModuleDoc::id_of(docs, step),
+            step
+        ));
+    }
+    for step in flow_steps(docs, flow) {
+        lines.push(format!(
+            "    {}->>{}: {}",
+            ModuleDoc::id_of(docs, step.from),
+            ModuleDoc::id_of(docs, step.to),
+            step.message()
+        ));
+    }
+    lines.push("```".to_string());
+    lines.join("\n")
+}
+
+/// Element label, suffixed with its build condition for optional elements.
+fn with_condition(name: &str, doc: &ModuleDoc) -> String {
+    match &doc.condition {
+        Some(condition) => format!("{} [{}]", name, condition),
+        None => name.to_string(),
+    }
+}
+
+/// Gray styling for optional (cfg/feature-gated) elements, so they read as
+/// conditional rather than always present.
+fn optional_styles(docs: &[&ModuleDoc]) -> String {
+    docs.iter()
+        .filter(|d| d.condition.is_some())
+        .map(|d| {
+            format!(
+                "    UpdateElementStyle({}, $bgColor=\"#9e9e9e\", $borderColor=\"#616161\")\n",
+                d.element_id()
+            )
+        })
+        .collect()
 }
 
 fn to_title_case(s: &str) -> String {
     s.split('.')
-        .last()
+        .next_back()
         .unwrap_or(s)
         .split('_')
         .map(|word| {
@@ -0,0 +1,188 @@
+use archidoc_types::ModuleDoc;
+
+use crate::architecture::ArchitectureGenerator;
+use crate::generator::{Artifact, Generator};
+use crate::site;
+
+/// An MkDocs project under `dir`: `mkdocs.yml` with the navigation, and
+/// under `docs/` an index page with the ARCHITECTURE.md sections plus a
+/// page per container holding the container, a diagram of its
+/// dependencies, and its components. Links between elements point at the
+/// page holding the target; links to source files keep only their text,
+/// since the site is built without the sources. Diagrams and collapsed
+/// sections use the Material theme and `pymdownx` extensions.
+pub struct MkDocsGenerator {
+    /// The document to split into pages; its front matter is ignored.
+    pub document: ArchitectureGenerator,
+    /// Directory of the MkDocs project, relative to the output directory.
+    pub dir: String,
+}
+
+impl Generator for MkDocsGenerator {
+    fn id(&self) -> &str {
+        "mkdocs"
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        let t = crate::i18n::strings(self.document.language);
+        let site = site::split(docs, &self.document);
+        let page_ids: Vec<String> = site.pages.iter().map(|(container, _)| container.element_id()).collect();
+
+        let path = |from: Option<&str>, to: Option<&str>| match (to, from) {
+            (Some(to), None) => format!("containers/{}.md", to),
+            (Some(to), Some(_)) => format!("{}.md", to),
+            (None, _) => "../index.md".to_string(),
+        };
+        let mut index = site::rewrite_links(&site.index, &|target| site.retarget(target, None, &|to| path(None, to)));
+        site.insert_container_index(&mut index, &self.document, &|to| path(None, Some(to)));
+
+        let mut artifacts = vec![
+            Artifact {
+                path: format!("{}/mkdocs.yml", self.dir),
+                content: config(t.architecture_title, t.component_index, &site.pages),
+            },
+            Artifact {
+                path: format!("{}/docs/index.md", self.dir),
+                content: collapsible(&index),
+            },
+        ];
+        for ((_, page), id) in site.pages.iter().zip(&page_ids) {
+            artifacts.push(Artifact {
+                path: format!("{}/docs/containers/{}.md", self.dir, id),
+                content: collapsible(&site::rewrite_links(page, &|target| site.retarget(target, Some(id), &|to| path(Some(id), to)))),
+            });
+        }
+        Ok(artifacts)
+    }
+}
+
+/// `mkdocs.yml` with the index page and, under `containers_title`, the
+/// container pages. Strings are written as JSON, which YAML reads as-is.
+fn config(site_name: &str, containers_title: &str, pages: &[(&ModuleDoc, String)]) -> String {
+    let quote = |s: &str| serde_json::to_string(s).unwrap_or_default();
+    let mut out = format!("site_name: {}\ndocs_dir: docs\nnav:\n  - {}: index.md\n", quote(site_name), quote(site_name));
+    if !pages.is_empty() {
+        out.push_str(&format!("  - {}:\n", quote(containers_title)));
+        for (container, _) in pages {
+            out.push_str(&format!(
+                "      - {}: containers/{}.md\n",
+                quote(&container.module_path),
+                container.element_id()
+            ));
+        }
+    }
+    out.push_str(
+        "theme:\n  name: material\nmarkdown_extensions:\n  - tables\n  - admonition\n  - pymdownx.details\n  - pymdownx.superfences:\n      custom_fences:\n        - name: mermaid\n          class: mermaid\n          format: !!python/name:pymdownx.superfences.fence_code_format\n",
+    );
+    out
+}
+
+/// `<details>` blocks as `pymdownx.details` blocks, since MkDocs leaves
+/// Markdown inside HTML blocks unrendered.
+fn collapsible(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut open = false;
+    for line in markdown.split_inclusive('\n') {
+        let text = line.trim_end_matches('\n');
+        if text.starts_with("<details") {
+            open = true;
+        } else if let Some(summary) = text.strip_prefix("<summary>").and_then(|s| s.strip_suffix("</summary>")) {
+            out.push_str(&format!("??? note {}\n", serde_json::to_string(summary).unwrap_or_default()));
+        } else if open && text == "</details>" {
+            open = false;
+        } else if open && !text.is_empty() {
+            out.push_str("    ");
+            out.push_str(line);
+        } else {
+            out.push_str(line);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use archidoc_types::{
+        ArchitectureConfig, DiagramConfig, ElementKind, Language, Relationship,
+    };
+
+    fn doc(path: &str, kind: ElementKind, parent: Option<&str>, uses: &[&str]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: format!("src/{}/mod.rs", path.replace('.', "/")),
+            c4_level: kind,
+            pattern: "--".to_string(),
+            description: format!("The {} element", path),
+            parent_container: parent.map(str::to_string),
+            relationships: uses
+                .iter()
+                .map(|target| Relationship {
+                    target: target.to_string(),
+                    label: "Calls".to_string(),
+                    protocol: "--".to_string(),
+                    hidden: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn generator() -> MkDocsGenerator {
+        MkDocsGenerator {
+            document: ArchitectureGenerator {
+                file_name: "ARCHITECTURE.md".to_string(),
+                link_base: PathBuf::new(),
+                fitness: vec![],
+                snapshot: Default::default(),
+                language: Language::En,
+                layout: ArchitectureConfig::default(),
+                front_matter: None,
+                diagrams: DiagramConfig::default(),
+            },
+            dir: "site".to_string(),
+        }
+    }
+
+    #[test]
+    fn project_with_an_index_and_one_page_per_container() {
+        let docs = vec![
+            doc("api", ElementKind::Container, None, &["bus.lanes"]),
+            doc("bus", ElementKind::Container, None, &[]),
+            doc("bus.lanes", ElementKind::Component, Some("bus"), &[]),
+            doc("customer", ElementKind::Person, None, &["api"]),
+        ];
+
+        let artifacts = generator().generate(&docs).unwrap();
+        let paths: Vec<&str> = artifacts.iter().map(|a| a.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["site/mkdocs.yml", "site/docs/index.md", "site/docs/containers/api.md", "site/docs/containers/bus.md"]
+        );
+
+        let config = &artifacts[0].content;
+        assert!(config.starts_with("site_name: \"Architecture Context\"\ndocs_dir: docs\nnav:\n  - \"Architecture Context\": index.md\n  - \"Component Index\":\n      - \"api\": containers/api.md\n      - \"bus\": containers/bus.md\ntheme:\n"));
+        assert!(config.contains("        - name: mermaid\n"));
+
+        let index = &artifacts[1].content;
+        assert!(index.contains("## Component Index\n\n- [api](containers/api.md) — The api element\n"));
+        assert!(index.contains("### customer\n"), "elements outside containers stay on the index page");
+        assert!(index.contains("??? note \"Text description of the system diagram\"\n"));
+        assert!(!index.contains("<details>"));
+
+        let api = &artifacts[2].content;
+        assert!(api.starts_with("# api\n\nThe api element\n"));
+        assert!(api.contains("[bus.lanes](bus.md#bus_lanes)"));
+        assert!(api.contains("[customer](../index.md#customer)"));
+        assert!(!api.contains("](src/"), "source links keep only their text");
+    }
+
+    #[test]
+    fn details_blocks_become_collapsible_admonitions() {
+        assert_eq!(
+            collapsible("<details>\n<summary>More</summary>\n\n- a\n\n</details>\n\nAfter\n"),
+            "??? note \"More\"\n\n    - a\n\n\nAfter\n"
+        );
+    }
+}
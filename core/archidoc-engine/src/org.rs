@@ -0,0 +1,413 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use archidoc_types::{
+    DriftReport, HealthReport, ModuleDoc, OrgReport, PatternAdoption, RepoRelationship, RepoSummary,
+};
+
+use crate::health_html::{escape, number, SCRIPT, STYLE};
+
+/// One repository's inputs to the roll-up. Any of them may be missing.
+#[derive(Debug, Clone, Default)]
+pub struct RepoInput {
+    pub name: String,
+    /// The repository's IR, from `--emit-ir`.
+    pub docs: Option<Vec<ModuleDoc>>,
+    /// A health report, from `--health --json`. Ignored when the IR is
+    /// present, since the report is then computed from it.
+    pub health: Option<HealthReport>,
+    /// A drift report, from `--check --json`.
+    pub drift: Option<DriftReport>,
+}
+
+impl RepoInput {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Add one JSON file, recognized by its shape: IR (`ir_version`, or a
+    /// version 1 array), a health report (`per_element`), or a drift report
+    /// (`drifted_files`).
+    pub fn add_json(&mut self, json: &str) -> Result<(), String> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(|e| format!("invalid JSON: {}", e))?;
+        if value.is_array() || value.get("ir_version").is_some() {
+            self.docs = Some(crate::ir::deserialize(json)?);
+        } else if value.get("per_element").is_some() {
+            self.health = Some(serde_json::from_value(value).map_err(|e| format!("invalid health report: {}", e))?);
+        } else if value.get("drifted_files").is_some() {
+            self.drift = Some(serde_json::from_value(value).map_err(|e| format!("invalid drift report: {}", e))?);
+        } else {
+            return Err("not an IR, health report, or drift report".to_string());
+        }
+        Ok(())
+    }
+
+    /// The health report, computed from the IR when there is one.
+    fn health_report(&self) -> HealthReport {
+        match (&self.docs, &self.health) {
+            (Some(docs), _) => crate::health::aggregate_health(docs),
+            (None, Some(health)) => health.clone(),
+            (None, None) => HealthReport::default(),
+        }
+    }
+}
+
+/// File maturity from 0 to 100: stable files count fully, active ones
+/// half, planned ones not at all. `None` without cataloged files.
+pub fn health_score(report: &HealthReport) -> Option<u32> {
+    if report.total_files == 0 {
+        return None;
+    }
+    let points = report.files_stable * 2 + report.files_active;
+    Some((points * 50 / report.total_files) as u32)
+}
+
+/// Roll the repositories up into a portfolio report.
+///
+/// Pattern adoption counts every element naming a pattern. A relationship
+/// counts as crossing into another repository when its target is not an
+/// element of its own repository but is that repository's name or one of
+/// its module paths; only repositories given as IR contribute them.
+pub fn org_report(repos: &[RepoInput]) -> OrgReport {
+    let mut report = OrgReport::default();
+    let mut patterns: BTreeMap<&str, (BTreeSet<&str>, usize)> = BTreeMap::new();
+    let healths: Vec<HealthReport> = repos.iter().map(RepoInput::health_report).collect();
+
+    for (repo, health) in repos.iter().zip(&healths) {
+        report.repos.push(RepoSummary {
+            name: repo.name.clone(),
+            elements: health.total_elements,
+            containers: health.container_count,
+            components: health.component_count,
+            files: health.total_files,
+            health_score: health_score(health),
+            patterns_total: health.patterns_total,
+            patterns_verified: health.patterns_verified,
+            drifted: repo.drift.as_ref().map(DriftReport::has_drift),
+        });
+        for element in health.per_element.iter().filter(|e| !e.pattern.is_empty() && e.pattern != "--") {
+            let entry = patterns.entry(element.pattern.as_str()).or_default();
+            entry.0.insert(repo.name.as_str());
+            entry.1 += 1;
+        }
+    }
+
+    report.patterns = patterns
+        .into_iter()
+        .map(|(pattern, (repos, elements))| PatternAdoption {
+            pattern: pattern.to_string(),
+            repos: repos.into_iter().map(str::to_string).collect(),
+            elements,
+        })
+        .collect();
+    report.patterns.sort_by(|a, b| b.repos.len().cmp(&a.repos.len()).then(b.elements.cmp(&a.elements)));
+
+    report.relationships = cross_repo_relationships(repos);
+    report
+}
+
+fn cross_repo_relationships(repos: &[RepoInput]) -> Vec<RepoRelationship> {
+    let paths: Vec<BTreeSet<&str>> = repos
+        .iter()
+        .map(|r| r.docs.iter().flatten().map(|d| d.module_path.as_str()).collect())
+        .collect();
+    let owner = |target: &str, from: usize| {
+        (0..repos.len())
+            .filter(|&i| i != from)
+            .find(|&i| repos[i].name == target)
+            .or_else(|| (0..repos.len()).filter(|&i| i != from).find(|&i| paths[i].contains(target)))
+    };
+
+    let mut counts: BTreeMap<(usize, usize), usize> = BTreeMap::new();
+    for (from, repo) in repos.iter().enumerate() {
+        for rel in repo.docs.iter().flatten().flat_map(|d| &d.relationships) {
+            if paths[from].contains(rel.target.as_str()) {
+                continue;
+            }
+            if let Some(to) = owner(&rel.target, from) {
+                *counts.entry((from, to)).or_default() += 1;
+            }
+        }
+    }
+    counts
+        .into_iter()
+        .map(|((from, to), count)| RepoRelationship {
+            from: repos[from].name.clone(),
+            to: repos[to].name.clone(),
+            count,
+        })
+        .collect()
+}
+
+/// The portfolio report as markdown: a repository table, pattern adoption,
+/// and a Mermaid C4 context diagram of the repositories.
+pub fn format_org_markdown(report: &OrgReport) -> String {
+    let mut out = String::from("# Organization Architecture Report\n\n## Repositories\n\n");
+    out.push_str("| Repository | Elements | Containers | Components | Files | Health | Patterns verified | Drift |\n");
+    out.push_str("|------------|----------|------------|------------|-------|--------|-------------------|-------|\n");
+    for repo in &report.repos {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} / {} | {} |\n",
+            repo.name,
+            repo.elements,
+            repo.containers,
+            repo.components,
+            repo.files,
+            score_text(repo.health_score),
+            repo.patterns_verified,
+            repo.patterns_total,
+            drift_text(repo.drifted)
+        ));
+    }
+    out.push_str("\nHealth is file maturity from 0 to 100: stable files count fully, active ones half.\n\n");
+
+    if !report.patterns.is_empty() {
+        out.push_str("## Pattern Adoption\n\n| Pattern | Repositories | Elements |\n|---------|--------------|----------|\n");
+        for pattern in &report.patterns {
+            out.push_str(&format!(
+                "| {} | {} ({}) | {} |\n",
+                pattern.pattern,
+                pattern.repos.len(),
+                pattern.repos.join(", "),
+                pattern.elements
+            ));
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&format!("## System Context\n\n```mermaid\n{}```\n", context_diagram(report)));
+    out
+}
+
+/// Mermaid C4 context diagram: one system per repository and one arrow per
+/// pair of repositories with relationships between them.
+pub fn context_diagram(report: &OrgReport) -> String {
+    let mut out = String::from("C4Context\n    title System Landscape\n\n");
+    for repo in &report.repos {
+        out.push_str(&format!(
+            "    System({}, \"{}\", \"{}\")\n",
+            diagram_id(&repo.name),
+            repo.name,
+            count(repo.elements, "element")
+        ));
+    }
+    if !report.relationships.is_empty() {
+        out.push('\n');
+    }
+    for rel in &report.relationships {
+        out.push_str(&format!(
+            "    Rel({}, {}, \"{}\")\n",
+            diagram_id(&rel.from),
+            diagram_id(&rel.to),
+            count(rel.count, "dependency")
+        ));
+    }
+    out
+}
+
+/// `n` and `noun`, pluralized when `n` is not 1.
+fn count(n: usize, noun: &str) -> String {
+    match (n, noun.strip_suffix('y')) {
+        (1, _) => format!("1 {}", noun),
+        (_, Some(stem)) => format!("{} {}ies", n, stem),
+        (_, None) => format!("{} {}s", n, noun),
+    }
+}
+
+/// The portfolio report as a self-contained HTML page, styled like the
+/// health dashboard. The context diagram is embedded as Mermaid source.
+pub fn render_org_html(report: &OrgReport, title: &str) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{} — Organization Architecture</title>\n", escape(title)));
+    out.push_str(&format!("<style>\n{}\n</style>\n</head>\n<body>\n", STYLE));
+    out.push_str(&format!("<h1>{}</h1>\n<p>Organization architecture</p>\n", escape(title)));
+
+    let scores: Vec<u32> = report.repos.iter().filter_map(|r| r.health_score).collect();
+    let mut cards = vec![
+        ("Repositories", report.repos.len().to_string()),
+        ("Elements", report.repos.iter().map(|r| r.elements).sum::<usize>().to_string()),
+        ("Drifted", report.repos.iter().filter(|r| r.drifted == Some(true)).count().to_string()),
+    ];
+    if !scores.is_empty() {
+        cards.push(("Average health", (scores.iter().sum::<u32>() / scores.len() as u32).to_string()));
+    }
+    out.push_str("<div class=\"summary\">\n");
+    for (label, value) in cards {
+        out.push_str(&format!("<div class=\"card\"><b>{}</b>{}</div>\n", value, label));
+    }
+    out.push_str("</div>\n");
+
+    out.push_str("<table>\n<thead><tr><th>Repository</th><th>Elements</th><th>Containers</th><th>Components</th><th>Files</th><th>Health</th><th>Patterns verified</th><th>Drift</th></tr></thead>\n<tbody>\n");
+    for repo in &report.repos {
+        out.push_str(&format!(
+            "<tr><td>{}</td>{}{}{}{}<td class=\"num\" data-value=\"{}\">{}</td><td>{} / {}</td><td>{}</td></tr>\n",
+            escape(&repo.name),
+            number(repo.elements),
+            number(repo.containers),
+            number(repo.components),
+            number(repo.files),
+            repo.health_score.map_or(-1, i64::from),
+            score_text(repo.health_score),
+            repo.patterns_verified,
+            repo.patterns_total,
+            drift_text(repo.drifted)
+        ));
+    }
+    out.push_str("</tbody>\n</table>\n");
+
+    if !report.patterns.is_empty() {
+        out.push_str("<h2>Pattern adoption</h2>\n<table>\n<thead><tr><th>Pattern</th><th>Repositories</th><th>Elements</th></tr></thead>\n<tbody>\n");
+        for pattern in &report.patterns {
+            out.push_str(&format!(
+                "<tr><td>{}</td><td data-value=\"{}\">{}</td>{}</tr>\n",
+                escape(&pattern.pattern),
+                pattern.repos.len(),
+                escape(&pattern.repos.join(", ")),
+                number(pattern.elements)
+            ));
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+
+    out.push_str(&format!(
+        "<h2>System context</h2>\n<pre class=\"mermaid\">\n{}</pre>\n",
+        escape(&context_diagram(report))
+    ));
+    out.push_str(&format!("<script>\n{}\n</script>\n</body>\n</html>\n", SCRIPT));
+    out
+}
+
+fn score_text(score: Option<u32>) -> String {
+    score.map_or_else(|| "-".to_string(), |s| s.to_string())
+}
+
+fn drift_text(drifted: Option<bool>) -> &'static str {
+    match drifted {
+        Some(true) => "drifted",
+        Some(false) => "clean",
+        None => "unknown",
+    }
+}
+
+/// A Mermaid identifier for a repository name.
+fn diagram_id(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{DriftedFile, ElementKind, FileEntry, HealthStatus, PatternStatus, Relationship};
+
+    fn doc(path: &str, pattern: &str, uses: &[&str], health: &[HealthStatus]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            c4_level: ElementKind::Container,
+            pattern: pattern.to_string(),
+            relationships: uses
+                .iter()
+                .map(|target| Relationship {
+                    target: target.to_string(),
+                    label: "Calls".to_string(),
+                    protocol: String::new(),
+                    hidden: false,
+                })
+                .collect(),
+            files: health
+                .iter()
+                .enumerate()
+                .map(|(i, health)| FileEntry {
+                    name: format!("f{}.rs", i),
+                    pattern: "--".to_string(),
+                    pattern_status: PatternStatus::Planned,
+                    purpose: String::new(),
+                    health: *health,
+                    loc: None,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn repos() -> Vec<RepoInput> {
+        let mut payments = RepoInput::new("payments");
+        payments.docs = Some(vec![
+            doc("api", "Facade", &["core", "ledger", "store"], &[HealthStatus::Stable, HealthStatus::Active]),
+            doc("core", "Observer", &["store"], &[HealthStatus::Planned, HealthStatus::Stable]),
+        ]);
+        payments.drift = Some(DriftReport {
+            drifted_files: vec![DriftedFile {
+                path: "ARCHITECTURE.md".to_string(),
+                expected_lines: 1,
+                actual_lines: 2,
+                diff: String::new(),
+            }],
+            ..Default::default()
+        });
+        let mut ledger = RepoInput::new("ledger");
+        ledger.docs = Some(vec![doc("store", "Observer", &[], &[HealthStatus::Stable])]);
+        ledger.drift = Some(DriftReport::default());
+        vec![payments, ledger, RepoInput::new("legacy")]
+    }
+
+    #[test]
+    fn repositories_patterns_and_cross_repo_dependencies_roll_up() {
+        let report = org_report(&repos());
+
+        let payments = &report.repos[0];
+        assert_eq!((payments.elements, payments.files, payments.health_score), (2, 4, Some(62)));
+        assert_eq!(payments.drifted, Some(true));
+        assert_eq!(report.repos[1].drifted, Some(false));
+        assert_eq!(report.repos[2].health_score, None);
+        assert_eq!(report.repos[2].drifted, None);
+
+        assert_eq!(report.patterns[0].pattern, "Observer");
+        assert_eq!(report.patterns[0].repos, vec!["ledger", "payments"]);
+        assert_eq!(report.patterns[1].pattern, "Facade");
+
+        assert_eq!(
+            report.relationships,
+            vec![RepoRelationship {
+                from: "payments".to_string(),
+                to: "ledger".to_string(),
+                count: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn markdown_and_html_include_the_context_diagram() {
+        let report = org_report(&repos());
+
+        let markdown = format_org_markdown(&report);
+        assert!(markdown.contains("| payments | 2 | 2 | 0 | 4 | 62 | 0 / 2 | drifted |\n"), "{}", markdown);
+        assert!(markdown.contains("| legacy | 0 | 0 | 0 | 0 | - | 0 / 0 | unknown |\n"));
+        assert!(markdown.contains("| Observer | 2 (ledger, payments) | 2 |\n"));
+        assert!(markdown.contains("    System(payments, \"payments\", \"2 elements\")\n"));
+        assert!(markdown.contains("    Rel(payments, ledger, \"3 dependencies\")\n```\n"));
+
+        let html = render_org_html(&report, "Acme");
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<div class=\"card\"><b>1</b>Drifted</div>"));
+        assert!(html.contains("Rel(payments, ledger, &quot;3 dependencies&quot;)"));
+    }
+
+    #[test]
+    fn json_inputs_are_recognized_by_shape() {
+        let mut repo = RepoInput::new("payments");
+
+        repo.add_json("[]").unwrap();
+        repo.add_json(&serde_json::to_string(&HealthReport::default()).unwrap()).unwrap();
+        repo.add_json(&serde_json::to_string(&DriftReport::default()).unwrap()).unwrap();
+
+        assert_eq!(repo.docs, Some(vec![]));
+        assert!(repo.health.is_some() && repo.drift.is_some());
+        assert_eq!(
+            repo.add_json("{\"name\": 1}"),
+            Err("not an IR, health report, or drift report".to_string())
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use archidoc_types::ModuleDoc;
+
+/// Maps source files to the module that owns them.
+///
+/// A file belongs to the module whose entry file it is, otherwise to the
+/// module whose directory most closely contains it, so nested modules claim
+/// their own files. Paths are compared after canonicalization, so relative
+/// and absolute paths mix freely.
+pub struct FileOwnership {
+    sources: Vec<PathBuf>,
+    dirs: Vec<Option<PathBuf>>,
+}
+
+impl FileOwnership {
+    pub fn new(docs: &[ModuleDoc]) -> Self {
+        Self {
+            sources: docs.iter().map(|d| canonical(Path::new(&d.source_file))).collect(),
+            dirs: docs
+                .iter()
+                .map(|d| ModuleDoc::own_dir(Path::new(&d.source_file)).map(|dir| canonical(&dir)))
+                .collect(),
+        }
+    }
+
+    /// Index into the `docs` this was built from of the module owning `file`.
+    pub fn owner(&self, file: &Path) -> Option<usize> {
+        let file = canonical(file);
+        self.sources.iter().position(|s| *s == file).or_else(|| {
+            self.dirs
+                .iter()
+                .enumerate()
+                .filter_map(|(i, dir)| dir.as_ref().map(|d| (i, d)))
+                .filter(|(_, dir)| file.starts_with(dir))
+                .max_by_key(|(_, dir)| dir.components().count())
+                .map(|(i, _)| i)
+        })
+    }
+}
+
+/// Module paths of the modules owning `files`, the changed files of a
+/// run scoped to what changed.
+///
+/// `None` when a file was deleted or no module owns it — a removed module,
+/// a hand-edited generated output — since those can change the result
+/// without touching any module the scope would keep.
+pub fn changed_modules(docs: &[ModuleDoc], files: &[PathBuf]) -> Option<BTreeSet<String>> {
+    let ownership = FileOwnership::new(docs);
+    files
+        .iter()
+        .map(|file| {
+            let owner = ownership.owner(file).filter(|_| file.exists())?;
+            Some(docs[owner].module_path.clone())
+        })
+        .collect()
+}
+
+/// Canonicalize a path, or its closest existing ancestor for files that
+/// are not on disk.
+fn canonical(path: &Path) -> PathBuf {
+    if let Ok(resolved) = fs::canonicalize(path) {
+        return resolved;
+    }
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) if !parent.as_os_str().is_empty() => canonical(parent).join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::ElementKind;
+
+    fn doc(source: &Path, path: &str) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: source.to_string_lossy().to_string(),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn files_belong_to_the_closest_module() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        fs::create_dir_all(src.join("bus/calc")).unwrap();
+        fs::create_dir_all(src.join("api")).unwrap();
+        fs::write(src.join("api.rs"), "").unwrap();
+        let docs = vec![
+            doc(&src.join("lib.rs"), "_lib"),
+            doc(&src.join("bus/mod.rs"), "bus"),
+            doc(&src.join("bus/calc/mod.rs"), "bus.calc"),
+            doc(&src.join("api.rs"), "api"),
+        ];
+
+        fs::create_dir_all(src.join("api")).unwrap();
+        fs::write(src.join("bus/calc/math.rs"), "").unwrap();
+        fs::write(src.join("api/routes.rs"), "").unwrap();
+        fs::write(tmp.path().join("ARCHITECTURE.md"), "").unwrap();
+        let changed = [src.join("bus/calc/math.rs"), src.join("api/routes.rs")];
+
+        let touched = changed_modules(&docs, &changed);
+
+        assert_eq!(touched, Some(BTreeSet::from(["api".to_string(), "bus.calc".to_string()])));
+        assert_eq!(FileOwnership::new(&docs).owner(&src.join("util.rs")), Some(0));
+        assert_eq!(changed_modules(&docs, &[changed[0].clone(), tmp.path().join("ARCHITECTURE.md")]), None);
+        assert_eq!(changed_modules(&docs, &[src.join("legacy/mod.rs")]), None);
+    }
+}
@@ -2,64 +2,168 @@ use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
-use archidoc_types::{C4Level, ModuleDoc};
+use archidoc_types::{DiagramConfig, ElementKind, Flow, LineStyle, ModuleDoc, Relationship};
+
+use crate::flows::{all_flows, flow_steps, participants};
+use crate::generator::{Artifact, Generator};
+use crate::mermaid::{
+    container_edges, containers_by_group, context_edges, drawn_relationships, group_boundary, has_context, node_def,
+    system_description, SYSTEM_ID,
+};
+
+/// File name of the PlantUML system context diagram.
+pub const CONTEXT_FILE: &str = "c4-context.puml";
+/// File name of the PlantUML container diagram.
+pub const CONTAINER_FILE: &str = "c4-container.puml";
+/// File name of the PlantUML component diagram.
+pub const COMPONENT_FILE: &str = "c4-component.puml";
+
+/// PlantUML container and component diagrams under `c4/`, a system context
+/// diagram when there are people or external systems, and a dynamic
+/// diagram and a sequence diagram per flow, named `c4-dynamic-<flow>.puml`
+/// and `sequence-<flow>.puml`.
+pub struct PlantUmlGenerator {
+    pub diagrams: DiagramConfig,
+}
+
+impl Generator for PlantUmlGenerator {
+    fn id(&self) -> &str {
+        "plantuml"
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        let mut artifacts = vec![
+            Artifact {
+                path: format!("c4/{}", CONTAINER_FILE),
+                content: container_diagram(docs, &self.diagrams),
+            },
+            Artifact {
+                path: format!("c4/{}", COMPONENT_FILE),
+                content: component_diagram(docs, &self.diagrams),
+            },
+        ];
+        if has_context(docs) {
+            artifacts.push(Artifact {
+                path: format!("c4/{}", CONTEXT_FILE),
+                content: context_diagram(docs, &self.diagrams),
+            });
+        }
+        for flow in all_flows(docs) {
+            artifacts.push(Artifact {
+                path: format!("c4/c4-dynamic-{}.puml", flow.name),
+                content: dynamic_diagram(docs, flow),
+            });
+            artifacts.push(Artifact {
+                path: format!("c4/sequence-{}.puml", flow.name),
+                content: sequence_diagram(docs, flow),
+            });
+        }
+        Ok(artifacts)
+    }
+}
+
+/// Render the PlantUML C4 system context diagram: the system as one box
+/// among the people and systems outside it, with the relationships
+/// crossing its boundary.
+pub fn context_diagram(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
+    let mut defs = String::new();
+    for doc in docs.iter().filter(|d| d.c4_level.is_external()) {
+        defs.push_str(&node_def(doc, &to_title_case(&doc.module_path), ""));
+    }
+    defs.push_str(&format!("System({}, \"System\", \"{}\")\n", SYSTEM_ID, system_description(docs)));
+
+    let mut rel_defs = String::new();
+    for (from, to, rel) in context_edges(docs) {
+        rel_defs.push_str(&rel_def(&from, &to, &rel, diagrams));
+    }
+
+    format!(
+        r#"@startuml c4-context
+!include https://raw.githubusercontent.com/plantuml-stdlib/C4-PlantUML/master/C4_Context.puml
+
+{}title System Context Diagram
+
+{}
+{}
+@enduml
+"#,
+        rel_tags(diagrams),
+        defs,
+        rel_defs
+    )
+}
 
 /// Generate PlantUML C4 container diagram from `@c4 container` modules.
 pub fn generate_container(output_dir: &Path, docs: &[ModuleDoc]) {
-    let filepath = output_dir.join("c4-container.puml");
+    let filepath = output_dir.join(CONTAINER_FILE);
+    fs::write(&filepath, container_diagram(docs, &DiagramConfig::default())).expect("Failed to write c4-container.puml");
+}
 
-    let containers: Vec<&ModuleDoc> = docs
-        .iter()
-        .filter(|d| d.c4_level == C4Level::Container)
-        .collect();
+/// Render the PlantUML C4 container diagram.
+pub fn container_diagram(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
+    let elements: Vec<&ModuleDoc> = docs.iter().filter(|d| d.c4_level.in_container_view()).collect();
+    let (external, containers): (Vec<&ModuleDoc>, Vec<&ModuleDoc>) =
+        elements.iter().partition(|d| d.c4_level.is_external());
+
+    let container_def = |doc: &ModuleDoc, indent: &str| node_def(doc, &to_title_case(&doc.module_path), indent);
 
+    let mut external_defs = String::new();
+    for doc in &external {
+        external_defs.push_str(&container_def(doc, ""));
+    }
+    if !external_defs.is_empty() {
+        external_defs.push('\n');
+    }
+
+    let (groups, ungrouped) = containers_by_group(&containers);
     let mut container_defs = String::new();
-    for doc in &containers {
-        let id = doc.module_path.replace('.', "_");
-        let name = to_title_case(&doc.module_path);
-        container_defs.push_str(&format!(
-            "    Container({}, \"{}\", \"{}\", \"{}\")\n",
-            id, name, doc.pattern, doc.description
-        ));
+    for doc in ungrouped {
+        container_defs.push_str(&container_def(doc, "    "));
+    }
+    for (group, members) in &groups {
+        let (id, label, _) = group_boundary(group);
+        container_defs.push_str(&format!("    Enterprise_Boundary({}, \"{}\") {{\n", id, label));
+        for doc in members {
+            container_defs.push_str(&container_def(doc, "        "));
+        }
+        container_defs.push_str("    }\n");
     }
 
     let mut rel_defs = String::new();
-    for doc in &containers {
-        let from_id = doc.module_path.replace('.', "_");
-        for rel in &doc.relationships {
-            let to_id = rel.target.replace('.', "_");
-            rel_defs.push_str(&format!(
-                "Rel({}, {}, \"{}\", \"{}\")\n",
-                from_id, to_id, rel.label, rel.protocol
-            ));
-        }
+    for (from, rel) in container_edges(docs, &elements, diagrams) {
+        rel_defs.push_str(&rel_def(&ModuleDoc::id_of(docs, from), &ModuleDoc::id_of(docs, &rel.target), &rel, diagrams));
     }
 
-    let content = format!(
+    format!(
         r#"@startuml c4-container
 !include https://raw.githubusercontent.com/plantuml-stdlib/C4-PlantUML/master/C4_Container.puml
 
-title Container Diagram
+{}title Container Diagram
 
-System_Boundary(sys, "System") {{
+{}System_Boundary(sys, "System") {{
 {}}}
 
 {}
 @enduml
 "#,
-        container_defs, rel_defs
-    );
-
-    fs::write(&filepath, content).expect("Failed to write c4-container.puml");
+        rel_tags(diagrams),
+        external_defs,
+        container_defs,
+        rel_defs
+    )
 }
 
 /// Generate PlantUML C4 component diagram from `@c4 component` modules.
 pub fn generate_component(output_dir: &Path, docs: &[ModuleDoc]) {
-    let filepath = output_dir.join("c4-component.puml");
+    let filepath = output_dir.join(COMPONENT_FILE);
+    fs::write(&filepath, component_diagram(docs, &DiagramConfig::default())).expect("Failed to write c4-component.puml");
+}
 
+/// Render the PlantUML C4 component diagram.
+pub fn component_diagram(docs: &[ModuleDoc], diagrams: &DiagramConfig) -> String {
     let components: Vec<&ModuleDoc> = docs
         .iter()
-        .filter(|d| d.c4_level == C4Level::Component)
+        .filter(|d| d.c4_level == ElementKind::Component)
         .collect();
 
     let mut grouped: BTreeMap<String, Vec<&ModuleDoc>> = BTreeMap::new();
@@ -73,18 +177,18 @@ pub fn generate_component(output_dir: &Path, docs: &[ModuleDoc]) {
 
     let mut boundary_defs = String::new();
     for (parent, component_docs) in &grouped {
-        let parent_id = parent.replace('.', "_");
+        let parent_id = ModuleDoc::id_of(docs, parent);
         let parent_name = to_title_case(parent);
         boundary_defs.push_str(&format!(
             "Container_Boundary({}_boundary, \"{}\") {{\n",
             parent_id, parent_name
         ));
         for doc in component_docs {
-            let id = doc.module_path.replace('.', "_");
+            let id = doc.element_id();
             let name = doc
                 .module_path
                 .split('.')
-                .last()
+                .next_back()
                 .unwrap_or(&doc.module_path);
             boundary_defs.push_str(&format!(
                 "    Component({}, \"{}\", \"{}\", \"{}\")\n",
@@ -96,34 +200,131 @@ pub fn generate_component(output_dir: &Path, docs: &[ModuleDoc]) {
 
     let mut rel_defs = String::new();
     for doc in &components {
-        let from_id = doc.module_path.replace('.', "_");
-        for rel in &doc.relationships {
-            let to_id = rel.target.replace('.', "_");
-            rel_defs.push_str(&format!(
-                "Rel({}, {}, \"{}\", \"{}\")\n",
-                from_id, to_id, rel.label, rel.protocol
-            ));
+        let from_id = doc.element_id();
+        for rel in drawn_relationships(docs, doc) {
+            rel_defs.push_str(&rel_def(&from_id, &ModuleDoc::id_of(docs, &rel.target), rel, diagrams));
         }
     }
 
-    let content = format!(
+    format!(
         r#"@startuml c4-component
 !include https://raw.githubusercontent.com/plantuml-stdlib/C4-PlantUML/master/C4_Component.puml
 
-title Component Diagram (GoF Patterns)
+{}title Component Diagram (GoF Patterns)
 
 {}{}
 @enduml
 "#,
-        boundary_defs, rel_defs
-    );
+        rel_tags(diagrams),
+        boundary_defs,
+        rel_defs
+    )
+}
+
+/// One `AddRelTag` per protocol in `[diagrams.edge_styles]`, with a blank
+/// line after them.
+fn rel_tags(diagrams: &DiagramConfig) -> String {
+    let mut out = String::new();
+    for (protocol, style) in &diagrams.edge_styles {
+        let mut args = vec![format!("\"{}\"", protocol)];
+        if let Some(color) = &style.color {
+            args.push(format!("$textColor=\"{}\"", color));
+            args.push(format!("$lineColor=\"{}\"", color));
+        }
+        match style.line {
+            LineStyle::Solid => {}
+            LineStyle::Dashed => args.push("$lineStyle=DashedLine()".to_string()),
+            LineStyle::Dotted => args.push("$lineStyle=DottedLine()".to_string()),
+            LineStyle::Bold => args.push("$lineStyle=BoldLine()".to_string()),
+        }
+        out.push_str(&format!("AddRelTag({})\n", args.join(", ")));
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+/// A `Rel` arrow, tagged with its protocol when that has a style.
+fn rel_def(from_id: &str, to_id: &str, rel: &Relationship, diagrams: &DiagramConfig) -> String {
+    let tag = diagrams.edge_styles.keys().find(|name| !rel.protocol.is_empty() && name.eq_ignore_ascii_case(&rel.protocol));
+    match tag {
+        Some(tag) => format!(
+            "Rel({}, {}, \"{}\", \"{}\", $tags=\"{}\")\n",
+            from_id, to_id, rel.label, rel.protocol, tag
+        ),
+        None => format!("Rel({}, {}, \"{}\", \"{}\")\n", from_id, to_id, rel.label, rel.protocol),
+    }
+}
+
+/// Render the PlantUML C4 dynamic diagram of `flow`: its participants and
+/// one `RelIndex` arrow per step. Steps that are not elements are drawn as
+/// external systems.
+pub fn dynamic_diagram(docs: &[ModuleDoc], flow: &Flow) -> String {
+    let mut participant_defs = String::new();
+    for step in participants(flow) {
+        let title = to_title_case(step);
+        match docs.iter().find(|d| d.module_path == step) {
+            Some(doc) => participant_defs.push_str(&node_def(doc, &title, "")),
+            None => participant_defs.push_str(&format!("System_Ext({}, \"{}\", \"\")\n", ModuleDoc::path_slug(step), title)),
+        }
+    }
+
+    let mut rel_defs = String::new();
+    for step in flow_steps(docs, flow) {
+        rel_defs.push_str(&format!(
+            "RelIndex({}, {}, {}, \"{}\", \"{}\")\n",
+            step.index,
+            ModuleDoc::id_of(docs, step.from),
+            ModuleDoc::id_of(docs, step.to),
+            step.label,
+            step.protocol
+        ));
+    }
+
+    format!(
+        r#"@startuml c4-dynamic-{}
+!include https://raw.githubusercontent.com/plantuml-stdlib/C4-PlantUML/master/C4_Dynamic.puml
+
+title Dynamic Diagram: {}
+
+{}
+{}
+@enduml
+"#,
+        flow.name, flow.name, participant_defs, rel_defs
+    )
+}
 
-    fs::write(&filepath, content).expect("Failed to write c4-component.puml");
+/// Render the PlantUML sequence diagram of `flow`: a participant per step,
+/// people as actors, and one numbered message per step.
+pub fn sequence_diagram(docs: &[ModuleDoc], flow: &Flow) -> String {
+    let mut out = format!("@startuml sequence-{}\ntitle {}\n\n", flow.name, flow.name);
+    for step in participants(flow) {
+        let is_person = docs.iter().any(|d| d.module_path == step && d.c4_level == ElementKind::Person);
+        out.push_str(&format!(
+            "{} \"{}\" as {}\n",
+            if is_person { "actor" } else { "participant" },
+            step,
+            ModuleDoc::id_of(docs, step)
+        ));
+    }
+    out.push('\n');
+    for step in flow_steps(docs, flow) {
+        out.push_str(&format!(
+            "{} -> {} : {}\n",
+            ModuleDoc::id_of(docs, step.from),
+            ModuleDoc::id_of(docs, step.to),
+            step.message()
+        ));
+    }
+    out.push_str("@enduml\n");
+    out
 }
 
 fn to_title_case(s: &str) -> String {
     s.split('.')
-        .last()
+        .next_back()
         .unwrap_or(s)
         .split('_')
         .map(|word| {
@@ -136,3 +337,205 @@ fn to_title_case(s: &str) -> String {
         .collect::<Vec<_>>()
         .join(" ")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container(path: &str, group: Option<&str>) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            group: group.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn groups_become_enterprise_boundaries() {
+        let docs = vec![container("billing", Some("domain:payments")), container("bus", None)];
+
+        let diagram = container_diagram(&docs, &DiagramConfig::default());
+
+        assert!(diagram.contains(
+            "    Container(bus, \"Bus\", \"--\", \"\")\n    Enterprise_Boundary(group_domain_payments, \"Payments\") {\n        Container(billing, \"Billing\", \"--\", \"\")\n    }\n"
+        ), "{}", diagram);
+    }
+
+    #[test]
+    fn people_and_external_systems_sit_outside_the_system_boundary() {
+        let mut user = container("user", None);
+        user.c4_level = ElementKind::Person;
+        user.description = "Places orders".to_string();
+        let mut psp = container("psp", None);
+        psp.c4_level = ElementKind::ExternalSystem;
+        let mut deploy = container("k8s", None);
+        deploy.c4_level = ElementKind::DeploymentNode;
+        let docs = vec![user, psp, deploy, container("billing", None)];
+
+        let diagram = container_diagram(&docs, &DiagramConfig::default());
+
+        assert!(diagram.contains(
+            "Person(user, \"User\", \"Places orders\")\nSystem_Ext(psp, \"Psp\", \"\")\n\nSystem_Boundary(sys, \"System\") {\n    Container(billing, \"Billing\", \"--\", \"\")\n}"
+        ), "{}", diagram);
+        assert!(!diagram.contains("k8s"));
+    }
+
+    #[test]
+    fn context_diagram_draws_the_system_as_one_box() {
+        let uses = |doc: &mut ModuleDoc, target: &str, label: &str| {
+            doc.relationships.push(archidoc_types::Relationship {
+                target: target.to_string(),
+                label: label.to_string(),
+                protocol: String::new(),
+                hidden: false,
+            });
+        };
+        let mut root = container("_lib", None);
+        root.description = "Online shop".to_string();
+        let mut customer = container("customer", None);
+        customer.c4_level = ElementKind::Person;
+        uses(&mut customer, "api", "Browses");
+        uses(&mut customer, "store", "Also browses");
+        let mut stripe = container("stripe", None);
+        stripe.c4_level = ElementKind::ExternalSystem;
+        let mut api = container("api", None);
+        uses(&mut api, "store", "Reads");
+        uses(&mut api, "stripe", "Charges cards");
+        uses(&mut api, "smtp", "Sends mail");
+        let docs = vec![root, api, customer, container("store", None), stripe];
+
+        let diagram = context_diagram(&docs, &DiagramConfig::default());
+
+        assert_eq!(
+            diagram,
+            "@startuml c4-context\n!include https://raw.githubusercontent.com/plantuml-stdlib/C4-PlantUML/master/C4_Context.puml\n\ntitle System Context Diagram\n\nPerson(customer, \"Customer\", \"\")\nSystem_Ext(stripe, \"Stripe\", \"\")\nSystem(sys, \"System\", \"Online shop\")\n\nRel(sys, stripe, \"Charges cards\", \"\")\nRel(customer, sys, \"Browses\", \"\")\n\n@enduml\n"
+        );
+        assert!(!has_context(&docs[..2]));
+    }
+
+    #[test]
+    fn component_edges_aggregate_into_one_container_arrow() {
+        let uses = |path: &str, target: &str, label: &str, protocol: &str| {
+            let mut doc = container(path, None);
+            doc.c4_level = ElementKind::Component;
+            doc.relationships.push(archidoc_types::Relationship {
+                target: target.to_string(),
+                label: label.to_string(),
+                protocol: protocol.to_string(),
+                hidden: false,
+            });
+            doc
+        };
+        let docs = vec![
+            container("api", None),
+            uses("api.handlers", "db.orders", "Reads", "sqlx"),
+            uses("api.jobs", "db", "Writes", "sqlx"),
+            uses("api.auth", "api.handlers", "Guards", ""),
+            uses("api.cache", "bus", "Publishes", "channel"),
+            container("bus", None),
+            container("db", None),
+        ];
+        let aggregate = DiagramConfig {
+            aggregate_relationships: true,
+            ..Default::default()
+        };
+
+        let detailed = container_diagram(&docs, &DiagramConfig::default());
+        let aggregated = container_diagram(&docs, &aggregate);
+
+        assert!(!detailed.contains("Rel("), "{}", detailed);
+        assert!(aggregated.contains(
+            "Rel(api, bus, \"Publishes\", \"channel\")\nRel(api, db, \"2 dependencies\", \"sqlx\")\n"
+        ), "{}", aggregated);
+        assert!(!aggregated.contains("Guards"));
+    }
+
+    #[test]
+    fn edge_styles_become_relationship_tags() {
+        let mut api = container("api", None);
+        for (target, protocol) in [("bus", "channel"), ("db", "sqlx")] {
+            api.relationships.push(archidoc_types::Relationship {
+                target: target.to_string(),
+                label: "Uses".to_string(),
+                protocol: protocol.to_string(),
+                hidden: false,
+            });
+        }
+        let docs = vec![api, container("bus", None), container("db", None)];
+        let mut diagrams = DiagramConfig::default();
+        diagrams.edge_styles.insert(
+            "channel".to_string(),
+            archidoc_types::EdgeStyle {
+                color: Some("green".to_string()),
+                line: LineStyle::Dashed,
+            },
+        );
+        diagrams.edge_styles.insert(
+            "grpc".to_string(),
+            archidoc_types::EdgeStyle {
+                color: None,
+                line: LineStyle::Bold,
+            },
+        );
+
+        let diagram = container_diagram(&docs, &diagrams);
+
+        assert!(diagram.contains(
+            "AddRelTag(\"channel\", $textColor=\"green\", $lineColor=\"green\", $lineStyle=DashedLine())\nAddRelTag(\"grpc\", $lineStyle=BoldLine())\n\ntitle Container Diagram\n"
+        ), "{}", diagram);
+        assert!(diagram.contains("Rel(api, bus, \"Uses\", \"channel\", $tags=\"channel\")\nRel(api, db, \"Uses\", \"sqlx\")\n"));
+        assert!(!container_diagram(&docs, &DiagramConfig::default()).contains("AddRelTag"));
+    }
+
+    #[test]
+    fn flows_become_numbered_dynamic_diagrams() {
+        let mut api = container("api", None);
+        api.relationships.push(archidoc_types::Relationship {
+            target: "payments".to_string(),
+            label: "Charges card".to_string(),
+            protocol: "HTTPS".to_string(),
+            hidden: false,
+        });
+        let flow = Flow {
+            name: "checkout".to_string(),
+            steps: vec!["api".to_string(), "payments".to_string(), "bank.gateway".to_string()],
+        };
+        let docs = vec![api, container("payments", None)];
+
+        let diagram = dynamic_diagram(&docs, &flow);
+
+        assert!(diagram.starts_with("@startuml c4-dynamic-checkout\n"));
+        assert!(diagram.contains(
+            "Container(api, \"Api\", \"--\", \"\")\nContainer(payments, \"Payments\", \"--\", \"\")\nSystem_Ext(bank_gateway, \"Gateway\", \"\")\n"
+        ), "{}", diagram);
+        assert!(diagram.contains(
+            "RelIndex(1, api, payments, \"Charges card\", \"HTTPS\")\nRelIndex(2, payments, bank_gateway, \"\", \"\")\n"
+        ), "{}", diagram);
+    }
+
+    #[test]
+    fn flows_become_sequence_diagrams() {
+        let mut api = container("api", None);
+        api.relationships.push(archidoc_types::Relationship {
+            target: "payments".to_string(),
+            label: "Charges card".to_string(),
+            protocol: "HTTPS".to_string(),
+            hidden: false,
+        });
+        api.flows.push(Flow {
+            name: "checkout".to_string(),
+            steps: vec!["api".to_string(), "payments".to_string(), "bank.gateway".to_string()],
+        });
+        let docs = vec![api, container("payments", None)];
+
+        let artifacts = PlantUmlGenerator { diagrams: DiagramConfig::default() }.generate(&docs).unwrap();
+
+        assert_eq!(artifacts.last().unwrap().path, "c4/sequence-checkout.puml");
+        assert_eq!(
+            artifacts.last().unwrap().content,
+            "@startuml sequence-checkout\ntitle checkout\n\nparticipant \"api\" as api\nparticipant \"payments\" as payments\nparticipant \"bank.gateway\" as bank_gateway\n\napi -> payments : 1. Charges card (HTTPS)\npayments -> bank_gateway : 2.\n@enduml\n"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use archidoc_types::{ModuleDoc, PluginConfig, ProjectConfig};
+
+use crate::generator::{Artifact, Generator};
+
+/// An external generator declared as `[generator.<name>]` in
+/// `archidoc.toml`.
+///
+/// The command runs in the project root with the JSON IR on stdin and must
+/// print a JSON array of `{"path": ..., "content": ...}` objects; paths are
+/// relative to the output directory, like the built-in generators'.
+pub struct SubprocessGenerator {
+    pub name: String,
+    pub command: PathBuf,
+    pub args: Vec<String>,
+    pub root: PathBuf,
+}
+
+impl SubprocessGenerator {
+    pub fn new(name: &str, config: &PluginConfig, root: &Path) -> Self {
+        Self {
+            name: name.to_string(),
+            command: resolve_command(&config.command, root),
+            args: config.args.clone(),
+            root: root.to_path_buf(),
+        }
+    }
+}
+
+impl Generator for SubprocessGenerator {
+    fn id(&self) -> &str {
+        &self.name
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        let mut child = Command::new(&self.command)
+            .args(&self.args)
+            .current_dir(&self.root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("failed to run generator '{}' ({}): {}", self.name, self.command.display(), e))?;
+
+        // Feed stdin from a thread so a generator that writes before it
+        // finishes reading cannot deadlock on a full pipe
+        let ir = crate::ir::serialize(docs);
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let writer = std::thread::spawn(move || stdin.write_all(ir.as_bytes()));
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("failed to run generator '{}': {}", self.name, e))?;
+        // A generator may exit without reading its input; that is not an error
+        let _ = writer.join();
+
+        if !output.status.success() {
+            return Err(format!(
+                "generator '{}' failed ({}): {}",
+                self.name,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let artifacts: Vec<Artifact> = serde_json::from_slice(&output.stdout)
+            .map_err(|e| format!("generator '{}' printed invalid artifacts: {}", self.name, e))?;
+        if let Some(bad) = artifacts.iter().find(|a| !is_relative_path(&a.path)) {
+            return Err(format!(
+                "generator '{}' returned path '{}' outside the output directory",
+                self.name, bad.path
+            ));
+        }
+        Ok(artifacts)
+    }
+}
+
+/// Every external generator declared in `config`, in name order.
+pub fn declared(config: &ProjectConfig, root: &Path) -> Vec<Box<dyn Generator>> {
+    config
+        .generator
+        .iter()
+        .map(|(name, plugin)| Box::new(SubprocessGenerator::new(name, plugin, root)) as Box<dyn Generator>)
+        .collect()
+}
+
+/// Resolve `./gen` against the project root; bare names are looked up on
+/// `PATH`.
+pub(crate) fn resolve_command(command: &str, root: &Path) -> PathBuf {
+    if command.contains(['/', '\\']) {
+        root.join(command)
+    } else {
+        PathBuf::from(command)
+    }
+}
+
+fn is_relative_path(path: &str) -> bool {
+    !path.is_empty() && Path::new(path).components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> SubprocessGenerator {
+        let config = PluginConfig {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+        };
+        SubprocessGenerator::new("custom", &config, &std::env::temp_dir())
+    }
+
+    #[test]
+    fn reads_artifacts_from_stdout() {
+        // Echo the IR's length back to show it arrived on stdin
+        let generator = shell(r#"n=$(wc -c); printf '[{"path":"custom/ir.txt","content":"%s"}]' $n"#);
+
+        let artifacts = generator.generate(&[]).unwrap();
+
+        let expected = crate::ir::serialize(&[]).len().to_string();
+        assert_eq!(
+            artifacts,
+            vec![Artifact {
+                path: "custom/ir.txt".to_string(),
+                content: expected,
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_failures_and_escaping_paths() {
+        let err = shell("echo 'no credentials' >&2; exit 3").generate(&[]).unwrap_err();
+        assert!(err.starts_with("generator 'custom' failed"), "{}", err);
+        assert!(err.ends_with("no credentials"), "{}", err);
+
+        let err = shell("echo not json").generate(&[]).unwrap_err();
+        assert!(err.contains("invalid artifacts"), "{}", err);
+
+        let err = shell(r#"echo '[{"path":"../x","content":""}]'"#).generate(&[]).unwrap_err();
+        assert!(err.contains("outside the output directory"), "{}", err);
+    }
+
+    #[test]
+    fn relative_commands_resolve_from_the_root() {
+        let command = |command: &str| {
+            let config = PluginConfig {
+                command: command.to_string(),
+                args: vec![],
+            };
+            SubprocessGenerator::new("custom", &config, Path::new("/repo")).command
+        };
+
+        assert_eq!(command("./gen-confluence"), Path::new("/repo/gen-confluence"));
+        assert_eq!(command("gen-confluence"), Path::new("gen-confluence"));
+    }
+}
@@ -0,0 +1,132 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use archidoc_types::{PromotionRecord, StatusChange};
+
+/// Location of the promotions audit log, relative to the project root.
+///
+/// One JSON object per line, appended whenever an element's pattern status
+/// is changed automatically (e.g. auto-promotion from `planned` to
+/// `verified`), so the log explains why the docs claim what they claim.
+pub const PROMOTIONS_LOG: &str = ".archidoc/promotions.log";
+
+/// Append status changes to the audit log in `root`.
+///
+/// Annotations are not rewritten, so the same promotion is found again on
+/// every run; a change is only logged when it differs from the latest entry
+/// for that element and pattern (new status or new evidence). Returns the
+/// number of entries appended.
+pub fn record(
+    root: &Path,
+    changes: &[StatusChange],
+    timestamp: &str,
+    tool_version: &str,
+) -> Result<usize, String> {
+    let logged = load(root)?;
+    let fresh: Vec<&StatusChange> = changes
+        .iter()
+        .filter(|change| {
+            let latest = logged
+                .iter()
+                .rev()
+                .find(|r| r.change.element == change.element && r.change.pattern == change.pattern);
+            latest.is_none_or(|r| r.change.to != change.to || r.change.evidence != change.evidence)
+        })
+        .collect();
+    if fresh.is_empty() {
+        return Ok(0);
+    }
+
+    let path = root.join(PROMOTIONS_LOG);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("failed to open {}: {}", path.display(), e))?;
+    for change in &fresh {
+        let entry = PromotionRecord {
+            timestamp: timestamp.to_string(),
+            tool_version: tool_version.to_string(),
+            change: (*change).clone(),
+        };
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| format!("failed to serialize promotion: {}", e))?;
+        writeln!(file, "{}", line).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    }
+    Ok(fresh.len())
+}
+
+/// Read the audit log, oldest first. A missing file means no entries;
+/// unreadable lines are skipped.
+pub fn load(root: &Path) -> Result<Vec<PromotionRecord>, String> {
+    let path = root.join(PROMOTIONS_LOG);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let text = fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    Ok(text
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{PatternEvidence, PatternStatus};
+
+    fn promotion(element: &str, item: &str) -> StatusChange {
+        StatusChange {
+            element: element.to_string(),
+            pattern: "Strategy".to_string(),
+            from: PatternStatus::Planned,
+            to: PatternStatus::Verified,
+            evidence: Some(PatternEvidence {
+                file: "calc.rs".to_string(),
+                item: item.to_string(),
+            }),
+        }
+    }
+
+    #[test]
+    fn appends_entries_with_timestamp_and_version() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        let written = record(tmp.path(), &[promotion("bus.calc", "Indicator")], "2026-10-01T09:30:00Z", "0.3.0").unwrap();
+
+        assert_eq!(written, 1);
+        let log = fs::read_to_string(tmp.path().join(PROMOTIONS_LOG)).unwrap();
+        assert_eq!(
+            log,
+            "{\"timestamp\":\"2026-10-01T09:30:00Z\",\"tool_version\":\"0.3.0\",\"element\":\"bus.calc\",\
+             \"pattern\":\"Strategy\",\"from\":\"planned\",\"to\":\"verified\",\
+             \"evidence\":{\"file\":\"calc.rs\",\"item\":\"Indicator\"}}\n"
+        );
+    }
+
+    #[test]
+    fn repeated_promotions_are_logged_once_until_evidence_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        record(tmp.path(), &[promotion("bus.calc", "Indicator")], "2026-10-01T09:30:00Z", "0.3.0").unwrap();
+
+        let again = record(tmp.path(), &[promotion("bus.calc", "Indicator")], "2026-10-02T09:30:00Z", "0.3.0").unwrap();
+        let moved = record(
+            tmp.path(),
+            &[promotion("bus.calc", "Scorer"), promotion("api.auth", "Login")],
+            "2026-10-03T09:30:00Z",
+            "0.3.1",
+        )
+        .unwrap();
+
+        assert_eq!((again, moved), (0, 2));
+        let log = load(tmp.path()).unwrap();
+        assert_eq!(log.len(), 3);
+        assert_eq!(log[1].change, promotion("bus.calc", "Scorer"));
+        assert_eq!(log[1].tool_version, "0.3.1");
+    }
+}
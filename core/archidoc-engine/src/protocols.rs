@@ -0,0 +1,98 @@
+use archidoc_types::{ModuleDoc, ProtocolConfig};
+
+/// Rewrite every relationship protocol to its catalog spelling and report
+/// the ones the catalog does not know.
+///
+/// A protocol matching an alias or an `allowed` entry, ignoring case,
+/// becomes that entry. Unknown protocols are left as written and, when the
+/// catalog has an `allowed` list, produce one warning each. Relationships
+/// without a protocol are skipped.
+pub fn normalize_protocols(docs: &mut [ModuleDoc], config: &ProtocolConfig) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for doc in docs.iter_mut() {
+        for rel in &mut doc.relationships {
+            if rel.protocol.is_empty() {
+                continue;
+            }
+            match canonical(&rel.protocol, config) {
+                Some(name) => rel.protocol = name.to_string(),
+                None if !config.allowed.is_empty() => warnings.push(format!(
+                    "{}: unknown protocol '{}' in relationship to '{}' (allowed: {})",
+                    doc.module_path,
+                    rel.protocol,
+                    rel.target,
+                    config.allowed.join(", ")
+                )),
+                None => {}
+            }
+        }
+    }
+    warnings
+}
+
+/// Catalog spelling of `protocol`, through an alias or an allowed entry.
+fn canonical<'a>(protocol: &str, config: &'a ProtocolConfig) -> Option<&'a str> {
+    let protocol = protocol.trim();
+    let in_catalog = |name: &str| config.allowed.iter().find(|a| a.eq_ignore_ascii_case(name)).map(String::as_str);
+    match config.aliases.iter().find(|(alias, _)| alias.eq_ignore_ascii_case(protocol)) {
+        Some((_, target)) => in_catalog(target).or(Some(target.as_str())),
+        None => in_catalog(protocol),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{ElementKind, Relationship};
+
+    fn doc_with_protocols(protocols: &[&str]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: "api".to_string(),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            relationships: protocols
+                .iter()
+                .map(|p| Relationship {
+                    target: "db".to_string(),
+                    label: "Reads".to_string(),
+                    protocol: p.to_string(),
+                    hidden: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn protocols_take_catalog_spelling_and_unknown_ones_warn() {
+        let config = ProtocolConfig {
+            allowed: vec!["sqlx".to_string(), "HTTP".to_string()],
+            aliases: [("https".to_string(), "HTTP".to_string())].into_iter().collect(),
+        };
+        let mut docs = vec![doc_with_protocols(&["SQLx", "https", "http", "kafka", ""])];
+
+        let warnings = normalize_protocols(&mut docs, &config);
+
+        let protocols: Vec<&str> = docs[0].relationships.iter().map(|r| r.protocol.as_str()).collect();
+        assert_eq!(protocols, vec!["sqlx", "HTTP", "HTTP", "kafka", ""]);
+        assert_eq!(
+            warnings,
+            vec!["api: unknown protocol 'kafka' in relationship to 'db' (allowed: sqlx, HTTP)"]
+        );
+    }
+
+    #[test]
+    fn aliases_apply_without_a_catalog() {
+        let config = ProtocolConfig {
+            allowed: vec![],
+            aliases: [("mpsc".to_string(), "channel".to_string())].into_iter().collect(),
+        };
+        let mut docs = vec![doc_with_protocols(&["MPSC", "kafka"])];
+
+        let warnings = normalize_protocols(&mut docs, &config);
+
+        assert_eq!(docs[0].relationships[0].protocol, "channel");
+        assert_eq!(docs[0].relationships[1].protocol, "kafka");
+        assert!(warnings.is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+use archidoc_types::{HealthStatus, ModuleDoc};
+
+/// A file-table change read back from an edited ARCHITECTURE.md.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEdit {
+    pub element: String,
+    /// The annotated file holding the element's file table.
+    pub source_file: String,
+    pub file: String,
+    /// The new purpose, when it was edited.
+    pub purpose: Option<String>,
+    /// The new health, when it was edited.
+    pub health: Option<HealthStatus>,
+}
+
+/// Compare the file tables under each element heading of `markdown` (see
+/// [`crate::architecture`]) with the annotations in `docs`, and return the
+/// purposes and health values that were edited.
+///
+/// Only those two cells are read back; everything else in the document is
+/// generated and ignored. A row for a file the element does not catalog, or
+/// a health other than `stable`, `active`, or `planned`, is an error.
+pub fn read_edits(docs: &[ModuleDoc], markdown: &str) -> Result<Vec<FileEdit>, String> {
+    let mut edits = Vec::new();
+    let mut current: Option<&ModuleDoc> = None;
+
+    for line in markdown.lines() {
+        if let Some(heading) = line.strip_prefix("### ") {
+            current = docs.iter().find(|d| d.module_path == heading.trim());
+            continue;
+        }
+        if line.starts_with("## ") {
+            current = None;
+            continue;
+        }
+        let Some(doc) = current else {
+            continue;
+        };
+        let cells = split_cells(line);
+        let Some(file) = cells.first().and_then(|c| c.strip_prefix('`')).and_then(|c| c.strip_suffix('`')) else {
+            continue;
+        };
+        if cells.len() < 4 {
+            continue;
+        }
+
+        let Some(entry) = doc.files.iter().find(|f| f.name == file) else {
+            return Err(format!(
+                "{}: `{}` is not in the element's file table (add new files in the source)",
+                doc.module_path, file
+            ));
+        };
+        let health = parse_health(&cells[3])
+            .ok_or_else(|| format!("{}: `{}` health '{}' is not stable, active, or planned", doc.module_path, file, cells[3]))?;
+        let edit = FileEdit {
+            element: doc.module_path.clone(),
+            source_file: doc.source_file.clone(),
+            file: file.to_string(),
+            purpose: (cells[2] != entry.purpose).then(|| cells[2].clone()),
+            health: (health != entry.health).then_some(health),
+        };
+        if edit.purpose.is_some() || edit.health.is_some() {
+            edits.push(edit);
+        }
+    }
+    Ok(edits)
+}
+
+/// Format the edits as human-readable text, one line per changed cell.
+pub fn format_edits(edits: &[FileEdit]) -> String {
+    if edits.is_empty() {
+        return "No edits to push back.\n".to_string();
+    }
+    let mut out = String::new();
+    for edit in edits {
+        if let Some(purpose) = &edit.purpose {
+            out.push_str(&format!("{} `{}` purpose: {}\n", edit.element, edit.file, purpose));
+        }
+        if let Some(health) = edit.health {
+            out.push_str(&format!("{} `{}` health: {}\n", edit.element, edit.file, health));
+        }
+    }
+    out
+}
+
+/// `HealthStatus::parse` accepts anything; edits are held to the three names.
+fn parse_health(cell: &str) -> Option<HealthStatus> {
+    match cell.to_lowercase().as_str() {
+        "stable" | "active" | "planned" => Some(HealthStatus::parse(cell)),
+        _ => None,
+    }
+}
+
+/// The trimmed cells of a markdown table row, with `\|` unescaped; empty
+/// for any other line.
+fn split_cells(line: &str) -> Vec<String> {
+    let Some(inner) = line.trim().strip_prefix('|').and_then(|l| l.strip_suffix('|')) else {
+        return Vec::new();
+    };
+    let mut cells = vec![String::new()];
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                chars.next();
+                cells.last_mut().unwrap().push('|');
+            }
+            '|' => cells.push(String::new()),
+            _ => cells.last_mut().unwrap().push(c),
+        }
+    }
+    cells.iter().map(|c| c.trim().to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{ElementKind, FileEntry, PatternStatus};
+    use std::path::Path;
+
+    fn doc(path: &str, files: &[(&str, &str, HealthStatus)]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: format!("src/{}/mod.rs", path),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            description: "Routes events".to_string(),
+            files: files
+                .iter()
+                .map(|(name, purpose, health)| FileEntry {
+                    name: name.to_string(),
+                    pattern: "--".to_string(),
+                    pattern_status: PatternStatus::Planned,
+                    purpose: purpose.to_string(),
+                    health: *health,
+                    loc: None,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn edited_purposes_and_health_are_read_back_from_the_generated_tables() {
+        let docs = vec![
+            doc("api", &[("routes.rs", "HTTP routes", HealthStatus::Active)]),
+            doc("bus", &[("lanes.rs", "Event lanes | queues", HealthStatus::Planned)]),
+        ];
+        let generated = crate::architecture::generate(&docs, Path::new("."));
+        assert!(read_edits(&docs, &generated).unwrap().is_empty());
+
+        let edited = generated
+            .replace("| HTTP routes | active |", "| Public HTTP routes | stable |")
+            .replace("| planned |", "| active |");
+        let edits = read_edits(&docs, &edited).unwrap();
+
+        assert_eq!(
+            edits,
+            vec![
+                FileEdit {
+                    element: "api".to_string(),
+                    source_file: "src/api/mod.rs".to_string(),
+                    file: "routes.rs".to_string(),
+                    purpose: Some("Public HTTP routes".to_string()),
+                    health: Some(HealthStatus::Stable),
+                },
+                FileEdit {
+                    element: "bus".to_string(),
+                    source_file: "src/bus/mod.rs".to_string(),
+                    file: "lanes.rs".to_string(),
+                    purpose: None,
+                    health: Some(HealthStatus::Active),
+                },
+            ]
+        );
+        assert_eq!(
+            format_edits(&edits),
+            "api `routes.rs` purpose: Public HTTP routes\napi `routes.rs` health: stable\nbus `lanes.rs` health: active\n"
+        );
+    }
+
+    #[test]
+    fn unknown_files_and_health_values_are_errors() {
+        let docs = vec![doc("api", &[("routes.rs", "HTTP routes", HealthStatus::Active)])];
+        let generated = crate::architecture::generate(&docs, Path::new("."));
+
+        assert_eq!(
+            read_edits(&docs, &generated.replace("| active |", "| done |")),
+            Err("api: `routes.rs` health 'done' is not stable, active, or planned".to_string())
+        );
+        assert!(read_edits(&docs, &generated.replace("`routes.rs`", "`handlers.rs`"))
+            .unwrap_err()
+            .starts_with("api: `handlers.rs` is not in the element's file table"));
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use archidoc_types::{ElementKind, ModuleDoc};
+
+use crate::front_matter::{self, FrontMatterSettings};
+
+/// First line of every generated module README, after any front matter.
+/// Files without it are treated as hand-written and never overwritten.
+pub const GENERATED_MARKER: &str =
+    "<!-- Generated by archidoc scaffold-readmes from module annotations. Do not edit manually. -->";
+
+/// Result of writing per-module READMEs.
+#[derive(Debug, Default)]
+pub struct ScaffoldOutcome {
+    /// READMEs created or rewritten.
+    pub written: Vec<PathBuf>,
+    /// Generated READMEs already up to date.
+    pub unchanged: Vec<PathBuf>,
+    /// Existing hand-written READMEs left untouched.
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Generate the README for one module directory.
+///
+/// Summarizes the module's purpose, pattern, file catalog, outgoing
+/// dependencies, documentation links, and incoming dependents from the
+/// rest of `docs`.
+pub fn generate_readme(doc: &ModuleDoc, docs: &[ModuleDoc]) -> String {
+    let mut out = String::new();
+
+    out.push_str(GENERATED_MARKER);
+    out.push_str("\n\n");
+    out.push_str(&format!("# {}\n\n", doc.module_path));
+    out.push_str(&format!("{}\n\n", doc.description));
+
+    out.push_str(&format!("- **Level:** {}\n", doc.c4_level));
+    if doc.pattern != "--" {
+        out.push_str(&format!(
+            "- **Pattern:** {} ({})\n",
+            doc.pattern, doc.pattern_status
+        ));
+    }
+    if let Some(parent) = &doc.parent_container {
+        out.push_str(&format!("- **Part of:** `{}`\n", parent));
+    }
+    out.push('\n');
+
+    if !doc.files.is_empty() {
+        out.push_str("## Files\n\n");
+        out.push_str("| File | Pattern | Purpose | Health |\n");
+        out.push_str("|------|---------|---------|--------|\n");
+        for file in &doc.files {
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                file.name,
+                file.pattern,
+                file.purpose.replace('|', "\\|"),
+                file.health
+            ));
+        }
+        out.push('\n');
+    }
+
+    if !doc.relationships.is_empty() {
+        out.push_str("## Dependencies\n\n");
+        for rel in &doc.relationships {
+            if rel.protocol.is_empty() {
+                out.push_str(&format!("- `{}`: {}\n", rel.target, rel.label));
+            } else {
+                out.push_str(&format!(
+                    "- `{}`: {} ({})\n",
+                    rel.target, rel.label, rel.protocol
+                ));
+            }
+        }
+        out.push('\n');
+    }
+
+    let links: Vec<String> = doc
+        .links
+        .iter()
+        .filter(|l| l.has_valid_url())
+        .map(|l| format!("- [{}]({})\n", l.text(), l.url))
+        .collect();
+    if !links.is_empty() {
+        out.push_str("## Links\n\n");
+        out.push_str(&links.concat());
+        out.push('\n');
+    }
+
+    let dependents: Vec<(&str, &str)> = docs
+        .iter()
+        .flat_map(|other| {
+            other
+                .relationships
+                .iter()
+                .filter(|rel| rel.target == doc.module_path)
+                .map(move |rel| (other.module_path.as_str(), rel.label.as_str()))
+        })
+        .collect();
+    if !dependents.is_empty() {
+        out.push_str("## Used By\n\n");
+        for (source, label) in dependents {
+            out.push_str(&format!("- `{}`: {}\n", source, label));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Write or update `README.md` in each annotated module directory.
+///
+/// Crate roots are skipped (their directory holds the project README), as
+/// are directories claimed by more than one module, which happens when a
+/// flat module file has no sibling directory. Existing READMEs without the
+/// generated marker are left untouched.
+///
+/// With `front_matter`, each README starts with YAML front matter; READMEs
+/// take the weights after ARCHITECTURE.md's, in module path order.
+pub fn scaffold_readmes(
+    docs: &[ModuleDoc],
+    front_matter: Option<&FrontMatterSettings>,
+) -> Result<ScaffoldOutcome, String> {
+    let mut outcome = ScaffoldOutcome::default();
+
+    let mut targets: Vec<(&ModuleDoc, PathBuf)> = docs
+        .iter()
+        .filter(|d| !d.module_path.starts_with('_') && d.c4_level != ElementKind::Unknown)
+        .filter_map(|d| d.catalog_dir().map(|dir| (d, dir)))
+        .collect();
+    targets.sort_by(|a, b| a.0.module_path.cmp(&b.0.module_path));
+    let ir_hash = front_matter.map(|_| front_matter::ir_hash(docs)).unwrap_or_default();
+
+    let mut seen = BTreeSet::new();
+    let shared: BTreeSet<PathBuf> = targets
+        .iter()
+        .filter(|(_, dir)| !seen.insert(dir.clone()))
+        .map(|(_, dir)| dir.clone())
+        .collect();
+
+    for (weight, (doc, dir)) in (1..).zip(targets) {
+        if shared.contains(&dir) || !dir.is_dir() {
+            continue;
+        }
+
+        let path = dir.join("README.md");
+        let mut content = generate_readme(doc, docs);
+        if let Some(settings) = front_matter {
+            let header = front_matter::render(&doc.module_path, &ir_hash, settings.weight + weight, settings);
+            content.insert_str(0, &header);
+        }
+        write_readme(&path, &content, &mut outcome)?;
+    }
+
+    Ok(outcome)
+}
+
+fn write_readme(path: &Path, content: &str, outcome: &mut ScaffoldOutcome) -> Result<(), String> {
+    if let Ok(existing) = fs::read_to_string(path) {
+        if !front_matter::strip(&existing).starts_with(GENERATED_MARKER) {
+            outcome.skipped.push(path.to_path_buf());
+            return Ok(());
+        }
+        if front_matter::without_timestamp(&existing) == front_matter::without_timestamp(content) {
+            outcome.unchanged.push(path.to_path_buf());
+            return Ok(());
+        }
+    }
+
+    fs::write(path, content).map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    outcome.written.push(path.to_path_buf());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::Relationship;
+
+    fn doc(dir: &Path, path: &str) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: dir.join(path).join("mod.rs").to_string_lossy().to_string(),
+            c4_level: ElementKind::Container,
+            pattern: "Mediator".to_string(),
+            description: format!("{} module", path),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn readme_lists_dependencies_both_ways() {
+        let root = Path::new("src");
+        let mut api = doc(root, "api");
+        api.relationships.push(Relationship {
+            target: "bus".to_string(),
+            label: "Publishes requests".to_string(),
+            protocol: "crossbeam".to_string(),
+            hidden: false,
+        });
+        let bus = doc(root, "bus");
+        let docs = vec![api.clone(), bus.clone()];
+
+        let api_readme = generate_readme(&api, &docs);
+        let bus_readme = generate_readme(&bus, &docs);
+
+        assert!(api_readme.starts_with(GENERATED_MARKER));
+        assert!(api_readme.contains("- `bus`: Publishes requests (crossbeam)"));
+        assert!(bus_readme.contains("## Used By\n\n- `api`: Publishes requests"));
+        assert!(bus_readme.contains("- **Pattern:** Mediator (planned)"));
+    }
+
+    #[test]
+    fn hand_written_readmes_are_kept() {
+        let tmp = tempfile::tempdir().unwrap();
+        for name in ["api", "bus"] {
+            fs::create_dir_all(tmp.path().join(name)).unwrap();
+        }
+        fs::write(tmp.path().join("bus/README.md"), "# Bus\n\nHand-written.\n").unwrap();
+        let docs = vec![doc(tmp.path(), "api"), doc(tmp.path(), "bus")];
+
+        let first = scaffold_readmes(&docs, None).unwrap();
+        let second = scaffold_readmes(&docs, None).unwrap();
+
+        assert_eq!(first.written, vec![tmp.path().join("api/README.md")]);
+        assert_eq!(first.skipped, vec![tmp.path().join("bus/README.md")]);
+        assert_eq!(second.unchanged, vec![tmp.path().join("api/README.md")]);
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("bus/README.md")).unwrap(),
+            "# Bus\n\nHand-written.\n"
+        );
+    }
+
+    #[test]
+    fn front_matter_is_prepended_and_ignores_the_timestamp() {
+        let tmp = tempfile::tempdir().unwrap();
+        for name in ["api", "bus"] {
+            fs::create_dir_all(tmp.path().join(name)).unwrap();
+        }
+        let docs = vec![doc(tmp.path(), "bus"), doc(tmp.path(), "api")];
+        let settings = |at: &str| FrontMatterSettings {
+            weight: 10,
+            generated_at: Some(at.to_string()),
+        };
+
+        let first = scaffold_readmes(&docs, Some(&settings("2026-10-01T09:30:00Z"))).unwrap();
+        let second = scaffold_readmes(&docs, Some(&settings("2026-10-02T11:00:00Z"))).unwrap();
+
+        assert_eq!(first.written.len(), 2);
+        assert_eq!(second.unchanged.len(), 2);
+        let api = fs::read_to_string(tmp.path().join("api/README.md")).unwrap();
+        let bus = fs::read_to_string(tmp.path().join("bus/README.md")).unwrap();
+        assert!(api.starts_with("---\ntitle: \"api\"\ngenerated_at: 2026-10-01T09:30:00Z\n"), "{}", api);
+        assert!(api.contains("\nweight: 11\n---\n\n"));
+        assert!(bus.contains("\nweight: 12\n---\n\n"));
+        assert!(front_matter::strip(&api).starts_with(GENERATED_MARKER));
+    }
+}
@@ -0,0 +1,234 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use archidoc_types::{OutputFile, PluginConfig, RenderConfig, RenderFormat};
+
+/// Images written by [`render`], and the renderers it had to skip.
+#[derive(Debug, Default)]
+pub struct Rendered {
+    pub files: Vec<OutputFile>,
+    pub warnings: Vec<String>,
+}
+
+/// Render the diagram files among `outputs` into `formats`, next to them:
+/// each Mermaid block of the `mermaid/` pages with the Mermaid CLI
+/// (`mermaid/c4-container.md` -> `mermaid/c4-container.svg`, further
+/// blocks of the same page numbered from `-2`), and each `.puml` file with
+/// PlantUML. Commands run in `root`; a renderer that is not installed is
+/// skipped with a warning, like the cargo-modules integration, while one
+/// that fails is an error. Without any diagram files to render, there is
+/// a warning instead.
+pub fn render(
+    outputs: &[OutputFile],
+    out_dir: &Path,
+    formats: &[RenderFormat],
+    config: &RenderConfig,
+    root: &Path,
+) -> Result<Rendered, String> {
+    let mut rendered = Rendered::default();
+    if formats.is_empty() {
+        return Ok(rendered);
+    }
+    let mut mermaid = Renderer::new("Mermaid CLI", &config.mermaid, root);
+    let mut plantuml = Renderer::new("PlantUML", &config.plantuml, root);
+    let mut sources = 0;
+
+    for output in outputs {
+        let path = PathBuf::from(&output.path);
+        let relative = path.strip_prefix(out_dir).unwrap_or(&path);
+        let is_mermaid_page = relative.starts_with("mermaid") && relative.extension().is_some_and(|e| e == "md");
+        let is_puml = relative.extension().is_some_and(|e| e == "puml");
+        if !is_mermaid_page && !is_puml {
+            continue;
+        }
+        sources += 1;
+
+        for &format in formats {
+            if is_puml {
+                let image = path.with_extension(format.extension());
+                let flag = format!("-t{}", format.extension());
+                if plantuml.run(&[flag.as_ref(), path.as_os_str()], relative)? {
+                    rendered.files.push(written(&image)?);
+                }
+                continue;
+            }
+
+            let markdown = fs::read_to_string(&path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+            for (n, block) in mermaid_blocks(&markdown).iter().enumerate() {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                let name = match n {
+                    0 => format!("{}.{}", stem, format.extension()),
+                    n => format!("{}-{}.{}", stem, n + 1, format.extension()),
+                };
+                let image = path.with_file_name(name);
+                let source = std::env::temp_dir().join(format!("archidoc-{}-{}-{}.mmd", std::process::id(), stem, n + 1));
+                fs::write(&source, block).map_err(|e| format!("failed to write {}: {}", source.display(), e))?;
+                let result = mermaid.run(&["-i".as_ref(), source.as_os_str(), "-o".as_ref(), image.as_os_str()], relative);
+                let _ = fs::remove_file(&source);
+                if result? {
+                    rendered.files.push(written(&image)?);
+                }
+            }
+        }
+    }
+
+    if sources == 0 {
+        rendered.warnings.push(
+            "no diagram files to render; enable the `mermaid` generator or pass --plantuml".to_string(),
+        );
+    }
+    rendered.warnings.extend(mermaid.missing);
+    rendered.warnings.extend(plantuml.missing);
+    Ok(rendered)
+}
+
+/// A renderer command; once it turns out not to be installed, later runs
+/// are skipped.
+struct Renderer<'a> {
+    name: &'static str,
+    config: &'a PluginConfig,
+    command: PathBuf,
+    root: &'a Path,
+    missing: Option<String>,
+}
+
+impl<'a> Renderer<'a> {
+    fn new(name: &'static str, config: &'a PluginConfig, root: &'a Path) -> Self {
+        Self {
+            name,
+            config,
+            command: crate::plugin::resolve_command(&config.command, root),
+            root,
+            missing: None,
+        }
+    }
+
+    /// Run the command with `args` after the configured ones. Returns
+    /// whether it ran, i.e. whether the renderer is installed.
+    fn run(&mut self, args: &[&std::ffi::OsStr], diagram: &Path) -> Result<bool, String> {
+        if self.missing.is_some() {
+            return Ok(false);
+        }
+        let output = match Command::new(&self.command).args(&self.config.args).args(args).current_dir(self.root).output() {
+            Ok(output) => output,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                self.missing = Some(format!(
+                    "{} ({}) is not installed; diagrams were not rendered",
+                    self.name, self.config.command
+                ));
+                return Ok(false);
+            }
+            Err(e) => return Err(format!("failed to run {} ({}): {}", self.name, self.config.command, e)),
+        };
+        if !output.status.success() {
+            return Err(format!(
+                "{} failed on {} ({}): {}",
+                self.name,
+                diagram.display(),
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(true)
+    }
+}
+
+/// The bodies of the ```` ```mermaid ```` blocks in `markdown`.
+fn mermaid_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut block: Option<String> = None;
+    for line in markdown.lines() {
+        match block.as_mut() {
+            None if line.trim() == "```mermaid" => block = Some(String::new()),
+            None => {}
+            Some(_) if line.trim() == "```" => blocks.extend(block.take()),
+            Some(body) => {
+                body.push_str(line);
+                body.push('\n');
+            }
+        }
+    }
+    blocks
+}
+
+fn written(image: &Path) -> Result<OutputFile, String> {
+    let content = fs::read(image).map_err(|e| format!("renderer did not write {}: {}", image.display(), e))?;
+    Ok(OutputFile {
+        path: image.to_string_lossy().to_string(),
+        bytes: content.len(),
+        hash: crate::hash::bytes_hash(&content),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(dir: &Path, path: &str, content: &str) -> OutputFile {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        OutputFile {
+            path: path.to_string_lossy().to_string(),
+            bytes: content.len(),
+            hash: String::new(),
+        }
+    }
+
+    fn shell(script: &str) -> PluginConfig {
+        PluginConfig {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string(), "sh".to_string()],
+        }
+    }
+
+    #[test]
+    fn mermaid_blocks_and_plantuml_files_become_images_beside_them() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let outputs = vec![
+            output(tmp.path(), "ARCHITECTURE.md", "```mermaid\nC4Context\n```\n"),
+            output(tmp.path(), "mermaid/c4-container.md", "# C4\n\n```mermaid\nC4Container\n```\n\n```mermaid\nC4Component\n```\n"),
+            output(tmp.path(), "c4/c4-container.puml", "@startuml\n@enduml\n"),
+        ];
+        let config = RenderConfig {
+            formats: vec![],
+            // mmdc -i <source> -o <image>: copy the source to the image
+            mermaid: shell(r#"cp "$2" "$4""#),
+            // plantuml -t<format> <file>: write the file with the format as its extension
+            plantuml: shell(r#"cp "$2" "${2%.puml}.${1#-t}""#),
+        };
+
+        let rendered = render(&outputs, tmp.path(), &[RenderFormat::Svg], &config, tmp.path()).unwrap();
+
+        let names: Vec<String> = rendered
+            .files
+            .iter()
+            .map(|f| Path::new(&f.path).strip_prefix(tmp.path()).unwrap().display().to_string())
+            .collect();
+        assert_eq!(names, ["mermaid/c4-container.svg", "mermaid/c4-container-2.svg", "c4/c4-container.svg"]);
+        assert_eq!(fs::read_to_string(tmp.path().join("mermaid/c4-container-2.svg")).unwrap(), "C4Component\n");
+        assert!(rendered.warnings.is_empty());
+    }
+
+    #[test]
+    fn missing_renderers_are_skipped_with_a_warning_and_failures_are_errors() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let outputs = vec![output(tmp.path(), "c4/c4-component.puml", "@startuml\n@enduml\n")];
+        let mut config = RenderConfig::default();
+        config.plantuml.command = "archidoc-no-such-renderer".to_string();
+
+        let rendered = render(&outputs, tmp.path(), &[RenderFormat::Svg, RenderFormat::Png], &config, tmp.path()).unwrap();
+        assert!(rendered.files.is_empty());
+        assert_eq!(
+            rendered.warnings,
+            ["PlantUML (archidoc-no-such-renderer) is not installed; diagrams were not rendered"]
+        );
+
+        config.plantuml = shell("echo 'syntax error' >&2; exit 1");
+        let err = render(&outputs, tmp.path(), &[RenderFormat::Png], &config, tmp.path()).unwrap_err();
+        assert!(err.starts_with("PlantUML failed on c4/c4-component.puml"), "{}", err);
+        assert!(err.ends_with("syntax error"));
+    }
+}
@@ -0,0 +1,61 @@
+use std::path::Path;
+
+use archidoc_types::{CompileReport, ModuleDoc};
+
+/// Sort `docs` by module path and make their source files relative to
+/// `root`, so the IR and everything generated from it is the same wherever
+/// the project is checked out.
+///
+/// Sources outside `root` keep their path.
+pub fn normalize_docs(docs: &mut [ModuleDoc], root: &Path) {
+    docs.sort_by(|a, b| a.module_path.cmp(&b.module_path));
+    for doc in docs.iter_mut() {
+        doc.source_file = relative(&doc.source_file, root);
+    }
+}
+
+/// Zero the phase timings of `report` and make its output paths relative
+/// to `root`.
+pub fn normalize_report(report: &mut CompileReport, root: &Path) {
+    for phase in &mut report.phases {
+        phase.duration_ms = 0.0;
+    }
+    for output in &mut report.outputs {
+        output.path = relative(&output.path, root);
+    }
+}
+
+/// `path` relative to `root` with `/` separators, or unchanged when it is
+/// not under `root`.
+pub(crate) fn relative(path: &str, root: &Path) -> String {
+    Path::new(path)
+        .strip_prefix(root)
+        .map(|rel| rel.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_else(|_| path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{OutputFile, PhaseTiming};
+
+    #[test]
+    fn paths_become_relative_and_timings_zero() {
+        let mut report = CompileReport::default();
+        report.phases.push(PhaseTiming {
+            phase: "generate".to_string(),
+            duration_ms: 12.5,
+        });
+        report.outputs.push(OutputFile {
+            path: "/tmp/run-1/docs/ARCHITECTURE.md".to_string(),
+            bytes: 10,
+            hash: "00ff".to_string(),
+        });
+
+        normalize_report(&mut report, Path::new("/tmp/run-1"));
+
+        assert_eq!(report.phases[0].duration_ms, 0.0);
+        assert_eq!(report.outputs[0].path, "docs/ARCHITECTURE.md");
+        assert_eq!(relative("/elsewhere/lib.rs", Path::new("/tmp/run-1")), "/elsewhere/lib.rs");
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+
+use archidoc_types::{ModuleDoc, RewriteConfig};
+
+/// A parsed `"from -> to"` rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rule<'a> {
+    /// `from` and its descendants move under `to`.
+    Rename(&'a str, &'a str),
+    /// Every path matching the `*` pattern becomes `to`.
+    Collapse(&'a str, &'a str),
+}
+
+/// Split a `"from -> to"` rule into its two trimmed sides, or `None` when it
+/// is malformed.
+pub fn parse_rule(entry: &str) -> Option<(&str, &str)> {
+    let (from, to) = entry.split_once("->")?;
+    let (from, to) = (from.trim(), to.trim());
+    let valid = |side: &str| !side.is_empty() && !side.contains(char::is_whitespace);
+    (valid(from) && valid(to)).then_some((from, to))
+}
+
+/// Apply the `[rewrite]` rules to every module path, parent container, and
+/// relationship target.
+///
+/// Elements that end up on the same path are merged into one: the element
+/// already at that path (or else the first one) keeps its annotation, and
+/// the others add their relationships, files, risks, terms, and links.
+/// Relationships that now point at their own element are dropped. Returns
+/// a warning for each rule that matched no element.
+pub fn rewrite_paths(docs: &mut Vec<ModuleDoc>, config: &RewriteConfig) -> Vec<String> {
+    let rules: Vec<Rule> = config
+        .rename
+        .iter()
+        .filter_map(|entry| parse_rule(entry).map(|(from, to)| Rule::Rename(from, to)))
+        .chain(
+            config
+                .collapse
+                .iter()
+                .filter_map(|entry| parse_rule(entry).map(|(from, to)| Rule::Collapse(from, to))),
+        )
+        .collect();
+    if rules.is_empty() {
+        return Vec::new();
+    }
+
+    let mut used = vec![false; rules.len()];
+    let mut rewrite = |path: &str, track: bool| {
+        let mut path = path.to_string();
+        for (i, rule) in rules.iter().enumerate() {
+            if let Some(new) = apply(*rule, &path) {
+                used[i] |= track;
+                path = new;
+            }
+        }
+        path
+    };
+
+    let mut merged: BTreeMap<String, ModuleDoc> = BTreeMap::new();
+    for mut doc in std::mem::take(docs) {
+        let original = doc.module_path.clone();
+        doc.module_path = rewrite(&doc.module_path, true);
+        doc.parent_container = doc.parent_container.map(|p| rewrite(&p, false));
+        for rel in &mut doc.relationships {
+            rel.target = rewrite(&rel.target, false);
+        }
+
+        match merged.remove(&doc.module_path) {
+            None => {
+                merged.insert(doc.module_path.clone(), doc);
+            }
+            Some(existing) => {
+                // The element that already lived at this path leads
+                let (mut base, other) = if original == doc.module_path { (doc, existing) } else { (existing, doc) };
+                absorb(&mut base, other);
+                merged.insert(base.module_path.clone(), base);
+            }
+        }
+    }
+
+    for doc in merged.values_mut() {
+        let path = doc.module_path.clone();
+        let mut seen = Vec::new();
+        doc.relationships.retain(|rel| {
+            let key = (rel.target.clone(), rel.label.clone(), rel.protocol.clone());
+            rel.target != path && !seen.contains(&key) && {
+                seen.push(key);
+                true
+            }
+        });
+        if doc.parent_container.as_deref() == Some(path.as_str()) {
+            doc.parent_container = None;
+        }
+    }
+    *docs = merged.into_values().collect();
+
+    let entries = config.rename.iter().chain(&config.collapse).filter(|entry| parse_rule(entry).is_some());
+    entries
+        .zip(used)
+        .filter(|(_, used)| !used)
+        .map(|(entry, _)| format!("[rewrite] rule '{}' matches no module", entry))
+        .collect()
+}
+
+/// The rewritten path, or `None` when `rule` does not match `path`.
+fn apply(rule: Rule, path: &str) -> Option<String> {
+    match rule {
+        Rule::Rename(from, to) if path == from => Some(to.to_string()),
+        Rule::Rename(from, to) => path
+            .strip_prefix(from)
+            .and_then(|rest| rest.strip_prefix('.'))
+            .map(|rest| format!("{}.{}", to, rest)),
+        Rule::Collapse(pattern, to) => (path != to && matches(pattern, path)).then(|| to.to_string()),
+    }
+}
+
+/// Whether `path` matches `pattern`, where `*` stands for any run of
+/// characters (dots included).
+fn matches(pattern: &str, path: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = path.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+fn absorb(base: &mut ModuleDoc, other: ModuleDoc) {
+    base.relationships.extend(other.relationships);
+    base.files.extend(other.files);
+    base.risks.extend(other.risks);
+    base.terms.extend(other.terms);
+    base.links.extend(other.links);
+    base.unsafe_count += other.unsafe_count;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{ElementKind, Relationship};
+
+    fn doc(path: &str, uses: &[&str]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            description: format!("The {} module", path),
+            parent_container: path.rsplit_once('.').map(|(parent, _)| parent.to_string()),
+            relationships: uses
+                .iter()
+                .map(|target| Relationship {
+                    target: target.to_string(),
+                    label: "Calls".to_string(),
+                    protocol: String::new(),
+                    hidden: false,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn config(rename: &[&str], collapse: &[&str]) -> RewriteConfig {
+        RewriteConfig {
+            rename: rename.iter().map(|r| r.to_string()).collect(),
+            collapse: collapse.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    fn paths(docs: &[ModuleDoc]) -> Vec<&str> {
+        docs.iter().map(|d| d.module_path.as_str()).collect()
+    }
+
+    #[test]
+    fn rename_moves_a_module_and_its_descendants() {
+        let mut docs = vec![
+            doc("api", &["legacy_core.store"]),
+            doc("legacy_core", &[]),
+            doc("legacy_core.store", &[]),
+            doc("legacy_core_ext", &[]),
+        ];
+
+        let warnings = rewrite_paths(&mut docs, &config(&["legacy_core -> core"], &[]));
+
+        assert!(warnings.is_empty());
+        assert_eq!(paths(&docs), vec!["api", "core", "core.store", "legacy_core_ext"]);
+        assert_eq!(docs[0].relationships[0].target, "core.store");
+        assert_eq!(docs[2].parent_container.as_deref(), Some("core"));
+    }
+
+    #[test]
+    fn collapse_folds_matching_modules_into_one() {
+        let mut docs = vec![
+            doc("api", &["utils.fs", "utils.time"]),
+            doc("utils", &[]),
+            doc("utils.fs", &["utils.time", "db"]),
+            doc("utils.time", &["db"]),
+        ];
+
+        let warnings = rewrite_paths(&mut docs, &config(&[], &["utils.* -> utils", "gone.* -> gone"]));
+
+        assert_eq!(warnings, vec!["[rewrite] rule 'gone.* -> gone' matches no module"]);
+        assert_eq!(paths(&docs), vec!["api", "utils"]);
+        assert_eq!(docs[1].description, "The utils module");
+        let targets = |doc: &ModuleDoc| doc.relationships.iter().map(|r| r.target.clone()).collect::<Vec<_>>();
+        assert_eq!(targets(&docs[0]), vec!["utils"]);
+        assert_eq!(targets(&docs[1]), vec!["db"]);
+    }
+
+    #[test]
+    fn patterns_and_rules() {
+        assert!(matches("utils.*", "utils.fs.path"));
+        assert!(!matches("utils.*", "utils"));
+        assert!(matches("*.tests", "api.tests"));
+        assert!(matches("a*b*c", "abc"));
+        assert!(!matches("a*b*c", "acb"));
+        assert_eq!(parse_rule(" legacy_core -> core "), Some(("legacy_core", "core")));
+        assert_eq!(parse_rule("legacy_core core"), None);
+        assert_eq!(parse_rule("legacy core -> core"), None);
+    }
+}
@@ -0,0 +1,80 @@
+use archidoc_types::{ModuleDoc, RiskEntry, RiskRegister};
+
+/// Collect every `@risk` annotation into a consolidated register.
+///
+/// Entries are ordered most severe first, then by element path, keeping
+/// declaration order within an element.
+pub fn collect_risks(docs: &[ModuleDoc]) -> RiskRegister {
+    let mut entries: Vec<RiskEntry> = docs
+        .iter()
+        .flat_map(|doc| {
+            doc.risks.iter().map(move |risk| RiskEntry {
+                element: doc.module_path.clone(),
+                description: risk.description.clone(),
+                severity: risk.severity,
+            })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| b.severity.cmp(&a.severity).then_with(|| a.element.cmp(&b.element)));
+
+    RiskRegister { entries }
+}
+
+/// Format a risk register as human-readable text.
+pub fn format_risk_register(register: &RiskRegister) -> String {
+    let mut out = String::new();
+
+    if register.entries.is_empty() {
+        out.push_str("No risks recorded.\n");
+        return out;
+    }
+
+    out.push_str(&format!("Risk register ({}):\n", register.entries.len()));
+    for entry in &register.entries {
+        out.push_str(&format!(
+            "  [{}] {} — {}\n",
+            entry.severity, entry.element, entry.description
+        ));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{ElementKind, Risk, RiskSeverity};
+
+    fn doc(path: &str, risks: &[(&str, RiskSeverity)]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: format!("src/{}/mod.rs", path),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            risks: risks
+                .iter()
+                .map(|(description, severity)| Risk {
+                    description: description.to_string(),
+                    severity: *severity,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn risks_are_ordered_by_severity() {
+        let docs = vec![
+            doc("api", &[("Rate limits untested", RiskSeverity::Low)]),
+            doc("bus", &[("Single point of failure", RiskSeverity::Critical)]),
+            doc("store", &[]),
+        ];
+
+        let register = collect_risks(&docs);
+
+        assert_eq!(register.entries.len(), 2);
+        assert_eq!(register.entries[0].element, "bus");
+        assert_eq!(register.entries[1].severity, RiskSeverity::Low);
+    }
+}
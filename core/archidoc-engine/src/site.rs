@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+
+use archidoc_types::ModuleDoc;
+
+use crate::architecture::{self, ArchitectureGenerator};
+
+/// ARCHITECTURE.md split into pages, for documentation sites that would
+/// rather not scroll through one long document.
+pub struct Site<'a> {
+    /// The document without its contents list, Component Index, and the
+    /// element sections that moved to container pages. Elements outside
+    /// any container stay here, under Elements.
+    pub index: String,
+    /// Each container and its page: a heading, the container's section, a
+    /// diagram of its dependencies, and an anchored section per component.
+    pub pages: Vec<(&'a ModuleDoc, String)>,
+    /// Element id to the container whose page holds the element; `None`
+    /// for elements on the index page.
+    pub homes: BTreeMap<String, Option<&'a ModuleDoc>>,
+}
+
+/// Split `document`'s rendering of `docs` into an index page and one page
+/// per container. Without element sections (`[architecture] skip`), there
+/// are no container pages.
+pub fn split<'a>(docs: &'a [ModuleDoc], document: &ArchitectureGenerator) -> Site<'a> {
+    let t = crate::i18n::strings(document.language);
+    let bodies = if document.layout.skip.iter().any(|s| s == "elements") {
+        Vec::new()
+    } else {
+        architecture::element_bodies(docs, &document.link_base, document.language)
+    };
+    let containers: Vec<&ModuleDoc> = bodies
+        .iter()
+        .map(|(doc, _)| *doc)
+        .filter(|doc| doc.c4_level.is_container())
+        .collect();
+    let home = |doc: &ModuleDoc| {
+        let container = architecture::container_of(doc);
+        containers.iter().copied().find(|c| c.module_path == container)
+    };
+
+    let mut layout = document.layout.clone();
+    layout.skip.extend(["contents", "component_index", "elements"].map(String::from));
+    let mut index = architecture::generate_document(
+        docs,
+        &document.link_base,
+        &document.fitness,
+        &document.snapshot,
+        document.language,
+        &layout,
+        &document.diagrams,
+    );
+    let loose: Vec<&(&ModuleDoc, String)> = bodies.iter().filter(|(doc, _)| home(doc).is_none()).collect();
+    if !loose.is_empty() {
+        let mut section = architecture::anchor_line("section-elements");
+        section.push_str(&format!("## {}\n\n", t.elements));
+        for (doc, body) in loose {
+            section.push_str(&architecture::anchor_line(&doc.element_id()));
+            section.push_str(&format!("### {}\n\n{}", doc.module_path, body));
+        }
+        architecture::insert_section(&mut index, "elements", &section);
+    }
+
+    let pages = containers
+        .iter()
+        .map(|container| {
+            let mut page = format!("# {}\n\n", container.module_path);
+            for (doc, body) in &bodies {
+                if doc.module_path == container.module_path {
+                    page.push_str(&format!("{}{}\n\n", body, crate::mermaid::element_diagram(docs, doc, &document.diagrams)));
+                }
+            }
+            for (doc, body) in &bodies {
+                if doc.module_path != container.module_path && home(doc).is_some_and(|c| c.module_path == container.module_path) {
+                    page.push_str(&architecture::anchor_line(&doc.element_id()));
+                    page.push_str(&format!("## {}\n\n{}", doc.module_path, body));
+                }
+            }
+            (*container, page)
+        })
+        .collect();
+    let homes = bodies.iter().map(|(doc, _)| (doc.element_id(), home(doc))).collect();
+
+    Site { index, pages, homes }
+}
+
+impl Site<'_> {
+    /// Where a link on the page of container `from` (`None` for the index
+    /// page) should point, given `path` from that page to the page of a
+    /// container (`None` for the index page). Element links go to the page
+    /// holding the element; other in-page anchors and outside URLs stay;
+    /// anything else, such as a source file, keeps only its text.
+    pub fn retarget(&self, target: &str, from: Option<&str>, path: &dyn Fn(Option<&str>) -> String) -> Option<String> {
+        if is_external(target) {
+            return Some(target.to_string());
+        }
+        let id = target.strip_prefix('#')?;
+        let Some(home) = self.homes.get(id) else {
+            return Some(target.to_string());
+        };
+        let page = home.map(|container| container.element_id());
+        if page.as_deref() == from {
+            return Some(target.to_string());
+        }
+        if page.as_deref() == Some(id) {
+            Some(path(page.as_deref()))
+        } else {
+            Some(format!("{}#{}", path(page.as_deref()), id))
+        }
+    }
+
+    /// Put a Component Index listing the container pages, linked through
+    /// `path` from the index page, where `document` has it.
+    pub fn insert_container_index(&self, index: &mut String, document: &ArchitectureGenerator, path: &dyn Fn(&str) -> String) {
+        if self.pages.is_empty() || document.layout.skip.iter().any(|s| s == "component_index") {
+            return;
+        }
+        let t = crate::i18n::strings(document.language);
+        let mut section = architecture::anchor_line("section-component-index");
+        section.push_str(&format!("## {}\n\n", t.component_index));
+        for (container, _) in &self.pages {
+            section.push_str(&format!(
+                "- [{}]({}) — {}\n",
+                container.module_path,
+                path(&container.element_id()),
+                container.description
+            ));
+        }
+        section.push('\n');
+        architecture::insert_section(index, "component_index", &section);
+    }
+}
+
+/// Rewrite the targets of Markdown links outside code: `retarget` gets
+/// each target and returns the new one, or `None` to keep only the link
+/// text.
+pub fn rewrite_links(markdown: &str, retarget: &dyn Fn(&str) -> Option<String>) -> String {
+    let mut out = String::new();
+    let mut fenced = false;
+    for line in markdown.split_inclusive('\n') {
+        if line.trim_start().starts_with("```") {
+            fenced = !fenced;
+        }
+        if fenced || line.trim_start().starts_with("```") {
+            out.push_str(line);
+            continue;
+        }
+        for (n, part) in line.split('`').enumerate() {
+            if n > 0 {
+                out.push('`');
+            }
+            if n % 2 == 1 {
+                out.push_str(part);
+            } else {
+                out.push_str(&rewrite_line(part, retarget));
+            }
+        }
+    }
+    out
+}
+
+fn rewrite_line(text: &str, retarget: &dyn Fn(&str) -> Option<String>) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        let after = &rest[open + 1..];
+        let link = after.find(']').and_then(|close| {
+            let target = after[close + 1..].strip_prefix('(')?;
+            let end = target.find(')')?;
+            Some((&after[..close], &target[..end], &target[end + 1..]))
+        });
+        let Some((label, target, tail)) = link else {
+            out.push_str(&rest[..=open]);
+            rest = after;
+            continue;
+        };
+        out.push_str(&rest[..open]);
+        match retarget(target) {
+            Some(target) => out.push_str(&format!("[{}]({})", label, target)),
+            None => out.push_str(label),
+        }
+        rest = tail;
+    }
+    out.push_str(rest);
+    out
+}
+
+/// A link target that leaves the site: a URL with a scheme or a mail
+/// address.
+pub fn is_external(target: &str) -> bool {
+    target.contains("://") || target.starts_with("mailto:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use archidoc_types::{ArchitectureConfig, DiagramConfig, ElementKind, Language};
+
+    fn doc(path: &str, kind: ElementKind, parent: Option<&str>) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            source_file: format!("src/{}/mod.rs", path.replace('.', "/")),
+            c4_level: kind,
+            pattern: "--".to_string(),
+            description: format!("The {} element", path),
+            parent_container: parent.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn document() -> ArchitectureGenerator {
+        ArchitectureGenerator {
+            file_name: "ARCHITECTURE.md".to_string(),
+            link_base: PathBuf::new(),
+            fitness: vec![],
+            snapshot: Default::default(),
+            language: Language::En,
+            layout: ArchitectureConfig::default(),
+            front_matter: None,
+            diagrams: DiagramConfig::default(),
+        }
+    }
+
+    #[test]
+    fn containers_get_pages_and_other_elements_stay_on_the_index() {
+        let docs = vec![
+            doc("bus", ElementKind::Container, None),
+            doc("bus.lanes", ElementKind::Component, Some("bus")),
+            doc("customer", ElementKind::Person, None),
+        ];
+
+        let site = split(&docs, &document());
+
+        assert_eq!(site.pages.len(), 1);
+        let (bus, page) = &site.pages[0];
+        assert_eq!(bus.module_path, "bus");
+        assert!(page.starts_with("# bus\n\nThe bus element\n"));
+        assert!(page.contains("```mermaid\nC4Component\n    title bus\n"));
+        assert!(page.contains("<a id=\"bus_lanes\"></a>\n\n## bus.lanes\n\nThe bus.lanes element\n"));
+        assert!(site.index.contains("<a id=\"customer\"></a>\n\n### customer\n"));
+        assert!(!site.index.contains("## Component Index") && !site.index.contains("### bus.lanes"));
+        assert_eq!(site.homes["bus_lanes"].map(|c| c.module_path.as_str()), Some("bus"));
+        assert_eq!(site.homes["customer"], None);
+
+        let mut layout = ArchitectureConfig::default();
+        layout.skip.push("elements".to_string());
+        assert!(split(&docs, &ArchitectureGenerator { layout, ..document() }).pages.is_empty());
+    }
+
+    #[test]
+    fn links_are_rewritten_outside_code() {
+        let markdown = "[a](#a) and [b] [c](src/c.rs)\n`[d](#d)` [e](https://e.io)\n```\n[f](#f)\n```\n";
+        let retarget = |target: &str| match target {
+            "#a" => Some("page.md#a".to_string()),
+            t if is_external(t) => Some(t.to_string()),
+            _ => None,
+        };
+
+        assert_eq!(
+            rewrite_links(markdown, &retarget),
+            "[a](page.md#a) and [b] c\n`[d](#d)` [e](https://e.io)\n```\n[f](#f)\n```\n"
+        );
+    }
+}
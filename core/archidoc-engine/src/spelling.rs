@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+
+use archidoc_types::{is_marker_line, LintFinding, ModuleDoc};
+
+/// Built-in `misspelling->correction` list, compiled in with the
+/// `spellcheck` feature (on by default).
+#[cfg(feature = "spellcheck")]
+const BUILTIN: &str = include_str!("../dictionary/misspellings.txt");
+#[cfg(not(feature = "spellcheck"))]
+const BUILTIN: &str = "";
+
+/// Misspellings the checker knows, each with its correction. Empty when
+/// built without the `spellcheck` feature.
+pub fn dictionary() -> BTreeMap<&'static str, &'static str> {
+    BUILTIN
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once("->"))
+        .collect()
+}
+
+/// Spell-check the prose of every element: its narrative and file table
+/// purposes, or its description and purposes when the IR carries no
+/// annotation content. Code spans, fenced code, URLs, markers, and words
+/// that look like identifiers are skipped, as is every word in `ignore`
+/// (case-insensitive).
+///
+/// Each finding names the source file and, when the file can be read, the
+/// line of the misspelling.
+pub fn check_spelling(docs: &[ModuleDoc], ignore: &[String]) -> Vec<LintFinding> {
+    let dictionary = dictionary();
+    let ignore: HashSet<String> = ignore.iter().map(|w| w.to_lowercase()).collect();
+    let mut findings = Vec::new();
+
+    for doc in docs {
+        let source = fs::read_to_string(&doc.source_file).ok();
+        let mut seen = HashSet::new();
+        for text in prose_lines(doc) {
+            for word in words(&text) {
+                let lower = word.to_lowercase();
+                let Some(correction) = dictionary.get(lower.as_str()) else {
+                    continue;
+                };
+                if ignore.contains(&lower) {
+                    continue;
+                }
+                let line = source.as_deref().and_then(|s| find_line(s, &text, word));
+                if !seen.insert((lower.clone(), line)) {
+                    continue;
+                }
+                findings.push(LintFinding {
+                    element: doc.module_path.clone(),
+                    rule: "spelling".to_string(),
+                    message: format!("'{}' should be '{}'", word, correction),
+                    file: (!doc.source_file.is_empty()).then(|| doc.source_file.clone()),
+                    line,
+                });
+            }
+        }
+    }
+    findings
+}
+
+/// Lines of prose to check for `doc`.
+fn prose_lines(doc: &ModuleDoc) -> Vec<String> {
+    if doc.content.is_empty() {
+        let mut lines = vec![doc.description.clone()];
+        lines.extend(doc.files.iter().map(|f| f.purpose.clone()));
+        return lines;
+    }
+
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    for line in doc.content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || is_marker_line(trimmed) {
+            continue;
+        }
+        lines.push(trimmed.to_string());
+    }
+    lines
+}
+
+/// Words of `text` outside code spans and URLs that could be misspelled:
+/// alphabetic, at least three letters, and not camel-cased.
+fn words(text: &str) -> Vec<&str> {
+    text.split('`')
+        .step_by(2)
+        .flat_map(str::split_whitespace)
+        .filter(|token| !token.contains("://"))
+        .flat_map(|token| token.split(|c: char| !c.is_alphabetic() && c != '\''))
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| word.chars().count() >= 3)
+        .filter(|word| !word.chars().skip(1).any(char::is_uppercase))
+        .collect()
+}
+
+/// 1-based line of `source` holding `text`, or else the first holding `word`.
+fn find_line(source: &str, text: &str, word: &str) -> Option<usize> {
+    let position = |needle: &str| source.lines().position(|line| line.contains(needle));
+    position(text.trim()).or_else(|| position(word)).map(|i| i + 1)
+}
+
+#[cfg(all(test, feature = "spellcheck"))]
+mod tests {
+    use super::*;
+    use archidoc_types::{ElementKind, FileEntry, HealthStatus, PatternStatus};
+
+    fn doc(content: &str, source_file: &str) -> ModuleDoc {
+        ModuleDoc {
+            module_path: "bus".to_string(),
+            content: content.to_string(),
+            source_file: source_file.to_string(),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            description: "Routes mesage".to_string(),
+            files: vec![FileEntry {
+                name: "lanes.rs".to_string(),
+                pattern: "--".to_string(),
+                pattern_status: PatternStatus::Planned,
+                purpose: "Recieve events".to_string(),
+                health: HealthStatus::Active,
+                loc: None,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn misspellings_are_reported_with_their_source_line() {
+        let tmp = tempfile::tempdir().unwrap();
+        let source = tmp.path().join("mod.rs");
+        let content = "@c4 container\n\nRoutes mesage between lanes.\n\n| File | Pattern | Purpose | Health |\n|------|---------|---------|--------|\n| `lanes.rs` | -- | Recieve events, see `teh_lane` | active |\n\n```text\nteh\n```\n";
+        let commented: String = content.lines().map(|l| format!("//! {}\n", l)).collect();
+        fs::write(&source, commented).unwrap();
+
+        let findings = check_spelling(&[doc(content, &source.to_string_lossy())], &[]);
+
+        let found: Vec<(&str, Option<usize>)> = findings.iter().map(|f| (f.message.as_str(), f.line)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("'mesage' should be 'message'", Some(3)),
+                ("'Recieve' should be 'receive'", Some(7))
+            ]
+        );
+    }
+
+    #[test]
+    fn ignore_list_and_missing_content() {
+        let findings = check_spelling(&[doc("", "")], &["MESAGE".to_string()]);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].message, "'Recieve' should be 'receive'");
+        assert_eq!(findings[0].file, None);
+        assert_eq!(findings[0].line, None);
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use archidoc_types::{CompileReport, RunStats};
+
+/// Location of the usage statistics file, relative to the project root.
+///
+/// One JSON object per line, appended after each run when
+/// `[stats] enabled = true` is set in `archidoc.toml`.
+pub const STATS_FILE: &str = ".archidoc/stats.jsonl";
+
+/// Summarize a finished run for the statistics file.
+pub fn from_report(report: &CompileReport, mode: &str, date: &str) -> RunStats {
+    RunStats {
+        date: date.to_string(),
+        mode: mode.to_string(),
+        duration_ms: report.phases.iter().map(|p| p.duration_ms).sum(),
+        modules: report.modules_found,
+    }
+}
+
+/// Append one run to the statistics file in `root`.
+pub fn record(root: &Path, run: &RunStats) -> Result<(), String> {
+    let path = root.join(STATS_FILE);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+    }
+    let line = serde_json::to_string(run).map_err(|e| format!("failed to serialize stats: {}", e))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("failed to open {}: {}", path.display(), e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+/// Read all recorded runs, oldest first. A missing file means no runs;
+/// unreadable lines are skipped.
+pub fn load(root: &Path) -> Result<Vec<RunStats>, String> {
+    let path = root.join(STATS_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let text = fs::read_to_string(&path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    Ok(text
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Format recorded runs as a per-mode summary table.
+pub fn format_stats(runs: &[RunStats]) -> String {
+    if runs.is_empty() {
+        return format!(
+            "No runs recorded. Set `[stats] enabled = true` in archidoc.toml to record runs in {}.\n",
+            STATS_FILE
+        );
+    }
+
+    let mut by_mode: BTreeMap<&str, Vec<&RunStats>> = BTreeMap::new();
+    for run in runs {
+        by_mode.entry(&run.mode).or_default().push(run);
+    }
+
+    let mut out = format!(
+        "archidoc stats: {} run(s), {} to {}\n\n",
+        runs.len(),
+        runs[0].date,
+        runs[runs.len() - 1].date
+    );
+    out.push_str(&format!(
+        "{:<10} {:>5} {:>10} {:>10} {:>10} {:>8}\n",
+        "Mode", "Runs", "Avg ms", "Min ms", "Max ms", "Modules"
+    ));
+    for (mode, runs) in &by_mode {
+        let durations: Vec<f64> = runs.iter().map(|r| r.duration_ms).collect();
+        let avg = durations.iter().sum::<f64>() / durations.len() as f64;
+        let min = durations.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = durations.iter().copied().fold(0.0, f64::max);
+        let modules = runs.last().map_or(0, |r| r.modules);
+        out.push_str(&format!(
+            "{:<10} {:>5} {:>10.1} {:>10.1} {:>10.1} {:>8}\n",
+            mode,
+            runs.len(),
+            avg,
+            min,
+            max,
+            modules
+        ));
+    }
+
+    out.push_str("\nModules is the count from the most recent run of each mode.\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(mode: &str, duration_ms: f64, modules: usize) -> RunStats {
+        RunStats {
+            date: "2026-10-01".to_string(),
+            mode: mode.to_string(),
+            duration_ms,
+            modules,
+        }
+    }
+
+    #[test]
+    fn records_append_and_load_in_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        assert!(load(tmp.path()).unwrap().is_empty());
+
+        record(tmp.path(), &run("generate", 12.5, 3)).unwrap();
+        record(tmp.path(), &run("check", 8.0, 4)).unwrap();
+        // Lines from earlier versions carry cache counters, which are ignored
+        let mut file = OpenOptions::new().append(true).open(tmp.path().join(STATS_FILE)).unwrap();
+        writeln!(file, r#"{{"date":"2026-10-01","mode":"lint","duration_ms":1.0,"modules":2,"cache_hits":0,"cache_misses":0}}"#).unwrap();
+
+        let runs = load(tmp.path()).unwrap();
+        assert_eq!(runs, vec![run("generate", 12.5, 3), run("check", 8.0, 4), run("lint", 1.0, 2)]);
+    }
+
+    #[test]
+    fn summary_groups_by_mode() {
+        let runs = vec![run("generate", 10.0, 4), run("generate", 30.0, 5), run("check", 5.0, 5)];
+
+        let text = format_stats(&runs);
+
+        assert!(text.contains("3 run(s)"));
+        assert!(text.contains(&format!("{:<10} {:>5} {:>10.1} {:>10.1} {:>10.1} {:>8}", "generate", 2, 20.0, 10.0, 30.0, 5)));
+    }
+}
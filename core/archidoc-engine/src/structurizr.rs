@@ -0,0 +1,298 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use archidoc_types::{ElementKind, ModuleDoc};
+
+use crate::generator::{Artifact, Generator};
+use crate::mermaid::{components_by_container, containers_by_group, drawn_relationships, group_boundary};
+
+/// File name of the Structurizr DSL workspace.
+pub const WORKSPACE_FILE: &str = "workspace.dsl";
+
+/// A Structurizr DSL workspace under `structurizr/`.
+pub struct StructurizrGenerator;
+
+impl Generator for StructurizrGenerator {
+    fn id(&self) -> &str {
+        "structurizr"
+    }
+
+    fn generate(&self, docs: &[ModuleDoc]) -> Result<Vec<Artifact>, String> {
+        Ok(vec![Artifact {
+            path: format!("structurizr/{}", WORKSPACE_FILE),
+            content: workspace(docs),
+        }])
+    }
+}
+
+/// Render the Structurizr DSL workspace.
+///
+/// People and systems outside the boundary are top-level elements;
+/// containers (grouped by `@group`) and their components sit inside one
+/// software system, with a placeholder container for components whose
+/// container is not annotated. Deployment nodes go into a `Live`
+/// environment. Relationships are the ones diagrams draw, between elements
+/// in the model; Structurizr derives the container-level ones from the
+/// component-level ones itself.
+pub fn workspace(docs: &[ModuleDoc]) -> String {
+    let mut model = String::new();
+    let mut in_model = BTreeSet::new();
+
+    for doc in docs.iter().filter(|d| d.c4_level.is_external()) {
+        let keyword = if doc.c4_level == ElementKind::Person { "person" } else { "softwareSystem" };
+        let tags = if doc.c4_level == ElementKind::ExternalSystem { " \"External\"" } else { "" };
+        model.push_str(&format!(
+            "        {} = {} \"{}\" \"{}\"{}\n",
+            doc.element_id(),
+            keyword,
+            escape(&to_title_case(&doc.module_path)),
+            escape(&doc.description),
+            tags
+        ));
+        in_model.insert(doc.module_path.as_str());
+    }
+
+    let containers: Vec<&ModuleDoc> = docs.iter().filter(|d| d.c4_level.is_container()).collect();
+    let components = components_by_container(docs);
+    let (groups, ungrouped) = containers_by_group(&containers);
+    model.push_str("\n        sys = softwareSystem \"System\" {\n");
+    for doc in ungrouped {
+        model.push_str(&container_def(docs, doc, &components, "            "));
+        in_model.insert(doc.module_path.as_str());
+    }
+    for (group, members) in &groups {
+        let (_, label, _) = group_boundary(group);
+        model.push_str(&format!("            group \"{}\" {{\n", escape(&label)));
+        for doc in members {
+            model.push_str(&container_def(docs, doc, &components, "                "));
+            in_model.insert(doc.module_path.as_str());
+        }
+        model.push_str("            }\n");
+    }
+    for (container, members) in &components {
+        if !containers.iter().any(|c| &c.module_path == container) {
+            model.push_str(&format!(
+                "            {} = container \"{}\" {{\n",
+                ModuleDoc::id_of(docs, container),
+                escape(&to_title_case(container))
+            ));
+            for doc in members {
+                model.push_str(&component_def(doc, "                "));
+            }
+            model.push_str("            }\n");
+        }
+    }
+    model.push_str("        }\n");
+    for members in components.values() {
+        in_model.extend(members.iter().map(|d| d.module_path.as_str()));
+    }
+
+    let nodes: Vec<&ModuleDoc> = docs.iter().filter(|d| d.c4_level == ElementKind::DeploymentNode).collect();
+    if !nodes.is_empty() {
+        model.push_str("\n        deploymentEnvironment \"Live\" {\n");
+        for doc in nodes {
+            model.push_str(&format!(
+                "            {} = deploymentNode \"{}\" \"{}\" \"{}\"\n",
+                doc.element_id(),
+                escape(&to_title_case(&doc.module_path)),
+                escape(&doc.description),
+                escape(&doc.pattern)
+            ));
+        }
+        model.push_str("        }\n");
+    }
+
+    let mut rel_defs = String::new();
+    for doc in docs.iter().filter(|d| in_model.contains(d.module_path.as_str())) {
+        for rel in drawn_relationships(docs, doc) {
+            if in_model.contains(rel.target.as_str()) && rel.target != doc.module_path {
+                rel_defs.push_str(&format!(
+                    "        {} -> {} \"{}\" \"{}\"\n",
+                    doc.element_id(),
+                    ModuleDoc::id_of(docs, &rel.target),
+                    escape(&rel.label),
+                    escape(&rel.protocol)
+                ));
+            }
+        }
+    }
+    if !rel_defs.is_empty() {
+        model.push('\n');
+        model.push_str(&rel_defs);
+    }
+
+    let mut views = String::from(
+        "        systemContext sys \"SystemContext\" {\n            include *\n            autolayout lr\n        }\n\n        container sys \"Containers\" {\n            include *\n            autolayout lr\n        }\n",
+    );
+    for container in components.keys() {
+        let id = ModuleDoc::id_of(docs, container);
+        views.push_str(&format!(
+            "\n        component {} \"Components_{}\" {{\n            include *\n            autolayout lr\n        }}\n",
+            id, id
+        ));
+    }
+
+    format!(
+        r#"workspace "System" "Generated by archidoc from @c4 annotations" {{
+
+    model {{
+{}    }}
+
+    views {{
+{}
+        styles {{
+            element "Person" {{
+                shape Person
+            }}
+            element "External" {{
+                background #999999
+            }}
+        }}
+    }}
+}}
+"#,
+        model, views
+    )
+}
+
+/// A container with its components nested inside.
+fn container_def(
+    docs: &[ModuleDoc],
+    doc: &ModuleDoc,
+    components: &BTreeMap<String, Vec<&ModuleDoc>>,
+    indent: &str,
+) -> String {
+    let tags = match &doc.c4_level {
+        ElementKind::Custom(name) => format!(" \"{}\"", escape(name)),
+        _ => String::new(),
+    };
+    let head = format!(
+        "{}{} = container \"{}\" \"{}\" \"{}\"{}",
+        indent,
+        ModuleDoc::id_of(docs, &doc.module_path),
+        escape(&to_title_case(&doc.module_path)),
+        escape(&doc.description),
+        escape(&doc.pattern),
+        tags
+    );
+    match components.get(&doc.module_path) {
+        Some(members) => {
+            let inner = format!("{}    ", indent);
+            let body: String = members.iter().map(|c| component_def(c, &inner)).collect();
+            format!("{} {{\n{}{}}}\n", head, body, indent)
+        }
+        None => format!("{}\n", head),
+    }
+}
+
+fn component_def(doc: &ModuleDoc, indent: &str) -> String {
+    let name = doc.module_path.split('.').next_back().unwrap_or(&doc.module_path);
+    format!(
+        "{}{} = component \"{}\" \"{}\" \"{}\"\n",
+        indent,
+        doc.element_id(),
+        escape(name),
+        escape(&doc.description),
+        escape(&doc.pattern)
+    )
+}
+
+/// `text` as the inside of a DSL string.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn to_title_case(s: &str) -> String {
+    s.split('.')
+        .next_back()
+        .unwrap_or(s)
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(c) => c.to_uppercase().to_string() + chars.as_str(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::Relationship;
+
+    fn doc(path: &str, kind: ElementKind, uses: &[(&str, bool)]) -> ModuleDoc {
+        ModuleDoc {
+            module_path: path.to_string(),
+            c4_level: kind,
+            pattern: "--".to_string(),
+            description: format!("The \"{}\" element", path),
+            relationships: uses
+                .iter()
+                .map(|(target, hidden)| Relationship {
+                    target: target.to_string(),
+                    label: "Uses".to_string(),
+                    protocol: "http".to_string(),
+                    hidden: *hidden,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn elements_nest_by_kind_and_only_drawn_relationships_are_modelled() {
+        let mut store = doc("store", ElementKind::Custom("database".to_string()), &[]);
+        store.group = Some("domain:data".to_string());
+        let docs = vec![
+            doc("customer", ElementKind::Person, &[("api", false)]),
+            doc("stripe", ElementKind::ExternalSystem, &[]),
+            doc("api", ElementKind::Container, &[("store", false), ("legacy", false)]),
+            doc("api.routes", ElementKind::Component, &[("stripe", false), ("api", true)]),
+            store,
+            doc("jobs.worker", ElementKind::Component, &[]),
+            doc("cluster", ElementKind::DeploymentNode, &[]),
+        ];
+
+        let dsl = workspace(&docs);
+
+        assert!(dsl.contains(
+            r#"        customer = person "Customer" "The \"customer\" element"
+        stripe = softwareSystem "Stripe" "The \"stripe\" element" "External"
+
+        sys = softwareSystem "System" {
+            api = container "Api" "The \"api\" element" "--" {
+                api_routes = component "routes" "The \"api.routes\" element" "--"
+            }
+            group "Data" {
+                store = container "Store" "The \"store\" element" "--" "database"
+            }
+            other = container "Other" {
+                jobs_worker = component "worker" "The \"jobs.worker\" element" "--"
+            }
+        }
+
+        deploymentEnvironment "Live" {
+            cluster = deploymentNode "Cluster" "The \"cluster\" element" "--"
+        }
+
+        customer -> api "Uses" "http"
+        api -> store "Uses" "http"
+        api_routes -> stripe "Uses" "http"
+    }
+"#
+        ), "{}", dsl);
+        assert!(dsl.contains("        component api \"Components_api\" {\n"));
+        assert!(dsl.contains("        component other \"Components_other\" {\n"));
+    }
+
+    #[test]
+    fn an_empty_model_is_still_a_workspace() {
+        let dsl = workspace(&[]);
+
+        assert!(dsl.starts_with("workspace \"System\""));
+        assert!(dsl.contains("        sys = softwareSystem \"System\" {\n        }\n    }\n"));
+        assert!(!dsl.contains("deploymentEnvironment") && !dsl.contains("component "));
+    }
+}
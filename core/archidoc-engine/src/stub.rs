@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use archidoc_types::ElementKind;
+
+/// Files written by [`create_stub`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct StubOutcome {
+    /// The new module's `mod.rs`.
+    pub entry_file: PathBuf,
+    /// The parent module file the new module was declared in, if any.
+    pub registered_in: Option<PathBuf>,
+}
+
+/// The annotated `mod.rs` of a new, still undescribed module: its `@c4`
+/// marker, title, a TODO description, and an empty file table.
+pub fn stub_annotation(module_path: &str, kind: &ElementKind) -> String {
+    let name = module_path.rsplit('.').next().unwrap_or(module_path);
+    let mut title = name.replace('_', " ");
+    if let Some(first) = title.get(..1) {
+        title.replace_range(..1, &first.to_uppercase());
+    }
+    format!(
+        "//! @c4 {}\n//!\n//! # {}\n//!\n//! [TODO: describe this module's responsibility]\n//!\n//! | File | Pattern | Purpose | Health |\n//! |------|---------|---------|--------|\n",
+        kind, title
+    )
+}
+
+/// The element kind a new module gets by default: a container at the top
+/// level, a component below it.
+pub fn default_kind(module_path: &str) -> ElementKind {
+    if module_path.contains('.') {
+        ElementKind::Component
+    } else {
+        ElementKind::Container
+    }
+}
+
+/// Create `<root>/<module/path>/mod.rs` with [`stub_annotation`].
+///
+/// With `register`, also declares `pub mod <name>;` in the parent module's
+/// file: `mod.rs` in the parent directory or the flat `<parent>.rs`, and for
+/// a top-level module `lib.rs` or `main.rs` in `root`. Nothing is written
+/// when the module already exists, a segment of `module_path` is not an
+/// identifier, or the parent file cannot be found.
+pub fn create_stub(root: &Path, module_path: &str, kind: &ElementKind, register: bool) -> Result<StubOutcome, String> {
+    let segments: Vec<&str> = module_path.split('.').collect();
+    if let Some(bad) = segments.iter().find(|s| !is_identifier(s)) {
+        return Err(format!("invalid module path '{}': '{}' is not an identifier", module_path, bad));
+    }
+    let dir = segments.iter().fold(root.to_path_buf(), |dir, s| dir.join(s));
+    let entry_file = dir.join("mod.rs");
+    let flat_file = dir.with_extension("rs");
+    if entry_file.exists() || flat_file.exists() {
+        return Err(format!("module '{}' already exists", module_path));
+    }
+
+    let (name, parents) = segments.split_last().expect("split yields at least one segment");
+    let registration = if register {
+        let parent_dir = parents.iter().fold(root.to_path_buf(), |dir, s| dir.join(s));
+        let candidates = if parents.is_empty() {
+            vec![root.join("lib.rs"), root.join("main.rs")]
+        } else {
+            vec![parent_dir.join("mod.rs"), parent_dir.with_extension("rs")]
+        };
+        let Some(parent_file) = candidates.iter().find(|c| c.is_file()) else {
+            let looked: Vec<String> = candidates.iter().map(|c| c.display().to_string()).collect();
+            return Err(format!("no parent module file (looked for {})", looked.join(", ")));
+        };
+        let source = fs::read_to_string(parent_file)
+            .map_err(|e| format!("cannot read {}: {}", parent_file.display(), e))?;
+        declare_module(&source, name).map(|updated| (parent_file.clone(), updated))
+    } else {
+        None
+    };
+
+    fs::create_dir_all(&dir).map_err(|e| format!("cannot create {}: {}", dir.display(), e))?;
+    fs::write(&entry_file, stub_annotation(module_path, kind))
+        .map_err(|e| format!("cannot write {}: {}", entry_file.display(), e))?;
+    let mut outcome = StubOutcome {
+        entry_file,
+        registered_in: None,
+    };
+    if let Some((parent_file, updated)) = registration {
+        fs::write(&parent_file, updated).map_err(|e| format!("cannot write {}: {}", parent_file.display(), e))?;
+        outcome.registered_in = Some(parent_file);
+    }
+    Ok(outcome)
+}
+
+/// `source` with `pub mod <name>;` added after its last module declaration,
+/// or after the leading inner doc comments and attributes when it has none.
+/// `None` when `name` is already declared.
+pub fn declare_module(source: &str, name: &str) -> Option<String> {
+    let lines: Vec<&str> = source.lines().collect();
+    if lines.iter().any(|line| declared_name(line) == Some(name)) {
+        return None;
+    }
+
+    let at = match lines.iter().rposition(|line| declared_name(line).is_some()) {
+        Some(last) => last + 1,
+        None => {
+            let header = lines
+                .iter()
+                .take_while(|l| l.starts_with("//!") || l.starts_with("#!") || l.trim().is_empty())
+                .count();
+            lines[..header].iter().rposition(|l| !l.trim().is_empty()).map_or(0, |i| i + 1)
+        }
+    };
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    let declaration = format!("pub mod {};", name);
+    if at == 0 || lines.get(at - 1).is_some_and(|l| declared_name(l).is_some()) {
+        out.insert(at, declaration);
+    } else {
+        out.splice(at..at, [String::new(), declaration]);
+    }
+    Some(out.join("\n") + "\n")
+}
+
+/// The module named by a `mod <name>;` line, with or without visibility.
+fn declared_name(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let rest = line.strip_prefix("pub ").or_else(|| line.strip_prefix("pub(crate) ")).unwrap_or(line);
+    rest.strip_prefix("mod ").map(|r| r.trim_end_matches(';').trim())
+}
+
+fn is_identifier(segment: &str) -> bool {
+    let mut chars = segment.chars();
+    chars.next().is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stub_is_created_and_registered_in_the_parent() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::create_dir_all(tmp.path().join("bus")).unwrap();
+        fs::write(tmp.path().join("bus/mod.rs"), "//! @c4 container\n\npub mod calc;\n\npub fn run() {}\n").unwrap();
+
+        let outcome = create_stub(tmp.path(), "bus.event_lanes", &default_kind("bus.event_lanes"), true).unwrap();
+
+        assert_eq!(outcome.entry_file, tmp.path().join("bus/event_lanes/mod.rs"));
+        assert_eq!(outcome.registered_in, Some(tmp.path().join("bus/mod.rs")));
+        let stub = fs::read_to_string(&outcome.entry_file).unwrap();
+        assert!(stub.starts_with("//! @c4 component\n//!\n//! # Event lanes\n"), "{}", stub);
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("bus/mod.rs")).unwrap(),
+            "//! @c4 container\n\npub mod calc;\npub mod event_lanes;\n\npub fn run() {}\n"
+        );
+        assert_eq!(
+            create_stub(tmp.path(), "bus.event_lanes", &ElementKind::Component, false),
+            Err("module 'bus.event_lanes' already exists".to_string())
+        );
+    }
+
+    #[test]
+    fn top_level_modules_register_in_lib_rs_and_bad_paths_write_nothing() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        let err = create_stub(tmp.path(), "api", &ElementKind::Container, true).unwrap_err();
+        assert!(err.starts_with("no parent module file"), "{}", err);
+        assert!(!tmp.path().join("api").exists());
+        assert_eq!(
+            create_stub(tmp.path(), "bus.Lanes", &ElementKind::Component, false),
+            Err("invalid module path 'bus.Lanes': 'Lanes' is not an identifier".to_string())
+        );
+
+        fs::write(tmp.path().join("lib.rs"), "//! @c4 container\n//! # App\n").unwrap();
+        create_stub(tmp.path(), "api", &ElementKind::Container, true).unwrap();
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("lib.rs")).unwrap(),
+            "//! @c4 container\n//! # App\n\npub mod api;\n"
+        );
+        assert_eq!(declare_module("pub mod api;\n", "api"), None);
+    }
+}
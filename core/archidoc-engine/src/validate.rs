@@ -1,7 +1,6 @@
 use std::collections::HashSet;
-use std::path::Path;
 
-use archidoc_types::{GhostEntry, ModuleDoc, OrphanEntry, ValidationReport};
+use archidoc_types::{GhostEntry, InvalidLink, ModuleDoc, OrphanEntry, Severity, ValidationReport};
 
 /// Validate file tables against the actual filesystem.
 ///
@@ -9,16 +8,31 @@ use archidoc_types::{GhostEntry, ModuleDoc, OrphanEntry, ValidationReport};
 /// - **Ghost detection** (B4): catalog entries pointing to files that don't exist on disk
 /// - **Orphan detection** (B3): `.rs` files on disk not listed in any catalog
 ///
-/// Modules without file catalogs are silently skipped.
+/// Catalogs resolve against [`ModuleDoc::catalog_dir`], so a flat `foo.rs`
+/// is checked against `foo/` in mod.rs-less layouts.
+///
+/// Modules without file catalogs are silently skipped. `@doc` links are
+/// checked for basic URL shape on every module.
+///
+/// Findings get the default severities (ghosts and invalid links are
+/// errors, orphans warnings); set [`ValidationReport::severities`] to apply
+/// a project's `[validate]` settings.
 pub fn validate_file_tables(docs: &[ModuleDoc]) -> ValidationReport {
     let mut report = ValidationReport::default();
 
     for doc in docs {
+        for link in doc.links.iter().filter(|l| !l.has_valid_url()) {
+            report.invalid_links.push(InvalidLink {
+                element: doc.module_path.clone(),
+                url: link.url.clone(),
+            });
+        }
+
         if doc.files.is_empty() {
             continue;
         }
 
-        let source_dir = match Path::new(&doc.source_file).parent() {
+        let source_dir = match doc.catalog_dir() {
             Some(dir) => dir,
             None => continue,
         };
@@ -44,18 +58,21 @@ pub fn validate_file_tables(docs: &[ModuleDoc]) -> ValidationReport {
         let structural_files: HashSet<&str> =
             ["mod.rs", "lib.rs", "main.rs"].iter().copied().collect();
 
-        if let Ok(entries) = std::fs::read_dir(source_dir) {
-            for entry in entries.filter_map(|e| e.ok()) {
-                let filename = entry.file_name();
-                let name = filename.to_string_lossy();
-
+        if let Ok(entries) = std::fs::read_dir(&source_dir) {
+            // read_dir order depends on the filesystem
+            let mut names: Vec<String> = entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect();
+            names.sort();
+            for name in names {
                 if name.ends_with(".rs")
-                    && !structural_files.contains(name.as_ref())
-                    && !cataloged_names.contains(name.as_ref())
+                    && !structural_files.contains(name.as_str())
+                    && !cataloged_names.contains(name.as_str())
                 {
                     report.orphans.push(OrphanEntry {
                         element: doc.module_path.clone(),
-                        filename: name.to_string(),
+                        filename: name,
                         source_dir: source_dir_str.clone(),
                     });
                 }
@@ -76,7 +93,11 @@ pub fn format_validation_report(report: &ValidationReport) -> String {
     }
 
     if !report.ghosts.is_empty() {
-        out.push_str(&format!("Ghost entries ({} found):\n", report.ghosts.len()));
+        out.push_str(&format!(
+            "Ghost entries [AD101] ({} found, {}):\n",
+            report.ghosts.len(),
+            report.severities.ghost.as_str()
+        ));
         for ghost in &report.ghosts {
             out.push_str(&format!(
                 "  {} — '{}' listed in catalog but not found on disk\n",
@@ -87,8 +108,9 @@ pub fn format_validation_report(report: &ValidationReport) -> String {
 
     if !report.orphans.is_empty() {
         out.push_str(&format!(
-            "Orphan files ({} found):\n",
-            report.orphans.len()
+            "Orphan files [AD102] ({} found, {}):\n",
+            report.orphans.len(),
+            report.severities.orphan.as_str()
         ));
         for orphan in &report.orphans {
             out.push_str(&format!(
@@ -98,5 +120,24 @@ pub fn format_validation_report(report: &ValidationReport) -> String {
         }
     }
 
+    if !report.invalid_links.is_empty() {
+        out.push_str(&format!(
+            "Invalid doc links [AD103] ({} found, {}):\n",
+            report.invalid_links.len(),
+            report.severities.invalid_link.as_str()
+        ));
+        for link in &report.invalid_links {
+            out.push_str(&format!(
+                "  {} — '{}' is not an http(s) URL\n",
+                link.element, link.url
+            ));
+        }
+    }
+
+    out.push_str(&format!(
+        "{} error(s), {} warning(s)\n",
+        report.count(Severity::Error),
+        report.count(Severity::Warning)
+    ));
     out
 }
@@ -0,0 +1,166 @@
+use std::collections::BTreeSet;
+
+use archidoc_types::ModuleDoc;
+
+/// Replace `${NAME}` placeholders in the annotation prose of every element:
+/// its content, description, file purposes, relationship labels, links,
+/// risks, and term definitions. `lookup` resolves a name to its value.
+///
+/// `$${NAME}` is written as a literal `${NAME}`. Placeholders `lookup`
+/// cannot resolve are left as written, with one warning per element and
+/// name. Content is hashed into `content_hash` before it is rewritten, so
+/// reviews compare against the annotation as written, whatever the values.
+pub fn substitute_variables(docs: &mut [ModuleDoc], lookup: impl Fn(&str) -> Option<String>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for doc in docs.iter_mut() {
+        let mut missing = BTreeSet::new();
+        let mut apply = |text: &mut String| {
+            if text.contains("${") {
+                *text = substitute(text, &lookup, &mut missing);
+            }
+        };
+
+        if doc.content.contains("${") && doc.content_hash.is_none() {
+            doc.content_hash = crate::hash::annotation_hash(doc);
+        }
+        apply(&mut doc.content);
+        apply(&mut doc.description);
+        for file in &mut doc.files {
+            apply(&mut file.purpose);
+        }
+        for rel in &mut doc.relationships {
+            apply(&mut rel.label);
+        }
+        for link in &mut doc.links {
+            apply(&mut link.url);
+            apply(&mut link.title);
+        }
+        for risk in &mut doc.risks {
+            apply(&mut risk.description);
+        }
+        for term in &mut doc.terms {
+            apply(&mut term.definition);
+        }
+
+        warnings.extend(
+            missing
+                .into_iter()
+                .map(|name| format!("{}: unresolved variable ${{{}}}", doc.module_path, name)),
+        );
+    }
+    warnings
+}
+
+/// `text` with its placeholders resolved, adding the names `lookup` does not
+/// know to `missing`.
+pub fn substitute(text: &str, lookup: impl Fn(&str) -> Option<String>, missing: &mut BTreeSet<String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        let escaped = rest[..start].ends_with('$');
+        out.push_str(&rest[..start - usize::from(escaped)]);
+        let after = &rest[start + 2..];
+        let name = after.find('}').map(|end| &after[..end]).filter(|name| is_variable_name(name));
+        let Some(name) = name else {
+            out.push_str("${");
+            rest = after;
+            continue;
+        };
+        let placeholder = &rest[start..start + name.len() + 3];
+        match lookup(name) {
+            _ if escaped => out.push_str(placeholder),
+            Some(value) => out.push_str(&value),
+            None => {
+                missing.insert(name.to_string());
+                out.push_str(placeholder);
+            }
+        }
+        rest = &rest[start + placeholder.len()..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Whether `name` is a letter or `_` followed by letters, digits, and `_`.
+pub fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use archidoc_types::{DocLink, ElementKind, Relationship};
+
+    #[test]
+    fn reviews_compare_against_the_unresolved_content() {
+        let mut docs = vec![doc("Serves ${ENV}")];
+        let raw = crate::hash::content_hash(&docs[0].content);
+        docs[0].review = Some(archidoc_types::Review {
+            reviewer: "ana".to_string(),
+            date: "2024-05-01".to_string(),
+            content_hash: Some(raw.clone()),
+        });
+
+        substitute_variables(&mut docs, |_| Some("production".to_string()));
+
+        assert!(docs[0].content.contains("Serves production"));
+        assert_eq!(docs[0].content_hash.as_deref(), Some(raw.as_str()));
+        assert_eq!(crate::health::aggregate_health(&docs).reviews_current, 1);
+    }
+
+    fn doc(description: &str) -> ModuleDoc {
+        ModuleDoc {
+            module_path: "api".to_string(),
+            content: format!("@c4 container\n\n{}\n", description),
+            c4_level: ElementKind::Container,
+            pattern: "--".to_string(),
+            description: description.to_string(),
+            relationships: vec![Relationship {
+                target: "db".to_string(),
+                label: "Reads ${ENV} data".to_string(),
+                protocol: String::new(),
+                hidden: false,
+            }],
+            links: vec![DocLink {
+                url: "https://${DOMAIN}/runbook".to_string(),
+                title: String::new(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "ENV" => Some("staging".to_string()),
+            "DOMAIN" => Some("staging.example.com".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn placeholders_are_resolved_across_the_prose() {
+        let mut docs = vec![doc("Serves the ${ENV} API at ${DOMAIN}")];
+
+        let warnings = substitute_variables(&mut docs, lookup);
+
+        assert!(warnings.is_empty());
+        assert_eq!(docs[0].description, "Serves the staging API at staging.example.com");
+        assert!(docs[0].content.contains("Serves the staging API"));
+        assert_eq!(docs[0].relationships[0].label, "Reads staging data");
+        assert_eq!(docs[0].links[0].url, "https://staging.example.com/runbook");
+    }
+
+    #[test]
+    fn unknown_escaped_and_malformed_placeholders_stay_as_written() {
+        let mut docs = vec![doc("${REGION} and ${REGION}, $${ENV}, ${not valid}, ${ENV")];
+
+        let warnings = substitute_variables(&mut docs, lookup);
+
+        assert_eq!(docs[0].description, "${REGION} and ${REGION}, ${ENV}, ${not valid}, ${ENV");
+        assert_eq!(warnings, vec!["api: unresolved variable ${REGION}"]);
+        assert!(is_variable_name("_ENV2"));
+        assert!(!is_variable_name("2ENV"));
+    }
+}
@@ -5,19 +5,14 @@ use std::fmt;
 ///
 /// `planned` — developer intent, not yet structurally validated.
 /// `verified` — structural heuristic has confirmed pattern alignment.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum PatternStatus {
+    #[default]
     Planned,
     Verified,
 }
 
-impl Default for PatternStatus {
-    fn default() -> Self {
-        Self::Planned
-    }
-}
-
 impl fmt::Display for PatternStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -39,20 +34,15 @@ impl PatternStatus {
 /// Implementation maturity of a file.
 ///
 /// Progression: `planned` -> `active` -> `stable`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum HealthStatus {
+    #[default]
     Planned,
     Active,
     Stable,
 }
 
-impl Default for HealthStatus {
-    fn default() -> Self {
-        Self::Planned
-    }
-}
-
 impl fmt::Display for HealthStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -72,3 +62,45 @@ impl HealthStatus {
         }
     }
 }
+
+/// Severity of an architectural risk from `@risk`.
+///
+/// Ordered from least to most severe, so registers can sort by it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RiskSeverity {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for RiskSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Medium => write!(f, "medium"),
+            Self::High => write!(f, "high"),
+            Self::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+impl RiskSeverity {
+    pub fn parse(s: &str) -> Self {
+        match s.trim().to_lowercase().as_str() {
+            "low" => Self::Low,
+            "high" => Self::High,
+            "critical" => Self::Critical,
+            _ => Self::Medium,
+        }
+    }
+}
+
+/// Check whether a doc line is an annotation marker such as `@c4 container`
+/// or `@files-dir ../proto`, rather than prose.
+pub fn is_marker_line(line: &str) -> bool {
+    let mut chars = line.trim_start().chars();
+    chars.next() == Some('@') && chars.next().is_some_and(|c| c.is_ascii_lowercase())
+}
@@ -0,0 +1,537 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Project-level settings from `archidoc.toml` at the project root.
+///
+/// Every section is optional; a missing file means all defaults. Other
+/// top-level keys are ignored, since the same file may also carry a `c4`
+/// sidecar annotation for the root directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectConfig {
+    pub generators: GeneratorConfig,
+    pub architecture: ArchitectureConfig,
+    pub diagrams: DiagramConfig,
+    pub html: HtmlConfig,
+    pub confluence: ConfluenceConfig,
+    pub site: SiteConfig,
+    pub render: RenderConfig,
+    pub front_matter: FrontMatterConfig,
+    pub header: HeaderConfig,
+    pub check: CheckConfig,
+    pub validate: ValidateConfig,
+    pub catalog: CatalogConfig,
+    pub lint: LintConfig,
+    pub stats: StatsConfig,
+    pub heuristics: HeuristicConfig,
+    pub fitness: FitnessConfig,
+    pub protocols: ProtocolConfig,
+    pub rewrite: RewriteConfig,
+    /// Values for `${NAME}` placeholders in annotation prose (`[variables]`).
+    /// The environment variable of the same name, and `--var`, take
+    /// precedence.
+    pub variables: BTreeMap<String, String>,
+    /// External generators, by name (`[generator.<name>]`).
+    pub generator: BTreeMap<String, PluginConfig>,
+    /// Post-generation commands, by generator id (`[hooks.<id>]`). Each
+    /// artifact of the generator is piped through the command, which prints
+    /// the content to write; `--check` compares against the same output.
+    pub hooks: BTreeMap<String, PluginConfig>,
+}
+
+/// Which generators run alongside ARCHITECTURE.md. CLI flags can enable
+/// more; `--no-ai` turns the AI context off.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeneratorConfig {
+    /// ARCHITECTURE.ai.md (on by default).
+    pub ai_context: bool,
+    /// PlantUML diagrams under `c4/`.
+    pub plantuml: bool,
+    /// draw.io CSV files under `drawio/`.
+    pub drawio: bool,
+    /// D2 diagrams under `d2/`.
+    pub d2: bool,
+    /// Structurizr DSL workspace under `structurizr/`.
+    pub structurizr: bool,
+    /// ARCHITECTURE.md as a single HTML page, `<stem>.html`.
+    pub html: bool,
+    /// Confluence storage-format pages under `confluence/`: an index page
+    /// and one page per container.
+    pub confluence: bool,
+    /// MkDocs project under the `[site]` directory: `mkdocs.yml`, an index
+    /// page, and one page per container.
+    pub mkdocs: bool,
+    /// Docusaurus doc category under `<[site] dir>/architecture/`: an index
+    /// page and one page per container, each with front matter.
+    pub docusaurus: bool,
+    /// Architectural Fitness section in ARCHITECTURE.md with the result of
+    /// every built-in fitness function. Needs source access, so it is
+    /// left out when generating from an IR file.
+    pub fitness: bool,
+    /// Further generators to run, by id (e.g. `["mermaid"]`).
+    pub enable: Vec<String>,
+    /// Language of generated headings and report labels.
+    pub language: Language,
+}
+
+/// Languages for generated headings and report labels. Annotation content
+/// and keywords (`planned`, `stable`, ...) are never translated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    De,
+    Ja,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            ai_context: true,
+            plantuml: false,
+            drawio: false,
+            d2: false,
+            structurizr: false,
+            html: false,
+            confluence: false,
+            mkdocs: false,
+            docusaurus: false,
+            fitness: false,
+            enable: Vec::new(),
+            language: Language::En,
+        }
+    }
+}
+
+/// Layout of ARCHITECTURE.md.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ArchitectureConfig {
+    /// Sections to leave out, by id: `contents`, `narrative`,
+    /// `system_context`, `system_diagram`, `component_diagram`,
+    /// `component_index`, `elements`, `relationship_map`, `links`,
+    /// `risk_register`, `fitness`, `health`, `validation`, `glossary`,
+    /// `patterns_in_use`, `metrics`, or `archived`.
+    pub skip: Vec<String>,
+    /// Add a Project Health section with file and pattern maturity at
+    /// generation time.
+    pub health: bool,
+    /// Add a Validation Findings section listing ghost and orphan files and
+    /// invalid doc links at generation time.
+    pub validation: bool,
+    /// Add an Archived Elements appendix listing elements found in the
+    /// snapshot history (`archidoc snapshot`) but no longer in the code,
+    /// until `archidoc purge-archived` drops them.
+    pub archive: bool,
+    /// Order of the Component Index rows.
+    pub index_order: IndexOrder,
+}
+
+/// Orders for the Component Index, set by `[architecture] index_order`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexOrder {
+    /// By module path.
+    #[default]
+    Alphabetical,
+    /// Each container followed by its components, then by level.
+    Container,
+    /// Least mature file tables first; elements without one last.
+    Health,
+    /// By `@group` (a component takes its container's), ungrouped last.
+    Owner,
+}
+
+/// Rendering of the Mermaid, PlantUML, D2, and draw.io diagrams.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiagramConfig {
+    /// Lift component relationships that cross containers into the
+    /// container diagram: one arrow per container pair, labeled with the
+    /// number of relationships it stands for. The component diagram keeps
+    /// every edge.
+    pub aggregate_relationships: bool,
+    /// Arrow style per relationship protocol, e.g.
+    /// `http = { color = "blue" }` under `[diagrams.edge_styles]`. Applies
+    /// to the Mermaid, PlantUML, and D2 diagrams; protocols match ignoring
+    /// case.
+    pub edge_styles: BTreeMap<String, EdgeStyle>,
+}
+
+impl DiagramConfig {
+    /// The style for arrows carrying `protocol`, if one is configured.
+    pub fn edge_style(&self, protocol: &str) -> Option<&EdgeStyle> {
+        if protocol.is_empty() {
+            return None;
+        }
+        self.edge_styles
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(protocol))
+            .map(|(_, style)| style)
+    }
+}
+
+/// How diagrams draw the arrows of one protocol.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EdgeStyle {
+    /// Line and label color: a color name or `#rrggbb`.
+    pub color: Option<String>,
+    /// Line pattern or weight. Mermaid C4 draws every line solid, so its
+    /// diagrams take only the color; PlantUML, D2, and draw.io draw it.
+    pub line: LineStyle,
+}
+
+/// Line pattern or weight of an arrow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineStyle {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
+    Bold,
+}
+
+impl LineStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineStyle::Solid => "solid",
+            LineStyle::Dashed => "dashed",
+            LineStyle::Dotted => "dotted",
+            LineStyle::Bold => "bold",
+        }
+    }
+}
+
+/// The HTML page written by the `html` generator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HtmlConfig {
+    /// Mermaid ES module the page loads to draw its diagrams. Point it at a
+    /// copy on the same host to publish without outside requests.
+    pub mermaid_url: String,
+}
+
+impl Default for HtmlConfig {
+    fn default() -> Self {
+        Self {
+            mermaid_url: "https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.esm.min.mjs".to_string(),
+        }
+    }
+}
+
+/// The pages written by the `confluence` generator, and where
+/// `archidoc publish-confluence` puts them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfluenceConfig {
+    /// Site address, e.g. `https://example.atlassian.net/wiki`.
+    pub base_url: String,
+    /// Key of the space to publish to.
+    pub space: String,
+    /// Id of the page the index page is published under; the space's
+    /// top level when unset.
+    pub parent_id: Option<String>,
+    /// Put before every page title, since titles are unique per space.
+    pub title_prefix: String,
+    /// Macro that draws the Mermaid diagrams; its name depends on the
+    /// Mermaid app installed on the site.
+    pub diagram_macro: String,
+}
+
+impl Default for ConfluenceConfig {
+    fn default() -> Self {
+        Self {
+            base_url: String::new(),
+            space: String::new(),
+            parent_id: None,
+            title_prefix: String::new(),
+            diagram_macro: "mermaid-cloud".to_string(),
+        }
+    }
+}
+
+/// The documentation site projects written by the `mkdocs` and
+/// `docusaurus` generators.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SiteConfig {
+    /// Directory of the site project, relative to the output directory.
+    pub dir: String,
+}
+
+impl Default for SiteConfig {
+    fn default() -> Self {
+        Self { dir: "site".to_string() }
+    }
+}
+
+/// Images rendered from the Mermaid and PlantUML diagram files after
+/// generation, with `--render` or `formats`. A renderer that is not
+/// installed is skipped with a warning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RenderConfig {
+    /// Formats to render on every generate run; `--render` adds to them.
+    pub formats: Vec<RenderFormat>,
+    /// The Mermaid CLI, run as `<command> <args> -i <diagram.mmd> -o <image>`.
+    pub mermaid: PluginConfig,
+    /// PlantUML, run as `<command> <args> -t<format> <diagram.puml>`, e.g.
+    /// `command = "java"` with `args = ["-jar", "plantuml.jar"]`.
+    pub plantuml: PluginConfig,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self {
+            formats: Vec::new(),
+            mermaid: PluginConfig {
+                command: "mmdc".to_string(),
+                args: Vec::new(),
+            },
+            plantuml: PluginConfig {
+                command: "plantuml".to_string(),
+                args: Vec::new(),
+            },
+        }
+    }
+}
+
+/// Image format of a rendered diagram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderFormat {
+    Svg,
+    Png,
+}
+
+impl RenderFormat {
+    /// File extension of the format, which is also its name for the renderers.
+    pub fn extension(self) -> &'static str {
+        match self {
+            RenderFormat::Svg => "svg",
+            RenderFormat::Png => "png",
+        }
+    }
+}
+
+/// YAML front matter on ARCHITECTURE.md and generated module READMEs, for
+/// static site generators (Hugo, Docusaurus, MkDocs).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FrontMatterConfig {
+    pub enabled: bool,
+    /// Navigation weight of ARCHITECTURE.md. Module READMEs get the
+    /// following weights, in module path order.
+    pub weight: i64,
+    /// Include `generated_at`; turn off for reproducible builds.
+    pub timestamp: bool,
+}
+
+impl Default for FrontMatterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            weight: 1,
+            timestamp: true,
+        }
+    }
+}
+
+/// Header comment naming the archidoc version, time, and command that
+/// produced each Markdown and PlantUML file. `--check` ignores it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HeaderConfig {
+    pub enabled: bool,
+    /// Include the generation time; turn off for reproducible builds.
+    pub timestamp: bool,
+}
+
+impl Default for HeaderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timestamp: true,
+        }
+    }
+}
+
+/// Settings for `--check`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CheckConfig {
+    /// Generators whose output is excluded from drift checking even when
+    /// generated, by id: `architecture`, `ai_context`, `mermaid`,
+    /// `plantuml`, `drawio`, `d2`, `structurizr`, `html`, `confluence`,
+    /// `mkdocs`, or `docusaurus`.
+    pub skip: Vec<String>,
+}
+
+/// Severity of each kind of `--validate` finding. Errors fail the run;
+/// warnings are reported but pass unless `--warnings-as-errors` is given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ValidateConfig {
+    /// Catalog entries missing on disk.
+    pub ghost: Severity,
+    /// Files on disk missing from their catalog.
+    pub orphan: Severity,
+    /// Malformed `@doc` URLs.
+    pub invalid_link: Severity,
+}
+
+impl Default for ValidateConfig {
+    fn default() -> Self {
+        Self {
+            ghost: Severity::Error,
+            orphan: Severity::Warning,
+            invalid_link: Severity::Error,
+        }
+    }
+}
+
+/// Settings for `archidoc catalog`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CatalogConfig {
+    /// Write the proposed file-table rows into the annotations instead of
+    /// only listing them, as if `--apply` were given.
+    pub auto_apply: bool,
+}
+
+/// Settings for `--lint`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    /// Shortest acceptable element description, in characters.
+    pub min_description_length: usize,
+    /// Spell-check narratives and file purposes against the built-in list
+    /// of common misspellings.
+    pub spelling: bool,
+    /// Words the spell check accepts as written (case-insensitive).
+    pub ignore_words: Vec<String>,
+    /// Relationship targets outside the annotated code, such as databases
+    /// and third-party APIs, e.g. `["postgres", "stripe"]`.
+    pub external_systems: Vec<String>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            min_description_length: 10,
+            spelling: false,
+            ignore_words: Vec::new(),
+            external_systems: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// Catalog of relationship protocols (`@c4 uses target "label" "protocol"`).
+///
+/// Protocols are matched case-insensitively and rewritten to their catalog
+/// spelling; aliases map other spellings onto a catalog entry. With a
+/// non-empty `allowed` list, any other protocol is reported as a warning.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProtocolConfig {
+    /// Known protocols, e.g. `["crossbeam", "sqlx", "http", "grpc"]`.
+    pub allowed: Vec<String>,
+    /// Alternative spellings, e.g. `{ "https" = "http", "mpsc" = "channel" }`.
+    pub aliases: BTreeMap<String, String>,
+}
+
+/// Module path rewrites applied to the extracted elements before anything
+/// is generated, so documentation can be restructured without touching the
+/// annotations.
+///
+/// Each rule is written `"from -> to"`. Renames run first, then collapses,
+/// each in the order listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RewriteConfig {
+    /// Rename a module and everything below it, e.g. `"legacy_core -> core"`.
+    pub rename: Vec<String>,
+    /// Fold every module matching a `*` pattern into one element, e.g.
+    /// `"utils.* -> utils"`.
+    pub collapse: Vec<String>,
+}
+
+/// Local usage statistics (`archidoc stats`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatsConfig {
+    /// Append a record of every run to `.archidoc/stats.jsonl`. Nothing is
+    /// sent anywhere.
+    pub enabled: bool,
+}
+
+/// Tunables for the structural pattern heuristics used by auto-promotion
+/// and fitness checks. Defaults match the built-in behavior.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HeuristicConfig {
+    /// `pub mod` declarations that make a file a Facade when it has no
+    /// `pub use` re-exports.
+    pub facade_min_pub_mods: usize,
+    /// Smallest named-field count for an Adapter's wrapper struct.
+    pub adapter_min_fields: usize,
+    /// Largest named-field count for an Adapter's wrapper struct.
+    pub adapter_max_fields: usize,
+    /// Methods returning `Self` that make an impl a Builder when it has no
+    /// `build()` method.
+    pub builder_min_setters: usize,
+}
+
+impl Default for HeuristicConfig {
+    fn default() -> Self {
+        Self {
+            facade_min_pub_mods: 2,
+            adapter_min_fields: 1,
+            adapter_max_fields: 2,
+            builder_min_setters: 2,
+        }
+    }
+}
+
+/// Settings for the built-in fitness functions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FitnessConfig {
+    /// Exceptions to the cross-container check: `"from -> to"` allows one
+    /// dependency on another container's component, a bare `"to"` lets
+    /// any element use that component directly.
+    pub allow_cross_container: Vec<String>,
+}
+
+/// An external generator run as a subprocess. It receives the JSON IR on
+/// stdin and prints its artifacts as a JSON array of `{"path", "content"}`
+/// objects.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginConfig {
+    /// Program to run; a relative path is resolved from the project root.
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
@@ -6,16 +6,30 @@
 //!
 //! | File | Pattern | Purpose | Health |
 //! |------|---------|---------|--------|
-//! | `module_doc.rs` | -- | Core data structures | planned |
-//! | `annotation.rs` | -- | Annotation spec enums | planned |
+//! | `module_doc.rs` | -- | Core data structures | stable |
+//! | `annotation.rs` | -- | Annotation spec enums | stable |
+//! | `config.rs` | -- | Project configuration (archidoc.toml) | active |
+//! | `report.rs` | -- | Health, validation, drift, diff, and risk reports | active |
+//! | `snapshot.rs` | -- | Approved IR snapshot | active |
 
 pub mod annotation;
+pub mod config;
 pub mod module_doc;
 pub mod report;
+pub mod snapshot;
 
-pub use annotation::{HealthStatus, PatternStatus};
-pub use module_doc::{C4Level, FileEntry, ModuleDoc, Relationship};
+pub use annotation::{is_marker_line, HealthStatus, PatternStatus, RiskSeverity};
+pub use config::{
+    ArchitectureConfig, CatalogConfig, CheckConfig, ConfluenceConfig, DiagramConfig, EdgeStyle, FitnessConfig, FrontMatterConfig, GeneratorConfig, HeaderConfig, HeuristicConfig, HtmlConfig, IndexOrder,
+    Language, LineStyle, LintConfig, PluginConfig, ProjectConfig, ProtocolConfig, RenderConfig, RenderFormat, RewriteConfig, Severity, SiteConfig, StatsConfig, ValidateConfig,
+};
+#[allow(deprecated)]
+pub use module_doc::C4Level;
+pub use module_doc::{DocLink, ElementKind, FileEntry, Flow, ModuleDoc, PatternEvidence, Relationship, Review, Risk, Term};
 pub use report::{
-    DriftReport, DriftedFile, ElementHealth, GhostEntry, HealthReport, OrphanEntry,
-    ValidationReport,
+    AdapterStatus, ApprovalReport, CompileReport, DriftReport, DriftedFile, ElementChange, ElementHealth, ElementRename, FieldChange,
+    FitnessSummary, GapReport, GhostEntry, HealthReport, InvalidLink, IrDiff, IrValidationReport, IrViolation, LintFinding, LintReport, OrgReport, OrphanEntry,
+    OutputFile, PatternAdoption, PhaseTiming, PromotionRecord, RelationshipChange, RepoRelationship, RepoSummary, RiskEntry,
+    RiskRegister, RunStats, SkippedFile, StaleReview, StatusChange, ValidationReport,
 };
+pub use snapshot::ApprovedSnapshot;
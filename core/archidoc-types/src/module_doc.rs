@@ -1,43 +1,134 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
+use std::path::{Path, PathBuf};
 
-use crate::annotation::{HealthStatus, PatternStatus};
+use crate::annotation::{HealthStatus, PatternStatus, RiskSeverity};
 
-/// C4 architecture level for a module.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum C4Level {
+/// What kind of architecture element a module is: a C4 level or one of
+/// the other element kinds the C4 model draws.
+///
+/// Serialized as its `@c4` keyword (`container`, `external_system`, ...),
+/// with custom kinds as `custom:<name>`; `external` is read as
+/// `external_system`. Deserializing a string that names no kind fails;
+/// [`ElementKind::parse`] reads it as [`ElementKind::Unknown`] instead.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum ElementKind {
+    /// A software system as a whole, drawn as one box.
+    Context,
     Container,
     Component,
+    /// A user or role interacting with the system.
+    Person,
+    /// A system outside the one being documented.
+    ExternalSystem,
+    /// Infrastructure that containers are deployed onto.
+    DeploymentNode,
+    /// A project-specific kind, from `@c4 custom:<name>`.
+    Custom(String),
+    #[default]
     Unknown,
 }
 
-impl fmt::Display for C4Level {
+impl fmt::Display for ElementKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::Context => write!(f, "context"),
             Self::Container => write!(f, "container"),
             Self::Component => write!(f, "component"),
+            Self::Person => write!(f, "person"),
+            Self::ExternalSystem => write!(f, "external_system"),
+            Self::DeploymentNode => write!(f, "deployment_node"),
+            Self::Custom(name) => write!(f, "custom:{}", name),
             Self::Unknown => write!(f, "unknown"),
         }
     }
 }
 
-impl C4Level {
+/// The element kind's name before kinds beyond the C4 levels existed.
+#[deprecated(note = "renamed to `ElementKind`")]
+pub type C4Level = ElementKind;
+
+impl ElementKind {
     pub fn parse(s: &str) -> Self {
-        match s.trim().to_lowercase().as_str() {
+        let s = s.trim().to_lowercase();
+        match s.as_str() {
+            "context" => Self::Context,
             "container" => Self::Container,
             "component" => Self::Component,
-            _ => Self::Unknown,
+            "person" => Self::Person,
+            "external_system" | "external" => Self::ExternalSystem,
+            "deployment_node" => Self::DeploymentNode,
+            _ => match s.strip_prefix("custom:") {
+                Some(name) if is_kind_name(name) => Self::Custom(name.to_string()),
+                _ => Self::Unknown,
+            },
+        }
+    }
+
+    /// Whether diagrams treat the element as a container: containers and
+    /// custom kinds.
+    pub fn is_container(&self) -> bool {
+        matches!(self, Self::Container | Self::Custom(_))
+    }
+
+    /// Whether the container diagram draws the element: containers and
+    /// everything outside the system boundary.
+    pub fn in_container_view(&self) -> bool {
+        self.is_container() || self.is_external()
+    }
+
+    /// Whether the element sits outside the documented system's boundary.
+    pub fn is_external(&self) -> bool {
+        matches!(self, Self::Context | Self::Person | Self::ExternalSystem)
+    }
+
+    /// C4 macro that draws the element in Mermaid and PlantUML diagrams.
+    /// `Person`, `System`, and `System_Ext` take no technology argument.
+    pub fn c4_macro(&self) -> &'static str {
+        match self {
+            Self::Context => "System",
+            Self::Component | Self::Unknown => "Component",
+            Self::Person => "Person",
+            Self::ExternalSystem => "System_Ext",
+            Self::DeploymentNode => "Deployment_Node",
+            Self::Container | Self::Custom(_) => "Container",
         }
     }
 }
 
+fn is_kind_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+impl TryFrom<String> for ElementKind {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match Self::parse(&s) {
+            Self::Unknown if s != "unknown" => Err(format!("unknown element kind '{}'", s)),
+            kind => Ok(kind),
+        }
+    }
+}
+
+impl From<ElementKind> for String {
+    fn from(kind: ElementKind) -> Self {
+        kind.to_string()
+    }
+}
+
 /// A runtime dependency between modules.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Relationship {
     pub target: String,
     pub label: String,
     pub protocol: String,
+    /// Left out of diagrams, from a trailing `[hidden]`. Tables and the IR
+    /// still list it.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub hidden: bool,
 }
 
 /// A file entry from the module's file table.
@@ -48,22 +139,306 @@ pub struct FileEntry {
     pub pattern_status: PatternStatus,
     pub purpose: String,
     pub health: HealthStatus,
+    /// Non-blank line count, measured by the adapter when the file exists.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loc: Option<usize>,
+}
+
+/// A review sign-off from `@reviewed-by reviewer YYYY-MM-DD [hash]`.
+///
+/// The optional hash records the module's content hash at review time, so
+/// later edits can be flagged as a stale review.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Review {
+    pub reviewer: String,
+    pub date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+}
+
+/// An architectural risk from `@risk "description" severity=high`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Risk {
+    pub description: String,
+    pub severity: RiskSeverity,
+}
+
+/// A glossary term from `@term Name: definition`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Term {
+    pub name: String,
+    pub definition: String,
+}
+
+/// A named runtime scenario from `@flow name: a -> b -> c`: the elements
+/// a request passes through, in order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Flow {
+    pub name: String,
+    /// Module paths (or external system names) of each step.
+    pub steps: Vec<String>,
+}
+
+/// An external documentation link from `@doc <url> ["title"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocLink {
+    pub url: String,
+    /// Link text; empty when the marker had no title.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
+}
+
+impl DocLink {
+    /// Basic URL shape check: an `http`/`https` scheme, a non-empty host,
+    /// and no whitespace.
+    pub fn has_valid_url(&self) -> bool {
+        let rest = self
+            .url
+            .strip_prefix("https://")
+            .or_else(|| self.url.strip_prefix("http://"));
+        match rest {
+            Some(rest) => {
+                let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+                !host.is_empty() && !self.url.chars().any(char::is_whitespace)
+            }
+            None => false,
+        }
+    }
+
+    /// Link text, falling back to the URL.
+    pub fn text(&self) -> &str {
+        if self.title.is_empty() {
+            &self.url
+        } else {
+            &self.title
+        }
+    }
+}
+
+/// Where a pattern heuristic found its evidence: a source file in the
+/// module's directory and the item within it that carries the pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatternEvidence {
+    pub file: String,
+    /// Name of the matching item; empty when the evidence spans the file
+    /// rather than one item (e.g. a re-export facade).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub item: String,
+}
+
+impl fmt::Display for PatternEvidence {
+    /// `indicators.rs::IndicatorCalc`, or just the file name.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.item.is_empty() {
+            write!(f, "{}", self.file)
+        } else {
+            write!(f, "{}::{}", self.file, self.item)
+        }
+    }
 }
 
 /// A parsed module documentation unit.
 ///
 /// This is the core data structure — the JSON IR contract between
 /// language adapters and the core generator.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// `Default` is an unannotated element with an empty path, for building
+/// docs field by field: `ModuleDoc { module_path, ..Default::default() }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ModuleDoc {
     pub module_path: String,
+    /// Raw annotation text. Empty, and left out of the IR, when the IR was
+    /// emitted without content (see `--no-content`); generators then work
+    /// from the structured fields alone.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub content: String,
+    /// Hash of the raw annotation text, recorded when `content` is dropped
+    /// or rewritten, so review staleness and rename detection still see the
+    /// annotation as written. Absent while `content` is the raw text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
     pub source_file: String,
-    pub c4_level: C4Level,
+    /// Element kind, from the `@c4` marker. Keeps its original name in the IR.
+    pub c4_level: ElementKind,
     pub pattern: String,
     pub pattern_status: PatternStatus,
     pub description: String,
     pub parent_container: Option<String>,
     pub relationships: Vec<Relationship>,
     pub files: Vec<FileEntry>,
+    /// Directory the file table describes, from `@files-dir`. Relative paths
+    /// are resolved against the entry file's directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub files_dir: Option<String>,
+    /// Latest review sign-off, from `@reviewed-by`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<Review>,
+    /// Architectural risks, from `@risk`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub risks: Vec<Risk>,
+    /// Glossary terms defined by this module, from `@term`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub terms: Vec<Term>,
+    /// External documentation links (runbooks, dashboards, design docs), from `@doc`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<DocLink>,
+    /// Build condition the module is compiled under, e.g. `feature: gpu`.
+    /// `None` for modules that are always present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    /// Measurements keyed by metric name: complexity (lines, files, public
+    /// items, function length) and line coverage. Empty unless measured.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metrics: BTreeMap<String, u64>,
+    /// `unsafe` blocks and functions in the module's own source files.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub unsafe_count: usize,
+    /// Evidence behind a pattern verified by auto-promotion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern_evidence: Option<PatternEvidence>,
+    /// Declared as intentionally unconnected, from `@standalone`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub standalone: bool,
+    /// Stable identifier from `@id`, kept when the module moves. See
+    /// [`ModuleDoc::element_id`] for the default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Business grouping from `@group kind:name`, e.g. `domain:payments`.
+    /// Container diagrams draw a boundary around the containers of a group,
+    /// independent of where they live in the source tree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Declared a cross-cutting concern (logging, metrics, auth) with
+    /// `@crosscutting`. Diagrams draw no arrows into it; ARCHITECTURE.md
+    /// lists its users under Cross-cutting Concerns instead.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub crosscutting: bool,
+    /// Runtime scenarios from `@flow` and the Data Flow section, drawn as
+    /// sequence and C4 dynamic diagrams.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flows: Vec<Flow>,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
+}
+
+fn is_segment_char(c: char) -> bool {
+    c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'
+}
+
+impl ModuleDoc {
+    /// Identifier used for diagram nodes and anchors: the `@id`
+    /// annotation, else the module path with dots replaced by underscores.
+    pub fn element_id(&self) -> String {
+        self.id.clone().unwrap_or_else(|| Self::path_slug(&self.module_path))
+    }
+
+    /// [`ModuleDoc::element_id`] of the element at `module_path`, or the
+    /// path's slug when no such element exists (e.g. a relationship to an
+    /// unannotated module).
+    pub fn id_of(docs: &[ModuleDoc], module_path: &str) -> String {
+        docs.iter()
+            .find(|d| d.module_path == module_path)
+            .map_or_else(|| Self::path_slug(module_path), |d| d.element_id())
+    }
+
+    /// Default identifier for a module path: `bus.calc` -> `bus_calc`.
+    pub fn path_slug(module_path: &str) -> String {
+        module_path.replace('.', "_")
+    }
+
+    /// First segments adapters reserve for crate roots: `_lib`, `_main`,
+    /// and the binary targets under `_bin`.
+    pub const RESERVED_NAMES: &'static [&'static str] = &["_lib", "_main", "_bin"];
+
+    /// Check `module_path` against the module path grammar: one or more
+    /// segments of lowercase ASCII letters, digits, and `_`, joined by `.`.
+    /// Paths of this form slug to identifiers every diagram format accepts.
+    pub fn check_path(module_path: &str) -> Result<(), String> {
+        if module_path.is_empty() {
+            return Err("empty module path".to_string());
+        }
+        for segment in module_path.split('.') {
+            if segment.is_empty() {
+                return Err(format!("empty segment in module path '{}'", module_path));
+            }
+            if let Some(c) = segment.chars().find(|c| !is_segment_char(*c)) {
+                return Err(format!(
+                    "'{}' in module path '{}' is not a lowercase ASCII letter, digit, or `_`",
+                    c, module_path
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// A directory or file name as a module path segment: lowercased, with
+    /// every other character outside the grammar (spaces, dots, `-`,
+    /// non-ASCII) replaced by `_`, e.g. `Order Book` -> `order_book`.
+    pub fn path_segment(name: &str) -> String {
+        name.chars()
+            .map(|c| c.to_ascii_lowercase())
+            .map(|c| if is_segment_char(c) { c } else { '_' })
+            .collect()
+    }
+
+    /// `module_path` brought into the grammar segment by segment with
+    /// [`ModuleDoc::path_segment`], dropping empty segments.
+    pub fn normalize_path(module_path: &str) -> String {
+        module_path
+            .split('.')
+            .filter(|segment| !segment.is_empty())
+            .map(Self::path_segment)
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Find the crate root whose content provides the project narrative.
+    ///
+    /// A library root (`_lib`) wins; binary-only crates fall back to `_main`.
+    pub fn narrative_root(docs: &[ModuleDoc]) -> Option<&ModuleDoc> {
+        docs.iter()
+            .find(|d| d.module_path == "_lib")
+            .or_else(|| docs.iter().find(|d| d.module_path == "_main"))
+    }
+
+    /// Directory holding the module's files, against which its file catalog
+    /// is resolved.
+    ///
+    /// An explicit `@files-dir` wins. Otherwise it is the
+    /// [`ModuleDoc::own_dir`] of the source file, falling back to the
+    /// directory of a flat module file without one.
+    pub fn catalog_dir(&self) -> Option<PathBuf> {
+        let source = Path::new(&self.source_file);
+        let parent = source.parent()?;
+
+        if let Some(dir) = &self.files_dir {
+            return Some(parent.join(dir));
+        }
+        Self::own_dir(source).or_else(|| Some(parent.to_path_buf()))
+    }
+
+    /// Directory whose files belong to the module with entry file `source`.
+    ///
+    /// Entry files (`mod.rs`, `lib.rs`, `main.rs`, `index.ts`, sidecars)
+    /// own their directory. A flat module file such as `foo.rs` owns the
+    /// sibling `foo/` directory — the mod.rs-less layout where `foo.rs`
+    /// declares submodules living in `foo/` — and no directory when there
+    /// is none.
+    pub fn own_dir(source: &Path) -> Option<PathBuf> {
+        let parent = source.parent()?;
+        let stem = source.file_stem()?.to_str()?;
+        let is_code = source.extension().is_some_and(|e| e == "rs" || e == "ts");
+
+        if is_code && !matches!(stem, "mod" | "lib" | "main" | "index") {
+            let dir = parent.join(stem);
+            return dir.is_dir().then_some(dir);
+        }
+        Some(parent.to_path_buf())
+    }
 }
@@ -1,5 +1,11 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
+use crate::annotation::{PatternStatus, RiskSeverity};
+use crate::config::{Severity, ValidateConfig};
+use crate::module_doc::PatternEvidence;
+
 /// Aggregated health report across all architectural elements.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HealthReport {
@@ -13,6 +19,41 @@ pub struct HealthReport {
     pub patterns_total: usize,
     pub patterns_planned: usize,
     pub patterns_verified: usize,
+    /// File-table rows claiming a pattern, by confidence.
+    #[serde(default)]
+    pub file_patterns_total: usize,
+    #[serde(default)]
+    pub file_patterns_planned: usize,
+    #[serde(default)]
+    pub file_patterns_verified: usize,
+    #[serde(default)]
+    pub reviews_current: usize,
+    #[serde(default)]
+    pub reviews_unreviewed: usize,
+    #[serde(default)]
+    pub reviews_stale: usize,
+    /// Reviews recorded without a content hash, which cannot be checked
+    /// for staleness.
+    #[serde(default)]
+    pub reviews_unverifiable: usize,
+    #[serde(default)]
+    pub stale_reviews: Vec<StaleReview>,
+    /// Lines of code across files with a measured size, by maturity.
+    /// All zero when the IR carries no line counts.
+    #[serde(default)]
+    pub loc_total: usize,
+    #[serde(default)]
+    pub loc_planned: usize,
+    #[serde(default)]
+    pub loc_active: usize,
+    #[serde(default)]
+    pub loc_stable: usize,
+    /// `unsafe` blocks and functions across all elements.
+    #[serde(default)]
+    pub unsafe_total: usize,
+    /// Project-wide line coverage percentage, when a coverage report was supplied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<f64>,
     pub per_element: Vec<ElementHealth>,
 }
 
@@ -27,6 +68,31 @@ pub struct ElementHealth {
     pub files_stable: usize,
     pub pattern: String,
     pub pattern_confidence: String,
+    /// `reviewed`, `unreviewed`, `stale`, or `unverifiable`.
+    #[serde(default)]
+    pub review_status: String,
+    /// Current content hash, to record in `@reviewed-by` when signing off.
+    #[serde(default)]
+    pub content_hash: String,
+    /// Lines of code across the element's measured files.
+    #[serde(default)]
+    pub loc: usize,
+    /// `unsafe` blocks and functions in the element's own sources.
+    #[serde(default)]
+    pub unsafe_count: usize,
+    /// Line coverage percentage, when a coverage report was supplied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<f64>,
+}
+
+/// A module whose content changed after its last review.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleReview {
+    pub element: String,
+    pub reviewer: String,
+    pub reviewed_on: String,
+    pub reviewed_hash: String,
+    pub current_hash: String,
 }
 
 /// Validation report for file table integrity.
@@ -34,14 +100,54 @@ pub struct ElementHealth {
 pub struct ValidationReport {
     pub ghosts: Vec<GhostEntry>,
     pub orphans: Vec<OrphanEntry>,
+    #[serde(default)]
+    pub invalid_links: Vec<InvalidLink>,
+    /// Severity of each kind of finding, from `[validate]` in archidoc.toml.
+    #[serde(default)]
+    pub severities: ValidateConfig,
 }
 
 impl ValidationReport {
     pub fn is_clean(&self) -> bool {
-        self.ghosts.is_empty() && self.orphans.is_empty()
+        self.ghosts.is_empty() && self.orphans.is_empty() && self.invalid_links.is_empty()
+    }
+
+    /// Findings of the given severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        [
+            (self.severities.ghost, self.ghosts.len()),
+            (self.severities.orphan, self.orphans.len()),
+            (self.severities.invalid_link, self.invalid_links.len()),
+        ]
+        .iter()
+        .filter(|(s, _)| *s == severity)
+        .map(|(_, n)| n)
+        .sum()
+    }
+
+    /// Whether the run passes: no errors, and no warnings either when
+    /// `warnings_as_errors` is set.
+    pub fn passes(&self, warnings_as_errors: bool) -> bool {
+        self.count(Severity::Error) == 0 && (!warnings_as_errors || self.count(Severity::Warning) == 0)
     }
 }
 
+/// Result of checking JSON IR against the `ModuleDoc[]` schema.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IrValidationReport {
+    pub valid: bool,
+    pub violations: Vec<IrViolation>,
+}
+
+/// A value in JSON IR that breaks the schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IrViolation {
+    /// JSON pointer to the value (e.g. `/3/files/0/health`); empty when the
+    /// document as a whole is at fault.
+    pub pointer: String,
+    pub message: String,
+}
+
 /// A file listed in a catalog but not present on disk.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GhostEntry {
@@ -58,6 +164,13 @@ pub struct OrphanEntry {
     pub source_dir: String,
 }
 
+/// An `@doc` link whose URL is not a well-formed http(s) URL.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvalidLink {
+    pub element: String,
+    pub url: String,
+}
+
 /// Drift detection report — comparison of generated vs existing docs.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DriftReport {
@@ -80,4 +193,296 @@ pub struct DriftedFile {
     pub path: String,
     pub expected_lines: usize,
     pub actual_lines: usize,
+    /// Unified diff from the file on disk to the generated content.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub diff: String,
+}
+
+/// Structural difference between two IR snapshots.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IrDiff {
+    pub added_elements: Vec<String>,
+    pub removed_elements: Vec<String>,
+    /// Elements whose module path changed; not listed as added or removed.
+    #[serde(default)]
+    pub renamed_elements: Vec<ElementRename>,
+    pub changed_elements: Vec<ElementChange>,
+    pub added_relationships: Vec<RelationshipChange>,
+    pub removed_relationships: Vec<RelationshipChange>,
+}
+
+impl IrDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_elements.is_empty()
+            && self.removed_elements.is_empty()
+            && self.renamed_elements.is_empty()
+            && self.changed_elements.is_empty()
+            && self.added_relationships.is_empty()
+            && self.removed_relationships.is_empty()
+    }
+}
+
+/// An element that probably moved from one module path to another.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElementRename {
+    pub from: String,
+    pub to: String,
+    /// What identified the pair: `id` (same `@id`), `content` (same
+    /// annotation content hash), or `files` (same file table).
+    pub matched_by: String,
+}
+
+/// An element present in both snapshots whose attributes changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ElementChange {
+    pub element: String,
+    pub fields: Vec<FieldChange>,
+}
+
+/// A single attribute change on an element.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// A relationship added or removed between snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelationshipChange {
+    pub from: String,
+    pub to: String,
+    pub label: String,
+    pub protocol: String,
+}
+
+/// Gaps between a planned (to-be) architecture and the extracted (as-is) one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GapReport {
+    /// Planned elements the code does not have yet.
+    pub missing_elements: Vec<String>,
+    /// Planned relationships the code does not have yet.
+    pub missing_relationships: Vec<RelationshipChange>,
+    /// Dependencies of planned elements that the plan does not include.
+    pub unplanned_relationships: Vec<RelationshipChange>,
+}
+
+impl GapReport {
+    pub fn is_empty(&self) -> bool {
+        self.missing_elements.is_empty()
+            && self.missing_relationships.is_empty()
+            && self.unplanned_relationships.is_empty()
+    }
+}
+
+/// Portfolio roll-up of many repositories' IR, health, and drift reports.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrgReport {
+    pub repos: Vec<RepoSummary>,
+    /// Patterns in use, most widely adopted first.
+    pub patterns: Vec<PatternAdoption>,
+    /// Dependencies between repositories, from relationships whose target
+    /// lives in another repository.
+    pub relationships: Vec<RepoRelationship>,
+}
+
+/// One repository's line in an [`OrgReport`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RepoSummary {
+    pub name: String,
+    pub elements: usize,
+    pub containers: usize,
+    pub components: usize,
+    pub files: usize,
+    /// File maturity from 0 to 100: stable files count fully, active ones
+    /// half. `None` when the repository catalogs no files.
+    pub health_score: Option<u32>,
+    pub patterns_total: usize,
+    pub patterns_verified: usize,
+    /// Whether the generated docs had drifted; `None` without a drift report.
+    pub drifted: Option<bool>,
+}
+
+/// How widely one pattern is used across repositories.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatternAdoption {
+    pub pattern: String,
+    pub repos: Vec<String>,
+    pub elements: usize,
+}
+
+/// Relationships from one repository into another, counted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoRelationship {
+    pub from: String,
+    pub to: String,
+    pub count: usize,
+}
+
+/// Result of checking the current architecture against an approved snapshot.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApprovalReport {
+    pub reviewer: String,
+    pub approved_at: String,
+    pub new_elements: Vec<String>,
+    pub removed_elements: Vec<String>,
+    pub renamed_elements: Vec<ElementRename>,
+    pub new_dependencies: Vec<RelationshipChange>,
+}
+
+impl ApprovalReport {
+    pub fn is_approved(&self) -> bool {
+        self.new_elements.is_empty()
+            && self.removed_elements.is_empty()
+            && self.renamed_elements.is_empty()
+            && self.new_dependencies.is_empty()
+    }
+}
+
+/// Consolidated risk register across all elements, most severe first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RiskRegister {
+    pub entries: Vec<RiskEntry>,
+}
+
+/// A risk attributed to the element that declared it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RiskEntry {
+    pub element: String,
+    pub description: String,
+    pub severity: RiskSeverity,
+}
+
+/// Findings of `--lint`, in element order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintReport {
+    pub findings: Vec<LintFinding>,
+}
+
+impl LintReport {
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// A problem with an element's annotation prose.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintFinding {
+    pub element: String,
+    /// Rule id, e.g. `description_placeholder`.
+    pub rule: String,
+    pub message: String,
+    /// Source file and 1-based line of the problem, for findings tied to a
+    /// specific spot in the annotation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+/// Machine-readable summary of one run (`--report`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompileReport {
+    pub modules_found: usize,
+    pub skipped_files: Vec<SkippedFile>,
+    pub warnings: Vec<String>,
+    pub phases: Vec<PhaseTiming>,
+    pub outputs: Vec<OutputFile>,
+    /// Module path to the id its element has in diagrams and anchors, after
+    /// collisions were resolved.
+    #[serde(default)]
+    pub element_ids: BTreeMap<String, String>,
+}
+
+/// A candidate source or sidecar file that did not produce a module.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedFile {
+    pub path: String,
+    pub reason: String,
+}
+
+/// Wall-clock time spent in one phase of a run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub phase: String,
+    pub duration_ms: f64,
+}
+
+/// A file written by a run, with a hash of its content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputFile {
+    pub path: String,
+    pub bytes: usize,
+    pub hash: String,
+}
+
+/// One line of the local usage statistics file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunStats {
+    /// Run date as `YYYY-MM-DD` (UTC).
+    pub date: String,
+    /// What the run did: `generate`, `check`, `validate`, ...
+    pub mode: String,
+    /// Total time across all recorded phases.
+    pub duration_ms: f64,
+    pub modules: usize,
+}
+
+/// Outcome of one fitness function, for the Architectural Fitness section
+/// of ARCHITECTURE.md.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FitnessSummary {
+    pub name: String,
+    /// Modules the rule applied to.
+    pub checked: usize,
+    /// Failures reported; a module may fail more than once.
+    pub failed: usize,
+}
+
+impl FitnessSummary {
+    pub fn passed(&self) -> bool {
+        self.failed == 0
+    }
+}
+
+/// An automatic change to an element's pattern status, such as
+/// auto-promotion from `planned` to `verified`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusChange {
+    /// Module path, or `module/file.rs` for a file-table row.
+    pub element: String,
+    pub pattern: String,
+    pub from: PatternStatus,
+    pub to: PatternStatus,
+    /// What the heuristic matched; absent for changes without evidence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<PatternEvidence>,
+}
+
+/// One line of the promotions audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromotionRecord {
+    /// When the change was made, as `YYYY-MM-DDTHH:MM:SSZ` (UTC).
+    pub timestamp: String,
+    /// archidoc version that made the change.
+    pub tool_version: String,
+    #[serde(flatten)]
+    pub change: StatusChange,
+}
+
+/// A language adapter as listed by `archidoc adapters`: whether the project
+/// uses it, its version, and the annotation features it honors.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdapterStatus {
+    pub name: String,
+    pub language: String,
+    /// Installed version; `None` when the adapter could not be found.
+    pub version: Option<String>,
+    /// The project has a manifest for the adapter's language.
+    pub detected: bool,
+    /// Feature ids the adapter honors.
+    pub supported: Vec<String>,
+    /// Feature ids the adapter ignores; annotations using them are dropped
+    /// for this language.
+    pub gaps: Vec<String>,
 }
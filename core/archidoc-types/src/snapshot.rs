@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+use crate::module_doc::ModuleDoc;
+
+/// An IR snapshot signed off by a reviewer.
+///
+/// Written by `archidoc freeze` to `.archidoc/approved.json`; the current
+/// architecture is checked against it until the next freeze.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovedSnapshot {
+    pub reviewer: String,
+    /// Approval date as `YYYY-MM-DD`.
+    pub approved_at: String,
+    /// Elements with only the fields an approval compares, and source
+    /// files relative to the project root.
+    pub elements: Vec<ModuleDoc>,
+}
@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use archidoc_types::ModuleDoc;
+use serde_json::Value;
+
+/// A language adapter under conformance test.
+///
+/// Fixtures describe modules as raw annotation text, without comment
+/// markers; the adapter writes them the way its language documents a module
+/// and extracts them again. An adapter passes when its IR matches the
+/// fixture's expected IR, ignoring `source_file` and `content`, which are
+/// language-specific.
+pub trait ConformanceAdapter {
+    /// Write each `(module_path, annotation)` as an annotated module of a
+    /// fresh source tree and return the docs the adapter extracts from it.
+    fn extract(&self, modules: &[(String, String)]) -> Vec<ModuleDoc>;
+}
+
+/// One fixture: a source tree and the IR every adapter must produce for it.
+///
+/// On disk, `core/tests/conformance/<name>/annotations/<module_path>.md`
+/// holds each module's annotation and `expected.json` the expected IR.
+#[derive(Debug, Clone)]
+pub struct Fixture {
+    pub name: String,
+    /// `(module_path, annotation)`, in module path order.
+    pub modules: Vec<(String, String)>,
+    pub expected: Value,
+}
+
+/// Directory holding the fixtures.
+pub fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("conformance")
+}
+
+/// Every fixture, in name order.
+pub fn fixtures() -> Vec<Fixture> {
+    read_dir_sorted(&fixtures_dir())
+        .into_iter()
+        .filter(|p| p.is_dir())
+        .map(|dir| load_fixture(&dir))
+        .collect()
+}
+
+fn load_fixture(dir: &Path) -> Fixture {
+    let name = dir.file_name().unwrap().to_string_lossy().to_string();
+    let modules = read_dir_sorted(&dir.join("annotations"))
+        .into_iter()
+        .filter_map(|path| {
+            let module_path = path.file_name()?.to_str()?.strip_suffix(".md")?.to_string();
+            let annotation = fs::read_to_string(&path).expect("failed to read annotation");
+            Some((module_path, annotation))
+        })
+        .collect();
+    let expected_path = dir.join("expected.json");
+    let expected = fs::read_to_string(&expected_path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", expected_path.display(), e));
+    let expected = serde_json::from_str(&expected)
+        .unwrap_or_else(|e| panic!("invalid JSON in {}: {}", expected_path.display(), e));
+    Fixture {
+        name,
+        modules,
+        expected,
+    }
+}
+
+fn read_dir_sorted(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", dir.display(), e))
+        .map(|entry| entry.expect("failed to read directory entry").path())
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// `docs` as compared against a fixture: sorted by module path, without
+/// the language-specific `source_file` and `content`.
+pub fn comparable_ir(docs: &[ModuleDoc]) -> Value {
+    let mut docs = docs.to_vec();
+    docs.sort_by(|a, b| a.module_path.cmp(&b.module_path));
+    let mut ir = serde_json::to_value(&docs).expect("failed to serialize ModuleDoc");
+    for doc in ir.as_array_mut().expect("IR is an array") {
+        let doc = doc.as_object_mut().expect("ModuleDoc is an object");
+        doc.remove("source_file");
+        doc.remove("content");
+    }
+    ir
+}
+
+/// Run `fixture` through `adapter`. Fails with both IRs when they differ.
+pub fn check(adapter: &dyn ConformanceAdapter, fixture: &Fixture) -> Result<(), String> {
+    let actual = comparable_ir(&adapter.extract(&fixture.modules));
+    if actual == fixture.expected {
+        return Ok(());
+    }
+    Err(format!(
+        "fixture '{}' produced different IR.\nexpected:\n{}\nactual:\n{}",
+        fixture.name,
+        serde_json::to_string_pretty(&fixture.expected).unwrap(),
+        serde_json::to_string_pretty(&actual).unwrap()
+    ))
+}
+
+/// Run every fixture through `adapter`, panicking with each mismatch.
+pub fn assert_conforms(adapter: &dyn ConformanceAdapter) {
+    let failures: Vec<String> = fixtures()
+        .iter()
+        .filter_map(|fixture| check(adapter, fixture).err())
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
@@ -1,7 +1,7 @@
 use std::fs;
 use std::path::PathBuf;
 
-use archidoc_types::{HealthReport, ModuleDoc, ValidationReport, DriftReport};
+use archidoc_types::{ApprovalReport, HealthReport, ModuleDoc, ProjectConfig, ValidationReport, DriftReport};
 use tempfile::TempDir;
 
 use crate::drivers::protocol_driver::ArchitectureDriver;
@@ -22,6 +22,7 @@ pub struct InMemoryArchitectureDriver {
     suggestion_output: Option<String>,
     ir_snapshots: std::collections::HashMap<String, String>,
     merged_results: Option<Vec<ModuleDoc>>,
+    merge_warnings: Vec<String>,
 }
 
 impl InMemoryArchitectureDriver {
@@ -36,6 +37,7 @@ impl InMemoryArchitectureDriver {
             suggestion_output: None,
             ir_snapshots: std::collections::HashMap::new(),
             merged_results: None,
+            merge_warnings: Vec::new(),
         }
     }
 
@@ -62,23 +64,66 @@ impl InMemoryArchitectureDriver {
         self.output_dir.path().join("ARCHITECTURE.md")
     }
 
+    fn check_approval(&self) -> ApprovalReport {
+        let approved = archidoc_engine::freeze::read_approved(self.output_dir.path())
+            .expect("no approved snapshot — call freeze_architecture first");
+        archidoc_engine::freeze::check_approved(&approved, &self.results)
+    }
+
+    fn project_config(&self) -> ProjectConfig {
+        archidoc_engine::config::load(self.source_tree.root()).expect("invalid archidoc.toml")
+    }
+
     fn generate_architecture(&mut self) {
         let link_base = self.output_dir.path().to_path_buf();
-        let content = archidoc_engine::architecture::generate(&self.results, &link_base);
+        let config = self.project_config();
+        let fitness = if config.generators.fitness {
+            archidoc_rust::fitness::run_all(&self.results, &config)
+        } else {
+            Vec::new()
+        };
+        let content = archidoc_engine::architecture::generate_document(
+            &self.results,
+            &link_base,
+            &fitness,
+            &archidoc_engine::architecture::Snapshot::default(),
+            config.generators.language,
+            &config.architecture,
+            &config.diagrams,
+        );
         fs::write(self.arch_file_path(), &content)
             .expect("failed to write ARCHITECTURE.md");
         self.architecture_content = Some(content);
     }
 }
 
+impl Default for InMemoryArchitectureDriver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ArchitectureDriver for InMemoryArchitectureDriver {
     fn create_annotated_source(&mut self, name: &str, content: &str) {
         self.source_tree.create_module(name, content);
     }
 
+    fn create_workspace(&mut self, members: &[String]) {
+        self.source_tree.create_workspace(members);
+    }
+
+    fn create_crate_source(&mut self, crate_dir: &str, name: &str, content: &str) {
+        self.source_tree.create_crate_module(crate_dir, name, content);
+    }
+
     fn compile(&mut self) {
-        let src_dir = self.source_tree.root().join("src");
-        self.results = archidoc_rust::walker::extract_all_docs(&src_dir);
+        self.results = archidoc_rust::walker::extract(&self.source_tree.source_root()).docs;
+        self.generate_architecture();
+        self.compiled = true;
+    }
+
+    fn compile_crate(&mut self, crate_dir: &str) {
+        self.results = archidoc_rust::walker::extract(&self.source_tree.crate_src(crate_dir)).docs;
         self.generate_architecture();
         self.compiled = true;
     }
@@ -105,6 +150,15 @@ impl ArchitectureDriver for InMemoryArchitectureDriver {
         );
     }
 
+    fn confirm_architecture_omits(&self, unexpected: &str) {
+        let content = self.arch_content();
+        assert!(
+            !content.contains(unexpected),
+            "ARCHITECTURE.md unexpectedly contains '{}'. Content:\n{}",
+            unexpected, content
+        );
+    }
+
     fn confirm_index_lists(&self, name: &str) {
         let content = self.arch_content();
         assert!(
@@ -332,6 +386,20 @@ impl ArchitectureDriver for InMemoryArchitectureDriver {
         );
     }
 
+    fn confirm_health_file_pattern_count(&self, confidence: &str, expected: usize) {
+        let report = self.request_health_report();
+        let actual = match confidence {
+            "planned" => report.file_patterns_planned,
+            "verified" => report.file_patterns_verified,
+            _ => panic!("unknown confidence: '{}'", confidence),
+        };
+        assert_eq!(
+            actual, expected,
+            "health report: expected {} '{}' file patterns, got {}",
+            expected, confidence, actual
+        );
+    }
+
     fn confirm_health_total_files(&self, expected: usize) {
         let report = self.request_health_report();
         assert_eq!(
@@ -341,6 +409,35 @@ impl ArchitectureDriver for InMemoryArchitectureDriver {
         );
     }
 
+    fn confirm_unsafe_count(&self, element: &str, expected: usize) {
+        let report = self.request_health_report();
+        let elem = report
+            .per_element
+            .iter()
+            .find(|e| e.name == element)
+            .unwrap_or_else(|| panic!("no health entry for '{}'", element));
+        assert_eq!(
+            elem.unsafe_count, expected,
+            "health report: expected {} unsafe site(s) in '{}', got {}",
+            expected, element, elem.unsafe_count
+        );
+    }
+
+    fn confirm_health_loc(&self, maturity: &str, expected: usize) {
+        let report = self.request_health_report();
+        let actual = match maturity {
+            "planned" => report.loc_planned,
+            "active" => report.loc_active,
+            "stable" => report.loc_stable,
+            _ => panic!("unknown maturity: '{}'", maturity),
+        };
+        assert_eq!(
+            actual, expected,
+            "health report: expected {} lines of '{}' code, got {}",
+            expected, maturity, actual
+        );
+    }
+
     // =========================================================================
     // Phase B — Validation (ghost/orphan detection)
     // =========================================================================
@@ -383,8 +480,20 @@ impl ArchitectureDriver for InMemoryArchitectureDriver {
         let report = self.validate();
         assert!(
             report.is_clean(),
-            "expected clean validation but found {} ghosts and {} orphans",
-            report.ghosts.len(), report.orphans.len()
+            "expected clean validation but found {} ghosts, {} orphans, and {} invalid links",
+            report.ghosts.len(), report.orphans.len(), report.invalid_links.len()
+        );
+    }
+
+    fn confirm_validation_passes(&self, warnings_as_errors: bool, passes: bool) {
+        let report = self.validate();
+        assert_eq!(
+            report.passes(warnings_as_errors),
+            passes,
+            "expected validation to {} (warnings as errors: {}): {} ghosts, {} orphans, {} invalid links",
+            if passes { "pass" } else { "fail" },
+            warnings_as_errors,
+            report.ghosts.len(), report.orphans.len(), report.invalid_links.len()
         );
     }
 
@@ -395,7 +504,7 @@ impl ArchitectureDriver for InMemoryArchitectureDriver {
     fn modify_source_annotation(&mut self, name: &str, new_purpose: &str) {
         let content = format!(
             "@c4 container\n\n# {}\n\n{}\n",
-            name.split('.').last().unwrap_or(name),
+            name.split('.').next_back().unwrap_or(name),
             new_purpose
         );
         self.source_tree.create_module(name, &content);
@@ -403,7 +512,7 @@ impl ArchitectureDriver for InMemoryArchitectureDriver {
 
     fn check_for_drift(&self) -> DriftReport {
         let src_dir = self.source_tree.root().join("src");
-        let fresh_docs = archidoc_rust::walker::extract_all_docs(&src_dir);
+        let fresh_docs = archidoc_rust::walker::extract(&src_dir).docs;
         let link_base = self.output_dir.path().to_path_buf();
         archidoc_engine::check::check_drift(&fresh_docs, &self.arch_file_path(), &link_base)
     }
@@ -537,13 +646,60 @@ impl ArchitectureDriver for InMemoryArchitectureDriver {
         self.source_tree.create_code_file(element, filename, code);
     }
 
+    fn write_project_config(&mut self, content: &str) {
+        let path = self.source_tree.root().join(archidoc_engine::config::CONFIG_FILE);
+        let mut config = fs::read_to_string(&path).unwrap_or_default();
+        config.push_str(content);
+        fs::write(path, config).expect("failed to write archidoc.toml");
+    }
+
     fn verify_patterns(&mut self) {
         assert!(self.compiled, "must compile before verifying patterns");
-        archidoc_rust::promote::auto_promote(&mut self.results);
+        let thresholds = self.project_config().heuristics;
+        let changes = archidoc_rust::promote::auto_promote(&mut self.results, &thresholds);
+        archidoc_engine::promotions::record(
+            self.source_tree.root(),
+            &changes,
+            &archidoc_engine::date::now_timestamp(),
+            env!("CARGO_PKG_VERSION"),
+        )
+        .expect("failed to write promotions log");
+    }
+
+    fn confirm_pattern_evidence(&self, name: &str, evidence: &str) {
+        let doc = self.find_module(name);
+        let actual = doc.pattern_evidence.as_ref().map(|e| e.to_string()).unwrap_or_default();
+        assert_eq!(
+            actual, evidence,
+            "element '{}': expected pattern evidence '{}', got '{}'",
+            name, evidence, actual
+        );
+    }
+
+    fn confirm_promotion_logged(&self, name: &str, evidence: &str) {
+        let log = archidoc_engine::promotions::load(self.source_tree.root()).expect("unreadable promotions log");
+        let entry = log
+            .iter()
+            .rev()
+            .find(|r| r.change.element == name)
+            .unwrap_or_else(|| panic!("no promotion logged for '{}': {:?}", name, log));
+        let actual = entry.change.evidence.as_ref().map(|e| e.to_string()).unwrap_or_default();
+        assert_eq!(
+            (entry.change.to, actual.as_str()),
+            (archidoc_types::PatternStatus::Verified, evidence),
+            "element '{}': unexpected promotion log entry {:?}",
+            name, entry
+        );
+        assert!(!entry.timestamp.is_empty() && !entry.tool_version.is_empty());
+    }
+
+    fn confirm_promotion_log_size(&self, expected: usize) {
+        let log = archidoc_engine::promotions::load(self.source_tree.root()).expect("unreadable promotions log");
+        assert_eq!(log.len(), expected, "expected {} promotion log entries, got {:?}", expected, log);
     }
 
     fn confirm_fitness_passes(&self, fitness_name: &str) {
-        let result = archidoc_rust::fitness::run_fitness(fitness_name, &self.results)
+        let result = archidoc_rust::fitness::run_fitness(fitness_name, &self.results, &self.project_config())
             .unwrap_or_else(|| panic!("unknown fitness function: '{}'", fitness_name));
         assert!(
             result.passed,
@@ -555,7 +711,7 @@ impl ArchitectureDriver for InMemoryArchitectureDriver {
     }
 
     fn confirm_fitness_fails(&self, fitness_name: &str, failing_module: &str) {
-        let result = archidoc_rust::fitness::run_fitness(fitness_name, &self.results)
+        let result = archidoc_rust::fitness::run_fitness(fitness_name, &self.results, &self.project_config())
             .unwrap_or_else(|| panic!("unknown fitness function: '{}'", fitness_name));
         assert!(
             !result.passed,
@@ -628,7 +784,10 @@ impl ArchitectureDriver for InMemoryArchitectureDriver {
         }).collect();
 
         match archidoc_engine::merge::merge_ir(ir_sets) {
-            Ok(docs) => self.merged_results = Some(docs),
+            Ok(outcome) => {
+                self.merged_results = Some(outcome.docs);
+                self.merge_warnings = outcome.warnings;
+            }
             Err(e) => panic!("merge failed: {}", e),
         }
     }
@@ -644,6 +803,14 @@ impl ArchitectureDriver for InMemoryArchitectureDriver {
         );
     }
 
+    fn confirm_merge_warned_about(&self, name: &str) {
+        assert!(
+            self.merge_warnings.iter().any(|w| w.contains(&format!("'{}'", name))),
+            "expected a merge warning about '{}', got: {:?}",
+            name, self.merge_warnings
+        );
+    }
+
     fn confirm_merged_contains(&self, name: &str, level: &str) {
         let merged = self.merged_results.as_ref()
             .expect("no merged results — call merge_ir_snapshots first");
@@ -655,4 +822,188 @@ impl ArchitectureDriver for InMemoryArchitectureDriver {
             merged.iter().map(|d| format!("{} ({})", d.module_path, d.c4_level)).collect::<Vec<_>>()
         );
     }
+
+    // =========================================================================
+    // Architecture approval
+    // =========================================================================
+
+    fn freeze_architecture(&mut self, reviewer: &str, date: &str) {
+        assert!(self.compiled, "must compile before freezing");
+        let snapshot = archidoc_engine::freeze::freeze(&self.results, self.source_tree.root(), reviewer, date);
+        archidoc_engine::freeze::write_approved(self.output_dir.path(), &snapshot)
+            .expect("failed to write approved snapshot");
+    }
+
+    fn confirm_matches_approved(&self) {
+        let report = self.check_approval();
+        assert!(
+            report.is_approved(),
+            "expected architecture to match approved snapshot. Report:\n{}",
+            archidoc_engine::freeze::format_approval_report(&report)
+        );
+    }
+
+    fn confirm_unapproved_element(&self, name: &str) {
+        let report = self.check_approval();
+        assert!(
+            report.new_elements.iter().any(|e| e == name),
+            "expected '{}' to be flagged as unapproved. New elements: {:?}",
+            name, report.new_elements
+        );
+    }
+
+    fn confirm_unapproved_dependency(&self, from: &str, to: &str) {
+        let report = self.check_approval();
+        assert!(
+            report.new_dependencies.iter().any(|r| r.from == from && r.to == to),
+            "expected dependency {} -> {} to be flagged as unapproved. New dependencies: {:?}",
+            from, to,
+            report.new_dependencies.iter().map(|r| format!("{} -> {}", r.from, r.to)).collect::<Vec<_>>()
+        );
+    }
+
+    // =========================================================================
+    // Review sign-off tracking
+    // =========================================================================
+
+    fn reviewed_content_hash(&self, name: &str) -> String {
+        assert!(self.compiled, "must compile before reviewing");
+        let doc = self.results.iter().find(|d| d.module_path == name)
+            .unwrap_or_else(|| panic!("element '{}' not found in compiled output", name));
+        archidoc_engine::hash::annotation_hash(doc).unwrap_or_default()
+    }
+
+    fn confirm_health_review_count(&self, status: &str, expected: usize) {
+        let report = self.request_health_report();
+        let actual = match status {
+            "reviewed" => report.reviews_current,
+            "unreviewed" => report.reviews_unreviewed,
+            "stale" => report.reviews_stale,
+            "unverifiable" => report.reviews_unverifiable,
+            _ => panic!("unknown review status: '{}'", status),
+        };
+        assert_eq!(
+            actual, expected,
+            "health report: expected {} '{}' reviews, got {}",
+            expected, status, actual
+        );
+    }
+
+    fn confirm_stale_review(&self, name: &str) {
+        let report = self.request_health_report();
+        assert!(
+            report.stale_reviews.iter().any(|s| s.element == name),
+            "expected '{}' to have a stale review. Stale: {:?}",
+            name,
+            report.stale_reviews.iter().map(|s| &s.element).collect::<Vec<_>>()
+        );
+    }
+
+    // =========================================================================
+    // Risk register
+    // =========================================================================
+
+    fn confirm_risk_registered(&self, element: &str, description: &str, severity: &str) {
+        let register = archidoc_engine::risk::collect_risks(&self.results);
+        let found = register.entries.iter().any(|r| {
+            r.element == element
+                && r.description == description
+                && r.severity.to_string() == severity
+        });
+        assert!(
+            found,
+            "risk '{}' ({}) not registered for '{}'. Register: {:?}",
+            description, severity, element,
+            register.entries.iter().map(|r| format!("{}: {} ({})", r.element, r.description, r.severity)).collect::<Vec<_>>()
+        );
+    }
+
+    fn confirm_risk_count(&self, expected: usize) {
+        let register = archidoc_engine::risk::collect_risks(&self.results);
+        assert_eq!(
+            register.entries.len(), expected,
+            "risk register: expected {} entries, got {}",
+            expected, register.entries.len()
+        );
+    }
+
+    // =========================================================================
+    // Glossary
+    // =========================================================================
+
+    fn confirm_glossary_entry(&self, term: &str, defined_in: &str) {
+        let content = self.arch_content();
+        let glossary = content
+            .find("## Glossary")
+            .map(|start| &content[start..])
+            .unwrap_or_else(|| panic!("ARCHITECTURE.md has no Glossary section. Content:\n{}", content));
+        let entry = glossary
+            .lines()
+            .find(|l| l.starts_with(&format!("- **{}**", term)))
+            .unwrap_or_else(|| panic!("glossary does not define '{}'. Glossary:\n{}", term, glossary));
+        assert!(
+            entry.contains(&format!("[{}](", defined_in)),
+            "glossary entry for '{}' does not link to '{}': {}",
+            term, defined_in, entry
+        );
+    }
+
+    // =========================================================================
+    // Patterns in Use appendix
+    // =========================================================================
+
+    fn confirm_pattern_in_use(&self, pattern: &str, claimed_by: &str, status: &str) {
+        let content = self.arch_content();
+        let appendix = content
+            .find("## Patterns in Use")
+            .map(|start| &content[start..])
+            .unwrap_or_else(|| panic!("ARCHITECTURE.md has no Patterns in Use section. Content:\n{}", content));
+        let prefix = format!("| {} | [{}](", pattern, claimed_by);
+        let suffix = format!(") | {} |", status);
+        assert!(
+            appendix.lines().any(|l| l.starts_with(&prefix) && l.ends_with(&suffix)),
+            "patterns appendix has no {} claim by '{}' ({}). Appendix:\n{}",
+            pattern, claimed_by, status, appendix
+        );
+    }
+
+    // =========================================================================
+    // Module README scaffolding
+    // =========================================================================
+
+    fn scaffold_readmes(&mut self) {
+        assert!(self.compiled, "must compile before scaffolding READMEs");
+        archidoc_engine::readme::scaffold_readmes(&self.results, None)
+            .expect("failed to scaffold READMEs");
+    }
+
+    fn place_hand_written_readme(&mut self, element: &str, content: &str) {
+        let path = self.source_tree.module_dir(element).join("README.md");
+        std::fs::write(&path, content).expect("failed to write README.md");
+    }
+
+    fn confirm_module_readme_contains(&self, element: &str, expected: &str) {
+        let path = self.source_tree.module_dir(element).join("README.md");
+        let content = std::fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("no README.md for '{}' at {}", element, path.display()));
+        assert!(
+            content.contains(expected),
+            "README.md for '{}' does not contain '{}'. Content:\n{}",
+            element, expected, content
+        );
+    }
+
+    // =========================================================================
+    // Documentation links
+    // =========================================================================
+
+    fn confirm_invalid_link(&self, element: &str, url: &str) {
+        let report = self.validate();
+        assert!(
+            report.invalid_links.iter().any(|l| l.element == element && l.url == url),
+            "expected invalid link '{}' on '{}'. Invalid links: {:?}",
+            url, element,
+            report.invalid_links.iter().map(|l| format!("{}: {}", l.element, l.url)).collect::<Vec<_>>()
+        );
+    }
 }
@@ -16,6 +16,14 @@ pub trait ArchitectureDriver: Send {
     /// Create an annotated source file for a container or component.
     fn create_annotated_source(&mut self, name: &str, content: &str);
 
+    /// Lay the project out as a Cargo workspace of the given member crate
+    /// directories.
+    fn create_workspace(&mut self, members: &[String]);
+
+    /// Create an annotated source file inside a member crate; `name` is
+    /// the module path within the crate, `_lib` for its root.
+    fn create_crate_source(&mut self, crate_dir: &str, name: &str, content: &str);
+
     // =========================================================================
     // Action — run the architecture compiler
     // =========================================================================
@@ -23,6 +31,9 @@ pub trait ArchitectureDriver: Send {
     /// Compile: parse all annotated sources and generate all outputs.
     fn compile(&mut self);
 
+    /// Compile a single member crate, as when running in its directory.
+    fn compile_crate(&mut self, crate_dir: &str);
+
     /// Get the parsed modules (for assertions that need the IR).
     fn compiled_modules(&self) -> &[ModuleDoc];
 
@@ -38,6 +49,9 @@ pub trait ArchitectureDriver: Send {
     /// Confirm the ARCHITECTURE.md contains expected text.
     fn confirm_architecture_contains(&self, expected: &str);
 
+    /// Confirm the ARCHITECTURE.md does not contain some text.
+    fn confirm_architecture_omits(&self, unexpected: &str);
+
     /// Confirm the component index table lists a named element.
     fn confirm_index_lists(&self, name: &str);
 
@@ -111,9 +125,18 @@ pub trait ArchitectureDriver: Send {
     /// Confirm the health report pattern confidence count.
     fn confirm_health_pattern_count(&self, confidence: &str, expected: usize);
 
+    /// Confirm the health report count of file-table patterns by confidence.
+    fn confirm_health_file_pattern_count(&self, confidence: &str, expected: usize);
+
     /// Confirm the health report total file count.
     fn confirm_health_total_files(&self, expected: usize);
 
+    /// Confirm the health report's unsafe count for an element.
+    fn confirm_unsafe_count(&self, element: &str, expected: usize);
+
+    /// Confirm the health report's line count at a maturity level.
+    fn confirm_health_loc(&self, maturity: &str, expected: usize);
+
     // =========================================================================
     // Phase B — Validation (ghost/orphan detection)
     // =========================================================================
@@ -136,6 +159,9 @@ pub trait ArchitectureDriver: Send {
     /// Confirm validation found no issues.
     fn confirm_validation_clean(&self);
 
+    /// Confirm whether validation passes under the default severities.
+    fn confirm_validation_passes(&self, warnings_as_errors: bool, passes: bool);
+
     // =========================================================================
     // Phase B — Drift detection
     // =========================================================================
@@ -193,9 +219,22 @@ pub trait ArchitectureDriver: Send {
     /// Create a Rust code file (not annotations) in a module's source directory.
     fn create_code_file(&mut self, element: &str, filename: &str, code: &str);
 
+    /// Append a section to `archidoc.toml` at the project root.
+    fn write_project_config(&mut self, content: &str);
+
     /// Run structural heuristics and auto-promote matching patterns.
     fn verify_patterns(&mut self);
 
+    /// Confirm the evidence recorded for a verified pattern (`file::item`).
+    fn confirm_pattern_evidence(&self, name: &str, evidence: &str);
+
+    /// Confirm the latest promotions log entry for an element records its
+    /// promotion to verified with the given evidence (`file::item`).
+    fn confirm_promotion_logged(&self, name: &str, evidence: &str);
+
+    /// Confirm the number of entries in the promotions log.
+    fn confirm_promotion_log_size(&self, expected: usize);
+
     /// Run a named fitness function and confirm it passes.
     fn confirm_fitness_passes(&self, fitness_name: &str);
 
@@ -233,4 +272,81 @@ pub trait ArchitectureDriver: Send {
 
     /// Confirm the merged IR contains a specific element at a given level.
     fn confirm_merged_contains(&self, name: &str, level: &str);
+
+    /// Confirm the merge reported a warning about an overwritten element.
+    fn confirm_merge_warned_about(&self, name: &str);
+
+    // =========================================================================
+    // Architecture approval
+    // =========================================================================
+
+    /// Record the compiled architecture as approved by a reviewer.
+    fn freeze_architecture(&mut self, reviewer: &str, date: &str);
+
+    /// Confirm the compiled architecture matches the approved snapshot.
+    fn confirm_matches_approved(&self);
+
+    /// Confirm an element is flagged as not in the approved snapshot.
+    fn confirm_unapproved_element(&self, name: &str);
+
+    /// Confirm a cross-container dependency is flagged as not approved.
+    fn confirm_unapproved_dependency(&self, from: &str, to: &str);
+
+    // =========================================================================
+    // Review sign-off tracking
+    // =========================================================================
+
+    /// Content hash of a compiled element, as recorded by `@reviewed-by`.
+    fn reviewed_content_hash(&self, name: &str) -> String;
+
+    /// Confirm the health report review count for a status (reviewed/unreviewed/stale).
+    fn confirm_health_review_count(&self, status: &str, expected: usize);
+
+    /// Confirm an element is listed as having a stale review.
+    fn confirm_stale_review(&self, name: &str);
+
+    // =========================================================================
+    // Risk register
+    // =========================================================================
+
+    /// Confirm the risk register lists a risk for an element at a severity.
+    fn confirm_risk_registered(&self, element: &str, description: &str, severity: &str);
+
+    /// Confirm the risk register has the expected number of entries.
+    fn confirm_risk_count(&self, expected: usize);
+
+    // =========================================================================
+    // Glossary
+    // =========================================================================
+
+    /// Confirm the glossary defines a term and links back to the defining element.
+    fn confirm_glossary_entry(&self, term: &str, defined_in: &str);
+
+    // =========================================================================
+    // Patterns in Use appendix
+    // =========================================================================
+
+    /// Confirm the patterns appendix lists a claim of a pattern at a status.
+    /// `claimed_by` is an element path, or `element/file` for a cataloged file.
+    fn confirm_pattern_in_use(&self, pattern: &str, claimed_by: &str, status: &str);
+
+    // =========================================================================
+    // Module README scaffolding
+    // =========================================================================
+
+    /// Write generated READMEs into each module directory.
+    fn scaffold_readmes(&mut self);
+
+    /// Write a hand-written README into an element's directory.
+    fn place_hand_written_readme(&mut self, element: &str, content: &str);
+
+    /// Confirm an element's README contains expected text.
+    fn confirm_module_readme_contains(&self, element: &str, expected: &str);
+
+    // =========================================================================
+    // Documentation links
+    // =========================================================================
+
+    /// Confirm validation reports a malformed `@doc` URL on an element.
+    fn confirm_invalid_link(&self, element: &str, url: &str);
 }
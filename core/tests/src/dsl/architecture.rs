@@ -26,12 +26,45 @@ pub struct ArchitectureDsl {
     dependencies: Vec<DependencyDecl>,
     /// Pattern confidence overrides
     confidence_overrides: HashMap<String, String>,
+    /// Recorded review sign-offs: name -> `@reviewed-by` arguments
+    reviews: HashMap<String, String>,
+    /// Pending risk declarations
+    risks: Vec<RiskDecl>,
+    /// Pending glossary terms: (element, term, definition)
+    terms: Vec<(String, String, String)>,
+    /// Pending documentation links: (element, url, title)
+    links: Vec<(String, String, String)>,
+    /// Elements marked `@standalone`
+    standalone: Vec<String>,
+    /// Elements marked `@crosscutting`
+    crosscutting: Vec<String>,
+    /// Stable `@id` identifiers: name -> id
+    stable_ids: HashMap<String, String>,
+    /// `@group` memberships: name -> group
+    groups: HashMap<String, String>,
+    /// Pending runtime scenarios: (element, name, steps)
+    flows: Vec<(String, String, String)>,
+    /// Annotated crate roots of workspace members: crate directory -> setup
+    crate_roots: Vec<(String, ElementSetup)>,
 }
 
 struct ElementSetup {
     c4_level: String,
     purpose: String,
     design_pattern: String,
+    /// Member crate directory holding the element; `None` for the root crate
+    crate_dir: Option<String>,
+}
+
+impl ElementSetup {
+    fn new(c4_level: String, params: &Params) -> Self {
+        Self {
+            c4_level,
+            purpose: params.get("purpose"),
+            design_pattern: params.get_opt("design_pattern").unwrap_or_default(),
+            crate_dir: params.get_opt("crate"),
+        }
+    }
 }
 
 struct CatalogEntry {
@@ -42,11 +75,18 @@ struct CatalogEntry {
     maturity: String,
 }
 
+struct RiskDecl {
+    element: String,
+    description: String,
+    severity: Option<String>,
+}
+
 struct DependencyDecl {
     from: String,
     to: String,
     label: String,
     protocol: String,
+    hidden: bool,
 }
 
 impl ArchitectureDsl {
@@ -58,6 +98,16 @@ impl ArchitectureDsl {
             catalog_entries: Vec::new(),
             dependencies: Vec::new(),
             confidence_overrides: HashMap::new(),
+            reviews: HashMap::new(),
+            risks: Vec::new(),
+            terms: Vec::new(),
+            links: Vec::new(),
+            standalone: Vec::new(),
+            crosscutting: Vec::new(),
+            stable_ids: HashMap::new(),
+            groups: HashMap::new(),
+            flows: Vec::new(),
+            crate_roots: Vec::new(),
         }
     }
 
@@ -67,34 +117,50 @@ impl ArchitectureDsl {
 
     /// Annotate a container in the architecture.
     /// Format: "name: bus, purpose: Central messaging backbone, design_pattern: Mediator"
+    /// Add "crate: api" to place it in a workspace member crate.
     pub fn annotate_container(&mut self, args: &[&str]) {
         let params = Params::parse(args);
-        self.elements.insert(
-            params.get("name"),
-            ElementSetup {
-                c4_level: "container".to_string(),
-                purpose: params.get("purpose"),
-                design_pattern: params.get_opt("design_pattern").unwrap_or_default(),
-            },
-        );
+        self.elements
+            .insert(params.get("name"), ElementSetup::new("container".to_string(), &params));
     }
 
     /// Annotate a component in the architecture.
     /// Format: "name: bus.calc, purpose: Indicator calculations, design_pattern: Strategy"
+    /// Add "crate: api" to place it in a workspace member crate.
     pub fn annotate_component(&mut self, args: &[&str]) {
         let params = Params::parse(args);
-        self.elements.insert(
-            params.get("name"),
-            ElementSetup {
-                c4_level: "component".to_string(),
-                purpose: params.get("purpose"),
-                design_pattern: params.get_opt("design_pattern").unwrap_or_default(),
-            },
-        );
+        self.elements
+            .insert(params.get("name"), ElementSetup::new("component".to_string(), &params));
+    }
+
+    /// Annotate an element of any kind: person, external_system, context,
+    /// deployment_node, or custom:<name>.
+    /// Format: "name: trader, kind: person, purpose: Places orders"
+    pub fn annotate_element(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.elements.insert(params.get("name"), ElementSetup::new(params.get("kind"), &params));
+    }
+
+    /// Lay the project out as a Cargo workspace; members are crate
+    /// directories, possibly nested.
+    /// Format: "members: [api, libs/store]"
+    pub fn set_up_workspace(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver.create_workspace(&params.get_list("members"));
+    }
+
+    /// Annotate the root (`lib.rs`) of a workspace member crate; its kind
+    /// defaults to container.
+    /// Format: "crate: api, purpose: HTTP entry point, kind: container"
+    pub fn annotate_crate(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        let kind = params.get_opt("kind").unwrap_or_else(|| "container".to_string());
+        self.crate_roots.push((params.get("crate"), ElementSetup::new(kind, &params)));
     }
 
     /// Declare a dependency between elements.
     /// Format: "from: engine, to: bus, label: Routes commands, protocol: crossbeam"
+    /// Add the "hidden" flag to keep it out of diagrams.
     pub fn declare_dependency(&mut self, args: &[&str]) {
         let params = Params::parse(args);
         self.dependencies.push(DependencyDecl {
@@ -102,9 +168,45 @@ impl ArchitectureDsl {
             to: params.get("to"),
             label: params.get("label"),
             protocol: params.get("protocol"),
+            hidden: params.get_bool("hidden"),
         });
     }
 
+    /// Give an element a stable identifier (`@id`).
+    /// Format: "element: bus.calc, id: calc_engine"
+    pub fn assign_id(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.stable_ids.insert(params.get("element"), params.get("id"));
+    }
+
+    /// Declare an element a cross-cutting concern (`@crosscutting`).
+    /// Format: "element: logging"
+    pub fn mark_crosscutting(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.crosscutting.push(params.get("element"));
+    }
+
+    /// Place an element in a business grouping (`@group`).
+    /// Format: "element: billing, group: domain:payments"
+    pub fn assign_group(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.groups.insert(params.get("element"), params.get("group"));
+    }
+
+    /// Declare a runtime scenario on an element (`@flow`).
+    /// Format: "element: api, name: checkout, steps: api -> payments -> store"
+    pub fn declare_flow(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.flows.push((params.get("element"), params.get("name"), params.get("steps")));
+    }
+
+    /// Mark an element as intentionally unconnected (`@standalone`).
+    /// Format: "element: tools"
+    pub fn mark_standalone(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.standalone.push(params.get("element"));
+    }
+
     /// Add a file to an element's catalog.
     /// Format: "element: bus, file: lanes.rs, design_pattern: Observer, responsibility: Event routing, maturity: active"
     pub fn catalog_file(&mut self, args: &[&str]) {
@@ -145,6 +247,14 @@ impl ArchitectureDsl {
         self.driver.compile();
     }
 
+    /// Build annotated source files and compile one workspace member crate.
+    /// Format: "crate: api"
+    pub fn compile_crate(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.build_source_files();
+        self.driver.compile_crate(&params.get("crate"));
+    }
+
     // =========================================================================
     // Assertions — verify user-visible outcomes
     // =========================================================================
@@ -162,6 +272,13 @@ impl ArchitectureDsl {
             .confirm_architecture_contains(&params.get("contains"));
     }
 
+    /// Assert the ARCHITECTURE.md does not contain text.
+    /// Format: "omits: ## Architectural Fitness"
+    pub fn assert_architecture_omits(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver.confirm_architecture_omits(&params.get("omits"));
+    }
+
     /// Assert the architecture index lists an element.
     /// Format: "name: bus"
     pub fn assert_index_lists(&self, args: &[&str]) {
@@ -293,6 +410,14 @@ impl ArchitectureDsl {
             .confirm_health_pattern_count(&params.get("confidence"), params.get_usize("count"));
     }
 
+    /// Assert the number of file-table patterns at a given confidence level.
+    /// Format: "confidence: verified, count: 1"
+    pub fn assert_health_file_pattern_count(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver
+            .confirm_health_file_pattern_count(&params.get("confidence"), params.get_usize("count"));
+    }
+
     /// Assert the total number of files in the health report.
     /// Format: "count: 5"
     pub fn assert_health_total_files(&self, args: &[&str]) {
@@ -301,6 +426,22 @@ impl ArchitectureDsl {
             .confirm_health_total_files(params.get_usize("count"));
     }
 
+    /// Assert the number of unsafe blocks and functions counted for an element.
+    /// Format: "element: bus.ffi, count: 2"
+    pub fn assert_unsafe_count(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver
+            .confirm_unsafe_count(&params.get("element"), params.get_usize("count"));
+    }
+
+    /// Assert the lines of code counted at a given maturity level.
+    /// Format: "maturity: planned, lines: 40"
+    pub fn assert_health_loc(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver
+            .confirm_health_loc(&params.get("maturity"), params.get_usize("lines"));
+    }
+
     // =========================================================================
     // Phase B — Validation (ghost/orphan detection)
     // =========================================================================
@@ -342,6 +483,21 @@ impl ArchitectureDsl {
         self.driver.confirm_validation_clean();
     }
 
+    /// Assert validation passes: findings, if any, are only warnings.
+    pub fn assert_validation_passes(&self) {
+        self.driver.confirm_validation_passes(false, true);
+    }
+
+    /// Assert validation fails: at least one finding is an error.
+    pub fn assert_validation_fails(&self) {
+        self.driver.confirm_validation_passes(false, false);
+    }
+
+    /// Assert validation fails once warnings count as errors.
+    pub fn assert_validation_fails_with_warnings_as_errors(&self) {
+        self.driver.confirm_validation_passes(true, false);
+    }
+
     // =========================================================================
     // Phase B — Drift detection
     // =========================================================================
@@ -427,11 +583,79 @@ impl ArchitectureDsl {
         self.driver.create_code_file(element, filename, code);
     }
 
+    /// Set heuristic thresholds in the project's `archidoc.toml`.
+    /// Format: "facade_min_pub_mods: 3", "builder_min_setters: 1"
+    pub fn configure_heuristics(&mut self, args: &[&str]) {
+        let mut config = String::from("[heuristics]\n");
+        for arg in args {
+            let (key, value) = arg.split_once(':').expect("expected 'name: value'");
+            config.push_str(&format!("{} = {}\n", key.trim(), value.trim()));
+        }
+        self.driver.write_project_config(&config);
+    }
+
+    /// Allow dependencies on another container's components in `archidoc.toml`.
+    /// Format: "api.auth -> bus.calc" or a bare "bus.events"
+    pub fn allow_cross_container(&mut self, entries: &[&str]) {
+        let quoted: Vec<String> = entries.iter().map(|e| format!("\"{}\"", e)).collect();
+        let config = format!("[fitness]\nallow_cross_container = [{}]\n", quoted.join(", "));
+        self.driver.write_project_config(&config);
+    }
+
+    /// Embed fitness results in the generated ARCHITECTURE.md via `archidoc.toml`.
+    pub fn enable_fitness_summary(&mut self) {
+        self.driver.write_project_config("[generators]\nfitness = true\n");
+    }
+
+    /// Leave ARCHITECTURE.md sections out via `archidoc.toml`.
+    /// Format: "elements", "metrics"
+    pub fn skip_architecture_sections(&mut self, sections: &[&str]) {
+        let quoted: Vec<String> = sections.iter().map(|s| format!("\"{}\"", s)).collect();
+        let config = format!("[architecture]\nskip = [{}]\n", quoted.join(", "));
+        self.driver.write_project_config(&config);
+    }
+
+    /// Set the language of generated headings in `archidoc.toml`.
+    /// Format: "language: de"
+    pub fn set_output_language(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        let config = format!("[generators]\nlanguage = \"{}\"\n", params.get("language"));
+        self.driver.write_project_config(&config);
+    }
+
+    /// Draw component dependencies as container-level arrows via `archidoc.toml`.
+    pub fn aggregate_relationships(&mut self) {
+        self.driver.write_project_config("[diagrams]\naggregate_relationships = true\n");
+    }
+
     /// Run structural heuristics and auto-promote matching patterns.
     pub fn verify_patterns(&mut self) {
         self.driver.verify_patterns();
     }
 
+    /// Assert where auto-promotion found a module's pattern evidence.
+    /// Format: "name: bus.calc, evidence: indicators.rs::IndicatorCalc"
+    pub fn assert_pattern_evidence(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver
+            .confirm_pattern_evidence(&params.get("name"), &params.get("evidence"));
+    }
+
+    /// Assert auto-promotion of a module was written to the promotions log.
+    /// Format: "name: bus.calc, evidence: indicators.rs::IndicatorCalc"
+    pub fn assert_promotion_logged(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver
+            .confirm_promotion_logged(&params.get("name"), &params.get("evidence"));
+    }
+
+    /// Assert the number of entries in the promotions log.
+    /// Format: "entries: 1"
+    pub fn assert_promotion_log_size(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver.confirm_promotion_log_size(params.get_usize("entries"));
+    }
+
     /// Assert a named fitness function passes for all matching modules.
     /// Format: "fitness: all_strategy_modules_define_a_trait"
     pub fn assert_fitness_passes(&self, args: &[&str]) {
@@ -502,68 +726,332 @@ impl ArchitectureDsl {
         self.driver.confirm_merged_contains(&params.get("name"), &params.get("level"));
     }
 
+    /// Assert the merge warned that an element was overwritten.
+    /// Format: "name: api"
+    pub fn assert_merge_warned_about(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver.confirm_merge_warned_about(&params.get("name"));
+    }
+
+    // =========================================================================
+    // Architecture approval (freeze and governance check)
+    // =========================================================================
+
+    /// Approve the compiled architecture.
+    /// Format: "reviewer: alice, date: 2026-01-05"
+    pub fn freeze_architecture(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver.freeze_architecture(
+            &params.get("reviewer"),
+            &params.get_opt("date").unwrap_or_else(|| "2026-01-01".to_string()),
+        );
+    }
+
+    /// Assert the compiled architecture matches the approved snapshot.
+    pub fn assert_matches_approved(&self) {
+        self.driver.confirm_matches_approved();
+    }
+
+    /// Assert an element is not covered by the approved snapshot.
+    /// Format: "name: cache"
+    pub fn assert_unapproved_element(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver.confirm_unapproved_element(&params.get("name"));
+    }
+
+    /// Assert a cross-container dependency is not covered by the approved snapshot.
+    /// Format: "from: api, to: bus"
+    pub fn assert_unapproved_dependency(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver
+            .confirm_unapproved_dependency(&params.get("from"), &params.get("to"));
+    }
+
+    // =========================================================================
+    // Review sign-off tracking
+    // =========================================================================
+
+    /// Sign off an element's compiled content on behalf of a reviewer.
+    /// Takes effect on the next compile. The `without_hash` flag leaves
+    /// the content hash out of the sign-off.
+    /// Format: "name: bus, reviewer: alice, date: 2024-06-01"
+    pub fn review_element(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        let name = params.get("name");
+        let mut review = format!("{} {}", params.get("reviewer"), params.get("date"));
+        if !params.get_bool("without_hash") {
+            review.push(' ');
+            review.push_str(&self.driver.reviewed_content_hash(&name));
+        }
+        self.reviews.insert(name, review);
+    }
+
+    /// Assert the number of elements with a given review status.
+    /// Format: "status: stale, count: 1"
+    pub fn assert_health_review_count(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver
+            .confirm_health_review_count(&params.get("status"), params.get_usize("count"));
+    }
+
+    /// Assert an element's review no longer matches its content.
+    /// Format: "name: bus"
+    pub fn assert_stale_review(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver.confirm_stale_review(&params.get("name"));
+    }
+
+    // =========================================================================
+    // Risk register
+    // =========================================================================
+
+    /// Record an architectural risk against an element.
+    /// Format: "element: bus, risk: Single point of failure, severity: high"
+    pub fn record_risk(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.risks.push(RiskDecl {
+            element: params.get("element"),
+            description: params.get("risk"),
+            severity: params.get_opt("severity"),
+        });
+    }
+
+    /// Assert the risk register lists a risk for an element.
+    /// Format: "element: bus, risk: Single point of failure, severity: high"
+    pub fn assert_risk_registered(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver.confirm_risk_registered(
+            &params.get("element"),
+            &params.get("risk"),
+            &params.get("severity"),
+        );
+    }
+
+    /// Assert the number of entries in the risk register.
+    /// Format: "count: 2"
+    pub fn assert_risk_count(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver.confirm_risk_count(params.get_usize("count"));
+    }
+
+    // =========================================================================
+    // Glossary
+    // =========================================================================
+
+    /// Define a glossary term in an element.
+    /// Format: "element: bus, term: EventLane, definition: A typed event channel"
+    pub fn define_term(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.terms.push((
+            params.get("element"),
+            params.get("term"),
+            params.get("definition"),
+        ));
+    }
+
+    /// Assert the glossary defines a term with a link back to its element.
+    /// Format: "term: EventLane, defined_in: bus"
+    pub fn assert_glossary_entry(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver
+            .confirm_glossary_entry(&params.get("term"), &params.get("defined_in"));
+    }
+
+    // =========================================================================
+    // Patterns in Use appendix
+    // =========================================================================
+
+    /// Assert the patterns appendix lists a pattern claim.
+    /// Format: "pattern: Observer, claimed_by: bus/lanes.rs, status: verified"
+    pub fn assert_pattern_in_use(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver.confirm_pattern_in_use(
+            &params.get("pattern"),
+            &params.get("claimed_by"),
+            &params.get("status"),
+        );
+    }
+
+    // =========================================================================
+    // Documentation links
+    // =========================================================================
+
+    /// Link an element to external documentation (runbook, dashboard, design doc).
+    /// Format: "element: bus, url: https://wiki.example.com/bus, title: Runbook"
+    pub fn link_documentation(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.links.push((
+            params.get("element"),
+            params.get("url"),
+            params.get_opt("title").unwrap_or_default(),
+        ));
+    }
+
+    /// Assert validation reports a malformed documentation link.
+    /// Format: "element: bus, url: wiki/bus"
+    pub fn assert_invalid_link(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver
+            .confirm_invalid_link(&params.get("element"), &params.get("url"));
+    }
+
+    // =========================================================================
+    // Module README scaffolding
+    // =========================================================================
+
+    /// Write generated READMEs into each module directory.
+    pub fn scaffold_readmes(&mut self) {
+        self.driver.scaffold_readmes();
+    }
+
+    /// Write a hand-written README into an element's directory.
+    /// Format: "element: bus, contains: Maintained by hand"
+    pub fn place_hand_written_readme(&mut self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver
+            .place_hand_written_readme(&params.get("element"), &params.get("contains"));
+    }
+
+    /// Assert an element's README contains expected text.
+    /// Format: "element: bus, contains: Central messaging backbone"
+    pub fn assert_module_readme_contains(&self, args: &[&str]) {
+        let params = Params::parse(args);
+        self.driver
+            .confirm_module_readme_contains(&params.get("element"), &params.get("contains"));
+    }
+
     // =========================================================================
     // Internal — build source files from accumulated setup
     // =========================================================================
 
     fn build_source_files(&mut self) {
-        for (name, setup) in &self.elements {
-            let mut content = String::new();
-
-            // Header with C4 marker
-            let title = to_title_case(name.split('.').last().unwrap_or(name));
-            content.push_str(&format!("@c4 {}\n\n", setup.c4_level));
-            content.push_str(&format!("# {}\n\n", title));
+        let mut sources: Vec<(Option<&str>, &str, String)> = self
+            .elements
+            .iter()
+            .map(|(name, setup)| (setup.crate_dir.as_deref(), name.as_str(), self.element_source(name, setup)))
+            .collect();
+        for (crate_dir, setup) in &self.crate_roots {
+            sources.push((Some(crate_dir), "_lib", self.element_source("_lib", setup)));
+        }
 
-            // Purpose
-            if !setup.purpose.is_empty() {
-                content.push_str(&format!("{}\n\n", setup.purpose));
+        for (crate_dir, name, content) in sources {
+            match crate_dir {
+                Some(crate_dir) => self.driver.create_crate_source(crate_dir, name, &content),
+                None => self.driver.create_annotated_source(name, &content),
             }
+        }
+    }
+
+    /// The annotation block of element `name`, with everything declared
+    /// about it.
+    fn element_source(&self, name: &str, setup: &ElementSetup) -> String {
+        let mut content = String::new();
+
+        // Header with C4 marker; crate roots are titled after their crate
+        let titled = match &setup.crate_dir {
+            Some(crate_dir) if name == "_lib" => crate_dir.rsplit('/').next().unwrap_or(crate_dir),
+            _ => name.split('.').next_back().unwrap_or(name),
+        };
+        let title = to_title_case(titled);
+        content.push_str(&format!("@c4 {}\n\n", setup.c4_level));
+        content.push_str(&format!("# {}\n\n", title));
 
-            // Design pattern
-            if !setup.design_pattern.is_empty() {
-                if let Some(confidence) = self.confidence_overrides.get(name) {
-                    content.push_str(&format!("GoF: {} ({})\n\n", setup.design_pattern, confidence));
-                } else {
-                    content.push_str(&format!("GoF: {}\n\n", setup.design_pattern));
-                }
+        // Purpose
+        if !setup.purpose.is_empty() {
+            content.push_str(&format!("{}\n\n", setup.purpose));
+        }
+
+        // Design pattern
+        if !setup.design_pattern.is_empty() {
+            if let Some(confidence) = self.confidence_overrides.get(name) {
+                content.push_str(&format!("GoF: {} ({})\n\n", setup.design_pattern, confidence));
+            } else {
+                content.push_str(&format!("GoF: {}\n\n", setup.design_pattern));
             }
+        }
+
+        // Dependencies
+        let deps: Vec<&DependencyDecl> = self
+            .dependencies
+            .iter()
+            .filter(|d| d.from == *name)
+            .collect();
+        for dep in &deps {
+            content.push_str(&format!(
+                "@c4 uses {} \"{}\" \"{}\"{}\n",
+                dep.to,
+                dep.label,
+                dep.protocol,
+                if dep.hidden { " [hidden]" } else { "" }
+            ));
+        }
+        if !deps.is_empty() {
+            content.push('\n');
+        }
+        if self.standalone.iter().any(|e| e == name) {
+            content.push_str("@standalone\n\n");
+        }
+        if self.crosscutting.iter().any(|e| e == name) {
+            content.push_str("@crosscutting\n\n");
+        }
+        if let Some(id) = self.stable_ids.get(name) {
+            content.push_str(&format!("@id {}\n\n", id));
+        }
+        if let Some(group) = self.groups.get(name) {
+            content.push_str(&format!("@group {}\n\n", group));
+        }
+        for (_, flow, steps) in self.flows.iter().filter(|(e, _, _)| e == name) {
+            content.push_str(&format!("@flow {}: {}\n\n", flow, steps));
+        }
 
-            // Dependencies
-            let deps: Vec<&DependencyDecl> = self
-                .dependencies
-                .iter()
-                .filter(|d| d.from == *name)
-                .collect();
-            for dep in &deps {
+        // File catalog
+        let entries: Vec<&CatalogEntry> = self
+            .catalog_entries
+            .iter()
+            .filter(|e| e.element == *name)
+            .collect();
+        if !entries.is_empty() {
+            content.push_str("| File | Pattern | Purpose | Health |\n");
+            content.push_str("|------|---------|---------|--------|\n");
+            for entry in &entries {
                 content.push_str(&format!(
-                    "@c4 uses {} \"{}\" \"{}\"\n",
-                    dep.to, dep.label, dep.protocol
+                    "| `{}` | {} | {} | {} |\n",
+                    entry.filename, entry.design_pattern, entry.responsibility, entry.maturity
                 ));
             }
-            if !deps.is_empty() {
-                content.push('\n');
+        }
+
+        // Risks
+        for risk in self.risks.iter().filter(|r| r.element == *name) {
+            match &risk.severity {
+                Some(severity) => content.push_str(&format!(
+                    "@risk \"{}\" severity={}\n",
+                    risk.description, severity
+                )),
+                None => content.push_str(&format!("@risk \"{}\"\n", risk.description)),
             }
+        }
+
+        // Glossary terms
+        for (_, term, definition) in self.terms.iter().filter(|(e, _, _)| e == name) {
+            content.push_str(&format!("@term {}: {}\n", term, definition));
+        }
 
-            // File catalog
-            let entries: Vec<&CatalogEntry> = self
-                .catalog_entries
-                .iter()
-                .filter(|e| e.element == *name)
-                .collect();
-            if !entries.is_empty() {
-                content.push_str("| File | Pattern | Purpose | Health |\n");
-                content.push_str("|------|---------|---------|--------|\n");
-                for entry in &entries {
-                    content.push_str(&format!(
-                        "| `{}` | {} | {} | {} |\n",
-                        entry.filename, entry.design_pattern, entry.responsibility, entry.maturity
-                    ));
-                }
+        // Documentation links
+        for (_, url, title) in self.links.iter().filter(|(e, _, _)| e == name) {
+            if title.is_empty() {
+                content.push_str(&format!("@doc {}\n", url));
+            } else {
+                content.push_str(&format!("@doc {} \"{}\"\n", url, title));
             }
+        }
 
-            self.driver.create_annotated_source(name, &content);
+        // Review sign-off
+        if let Some(review) = self.reviews.get(name) {
+            content.push_str(&format!("\n@reviewed-by {}\n", review));
         }
+
+        content
     }
 }
 
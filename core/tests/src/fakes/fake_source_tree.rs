@@ -8,10 +8,22 @@ use tempfile::TempDir;
 /// - `"bus"` -> `bus/mod.rs`
 /// - `"bus.calc"` -> `bus/calc/mod.rs`
 /// - `"_lib"` -> `lib.rs` (crate root)
+///
+/// Workspaces put each member crate in its own directory, with its own
+/// `Cargo.toml` and `src/` tree:
+/// - `create_workspace(&["api", "libs/store"])` -> `Cargo.toml` listing both,
+///   `api/Cargo.toml`, `libs/store/Cargo.toml`
+/// - `create_crate_module("api", "_lib", ..)` -> `api/src/lib.rs`
 pub struct FakeSourceTree {
     temp_dir: TempDir,
 }
 
+impl Default for FakeSourceTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl FakeSourceTree {
     pub fn new() -> Self {
         Self {
@@ -29,8 +41,55 @@ impl FakeSourceTree {
     /// The content should be raw annotation text (without `//!` prefixes).
     /// This method wraps each line with `//!` to create valid Rust doc comments.
     pub fn create_module(&self, module_path: &str, content: &str) {
-        let file_path = self.module_path_to_file(module_path);
+        self.write_module(self.module_path_to_file(module_path), content);
+    }
+
+    /// Write a workspace manifest listing `members`, and a package manifest
+    /// in each member directory, named after its last path segment.
+    pub fn create_workspace(&self, members: &[String]) {
+        let root = self.temp_dir.path();
+        let list: Vec<String> = members.iter().map(|m| format!("\"{}\"", m)).collect();
+        fs::write(
+            root.join("Cargo.toml"),
+            format!("[workspace]\nresolver = \"2\"\nmembers = [{}]\n", list.join(", ")),
+        )
+        .expect("failed to write workspace manifest");
 
+        for member in members {
+            let dir = root.join(member);
+            fs::create_dir_all(dir.join("src")).expect("failed to create crate directory");
+            let name = member.rsplit('/').next().unwrap_or(member);
+            fs::write(
+                dir.join("Cargo.toml"),
+                format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n", name),
+            )
+            .expect("failed to write crate manifest");
+        }
+    }
+
+    /// Create an annotated source file in a member crate, like
+    /// [`create_module`](Self::create_module) under `<crate_dir>/src/`.
+    pub fn create_crate_module(&self, crate_dir: &str, module_path: &str, content: &str) {
+        self.write_module(module_file(&self.crate_src(crate_dir), module_path), content);
+    }
+
+    /// The `src/` directory of a member crate.
+    pub fn crate_src(&self, crate_dir: &str) -> PathBuf {
+        self.temp_dir.path().join(crate_dir).join("src")
+    }
+
+    /// The directory a compile starts from: `src/` for a single crate, the
+    /// root for a workspace without a root package.
+    pub fn source_root(&self) -> PathBuf {
+        let src = self.temp_dir.path().join("src");
+        if src.exists() {
+            src
+        } else {
+            self.temp_dir.path().to_path_buf()
+        }
+    }
+
+    fn write_module(&self, file_path: PathBuf, content: &str) {
         // Ensure parent directory exists
         if let Some(parent) = file_path.parent() {
             fs::create_dir_all(parent).expect("failed to create module directory");
@@ -93,19 +152,21 @@ impl FakeSourceTree {
 
     /// Convert a dot-notation module path to a file path.
     fn module_path_to_file(&self, module_path: &str) -> PathBuf {
-        let root = self.temp_dir.path();
+        // Add src/ to match real Rust project structure
+        module_file(&self.temp_dir.path().join("src"), module_path)
+    }
+}
 
-        if module_path == "_lib" {
-            return root.join("src").join("lib.rs");
-        }
+/// The file of `module_path` in the `src` directory of a crate.
+fn module_file(src: &Path, module_path: &str) -> PathBuf {
+    if module_path == "_lib" {
+        return src.join("lib.rs");
+    }
 
-        let parts: Vec<&str> = module_path.split('.').collect();
-        let mut path = root.to_path_buf();
-        path.push("src"); // Add src/ to match real Rust project structure
-        for part in &parts {
-            path.push(part);
-        }
-        path.push("mod.rs");
-        path
+    let mut path = src.to_path_buf();
+    for part in module_path.split('.') {
+        path.push(part);
     }
+    path.push("mod.rs");
+    path
 }
@@ -6,11 +6,13 @@
 //!
 //! | File | Pattern | Purpose | Health |
 //! |------|---------|---------|--------|
+//! | `conformance.rs` | -- | Adapter conformance fixtures and runner | active |
 //! | `dsl/` | Facade | Domain-specific language layer | active |
 //! | `drivers/` | Strategy | Protocol driver traits and implementations | active |
 //! | `fakes/` | -- | Test doubles for source tree creation | active |
-//! | `params.rs` | -- | String parameter parser | stable |
+//! | `params.rs` | -- | String parameter parser: quoting, lists, flags | stable |
 
+pub mod conformance;
 pub mod dsl;
 pub mod drivers;
 pub mod fakes;
@@ -3,31 +3,57 @@ use std::collections::HashMap;
 /// Parses Farley-style string parameters.
 ///
 /// Input: `&["module: bus", "pattern: Mediator"]`
-/// Output: HashMap with `"module" -> "bus"`, `"pattern" -> "Mediator"`
+/// Output: `"module" -> "bus"`, `"pattern" -> "Mediator"`
+///
+/// Each argument is one parameter:
+/// - `key: value` — the rest of the argument, trimmed; colons and commas
+///   are part of the value
+/// - `key: "value"` — quoted, to keep surrounding spaces or a leading `"`
+///   or `[`; `\"` and `\\` are escapes
+/// - `key: [a, b, "c, d"]` — a list, read with [`Params::get_list`]; items
+///   are split on commas unless quoted
+/// - `key` — a boolean flag, read with [`Params::get_bool`]
+///
+/// A key given more than once collects every value, read with
+/// [`Params::get_list`]. Malformed arguments panic naming the argument.
 pub struct Params {
-    map: HashMap<String, String>,
+    map: HashMap<String, Vec<Value>>,
+}
+
+enum Value {
+    Text(String),
+    List(Vec<String>),
+    Flag,
 }
 
 impl Params {
     pub fn parse(args: &[&str]) -> Self {
-        let mut map = HashMap::new();
+        let mut map: HashMap<String, Vec<Value>> = HashMap::new();
         for arg in args {
-            if let Some((key, value)) = arg.split_once(':') {
-                map.insert(key.trim().to_string(), value.trim().to_string());
+            let (key, value) = match arg.split_once(':') {
+                Some((key, value)) => (key.trim(), parse_value(arg, value.trim())),
+                None => (arg.trim(), Value::Flag),
+            };
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                panic!("malformed parameter '{}': expected `key: value` or a flag name", arg);
             }
+            map.entry(key.to_string()).or_default().push(value);
         }
         Self { map }
     }
 
     pub fn get(&self, key: &str) -> String {
-        self.map
-            .get(key)
+        self.get_opt(key)
             .unwrap_or_else(|| panic!("missing required parameter: {}", key))
-            .clone()
     }
 
     pub fn get_opt(&self, key: &str) -> Option<String> {
-        self.map.get(key).cloned()
+        match self.map.get(key)?.as_slice() {
+            [Value::Text(text)] => Some(text.clone()),
+            [Value::List(_)] => panic!("parameter '{}' is a list; read it with get_list", key),
+            [Value::Flag] => panic!("parameter '{}' is a flag without a value", key),
+            values => panic!("parameter '{}' is given {} times; read it with get_list", key, values.len()),
+        }
     }
 
     pub fn get_usize(&self, key: &str) -> usize {
@@ -35,4 +61,84 @@ impl Params {
             .parse()
             .unwrap_or_else(|_| panic!("parameter '{}' is not a valid usize", key))
     }
+
+    /// Whether flag `key` is set: given bare, or as `key: true`.
+    pub fn get_bool(&self, key: &str) -> bool {
+        match self.map.get(key).map(Vec::as_slice) {
+            None => false,
+            Some([Value::Flag]) => true,
+            Some([Value::Text(text)]) if text == "true" => true,
+            Some([Value::Text(text)]) if text == "false" => false,
+            Some(_) => panic!("parameter '{}' must be a flag, `true`, or `false`", key),
+        }
+    }
+
+    /// Every value of `key`, in order: list items and repeated values
+    /// alike. Empty when the key is absent.
+    pub fn get_list(&self, key: &str) -> Vec<String> {
+        self.map
+            .get(key)
+            .into_iter()
+            .flatten()
+            .flat_map(|value| match value {
+                Value::Text(text) => vec![text.clone()],
+                Value::List(items) => items.clone(),
+                Value::Flag => panic!("parameter '{}' is a flag without a value", key),
+            })
+            .collect()
+    }
+}
+
+/// A trimmed value: quoted text, a `[...]` list, or plain text.
+fn parse_value(arg: &str, value: &str) -> Value {
+    if value.starts_with('"') {
+        let (text, rest) = quoted(arg, value);
+        if !rest.trim().is_empty() {
+            panic!("malformed parameter '{}': unexpected text after the closing quote", arg);
+        }
+        return Value::Text(text);
+    }
+    match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        Some(items) => Value::List(list_items(arg, items)),
+        None => Value::Text(value.to_string()),
+    }
+}
+
+/// Split list items on commas outside quotes.
+fn list_items(arg: &str, items: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = items.trim();
+    while !rest.is_empty() {
+        let (item, after) = if rest.starts_with('"') {
+            let (text, after) = quoted(arg, rest);
+            (text, after.trim_start())
+        } else {
+            let end = rest.find(',').unwrap_or(rest.len());
+            (rest[..end].trim().to_string(), &rest[end..])
+        };
+        out.push(item);
+        rest = match after.strip_prefix(',') {
+            Some(after) => after.trim_start(),
+            None if after.is_empty() => after,
+            None => panic!("malformed parameter '{}': expected `,` between list items", arg),
+        };
+    }
+    out
+}
+
+/// The text of the quoted string `value` starts with, and what follows it.
+fn quoted<'a>(arg: &str, value: &'a str) -> (String, &'a str) {
+    let mut text = String::new();
+    let mut chars = value.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return (text, &value[i + 1..]),
+            '\\' => match chars.next() {
+                Some((_, escaped @ ('"' | '\\'))) => text.push(escaped),
+                _ => panic!("malformed parameter '{}': only `\\\"` and `\\\\` are escapes", arg),
+            },
+            c => text.push(c),
+        }
+    }
+    panic!("malformed parameter '{}': unterminated quote", arg)
 }
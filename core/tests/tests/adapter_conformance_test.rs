@@ -0,0 +1,34 @@
+//! Adapter conformance: the Rust adapter produces the shared fixtures'
+//! expected IR.
+//!
+//! Other adapters run the same fixtures by implementing
+//! `ConformanceAdapter` and calling `assert_conforms`.
+
+use archidoc_tests::conformance::{self, ConformanceAdapter};
+use archidoc_tests::fakes::FakeSourceTree;
+use archidoc_types::ModuleDoc;
+
+struct RustAdapter;
+
+impl ConformanceAdapter for RustAdapter {
+    fn extract(&self, modules: &[(String, String)]) -> Vec<ModuleDoc> {
+        let tree = FakeSourceTree::new();
+        for (module_path, annotation) in modules {
+            tree.create_module(module_path, annotation);
+        }
+        archidoc_rust::walker::extract(&tree.root().join("src")).docs
+    }
+}
+
+/// Every fixture compiles to its expected IR through the Rust adapter.
+#[test]
+fn rust_adapter_conforms_to_the_shared_fixtures() {
+    conformance::assert_conforms(&RustAdapter);
+}
+
+/// The fixtures cover the annotation vocabulary adapters must agree on.
+#[test]
+fn fixtures_cover_levels_relationships_and_metadata() {
+    let names: Vec<String> = conformance::fixtures().into_iter().map(|f| f.name).collect();
+    assert_eq!(names, vec!["hierarchy", "metadata", "relationships"]);
+}
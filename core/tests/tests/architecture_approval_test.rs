@@ -0,0 +1,111 @@
+//! Architecture Approval — Freeze and Governance Check
+//!
+//! Given an architecture approved by a reviewer, when elements or
+//! cross-container dependencies are added without re-freezing, the
+//! governance check flags the divergence.
+
+use archidoc_tests::ArchitectureDsl;
+
+// =========================================================================
+// Unchanged architecture passes
+// =========================================================================
+
+#[test]
+fn frozen_architecture_matches_itself() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: api", "purpose: REST gateway"]);
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.compile();
+    arch.freeze_architecture(&["reviewer: alice", "date: 2026-01-05"]);
+
+    arch.assert_matches_approved();
+}
+
+#[test]
+fn description_changes_do_not_need_approval() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.compile();
+    arch.freeze_architecture(&["reviewer: alice"]);
+
+    arch.annotate_container(&["name: bus", "purpose: Rewritten description"]);
+    arch.compile();
+
+    arch.assert_matches_approved();
+}
+
+// =========================================================================
+// Structural divergence fails
+// =========================================================================
+
+#[test]
+fn new_element_requires_approval() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.compile();
+    arch.freeze_architecture(&["reviewer: alice"]);
+
+    arch.annotate_container(&["name: cache", "purpose: Read-through cache"]);
+    arch.compile();
+
+    arch.assert_unapproved_element(&["name: cache"]);
+}
+
+#[test]
+fn new_cross_container_dependency_requires_approval() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: api", "purpose: REST gateway"]);
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.compile();
+    arch.freeze_architecture(&["reviewer: alice"]);
+
+    arch.declare_dependency(&[
+        "from: api",
+        "to: bus",
+        "label: Publishes requests",
+        "protocol: channel",
+    ]);
+    arch.compile();
+
+    arch.assert_unapproved_dependency(&["from: api", "to: bus"]);
+}
+
+#[test]
+fn dependency_within_container_does_not_need_approval() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.annotate_component(&["name: bus.lanes", "purpose: Event lanes"]);
+    arch.annotate_component(&["name: bus.router", "purpose: Lane router"]);
+    arch.compile();
+    arch.freeze_architecture(&["reviewer: alice"]);
+
+    arch.declare_dependency(&[
+        "from: bus.router",
+        "to: bus.lanes",
+        "label: Dispatches",
+        "protocol: fn call",
+    ]);
+    arch.compile();
+
+    arch.assert_matches_approved();
+}
+
+#[test]
+fn refreezing_accepts_the_new_architecture() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.compile();
+    arch.freeze_architecture(&["reviewer: alice"]);
+
+    arch.annotate_container(&["name: cache", "purpose: Read-through cache"]);
+    arch.compile();
+    arch.freeze_architecture(&["reviewer: bob"]);
+
+    arch.assert_matches_approved();
+}
@@ -83,3 +83,149 @@ fn compiled_architecture_produces_navigable_index() {
     arch.assert_index_lists(&["name: engine"]);
     arch.assert_index_lists(&["name: bus"]);
 }
+
+// =========================================================================
+// Localized headings
+// =========================================================================
+
+#[test]
+fn configured_language_localizes_headings_but_not_content() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&[
+        "name: bus",
+        "purpose: Central messaging backbone",
+    ]);
+    arch.set_output_language(&["language: de"]);
+    arch.compile();
+
+    arch.assert_architecture_contains(&["contains: # Architekturkontext"]);
+    arch.assert_architecture_contains(&["contains: ## Komponentenverzeichnis"]);
+    arch.assert_architecture_contains(&["contains: | Modul | Ebene | Muster | Beschreibung |"]);
+    arch.assert_architecture_contains(&["contains: Central messaging backbone"]);
+    arch.assert_architecture_omits(&["omits: ## Component Index"]);
+}
+
+// =========================================================================
+// Section toggles
+// =========================================================================
+
+#[test]
+fn skipped_sections_are_left_out_of_the_overview() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&[
+        "name: engine",
+        "purpose: Trade execution engine",
+    ]);
+    arch.annotate_container(&[
+        "name: bus",
+        "purpose: Central messaging backbone",
+    ]);
+    arch.declare_dependency(&[
+        "from: engine",
+        "to: bus",
+        "label: Routes commands",
+        "protocol: crossbeam",
+    ]);
+    arch.skip_architecture_sections(&["elements", "relationship_map"]);
+    arch.compile();
+
+    arch.assert_index_lists(&["name: engine"]);
+    arch.assert_diagram_shows_dependency(&["from: engine", "to: bus"]);
+    arch.assert_architecture_omits(&["omits: ## Elements"]);
+    arch.assert_architecture_omits(&["omits: ## Relationship Map"]);
+}
+
+// =========================================================================
+// Business groupings
+// =========================================================================
+
+#[test]
+fn grouped_containers_share_a_boundary_in_the_container_diagram() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&[
+        "name: billing",
+        "purpose: Invoices and payment runs",
+    ]);
+    arch.annotate_container(&[
+        "name: ledger",
+        "purpose: Double-entry bookkeeping",
+    ]);
+    arch.annotate_container(&[
+        "name: bus",
+        "purpose: Central messaging backbone",
+    ]);
+    arch.assign_group(&["element: billing", "group: domain:payments"]);
+    arch.assign_group(&["element: ledger", "group: domain:payments"]);
+    arch.compile();
+
+    arch.assert_architecture_contains(&[
+        "contains: Boundary(group_domain_payments, \"Payments\", \"domain\") {\n            Container(billing",
+    ]);
+    arch.assert_architecture_contains(&["contains:             Container(ledger"]);
+    arch.assert_architecture_contains(&["contains:         Container(bus"]);
+}
+
+#[test]
+fn component_dependencies_aggregate_into_one_container_arrow() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: api", "purpose: Public HTTP API"]);
+    arch.annotate_component(&["name: api.orders", "purpose: Order handlers"]);
+    arch.annotate_component(&["name: api.quotes", "purpose: Quote handlers"]);
+    arch.annotate_container(&["name: store", "purpose: Persistence"]);
+    arch.declare_dependency(&["from: api.orders", "to: store", "label: Saves orders", "protocol: sqlx"]);
+    arch.declare_dependency(&["from: api.quotes", "to: store", "label: Reads quotes", "protocol: sqlx"]);
+    arch.aggregate_relationships();
+    arch.compile();
+
+    arch.assert_architecture_contains(&["contains: Rel(api, store, \"2 dependencies\", \"sqlx\")"]);
+    arch.assert_architecture_contains(&["contains: Rel(api_orders, store, \"Saves orders\""]);
+}
+
+#[test]
+fn flows_are_listed_as_numbered_scenarios() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: api", "purpose: Public HTTP API"]);
+    arch.annotate_container(&["name: payments", "purpose: Card payments"]);
+    arch.annotate_container(&["name: store", "purpose: Persistence"]);
+    arch.declare_dependency(&["from: api", "to: payments", "label: Charges card", "protocol: HTTPS"]);
+    arch.declare_dependency(&["from: payments", "to: store", "label: Records charge", "protocol: sqlx"]);
+    arch.declare_flow(&["element: api", "name: checkout", "steps: api -> payments -> store"]);
+    arch.compile();
+
+    arch.assert_architecture_contains(&[
+        "contains: ## Scenarios\n\n### checkout\n\n1. api -> payments: \"Charges card\" (HTTPS)\n2. payments -> store: \"Records charge\" (sqlx)\n",
+    ]);
+}
+
+// =========================================================================
+// Element kinds
+// =========================================================================
+
+#[test]
+fn people_and_external_systems_are_drawn_outside_the_system_boundary() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_element(&["name: trader", "kind: person", "purpose: Places and cancels orders"]);
+    arch.annotate_element(&["name: exchange", "kind: external_system", "purpose: Venue matching the orders"]);
+    arch.annotate_element(&["name: outbox", "kind: custom:queue", "purpose: Durable order queue"]);
+    arch.annotate_container(&["name: gateway", "purpose: Order entry API"]);
+    arch.declare_dependency(&["from: trader", "to: gateway", "label: Submits orders", "protocol: HTTPS"]);
+    arch.declare_dependency(&["from: gateway", "to: exchange", "label: Routes orders", "protocol: FIX"]);
+    arch.compile();
+
+    arch.assert_element_level(&["name: trader", "level: person"]);
+    arch.assert_element_level(&["name: outbox", "level: custom:queue"]);
+    arch.assert_architecture_contains(&[
+        "contains: System_Ext(exchange, \"Exchange\", \"Venue matching the orders\")",
+    ]);
+    arch.assert_architecture_contains(&[
+        "contains: Person(trader, \"Trader\", \"Places and cancels orders\")\n\n    System_Boundary(sys",
+    ]);
+    arch.assert_architecture_contains(&["contains:         Container(outbox"]);
+    arch.assert_architecture_contains(&["contains: Rel(trader, gateway, \"Submits orders\""]);
+}
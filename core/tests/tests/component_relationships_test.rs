@@ -117,3 +117,141 @@ fn component_dependencies_carry_protocol_details() {
         "protocol: channel",
     ]);
 }
+
+// =========================================================================
+// Stable element ids
+// =========================================================================
+
+#[test]
+fn explicit_id_names_diagram_node_and_index_anchor() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&[
+        "name: engine",
+        "purpose: Trade execution engine",
+    ]);
+    arch.annotate_container(&[
+        "name: bus",
+        "purpose: Central messaging backbone",
+    ]);
+    arch.assign_id(&["element: bus", "id: message_bus"]);
+    arch.declare_dependency(&[
+        "from: engine",
+        "to: bus",
+        "label: Routes commands",
+        "protocol: crossbeam",
+    ]);
+    arch.compile();
+
+    arch.assert_architecture_contains(&["contains: Rel(engine, message_bus"]);
+    arch.assert_architecture_contains(&["contains: <a id=\"message_bus\"></a>"]);
+}
+
+// =========================================================================
+// Diagram text alternatives
+// =========================================================================
+
+#[test]
+fn diagram_dependencies_are_described_in_text() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&[
+        "name: engine",
+        "purpose: Trade execution engine",
+    ]);
+    arch.annotate_container(&[
+        "name: bus",
+        "purpose: Central messaging backbone",
+    ]);
+    arch.declare_dependency(&[
+        "from: engine",
+        "to: bus",
+        "label: Routes commands",
+        "protocol: crossbeam",
+    ]);
+    arch.compile();
+
+    arch.assert_architecture_contains(&["contains: <summary>Text description of the system diagram</summary>"]);
+    arch.assert_architecture_contains(&[
+        "contains: - **engine**: Trade execution engine — uses **bus** (Routes commands, crossbeam)",
+    ]);
+}
+
+// =========================================================================
+// Hidden relationships
+// =========================================================================
+
+#[test]
+fn hidden_dependency_is_listed_but_not_drawn() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&[
+        "name: engine",
+        "purpose: Trade execution engine",
+    ]);
+    arch.annotate_container(&[
+        "name: metrics",
+        "purpose: Counters and gauges",
+    ]);
+    arch.declare_dependency(&[
+        "from: engine",
+        "to: metrics",
+        "label: Emits counters",
+        "protocol: statsd",
+        "hidden: true",
+    ]);
+    arch.compile();
+
+    arch.assert_dependency(&[
+        "from: engine",
+        "to: metrics",
+        "label: Emits counters",
+        "protocol: statsd",
+    ]);
+    arch.assert_architecture_contains(&["contains: - engine -> metrics: \"Emits counters\" (statsd)"]);
+    arch.assert_architecture_omits(&["omits: Rel(engine, metrics"]);
+}
+
+// =========================================================================
+// Cross-cutting concerns
+// =========================================================================
+
+#[test]
+fn crosscutting_concern_users_are_tabled_instead_of_drawn() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&[
+        "name: engine",
+        "purpose: Trade execution engine",
+    ]);
+    arch.annotate_container(&[
+        "name: bus",
+        "purpose: Central messaging backbone",
+    ]);
+    arch.annotate_container(&[
+        "name: logging",
+        "purpose: Structured logging",
+    ]);
+    arch.mark_crosscutting(&["element: logging"]);
+    for from in ["bus", "engine"] {
+        arch.declare_dependency(&[
+            &format!("from: {}", from),
+            "to: logging",
+            "label: Logs",
+            "protocol: tracing",
+        ]);
+    }
+    arch.declare_dependency(&[
+        "from: engine",
+        "to: bus",
+        "label: Routes commands",
+        "protocol: crossbeam",
+    ]);
+    arch.compile();
+
+    arch.assert_architecture_contains(&["contains: ## Cross-cutting Concerns"]);
+    arch.assert_architecture_contains(&["contains: | logging | Structured logging | bus, engine |"]);
+    arch.assert_diagram_shows_dependency(&["from: engine", "to: bus"]);
+    arch.assert_architecture_omits(&["omits: Rel(engine, logging"]);
+    arch.assert_architecture_omits(&["omits: Rel(bus, logging"]);
+}
@@ -0,0 +1,34 @@
+//! Documentation Links — Runbooks and Dashboards per Element
+//!
+//! Given `@doc` links on modules, the compiled architecture links each
+//! element to its external documentation, and validation reports links
+//! that are not well-formed URLs.
+
+use archidoc_tests::ArchitectureDsl;
+
+#[test]
+fn links_are_rendered_per_element() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.link_documentation(&["element: bus", "url: https://wiki.example.com/bus", "title: Runbook"]);
+    arch.link_documentation(&["element: bus", "url: https://grafana.example.com/d/bus", "title: Dashboard"]);
+    arch.compile();
+
+    arch.assert_architecture_contains(&["contains: ## Links"]);
+    arch.assert_architecture_contains(&[
+        "contains: - **bus**: [Runbook](https://wiki.example.com/bus), [Dashboard](https://grafana.example.com/d/bus)",
+    ]);
+    arch.assert_validation_clean();
+}
+
+#[test]
+fn malformed_link_is_reported() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.link_documentation(&["element: bus", "url: wiki/bus-runbook", "title: Runbook"]);
+    arch.compile();
+
+    arch.assert_invalid_link(&["element: bus", "url: wiki/bus-runbook"]);
+}
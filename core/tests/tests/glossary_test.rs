@@ -0,0 +1,35 @@
+//! Glossary — Terms Defined Next to Their Modules
+//!
+//! Given `@term` annotations on modules, compiling the architecture
+//! produces an alphabetized glossary linking back to the defining modules.
+
+use archidoc_tests::ArchitectureDsl;
+
+#[test]
+fn terms_appear_in_glossary() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.define_term(&["element: bus", "term: EventLane", "definition: A typed event channel"]);
+    arch.compile();
+
+    arch.assert_glossary_entry(&["term: EventLane", "defined_in: bus"]);
+    arch.assert_architecture_contains(&["contains: A typed event channel"]);
+}
+
+#[test]
+fn glossary_is_alphabetized_across_modules() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.annotate_container(&["name: api", "purpose: REST gateway"]);
+    arch.define_term(&["element: bus", "term: Lane", "definition: A typed event channel"]);
+    arch.define_term(&["element: api", "term: Envelope", "definition: Request wrapper"]);
+    arch.compile();
+
+    arch.assert_glossary_entry(&["term: Envelope", "defined_in: api"]);
+    arch.assert_glossary_entry(&["term: Lane", "defined_in: bus"]);
+    arch.assert_architecture_contains(&[
+        "contains: src/api/mod.rs))\n- **Lane** — A typed event channel",
+    ]);
+}
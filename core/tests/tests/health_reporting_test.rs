@@ -131,3 +131,67 @@ fn health_report_aggregates_across_multiple_elements() {
     arch.assert_health_file_count(&["maturity: active", "count: 1"]);
     arch.assert_health_file_count(&["maturity: stable", "count: 2"]);
 }
+
+// =========================================================================
+// Weighting by lines of code
+// =========================================================================
+
+#[test]
+fn health_report_weights_files_by_lines_of_code() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&[
+        "name: bus",
+        "purpose: Central messaging backbone",
+    ]);
+    arch.catalog_file(&[
+        "element: bus",
+        "file: engine.rs",
+        "responsibility: Giant planned rewrite",
+        "maturity: planned",
+    ]);
+    arch.catalog_file(&[
+        "element: bus",
+        "file: util.rs",
+        "responsibility: Small helper",
+        "maturity: stable",
+    ]);
+    let engine: String = (0..40).map(|i| format!("pub fn f{}() {{}}\n\n", i)).collect();
+    arch.place_code_file("bus", "engine.rs", &engine);
+    arch.place_code_file("bus", "util.rs", "pub fn helper() {}\n");
+    arch.compile();
+
+    arch.assert_health_file_count(&["maturity: planned", "count: 1"]);
+    arch.assert_health_file_count(&["maturity: stable", "count: 1"]);
+    arch.assert_health_loc(&["maturity: planned", "lines: 40"]);
+    arch.assert_health_loc(&["maturity: stable", "lines: 1"]);
+}
+
+// =========================================================================
+// Unsafe-code accounting
+// =========================================================================
+
+#[test]
+fn health_report_counts_unsafe_code_per_element() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&[
+        "name: bus",
+        "purpose: Central messaging backbone",
+    ]);
+    arch.annotate_component(&[
+        "name: bus.ffi",
+        "purpose: Native driver bindings",
+    ]);
+    arch.place_code_file(
+        "bus.ffi",
+        "driver.rs",
+        "pub unsafe fn open() {}\npub fn read() { unsafe { open() } }\n",
+    );
+    arch.place_code_file("bus", "queue.rs", "pub fn push() {}\n");
+    arch.compile();
+
+    arch.assert_unsafe_count(&["element: bus.ffi", "count: 2"]);
+    arch.assert_unsafe_count(&["element: bus", "count: 0"]);
+    arch.assert_architecture_contains(&["contains: | Description | Unsafe |"]);
+}
@@ -36,6 +36,7 @@ fn merge_deduplicates_by_module_path() {
     arch.merge_ir_snapshots(&["original", "duplicate"]);
     // api appears in both but should be deduplicated
     arch.assert_merged_element_count(&["count: 1"]);
+    arch.assert_merge_warned_about(&["name: api"]);
 }
 
 #[test]
@@ -0,0 +1,36 @@
+//! Module READMEs — Per-Directory Docs for Repository Browsing
+//!
+//! Given an annotated architecture, scaffolding writes a generated README
+//! into each module directory, leaving hand-written READMEs alone.
+
+use archidoc_tests::ArchitectureDsl;
+
+#[test]
+fn readme_summarizes_module() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone", "design_pattern: Mediator"]);
+    arch.annotate_container(&["name: api", "purpose: REST gateway"]);
+    arch.declare_dependency(&["from: api", "to: bus", "label: Publishes requests", "protocol: crossbeam"]);
+    arch.catalog_file(&["element: bus", "file: lanes.rs", "responsibility: Event routing", "maturity: active"]);
+    arch.compile();
+    arch.scaffold_readmes();
+
+    arch.assert_module_readme_contains(&["element: bus", "contains: Central messaging backbone"]);
+    arch.assert_module_readme_contains(&["element: bus", "contains: | `lanes.rs` |"]);
+    arch.assert_module_readme_contains(&["element: bus", "contains: - `api`: Publishes requests"]);
+    arch.assert_module_readme_contains(&["element: api", "contains: - `bus`: Publishes requests (crossbeam)"]);
+    arch.assert_module_readme_contains(&["element: api", "contains: Generated by archidoc"]);
+}
+
+#[test]
+fn hand_written_readme_is_kept() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.compile();
+    arch.place_hand_written_readme(&["element: bus", "contains: Maintained by hand"]);
+    arch.scaffold_readmes();
+
+    arch.assert_module_readme_contains(&["element: bus", "contains: Maintained by hand"]);
+}
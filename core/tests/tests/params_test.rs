@@ -0,0 +1,91 @@
+//! DSL Parameters — Quoting, Lists, Repeats, and Flags
+//!
+//! Given DSL arguments, the parameter parser keeps colons and commas inside
+//! values, reads quoted text and `[...]` lists, collects repeated keys,
+//! treats bare names as flags, and panics naming any malformed argument.
+
+use archidoc_tests::params::Params;
+
+#[test]
+fn values_keep_their_colons_and_commas() {
+    let params = Params::parse(&["contains: Uses: bus, lanes", "steps: 3"]);
+
+    assert_eq!(params.get("contains"), "Uses: bus, lanes");
+    assert_eq!(params.get_usize("steps"), 3);
+    assert_eq!(params.get_opt("label"), None);
+}
+
+#[test]
+fn quoted_values_keep_spaces_and_escapes() {
+    let params = Params::parse(&[r#"label: "  \"Charges\" card\\ ""#, r#"list: "[not a list]""#]);
+
+    assert_eq!(params.get("label"), r#"  "Charges" card\ "#);
+    assert_eq!(params.get("list"), "[not a list]");
+}
+
+#[test]
+fn lists_and_repeated_keys_collect_every_value() {
+    let params = Params::parse(&[r#"tags: [core, "a, b", ]"#, "tags: extra", "empty: []"]);
+
+    assert_eq!(params.get_list("tags"), ["core", "a, b", "extra"]);
+    assert!(params.get_list("empty").is_empty());
+    assert!(params.get_list("absent").is_empty());
+}
+
+#[test]
+fn bare_names_are_flags() {
+    let params = Params::parse(&["hidden", "shown: false", "kept: true"]);
+
+    assert!(params.get_bool("hidden"));
+    assert!(!params.get_bool("shown"));
+    assert!(params.get_bool("kept"));
+    assert!(!params.get_bool("absent"));
+}
+
+#[test]
+#[should_panic(expected = "malformed parameter 'label: \"open': unterminated quote")]
+fn unterminated_quotes_panic() {
+    Params::parse(&["label: \"open"]);
+}
+
+#[test]
+#[should_panic(expected = "malformed parameter 'label: \"a\" b': unexpected text after the closing quote")]
+fn text_after_a_quote_panics() {
+    Params::parse(&["label: \"a\" b"]);
+}
+
+#[test]
+#[should_panic(expected = "malformed parameter 'tags: [\"a\" \"b\"]': expected `,` between list items")]
+fn list_items_need_commas() {
+    Params::parse(&["tags: [\"a\" \"b\"]"]);
+}
+
+#[test]
+#[should_panic(expected = "malformed parameter 'two words': expected `key: value` or a flag name")]
+fn flags_are_single_names() {
+    Params::parse(&["two words"]);
+}
+
+#[test]
+#[should_panic(expected = "malformed parameter ': bus': expected `key: value` or a flag name")]
+fn keys_cannot_be_empty() {
+    Params::parse(&[": bus"]);
+}
+
+#[test]
+#[should_panic(expected = "parameter 'tags' is given 2 times; read it with get_list")]
+fn single_values_reject_repeats() {
+    Params::parse(&["tags: a", "tags: b"]).get("tags");
+}
+
+#[test]
+#[should_panic(expected = "parameter 'hidden' is a flag without a value")]
+fn single_values_reject_flags() {
+    Params::parse(&["hidden"]).get("hidden");
+}
+
+#[test]
+#[should_panic(expected = "parameter 'hidden' must be a flag, `true`, or `false`")]
+fn flags_reject_other_values() {
+    Params::parse(&["hidden: yes"]).get_bool("hidden");
+}
@@ -26,6 +26,92 @@ fn should_verify_strategy_pattern_when_trait_found() {
     arch.assert_pattern_confidence(&["name: bus.calc", "confidence: verified"]);
 }
 
+#[test]
+fn should_record_evidence_when_pattern_verified() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_component(&[
+        "name: bus.calc",
+        "purpose: Pluggable indicator calculations",
+        "design_pattern: Strategy",
+    ]);
+    arch.place_code_file(
+        "bus.calc",
+        "indicators.rs",
+        "pub struct Sma;\n\npub trait IndicatorCalc {\n    fn calculate(&self, prices: &[f64]) -> f64;\n}\n",
+    );
+
+    arch.compile();
+    arch.assert_pattern_evidence(&["name: bus.calc", "evidence: "]);
+
+    arch.verify_patterns();
+    arch.assert_pattern_evidence(&["name: bus.calc", "evidence: indicators.rs::IndicatorCalc"]);
+}
+
+#[test]
+fn should_log_promotion_once_per_status_change() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_component(&[
+        "name: bus.calc",
+        "purpose: Pluggable indicator calculations",
+        "design_pattern: Strategy",
+    ]);
+    arch.annotate_component(&["name: bus.store", "purpose: Storage", "design_pattern: Strategy"]);
+    arch.place_code_file(
+        "bus.calc",
+        "indicators.rs",
+        "pub trait IndicatorCalc {\n    fn calculate(&self, prices: &[f64]) -> f64;\n}\n",
+    );
+
+    arch.compile();
+    arch.verify_patterns();
+    arch.assert_promotion_logged(&["name: bus.calc", "evidence: indicators.rs::IndicatorCalc"]);
+    arch.assert_promotion_log_size(&["entries: 1"]);
+
+    // Annotations still say planned, so the next run promotes again
+    arch.compile();
+    arch.verify_patterns();
+    arch.assert_promotion_log_size(&["entries: 1"]);
+}
+
+#[test]
+fn should_verify_each_cataloged_file_on_its_own() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_component(&["name: bus.calc", "purpose: Indicator calculations"]);
+    arch.catalog_file(&[
+        "element: bus.calc",
+        "file: indicators.rs",
+        "design_pattern: Strategy",
+        "responsibility: Pluggable indicators",
+        "maturity: active",
+    ]);
+    arch.catalog_file(&[
+        "element: bus.calc",
+        "file: cache.rs",
+        "design_pattern: Strategy",
+        "responsibility: Result cache",
+        "maturity: active",
+    ]);
+    arch.place_code_file(
+        "bus.calc",
+        "indicators.rs",
+        "pub trait IndicatorCalc {\n    fn calculate(&self, prices: &[f64]) -> f64;\n}\n",
+    );
+    arch.place_code_file("bus.calc", "cache.rs", "pub struct Cache { entries: Vec<f64> }\n");
+
+    arch.compile();
+    arch.assert_health_file_pattern_count(&["confidence: planned", "count: 2"]);
+
+    arch.verify_patterns();
+    arch.assert_catalog_entry(&["element: bus.calc", "file: indicators.rs", "design_pattern: Strategy (verified)"]);
+    arch.assert_catalog_entry(&["element: bus.calc", "file: cache.rs", "design_pattern: Strategy (planned)"]);
+    arch.assert_promotion_logged(&["name: bus.calc/indicators.rs", "evidence: indicators.rs::IndicatorCalc"]);
+    arch.assert_health_file_pattern_count(&["confidence: verified", "count: 1"]);
+    arch.assert_health_file_pattern_count(&["confidence: planned", "count: 1"]);
+}
+
 #[test]
 fn should_keep_strategy_planned_when_no_trait() {
     let mut arch = ArchitectureDsl::setup();
@@ -90,6 +176,27 @@ fn should_keep_facade_planned_when_no_reexports() {
     arch.assert_pattern_confidence(&["name: bus.api", "confidence: planned"]);
 }
 
+#[test]
+fn should_apply_heuristic_thresholds_from_project_config() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_component(&[
+        "name: bus.api",
+        "purpose: Public API",
+        "design_pattern: Facade",
+    ]);
+    arch.place_code_file("bus.api", "surface.rs", "pub mod orders;\npub mod quotes;");
+    arch.configure_heuristics(&["facade_min_pub_mods: 3"]);
+
+    arch.compile();
+    arch.verify_patterns();
+    arch.assert_pattern_confidence(&["name: bus.api", "confidence: planned"]);
+    arch.assert_fitness_fails(&[
+        "fitness: all_facade_modules_reexport_submodules",
+        "failing_module: bus.api",
+    ]);
+}
+
 // =============================================================================
 // H1: Observer pattern heuristic
 // =============================================================================
@@ -344,6 +451,155 @@ fn fitness_should_fail_when_observer_module_has_no_channels() {
     ]);
 }
 
+// =============================================================================
+// Repository pattern heuristic and fitness function
+// =============================================================================
+
+#[test]
+fn should_verify_repository_pattern_when_crud_trait_found() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_component(&[
+        "name: bus.store",
+        "purpose: Order persistence",
+        "design_pattern: Repository",
+    ]);
+    arch.place_code_file(
+        "bus.store",
+        "orders.rs",
+        "pub trait OrderRepository {\n    fn find(&self, id: u64) -> Option<Order>;\n    fn save(&mut self, order: Order);\n}",
+    );
+
+    arch.compile();
+    arch.verify_patterns();
+    arch.assert_pattern_confidence(&["name: bus.store", "confidence: verified"]);
+    arch.assert_fitness_passes(&["fitness: all_repository_modules_expose_crud_operations"]);
+}
+
+#[test]
+fn fitness_should_fail_when_repository_module_has_no_mutations() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_component(&[
+        "name: bus.store",
+        "purpose: Order persistence",
+        "design_pattern: Repository",
+    ]);
+    arch.place_code_file(
+        "bus.store",
+        "orders.rs",
+        "pub trait OrderLookup {\n    fn find(&self, id: u64) -> Option<Order>;\n}",
+    );
+
+    arch.compile();
+    arch.verify_patterns();
+    arch.assert_pattern_confidence(&["name: bus.store", "confidence: planned"]);
+    arch.assert_fitness_fails(&[
+        "fitness: all_repository_modules_expose_crud_operations",
+        "failing_module: bus.store",
+    ]);
+}
+
+// =============================================================================
+// Mediator pattern heuristic
+// =============================================================================
+
+#[test]
+fn should_verify_mediator_pattern_when_hub_routes_between_collaborators() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&[
+        "name: bus",
+        "purpose: Central message bus",
+        "design_pattern: Mediator",
+    ]);
+    arch.place_code_file(
+        "bus",
+        "hub.rs",
+        "pub struct Hub {\n    feeds: Arc<Feeds>,\n    orders: Sender<Order>,\n}\nimpl Hub {\n    pub fn dispatch(&self, msg: Msg) {}\n}",
+    );
+
+    arch.compile();
+    arch.verify_patterns();
+    arch.assert_pattern_confidence(&["name: bus", "confidence: verified"]);
+}
+
+#[test]
+fn should_keep_mediator_planned_when_type_only_holds_data() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&[
+        "name: bus",
+        "purpose: Central message bus",
+        "design_pattern: Mediator",
+    ]);
+    arch.place_code_file(
+        "bus",
+        "hub.rs",
+        "pub struct Hub {\n    count: usize,\n    name: String,\n}\nimpl Hub {\n    pub fn dispatch(&self) {}\n}",
+    );
+
+    arch.compile();
+    arch.verify_patterns();
+    arch.assert_pattern_confidence(&["name: bus", "confidence: planned"]);
+}
+
+// =============================================================================
+// State and Template Method pattern heuristics and fitness functions
+// =============================================================================
+
+#[test]
+fn should_verify_state_pattern_when_enum_transitions_found() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_component(&[
+        "name: bus.session",
+        "purpose: Connection lifecycle",
+        "design_pattern: State",
+    ]);
+    arch.place_code_file(
+        "bus.session",
+        "lifecycle.rs",
+        "pub enum Phase { Idle, Open }\npub fn step(p: Phase) -> Phase {\n    match p { Phase::Idle => Phase::Open, Phase::Open => Phase::Idle }\n}",
+    );
+
+    arch.compile();
+    arch.assert_design_pattern(&["name: bus.session", "design_pattern: State"]);
+    arch.verify_patterns();
+    arch.assert_pattern_confidence(&["name: bus.session", "confidence: verified"]);
+    arch.assert_fitness_passes(&["fitness: all_state_modules_define_transitions"]);
+}
+
+#[test]
+fn fitness_should_fail_when_template_method_module_has_no_hook_calls() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_component(&[
+        "name: bus.report",
+        "purpose: Report rendering",
+        "design_pattern: Template Method",
+    ]);
+    arch.place_code_file(
+        "bus.report",
+        "render.rs",
+        "pub trait Report {\n    fn title(&self) -> String;\n    fn footer(&self) -> String { String::new() }\n}",
+    );
+
+    arch.compile();
+    arch.assert_fitness_fails(&[
+        "fitness: all_template_method_modules_define_hook_calls",
+        "failing_module: bus.report",
+    ]);
+
+    arch.place_code_file(
+        "bus.report",
+        "render.rs",
+        "pub trait Report {\n    fn title(&self) -> String;\n    fn render(&self) -> String { format!(\"# {}\", self.title()) }\n}",
+    );
+    arch.compile();
+    arch.assert_fitness_passes(&["fitness: all_template_method_modules_define_hook_calls"]);
+}
+
 // =============================================================================
 // Edge cases — multi-file modules, empty directories, boundary conditions
 // =============================================================================
@@ -440,3 +696,182 @@ fn should_not_verify_facade_with_single_pub_mod() {
     // Only 1 pub mod — not enough for Facade (need >= 2 or any pub use)
     arch.assert_pattern_confidence(&["name: bus.api", "confidence: planned"]);
 }
+
+// =============================================================================
+// H7: Fitness function — all_component_public_items_are_used
+// =============================================================================
+
+#[test]
+fn fitness_should_pass_when_component_public_items_are_used_elsewhere() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_component(&[
+        "name: bus.calc",
+        "purpose: Calculations",
+        "design_pattern: Facade",
+    ]);
+    arch.place_code_file("bus.calc", "engine.rs", "pub struct Engine;\nfn helper() {}\n");
+
+    arch.annotate_component(&[
+        "name: bus.api",
+        "purpose: Request handling",
+    ]);
+    arch.place_code_file(
+        "bus.api",
+        "handler.rs",
+        "use crate::bus::calc::engine::Engine;\nfn handle(_e: &Engine) {}\n",
+    );
+
+    arch.compile();
+    arch.assert_fitness_passes(&["fitness: all_component_public_items_are_used"]);
+}
+
+#[test]
+fn fitness_should_flag_public_items_nothing_outside_uses() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_component(&[
+        "name: bus.calc",
+        "purpose: Calculations",
+        "design_pattern: Facade",
+    ]);
+    arch.place_code_file(
+        "bus.calc",
+        "engine.rs",
+        "pub struct Engine;\npub fn internal_tuning() {}\n",
+    );
+
+    arch.annotate_component(&[
+        "name: bus.api",
+        "purpose: Request handling",
+    ]);
+    arch.place_code_file(
+        "bus.api",
+        "handler.rs",
+        "use crate::bus::calc::engine::Engine;\nfn handle(_e: &Engine) {}\n",
+    );
+
+    arch.compile();
+    arch.assert_fitness_fails(&[
+        "fitness: all_component_public_items_are_used",
+        "failing_module: bus.calc",
+    ]);
+}
+
+// =============================================================================
+// Fitness function — no_component_depends_on_another_containers_internals
+// =============================================================================
+
+fn two_containers(arch: &mut ArchitectureDsl) {
+    arch.annotate_container(&["name: api", "purpose: Request handling"]);
+    arch.annotate_component(&["name: api.auth", "purpose: Authentication"]);
+    arch.annotate_container(&["name: bus", "purpose: Messaging"]);
+    arch.annotate_component(&["name: bus.calc", "purpose: Calculations"]);
+}
+
+#[test]
+fn fitness_should_pass_when_depending_on_another_container() {
+    let mut arch = ArchitectureDsl::setup();
+    two_containers(&mut arch);
+    arch.declare_dependency(&["from: api.auth", "to: bus", "label: Publishes logins", "protocol: channel"]);
+    arch.place_code_file("api.auth", "login.rs", "use crate::bus::Publisher;\nuse crate::api::Session;\n");
+
+    arch.compile();
+    arch.assert_fitness_passes(&["fitness: no_component_depends_on_another_containers_internals"]);
+}
+
+#[test]
+fn fitness_should_flag_relationship_to_another_containers_component() {
+    let mut arch = ArchitectureDsl::setup();
+    two_containers(&mut arch);
+    arch.declare_dependency(&["from: api.auth", "to: bus.calc", "label: Scores risk", "protocol: call"]);
+
+    arch.compile();
+    arch.assert_fitness_fails(&[
+        "fitness: no_component_depends_on_another_containers_internals",
+        "failing_module: api.auth",
+    ]);
+}
+
+#[test]
+fn fitness_should_flag_import_of_another_containers_component() {
+    let mut arch = ArchitectureDsl::setup();
+    two_containers(&mut arch);
+    arch.place_code_file("api.auth", "login.rs", "use crate::bus::calc::RiskScore;\n");
+
+    arch.compile();
+    arch.assert_fitness_fails(&[
+        "fitness: no_component_depends_on_another_containers_internals",
+        "failing_module: api.auth",
+    ]);
+}
+
+#[test]
+fn fitness_should_accept_allowlisted_cross_container_dependencies() {
+    let mut arch = ArchitectureDsl::setup();
+    two_containers(&mut arch);
+    arch.declare_dependency(&["from: api.auth", "to: bus.calc", "label: Scores risk", "protocol: call"]);
+    arch.allow_cross_container(&["api.auth -> bus.calc"]);
+
+    arch.compile();
+    arch.assert_fitness_passes(&["fitness: no_component_depends_on_another_containers_internals"]);
+}
+
+// =============================================================================
+// Fitness function — every_container_is_connected_or_standalone
+// =============================================================================
+
+#[test]
+fn fitness_should_pass_when_containers_are_connected_or_standalone() {
+    let mut arch = ArchitectureDsl::setup();
+    two_containers(&mut arch);
+    arch.annotate_container(&["name: tools", "purpose: Offline maintenance scripts"]);
+    arch.mark_standalone(&["element: tools"]);
+    // A component's relationship connects both containers
+    arch.declare_dependency(&["from: api.auth", "to: bus", "label: Publishes logins", "protocol: channel"]);
+
+    arch.compile();
+    arch.assert_fitness_passes(&["fitness: every_container_is_connected_or_standalone"]);
+}
+
+#[test]
+fn fitness_should_flag_unconnected_container() {
+    let mut arch = ArchitectureDsl::setup();
+    two_containers(&mut arch);
+    // Internal to api: does not connect it to anything else
+    arch.declare_dependency(&["from: api.auth", "to: api", "label: Reads sessions", "protocol: call"]);
+    arch.declare_dependency(&["from: bus", "to: api", "label: Delivers events", "protocol: channel"]);
+    arch.annotate_container(&["name: reports", "purpose: Reporting"]);
+
+    arch.compile();
+    arch.assert_fitness_fails(&[
+        "fitness: every_container_is_connected_or_standalone",
+        "failing_module: reports",
+    ]);
+}
+
+// =============================================================================
+// Fitness summary in ARCHITECTURE.md
+// =============================================================================
+
+#[test]
+fn fitness_summary_should_list_results_when_enabled() {
+    let mut arch = ArchitectureDsl::setup();
+    two_containers(&mut arch);
+    arch.declare_dependency(&["from: api.auth", "to: bus.calc", "label: Scores risk", "protocol: call"]);
+    arch.enable_fitness_summary();
+
+    arch.compile();
+    arch.assert_architecture_contains(&["contains: 8 of 9 rules pass."]);
+    arch.assert_architecture_contains(&["contains: | `no_component_depends_on_another_containers_internals` | FAIL | 4 | 1 |"]);
+    arch.assert_architecture_contains(&["contains: | `every_container_is_connected_or_standalone` | pass |"]);
+}
+
+#[test]
+fn fitness_summary_should_be_omitted_by_default() {
+    let mut arch = ArchitectureDsl::setup();
+    two_containers(&mut arch);
+
+    arch.compile();
+    arch.assert_architecture_omits(&["omits: ## Architectural Fitness"]);
+}
@@ -0,0 +1,33 @@
+//! Patterns in Use — Cross-Reference of Pattern Claims
+//!
+//! Given modules and cataloged files that claim design patterns, the
+//! compiled architecture lists each pattern with its claimants and their
+//! verification status.
+
+use archidoc_tests::ArchitectureDsl;
+
+#[test]
+fn module_and_file_claims_are_listed() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone", "design_pattern: Mediator"]);
+    arch.catalog_file(&[
+        "element: bus", "file: lanes.rs", "design_pattern: Observer",
+        "responsibility: Event routing", "maturity: active",
+    ]);
+    arch.compile();
+
+    arch.assert_pattern_in_use(&["pattern: Mediator", "claimed_by: bus", "status: planned"]);
+    arch.assert_pattern_in_use(&["pattern: Observer", "claimed_by: bus/lanes.rs", "status: planned"]);
+}
+
+#[test]
+fn verified_module_pattern_is_listed_as_verified() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone", "design_pattern: Mediator"]);
+    arch.set_pattern_confidence(&["name: bus", "confidence: verified"]);
+    arch.compile();
+
+    arch.assert_pattern_in_use(&["pattern: Mediator", "claimed_by: bus", "status: verified"]);
+}
@@ -0,0 +1,70 @@
+//! Review Tracking — Sign-off and Stale Review Detection
+//!
+//! Given modules signed off with `@reviewed-by`, the health report counts
+//! reviewed, unreviewed, stale, and unverifiable modules, and lists modules
+//! whose content changed since their last review.
+
+use archidoc_tests::ArchitectureDsl;
+
+// =========================================================================
+// Review counts
+// =========================================================================
+
+#[test]
+fn unannotated_modules_are_unreviewed() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: api", "purpose: REST gateway"]);
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.compile();
+
+    arch.assert_health_review_count(&["status: unreviewed", "count: 2"]);
+    arch.assert_health_review_count(&["status: reviewed", "count: 0"]);
+}
+
+#[test]
+fn signed_off_module_is_reviewed() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: api", "purpose: REST gateway"]);
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.compile();
+    arch.review_element(&["name: bus", "reviewer: alice", "date: 2024-06-01"]);
+    arch.compile();
+
+    arch.assert_health_review_count(&["status: reviewed", "count: 1"]);
+    arch.assert_health_review_count(&["status: unreviewed", "count: 1"]);
+    arch.assert_health_review_count(&["status: stale", "count: 0"]);
+}
+
+#[test]
+fn sign_off_without_a_hash_is_unverifiable() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.compile();
+    arch.review_element(&["name: bus", "reviewer: alice", "date: 2024-06-01", "without_hash"]);
+    arch.compile();
+
+    arch.assert_health_review_count(&["status: unverifiable", "count: 1"]);
+    arch.assert_health_review_count(&["status: reviewed", "count: 0"]);
+}
+
+// =========================================================================
+// Stale reviews
+// =========================================================================
+
+#[test]
+fn content_change_after_review_is_stale() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.compile();
+    arch.review_element(&["name: bus", "reviewer: alice", "date: 2024-06-01"]);
+
+    arch.annotate_container(&["name: bus", "purpose: Rewritten without review"]);
+    arch.compile();
+
+    arch.assert_health_review_count(&["status: stale", "count: 1"]);
+    arch.assert_stale_review(&["name: bus"]);
+}
@@ -0,0 +1,46 @@
+//! Risk Register — Risks Declared Next to Their Modules
+//!
+//! Given `@risk` annotations on modules, compiling the architecture
+//! consolidates them into a risk register in ARCHITECTURE.md.
+
+use archidoc_tests::ArchitectureDsl;
+
+#[test]
+fn risks_appear_in_register() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.annotate_container(&["name: api", "purpose: REST gateway"]);
+    arch.record_risk(&["element: bus", "risk: Single point of failure", "severity: high"]);
+    arch.record_risk(&["element: api", "risk: Rate limits untested", "severity: low"]);
+    arch.compile();
+
+    arch.assert_risk_count(&["count: 2"]);
+    arch.assert_risk_registered(&["element: bus", "risk: Single point of failure", "severity: high"]);
+    arch.assert_architecture_contains(&["contains: ## Risk Register"]);
+    arch.assert_architecture_contains(&["contains: | high | bus | Single point of failure |"]);
+}
+
+#[test]
+fn severity_defaults_to_medium() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.record_risk(&["element: bus", "risk: Retry queue is unbounded"]);
+    arch.compile();
+
+    arch.assert_risk_registered(&["element: bus", "risk: Retry queue is unbounded", "severity: medium"]);
+}
+
+#[test]
+fn risk_markers_stay_out_of_descriptions() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.annotate_container(&["name: bus", "purpose: Central messaging backbone"]);
+    arch.record_risk(&["element: bus", "risk: Single point of failure", "severity: critical"]);
+    arch.compile();
+
+    arch.assert_index_lists(&["name: bus"]);
+    arch.assert_architecture_contains(&["contains: Central messaging backbone"]);
+    arch.assert_risk_count(&["count: 1"]);
+}
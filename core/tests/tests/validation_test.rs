@@ -108,6 +108,10 @@ fn orphan_detected_when_uncataloged_file_exists() {
     // lanes.rs is in catalog + on disk = fine
     // analytics.rs is on disk but NOT in catalog = orphan
     arch.assert_orphan_detected(&["element: bus", "file: analytics.rs"]);
+
+    // Orphans are warnings by default
+    arch.assert_validation_passes();
+    arch.assert_validation_fails_with_warnings_as_errors();
 }
 
 // =========================================================================
@@ -144,4 +148,7 @@ fn detects_both_ghosts_and_orphans_simultaneously() {
 
     arch.assert_ghost_detected(&["element: bus", "file: deleted.rs"]);
     arch.assert_orphan_detected(&["element: bus", "file: extra.rs"]);
+
+    // Ghosts are errors by default
+    arch.assert_validation_fails();
 }
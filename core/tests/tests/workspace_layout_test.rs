@@ -0,0 +1,56 @@
+//! Workspace Layouts — Member Crates Compiled on Their Own
+//!
+//! Given a Cargo workspace whose member crates, some nested inside others,
+//! each carry their own annotated `src/` tree, compiling a member documents
+//! that crate alone: its root and modules, and none of its siblings.
+
+use archidoc_tests::ArchitectureDsl;
+
+#[test]
+fn member_crate_compiles_its_root_and_modules() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.set_up_workspace(&["members: [api, store]"]);
+    arch.annotate_crate(&["crate: api", "purpose: HTTP entry point"]);
+    arch.annotate_component(&["name: handlers", "crate: api", "purpose: Request handlers"]);
+    arch.annotate_crate(&["crate: store", "purpose: Durable order storage"]);
+    arch.compile_crate(&["crate: api"]);
+
+    arch.assert_element_level(&["name: _lib", "level: container"]);
+    arch.assert_element_level(&["name: handlers", "level: component"]);
+    arch.assert_total_elements(&["count: 2"]);
+    arch.assert_architecture_contains(&["contains: HTTP entry point"]);
+    arch.assert_architecture_omits(&["omits: Durable order storage"]);
+}
+
+#[test]
+fn each_member_documents_only_its_own_crate() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.set_up_workspace(&["members: [api, store]"]);
+    arch.annotate_crate(&["crate: api", "purpose: HTTP entry point"]);
+    arch.annotate_crate(&["crate: store", "purpose: Durable order storage"]);
+    arch.annotate_component(&["name: tables", "crate: store", "purpose: Table layouts"]);
+    arch.compile_crate(&["crate: store"]);
+
+    arch.assert_element_level(&["name: tables", "level: component"]);
+    arch.assert_architecture_contains(&["contains: Durable order storage"]);
+    arch.assert_architecture_omits(&["omits: HTTP entry point"]);
+}
+
+#[test]
+fn nested_member_crate_stays_out_of_its_parent() {
+    let mut arch = ArchitectureDsl::setup();
+
+    arch.set_up_workspace(&["members: [api, api/macros]"]);
+    arch.annotate_crate(&["crate: api", "purpose: HTTP entry point"]);
+    arch.annotate_crate(&["crate: api/macros", "kind: component", "purpose: Derive macros"]);
+
+    arch.compile_crate(&["crate: api"]);
+    arch.assert_total_elements(&["count: 1"]);
+    arch.assert_architecture_omits(&["omits: Derive macros"]);
+
+    arch.compile_crate(&["crate: api/macros"]);
+    arch.assert_element_level(&["name: _lib", "level: component"]);
+    arch.assert_architecture_contains(&["contains: Derive macros"]);
+}